@@ -0,0 +1,185 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+/*
+ * C API of the Slint interpreter.
+ *
+ * This API is meant to build bindings for other programming languages: it loads .slint files
+ * at run-time, creates instances of the components, accesses their properties and callbacks,
+ * and runs the event loop. All the functions must be called from the thread that runs the
+ * event loop.
+ *
+ * Ownership of the values:
+ *  - Values passed to Slint, as property values or callback arguments, are borrowed: Slint
+ *    copies them and the caller keeps the ownership.
+ *  - Values returned by Slint, and the values created with the slint_value_new_* functions,
+ *    are owned by the caller and must be freed with slint_value_free().
+ *  - The return value of a callback handler is owned by Slint: it must either be a number, a
+ *    bool, a color or void, or have been created with the slint_value_new_* functions.
+ *  - Never call slint_value_free() on values that were not allocated by Slint.
+ *
+ * Errors: the functions report failures, including the internal errors of Slint, with their
+ * return value (false, null or a void value). The pointers to the instances, definitions and
+ * values may be null, in which case the functions fail.
+ */
+
+#ifndef SLINT_C_H
+#define SLINT_C_H
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* The type of a SlintValue, which tells which field of its data is valid */
+typedef enum SlintValueType {
+    SLINT_VALUE_VOID = 0,
+    SLINT_VALUE_NUMBER = 1, /* data.number: int, float, length, duration (in ms), angle (in deg) */
+    SLINT_VALUE_STRING = 2, /* data.string: also used for the values of enumerations */
+    SLINT_VALUE_BOOL = 3, /* data.boolean */
+    SLINT_VALUE_COLOR = 4, /* data.color: the color as 0xAARRGGBB, also used for brushes */
+    SLINT_VALUE_ARRAY = 5, /* data.array: the rows of a model */
+    SLINT_VALUE_STRUCT = 6, /* data.structure */
+    SLINT_VALUE_OTHER = 7, /* a value that cannot be represented, such as an image */
+} SlintValueType;
+
+struct SlintValue;
+struct SlintField;
+
+/* A UTF-8 string of len bytes. Strings allocated by Slint are also nul terminated. */
+typedef struct SlintString {
+    const char *data;
+    size_t len;
+} SlintString;
+
+typedef struct SlintArray {
+    const struct SlintValue *values;
+    size_t len;
+} SlintArray;
+
+typedef struct SlintStruct {
+    const struct SlintField *fields;
+    size_t len;
+} SlintStruct;
+
+/* A value of a property or of a callback argument */
+typedef struct SlintValue {
+    uint32_t ty; /* a SlintValueType, values of unknown types are ignored */
+    union {
+        double number;
+        uint8_t boolean; /* 0 is false, any other value is true */
+        SlintString string;
+        uint32_t color;
+        SlintArray array;
+        SlintStruct structure;
+    } data;
+} SlintValue;
+
+/* A field of a struct, with its nul terminated name */
+typedef struct SlintField {
+    const char *name;
+    SlintValue value;
+} SlintField;
+
+/* Opaque handle to a compiled component */
+typedef struct SlintComponentDefinition SlintComponentDefinition;
+/* Opaque handle to an instance of a component */
+typedef struct SlintComponentInstance SlintComponentInstance;
+
+/* Frees a value allocated by Slint, and sets it to void. Does nothing if value is null. */
+void slint_value_free(SlintValue *value);
+/* Returns a string value holding a copy of the len bytes of UTF-8 at data. */
+SlintValue slint_value_new_string(const char *data, size_t len);
+/* Returns an array value holding a deep copy of the given values. */
+SlintValue slint_value_new_array(const SlintValue *values, size_t len);
+/* Returns a struct value holding a deep copy of the given fields. */
+SlintValue slint_value_new_struct(const SlintField *fields, size_t len);
+
+/* Frees a string allocated by Slint, such as the diagnostics of slint_compile_file(). */
+void slint_string_free(char *string);
+
+/*
+ * Compiles the .slint file at path and returns the definition of its last component, or null if
+ * there were errors. The include paths are used to look up the imported files, and style, which
+ * may be null, selects the style of the widgets. When diagnostics is not null, it is set to the
+ * text of the errors and warnings, to be freed with slint_string_free(), or to null if there are
+ * none.
+ */
+SlintComponentDefinition *slint_compile_file(const char *path, const char *const *include_paths,
+                                             size_t include_path_count, const char *style,
+                                             char **diagnostics);
+/* Frees a component definition. The instances created from it stay valid. */
+void slint_component_definition_free(SlintComponentDefinition *definition);
+/*
+ * Creates a new instance of the component, to be freed with slint_component_instance_free().
+ * Returns null if definition is null or if the instance can't be created, for example because no
+ * backend is available.
+ */
+SlintComponentInstance *slint_component_definition_create(const SlintComponentDefinition *definition);
+/* Frees a component instance. */
+void slint_component_instance_free(SlintComponentInstance *instance);
+
+/*
+ * Stores the value of the property name in value, which must then be freed with
+ * slint_value_free(). Returns false if there is no such property.
+ */
+bool slint_component_instance_get_property(const SlintComponentInstance *instance,
+                                           const char *name, SlintValue *value);
+/*
+ * Sets the property name to a copy of value. Returns false if there is no such property or if
+ * the value has the wrong type.
+ */
+bool slint_component_instance_set_property(const SlintComponentInstance *instance,
+                                           const char *name, const SlintValue *value);
+
+/*
+ * The handler of a callback. It receives the arguments, which are freed after the call, and sets
+ * the return value, which Slint takes ownership of. return_value is void when the handler is
+ * called.
+ */
+typedef void (*SlintCallback)(void *user_data, const SlintValue *args, size_t arg_count,
+                              SlintValue *return_value);
+
+/*
+ * Sets the handler of the callback name. drop_user_data, which may be null, is called with
+ * user_data when the handler is replaced or the instance is freed. Returns false if there is no
+ * such callback.
+ */
+bool slint_component_instance_set_callback(const SlintComponentInstance *instance,
+                                           const char *name, SlintCallback callback,
+                                           void *user_data, void (*drop_user_data)(void *));
+/*
+ * Invokes the callback name with the given arguments and stores its return value in return_value,
+ * which must be freed with slint_value_free(). return_value may be null. Returns false if there is
+ * no such callback or if the arguments don't match its declaration.
+ */
+bool slint_component_instance_invoke(const SlintComponentInstance *instance, const char *name,
+                                     const SlintValue *args, size_t arg_count,
+                                     SlintValue *return_value);
+
+/* Shows the window of the component. Returns false if instance is null or on failure. */
+bool slint_component_instance_show(const SlintComponentInstance *instance);
+/* Hides the window of the component. Returns false if instance is null or on failure. */
+bool slint_component_instance_hide(const SlintComponentInstance *instance);
+/*
+ * Shows the window of the component, runs the event loop, and hides the window. Returns false if
+ * instance is null or if the event loop failed.
+ */
+bool slint_component_instance_run(const SlintComponentInstance *instance);
+
+/*
+ * Runs the event loop until the last window is closed or slint_quit_event_loop() is called.
+ * Returns false if the event loop failed.
+ */
+bool slint_run_event_loop(void);
+/* Stops the event loop. Returns false if there is no event loop. */
+bool slint_quit_event_loop(void);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* SLINT_C_H */
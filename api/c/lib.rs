@@ -0,0 +1,751 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+/*!
+# Slint C API
+
+This crate exposes the Slint interpreter with a C ABI, to build bindings for other
+programming languages. The functions and types are declared in `include/slint_c.h`, which
+documents them and the ownership rules of the values.
+
+Unlike the FFI used by the C++ API, which may change with every release, this API only uses
+plain C types and is meant to stay stable.
+*/
+
+#![allow(clippy::missing_safety_doc)] // The safety requirements are documented in the header
+
+use i_slint_core::model::{Model, ModelRc, SharedVectorModel};
+use slint_interpreter::{ComponentDefinition, ComponentHandle, ComponentInstance, Value};
+use std::ffi::{c_void, CStr, CString};
+use std::os::raw::c_char;
+
+/// The type of a [`SlintValue`], which tells which field of its data is valid
+#[repr(u32)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SlintValueType {
+    Void = 0,
+    Number = 1,
+    String = 2,
+    Bool = 3,
+    Color = 4,
+    Array = 5,
+    Struct = 6,
+    /// A value that cannot be represented with the C API, such as an image
+    Other = 7,
+}
+
+/// A UTF-8 string. Strings allocated by Slint are also terminated by a nul byte after `len` bytes.
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct SlintString {
+    pub data: *const c_char,
+    pub len: usize,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct SlintArray {
+    pub values: *const SlintValue,
+    pub len: usize,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct SlintStruct {
+    pub fields: *const SlintField,
+    pub len: usize,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub union SlintValueData {
+    pub number: f64,
+    /// 0 is false, any other value is true. Not a `bool`, since the C code may store any byte.
+    pub boolean: u8,
+    pub string: SlintString,
+    /// The color as 0xAARRGGBB
+    pub color: u32,
+    pub array: SlintArray,
+    pub structure: SlintStruct,
+}
+
+/// A value of a property or of a callback argument, as a tagged union
+#[repr(C)]
+pub struct SlintValue {
+    /// A [`SlintValueType`]. It is not stored as the enum since the C code may store any value.
+    pub ty: u32,
+    pub data: SlintValueData,
+}
+
+/// A field of a struct: its nul terminated name and its value
+#[repr(C)]
+pub struct SlintField {
+    pub name: *const c_char,
+    pub value: SlintValue,
+}
+
+impl SlintValue {
+    const VOID: Self = Self::new(SlintValueType::Void, SlintValueData { number: 0. });
+
+    const fn new(ty: SlintValueType, data: SlintValueData) -> Self {
+        Self { ty: ty as u32, data }
+    }
+
+    /// The type of the value, or None if `ty` is not a valid type
+    fn value_type(&self) -> Option<SlintValueType> {
+        use SlintValueType::*;
+        [Void, Number, String, Bool, Color, Array, Struct, Other].get(self.ty as usize).copied()
+    }
+}
+
+/// Converts a value of the interpreter to a value allocated by Slint
+fn to_slint_value(value: Value) -> SlintValue {
+    match value {
+        Value::Void => SlintValue::VOID,
+        Value::Number(number) => SlintValue::new(SlintValueType::Number, SlintValueData { number }),
+        Value::Bool(boolean) => {
+            SlintValue::new(SlintValueType::Bool, SlintValueData { boolean: boolean as u8 })
+        }
+        Value::String(s) => new_string(s.as_bytes()),
+        Value::EnumerationValue(_, s) => new_string(s.as_bytes()),
+        Value::Brush(brush) => SlintValue::new(
+            SlintValueType::Color,
+            SlintValueData { color: brush.color().as_argb_encoded() },
+        ),
+        Value::Model(model) => {
+            let values = model.iter().map(to_slint_value).collect::<Box<[_]>>();
+            let len = values.len();
+            SlintValue::new(
+                SlintValueType::Array,
+                SlintValueData {
+                    array: SlintArray { values: Box::into_raw(values) as *const _, len },
+                },
+            )
+        }
+        Value::Struct(s) => {
+            let fields = s
+                .iter()
+                .map(|(name, value)| SlintField {
+                    name: CString::new(name).unwrap_or_default().into_raw(),
+                    value: to_slint_value(value.clone()),
+                })
+                .collect::<Box<[_]>>();
+            let len = fields.len();
+            SlintValue::new(
+                SlintValueType::Struct,
+                SlintValueData {
+                    structure: SlintStruct { fields: Box::into_raw(fields) as *const _, len },
+                },
+            )
+        }
+        _ => SlintValue::new(SlintValueType::Other, SlintValueData { number: 0. }),
+    }
+}
+
+fn new_string(bytes: &[u8]) -> SlintValue {
+    let len = bytes.len();
+    let data = bytes.iter().copied().chain(std::iter::once(0)).collect::<Box<[u8]>>();
+    SlintValue::new(
+        SlintValueType::String,
+        SlintValueData { string: SlintString { data: Box::into_raw(data) as *const c_char, len } },
+    )
+}
+
+unsafe fn slice_from_raw<'a, T>(ptr: *const T, len: usize) -> &'a [T] {
+    if len == 0 {
+        &[]
+    } else {
+        std::slice::from_raw_parts(ptr, len)
+    }
+}
+
+/// Converts a value to a value of the interpreter. Returns None for the values of type `Other`
+/// or of an invalid type, and the strings that are not valid UTF-8.
+unsafe fn from_slint_value(value: &SlintValue) -> Option<Value> {
+    Some(match value.value_type()? {
+        SlintValueType::Void => Value::Void,
+        SlintValueType::Number => Value::Number(value.data.number),
+        SlintValueType::Bool => Value::Bool(value.data.boolean != 0),
+        SlintValueType::String => {
+            let string = value.data.string;
+            let bytes = slice_from_raw(string.data as *const u8, string.len);
+            Value::String(std::str::from_utf8(bytes).ok()?.into())
+        }
+        SlintValueType::Color => i_slint_core::Color::from_argb_encoded(value.data.color).into(),
+        SlintValueType::Array => {
+            let array = value.data.array;
+            let values = slice_from_raw(array.values, array.len)
+                .iter()
+                .map(|value| from_slint_value(value))
+                .collect::<Option<i_slint_core::SharedVector<_>>>()?;
+            Value::Model(ModelRc::new(SharedVectorModel::from(values)))
+        }
+        SlintValueType::Struct => {
+            let structure = value.data.structure;
+            Value::Struct(
+                slice_from_raw(structure.fields, structure.len)
+                    .iter()
+                    .map(|field| {
+                        let name = CStr::from_ptr(field.name).to_str().ok()?.to_string();
+                        Some((name, from_slint_value(&field.value)?))
+                    })
+                    .collect::<Option<_>>()?,
+            )
+        }
+        SlintValueType::Other => return None,
+    })
+}
+
+unsafe fn str_from_ptr<'a>(s: *const c_char) -> Option<&'a str> {
+    if s.is_null() {
+        None
+    } else {
+        CStr::from_ptr(s).to_str().ok()
+    }
+}
+
+/// Runs `f`, and returns `error` if it panics: a panic must not unwind into the C code.
+fn catch_panic<R>(error: R, f: impl FnOnce() -> R) -> R {
+    std::panic::catch_unwind(std::panic::AssertUnwindSafe(f)).unwrap_or(error)
+}
+
+/// Frees a value allocated by Slint, and sets it to void.
+#[no_mangle]
+pub unsafe extern "C" fn slint_value_free(value: *mut SlintValue) {
+    catch_panic((), || free_value(value))
+}
+
+unsafe fn free_value(value: *mut SlintValue) {
+    let value = match value.as_mut() {
+        Some(value) => value,
+        None => return,
+    };
+    match value.value_type() {
+        Some(SlintValueType::String) => {
+            let string = value.data.string;
+            drop(Box::from_raw(std::ptr::slice_from_raw_parts_mut(
+                string.data as *mut u8,
+                string.len + 1,
+            )));
+        }
+        Some(SlintValueType::Array) => {
+            let array = value.data.array;
+            let mut values = Box::from_raw(std::ptr::slice_from_raw_parts_mut(
+                array.values as *mut _,
+                array.len,
+            ));
+            values.iter_mut().for_each(|value| free_value(value));
+        }
+        Some(SlintValueType::Struct) => {
+            let structure = value.data.structure;
+            let mut fields = Box::from_raw(std::ptr::slice_from_raw_parts_mut(
+                structure.fields as *mut SlintField,
+                structure.len,
+            ));
+            for field in fields.iter_mut() {
+                drop(CString::from_raw(field.name as *mut c_char));
+                free_value(&mut field.value);
+            }
+        }
+        _ => {}
+    }
+    *value = SlintValue::VOID;
+}
+
+/// Returns a string value holding a copy of the `len` bytes of UTF-8 at `data`.
+#[no_mangle]
+pub unsafe extern "C" fn slint_value_new_string(data: *const c_char, len: usize) -> SlintValue {
+    catch_panic(SlintValue::VOID, || new_string(slice_from_raw(data as *const u8, len)))
+}
+
+/// Returns an array value holding a deep copy of the given values.
+#[no_mangle]
+pub unsafe extern "C" fn slint_value_new_array(
+    values: *const SlintValue,
+    len: usize,
+) -> SlintValue {
+    catch_panic(SlintValue::VOID, || {
+        let value = SlintValue::new(
+            SlintValueType::Array,
+            SlintValueData { array: SlintArray { values, len } },
+        );
+        from_slint_value(&value).map_or(SlintValue::VOID, to_slint_value)
+    })
+}
+
+/// Returns a struct value holding a deep copy of the given fields.
+#[no_mangle]
+pub unsafe extern "C" fn slint_value_new_struct(
+    fields: *const SlintField,
+    len: usize,
+) -> SlintValue {
+    catch_panic(SlintValue::VOID, || {
+        let value = SlintValue::new(
+            SlintValueType::Struct,
+            SlintValueData { structure: SlintStruct { fields, len } },
+        );
+        from_slint_value(&value).map_or(SlintValue::VOID, to_slint_value)
+    })
+}
+
+/// Frees a string allocated by Slint, such as the diagnostics of `slint_compile_file`.
+#[no_mangle]
+pub unsafe extern "C" fn slint_string_free(string: *mut c_char) {
+    if !string.is_null() {
+        catch_panic((), || drop(CString::from_raw(string)))
+    }
+}
+
+/// Opaque handle to a compiled component
+pub struct SlintComponentDefinition(ComponentDefinition);
+
+/// Opaque handle to an instance of a component
+pub struct SlintComponentInstance(ComponentInstance);
+
+/// Compiles the .slint file at `path`, and returns the definition of its last component, or
+/// null if there were errors.
+#[no_mangle]
+pub unsafe extern "C" fn slint_compile_file(
+    path: *const c_char,
+    include_paths: *const *const c_char,
+    include_path_count: usize,
+    style: *const c_char,
+    diagnostics: *mut *mut c_char,
+) -> *mut SlintComponentDefinition {
+    if let Some(diagnostics) = diagnostics.as_mut() {
+        *diagnostics = std::ptr::null_mut();
+    }
+    let path = match str_from_ptr(path) {
+        Some(path) => path,
+        None => return std::ptr::null_mut(),
+    };
+    catch_panic(std::ptr::null_mut(), || {
+        let mut compiler = slint_interpreter::ComponentCompiler::default();
+        compiler.set_include_paths(
+            slice_from_raw(include_paths, include_path_count)
+                .iter()
+                .filter_map(|p| str_from_ptr(*p))
+                .map(Into::into)
+                .collect(),
+        );
+        if let Some(style) = str_from_ptr(style) {
+            compiler.set_style(style.into());
+        }
+        let definition = spin_on::spin_on(compiler.build_from_path(path));
+        if let Some(diagnostics) = diagnostics.as_mut() {
+            if !compiler.diagnostics().is_empty() {
+                let text =
+                    compiler.diagnostics().iter().map(|d| format!("{}\n", d)).collect::<String>();
+                *diagnostics = CString::new(text).unwrap_or_default().into_raw();
+            }
+        }
+        definition.map_or(std::ptr::null_mut(), |definition| {
+            Box::into_raw(Box::new(SlintComponentDefinition(definition)))
+        })
+    })
+}
+
+/// Frees a component definition. The instances created from it stay valid.
+#[no_mangle]
+pub unsafe extern "C" fn slint_component_definition_free(
+    definition: *mut SlintComponentDefinition,
+) {
+    if !definition.is_null() {
+        catch_panic((), || drop(Box::from_raw(definition)))
+    }
+}
+
+/// Creates a new instance of the component. Returns null if `definition` is null or if the
+/// instance can't be created, for example because no backend is available.
+#[no_mangle]
+pub unsafe extern "C" fn slint_component_definition_create(
+    definition: *const SlintComponentDefinition,
+) -> *mut SlintComponentInstance {
+    let definition = match definition.as_ref() {
+        Some(definition) => definition,
+        None => return std::ptr::null_mut(),
+    };
+    catch_panic(std::ptr::null_mut(), || {
+        Box::into_raw(Box::new(SlintComponentInstance(definition.0.create())))
+    })
+}
+
+/// Frees a component instance.
+#[no_mangle]
+pub unsafe extern "C" fn slint_component_instance_free(instance: *mut SlintComponentInstance) {
+    if !instance.is_null() {
+        catch_panic((), || drop(Box::from_raw(instance)))
+    }
+}
+
+/// Stores the value of the property `name` in `value`. The value must be freed with
+/// `slint_value_free`. Returns false if there is no such property.
+#[no_mangle]
+pub unsafe extern "C" fn slint_component_instance_get_property(
+    instance: *const SlintComponentInstance,
+    name: *const c_char,
+    value: *mut SlintValue,
+) -> bool {
+    let (instance, name) = match (instance.as_ref(), str_from_ptr(name)) {
+        (Some(instance), Some(name)) if !value.is_null() => (instance, name),
+        _ => return false,
+    };
+    catch_panic(false, || match instance.0.get_property(name) {
+        Ok(v) => {
+            value.write(to_slint_value(v));
+            true
+        }
+        Err(_) => false,
+    })
+}
+
+/// Sets the property `name` to a copy of `value`. Returns false if there is no such property or
+/// if the value has the wrong type.
+#[no_mangle]
+pub unsafe extern "C" fn slint_component_instance_set_property(
+    instance: *const SlintComponentInstance,
+    name: *const c_char,
+    value: *const SlintValue,
+) -> bool {
+    let (instance, name, value) = match (instance.as_ref(), str_from_ptr(name), value.as_ref()) {
+        (Some(instance), Some(name), Some(value)) => (instance, name, value),
+        _ => return false,
+    };
+    catch_panic(false, || {
+        let value = match from_slint_value(value) {
+            Some(value) => value,
+            None => return false,
+        };
+        let value = match (value, instance.0.get_property(name)) {
+            // The values of enumerations are passed as strings
+            (Value::String(value), Ok(Value::EnumerationValue(enumeration, _))) => {
+                Value::EnumerationValue(enumeration, value.into())
+            }
+            (value, _) => value,
+        };
+        instance.0.set_property(name, value).is_ok()
+    })
+}
+
+/// The handler of a callback: it receives the arguments, which are freed after the call, and
+/// sets the return value, which Slint takes ownership of.
+pub type SlintCallback = unsafe extern "C" fn(
+    user_data: *mut c_void,
+    args: *const SlintValue,
+    arg_count: usize,
+    return_value: *mut SlintValue,
+);
+
+/// Calls the function to free the user data of a callback handler when it is dropped
+struct UserData {
+    user_data: *mut c_void,
+    drop_user_data: Option<unsafe extern "C" fn(*mut c_void)>,
+}
+
+impl Drop for UserData {
+    fn drop(&mut self) {
+        if let Some(drop_user_data) = self.drop_user_data {
+            unsafe { drop_user_data(self.user_data) };
+        }
+    }
+}
+
+/// Sets the handler of the callback `name`. `drop_user_data`, which may be null, is called with
+/// `user_data` when the handler is replaced or the instance is freed. Returns false if there is
+/// no such callback.
+#[no_mangle]
+pub unsafe extern "C" fn slint_component_instance_set_callback(
+    instance: *const SlintComponentInstance,
+    name: *const c_char,
+    callback: SlintCallback,
+    user_data: *mut c_void,
+    drop_user_data: Option<unsafe extern "C" fn(*mut c_void)>,
+) -> bool {
+    let user_data = UserData { user_data, drop_user_data };
+    let (instance, name) = match (instance.as_ref(), str_from_ptr(name)) {
+        (Some(instance), Some(name)) => (instance, name),
+        _ => return false,
+    };
+    catch_panic(false, || {
+        instance
+            .0
+            .set_callback(name, move |args| {
+                // The handler is called from Slint, but the conversions must not make it unwind
+                // through the frames of the C code that invoked the callback either
+                catch_panic(Value::Void, || {
+                    let mut args = args.iter().cloned().map(to_slint_value).collect::<Vec<_>>();
+                    let mut return_value = SlintValue::VOID;
+                    callback(user_data.user_data, args.as_ptr(), args.len(), &mut return_value);
+                    args.iter_mut().for_each(|arg| free_value(arg));
+                    let result = from_slint_value(&return_value).unwrap_or_default();
+                    free_value(&mut return_value);
+                    result
+                })
+            })
+            .is_ok()
+    })
+}
+
+/// Invokes the callback `name` with the given arguments and stores its return value in
+/// `return_value`, which must be freed with `slint_value_free`. `return_value` may be null.
+/// Returns false if there is no such callback or if the arguments don't match its declaration.
+#[no_mangle]
+pub unsafe extern "C" fn slint_component_instance_invoke(
+    instance: *const SlintComponentInstance,
+    name: *const c_char,
+    args: *const SlintValue,
+    arg_count: usize,
+    return_value: *mut SlintValue,
+) -> bool {
+    let (instance, name) = match (instance.as_ref(), str_from_ptr(name)) {
+        (Some(instance), Some(name)) => (instance, name),
+        _ => return false,
+    };
+    catch_panic(false, || {
+        let args = match slice_from_raw(args, arg_count)
+            .iter()
+            .map(|arg| from_slint_value(arg))
+            .collect::<Option<Vec<_>>>()
+        {
+            Some(args) => args,
+            None => return false,
+        };
+        match instance.0.invoke(name, &args) {
+            Ok(result) => {
+                if !return_value.is_null() {
+                    return_value.write(to_slint_value(result));
+                }
+                true
+            }
+            Err(_) => false,
+        }
+    })
+}
+
+/// Shows the window of the component. Returns false if `instance` is null or if the window
+/// can't be shown.
+#[no_mangle]
+pub unsafe extern "C" fn slint_component_instance_show(
+    instance: *const SlintComponentInstance,
+) -> bool {
+    match instance.as_ref() {
+        Some(instance) => catch_panic(false, || {
+            instance.0.show();
+            true
+        }),
+        None => false,
+    }
+}
+
+/// Hides the window of the component. Returns false if `instance` is null or if the window
+/// can't be hidden.
+#[no_mangle]
+pub unsafe extern "C" fn slint_component_instance_hide(
+    instance: *const SlintComponentInstance,
+) -> bool {
+    match instance.as_ref() {
+        Some(instance) => catch_panic(false, || {
+            instance.0.hide();
+            true
+        }),
+        None => false,
+    }
+}
+
+/// Shows the window of the component, runs the event loop, and hides the window. Returns false
+/// if `instance` is null or if the event loop failed.
+#[no_mangle]
+pub unsafe extern "C" fn slint_component_instance_run(
+    instance: *const SlintComponentInstance,
+) -> bool {
+    match instance.as_ref() {
+        Some(instance) => catch_panic(false, || {
+            instance.0.run();
+            true
+        }),
+        None => false,
+    }
+}
+
+/// Runs the event loop until the last window is closed or `slint_quit_event_loop` is called.
+/// Returns false if the event loop failed.
+#[no_mangle]
+pub extern "C" fn slint_run_event_loop() -> bool {
+    catch_panic(false, || {
+        slint_interpreter::run_event_loop();
+        true
+    })
+}
+
+/// Stops the event loop. Returns false if there is no event loop.
+#[no_mangle]
+pub extern "C" fn slint_quit_event_loop() -> bool {
+    catch_panic(false, || i_slint_core::api::quit_event_loop().is_ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compile_and_use_component() {
+        i_slint_backend_testing::init();
+        // A unique file, so that concurrent runs of the test don't overwrite it
+        let path = std::env::temp_dir().join(format!(
+            "slint_c_api_test_{}_{:?}.slint",
+            std::process::id(),
+            std::thread::current().id()
+        ));
+        std::fs::write(
+            &path,
+            r#"
+            export Demo := Window {
+                property <string> text: "hello";
+                property <TextHorizontalAlignment> alignment: left;
+                property <bool> flag;
+                property <[int]> values: [1, 2, 3];
+                callback add(int, int) -> int;
+            }
+            "#,
+        )
+        .unwrap();
+        let path_str = CString::new(path.to_str().unwrap()).unwrap();
+
+        unsafe {
+            let mut diagnostics = std::ptr::null_mut();
+            let definition = slint_compile_file(
+                path_str.as_ptr(),
+                std::ptr::null(),
+                0,
+                std::ptr::null(),
+                &mut diagnostics,
+            );
+            std::fs::remove_file(&path).ok();
+            assert!(diagnostics.is_null());
+            let instance = slint_component_definition_create(&*definition);
+            slint_component_definition_free(definition);
+            assert!(slint_component_definition_create(std::ptr::null()).is_null());
+            assert!(!instance.is_null());
+
+            let mut value = SlintValue::VOID;
+            assert!(slint_component_instance_get_property(
+                &*instance,
+                b"text\0".as_ptr() as _,
+                &mut value
+            ));
+            assert_eq!(value.value_type(), Some(SlintValueType::String));
+            assert_eq!(CStr::from_ptr(value.data.string.data).to_str(), Ok("hello"));
+            slint_value_free(&mut value);
+
+            let mut new_text = slint_value_new_string(b"world".as_ptr() as _, 5);
+            assert!(slint_component_instance_set_property(
+                &*instance,
+                b"text\0".as_ptr() as _,
+                &new_text
+            ));
+            slint_value_free(&mut new_text);
+            assert_eq!((*instance).0.get_property("text").unwrap(), Value::String("world".into()));
+
+            let mut alignment = slint_value_new_string(b"right".as_ptr() as _, 5);
+            assert!(slint_component_instance_set_property(
+                &*instance,
+                b"alignment\0".as_ptr() as _,
+                &alignment
+            ));
+            slint_value_free(&mut alignment);
+            assert!(slint_component_instance_get_property(
+                &*instance,
+                b"alignment\0".as_ptr() as _,
+                &mut value
+            ));
+            assert_eq!(CStr::from_ptr(value.data.string.data).to_str(), Ok("right"));
+            slint_value_free(&mut value);
+
+            // Any non-zero byte is true, and values of unknown types are rejected
+            let flag = SlintValue::new(SlintValueType::Bool, SlintValueData { boolean: 2 });
+            assert!(slint_component_instance_set_property(
+                &*instance,
+                b"flag\0".as_ptr() as _,
+                &flag
+            ));
+            assert_eq!((*instance).0.get_property("flag").unwrap(), Value::Bool(true));
+            let mut invalid = SlintValue { ty: 42, data: SlintValueData { number: 0. } };
+            assert!(!slint_component_instance_set_property(
+                &*instance,
+                b"flag\0".as_ptr() as _,
+                &invalid
+            ));
+            slint_value_free(&mut invalid);
+            assert_eq!(invalid.ty, SlintValueType::Void as u32);
+
+            assert!(slint_component_instance_get_property(
+                &*instance,
+                b"values\0".as_ptr() as _,
+                &mut value
+            ));
+            assert_eq!(value.value_type(), Some(SlintValueType::Array));
+            assert_eq!(value.data.array.len, 3);
+            assert_eq!((*value.data.array.values.add(2)).data.number, 3.);
+            slint_value_free(&mut value);
+
+            unsafe extern "C" fn add(
+                user_data: *mut c_void,
+                args: *const SlintValue,
+                arg_count: usize,
+                return_value: *mut SlintValue,
+            ) {
+                *(user_data as *mut usize) += 1;
+                let args = std::slice::from_raw_parts(args, arg_count);
+                *return_value = SlintValue::new(
+                    SlintValueType::Number,
+                    SlintValueData { number: args[0].data.number + args[1].data.number },
+                );
+            }
+            let mut call_count = 0usize;
+            assert!(slint_component_instance_set_callback(
+                &*instance,
+                b"add\0".as_ptr() as _,
+                add,
+                &mut call_count as *mut usize as *mut c_void,
+                None,
+            ));
+            let args = [
+                SlintValue::new(SlintValueType::Number, SlintValueData { number: 1. }),
+                SlintValue::new(SlintValueType::Number, SlintValueData { number: 2. }),
+            ];
+            assert!(slint_component_instance_invoke(
+                &*instance,
+                b"add\0".as_ptr() as _,
+                args.as_ptr(),
+                args.len(),
+                &mut value
+            ));
+            assert_eq!(value.value_type(), Some(SlintValueType::Number));
+            assert_eq!(value.data.number, 3.);
+            assert_eq!(call_count, 1);
+
+            assert!(!slint_component_instance_invoke(
+                &*instance,
+                b"add\0".as_ptr() as _,
+                args.as_ptr(),
+                1,
+                std::ptr::null_mut()
+            ));
+
+            // Null pointers are reported as errors
+            let mut value = SlintValue::VOID;
+            assert!(!slint_component_instance_get_property(
+                std::ptr::null(),
+                b"text\0".as_ptr() as _,
+                &mut value
+            ));
+            assert!(!slint_component_instance_set_property(
+                instance,
+                b"text\0".as_ptr() as _,
+                std::ptr::null()
+            ));
+            assert!(!slint_component_instance_show(std::ptr::null()));
+            slint_component_instance_free(instance);
+        }
+    }
+}
@@ -63,7 +63,11 @@ fn default_config() -> cbindgen::Config {
                 ("VoidArg".into(), "void".into()),
                 ("KeyEventArg".into(), "KeyEvent".into()),
                 ("PointerEventArg".into(), "PointerEvent".into()),
+                ("PointerScrollEventArg".into(), "PointerScrollEvent".into()),
                 ("PointArg".into(), "Point".into()),
+                ("DropEventArg".into(), "DropEvent".into()),
+                ("GestureEventArg".into(), "GestureEvent".into()),
+                ("StringArg".into(), "SharedString".into()),
                 ("FloatArg".into(), "float".into()),
                 ("Coord".into(), "float".into()),
             ]
@@ -121,6 +125,14 @@ fn gen_corelib(
         "ClippedImage",
         "TouchArea",
         "FocusScope",
+        "DragArea",
+        "DropArea",
+        "GestureArea",
+        "Shortcut",
+        "ToastQueue",
+        "SystemTrayIcon",
+        "PlatformMenuItem",
+        "PlatformMenuBar",
         "Flickable",
         "Text",
         "Path",
@@ -128,30 +140,45 @@ fn gen_corelib(
         "TextInput",
         "Clip",
         "BoxShadow",
-        "Rotate",
+        "DropShadow",
+        "Transform",
+        "ViewportObserver",
         "Opacity",
         "Layer",
+        "Effect",
     ];
 
     config.export.include = [
         "ComponentVTable",
         "Slice",
         "WindowAdapterRcOpaque",
+        "PopupWindowOptions",
         "PropertyAnimation",
         "EasingCurve",
         "TextHorizontalAlignment",
         "TextVerticalAlignment",
         "TextOverflow",
+        "TextElidePosition",
+        "TextDirection",
+        "LayoutDirection",
         "TextWrap",
         "ImageFit",
         "FillRule",
+        "LineCap",
+        "LineJoin",
         "MouseCursor",
         "InputType",
+        "FocusPolicy",
+        "OvershootBehavior",
+        "ColorScheme",
         "StandardButtonKind",
         "DialogButtonRole",
         "PointerEventKind",
         "PointerEventButton",
         "PointerEvent",
+        "PointerScrollEvent",
+        "DropEvent",
+        "GestureEvent",
         "Rect",
     ]
     .iter()
@@ -190,12 +217,17 @@ fn gen_corelib(
         "slint_property_listener_scope_evaluate",
         "slint_property_listener_scope_is_dirty",
         "PropertyTrackerOpaque",
+        "ChangeTrackerOpaque",
         "CallbackOpaque",
         "WindowAdapterRc",
         "VoidArg",
         "KeyEventArg",
         "PointerEventArg",
+        "PointerScrollEventArg",
         "PointArg",
+        "StringArg",
+        "DropEventArg",
+        "GestureEventArg",
         "Point",
         "slint_color_brighter",
         "slint_color_darker",
@@ -203,6 +235,8 @@ fn gen_corelib(
         "slint_image_path",
         "slint_image_load_from_path",
         "slint_image_load_from_embedded_data",
+        "slint_image_from_borrowed_gl_2d_rgba_texture",
+        "BorrowedOpenGLTexture",
         "Coord",
         "LogicalRect",
         "LogicalPoint",
@@ -272,6 +306,8 @@ fn gen_corelib(
                 "slint_image_path",
                 "slint_image_load_from_path",
                 "slint_image_load_from_embedded_data",
+                "slint_image_from_borrowed_gl_2d_rgba_texture",
+                "BorrowedOpenGLTexture",
                 "SharedPixelBuffer",
                 "SharedImageBuffer",
                 "StaticTextures",
@@ -314,6 +350,7 @@ fn gen_corelib(
             "slint_windowrc_set_focus_item",
             "slint_windowrc_set_component",
             "slint_windowrc_show_popup",
+            "slint_windowrc_close_popup",
             "slint_windowrc_set_rendering_notifier",
             "slint_windowrc_request_redraw",
             "slint_windowrc_on_close_requested",
@@ -324,6 +361,8 @@ fn gen_corelib(
             "slint_windowrc_set_logical_size",
             "slint_windowrc_set_physical_size",
             "slint_windowrc_dark_color_scheme",
+            "slint_windowrc_color_scheme",
+            "slint_windowrc_set_color_scheme",
             "slint_new_path_elements",
             "slint_new_path_events",
             "slint_color_brighter",
@@ -332,6 +371,8 @@ fn gen_corelib(
             "slint_image_path",
             "slint_image_load_from_path",
             "slint_image_load_from_embedded_data",
+            "slint_image_from_borrowed_gl_2d_rgba_texture",
+            "BorrowedOpenGLTexture",
         ]
         .iter()
         .filter(|exclusion| !rust_types.iter().any(|inclusion| inclusion == *exclusion))
@@ -436,12 +477,29 @@ fn gen_corelib(
         "StandardListViewItem".to_owned(),
         "friend bool operator==(const StandardListViewItem&, const StandardListViewItem&) = default;".into(),
     );
+    config.export.body.insert(
+        "StandardTreeViewItem".to_owned(),
+        "friend bool operator==(const StandardTreeViewItem&, const StandardTreeViewItem&) = default;".into(),
+    );
     config
         .export
         .body
         .insert("Flickable".to_owned(), "    inline Flickable(); inline ~Flickable();".into());
     config.export.pre_body.insert("FlickableDataBox".to_owned(), "struct FlickableData;".into());
+    config.export.body.insert("Text".to_owned(), "    inline Text(); inline ~Text();".into());
+    config.export.pre_body.insert("TextDataBox".to_owned(), "struct TextData;".into());
+    config
+        .export
+        .body
+        .insert("Shortcut".to_owned(), "    inline Shortcut(); inline ~Shortcut();".into());
+    config.export.pre_body.insert("ShortcutDataBox".to_owned(), "struct ShortcutData;".into());
+    config
+        .export
+        .body
+        .insert("ToastQueue".to_owned(), "    inline ToastQueue(); inline ~ToastQueue();".into());
+    config.export.pre_body.insert("ToastQueueDataBox".to_owned(), "struct ToastQueueData;".into());
     config.export.include.push("StandardListViewItem".into());
+    config.export.include.push("StandardTreeViewItem".into());
     cbindgen::Builder::new()
         .with_config(config)
         .with_src(crate_dir.join("lib.rs"))
@@ -465,7 +523,7 @@ fn gen_corelib(
     namespace cbindgen_private {
         using slint::private_api::WindowAdapterRc;
         using namespace vtable;
-        struct KeyEvent; struct PointerEvent;
+        struct KeyEvent; struct PointerEvent; struct PointerScrollEvent; struct DropEvent; struct GestureEvent;
         using private_api::Property;
         using private_api::PathData;
         using private_api::Point;
@@ -504,6 +562,10 @@ fn gen_backend_qt(
         "NativeComboBoxPopup",
         "NativeTabWidget",
         "NativeTab",
+        "NativeMenuBarItem",
+        "NativeMenuItem",
+        "NativeMenuFrame",
+        "NativeProgressIndicator",
         "NativeStyleMetrics",
     ];
 
@@ -1,12 +1,45 @@
 # Copyright © SixtyFPS GmbH <info@slint-ui.com>
 # SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
 
+# Usage:
+#   slint_target_sources(<target> <files>...
+#                        [INCLUDE_DIRECTORIES <directories>...]
+#                        [STYLE <style>]
+#                        [EMBED_RESOURCES <as-absolute-path|embed-files>]
+#                        [NAMESPACE <namespace>])
 function(SLINT_TARGET_SOURCES target)
-    foreach (it IN ITEMS ${ARGN})
+    cmake_parse_arguments(_SLINT "" "STYLE;EMBED_RESOURCES;NAMESPACE" "INCLUDE_DIRECTORIES" ${ARGN})
+
+    if (_SLINT_STYLE)
+        set(_SLINT_STYLE_ARG ${_SLINT_STYLE})
+    else()
+        get_property(_SLINT_STYLE_ARG GLOBAL PROPERTY SLINT_STYLE)
+    endif()
+
+    set(_SLINT_EXTRA_ARGS)
+    foreach (include_dir IN LISTS _SLINT_INCLUDE_DIRECTORIES)
+        get_filename_component(_SLINT_INCLUDE_ABSOLUTE ${include_dir} REALPATH BASE_DIR ${CMAKE_CURRENT_SOURCE_DIR})
+        list(APPEND _SLINT_EXTRA_ARGS -I ${_SLINT_INCLUDE_ABSOLUTE})
+    endforeach()
+    if (_SLINT_EMBED_RESOURCES)
+        list(APPEND _SLINT_EXTRA_ARGS --embed-resources ${_SLINT_EMBED_RESOURCES})
+    endif()
+    if (_SLINT_NAMESPACE)
+        list(APPEND _SLINT_EXTRA_ARGS --cpp-namespace ${_SLINT_NAMESPACE})
+    endif()
+
+    # Depfiles are supported by the Ninja generators, and by the Makefile generators since CMake 3.21
+    if (CMAKE_GENERATOR STREQUAL "Ninja" OR CMAKE_GENERATOR STREQUAL "Ninja Multi-Config"
+        OR (CMAKE_GENERATOR MATCHES "Makefiles" AND CMAKE_VERSION VERSION_GREATER_EQUAL 3.21))
+        set(_SLINT_USE_DEPFILE TRUE)
+    else()
+        set(_SLINT_USE_DEPFILE FALSE)
+    endif()
+
+    foreach (it IN LISTS _SLINT_UNPARSED_ARGUMENTS)
         get_filename_component(_SLINT_BASE_NAME ${it} NAME_WE)
         get_filename_component(_SLINT_ABSOLUTE ${it} REALPATH BASE_DIR ${CMAKE_CURRENT_SOURCE_DIR})
-        get_property(_SLINT_STYLE GLOBAL PROPERTY SLINT_STYLE)
-        if(CMAKE_GENERATOR STREQUAL "Ninja")
+        if(_SLINT_USE_DEPFILE)
             # this code is inspired from the llvm source
             # https://github.com/llvm/llvm-project/blob/a00290ed10a6b4e9f6e9be44ceec367562f270c6/llvm/cmake/modules/TableGen.cmake#L13
 
@@ -16,24 +49,32 @@ function(SLINT_TARGET_SOURCES target)
                 OUTPUT ${CMAKE_CURRENT_BINARY_DIR}/${_SLINT_BASE_NAME}.h
                 COMMAND Slint::slint-compiler ${_SLINT_ABSOLUTE}
                     -o ${_SLINT_BASE_NAME_REL}.h  --depfile ${_SLINT_BASE_NAME_REL}.d
-                    --style ${_SLINT_STYLE}
+                    --style ${_SLINT_STYLE_ARG}
+                    ${_SLINT_EXTRA_ARGS}
                 DEPENDS Slint::slint-compiler ${_SLINT_ABSOLUTE}
                 COMMENT "Generating ${_SLINT_BASE_NAME}.h"
                 DEPFILE ${CMAKE_CURRENT_BINARY_DIR}/${_SLINT_BASE_NAME}.d
                 WORKING_DIRECTORY ${CMAKE_BINARY_DIR}
             )
-        else(CMAKE_GENERATOR STREQUAL "Ninja")
+        else()
+            # Without depfile, depend on all the .slint files next to the file and in the include directories
             get_filename_component(_SLINT_DIR ${_SLINT_ABSOLUTE} DIRECTORY )
             file(GLOB ALL_SLINTS "${_SLINT_DIR}/*.slint")
+            foreach (include_dir IN LISTS _SLINT_INCLUDE_DIRECTORIES)
+                get_filename_component(_SLINT_INCLUDE_ABSOLUTE ${include_dir} REALPATH BASE_DIR ${CMAKE_CURRENT_SOURCE_DIR})
+                file(GLOB_RECURSE _SLINT_INCLUDED_SLINTS "${_SLINT_INCLUDE_ABSOLUTE}/*.slint")
+                list(APPEND ALL_SLINTS ${_SLINT_INCLUDED_SLINTS})
+            endforeach()
             add_custom_command(
                 OUTPUT ${CMAKE_CURRENT_BINARY_DIR}/${_SLINT_BASE_NAME}.h
                 COMMAND Slint::slint-compiler ${_SLINT_ABSOLUTE}
                     -o ${CMAKE_CURRENT_BINARY_DIR}/${_SLINT_BASE_NAME}.h
-                    --style ${_SLINT_STYLE}
+                    --style ${_SLINT_STYLE_ARG}
+                    ${_SLINT_EXTRA_ARGS}
                 DEPENDS Slint::slint-compiler ${_SLINT_ABSOLUTE} ${ALL_SLINTS}
                 COMMENT "Generating ${_SLINT_BASE_NAME}.h"
             )
-        endif(CMAKE_GENERATOR STREQUAL "Ninja")
+        endif()
 
         target_sources(${target} PRIVATE ${CMAKE_CURRENT_BINARY_DIR}/${_SLINT_BASE_NAME}.h)
     endforeach()
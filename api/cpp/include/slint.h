@@ -11,6 +11,7 @@
 #include <vector>
 #include <memory>
 #include <algorithm>
+#include <iterator>
 #include <iostream> // FIXME: remove: iostream always bring it lots of code so we should not have it in this header
 #include <chrono>
 #include <optional>
@@ -62,7 +63,11 @@ using ItemArray = slint::cbindgen_private::Slice<ItemArrayEntry>;
 using cbindgen_private::KeyboardModifiers;
 using cbindgen_private::KeyEvent;
 using cbindgen_private::PointerEvent;
+using cbindgen_private::PointerScrollEvent;
+using cbindgen_private::DropEvent;
+using cbindgen_private::GestureEvent;
 using cbindgen_private::StandardListViewItem;
+using cbindgen_private::StandardTreeViewItem;
 
 /// Internal function that checks that the API that must be called from the main
 /// thread is indeed called from the main thread, or abort the program otherwise
@@ -119,6 +124,15 @@ public:
 
     bool dark_color_scheme() const { return slint_windowrc_dark_color_scheme(&inner); }
 
+    cbindgen_private::ColorScheme color_scheme() const
+    {
+        return slint_windowrc_color_scheme(&inner);
+    }
+    void set_color_scheme(cbindgen_private::ColorScheme scheme) const
+    {
+        slint_windowrc_set_color_scheme(&inner, scheme);
+    }
+
     template<typename Component, typename ItemArray>
     void unregister_component(Component *c, ItemArray items) const
     {
@@ -148,12 +162,15 @@ public:
 
     template<typename Component, typename Parent>
     void show_popup(const Parent *parent_component, cbindgen_private::Point p,
+                    cbindgen_private::PopupWindowOptions options,
                     cbindgen_private::ItemRc parent_item) const
     {
         auto popup = Component::create(parent_component).into_dyn();
-        cbindgen_private::slint_windowrc_show_popup(&inner, &popup, p, &parent_item);
+        cbindgen_private::slint_windowrc_show_popup(&inner, &popup, p, options, &parent_item);
     }
 
+    void close_popup() const { cbindgen_private::slint_windowrc_close_popup(&inner); }
+
     template<typename F>
     std::optional<SetRenderingNotifierError> set_rendering_notifier(F callback) const
     {
@@ -382,6 +399,65 @@ public:
     }
 };
 
+/// A PropertyObserver calls a function every time the value returned by a getter function changes.
+/// The getter typically reads one or several properties of a component. It is only evaluated again
+/// when one of these properties changes, and the function is called from the event loop, after the
+/// properties changed, if the new value is different from the previous one.
+///
+/// The observation stops when the PropertyObserver is destroyed.
+///
+/// ```cpp
+/// auto app = MyApp::create();
+/// slint::ComponentWeakHandle<MyApp> weak_app(app);
+/// slint::PropertyObserver observer(
+///         [=] { return weak_app.lock().value()->get_counter(); },
+///         [](int counter) { std::cout << "The counter is now " << counter << std::endl; });
+/// app->run();
+/// ```
+class PropertyObserver
+{
+public:
+    /// Constructs an observer that calls \a on_changed with the new value every time the value
+    /// returned by \a getter changes. \a getter is called once from the constructor to get the
+    /// initial value.
+    template<typename Getter, typename OnChanged>
+    PropertyObserver(Getter getter, OnChanged on_changed)
+    {
+        using T = std::invoke_result_t<Getter>;
+        struct Data
+        {
+            Getter getter;
+            OnChanged on_changed;
+            std::optional<T> value;
+        };
+        cbindgen_private::slint_change_tracker_construct(&inner);
+        cbindgen_private::slint_change_tracker_init(
+                &inner, new Data { std::move(getter), std::move(on_changed), {} },
+                [](void *user_data) { delete reinterpret_cast<Data *>(user_data); },
+                [](void *user_data) {
+                    auto data = reinterpret_cast<Data *>(user_data);
+                    auto value = data->getter();
+                    bool changed = !data->value || !(*data->value == value);
+                    data->value = std::move(value);
+                    return changed;
+                },
+                [](void *user_data) {
+                    auto data = reinterpret_cast<Data *>(user_data);
+                    data->on_changed(*data->value);
+                });
+    }
+    /// Stops the observation.
+    ~PropertyObserver() { cbindgen_private::slint_change_tracker_drop(&inner); }
+    PropertyObserver(const PropertyObserver &) = delete;
+    PropertyObserver &operator=(const PropertyObserver &) = delete;
+
+private:
+    cbindgen_private::ChangeTrackerOpaque inner;
+};
+
+/// This enum describes whether the colors of the application are dark or light.
+using cbindgen_private::ColorScheme;
+
 /// This class represents a window towards the windowing system, that's used to render the
 /// scene of a component. It provides API to control windowing system specific aspects such
 /// as the position on the screen.
@@ -408,6 +484,10 @@ public:
 
     /// This function allows registering a callback that's invoked during the different phases of
     /// rendering. This allows custom rendering on top or below of the scene.
+    /// During RenderingState::BeforeRendering, the back buffer was cleared with the window's
+    /// background but no item was drawn yet, so that a scene rendered by the callback appears
+    /// underneath the UI. During RenderingState::AfterRendering, content rendered by the callback
+    /// appears on top of the UI.
     /// On success, the function returns a std::optional without value. On error, the function
     /// returns the error code as value in the std::optional.
     template<typename F>
@@ -455,6 +535,13 @@ public:
     /// a window frame (if present).
     void set_size(const slint::PhysicalSize &size) { inner.set_physical_size(size); }
 
+    /// Returns the color scheme used by the window. This is the color scheme of the platform,
+    /// unless it was overridden with set_color_scheme().
+    ColorScheme color_scheme() const { return inner.color_scheme(); }
+    /// Overrides the color scheme of the platform for this window. Set it to ColorScheme::Unknown
+    /// to follow the color scheme of the platform again.
+    void set_color_scheme(ColorScheme scheme) const { inner.set_color_scheme(scheme); }
+
     /// \private
     private_api::WindowAdapterRc &window_handle() { return inner; }
     /// \private
@@ -539,6 +626,12 @@ inline LayoutInfo LayoutInfo::merge(const LayoutInfo &other) const
 
 namespace private_api {
 
+inline void ensure_item_visible(const ComponentRc &component_rc, uintptr_t item_index)
+{
+    cbindgen_private::ItemRc item_rc { component_rc, item_index };
+    cbindgen_private::slint_ensure_item_visible(&item_rc);
+}
+
 inline SharedVector<float> solve_box_layout(const cbindgen_private::BoxLayoutData &data,
                                             cbindgen_private::Slice<int> repeater_indexes)
 {
@@ -649,6 +742,70 @@ public:
         std::cerr << "Model::set_row_data was called on a read-only model" << std::endl;
     };
 
+    /// An iterator over the rows of a model, that returns the data of the rows by value.
+    ///
+    /// The iterator only holds the model and the index of the row, so it stays valid when
+    /// rows are added or removed, but it may then skip or repeat rows.
+    class iterator
+    {
+    public:
+        /// \private
+        using iterator_category = std::input_iterator_tag;
+        /// \private
+        using iterator_concept = std::forward_iterator_tag;
+        /// \private
+        using value_type = ModelData;
+        /// \private
+        using difference_type = std::ptrdiff_t;
+        /// \private
+        using pointer = void;
+        /// \private
+        using reference = ModelData;
+
+        iterator() = default;
+        /// \private
+        iterator(const Model *model, int row) : model(model), row(row) { }
+
+        /// Returns the data of the current row.
+        ModelData operator*() const { return model->row_data(row).value_or(ModelData {}); }
+        /// Advances the iterator to the next row.
+        iterator &operator++()
+        {
+            ++row;
+            return *this;
+        }
+        /// Advances the iterator to the next row and returns the previous value of the iterator.
+        iterator operator++(int)
+        {
+            auto copy = *this;
+            ++row;
+            return copy;
+        }
+        /// Returns true if both iterators point to the same row of the same model.
+        friend bool operator==(const iterator &a, const iterator &b)
+        {
+            return a.model == b.model && a.row == b.row;
+        }
+
+    private:
+        const Model *model = nullptr;
+        int row = 0;
+    };
+
+    /// Returns an iterator to the first row of the model, so that models can be used in
+    /// range-based for loops, in the algorithms of the standard library and with std::ranges.
+    ///
+    /// For example:
+    /// \code
+    ///     auto model = std::make_shared<slint::VectorModel<int>>(std::vector { 1, 2, 3 });
+    ///     for (int value : *model) {
+    ///         std::cout << value << std::endl;
+    ///     }
+    /// \endcode
+    iterator begin() const { return iterator(this, 0); }
+    /// Returns an iterator past the last row of the model.
+    iterator end() const { return iterator(this, row_count()); }
+
     /// \private
     /// Internal function called by the view to register itself
     void attach_peer(private_api::ModelPeer p) { peers.push_back(std::move(p)); }
@@ -791,8 +948,16 @@ class VectorModel : public Model<ModelData>
 public:
     /// Constructs a new empty VectorModel.
     VectorModel() = default;
-    /// Constructs a new VectorModel from \a array.
+    /// Constructs a new VectorModel from \a array. Pass an rvalue to move the vector into the
+    /// model without copying its elements.
     VectorModel(std::vector<ModelData> array) : data(std::move(array)) { }
+    /// Constructs a new VectorModel from the elements of \a list.
+    VectorModel(std::initializer_list<ModelData> list) : data(list) { }
+    /// Constructs a new VectorModel from the elements in the range [\a first, \a last).
+    template<typename InputIt>
+    VectorModel(InputIt first, InputIt last) : data(first, last)
+    {
+    }
     int row_count() const override { return int(data.size()); }
     std::optional<ModelData> row_data(int i) const override
     {
@@ -815,6 +980,13 @@ public:
         this->row_added(int(data.size()) - 1, 1);
     }
 
+    /// Append a new row with the given value, moving it into the model
+    void push_back(ModelData &&value)
+    {
+        data.push_back(std::move(value));
+        this->row_added(int(data.size()) - 1, 1);
+    }
+
     /// Remove the row at the given index from the model
     void erase(int index)
     {
@@ -828,6 +1000,29 @@ public:
         data.insert(data.begin() + index, value);
         this->row_added(int(index), 1);
     }
+
+    /// Inserts the given value as a new row at the specified index, moving it into the model
+    void insert(size_t index, ModelData &&value)
+    {
+        data.insert(data.begin() + index, std::move(value));
+        this->row_added(int(index), 1);
+    }
+
+    /// Replaces all the rows of the model with the elements of \a array
+    void set_vector(std::vector<ModelData> array)
+    {
+        data = std::move(array);
+        this->reset();
+    }
+
+    /// Removes all the rows of the model
+    void clear()
+    {
+        if (!data.empty()) {
+            data.clear();
+            this->reset();
+        }
+    }
 };
 
 template<typename ModelData>
@@ -1411,6 +1606,30 @@ cbindgen_private::Flickable::~Flickable()
 {
     slint_flickable_data_free(&data);
 }
+cbindgen_private::Text::Text()
+{
+    slint_text_data_init(&data);
+}
+cbindgen_private::Text::~Text()
+{
+    slint_text_data_free(&data);
+}
+cbindgen_private::Shortcut::Shortcut()
+{
+    slint_shortcut_data_init(&data);
+}
+cbindgen_private::Shortcut::~Shortcut()
+{
+    slint_shortcut_data_free(&data);
+}
+cbindgen_private::ToastQueue::ToastQueue()
+{
+    slint_toast_queue_data_init(&data);
+}
+cbindgen_private::ToastQueue::~ToastQueue()
+{
+    slint_toast_queue_data_free(&data);
+}
 
 cbindgen_private::NativeStyleMetrics::NativeStyleMetrics(void *)
 {
@@ -1451,6 +1670,15 @@ inline void quit_event_loop()
     cbindgen_private::slint_quit_event_loop();
 }
 
+/// Registers a font family that is used to render the characters that the fonts of an element's
+/// `font-family` don't support, such as CJK characters or emoji in a latin font.
+/// The registered families are tried in the order in which they were registered, before the
+/// fallback fonts of the system. Call this function before showing the window.
+inline void register_font_fallback_family(const SharedString &family)
+{
+    cbindgen_private::slint_register_font_fallback_family(&family);
+}
+
 /// Adds the specified functor to an internal queue, notifies the event loop to wake up.
 /// Once woken up, any queued up functors will be invoked.
 /// This function is thread-safe and can be called from any thread, including the one
@@ -11,6 +11,12 @@
 
 namespace slint {
 
+struct Image;
+namespace private_api {
+inline Image load_image_from_path_in_background(const SharedString &path,
+                                                const Image &placeholder);
+}
+
 /// An image type that can be displayed by the Image element
 struct Image
 {
@@ -25,6 +31,22 @@ public:
         return img;
     }
 
+    /// Creates a new Image from an existing OpenGL texture. The texture must be a 2D texture with
+    /// the RGBA format and pre-multiplied alpha, and it remains owned by the caller: it must stay
+    /// valid in the OpenGL context of the window as long as the image is in use.
+    ///
+    /// The renderer samples the texture directly, which allows showing video frames or camera
+    /// previews without copying them. Call Window::request_redraw() after changing the contents
+    /// of the texture. Only the OpenGL based renderers can render such an image.
+    static Image create_from_borrowed_gl_2d_rgba_texture(uint32_t texture_id,
+                                                          Size<uint32_t> size)
+    {
+        Image img;
+        cbindgen_private::types::slint_image_from_borrowed_gl_2d_rgba_texture(texture_id, size,
+                                                                              &img.data);
+        return img;
+    }
+
     /*
     static Image load_from_argb(int width, int height, const SharedVector<uint32_t> &data) {
         Image img;
@@ -55,12 +77,23 @@ public:
     explicit Image(cbindgen_private::types::Image inner) : data(inner) { }
 
 private:
+    friend Image private_api::load_image_from_path_in_background(const SharedString &path,
+                                                                 const Image &placeholder);
     using Tag = cbindgen_private::types::ImageInner::Tag;
     using Data = cbindgen_private::types::Image;
     Data data;
 };
 
 namespace private_api {
+inline Image load_image_from_path_in_background(const SharedString &path,
+                                                const Image &placeholder)
+{
+    cbindgen_private::types::Image img(cbindgen_private::types::Image::ImageInner_None());
+    cbindgen_private::types::slint_image_load_from_path_in_background(&path, &placeholder.data,
+                                                                      &img);
+    return Image(img);
+}
+
 inline Image load_image_from_embedded_data(std::span<const uint8_t> data,
                                            std::string_view extension)
 {
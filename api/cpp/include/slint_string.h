@@ -2,7 +2,9 @@
 // SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
 
 #pragma once
+#include <string>
 #include <string_view>
+#include <functional>
 #include "slint_string_internal.h"
 
 namespace slint {
@@ -33,6 +35,9 @@ struct SharedString
     /// Creates a new SharedString from the null-terminated string pointer \a s. The underlying
     /// string data is copied. It is assumed that the string is UTF-8 encoded.
     SharedString(const char *s) : SharedString(std::string_view(s)) { }
+    /// Creates a new SharedString from the std::string \a s. The underlying string data is
+    /// copied. It is assumed that the string is UTF-8 encoded.
+    SharedString(const std::string &s) : SharedString(std::string_view(s)) { }
 #if defined(__cpp_char8_t) || __cplusplus >= 202002L
     /// Creates a new SharedString from the null-terminated string pointer \a s. The underlying
     /// string data is copied.
@@ -79,6 +84,12 @@ struct SharedString
     {
         return *this = std::string_view(s);
     }
+    /// Assigns the std::string \a s to this string and returns a reference to this string.
+    /// The underlying string data is copied. It is assumed that the string is UTF-8 encoded.
+    SharedString &operator=(const std::string &s)
+    {
+        return *this = std::string_view(s);
+    }
 
     /// Move-assigns \a other to this SharedString instance.
     SharedString &operator=(SharedString &&other)
@@ -93,6 +104,11 @@ struct SharedString
     {
         return cbindgen_private::slint_shared_string_bytes(this);
     }
+    /// Returns a copy of the string data as a std::string.
+    std::string to_std_string() const
+    {
+        return std::string(std::string_view(*this));
+    }
     /// Provides a raw pointer to the string data. The returned pointer is only valid as long as at
     /// least this SharedString exists.
     auto data() const -> const char *
@@ -135,6 +151,16 @@ struct SharedString
                 == 0;
     }
 
+    /// Returns a copy of this string where all characters are converted to lowercase, according
+    /// to the Unicode lowercase mapping.
+    SharedString to_lowercase() const
+    {
+        SharedString result;
+        cbindgen_private::slint_shared_string_drop(&result);
+        cbindgen_private::slint_shared_string_to_lowercase(&result, this);
+        return result;
+    }
+
     /// Creates a new SharedString from the given number \a n. The string representation of the
     /// number uses a minimal formatting scheme: If \a n has no fractional part, the number will be
     /// formatted as an integer.
@@ -226,3 +252,16 @@ inline cbindgen_private::Slice<uint8_t> string_to_slice(std::string_view str)
 }
 
 }
+
+namespace std {
+/// Hashes a SharedString like the std::string_view of its data, so that it can be used as the key
+/// of a std::unordered_map or std::unordered_set.
+template<>
+struct hash<slint::SharedString>
+{
+    size_t operator()(const slint::SharedString &s) const noexcept
+    {
+        return hash<std::string_view>()(std::string_view(s));
+    }
+};
+}
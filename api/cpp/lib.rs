@@ -64,6 +64,11 @@ unsafe impl Send for UserData {}
     i_slint_core::api::quit_event_loop().unwrap();
 }
 
+#[no_mangle]
+pub extern "C" fn slint_register_font_fallback_family(family: &i_slint_core::SharedString) {
+    i_slint_core::api::register_font_fallback_family(family.clone());
+}
+
 #[no_mangle]
 pub unsafe extern "C" fn slint_register_font_from_path(
     win: *const WindowAdapterRcOpaque,
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
 
 #include <chrono>
+#include <unordered_set>
 #define CATCH_CONFIG_MAIN
 #include "catch2/catch.hpp"
 
@@ -31,6 +32,16 @@ SCENARIO("SharedString API")
         REQUIRE(str == "Bar");
     }
 
+    SECTION("Convert from and to std::string")
+    {
+        std::string foo("Foo");
+        str = foo;
+        REQUIRE(str == foo);
+        REQUIRE(str.to_std_string() == "Foo");
+        std::unordered_set<slint::SharedString> set { str, slint::SharedString(foo) };
+        REQUIRE(set.size() == 1);
+    }
+
     SECTION("concatenate")
     {
         str = "Hello";
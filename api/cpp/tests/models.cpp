@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
 
 #include <chrono>
+#include <ranges>
 #define CATCH_CONFIG_MAIN
 #include "catch2/catch.hpp"
 
@@ -396,3 +397,28 @@ SCENARIO("Sorted Model Change")
     REQUIRE(sorted_model->row_data(2) == 2);
     REQUIRE(sorted_model->row_data(3) == 3);
 }
+
+SCENARIO("Vector Model Iterators")
+{
+    auto vec_model = std::make_shared<slint::VectorModel<int>>(std::vector<int> { 3, 1, 2 });
+
+    std::vector<int> rows;
+    for (int value : *vec_model) {
+        rows.push_back(value);
+    }
+    REQUIRE(rows == std::vector<int> { 3, 1, 2 });
+
+    REQUIRE(std::ranges::max(*vec_model) == 3);
+    auto doubled = *vec_model | std::views::transform([](int value) { return value * 2; });
+    REQUIRE(std::vector<int>(doubled.begin(), doubled.end()) == std::vector<int> { 6, 2, 4 });
+
+    vec_model->push_back(4);
+    REQUIRE(std::distance(vec_model->begin(), vec_model->end()) == 4);
+
+    vec_model->set_vector({ 5, 6 });
+    REQUIRE(vec_model->row_count() == 2);
+    REQUIRE(*vec_model->begin() == 5);
+
+    vec_model->clear();
+    REQUIRE(vec_model->begin() == vec_model->end());
+}
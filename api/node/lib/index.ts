@@ -22,11 +22,37 @@ let native = !process.env.SLINT_NODE_NATIVE_LIB ? require('../native/index.node'
 /**
  * @hidden
  */
-class Component {
+const EventEmitter = require('events');
+
+/**
+ * The suffix of the events emitted by a component when one of its properties changes.
+ * @hidden
+ */
+const CHANGED_EVENT_SUFFIX = '_changed';
+
+/**
+ * @hidden
+ */
+class Component extends EventEmitter {
     protected comp: any;
 
-    constructor(comp: any) {
+    constructor(comp: any, properties: string[] = []) {
+        super();
         this.comp = comp;
+        // Only observe the properties for which a listener is registered
+        let observed = new Set<string>();
+        this.on('newListener', (event: string | symbol) => {
+            if (typeof event !== 'string' || !event.endsWith(CHANGED_EVENT_SUFFIX)) {
+                return;
+            }
+            let name = event.slice(0, -CHANGED_EVENT_SUFFIX.length);
+            let property = properties.find((x) => x.replace(/-/g, '_') === name);
+            if (property === undefined || observed.has(property)) {
+                return;
+            }
+            observed.add(property);
+            comp.on_property_changed(property, (value: any) => { this.emit(event, value); });
+        });
     }
 
     run() {
@@ -123,12 +149,58 @@ interface Callback {
     setHandler(cb: any): void;
 }
 
+/**
+ * Wraps a callback handler so that it can be an async function: when the handler returns
+ * a Promise, errors are reported on the console instead of being lost, and nothing is returned
+ * to the run-time, which cannot wait for the result.
+ * The handler of a callback that returns a value cannot be async, since the run-time needs
+ * the value right away, so this throws a TypeError for such handlers.
+ * @hidden
+ */
+function wrapCallbackHandler(name: string, handler: any, returnsValue: boolean): any {
+    if (typeof handler !== 'function') {
+        return handler;
+    }
+    const asyncError = () => new TypeError(`The handler of callback ${name} cannot be async because the callback returns a value`);
+    if (returnsValue) {
+        if (Object.prototype.toString.call(handler) === '[object AsyncFunction]') {
+            throw asyncError();
+        }
+        return function () {
+            let result = handler(...arguments);
+            if (result instanceof Promise) {
+                throw asyncError();
+            }
+            return result;
+        };
+    }
+    return function () {
+        let result = handler(...arguments);
+        if (result instanceof Promise) {
+            result.catch((error) => { console.error(`Error in the handler of callback ${name}:`, error); });
+            return undefined;
+        }
+        return result;
+    };
+}
+
 require.extensions['.60'] = require.extensions['.slint'] =
     function (module, filename) {
         var c = native.load(filename);
         module.exports[c.name().replace(/-/g, '_')] = function (init_properties: any) {
+            let callbacks: string[] = c.callbacks();
+            let callbacks_with_return_value: string[] = c.callbacks_with_return_value();
+            if (init_properties) {
+                let props: any = {};
+                for (let key of Object.keys(init_properties)) {
+                    let is_callback = callbacks.some((x) => x.replace(/-/g, '_') === key.replace(/-/g, '_'));
+                    let returns_value = callbacks_with_return_value.some((x) => x.replace(/-/g, '_') === key.replace(/-/g, '_'));
+                    props[key] = is_callback ? wrapCallbackHandler(key, init_properties[key], returns_value) : init_properties[key];
+                }
+                init_properties = props;
+            }
             let comp = c.create(init_properties);
-            let ret = new Component(comp);
+            let ret = new Component(comp, c.properties());
             c.properties().forEach((x: string) => {
                 Object.defineProperty(ret, x.replace(/-/g, '_'), {
                     get() { return comp.get_property(x); },
@@ -136,11 +208,11 @@ require.extensions['.60'] = require.extensions['.slint'] =
                     enumerable: true,
                 })
             });
-            c.callbacks().forEach((x: string) => {
+            callbacks.forEach((x: string) => {
                 Object.defineProperty(ret, x.replace(/-/g, '_'), {
                     get() {
                         let callback = function () { return comp.invoke_callback(x, [...arguments]); } as Callback;
-                        callback.setHandler = function (callback) { comp.connect_callback(x, callback) };
+                        callback.setHandler = function (callback) { comp.connect_callback(x, wrapCallbackHandler(x, callback, callbacks_with_return_value.includes(x))) };
                         return callback;
                     },
                     enumerable: true,
@@ -227,7 +299,8 @@ class NullPeer implements ModelPeer {
 
 /**
  * ArrayModel wraps a JavaScript array for use in `.slint` views. The underlying
- * array can be modified with the [[ArrayModel.push]] and [[ArrayModel.remove]] methods.
+ * array can be modified with the [[ArrayModel.push]], [[ArrayModel.splice]] and
+ * [[ArrayModel.remove]] methods, which notify the run-time only about the rows that changed.
  */
 class ArrayModel<T> implements Model<T> {
     /**
@@ -266,18 +339,66 @@ class ArrayModel<T> implements Model<T> {
         Array.prototype.push.apply(this.a, values);
         this.notify.rowAdded(size, arguments.length);
     }
-    // FIXME: should this be named splice and have the splice api?
+    /**
+     * Removes the last value of the array that's backing the model and notifies
+     * the run-time about the removed row.
+     * @returns the removed value, or undefined if the model was empty.
+     */
+    pop(): T | undefined {
+        return this.splice(this.a.length - 1, 1)[0];
+    }
+    /**
+     * Removes the first value of the array that's backing the model and notifies
+     * the run-time about the removed row.
+     * @returns the removed value, or undefined if the model was empty.
+     */
+    shift(): T | undefined {
+        return this.splice(0, 1)[0];
+    }
+    /**
+     * Inserts new values at the beginning of the array that's backing the model and
+     * notifies the run-time about the added rows.
+     * @param values
+     */
+    unshift(...values: T[]) {
+        this.splice(0, 0, ...values);
+    }
+    /**
+     * Changes the array that's backing the model like Array.splice(): removes `deleteCount`
+     * values starting at `start` and inserts the given values in their place.
+     * The run-time is notified that the rows that were replaced changed, and about the rows
+     * that were added or removed, so the views of the other rows are kept.
+     * @param start the index at which to start changing the array. Negative values count from the end.
+     * @param deleteCount the number of values to remove. Removes everything after `start` if not specified.
+     * @param values the values to insert
+     * @returns an array containing the removed values.
+     */
+    splice(start: number, deleteCount?: number, ...values: T[]): T[] {
+        let length = this.a.length;
+        start = start < 0 ? Math.max(length + start, 0) : Math.min(start, length);
+        deleteCount = deleteCount === undefined ? length - start : Math.min(Math.max(deleteCount, 0), length - start);
+        let removed = this.a.splice(start, deleteCount, ...values);
+        let changed = Math.min(removed.length, values.length);
+        for (let row = start; row < start + changed; ++row) {
+            this.notify.rowDataChanged(row);
+        }
+        if (removed.length > changed) {
+            this.notify.rowRemoved(start + changed, removed.length - changed);
+        }
+        if (values.length > changed) {
+            this.notify.rowAdded(start + changed, values.length - changed);
+        }
+        return removed;
+    }
     /**
      * Removes the specified number of element from the array that's backing
      * the model, starting at the specified index. This is equivalent to calling
-     * Array.slice() on the array and notifying the run-time about the removed
-     * rows.
+     * [[ArrayModel.splice]] without values to insert.
      * @param index
      * @param size
      */
     remove(index: number, size: number) {
-        let r = this.a.splice(index, size);
-        this.notify.rowRemoved(index, size);
+        this.splice(index, size);
     }
 
     get length(): number {
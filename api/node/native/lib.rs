@@ -14,7 +14,11 @@
 mod persistent_context;
 
 struct WrappedComponentType(Option<slint_interpreter::ComponentDefinition>);
-struct WrappedComponentRc(Option<slint_interpreter::ComponentInstance>);
+/// The component instance, and the observers of the properties connected with `on_property_changed`
+struct WrappedComponentRc(
+    Option<slint_interpreter::ComponentInstance>,
+    Vec<slint_interpreter::PropertyObserver>,
+);
 struct WrappedWindow(Option<std::rc::Rc<dyn i_slint_core::window::WindowAdapter>>);
 
 /// We need to do some gymnastic with closures to pass the ExecuteContext with the right lifetime
@@ -254,7 +258,9 @@ fn to_js_value<'cx>(
             &ImageInner::EmbeddedImage { .. }
             | &ImageInner::StaticTextures { .. }
             | &ImageInner::Svg(..)
-            | &ImageInner::BackendStorage(..) => JsNull::new().as_value(cx), // TODO: maybe pass around node buffers?
+            | &ImageInner::BackendStorage(..)
+            | &ImageInner::BorrowedOpenGLTexture(..)
+            | &ImageInner::AnimatedImage(..) => JsNull::new().as_value(cx), // TODO: maybe pass around node buffers?
         },
         Value::Model(model) => {
             if let Some(js_model) = model.as_any().downcast_ref::<js_model::JsModel>() {
@@ -329,11 +335,25 @@ fn to_js_value<'cx>(
             }
             Ok(array.as_value(&mut cx))
         }
+        method callbacks_with_return_value(mut cx) {
+            let this = cx.this();
+            let ct = cx.borrow(&this, |x| x.0.clone());
+            let ct = ct.ok_or(()).or_else(|()| cx.throw_error("Invalid type"))?;
+            let callbacks = ct.properties_and_callbacks().filter(|(_, prop_type)| {
+                matches!(prop_type, Type::Callback { return_type: Some(ty), .. } if **ty != Type::Void)
+            });
+            let array = JsArray::new(&mut cx, 0);
+            for (len , (p, _)) in callbacks.enumerate() {
+                let prop_name = JsString::new(&mut cx, p);
+                array.set(&mut cx, len as u32, prop_name)?;
+            }
+            Ok(array.as_value(&mut cx))
+        }
     }
 
     class SlintComponent for WrappedComponentRc {
         init(_) {
-            Ok(WrappedComponentRc(None))
+            Ok(WrappedComponentRc(None, Vec::new()))
         }
         method run(mut cx) {
             let this = cx.this();
@@ -454,6 +474,27 @@ fn to_js_value<'cx>(
             }
         }
 
+        method on_property_changed(mut cx) {
+            let prop_name = cx.argument::<JsString>(0)?.value();
+            let handler = cx.argument::<JsFunction>(1)?;
+            let this = cx.this();
+            let persistent_context =
+                persistent_context::PersistentContext::from_object(&mut cx, this.downcast().unwrap())?;
+            let component = cx.borrow(&this, |x| x.0.as_ref().map(|c| c.clone_strong()));
+            let component = component.ok_or(()).or_else(|()| cx.throw_error("Invalid type"))?;
+            let handler = make_callback_handler(&mut cx, &persistent_context, handler, None);
+            let observer = component
+                .on_property_changed(prop_name.as_str(), move |value| {
+                    handler(core::slice::from_ref(value));
+                })
+                .or_else(|_| {
+                    cx.throw_error(format!("Property {} not found in the component", prop_name))
+                })?;
+            let mut this = this;
+            cx.borrow_mut(&mut this, |mut x| x.1.push(observer));
+            Ok(JsUndefined::new().as_value(&mut cx))
+        }
+
         method send_mouse_click(mut cx) {
             let x = cx.argument::<JsNumber>(0)?.value() as f32;
             let y = cx.argument::<JsNumber>(1)?.value() as f32;
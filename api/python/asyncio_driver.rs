@@ -0,0 +1,226 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+/*!
+Runs an asyncio event loop within the Slint event loop.
+
+An iteration of the asyncio loop is run from the Slint event loop when one of the file
+descriptors of the asyncio loop is ready, when its next timer expires, and when a task was
+created. A thread waits for the file descriptors with the file descriptor of the selector of the
+asyncio loop (epoll or kqueue), which becomes readable when one of them is ready.
+Threads that schedule callbacks with `call_soon_threadsafe()` wake up the asyncio loop with such a
+file descriptor.
+*/
+
+#![cfg_attr(not(unix), allow(dead_code))]
+
+use i_slint_core::timers::{Timer, TimerMode};
+use pyo3::exceptions::PyTypeError;
+use pyo3::prelude::*;
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::Duration;
+
+thread_local! {
+    /// The driver of the asyncio event loop passed to `run_event_loop()`
+    static DRIVER: RefCell<Option<Rc<AsyncioDriver>>> = RefCell::new(None);
+}
+
+#[derive(Default)]
+struct WatcherState {
+    /// The watcher thread triggered an iteration that didn't run yet
+    iteration_pending: bool,
+    stopped: bool,
+}
+
+pub struct AsyncioDriver {
+    asyncio_loop: PyObject,
+    /// Runs the iteration after the next asyncio timer expires, or after a task was created
+    timer: Timer,
+    watcher_state: Arc<(Mutex<WatcherState>, Condvar)>,
+    watcher: RefCell<Option<std::thread::JoinHandle<()>>>,
+    /// Written to stop the watcher thread
+    stop_write_fd: i32,
+    stop_read_fd: i32,
+}
+
+impl AsyncioDriver {
+    /// Creates the driver, and the thread waiting for the file descriptors of the asyncio loop.
+    #[cfg(unix)]
+    pub fn new(py: Python, asyncio_loop: PyObject) -> PyResult<Rc<Self>> {
+        let unsupported = |_| {
+            PyTypeError::new_err(
+                "the asyncio event loop must be a selector event loop with an epoll or kqueue selector, like the default event loop on Linux and macOS",
+            )
+        };
+        let loop_ref = asyncio_loop.as_ref(py);
+        // These are implementation details of asyncio.BaseEventLoop, used to know when the
+        // next iteration needs to run.
+        loop_ref.getattr("_ready").map_err(unsupported)?;
+        loop_ref.getattr("_scheduled").map_err(unsupported)?;
+        let loop_fd: i32 = loop_ref
+            .getattr("_selector")
+            .and_then(|selector| selector.call_method0("fileno"))
+            .and_then(|fd| fd.extract())
+            .map_err(unsupported)?;
+
+        let mut fds = [0; 2];
+        // Safety: fds has room for the two file descriptors
+        if unsafe { libc::pipe(fds.as_mut_ptr()) } != 0 {
+            return Err(pyo3::exceptions::PyOSError::new_err(
+                std::io::Error::last_os_error().to_string(),
+            ));
+        }
+
+        let driver = Rc::new(Self {
+            asyncio_loop,
+            timer: Default::default(),
+            watcher_state: Default::default(),
+            watcher: Default::default(),
+            stop_read_fd: fds[0],
+            stop_write_fd: fds[1],
+        });
+
+        let state = driver.watcher_state.clone();
+        let stop_read_fd = driver.stop_read_fd;
+        *driver.watcher.borrow_mut() = Some(std::thread::spawn(move || loop {
+            let mut fds = [
+                libc::pollfd { fd: loop_fd, events: libc::POLLIN, revents: 0 },
+                libc::pollfd { fd: stop_read_fd, events: libc::POLLIN, revents: 0 },
+            ];
+            // Safety: fds is a valid array of pollfd
+            unsafe { libc::poll(fds.as_mut_ptr(), fds.len() as _, -1) };
+            if fds[1].revents != 0 {
+                return;
+            }
+            if fds[0].revents == 0 {
+                // interrupted
+                continue;
+            }
+
+            let (lock, condvar) = &*state;
+            lock.lock().unwrap().iteration_pending = true;
+            let invoked = i_slint_core::api::invoke_from_event_loop(|| {
+                if let Some(driver) = current() {
+                    driver.run_iteration();
+                }
+            });
+            if invoked.is_err() {
+                return;
+            }
+            // The file descriptor remains readable until the iteration handled the events
+            let mut watcher_state = lock.lock().unwrap();
+            while watcher_state.iteration_pending && !watcher_state.stopped {
+                watcher_state = condvar.wait(watcher_state).unwrap();
+            }
+            if watcher_state.stopped {
+                return;
+            }
+        }));
+
+        // Run the callbacks that were already scheduled
+        driver.schedule_iteration(Duration::ZERO);
+        Ok(driver)
+    }
+
+    #[cfg(not(unix))]
+    pub fn new(_py: Python, _asyncio_loop: PyObject) -> PyResult<Rc<Self>> {
+        Err(PyTypeError::new_err(
+            "running an asyncio event loop is only supported on Linux and macOS",
+        ))
+    }
+
+    /// Runs a single iteration of the asyncio loop, and schedules the next one.
+    fn run_iteration(&self) {
+        Python::with_gil(|py| {
+            // Stopping the loop before running it runs a single iteration of it
+            let result = self
+                .asyncio_loop
+                .call_method0(py, "stop")
+                .and_then(|_| self.asyncio_loop.call_method0(py, "run_forever"));
+            if let Err(err) = result {
+                err.print(py);
+            }
+
+            match self.next_iteration_timeout(py) {
+                Ok(Some(timeout)) => self.schedule_iteration(timeout),
+                Ok(None) => self.timer.stop(),
+                Err(err) => err.print(py),
+            }
+        });
+
+        let (lock, condvar) = &*self.watcher_state;
+        lock.lock().unwrap().iteration_pending = false;
+        condvar.notify_all();
+    }
+
+    /// Returns when the next iteration needs to run for the callbacks and timers of the asyncio
+    /// loop, or None if it only waits for its file descriptors.
+    fn next_iteration_timeout(&self, py: Python) -> PyResult<Option<Duration>> {
+        let asyncio_loop = self.asyncio_loop.as_ref(py);
+        if asyncio_loop.getattr("_ready")?.len()? > 0 {
+            return Ok(Some(Duration::ZERO));
+        }
+        let scheduled = asyncio_loop.getattr("_scheduled")?;
+        if scheduled.len()? == 0 {
+            return Ok(None);
+        }
+        // _scheduled is a heap, the first timer expires first
+        let when: f64 = scheduled.get_item(0)?.call_method0("when")?.extract()?;
+        let now: f64 = asyncio_loop.call_method0("time")?.extract()?;
+        Ok(Some(Duration::from_secs_f64((when - now).max(0.))))
+    }
+
+    fn schedule_iteration(&self, timeout: Duration) {
+        self.timer.start(TimerMode::SingleShot, timeout, || {
+            if let Some(driver) = current() {
+                driver.run_iteration();
+            }
+        });
+    }
+
+    /// The asyncio loop, to schedule the tasks in
+    pub fn asyncio_loop(&self, py: Python) -> PyObject {
+        self.asyncio_loop.clone_ref(py)
+    }
+
+    /// Runs an iteration soon, for example for a task that was just created.
+    pub fn wake_up(&self) {
+        self.schedule_iteration(Duration::ZERO);
+    }
+}
+
+impl Drop for AsyncioDriver {
+    fn drop(&mut self) {
+        {
+            let (lock, condvar) = &*self.watcher_state;
+            lock.lock().unwrap().stopped = true;
+            condvar.notify_all();
+        }
+        #[cfg(unix)]
+        // Safety: writing one byte from a valid buffer to the pipe created in new()
+        unsafe {
+            libc::write(self.stop_write_fd, [0u8].as_ptr() as *const _, 1);
+        }
+        if let Some(watcher) = self.watcher.get_mut().take() {
+            let _ = watcher.join();
+        }
+        #[cfg(unix)]
+        // Safety: the file descriptors were created by pipe() and are not used anymore
+        unsafe {
+            libc::close(self.stop_read_fd);
+            libc::close(self.stop_write_fd);
+        }
+    }
+}
+
+/// Returns the driver of the asyncio loop passed to the running `run_event_loop()`
+pub fn current() -> Option<Rc<AsyncioDriver>> {
+    DRIVER.with(|driver| driver.borrow().clone())
+}
+
+/// Makes the driver current while the Slint event loop runs, and returns the previous one.
+pub fn set_current(driver: Option<Rc<AsyncioDriver>>) -> Option<Rc<AsyncioDriver>> {
+    DRIVER.with(|current| current.replace(driver))
+}
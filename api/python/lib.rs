@@ -0,0 +1,295 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+/*!
+Python module to load .slint files with the interpreter, and to run the components.
+
+The conversion between Python objects and the values of the properties is in the `value`
+module, the `ListModel` class that wraps a Python list in the `models` module, and the
+integration of asyncio in the `asyncio_driver` module.
+*/
+
+use i_slint_compiler::langtype::Type;
+use pyo3::exceptions::{PyAttributeError, PyException, PyRuntimeError, PyTypeError};
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyTuple};
+use slint_interpreter::{ComponentHandle, Value};
+
+mod asyncio_driver;
+mod models;
+mod value;
+
+pyo3::create_exception!(
+    slint,
+    CompileError,
+    PyException,
+    "Error raised when a .slint file fails to compile"
+);
+
+/// Loads the .slint file at the given path, and returns the definition of the last component
+/// of the file. Raises `CompileError` if the file has errors.
+///
+/// The include paths are used to look up the imported files, and the style selects the style of
+/// the widgets (for example "fluent" or "material").
+#[pyfunction]
+fn load_file(
+    path: std::path::PathBuf,
+    include_paths: Option<Vec<std::path::PathBuf>>,
+    style: Option<String>,
+) -> PyResult<ComponentDefinition> {
+    let mut compiler = slint_interpreter::ComponentCompiler::default();
+    if let Some(include_paths) = include_paths {
+        compiler.set_include_paths(include_paths);
+    }
+    if let Some(style) = style {
+        compiler.set_style(style);
+    }
+    let definition = spin_on::spin_on(compiler.build_from_path(&path));
+    slint_interpreter::print_diagnostics(compiler.diagnostics());
+    match definition {
+        Some(definition) => Ok(ComponentDefinition { definition }),
+        None => Err(CompileError::new_err(
+            compiler
+                .diagnostics()
+                .iter()
+                .filter(|d| d.level() == slint_interpreter::DiagnosticLevel::Error)
+                .map(|d| d.to_string())
+                .collect::<Vec<_>>()
+                .join("\n"),
+        )),
+    }
+}
+
+/// Runs the Slint event loop until the last window is closed or `quit_event_loop()` is called.
+///
+/// When an asyncio event loop is given, it runs within the Slint event loop: the handlers of the
+/// callbacks can then be coroutines, which are scheduled as tasks of that loop. This is supported
+/// with the default asyncio event loop on Linux and macOS.
+#[pyfunction]
+fn run_event_loop(py: Python, asyncio_loop: Option<PyObject>) -> PyResult<()> {
+    let driver = asyncio_loop
+        .map(|asyncio_loop| asyncio_driver::AsyncioDriver::new(py, asyncio_loop))
+        .transpose()?;
+    let previous_driver = asyncio_driver::set_current(driver);
+    py.allow_threads(slint_interpreter::run_event_loop);
+    // Stops the thread waiting for the asyncio loop
+    drop(asyncio_driver::set_current(previous_driver));
+    Ok(())
+}
+
+/// Stops the Slint event loop, `run_event_loop()` then returns.
+#[pyfunction]
+fn quit_event_loop() -> PyResult<()> {
+    i_slint_core::api::quit_event_loop()
+        .map_err(|e| PyRuntimeError::new_err(format!("cannot quit the event loop: {:?}", e)))
+}
+
+/// The definition of a component loaded from a .slint file, used to create instances of it.
+#[pyclass(unsendable, module = "slint")]
+struct ComponentDefinition {
+    definition: slint_interpreter::ComponentDefinition,
+}
+
+#[pymethods]
+impl ComponentDefinition {
+    /// The name of the component
+    #[getter]
+    fn name(&self) -> &str {
+        self.definition.name()
+    }
+
+    /// The names of the public properties
+    #[getter]
+    fn properties(&self) -> Vec<String> {
+        self.definition.properties().map(|(name, _)| name.replace('-', "_")).collect()
+    }
+
+    /// The names of the public callbacks
+    #[getter]
+    fn callbacks(&self) -> Vec<String> {
+        self.definition.callbacks().map(|name| name.replace('-', "_")).collect()
+    }
+
+    /// Creates a new instance of the component. The keyword arguments are the initial
+    /// values of the properties, or the handlers of the callbacks.
+    #[args(kwargs = "**")]
+    fn create(&self, kwargs: Option<&PyDict>) -> PyResult<ComponentInstance> {
+        let instance = ComponentInstance { instance: self.definition.create() };
+        for (name, value) in kwargs.into_iter().flatten() {
+            let name = name.extract::<&str>()?;
+            match instance.type_of(name)? {
+                Type::Callback { .. } => instance.set_callback(name, value.into())?,
+                _ => instance.set_property(name, value)?,
+            }
+        }
+        Ok(instance)
+    }
+}
+
+/// An instance of a component, with its properties and callbacks.
+///
+/// The properties are also accessible as attributes of the instance.
+#[pyclass(unsendable, module = "slint")]
+struct ComponentInstance {
+    instance: slint_interpreter::ComponentInstance,
+}
+
+impl ComponentInstance {
+    /// Returns the type of the public property or callback with the given name
+    fn type_of(&self, name: &str) -> PyResult<Type> {
+        let name = name.replace('_', "-");
+        self.instance
+            .definition()
+            .properties_and_callbacks()
+            .find_map(|(n, ty)| (n.replace('_', "-") == name).then(|| ty))
+            .ok_or_else(|| PyAttributeError::new_err(format!("no property or callback {}", name)))
+    }
+}
+
+#[pymethods]
+impl ComponentInstance {
+    /// Returns the value of the property with the given name
+    fn get_property(&self, py: Python, name: &str) -> PyResult<PyObject> {
+        let ty = self.type_of(name)?;
+        let value = self
+            .instance
+            .get_property(name)
+            .map_err(|_| PyAttributeError::new_err(format!("no property {}", name)))?;
+        Ok(value::to_py(py, value, &ty))
+    }
+
+    /// Sets the value of the property with the given name
+    fn set_property(&self, name: &str, value: &PyAny) -> PyResult<()> {
+        let ty = self.type_of(name)?;
+        let value = value::to_value(value, &ty)?;
+        self.instance
+            .set_property(name, value)
+            .map_err(|e| PyTypeError::new_err(format!("cannot set property {}: {}", name, e)))
+    }
+
+    /// Sets the handler of the callback with the given name.
+    ///
+    /// Exceptions raised by the handler are printed. When the handler returns a coroutine,
+    /// it is scheduled in the asyncio event loop given to `run_event_loop()`, and the callback
+    /// returns a default value.
+    fn set_callback(&self, name: &str, handler: PyObject) -> PyResult<()> {
+        let (arg_types, return_type) = match self.type_of(name)? {
+            Type::Callback { args, return_type } => (args, return_type),
+            _ => return Err(PyAttributeError::new_err(format!("{} is not a callback", name))),
+        };
+        self.instance
+            .set_callback(name, move |args| {
+                Python::with_gil(|py| {
+                    let args = PyTuple::new(
+                        py,
+                        args.iter().zip(&arg_types).map(|(a, ty)| value::to_py(py, a.clone(), ty)),
+                    );
+                    let result = handler.call1(py, args).and_then(|result| {
+                        let result = result.as_ref(py);
+                        if py
+                            .import("asyncio")?
+                            .call_method1("iscoroutine", (result,))?
+                            .is_true()?
+                        {
+                            schedule_coroutine(py, result)?;
+                            return Ok(Value::Void);
+                        }
+                        match &return_type {
+                            Some(return_type) => value::to_value(result, return_type),
+                            None => Ok(Value::Void),
+                        }
+                    });
+                    result.unwrap_or_else(|err| {
+                        err.print(py);
+                        Value::Void
+                    })
+                })
+            })
+            .map_err(|_| PyAttributeError::new_err(format!("no callback {}", name)))
+    }
+
+    /// Invokes the callback with the given name, and returns its result
+    #[args(args = "*")]
+    fn invoke(&self, py: Python, name: &str, args: &PyTuple) -> PyResult<PyObject> {
+        let (arg_types, return_type) = match self.type_of(name)? {
+            Type::Callback { args, return_type } => (args, return_type),
+            _ => return Err(PyAttributeError::new_err(format!("{} is not a callback", name))),
+        };
+        if args.len() != arg_types.len() {
+            return Err(PyTypeError::new_err(format!(
+                "{} expects {} arguments, but {} were given",
+                name,
+                arg_types.len(),
+                args.len()
+            )));
+        }
+        let args = args
+            .iter()
+            .zip(&arg_types)
+            .map(|(a, ty)| value::to_value(a, ty))
+            .collect::<PyResult<Vec<_>>>()?;
+        let result = self
+            .instance
+            .invoke_callback(name, &args)
+            .map_err(|e| PyTypeError::new_err(format!("cannot invoke {}: {}", name, e)))?;
+        Ok(value::to_py(py, result, &return_type.map_or(Type::Void, |ty| *ty)))
+    }
+
+    /// Shows the window of the component
+    fn show(&self) {
+        self.instance.show();
+    }
+
+    /// Hides the window of the component
+    fn hide(&self) {
+        self.instance.hide();
+    }
+
+    /// Shows the window, runs the event loop, and hides the window when the loop returns.
+    /// See `run_event_loop()` for the asyncio event loop argument.
+    fn run(&self, py: Python, asyncio_loop: Option<PyObject>) -> PyResult<()> {
+        self.instance.show();
+        let result = run_event_loop(py, asyncio_loop);
+        self.instance.hide();
+        result
+    }
+
+    fn __getattr__(&self, py: Python, name: &str) -> PyResult<PyObject> {
+        self.get_property(py, name)
+    }
+
+    fn __setattr__(&self, name: &str, value: &PyAny) -> PyResult<()> {
+        self.set_property(name, value)
+    }
+}
+
+/// Schedules the coroutine returned by a callback handler in the asyncio event loop
+fn schedule_coroutine(py: Python, coroutine: &PyAny) -> PyResult<()> {
+    match asyncio_driver::current() {
+        Some(driver) => {
+            driver.asyncio_loop(py).call_method1(py, "create_task", (coroutine,))?;
+            driver.wake_up();
+            Ok(())
+        }
+        None => {
+            // Close the coroutine to avoid a warning that it was never awaited
+            coroutine.call_method0("close")?;
+            Err(PyRuntimeError::new_err(
+                "callback handlers can only be coroutines when an asyncio event loop is passed to run_event_loop()",
+            ))
+        }
+    }
+}
+
+/// Python bindings for Slint: load .slint files with the interpreter and run the components.
+#[pymodule]
+fn slint(py: Python, m: &PyModule) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(load_file, m)?)?;
+    m.add_function(wrap_pyfunction!(run_event_loop, m)?)?;
+    m.add_function(wrap_pyfunction!(quit_event_loop, m)?)?;
+    m.add_class::<ComponentDefinition>()?;
+    m.add_class::<ComponentInstance>()?;
+    m.add_class::<models::PyListModel>()?;
+    m.add("CompileError", py.get_type::<CompileError>())?;
+    Ok(())
+}
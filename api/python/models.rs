@@ -0,0 +1,186 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+use i_slint_compiler::langtype::Type;
+use i_slint_core::model::{Model, ModelNotify, ModelTracker};
+use pyo3::exceptions::PyIndexError;
+use pyo3::prelude::*;
+use pyo3::types::PyList;
+use slint_interpreter::Value;
+use std::cell::RefCell;
+use std::rc::{Rc, Weak};
+
+/// Model backed by a Python list
+pub struct ListModel {
+    list: Py<PyList>,
+    notify: ModelNotify,
+    /// The type of the rows, known once the model is set on a property
+    data_type: RefCell<Type>,
+    self_weak: Weak<ListModel>,
+}
+
+impl ListModel {
+    fn new(list: Py<PyList>) -> Rc<Self> {
+        Rc::new_cyclic(|self_weak| Self {
+            list,
+            notify: Default::default(),
+            data_type: RefCell::new(Type::Invalid),
+            self_weak: self_weak.clone(),
+        })
+    }
+
+    pub fn set_data_type(&self, data_type: Type) {
+        *self.data_type.borrow_mut() = data_type;
+    }
+
+    pub fn as_rc(&self) -> Option<Rc<Self>> {
+        self.self_weak.upgrade()
+    }
+}
+
+impl Model for ListModel {
+    type Data = Value;
+
+    fn row_count(&self) -> usize {
+        Python::with_gil(|py| self.list.as_ref(py).len())
+    }
+
+    fn row_data(&self, row: usize) -> Option<Self::Data> {
+        Python::with_gil(|py| {
+            let item = self.list.as_ref(py).get_item(row).ok()?;
+            crate::value::to_value(item, &self.data_type.borrow()).map_err(|err| err.print(py)).ok()
+        })
+    }
+
+    fn set_row_data(&self, row: usize, data: Self::Data) {
+        Python::with_gil(|py| {
+            let data = crate::value::to_py(py, data, &self.data_type.borrow());
+            if let Err(err) = self.list.as_ref(py).set_item(row, data) {
+                err.print(py);
+                return;
+            }
+            self.notify.row_changed(row);
+        })
+    }
+
+    fn model_tracker(&self) -> &dyn ModelTracker {
+        &self.notify
+    }
+
+    fn as_any(&self) -> &dyn core::any::Any {
+        self
+    }
+}
+
+/// A model that wraps a Python list, to be set on the array properties of a component.
+///
+/// Modifying the model through its methods notifies the views that show it. Modifications
+/// of the wrapped list itself are not seen until the model is set again.
+#[pyclass(unsendable, name = "ListModel", module = "slint")]
+pub struct PyListModel {
+    pub model: Rc<ListModel>,
+}
+
+impl PyListModel {
+    fn list<'py>(&self, py: Python<'py>) -> &'py PyList {
+        self.model.list.as_ref(py)
+    }
+
+    /// Converts a Python index, which may be negative, to a row of the model
+    fn row(&self, py: Python, index: isize) -> PyResult<usize> {
+        let len = self.list(py).len() as isize;
+        let row = if index < 0 { index + len } else { index };
+        if row < 0 || row >= len {
+            return Err(PyIndexError::new_err("ListModel index out of range"));
+        }
+        Ok(row as usize)
+    }
+}
+
+#[pymethods]
+impl PyListModel {
+    #[new]
+    fn new(py: Python, items: Option<&PyAny>) -> PyResult<Self> {
+        let list = match items {
+            Some(items) => match items.downcast::<PyList>() {
+                Ok(list) => list.into(),
+                Err(_) => PyList::new(py, items.iter()?.collect::<PyResult<Vec<_>>>()?).into(),
+            },
+            None => PyList::empty(py).into(),
+        };
+        Ok(Self { model: ListModel::new(list) })
+    }
+
+    fn __len__(&self, py: Python) -> usize {
+        self.list(py).len()
+    }
+
+    fn __getitem__(&self, py: Python, index: isize) -> PyResult<PyObject> {
+        let row = self.row(py, index)?;
+        Ok(self.list(py).get_item(row)?.into())
+    }
+
+    fn __setitem__(&self, py: Python, index: isize, value: PyObject) -> PyResult<()> {
+        let row = self.row(py, index)?;
+        self.list(py).set_item(row, value)?;
+        self.model.notify.row_changed(row);
+        Ok(())
+    }
+
+    fn __delitem__(&self, py: Python, index: isize) -> PyResult<()> {
+        let row = self.row(py, index)?;
+        self.list(py).del_item(row)?;
+        self.model.notify.row_removed(row, 1);
+        Ok(())
+    }
+
+    fn __iter__(&self, py: Python) -> PyResult<PyObject> {
+        Ok(PyAny::iter(self.list(py))?.into())
+    }
+
+    /// Adds a row at the end of the model
+    fn append(&self, py: Python, value: PyObject) -> PyResult<()> {
+        let row = self.list(py).len();
+        self.list(py).append(value)?;
+        self.model.notify.row_added(row, 1);
+        Ok(())
+    }
+
+    /// Adds the items of the given iterable at the end of the model
+    fn extend(&self, py: Python, values: &PyAny) -> PyResult<()> {
+        let row = self.list(py).len();
+        let mut count = 0;
+        for value in values.iter()? {
+            self.list(py).append(value?)?;
+            count += 1;
+        }
+        self.model.notify.row_added(row, count);
+        Ok(())
+    }
+
+    /// Inserts a row before the given index, like `list.insert()`
+    fn insert(&self, py: Python, index: isize, value: PyObject) -> PyResult<()> {
+        let len = self.list(py).len() as isize;
+        let row = if index < 0 { (index + len).max(0) } else { index.min(len) } as usize;
+        self.list(py).insert(row, value)?;
+        self.model.notify.row_added(row, 1);
+        Ok(())
+    }
+
+    /// Removes and returns the row at the given index, the last one by default
+    fn pop(&self, py: Python, index: Option<isize>) -> PyResult<PyObject> {
+        let row = self.row(py, index.unwrap_or(-1))?;
+        let value: PyObject = self.list(py).get_item(row)?.into();
+        self.list(py).del_item(row)?;
+        self.model.notify.row_removed(row, 1);
+        Ok(value)
+    }
+
+    /// Removes all the rows
+    fn clear(&self, py: Python) -> PyResult<()> {
+        let count = self.list(py).len();
+        self.list(py).del_slice(0, count)?;
+        self.model.notify.row_removed(0, count);
+        Ok(())
+    }
+}
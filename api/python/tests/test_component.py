@@ -0,0 +1,72 @@
+# Copyright © SixtyFPS GmbH <info@slint-ui.com>
+# SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+import pytest
+import slint
+
+SOURCE = """
+export struct Person := { name: string, score: int }
+export TestCase := Rectangle {
+    property <int> counter: 42;
+    property <color> background-color: #ff0000;
+    property <[Person]> people;
+    property <int> people-count: people.length;
+    callback add(int, int) -> int;
+    callback clicked();
+}
+"""
+
+
+@pytest.fixture
+def definition(tmp_path):
+    path = tmp_path / "test.slint"
+    path.write_text(SOURCE)
+    return slint.load_file(path)
+
+
+def test_definition(definition):
+    assert definition.name == "TestCase"
+    assert sorted(definition.callbacks) == ["add", "clicked"]
+    assert "background_color" in definition.properties
+
+
+def test_compile_error(tmp_path):
+    path = tmp_path / "error.slint"
+    path.write_text("TestCase := Rectangle { property <int> x: \"string\"; }")
+    with pytest.raises(slint.CompileError):
+        slint.load_file(path)
+
+
+def test_properties(definition):
+    instance = definition.create(counter=1)
+    assert instance.counter == 1
+    instance.counter = 2
+    assert instance.get_property("counter") == 2
+    assert instance.background_color == "#ff0000ff"
+    instance.set_property("background-color", "blue")
+    assert instance.background_color == "#0000ffff"
+    with pytest.raises(AttributeError):
+        instance.no_such_property
+
+
+def test_callbacks(definition):
+    clicks = []
+    instance = definition.create(clicked=lambda: clicks.append(True))
+    instance.invoke("clicked")
+    assert clicks == [True]
+    instance.set_callback("add", lambda a, b: a + b)
+    assert instance.invoke("add", 1, 2) == 3
+
+
+def test_list_model(definition):
+    instance = definition.create()
+    instance.people = [{"name": "Olivier", "score": 1}]
+    assert instance.people_count == 1
+    model = slint.ListModel([{"name": "Simon", "score": 2}])
+    instance.people = model
+    assert instance.people_count == 1
+    model.append({"name": "Tobias", "score": 3})
+    assert instance.people_count == 2
+    assert instance.people[1]["name"] == "Tobias"
+    del model[0]
+    assert instance.people_count == 1
@@ -0,0 +1,133 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+//! Conversion between Python objects and the values of the interpreter, guided by the type of
+//! the property or of the callback argument.
+
+use i_slint_compiler::langtype::Type;
+use i_slint_core::model::{Model, ModelRc, SharedVectorModel};
+use pyo3::exceptions::{PyKeyError, PyTypeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList};
+use slint_interpreter::Value;
+
+use crate::models::{ListModel, PyListModel};
+
+/// Converts a Python object to a value of the given type
+pub fn to_value(obj: &PyAny, ty: &Type) -> PyResult<Value> {
+    Ok(match ty {
+        Type::Float32
+        | Type::Int32
+        | Type::Duration
+        | Type::Angle
+        | Type::PhysicalLength
+        | Type::LogicalLength
+        | Type::Percent
+        | Type::UnitProduct(_) => Value::Number(obj.extract::<f64>()?),
+        Type::String => Value::String(obj.extract::<&str>()?.into()),
+        Type::Bool => Value::Bool(obj.extract::<bool>()?),
+        Type::Color | Type::Brush => {
+            let c = obj
+                .extract::<&str>()?
+                .parse::<css_color_parser2::Color>()
+                .map_err(|e| PyValueError::new_err(e.to_string()))?;
+            i_slint_core::Color::from_argb_u8((c.a * 255.) as u8, c.r, c.g, c.b).into()
+        }
+        Type::Image => {
+            let path = obj.extract::<std::path::PathBuf>()?;
+            Value::Image(i_slint_core::graphics::Image::load_from_path(&path).map_err(|_| {
+                PyValueError::new_err(format!("cannot load image {}", path.display()))
+            })?)
+        }
+        Type::Array(data_type) => {
+            if let Ok(model) = obj.extract::<PyRef<PyListModel>>() {
+                model.model.set_data_type((**data_type).clone());
+                Value::Model(ModelRc::from(model.model.clone()))
+            } else {
+                let values = obj
+                    .iter()?
+                    .map(|item| to_value(item?, data_type))
+                    .collect::<PyResult<Vec<_>>>()?;
+                Value::Model(ModelRc::new(SharedVectorModel::from(
+                    values.into_iter().collect::<i_slint_core::SharedVector<_>>(),
+                )))
+            }
+        }
+        Type::Struct { fields, .. } => Value::Struct(
+            fields
+                .iter()
+                .map(|(name, field_type)| {
+                    let py_name = name.replace('-', "_");
+                    let field = match obj.downcast::<PyDict>() {
+                        Ok(dict) => dict.get_item(py_name.as_str()).ok_or_else(|| {
+                            PyKeyError::new_err(format!("missing field {}", py_name))
+                        })?,
+                        Err(_) => obj.getattr(py_name.as_str())?,
+                    };
+                    Ok((name.clone(), to_value(field, field_type)?))
+                })
+                .collect::<PyResult<_>>()?,
+        ),
+        Type::Enumeration(enumeration) => {
+            let value = obj.extract::<&str>()?.replace('_', "-");
+            if !enumeration.values.contains(&value) {
+                return Err(PyValueError::new_err(format!(
+                    "{} is not a value of {}",
+                    value, enumeration.name
+                )));
+            }
+            Value::EnumerationValue(enumeration.name.clone(), value)
+        }
+        Type::Void => Value::Void,
+        _ => {
+            return Err(PyTypeError::new_err(format!(
+                "cannot convert a Python object to a value of type {}",
+                ty
+            )))
+        }
+    })
+}
+
+/// Converts a value of the given type to a Python object
+pub fn to_py(py: Python, value: Value, ty: &Type) -> PyObject {
+    match (value, ty) {
+        (Value::Number(n), Type::Int32) => (n.round() as i64).into_py(py),
+        (Value::Number(n), _) => n.into_py(py),
+        (Value::String(s), _) => s.as_str().into_py(py),
+        (Value::Bool(b), _) => b.into_py(py),
+        (Value::Image(image), _) => image.path().map_or_else(|| py.None(), |path| path.into_py(py)),
+        (Value::Brush(brush), _) => {
+            let c = brush.color();
+            format!("#{:02x}{:02x}{:02x}{:02x}", c.red(), c.green(), c.blue(), c.alpha())
+                .into_py(py)
+        }
+        (Value::Model(model), _) => {
+            if let Some(list_model) = model.as_any().downcast_ref::<ListModel>() {
+                if let Some(list_model) = list_model.as_rc() {
+                    return PyListModel { model: list_model }.into_py(py);
+                }
+            }
+            let data_type = match ty {
+                Type::Array(data_type) => (**data_type).clone(),
+                _ => Type::Invalid,
+            };
+            PyList::new(py, model.iter().map(|row| to_py(py, row, &data_type)).collect::<Vec<_>>())
+                .into_py(py)
+        }
+        (Value::Struct(s), _) => {
+            let dict = PyDict::new(py);
+            for (name, field) in s.iter() {
+                let field_type = match ty {
+                    Type::Struct { fields, .. } => fields.get(name).cloned(),
+                    _ => None,
+                };
+                let field = to_py(py, field.clone(), &field_type.unwrap_or(Type::Invalid));
+                // Inserting a string key in a dict does not fail
+                dict.set_item(name.replace('-', "_"), field).unwrap();
+            }
+            dict.into_py(py)
+        }
+        (Value::EnumerationValue(_, value), _) => value.replace('-', "_").into_py(py),
+        _ => py.None(),
+    }
+}
@@ -40,6 +40,13 @@ fn main() {
     HelloWorld::new().run();
 }
 ```
+
+## Android
+
+When the crate is built for Android, the images and fonts are always embedded in the binary, since
+the paths of the build machine are not available on the device. See the
+[Slint on Android](https://docs.rs/slint/latest/slint/docs/android/index.html) documentation for
+how to package the application.
 */
 #![doc(html_logo_url = "https://slint-ui.com/logo/slint-logo-square-light.svg")]
 #![warn(missing_docs)]
@@ -50,6 +57,7 @@ fn main() {
     forward compatibility with future version of this crate"
 );
 
+use std::collections::HashMap;
 use std::env;
 use std::io::Write;
 use std::path::Path;
@@ -101,6 +109,24 @@ pub fn with_include_paths(self, include_paths: Vec<std::path::PathBuf>) -> Self
         Self { config }
     }
 
+    /// Create a new configuration that sets the library paths used for looking up the `.slint`
+    /// files imported with `@name`, by name of the library.
+    ///
+    /// The files imported with `@name/file.slint` are looked up in the directory of the library
+    /// `name`. A library path can also be a file, which is then imported with `@name`.
+    ///
+    /// ```ignore
+    /// let config = slint_build::CompilerConfiguration::new().with_library_paths(
+    ///     [("mylibrary".to_string(), "../mylibrary/ui".into())].into_iter().collect(),
+    /// );
+    /// ```
+    #[must_use]
+    pub fn with_library_paths(self, library_paths: HashMap<String, std::path::PathBuf>) -> Self {
+        let mut config = self.config;
+        config.library_paths = library_paths;
+        Self { config }
+    }
+
     /// Create a new configuration that selects the style to be used for widgets.
     #[must_use]
     pub fn with_style(self, style: String) -> Self {
@@ -109,6 +135,51 @@ pub fn with_style(self, style: String) -> Self {
         Self { config }
     }
 
+    /// Create a new configuration that selects the style used when the `native` style is selected.
+    ///
+    /// By default, the `native` style is only replaced when Qt is not available, by the builtin
+    /// style that looks the most like the native widgets of the target platform: `cupertino` on
+    /// macOS, `fluent` on Windows, and `material` on the other systems.
+    #[must_use]
+    pub fn with_native_style_fallback(self, style: String) -> Self {
+        let mut config = self.config;
+        config.native_style_fallback = Some(style);
+        Self { config }
+    }
+
+    /// Create a new configuration that sets the scale factor for which the images and the glyphs
+    /// are pre-rendered when they are embedded with [`EmbedResourcesKind::EmbedForSoftwareRenderer`].
+    ///
+    /// This overrides the `SLINT_SCALE_FACTOR` environment variable.
+    #[must_use]
+    pub fn with_scale_factor(self, scale_factor: f32) -> Self {
+        let mut config = self.config;
+        config.scale_factor = scale_factor as f64;
+        Self { config }
+    }
+
+    /// Create a new configuration that enables or disables the generation of the accessibility
+    /// information, which is enabled by default. It is always disabled with
+    /// [`EmbedResourcesKind::EmbedForSoftwareRenderer`].
+    #[must_use]
+    pub fn with_accessibility(self, enable: bool) -> Self {
+        let mut config = self.config;
+        config.accessibility = enable;
+        Self { config }
+    }
+
+    /// Create a new configuration that enables or disables the inlining of all the sub-components.
+    ///
+    /// This may help optimization to optimize the runtime resources usages, but at the cost of
+    /// much more generated code and binary size. This overrides the `SLINT_INLINING` environment
+    /// variable.
+    #[must_use]
+    pub fn with_inline_all_elements(self, enable: bool) -> Self {
+        let mut config = self.config;
+        config.inline_all_elements = enable;
+        Self { config }
+    }
+
     /// Selects how the resources such as images and font are processed.
     ///
     /// See [`EmbedResourcesKind`]
@@ -292,20 +363,39 @@ pub fn compile_with_config(
     let mut compiler_config = config.config;
     let mut rerun_if_changed = String::new();
 
+    // The files of the build machine are not available on an Android device, so they must be embedded
+    if std::env::var("CARGO_CFG_TARGET_OS").map_or(false, |os| os == "android")
+        && compiler_config.embed_resources
+            == i_slint_compiler::EmbedResourcesKind::OnlyBuiltinResources
+    {
+        compiler_config.embed_resources = i_slint_compiler::EmbedResourcesKind::EmbedAllResources;
+    }
+
+    let default_style = std::env::var_os("OUT_DIR").and_then(|path| {
+        // Same logic as in i-slint-backend-selector's build script to get the path
+        let path = Path::new(&path).parent()?.parent()?.join("SLINT_DEFAULT_STYLE.txt");
+        // unfortunately, if for some reason the file is changed by the i-slint-backend-selector's build script,
+        // it is changed after cargo decide to re-run this build script or not. So that means one will need two build
+        // to settle the right thing.
+        rerun_if_changed = format!("cargo:rerun-if-changed={}", path.display());
+        let style = std::fs::read_to_string(path).ok()?;
+        Some(style.trim().to_string())
+    });
+
+    // The default style is only `native` when Qt is available
+    if compiler_config.native_style_fallback.is_none()
+        && default_style.as_ref().map_or(false, |style| style != "native")
+    {
+        let target_os = std::env::var("CARGO_CFG_TARGET_OS").unwrap_or_default();
+        compiler_config.native_style_fallback =
+            Some(i_slint_compiler::native_style_lookalike(&target_os));
+    }
+
     if std::env::var_os("SLINT_STYLE").is_none()
         && std::env::var_os("SIXTYFPS_STYLE").is_none()
         && compiler_config.style.is_none()
     {
-        compiler_config.style = std::env::var_os("OUT_DIR").and_then(|path| {
-            // Same logic as in i-slint-backend-selector's build script to get the path
-            let path = Path::new(&path).parent()?.parent()?.join("SLINT_DEFAULT_STYLE.txt");
-            // unfortunately, if for some reason the file is changed by the i-slint-backend-selector's build script,
-            // it is changed after cargo decide to re-run this build script or not. So that means one will need two build
-            // to settle the right thing.
-            rerun_if_changed = format!("cargo:rerun-if-changed={}", path.display());
-            let style = std::fs::read_to_string(path).ok()?;
-            Some(style.trim().into())
-        });
+        compiler_config.style = default_style;
     }
 
     let syntax_node = syntax_node.expect("diags contained no compilation errors");
@@ -355,6 +445,7 @@ pub fn compile_with_config(
     }
     println!("cargo:rerun-if-env-changed=SLINT_STYLE");
     println!("cargo:rerun-if-env-changed=SIXTYFPS_STYLE");
+    println!("cargo:rerun-if-env-changed=SLINT_NATIVE_STYLE_FALLBACK");
     println!("cargo:rerun-if-env-changed=SLINT_FONT_SIZES");
     println!("cargo:rerun-if-env-changed=SLINT_SCALE_FACTOR");
     println!("cargo:rerun-if-env-changed=SLINT_ASSET_SECTION");
@@ -0,0 +1,107 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+//! Implementation of `#[derive(Struct)]`
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::spanned::Spanned;
+
+/// Returns the value of the `#[slint(target = "...")]` attribute, and whether there is a
+/// `#[slint(skip)]` attribute.
+fn parse_attributes(attrs: &[syn::Attribute]) -> syn::Result<(Option<syn::Path>, bool)> {
+    let mut target = None;
+    let mut skip = false;
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("slint")) {
+        let list = match attr.parse_meta()? {
+            syn::Meta::List(list) => list,
+            meta => return Err(syn::Error::new(meta.span(), "expected #[slint(...)]")),
+        };
+        for nested in list.nested {
+            match nested {
+                syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+                    path,
+                    lit: syn::Lit::Str(lit),
+                    ..
+                })) if path.is_ident("target") => target = Some(lit.parse()?),
+                syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("skip") => {
+                    skip = true
+                }
+                nested => {
+                    return Err(syn::Error::new(
+                        nested.span(),
+                        "unknown attribute, expected `target = \"...\"` or `skip`",
+                    ))
+                }
+            }
+        }
+    }
+    Ok((target, skip))
+}
+
+pub fn derive_struct(input: syn::DeriveInput) -> syn::Result<TokenStream> {
+    let name = &input.ident;
+    let target = match parse_attributes(&input.attrs)? {
+        (Some(target), false) => target,
+        _ => {
+            return Err(syn::Error::new(
+                name.span(),
+                "expected a #[slint(target = \"...\")] attribute with the path of the struct generated from the .slint code",
+            ))
+        }
+    };
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new(
+            input.generics.span(),
+            "#[derive(Struct)] doesn't support generic structs",
+        ));
+    }
+    let fields = match &input.data {
+        syn::Data::Struct(syn::DataStruct { fields: syn::Fields::Named(fields), .. }) => fields,
+        _ => {
+            return Err(syn::Error::new(
+                name.span(),
+                "#[derive(Struct)] only supports structs with named fields",
+            ))
+        }
+    };
+
+    let mut converted_fields = vec![];
+    let mut skipped_fields = vec![];
+    for field in &fields.named {
+        let (field_target, skip) = parse_attributes(&field.attrs)?;
+        if let Some(field_target) = field_target {
+            return Err(syn::Error::new(
+                field_target.span(),
+                "the `target` attribute is only allowed on the struct",
+            ));
+        }
+        if skip {
+            skipped_fields.push(field.ident.clone());
+        } else {
+            converted_fields.push(field.ident.clone());
+        }
+    }
+
+    // The fields of the target that have no counterpart in the Rust struct keep their default value.
+    Ok(quote! {
+        impl ::core::convert::From<#name> for #target {
+            #[allow(clippy::needless_update)]
+            fn from(value: #name) -> Self {
+                Self {
+                    #(#converted_fields: ::core::convert::Into::into(value.#converted_fields),)*
+                    ..::core::default::Default::default()
+                }
+            }
+        }
+
+        impl ::core::convert::From<#target> for #name {
+            fn from(value: #target) -> Self {
+                Self {
+                    #(#converted_fields: ::core::convert::Into::into(value.#converted_fields),)*
+                    #(#skipped_fields: ::core::default::Default::default(),)*
+                }
+            }
+        }
+    })
+}
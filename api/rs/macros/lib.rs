@@ -9,6 +9,8 @@
 extern crate proc_macro;
 use std::path::Path;
 
+mod derive_struct;
+
 use i_slint_compiler::diagnostics::BuildDiagnostics;
 use i_slint_compiler::parser::SyntaxKind;
 use i_slint_compiler::*;
@@ -329,38 +331,41 @@ pub fn slint(stream: TokenStream) -> TokenStream {
     let mut compiler_config =
         CompilerConfiguration::new(i_slint_compiler::generator::OutputFormat::Rust);
 
-    if std::env::var_os("SLINT_STYLE").is_none() && std::env::var_os("SIXTYFPS_STYLE").is_none() {
-        // This file is written by the i-slint-backend-selector's built script.
-        // It is in the target/xxx/build directory
-        let target_path = match std::env::var_os("OUT_DIR") {
-            Some(out_dir) => Some(
-                Path::new(&out_dir)
-                    .parent()
-                    .unwrap()
-                    .parent()
-                    .unwrap()
-                    .join("SLINT_DEFAULT_STYLE.txt"),
-            ),
-            None => {
-                // OUT_DIR is only defined when the crate having the macro has a build.rs script
-                // as a fallback, try to parse the rustc arguments
-                // https://stackoverflow.com/questions/60264534/getting-the-target-folder-from-inside-a-rust-proc-macro
-                let mut args = std::env::args();
-                let mut out_dir = None;
-                while let Some(arg) = args.next() {
-                    if arg == "--out-dir" {
-                        out_dir = args.next();
-                    }
+    // This file is written by the i-slint-backend-selector's built script.
+    // It is in the target/xxx/build directory
+    let target_path = match std::env::var_os("OUT_DIR") {
+        Some(out_dir) => Some(
+            Path::new(&out_dir).parent().unwrap().parent().unwrap().join("SLINT_DEFAULT_STYLE.txt"),
+        ),
+        None => {
+            // OUT_DIR is only defined when the crate having the macro has a build.rs script
+            // as a fallback, try to parse the rustc arguments
+            // https://stackoverflow.com/questions/60264534/getting-the-target-folder-from-inside-a-rust-proc-macro
+            let mut args = std::env::args();
+            let mut out_dir = None;
+            while let Some(arg) = args.next() {
+                if arg == "--out-dir" {
+                    out_dir = args.next();
                 }
-                out_dir.map(|out_dir| {
-                    Path::new(&out_dir).parent().unwrap().join("build/SLINT_DEFAULT_STYLE.txt")
-                })
             }
-        };
-        if let Some(target_path) = target_path {
-            compiler_config.style =
-                std::fs::read_to_string(target_path).map(|style| style.trim().into()).ok()
+            out_dir.map(|out_dir| {
+                Path::new(&out_dir).parent().unwrap().join("build/SLINT_DEFAULT_STYLE.txt")
+            })
         }
+    };
+    let default_style = target_path.and_then(|target_path| {
+        std::fs::read_to_string(target_path).map(|style| style.trim().to_string()).ok()
+    });
+
+    // The default style is only `native` when Qt is available. The target of the macro is not
+    // known here, so the lookalike of the host platform is used.
+    if default_style.as_ref().map_or(false, |style| style != "native") {
+        compiler_config.native_style_fallback =
+            Some(i_slint_compiler::native_style_lookalike(std::env::consts::OS));
+    }
+
+    if std::env::var_os("SLINT_STYLE").is_none() && std::env::var_os("SIXTYFPS_STYLE").is_none() {
+        compiler_config.style = default_style;
     }
 
     compiler_config.include_paths = include_paths;
@@ -387,3 +392,40 @@ pub fn slint(stream: TokenStream) -> TokenStream {
 
     result.into()
 }
+
+/// Derives the conversions, with the [`From`] trait, between a Rust struct and a struct declared in
+/// the `.slint` design markup, so that the application can use its own data types.
+///
+/// The path of the struct generated from the `.slint` code is given with the `#[slint(target = "...")]`
+/// attribute. Each field is converted with [`Into`] to the field of the same name, which must exist. The
+/// fields marked with `#[slint(skip)]` are not converted, and are set to their default value when
+/// converting from the generated struct. Likewise, the fields of the generated struct that the Rust
+/// struct doesn't have are set to their default value when converting to the generated struct.
+///
+/// To set the value of an array property, collect the converted items into a `ModelRc`.
+///
+/// ```rust,ignore
+/// slint::slint! {
+///     export struct Person := { name: string, age: int }
+///     MainWindow := Window { property <[Person]> people; }
+/// }
+///
+/// #[derive(slint::Struct)]
+/// #[slint(target = "Person")]
+/// struct Employee {
+///     name: String,
+///     age: i32,
+///     #[slint(skip)]
+///     salary: u32,
+/// }
+///
+/// let employees = vec![Employee { name: "Alice".into(), age: 42, salary: 1000 }];
+/// let window = MainWindow::new();
+/// window.set_people(employees.into_iter().map(Person::from).collect());
+/// let employees: Vec<Employee> = window.get_people().iter().map(Employee::from).collect();
+/// ```
+#[proc_macro_derive(Struct, attributes(slint))]
+pub fn derive_struct(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as syn::DeriveInput);
+    derive_struct::derive_struct(input).unwrap_or_else(|err| err.to_compile_error()).into()
+}
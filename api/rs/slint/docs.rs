@@ -86,6 +86,23 @@ pub fn new() -> Self {
             unimplemented!()
         }
 
+        /// Creates a new instance, with its own window, that shares the global singletons with `other`.
+        /// Use this to open secondary windows, such as tool palettes, that access the same globals.
+        ///
+        /// The new instance holds a reference to the globals, so `other` can be dropped before it.
+        pub fn new_sharing_globals_with(other: &Self) -> Self {
+            unimplemented!()
+        }
+
+        /// Returns a builder to create a new instance with initial values for its properties.
+        /// The values are set before the window is shown, so that it never shows the default values.
+        /// ```ignore
+        ///     let sample = SampleComponent::builder().counter(42).user_name("Anna".into()).build();
+        /// ```
+        pub fn builder() -> SampleComponentBuilder {
+            unimplemented!()
+        }
+
         /// A getter is generated for each property declared at the root of the component.
         /// In this case, this is the getter that returns the value of the `counter`
         /// property declared in the `.slint` design markup.
@@ -121,6 +138,45 @@ pub fn invoke_hello(&self) {}
         ///     });
         /// ```
         pub fn on_hello(&self, f: impl Fn() + 'static) {}
+        /// For each callback declared at the root of the component that doesn't return a value,
+        /// a function to register an async handler is also generated. The future returned by the
+        /// handler is spawned on the event loop with [`crate::spawn_local()`] each time the
+        /// callback is invoked.
+        /// ```ignore
+        ///     let sample = SampleComponent::new();
+        ///     let sample_weak = sample.as_weak();
+        ///     sample.on_hello_async(move || {
+        ///         let sample_weak = sample_weak.clone();
+        ///         async move {
+        ///             let counter = fetch_counter().await;
+        ///             sample_weak.unwrap().set_counter(counter);
+        ///         }
+        ///     });
+        /// ```
+        pub fn on_hello_async<Fut: core::future::Future<Output = ()> + 'static>(
+            &self,
+            f: impl FnMut() -> Fut + 'static,
+        ) {
+        }
+    }
+
+    /// The builder returned by [`SampleComponent::builder()`]. A function is generated for each
+    /// property declared at the root of the component, to set its initial value.
+    #[derive(Default)]
+    pub struct SampleComponentBuilder {}
+    impl SampleComponentBuilder {
+        /// Sets the initial value of the `counter` property.
+        pub fn counter(self, value: i32) -> Self {
+            unimplemented!()
+        }
+        /// Sets the initial value of the `user_name` property.
+        pub fn user_name(self, value: crate::SharedString) -> Self {
+            unimplemented!()
+        }
+        /// Creates the instance and sets the initial values of its properties.
+        pub fn build(self) -> SampleComponent {
+            unimplemented!()
+        }
     }
 
     impl ComponentHandle for SampleComponent {
@@ -191,3 +247,13 @@ mod slint {
         pub use crate::*;
     }
 }
+
+pub mod android {
+    #![doc = include_str!("android.md")]
+    #![doc = ""]
+}
+
+pub mod ios {
+    #![doc = include_str!("ios.md")]
+    #![doc = ""]
+}
@@ -22,6 +22,8 @@
  * [Positioning and Layout of Elements](docs::layouting)
  * [Debugging Techniques](docs::debugging_techniques)
  * [Slint on Microcontrollers](docs::mcu)
+ * [Slint on Android](docs::android)
+ * [Slint on iOS](docs::ios)
 
 ## How to use this crate:
 
@@ -164,6 +166,15 @@ struct MyComponent{ /*...*/ }
 You should perform the minimum amount of work in the main thread and delegate the actual logic to another
 thread to avoid blocking animations. Use the [`invoke_from_event_loop`] function to communicate from your worker thread to the UI thread.
 
+To set properties of a component from a worker thread, send a [`Weak`] handle to the thread and use
+[`Weak::upgrade_in_event_loop`]. [`Weak::call_in_event_loop`] also returns the result of the function, which the
+worker thread can wait for. The models can't be shared with other threads either: to modify a [`VecModel`] from a
+worker thread, create a [`VecModelHandle`] with [`VecModel::handle`] and send it to the thread.
+
+To run asynchronous code in the UI thread, for example to await a network request started from a callback, use
+[`spawn_local`], or register the callback handler with the `on_<callback>_async` function generated for the callbacks
+that don't return a value.
+
 To run a function with a delay or with an interval use a [`Timer`].
 
 ## Type Mappings
@@ -240,6 +251,7 @@ struct MyStruct {
 );
 
 pub use slint_macros::slint;
+pub use slint_macros::Struct;
 
 pub use i_slint_core::api::*;
 pub use i_slint_core::graphics::{
@@ -247,7 +259,7 @@ struct MyStruct {
 };
 pub use i_slint_core::model::{
     FilterModel, MapModel, Model, ModelExt, ModelNotify, ModelPeer, ModelRc, ModelTracker,
-    SortModel, StandardListViewItem, VecModel,
+    SortModel, StandardListViewItem, StandardTreeViewItem, VecModel, VecModelHandle,
 };
 pub use i_slint_core::sharedvector::SharedVector;
 pub use i_slint_core::timers::{Timer, TimerMode};
@@ -285,6 +297,49 @@ pub mod platform {
     pub use i_slint_core::platform::*;
 }
 
+/// This module contains functions to test the behavior of components in unit tests, without windowing system.
+///
+/// Call [`testing::init()`] at the beginning of each test, before creating any component. It installs a backend
+/// whose windows are not shown on any screen, and where time only passes when calling [`testing::mock_elapsed_time()`],
+/// so that animations and timers are deterministic. Then simulate the user input with the functions of this module,
+/// or dispatch any [`WindowEvent`] with [`Window::dispatch_event()`], and check the state of the component with
+/// its property getters. The state of the elements, such as the widgets, can also be read from their accessibility
+/// properties with [`testing::find_elements_by_accessible_label()`].
+///
+/// ```rust
+/// slint::slint! {
+///     Counter := Window {
+///         width: 100px;
+///         height: 100px;
+///         property <int> count;
+///         TouchArea { clicked => { count += 1; } }
+///     }
+/// }
+/// slint::testing::init();
+/// let counter = Counter::new();
+/// slint::testing::send_mouse_click(&counter, 5., 5.);
+/// assert_eq!(counter.get_count(), 1);
+/// ```
+///
+/// To compare the rendering of a component with a reference image, call [`testing::init_with_software_renderer()`]
+/// instead, and [`testing::assert_screenshot()`] with the path of the reference image. The window is rendered with
+/// the software renderer, so the text needs the glyphs to be embedded for the software renderer. The glyphs are
+/// taken from the fonts installed on the system, unless the `.slint` file imports the font files and sets the
+/// `default-font-family`: do that for the screenshots to be the same on all systems. Set the
+/// `SLINT_CREATE_SCREENSHOTS` environment variable to create or update the reference images.
+///
+/// This module is only available with the `testing` feature.
+#[cfg(feature = "testing")]
+pub mod testing {
+    pub use i_slint_backend_testing::{
+        assert_screenshot, compare_screenshot, find_elements_by_accessible_label, init,
+        init_with_software_renderer, mock_elapsed_time, render_screenshot, save_screenshot,
+        send_key_event, send_keyboard_string_sequence, send_mouse_click, send_touch_move,
+        send_touch_press, send_touch_release, set_window_scale_factor, window_is_shown,
+        ElementHandle, ScreenshotError, ScreenshotOptions,
+    };
+}
+
 /// Helper type that helps checking that the generated code is generated for the right version
 #[doc(hidden)]
 #[allow(non_camel_case_types)]
@@ -138,6 +138,13 @@ pub fn debug(s: SharedString) {
     }
 }
 
+/// Runs the future returned by the handler registered with `on_<callback>_async`.
+pub fn spawn_callback_future(future: impl core::future::Future<Output = ()> + 'static) {
+    if let Err(err) = i_slint_core::future::spawn_local(future) {
+        i_slint_core::debug_log!("Unable to run the async callback handler: {:?}", err);
+    }
+}
+
 /// Creates a new window to render components in.
 pub fn create_window_adapter() -> alloc::rc::Rc<dyn i_slint_core::window::WindowAdapter> {
     i_slint_backend_selector::with_platform(|b| b.create_window_adapter())
@@ -154,7 +161,7 @@ pub mod re_exports {
     pub use core::iter::FromIterator;
     pub use i_slint_backend_selector::native_widgets::*;
     pub use i_slint_core::accessibility::AccessibleStringProperty;
-    pub use i_slint_core::animations::{animation_tick, EasingCurve};
+    pub use i_slint_core::animations::{animation_tick, reduced_motion, EasingCurve};
     pub use i_slint_core::callbacks::Callback;
     pub use i_slint_core::component::{
         register_component, unregister_component, Component, ComponentRefPin, ComponentVTable,
@@ -174,7 +181,7 @@ pub mod re_exports {
     pub use i_slint_core::model::*;
     pub use i_slint_core::properties::{set_state_binding, Property, PropertyTracker, StateInfo};
     pub use i_slint_core::slice::Slice;
-    pub use i_slint_core::window::{WindowAdapter, WindowInner};
+    pub use i_slint_core::window::{PopupWindowOptions, WindowAdapter, WindowInner};
     pub use i_slint_core::Color;
     pub use i_slint_core::ComponentVTable_static;
     pub use i_slint_core::Coord;
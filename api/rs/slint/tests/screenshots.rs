@@ -0,0 +1,88 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+// The component has no text, so that its rendering doesn't depend on the fonts installed on the system
+slint::slint! {
+    Rectangles := Window {
+        width: 40px;
+        height: 30px;
+        background: white;
+        Rectangle { x: 10px; y: 5px; width: 20px; height: 10px; background: blue; }
+        Rectangle { x: 0px; y: 20px; width: 40px; height: 10px; background: green; }
+    }
+}
+
+#[test]
+fn compare_with_reference() {
+    slint::testing::init_with_software_renderer();
+    let reference = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/screenshots/rectangles.png");
+
+    let instance = Rectangles::new();
+    slint::testing::assert_screenshot(&instance, reference);
+
+    // Rendering with another scale factor doesn't change the scale factor of the window
+    let screenshot = slint::testing::render_screenshot(instance.window(), 2.).unwrap();
+    assert_eq!((screenshot.width(), screenshot.height()), (80, 60));
+    assert_eq!(instance.window().scale_factor(), 1.);
+}
+
+slint::slint! {
+    RoundedCorner := Window {
+        width: 40px;
+        height: 40px;
+        background: white;
+        Rectangle { background: blue; border-top-left-radius: 20px; }
+    }
+}
+
+#[test]
+fn per_corner_radius() {
+    slint::testing::init_with_software_renderer();
+    let instance = RoundedCorner::new();
+    let screenshot = slint::testing::render_screenshot(instance.window(), 1.).unwrap();
+    let pixel = |x: usize, y: usize| screenshot.as_slice()[y * screenshot.stride() as usize + x];
+    let white = slint::Rgb8Pixel { r: 255, g: 255, b: 255 };
+    let blue = slint::Rgb8Pixel { r: 0, g: 0, b: 255 };
+    // Only the top left corner is rounded
+    assert_eq!(pixel(0, 0), white);
+    assert_eq!(pixel(3, 3), white);
+    assert_eq!(pixel(39, 0), blue);
+    assert_eq!(pixel(39, 39), blue);
+    assert_eq!(pixel(0, 39), blue);
+    assert_eq!(pixel(20, 20), blue);
+}
+
+slint::slint! {
+    ToolTipWindow := Window {
+        width: 100px;
+        height: 100px;
+        background: white;
+        tooltip-background: red;
+        TouchArea { tooltip: "Hello"; }
+    }
+}
+
+#[test]
+fn tooltip() {
+    slint::testing::init_with_software_renderer();
+    let instance = ToolTipWindow::new();
+    let red = slint::Rgb8Pixel { r: 255, g: 0, b: 0 };
+    let pixel_below_pointer = || {
+        let screenshot = slint::testing::render_screenshot(instance.window(), 1.).unwrap();
+        // The tooltip is shown 20px below the pointer, with a 1px border and a 4px padding
+        screenshot.as_slice()[32 * screenshot.stride() as usize + 12]
+    };
+
+    instance.window().dispatch_event(slint::WindowEvent::PointerMoved {
+        position: slint::LogicalPosition::new(10., 10.),
+    });
+    assert_ne!(pixel_below_pointer(), red);
+
+    // The tooltip is shown after the pointer rests over the TouchArea
+    slint::testing::mock_elapsed_time(1000);
+    assert_eq!(pixel_below_pointer(), red);
+
+    // and hidden when the pointer leaves the window
+    instance.window().dispatch_event(slint::WindowEvent::PointerExited);
+    assert_ne!(pixel_below_pointer(), red);
+}
@@ -0,0 +1,163 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+use slint::platform::software_renderer::{
+    LineBufferProvider, MinimalSoftwareWindow, PhysicalRegion, SoftwareRenderer,
+};
+use slint::platform::{Platform, WindowAdapter};
+use slint::Rgb8Pixel;
+use std::rc::Rc;
+
+const WIDTH: usize = 100;
+const HEIGHT: usize = 60;
+
+slint::slint! {
+    ThreeRectangles := Window {
+        width: 100px;
+        height: 60px;
+        background: white;
+        property <color> top-left: blue;
+        property <color> top-right: blue;
+        property <color> bottom: blue;
+        Rectangle { x: 0px; y: 0px; width: 10px; height: 10px; background: top-left; }
+        Rectangle { x: 90px; y: 0px; width: 10px; height: 10px; background: top-right; }
+        Rectangle { x: 40px; y: 50px; width: 20px; height: 10px; background: bottom; }
+    }
+}
+
+struct TestPlatform {
+    window: Rc<MinimalSoftwareWindow<1>>,
+}
+
+impl Platform for TestPlatform {
+    fn create_window_adapter(&self) -> Rc<dyn WindowAdapter> {
+        self.window.clone()
+    }
+}
+
+const RED: Rgb8Pixel = Rgb8Pixel { r: 255, g: 0, b: 0 };
+const BLACK: Rgb8Pixel = Rgb8Pixel { r: 0, g: 0, b: 0 };
+
+/// Creates the component in a window of the software renderer, and renders its first frame
+fn init() -> (ThreeRectangles, Rc<MinimalSoftwareWindow<1>>) {
+    let window = MinimalSoftwareWindow::new();
+    slint::platform::set_platform(Box::new(TestPlatform { window: window.clone() })).unwrap();
+    let instance = ThreeRectangles::new();
+    instance.show();
+    let mut buffer = vec![BLACK; WIDTH * HEIGHT];
+    let region = draw(&window, |renderer| renderer.render(&mut buffer, WIDTH));
+    assert_eq!(rectangles(&region), vec![(0, 0, WIDTH as u32, HEIGHT as u32)]);
+    (instance, window)
+}
+
+fn draw(
+    window: &MinimalSoftwareWindow<1>,
+    render: impl FnOnce(&SoftwareRenderer<1>) -> PhysicalRegion,
+) -> PhysicalRegion {
+    window.request_redraw();
+    let mut region = None;
+    assert!(window.draw_if_needed(|renderer| region = Some(render(renderer))));
+    region.unwrap()
+}
+
+/// Returns the rectangles of the region as `(x, y, width, height)`, from left to right
+fn rectangles(region: &PhysicalRegion) -> Vec<(i32, i32, u32, u32)> {
+    let mut rectangles: Vec<_> =
+        region.iter().map(|(pos, size)| (pos.x, pos.y, size.width, size.height)).collect();
+    rectangles.sort();
+    rectangles
+}
+
+#[test]
+fn render_only_the_changed_rectangles() {
+    let (instance, window) = init();
+    instance.set_top_left(slint::Color::from_rgb_u8(255, 0, 0));
+    instance.set_bottom(slint::Color::from_rgb_u8(255, 0, 0));
+
+    let mut buffer = vec![BLACK; WIDTH * HEIGHT];
+    let region = draw(&window, |renderer| renderer.render(&mut buffer, WIDTH));
+    assert_eq!(rectangles(&region), vec![(0, 0, 10, 10), (40, 50, 20, 10)]);
+    assert_eq!(region.bounding_box_origin(), slint::PhysicalPosition::new(0, 0));
+    assert_eq!(region.bounding_box_size(), slint::PhysicalSize::new(60, 60));
+
+    let pixel = |x: usize, y: usize| buffer[y * WIDTH + x];
+    assert_eq!(pixel(0, 0), RED);
+    assert_eq!(pixel(9, 9), RED);
+    assert_eq!(pixel(59, 59), RED);
+    // The pixels between the rectangles of the region are not rendered
+    assert_eq!(pixel(10, 10), BLACK);
+    assert_eq!(pixel(30, 30), BLACK);
+    assert_eq!(pixel(95, 5), BLACK);
+
+    // Nothing changed
+    let region = draw(&window, |renderer| renderer.render(&mut buffer, WIDTH));
+    assert!(region.is_empty());
+}
+
+#[derive(Default)]
+struct RecordLines {
+    lines: Vec<(usize, core::ops::Range<usize>)>,
+    buffer: Vec<Rgb8Pixel>,
+}
+
+impl LineBufferProvider for &mut RecordLines {
+    type TargetPixel = Rgb8Pixel;
+    fn process_line(
+        &mut self,
+        line: usize,
+        range: core::ops::Range<usize>,
+        render_fn: impl FnOnce(&mut [Self::TargetPixel]),
+    ) {
+        self.lines.push((line, range.clone()));
+        render_fn(&mut self.buffer[line * WIDTH..][range]);
+    }
+}
+
+#[test]
+fn render_by_line_the_ranges_of_each_rectangle() {
+    let (instance, window) = init();
+    instance.set_top_left(slint::Color::from_rgb_u8(255, 0, 0));
+    instance.set_top_right(slint::Color::from_rgb_u8(255, 0, 0));
+
+    let mut lines = RecordLines { buffer: vec![BLACK; WIDTH * HEIGHT], ..Default::default() };
+    let region = draw(&window, |renderer| renderer.render_by_line(&mut lines));
+    assert_eq!(rectangles(&region), vec![(0, 0, 10, 10), (90, 0, 10, 10)]);
+
+    // Each line is rendered in two parts, from left to right
+    let expected: Vec<_> = (0..10).flat_map(|line| [(line, 0..10), (line, 90..100)]).collect();
+    assert_eq!(lines.lines, expected);
+    assert_eq!(lines.buffer[0], RED);
+    assert_eq!(lines.buffer[9 * WIDTH + 99], RED);
+    assert_eq!(lines.buffer[5 * WIDTH + 50], BLACK);
+}
+
+#[test]
+fn render_by_band_each_rectangle() {
+    let (instance, window) = init();
+    instance.set_top_left(slint::Color::from_rgb_u8(255, 0, 0));
+    instance.set_bottom(slint::Color::from_rgb_u8(255, 0, 0));
+
+    let mut band_buffer = vec![BLACK; WIDTH * 4];
+    let mut bands = Vec::new();
+    let region = draw(&window, |renderer| {
+        renderer.render_by_band(&mut band_buffer, WIDTH, |first_line, range, pixels| {
+            assert_eq!(pixels.len() % range.len(), 0);
+            assert!(pixels.iter().all(|pixel| *pixel == RED));
+            bands.push((first_line, range.clone(), pixels.len() / range.len()));
+        })
+    });
+    assert_eq!(rectangles(&region), vec![(0, 0, 10, 10), (40, 50, 20, 10)]);
+
+    // Bands of at most 4 lines, that don't span over both rectangles
+    assert_eq!(
+        bands,
+        vec![
+            (0, 0..10, 4),
+            (4, 0..10, 4),
+            (8, 0..10, 2),
+            (50, 40..60, 4),
+            (54, 40..60, 4),
+            (58, 40..60, 2),
+        ]
+    );
+}
@@ -0,0 +1,40 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+#[test]
+fn spawn_await_and_abort() {
+    slint::testing::init();
+
+    // Spawning from a thread that doesn't run the event loop fails
+    let result = std::thread::spawn(|| slint::spawn_local(async {}).is_err()).join().unwrap();
+    assert!(result);
+
+    let aborted_ran = Rc::new(Cell::new(false));
+    let aborted = slint::spawn_local({
+        let aborted_ran = aborted_ran.clone();
+        async move { aborted_ran.set(true) }
+    })
+    .unwrap();
+    aborted.abort();
+
+    let first = slint::spawn_local(async { 42 }).unwrap();
+    let result = Rc::new(Cell::new(0));
+    let second = slint::spawn_local({
+        let result = result.clone();
+        async move {
+            result.set(first.await + 1);
+            slint::quit_event_loop().unwrap();
+        }
+    })
+    .unwrap();
+
+    slint::run_event_loop();
+
+    assert_eq!(result.get(), 43);
+    assert!(second.is_finished());
+    assert!(!aborted_ran.get());
+    assert!(!aborted.is_finished());
+}
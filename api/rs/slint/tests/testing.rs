@@ -0,0 +1,64 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+use slint::testing::*;
+
+slint::slint! {
+    Test := Window {
+        width: 100px;
+        height: 100px;
+        property <int> clicks;
+        property <[string]> items: ["first", "second"];
+        Rectangle {
+            x: 10px;
+            y: 20px;
+            width: 30px;
+            height: 40px;
+            accessible-role: checkbox;
+            accessible-label: "check";
+            accessible-checked: clicks > 0;
+            TouchArea { clicked => { clicks += 1; } }
+        }
+        for item in items: Text {
+            y: 70px;
+            accessible-role: text;
+            accessible-label: "item";
+            accessible-value: item;
+        }
+    }
+}
+
+#[test]
+fn touch_and_element_state() {
+    init();
+    let instance = Test::new();
+
+    let check = find_elements_by_accessible_label(&instance, "check");
+    assert_eq!(check.len(), 1);
+    let check = &check[0];
+    assert_eq!(check.accessible_checked(), Some(false));
+    assert_eq!(check.absolute_position(), Some(slint::LogicalPosition::new(10., 20.)));
+    assert_eq!(check.size(), Some(slint::LogicalSize::new(30., 40.)));
+
+    // A touch that moves within the element is a click
+    send_touch_press(&instance, 1, 15., 25.);
+    send_touch_move(&instance, 1, 20., 30.);
+    send_touch_release(&instance, 1, 20., 30.);
+    assert_eq!(instance.get_clicks(), 1);
+    assert_eq!(check.accessible_checked(), Some(true));
+
+    // A touch outside of the element is not
+    send_touch_press(&instance, 2, 80., 80.);
+    send_touch_release(&instance, 2, 80., 80.);
+    assert_eq!(instance.get_clicks(), 1);
+
+    let items = find_elements_by_accessible_label(&instance, "item");
+    let values = items.iter().map(|item| item.accessible_value()).collect::<Vec<_>>();
+    assert_eq!(values, vec![Some("first".into()), Some("second".into())]);
+
+    // The elements of a repeater are destroyed when its model changes
+    instance.set_items(slint::ModelRc::new(slint::VecModel::from(vec!["third".into()])));
+    assert_eq!(find_elements_by_accessible_label(&instance, "item").len(), 1);
+    assert!(!items[1].is_valid());
+    assert_eq!(items[1].accessible_value(), None);
+}
@@ -0,0 +1,37 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+use slint::{Model, VecModel};
+use std::rc::Rc;
+
+#[test]
+fn modify_vec_model_from_thread() {
+    slint::testing::init();
+    let model = Rc::new(VecModel::from(vec![1, 2, 3]));
+    let handle = model.handle();
+    let thread = std::thread::spawn(move || {
+        handle.push(4).unwrap();
+        handle.remove(0).unwrap();
+        handle.set_row_data(0, 20).unwrap();
+        // Out of range indices are ignored
+        handle.insert(10, 5).unwrap();
+        handle.remove(10).unwrap();
+        handle.set_row_data(10, 5).unwrap();
+        handle.insert(0, 10).unwrap();
+        slint::quit_event_loop().unwrap();
+    });
+    slint::run_event_loop();
+    thread.join().unwrap();
+    assert_eq!(model.iter().collect::<Vec<_>>(), vec![10, 20, 3, 4]);
+
+    // The modifications of a dropped model are ignored
+    let handle = model.handle();
+    drop(model);
+    std::thread::spawn(move || {
+        handle.push(5).unwrap();
+        slint::quit_event_loop().unwrap();
+    })
+    .join()
+    .unwrap();
+    slint::run_event_loop();
+}
@@ -221,10 +221,11 @@ fn run_event_loop(&self) {
                             .map(|position| WindowEvent::PointerReleased { position, button })
                     })
                 {
+                    let is_release = matches!(event, WindowEvent::PointerReleased { .. });
                     window.dispatch_event(event);
 
                     // removes hover state on widgets
-                    if matches!(event, WindowEvent::PointerReleased { .. }) {
+                    if is_release {
                         window.dispatch_event(WindowEvent::PointerExited);
                     }
                     // Don't go to sleep after a touch event that forces a redraw
@@ -340,10 +340,11 @@ fn run_event_loop(&self) {
                 };
 
                 if let Some(event) = event {
+                    let is_release = matches!(event, slint::WindowEvent::PointerReleased { .. });
                     window.dispatch_event(event);
 
                     // removes hover state on widgets
-                    if matches!(event, slint::WindowEvent::PointerReleased { .. }) {
+                    if is_release {
                         window.dispatch_event(slint::WindowEvent::PointerExited);
                     }
                 }
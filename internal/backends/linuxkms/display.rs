@@ -0,0 +1,176 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+//! Presents the frames rendered by the software renderer on a KMS/DRM output
+
+use drm::buffer::DrmFourcc;
+use drm::control::{connector, crtc, dumbbuffer::DumbBuffer, framebuffer, Device as _, Mode};
+use i_slint_core::api::PhysicalSize;
+use i_slint_core::software_renderer::Bgra8888Pixel;
+use std::os::unix::io::{AsRawFd, RawFd};
+
+struct Card(std::fs::File);
+
+impl AsRawFd for Card {
+    fn as_raw_fd(&self) -> RawFd {
+        self.0.as_raw_fd()
+    }
+}
+
+impl drm::Device for Card {}
+impl drm::control::Device for Card {}
+
+struct Buffer {
+    dumb_buffer: DumbBuffer,
+    framebuffer: framebuffer::Handle,
+}
+
+/// The output on which the frames are presented, with the two buffers that are alternatively
+/// rendered into and scanned out.
+pub struct Display {
+    card: Card,
+    crtc: crtc::Handle,
+    connector: connector::Handle,
+    mode: Mode,
+    buffers: Vec<Buffer>,
+    /// The index of the buffer that is currently scanned out
+    front_buffer: usize,
+    /// Set once the mode was set, after which the buffers are presented with page flips
+    mode_set: bool,
+}
+
+impl Display {
+    /// Opens the device specified with the `SLINT_DRM_DEVICE` environment variable, or the first
+    /// card, and selects the first connected output with its preferred mode.
+    pub fn open() -> Result<Self, String> {
+        let path =
+            std::env::var("SLINT_DRM_DEVICE").unwrap_or_else(|_| String::from("/dev/dri/card0"));
+        let file = std::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(&path)
+            .map_err(|err| format!("error opening {}: {}", path, err))?;
+        let card = Card(file);
+
+        let resources = card
+            .resource_handles()
+            .map_err(|err| format!("error reading the resources of {}: {}", path, err))?;
+
+        let connector = resources
+            .connectors()
+            .iter()
+            .filter_map(|handle| card.get_connector(*handle).ok())
+            .find(|connector| connector.state() == connector::State::Connected)
+            .ok_or_else(|| format!("no connected output found on {}", path))?;
+
+        // The first mode is the preferred one
+        let mode = *connector
+            .modes()
+            .first()
+            .ok_or_else(|| format!("no mode available for the output {:?}", connector.handle()))?;
+
+        let crtc = connector
+            .current_encoder()
+            .and_then(|encoder| card.get_encoder(encoder).ok())
+            .and_then(|encoder| encoder.crtc())
+            .or_else(|| resources.crtcs().first().copied())
+            .ok_or_else(|| format!("no CRTC available on {}", path))?;
+
+        let (width, height) = mode.size();
+        let create_buffer = || -> Result<Buffer, String> {
+            let dumb_buffer = card
+                .create_dumb_buffer((width as u32, height as u32), DrmFourcc::Xrgb8888, 32)
+                .map_err(|err| format!("error creating a buffer: {}", err))?;
+            let framebuffer = card
+                .add_framebuffer(&dumb_buffer, 24, 32)
+                .map_err(|err| format!("error creating a frame buffer: {}", err))?;
+            Ok(Buffer { dumb_buffer, framebuffer })
+        };
+        let buffers = vec![create_buffer()?, create_buffer()?];
+
+        Ok(Self {
+            card,
+            crtc,
+            connector: connector.handle(),
+            mode,
+            buffers,
+            front_buffer: 0,
+            mode_set: false,
+        })
+    }
+
+    /// The size of the output, in pixels.
+    pub fn size(&self) -> PhysicalSize {
+        let (width, height) = self.mode.size();
+        PhysicalSize::new(width as u32, height as u32)
+    }
+
+    /// Calls `render` with the pixels and the stride of the back buffer, and shows it on the output.
+    ///
+    /// This waits until the new buffer is scanned out, so that the frames are presented at the
+    /// refresh rate of the output.
+    pub fn present(
+        &mut self,
+        render: impl FnOnce(&mut [Bgra8888Pixel], usize),
+    ) -> Result<(), String> {
+        let back_buffer = 1 - self.front_buffer;
+        let buffer = &mut self.buffers[back_buffer];
+        let stride = buffer.dumb_buffer.pitch() as usize / std::mem::size_of::<Bgra8888Pixel>();
+        {
+            let mut mapping = self
+                .card
+                .map_dumb_buffer(&mut buffer.dumb_buffer)
+                .map_err(|err| format!("error mapping the buffer: {}", err))?;
+            let bytes: &mut [u8] = mapping.as_mut();
+            // Safety: Bgra8888Pixel is a #[repr(C)] struct of four u8, which has the same layout
+            // as the XRGB8888 format of the buffer.
+            let pixels = unsafe {
+                std::slice::from_raw_parts_mut(
+                    bytes.as_mut_ptr() as *mut Bgra8888Pixel,
+                    bytes.len() / std::mem::size_of::<Bgra8888Pixel>(),
+                )
+            };
+            render(pixels, stride);
+        }
+
+        let framebuffer = buffer.framebuffer;
+        if self.mode_set {
+            self.card
+                .page_flip(
+                    self.crtc,
+                    framebuffer,
+                    &[drm::control::PageFlipFlags::PageFlipEvent],
+                    None,
+                )
+                .map_err(|err| format!("error flipping the buffers: {}", err))?;
+            // Wait for the page flip, after which the previous front buffer can be rendered into
+            'wait: loop {
+                for event in self
+                    .card
+                    .receive_events()
+                    .map_err(|err| format!("error receiving the page flip event: {}", err))?
+                {
+                    if let drm::control::Event::PageFlip(_) = event {
+                        break 'wait;
+                    }
+                }
+            }
+        } else {
+            self.card
+                .set_crtc(self.crtc, Some(framebuffer), (0, 0), &[self.connector], Some(self.mode))
+                .map_err(|err| format!("error setting the mode: {}", err))?;
+            self.mode_set = true;
+        }
+        self.front_buffer = back_buffer;
+        Ok(())
+    }
+}
+
+impl Drop for Display {
+    fn drop(&mut self) {
+        for buffer in self.buffers.drain(..) {
+            self.card.destroy_framebuffer(buffer.framebuffer).ok();
+            self.card.destroy_dumb_buffer(buffer.dumb_buffer).ok();
+        }
+    }
+}
@@ -0,0 +1,268 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+//! Reads the events of the input devices with libinput, and forwards them to the window
+
+use i_slint_core::api::{PhysicalPosition, PhysicalSize, WindowEvent};
+use i_slint_core::input::{key_codes, KeyEvent, KeyEventType, KeyboardModifiers};
+use i_slint_core::items::PointerEventButton;
+use i_slint_core::window::WindowInner;
+use i_slint_core::SharedString;
+use input::event::keyboard::{KeyState, KeyboardEvent, KeyboardEventTrait};
+use input::event::pointer::{Axis, ButtonState, PointerEvent};
+use input::event::touch::{TouchEvent, TouchEventPosition, TouchEventSlot};
+use input::{Libinput, LibinputInterface};
+use std::collections::HashMap;
+use std::os::unix::fs::OpenOptionsExt;
+use std::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd, RawFd};
+use std::path::Path;
+use xkbcommon::xkb;
+
+/// Opens the device files for libinput. This requires the permissions to read the
+/// `/dev/input/event*` files, for example by being in the `input` group.
+struct Interface;
+
+impl LibinputInterface for Interface {
+    fn open_restricted(&mut self, path: &Path, flags: i32) -> Result<RawFd, i32> {
+        let access_mode = flags & libc::O_ACCMODE;
+        std::fs::OpenOptions::new()
+            .custom_flags(flags & !libc::O_ACCMODE)
+            .read(access_mode == libc::O_RDONLY || access_mode == libc::O_RDWR)
+            .write(access_mode == libc::O_WRONLY || access_mode == libc::O_RDWR)
+            .open(path)
+            .map(|file| file.into_raw_fd())
+            .map_err(|err| err.raw_os_error().unwrap_or(libc::EIO))
+    }
+
+    fn close_restricted(&mut self, fd: RawFd) {
+        // Safety: the fd was opened by open_restricted
+        drop(unsafe { std::fs::File::from_raw_fd(fd) });
+    }
+}
+
+pub struct Input {
+    libinput: Libinput,
+    keyboard_state: xkb::State,
+    screen_size: PhysicalSize,
+    /// The position of the mouse pointer, in physical pixels
+    pointer_position: (f64, f64),
+    /// The last position of the touch points, by slot
+    touch_positions: HashMap<u32, (f64, f64)>,
+}
+
+impl Input {
+    pub fn new(screen_size: PhysicalSize) -> Result<Self, String> {
+        let mut libinput = Libinput::new_with_udev(Interface);
+        let seat = std::env::var("XDG_SEAT").unwrap_or_else(|_| String::from("seat0"));
+        libinput
+            .udev_assign_seat(&seat)
+            .map_err(|_| format!("error assigning the seat {}", seat))?;
+
+        let context = xkb::Context::new(xkb::CONTEXT_NO_FLAGS);
+        // Empty names select the default keymap, or the one from the XKB_DEFAULT_* environment variables
+        let keymap =
+            xkb::Keymap::new_from_names(&context, "", "", "", "", None, xkb::COMPILE_NO_FLAGS)
+                .ok_or_else(|| String::from("error loading the keymap"))?;
+
+        Ok(Self {
+            libinput,
+            keyboard_state: xkb::State::new(&keymap),
+            screen_size,
+            pointer_position: (screen_size.width as f64 / 2., screen_size.height as f64 / 2.),
+            touch_positions: Default::default(),
+        })
+    }
+
+    /// The file descriptor to poll to know when input events are available.
+    pub fn fd(&self) -> RawFd {
+        self.libinput.as_raw_fd()
+    }
+
+    /// Reads the pending input events and dispatches them to the window.
+    pub fn dispatch(&mut self, window: &i_slint_core::api::Window) {
+        if let Err(err) = self.libinput.dispatch() {
+            i_slint_core::debug_log!("slint linuxkms: error reading the input events: {}", err);
+            return;
+        }
+        let events: Vec<_> = self.libinput.by_ref().collect();
+        for event in events {
+            match event {
+                input::Event::Pointer(event) => self.handle_pointer_event(window, event),
+                input::Event::Touch(event) => self.handle_touch_event(window, event),
+                input::Event::Keyboard(event) => self.handle_keyboard_event(window, event),
+                _ => {}
+            }
+        }
+    }
+
+    fn logical_position(
+        &self,
+        window: &i_slint_core::api::Window,
+        (x, y): (f64, f64),
+    ) -> i_slint_core::api::LogicalPosition {
+        PhysicalPosition::new(x as i32, y as i32).to_logical(window.scale_factor())
+    }
+
+    fn handle_pointer_event(&mut self, window: &i_slint_core::api::Window, event: PointerEvent) {
+        let (width, height) = (self.screen_size.width as f64, self.screen_size.height as f64);
+        match event {
+            PointerEvent::Motion(motion) => {
+                let (x, y) = self.pointer_position;
+                self.pointer_position = (
+                    (x + motion.dx()).clamp(0., width - 1.),
+                    (y + motion.dy()).clamp(0., height - 1.),
+                );
+                let position = self.logical_position(window, self.pointer_position);
+                window.dispatch_event(WindowEvent::PointerMoved { position });
+            }
+            PointerEvent::MotionAbsolute(motion) => {
+                self.pointer_position = (
+                    motion.absolute_x_transformed(self.screen_size.width),
+                    motion.absolute_y_transformed(self.screen_size.height),
+                );
+                let position = self.logical_position(window, self.pointer_position);
+                window.dispatch_event(WindowEvent::PointerMoved { position });
+            }
+            PointerEvent::Button(button_event) => {
+                // The button codes are defined in linux/input-event-codes.h
+                const BTN_LEFT: u32 = 0x110;
+                const BTN_RIGHT: u32 = 0x111;
+                const BTN_MIDDLE: u32 = 0x112;
+                let button = match button_event.button() {
+                    BTN_LEFT => PointerEventButton::Left,
+                    BTN_RIGHT => PointerEventButton::Right,
+                    BTN_MIDDLE => PointerEventButton::Middle,
+                    _ => PointerEventButton::None,
+                };
+                let position = self.logical_position(window, self.pointer_position);
+                window.dispatch_event(match button_event.button_state() {
+                    ButtonState::Pressed => WindowEvent::PointerPressed { position, button },
+                    ButtonState::Released => WindowEvent::PointerReleased { position, button },
+                });
+            }
+            PointerEvent::Axis(axis_event) => {
+                let value = |axis| {
+                    if axis_event.has_axis(axis) {
+                        axis_event.axis_value(axis) as f32
+                    } else {
+                        0.
+                    }
+                };
+                let position = self.logical_position(window, self.pointer_position);
+                // libinput scrolls the content in the opposite direction than Slint
+                window.dispatch_event(WindowEvent::PointerScrolled {
+                    position,
+                    delta_x: -value(Axis::Horizontal),
+                    delta_y: -value(Axis::Vertical),
+                });
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_touch_event(&mut self, window: &i_slint_core::api::Window, event: TouchEvent) {
+        let (width, height) = (self.screen_size.width, self.screen_size.height);
+        match event {
+            TouchEvent::Down(down) => {
+                let physical_position = (down.x_transformed(width), down.y_transformed(height));
+                self.touch_positions.insert(down.seat_slot(), physical_position);
+                let position = self.logical_position(window, physical_position);
+                window.dispatch_event(WindowEvent::TouchPressed {
+                    id: down.seat_slot() as u64,
+                    position,
+                });
+            }
+            TouchEvent::Motion(motion) => {
+                let physical_position = (motion.x_transformed(width), motion.y_transformed(height));
+                self.touch_positions.insert(motion.seat_slot(), physical_position);
+                let position = self.logical_position(window, physical_position);
+                window.dispatch_event(WindowEvent::TouchMoved {
+                    id: motion.seat_slot() as u64,
+                    position,
+                });
+            }
+            TouchEvent::Up(up) => {
+                // libinput doesn't provide the position of a released touch point, use its last position
+                let physical_position =
+                    self.touch_positions.remove(&up.seat_slot()).unwrap_or(self.pointer_position);
+                let position = self.logical_position(window, physical_position);
+                window.dispatch_event(WindowEvent::TouchReleased {
+                    id: up.seat_slot() as u64,
+                    position,
+                });
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_keyboard_event(&mut self, window: &i_slint_core::api::Window, event: KeyboardEvent) {
+        let key_event = match event {
+            KeyboardEvent::Key(key_event) => key_event,
+            #[allow(unreachable_patterns)]
+            _ => return,
+        };
+        // The xkb key codes are offset by 8 from the evdev key codes
+        let keycode = key_event.key() + 8;
+        let (event_type, direction) = match key_event.key_state() {
+            KeyState::Pressed => (KeyEventType::KeyPressed, xkb::KeyDirection::Down),
+            KeyState::Released => (KeyEventType::KeyReleased, xkb::KeyDirection::Up),
+        };
+
+        let text = keysym_to_special_key(self.keyboard_state.key_get_one_sym(keycode))
+            .map(|key| SharedString::from(String::from(key)))
+            .unwrap_or_else(|| self.keyboard_state.key_get_utf8(keycode).into());
+
+        let modifier =
+            |name| self.keyboard_state.mod_name_is_active(name, xkb::STATE_MODS_EFFECTIVE);
+        let modifiers = KeyboardModifiers {
+            alt: modifier(xkb::MOD_NAME_ALT),
+            control: modifier(xkb::MOD_NAME_CTRL),
+            meta: modifier(xkb::MOD_NAME_LOGO),
+            shift: modifier(xkb::MOD_NAME_SHIFT),
+        };
+
+        self.keyboard_state.update_key(keycode, direction);
+
+        if !text.is_empty() {
+            let event = KeyEvent { event_type, text, modifiers, ..Default::default() };
+            WindowInner::from_pub(window).process_key_input(&event);
+        }
+    }
+}
+
+/// Returns the Slint representation of the keys that don't produce text
+fn keysym_to_special_key(keysym: xkb::Keysym) -> Option<char> {
+    use xkb::keysyms::*;
+    let key = match keysym {
+        KEY_BackSpace => key_codes::Backspace,
+        KEY_Tab => key_codes::Tab,
+        KEY_ISO_Left_Tab => key_codes::Backtab,
+        KEY_Return | KEY_KP_Enter => key_codes::Return,
+        KEY_Escape => key_codes::Escape,
+        KEY_Delete | KEY_KP_Delete => key_codes::Delete,
+        KEY_Up | KEY_KP_Up => key_codes::UpArrow,
+        KEY_Down | KEY_KP_Down => key_codes::DownArrow,
+        KEY_Left | KEY_KP_Left => key_codes::LeftArrow,
+        KEY_Right | KEY_KP_Right => key_codes::RightArrow,
+        KEY_Home | KEY_KP_Home => key_codes::Home,
+        KEY_End | KEY_KP_End => key_codes::End,
+        KEY_Page_Up | KEY_KP_Page_Up => key_codes::PageUp,
+        KEY_Page_Down | KEY_KP_Page_Down => key_codes::PageDown,
+        KEY_Insert | KEY_KP_Insert => key_codes::Insert,
+        KEY_F1 => key_codes::F1,
+        KEY_F2 => key_codes::F2,
+        KEY_F3 => key_codes::F3,
+        KEY_F4 => key_codes::F4,
+        KEY_F5 => key_codes::F5,
+        KEY_F6 => key_codes::F6,
+        KEY_F7 => key_codes::F7,
+        KEY_F8 => key_codes::F8,
+        KEY_F9 => key_codes::F9,
+        KEY_F10 => key_codes::F10,
+        KEY_F11 => key_codes::F11,
+        KEY_F12 => key_codes::F12,
+        KEY_Menu => key_codes::Menu,
+        _ => return None,
+    };
+    Some(key)
+}
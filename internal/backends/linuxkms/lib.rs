@@ -0,0 +1,293 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+/*!
+This backend renders Slint applications full-screen on a display driven by the Linux kernel
+mode setting (KMS/DRM) API, without Wayland or X11, and reads the input events of the touch
+screens, mice and keyboards with libinput.
+
+The rendering is done with the software renderer into double buffered dumb buffers. Only one
+window is shown: the last one that was created.
+
+The following environment variables are used:
+ * `SLINT_DRM_DEVICE`: the DRM device to open, `/dev/dri/card0` by default.
+ * `XKB_DEFAULT_LAYOUT`, `XKB_DEFAULT_VARIANT`, ...: the keyboard layout.
+*/
+
+#![doc(html_logo_url = "https://slint-ui.com/logo/slint-logo-square-light.svg")]
+#![cfg(target_os = "linux")]
+
+mod display;
+mod input;
+
+use i_slint_core::api::EventLoopError;
+use i_slint_core::platform::{EventLoopProxy, Platform, WindowAdapter};
+use i_slint_core::renderer::Renderer;
+use i_slint_core::software_renderer::SoftwareRenderer;
+use i_slint_core::window::WindowAdapterSealed;
+use std::cell::{Cell, RefCell};
+use std::collections::VecDeque;
+use std::os::unix::io::RawFd;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+
+#[doc(hidden)]
+pub fn use_modules() {}
+
+pub type NativeWidgets = ();
+pub type NativeGlobals = ();
+pub const HAS_NATIVE_STYLE: bool = false;
+pub mod native_widgets {}
+
+/// The window shown full-screen on the display.
+struct Window {
+    window: i_slint_core::api::Window,
+    renderer: SoftwareRenderer<2>,
+    needs_redraw: Cell<bool>,
+    shown: Cell<bool>,
+}
+
+impl Window {
+    fn new() -> Rc<Self> {
+        Rc::new_cyclic(|self_weak| Self {
+            window: i_slint_core::api::Window::new(self_weak.clone() as _),
+            renderer: SoftwareRenderer::new(self_weak.clone() as _),
+            needs_redraw: Default::default(),
+            shown: Default::default(),
+        })
+    }
+
+    /// Calls the callback with the renderer if the window needs to be redrawn.
+    fn draw_if_needed(&self, render_callback: impl FnOnce(&SoftwareRenderer<2>)) {
+        if self.needs_redraw.replace(false) {
+            render_callback(&self.renderer);
+        }
+    }
+}
+
+impl WindowAdapterSealed for Window {
+    fn show(&self) {
+        self.shown.set(true);
+        self.needs_redraw.set(true);
+    }
+
+    fn hide(&self) {
+        self.shown.set(false);
+    }
+
+    fn request_redraw(&self) {
+        self.needs_redraw.set(true);
+    }
+
+    fn renderer(&self) -> &dyn Renderer {
+        &self.renderer
+    }
+}
+
+impl WindowAdapter for Window {
+    fn window(&self) -> &i_slint_core::api::Window {
+        &self.window
+    }
+}
+
+impl core::ops::Deref for Window {
+    type Target = i_slint_core::api::Window;
+    fn deref(&self) -> &Self::Target {
+        &self.window
+    }
+}
+
+enum UserEvent {
+    Quit,
+    Invoke(Box<dyn FnOnce() + Send>),
+}
+
+/// The events sent from other threads, and the pipe used to wake up the event loop.
+struct UserEventQueue {
+    events: Mutex<VecDeque<UserEvent>>,
+    wake_read_fd: RawFd,
+    wake_write_fd: RawFd,
+}
+
+impl UserEventQueue {
+    fn new() -> Self {
+        let mut fds = [0 as RawFd; 2];
+        // Safety: fds has room for the two file descriptors
+        let result = unsafe { libc::pipe2(fds.as_mut_ptr(), libc::O_CLOEXEC | libc::O_NONBLOCK) };
+        if result != 0 {
+            panic!("slint linuxkms: could not create a pipe: {}", std::io::Error::last_os_error());
+        }
+        Self { events: Default::default(), wake_read_fd: fds[0], wake_write_fd: fds[1] }
+    }
+
+    fn send(&self, event: UserEvent) -> Result<(), EventLoopError> {
+        self.events.lock().map_err(|_| EventLoopError::EventLoopTerminated)?.push_back(event);
+        // Safety: writing one byte from a valid buffer to the pipe
+        unsafe { libc::write(self.wake_write_fd, [0u8].as_ptr() as *const _, 1) };
+        Ok(())
+    }
+
+    fn take_events(&self) -> VecDeque<UserEvent> {
+        let mut buffer = [0u8; 64];
+        // Safety: reading in a valid buffer from the non-blocking pipe
+        while unsafe { libc::read(self.wake_read_fd, buffer.as_mut_ptr() as *mut _, buffer.len()) }
+            > 0
+        {}
+        self.events.lock().map(|mut events| std::mem::take(&mut *events)).unwrap_or_default()
+    }
+}
+
+impl Drop for UserEventQueue {
+    fn drop(&mut self) {
+        // Safety: the file descriptors were created by pipe2 and are not used anymore
+        unsafe {
+            libc::close(self.wake_read_fd);
+            libc::close(self.wake_write_fd);
+        }
+    }
+}
+
+struct Proxy(Arc<UserEventQueue>);
+
+impl EventLoopProxy for Proxy {
+    fn quit_event_loop(&self) -> Result<(), EventLoopError> {
+        self.0.send(UserEvent::Quit)
+    }
+
+    fn invoke_from_event_loop(
+        &self,
+        event: Box<dyn FnOnce() + Send>,
+    ) -> Result<(), EventLoopError> {
+        self.0.send(UserEvent::Invoke(event))
+    }
+}
+
+pub struct Backend {
+    window: RefCell<Option<Rc<Window>>>,
+    user_events: Arc<UserEventQueue>,
+}
+
+impl Backend {
+    pub fn new() -> Self {
+        Self { window: Default::default(), user_events: Arc::new(UserEventQueue::new()) }
+    }
+
+    /// The window, if it is shown.
+    fn shown_window(&self) -> Option<Rc<Window>> {
+        self.window.borrow().clone().filter(|window| window.shown.get())
+    }
+}
+
+impl Default for Backend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Platform for Backend {
+    fn create_window_adapter(&self) -> Rc<dyn WindowAdapter> {
+        let window = Window::new();
+        self.window.replace(Some(window.clone()));
+        window
+    }
+
+    fn run_event_loop(&self) {
+        if self.shown_window().is_none() {
+            return;
+        }
+        let mut display = display::Display::open()
+            .unwrap_or_else(|err| panic!("slint linuxkms: could not open the display: {}", err));
+        let mut input = input::Input::new(display.size())
+            .unwrap_or_else(|err| panic!("slint linuxkms: could not read the input: {}", err));
+
+        loop {
+            i_slint_core::platform::update_timers_and_animations();
+
+            for event in self.user_events.take_events() {
+                match event {
+                    UserEvent::Quit => return,
+                    UserEvent::Invoke(f) => f(),
+                }
+            }
+
+            let window = match self.shown_window() {
+                Some(window) => window,
+                None => return,
+            };
+            if window.size() != display.size() {
+                window.set_size(display.size());
+            }
+
+            window.draw_if_needed(|renderer| {
+                // Presenting waits for the page flip, which throttles the animations to the refresh rate.
+                if let Err(err) = display.present(|buffer, stride| {
+                    renderer.render(buffer, stride);
+                }) {
+                    i_slint_core::debug_log!("slint linuxkms: error presenting the frame: {}", err);
+                }
+            });
+
+            let timeout = if window.has_active_animations() {
+                Some(std::time::Duration::ZERO)
+            } else {
+                i_slint_core::platform::duration_until_next_timer_update()
+            };
+
+            let mut fds = [
+                libc::pollfd { fd: input.fd(), events: libc::POLLIN, revents: 0 },
+                libc::pollfd {
+                    fd: self.user_events.wake_read_fd,
+                    events: libc::POLLIN,
+                    revents: 0,
+                },
+            ];
+            // round up, to not wake up just before the timer expires
+            let timeout_ms = timeout.map_or(-1, |timeout| {
+                ((timeout.as_micros() + 999) / 1000).min(i32::MAX as u128) as i32
+            });
+            // Safety: fds is a valid array of pollfd
+            unsafe { libc::poll(fds.as_mut_ptr(), fds.len() as _, timeout_ms) };
+
+            input.dispatch(&window);
+        }
+    }
+
+    fn new_event_loop_proxy(&self) -> Option<Box<dyn EventLoopProxy>> {
+        Some(Box::new(Proxy(self.user_events.clone())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn user_events_wake_up_the_event_loop() {
+        let queue = Arc::new(UserEventQueue::new());
+        let proxy = Proxy(queue.clone());
+        std::thread::spawn(move || proxy.invoke_from_event_loop(Box::new(|| ())).unwrap())
+            .join()
+            .unwrap();
+
+        let mut fd = libc::pollfd { fd: queue.wake_read_fd, events: libc::POLLIN, revents: 0 };
+        // Safety: fd is a valid pollfd
+        assert_eq!(unsafe { libc::poll(&mut fd, 1, 0) }, 1);
+        let events = queue.take_events();
+        assert_eq!(events.len(), 1);
+        assert!(matches!(events.front(), Some(UserEvent::Invoke(_))));
+        // Safety: fd is a valid pollfd
+        assert_eq!(unsafe { libc::poll(&mut fd, 1, 0) }, 0);
+    }
+
+    #[test]
+    fn event_loop_returns_when_the_window_is_hidden() {
+        let backend = Backend::new();
+        let window = backend.create_window_adapter();
+        window.show();
+        assert!(backend.shown_window().is_some());
+        window.hide();
+        assert!(backend.shown_window().is_none());
+        // Returns without opening the display
+        backend.run_event_loop();
+    }
+}
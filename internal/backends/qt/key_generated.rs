@@ -3,7 +3,7 @@
 
 /*! Generated with Qt5 and
 ```sh
-bindgen /usr/include/qt/QtCore/qnamespace.h --whitelist-type Qt::Key --whitelist-type Qt::KeyboardModifier --whitelist-type Qt::AlignmentFlag --whitelist-type Qt::TextFlag --whitelist-type Qt::FillRule --whitelist-type Qt::CursorShape -o internal/backends/qt/key_generated.rs -- -I /usr/include/qt -xc++
+bindgen /usr/include/qt/QtCore/qnamespace.h --whitelist-type Qt::Key --whitelist-type Qt::KeyboardModifier --whitelist-type Qt::AlignmentFlag --whitelist-type Qt::TextFlag --whitelist-type Qt::FillRule --whitelist-type Qt::CursorShape --whitelist-type Qt::PenCapStyle --whitelist-type Qt::PenJoinStyle -o internal/backends/qt/key_generated.rs -- -I /usr/include/qt -xc++
 ```
 then add licence header and this doc
 */
@@ -551,3 +551,14 @@
 pub const Qt_FillRule_OddEvenFill: Qt_FillRule = 0;
 pub const Qt_FillRule_WindingFill: Qt_FillRule = 1;
 pub type Qt_FillRule = ::std::os::raw::c_uint;
+pub const Qt_PenCapStyle_FlatCap: Qt_PenCapStyle = 0;
+pub const Qt_PenCapStyle_SquareCap: Qt_PenCapStyle = 16;
+pub const Qt_PenCapStyle_RoundCap: Qt_PenCapStyle = 32;
+pub const Qt_PenCapStyle_MPenCapStyle: Qt_PenCapStyle = 48;
+pub type Qt_PenCapStyle = ::std::os::raw::c_uint;
+pub const Qt_PenJoinStyle_MiterJoin: Qt_PenJoinStyle = 0;
+pub const Qt_PenJoinStyle_BevelJoin: Qt_PenJoinStyle = 64;
+pub const Qt_PenJoinStyle_RoundJoin: Qt_PenJoinStyle = 128;
+pub const Qt_PenJoinStyle_SvgMiterJoin: Qt_PenJoinStyle = 256;
+pub const Qt_PenJoinStyle_MPenJoinStyle: Qt_PenJoinStyle = 448;
+pub type Qt_PenJoinStyle = ::std::os::raw::c_uint;
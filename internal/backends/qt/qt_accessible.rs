@@ -311,6 +311,9 @@ fn arm_focus_delegation_tracker(self: Pin<&Self>) {
                     i_slint_core::items::AccessibleRole::Slider => QAccessible_Role_Slider,
                     i_slint_core::items::AccessibleRole::Spinbox => QAccessible_Role_SpinBox,
                     i_slint_core::items::AccessibleRole::Tab => QAccessible_Role_PageTab,
+                    i_slint_core::items::AccessibleRole::ProgressIndicator => QAccessible_Role_ProgressBar,
+                    i_slint_core::items::AccessibleRole::Switch => QAccessible_Role_CheckBox,
+                    i_slint_core::items::AccessibleRole::RadioButton => QAccessible_Role_RadioButton,
                     i_slint_core::items::AccessibleRole::Text => QAccessible_Role_StaticText,
                 }
             });
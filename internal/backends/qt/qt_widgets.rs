@@ -197,6 +197,12 @@ pub fn draw(&self, callback: &mut dyn FnMut(u32, u32, &[u8])) {
 mod combobox;
 pub use combobox::*;
 
+mod menu;
+pub use menu::*;
+
+mod progressindicator;
+pub use progressindicator::*;
+
 mod tabwidget;
 pub use tabwidget::*;
 
@@ -14,6 +14,7 @@ pub struct NativeComboBox {
     pub width: Property<LogicalLength>,
     pub height: Property<LogicalLength>,
     pub enabled: Property<bool>,
+    pub editable: Property<bool>,
     pub pressed: Property<bool>,
     pub is_open: Property<bool>,
     pub current_value: Property<SharedString>,
@@ -119,11 +120,13 @@ fn focus_event(
         let text: qttypes::QString =
             this.current_value().as_str().into();
         let enabled = this.enabled();
+        let editable = this.editable();
         cpp!(unsafe [
             painter as "QPainterPtr*",
             widget as "QWidget*",
             text as "QString",
             enabled as "bool",
+            editable as "bool",
             size as "QSize",
             down as "bool",
             is_open as "bool",
@@ -134,6 +137,7 @@ fn focus_event(
             QStyleOptionComboBox option;
             option.state |= QStyle::State(initial_state);
             option.currentText = std::move(text);
+            option.editable = editable;
             option.rect = QRect(QPoint(), size / dpr);
             if (down)
                 option.state |= QStyle::State_Sunken;
@@ -150,7 +154,9 @@ fn focus_event(
             }
             option.subControls = QStyle::SC_All;
             qApp->style()->drawComplexControl(QStyle::CC_ComboBox, &option, painter->get(), widget);
-            qApp->style()->drawControl(QStyle::CE_ComboBoxLabel, &option, painter->get(), widget);
+            // The text of an editable combobox is drawn by the line edit placed over it
+            if (!editable)
+                qApp->style()->drawControl(QStyle::CE_ComboBoxLabel, &option, painter->get(), widget);
         });
     }
 }
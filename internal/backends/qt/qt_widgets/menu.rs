@@ -0,0 +1,405 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+// cSpell: ignore menubar
+
+use i_slint_core::input::FocusEventResult;
+
+use super::*;
+
+#[repr(C)]
+#[derive(FieldOffsets, Default, SlintElement)]
+#[pin]
+pub struct NativeMenuBarItem {
+    pub x: Property<LogicalLength>,
+    pub y: Property<LogicalLength>,
+    pub width: Property<LogicalLength>,
+    pub height: Property<LogicalLength>,
+    pub text: Property<SharedString>,
+    pub enabled: Property<bool>,
+    pub has_hover: Property<bool>,
+    pub pressed: Property<bool>,
+    pub cached_rendering_data: CachedRenderingData,
+}
+
+impl Item for NativeMenuBarItem {
+    fn init(self: Pin<&Self>, _window_adapter: &Rc<dyn WindowAdapter>) {}
+
+    fn geometry(self: Pin<&Self>) -> LogicalRect {
+        LogicalRect::new(
+            LogicalPoint::from_lengths(self.x(), self.y()),
+            LogicalSize::from_lengths(self.width(), self.height()),
+        )
+    }
+
+    fn layout_info(
+        self: Pin<&Self>,
+        orientation: Orientation,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+    ) -> LayoutInfo {
+        let text: qttypes::QString = self.text().as_str().into();
+
+        let s = cpp!(unsafe [text as "QString"] -> qttypes::QSize as "QSize" {
+            ensure_initialized();
+
+            QStyleOptionMenuItem option;
+            option.menuItemType = QStyleOptionMenuItem::Normal;
+            option.text = text;
+            auto style = qApp->style();
+            QSize size = option.fontMetrics.size(Qt::TextShowMnemonic, text);
+            return style->sizeFromContents(QStyle::CT_MenuBarItem, &option, size, nullptr);
+        });
+        let min = match orientation {
+            Orientation::Horizontal => s.width,
+            Orientation::Vertical => s.height,
+        } as f32;
+        LayoutInfo { min, preferred: min, ..LayoutInfo::default() }
+    }
+
+    fn input_event_filter_before_children(
+        self: Pin<&Self>,
+        _: MouseEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> InputEventFilterResult {
+        InputEventFilterResult::ForwardAndIgnore
+    }
+
+    fn input_event(
+        self: Pin<&Self>,
+        _event: MouseEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &i_slint_core::items::ItemRc,
+    ) -> InputEventResult {
+        InputEventResult::EventIgnored
+    }
+
+    fn key_event(
+        self: Pin<&Self>,
+        _: &KeyEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> KeyEventResult {
+        KeyEventResult::EventIgnored
+    }
+
+    fn focus_event(
+        self: Pin<&Self>,
+        _: &FocusEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> FocusEventResult {
+        FocusEventResult::FocusIgnored
+    }
+
+    fn_render! { this dpr size painter widget initial_state =>
+        let text: qttypes::QString = this.text().as_str().into();
+        let enabled: bool = this.enabled();
+        let has_hover: bool = this.has_hover();
+        let pressed: bool = this.pressed();
+        cpp!(unsafe [
+            painter as "QPainterPtr*",
+            widget as "QWidget*",
+            size as "QSize",
+            dpr as "float",
+            text as "QString",
+            enabled as "bool",
+            has_hover as "bool",
+            pressed as "bool",
+            initial_state as "int"
+        ] {
+            QStyleOptionMenuItem option;
+            option.state |= QStyle::State(initial_state);
+            option.rect = QRect(QPoint(), size / dpr);
+            option.menuItemType = QStyleOptionMenuItem::Normal;
+            option.text = text;
+            if (enabled) {
+                option.state |= QStyle::State_Enabled;
+            } else {
+                option.palette.setCurrentColorGroup(QPalette::Disabled);
+            }
+            if (enabled && (has_hover || pressed)) {
+                option.state |= QStyle::State_Selected;
+            }
+            if (pressed) {
+                option.state |= QStyle::State_Sunken;
+            }
+            qApp->style()->drawControl(QStyle::CE_MenuBarItem, &option, painter->get(), widget);
+        });
+    }
+}
+
+impl ItemConsts for NativeMenuBarItem {
+    const cached_rendering_data_offset: const_field_offset::FieldOffset<Self, CachedRenderingData> =
+        Self::FIELD_OFFSETS.cached_rendering_data.as_unpinned_projection();
+}
+
+declare_item_vtable! {
+fn slint_get_NativeMenuBarItemVTable() -> NativeMenuBarItemVTable for NativeMenuBarItem
+}
+
+#[repr(C)]
+#[derive(FieldOffsets, Default, SlintElement)]
+#[pin]
+pub struct NativeMenuItem {
+    pub x: Property<LogicalLength>,
+    pub y: Property<LogicalLength>,
+    pub width: Property<LogicalLength>,
+    pub height: Property<LogicalLength>,
+    pub text: Property<SharedString>,
+    pub shortcut: Property<SharedString>,
+    pub enabled: Property<bool>,
+    pub checkable: Property<bool>,
+    pub checked: Property<bool>,
+    pub is_separator: Property<bool>,
+    pub has_submenu: Property<bool>,
+    pub has_hover: Property<bool>,
+    pub cached_rendering_data: CachedRenderingData,
+}
+
+impl NativeMenuItem {
+    /// The text of the item as Qt expects it, with the shortcut after a tab
+    fn qt_text(self: Pin<&Self>) -> qttypes::QString {
+        let shortcut = self.shortcut();
+        if shortcut.is_empty() {
+            self.text().as_str().into()
+        } else {
+            format!("{}\t{}", self.text(), shortcut).as_str().into()
+        }
+    }
+}
+
+impl Item for NativeMenuItem {
+    fn init(self: Pin<&Self>, _window_adapter: &Rc<dyn WindowAdapter>) {}
+
+    fn geometry(self: Pin<&Self>) -> LogicalRect {
+        LogicalRect::new(
+            LogicalPoint::from_lengths(self.x(), self.y()),
+            LogicalSize::from_lengths(self.width(), self.height()),
+        )
+    }
+
+    fn layout_info(
+        self: Pin<&Self>,
+        orientation: Orientation,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+    ) -> LayoutInfo {
+        let text = self.qt_text();
+        let is_separator: bool = self.is_separator();
+        let checkable: bool = self.checkable();
+        let has_submenu: bool = self.has_submenu();
+
+        let s = cpp!(unsafe [
+            text as "QString",
+            is_separator as "bool",
+            checkable as "bool",
+            has_submenu as "bool"
+        ] -> qttypes::QSize as "QSize" {
+            ensure_initialized();
+
+            QStyleOptionMenuItem option;
+            option.menuItemType = is_separator ? QStyleOptionMenuItem::Separator
+                : has_submenu ? QStyleOptionMenuItem::SubMenu
+                : QStyleOptionMenuItem::Normal;
+            option.checkType = checkable ? QStyleOptionMenuItem::NonExclusive : QStyleOptionMenuItem::NotCheckable;
+            option.text = text;
+            option.reservedShortcutWidth = 0;
+            option.maxIconWidth = 0;
+            auto style = qApp->style();
+            QSize size = is_separator ? QSize(0, 0) : option.fontMetrics.size(Qt::TextShowMnemonic | Qt::TextExpandTabs, text);
+            return style->sizeFromContents(QStyle::CT_MenuItem, &option, size, nullptr);
+        });
+        let min = match orientation {
+            Orientation::Horizontal => s.width,
+            Orientation::Vertical => s.height,
+        } as f32;
+        LayoutInfo { min, preferred: min, ..LayoutInfo::default() }
+    }
+
+    fn input_event_filter_before_children(
+        self: Pin<&Self>,
+        _: MouseEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> InputEventFilterResult {
+        InputEventFilterResult::ForwardAndIgnore
+    }
+
+    fn input_event(
+        self: Pin<&Self>,
+        _event: MouseEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &i_slint_core::items::ItemRc,
+    ) -> InputEventResult {
+        InputEventResult::EventIgnored
+    }
+
+    fn key_event(
+        self: Pin<&Self>,
+        _: &KeyEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> KeyEventResult {
+        KeyEventResult::EventIgnored
+    }
+
+    fn focus_event(
+        self: Pin<&Self>,
+        _: &FocusEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> FocusEventResult {
+        FocusEventResult::FocusIgnored
+    }
+
+    fn_render! { this dpr size painter widget initial_state =>
+        let text = this.qt_text();
+        let enabled: bool = this.enabled();
+        let checkable: bool = this.checkable();
+        let checked: bool = this.checked();
+        let is_separator: bool = this.is_separator();
+        let has_submenu: bool = this.has_submenu();
+        let has_hover: bool = this.has_hover();
+        cpp!(unsafe [
+            painter as "QPainterPtr*",
+            widget as "QWidget*",
+            size as "QSize",
+            dpr as "float",
+            text as "QString",
+            enabled as "bool",
+            checkable as "bool",
+            checked as "bool",
+            is_separator as "bool",
+            has_submenu as "bool",
+            has_hover as "bool",
+            initial_state as "int"
+        ] {
+            QStyleOptionMenuItem option;
+            option.state |= QStyle::State(initial_state);
+            option.rect = QRect(QPoint(), size / dpr);
+            option.menuRect = option.rect;
+            option.menuItemType = is_separator ? QStyleOptionMenuItem::Separator
+                : has_submenu ? QStyleOptionMenuItem::SubMenu
+                : QStyleOptionMenuItem::Normal;
+            option.checkType = checkable ? QStyleOptionMenuItem::NonExclusive : QStyleOptionMenuItem::NotCheckable;
+            option.checked = checked;
+            option.text = text;
+            option.reservedShortcutWidth = 0;
+            option.maxIconWidth = 0;
+            if (enabled) {
+                option.state |= QStyle::State_Enabled;
+            } else {
+                option.palette.setCurrentColorGroup(QPalette::Disabled);
+            }
+            if (enabled && has_hover && !is_separator) {
+                option.state |= QStyle::State_Selected;
+            }
+            qApp->style()->drawControl(QStyle::CE_MenuItem, &option, painter->get(), widget);
+        });
+    }
+}
+
+impl ItemConsts for NativeMenuItem {
+    const cached_rendering_data_offset: const_field_offset::FieldOffset<Self, CachedRenderingData> =
+        Self::FIELD_OFFSETS.cached_rendering_data.as_unpinned_projection();
+}
+
+declare_item_vtable! {
+fn slint_get_NativeMenuItemVTable() -> NativeMenuItemVTable for NativeMenuItem
+}
+
+#[repr(C)]
+#[derive(FieldOffsets, Default, SlintElement)]
+#[pin]
+pub struct NativeMenuFrame {
+    pub x: Property<LogicalLength>,
+    pub y: Property<LogicalLength>,
+    pub width: Property<LogicalLength>,
+    pub height: Property<LogicalLength>,
+    pub cached_rendering_data: CachedRenderingData,
+}
+
+impl Item for NativeMenuFrame {
+    fn init(self: Pin<&Self>, _window_adapter: &Rc<dyn WindowAdapter>) {}
+
+    fn geometry(self: Pin<&Self>) -> LogicalRect {
+        LogicalRect::new(
+            LogicalPoint::from_lengths(self.x(), self.y()),
+            LogicalSize::from_lengths(self.width(), self.height()),
+        )
+    }
+
+    fn layout_info(
+        self: Pin<&Self>,
+        _orientation: Orientation,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+    ) -> LayoutInfo {
+        Default::default()
+    }
+
+    fn input_event_filter_before_children(
+        self: Pin<&Self>,
+        _: MouseEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> InputEventFilterResult {
+        InputEventFilterResult::ForwardAndIgnore
+    }
+
+    fn input_event(
+        self: Pin<&Self>,
+        _: MouseEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &i_slint_core::items::ItemRc,
+    ) -> InputEventResult {
+        InputEventResult::EventIgnored
+    }
+
+    fn key_event(
+        self: Pin<&Self>,
+        _: &KeyEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> KeyEventResult {
+        KeyEventResult::EventIgnored
+    }
+
+    fn focus_event(
+        self: Pin<&Self>,
+        _: &FocusEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> FocusEventResult {
+        FocusEventResult::FocusIgnored
+    }
+
+    fn_render! { _this dpr size painter widget initial_state =>
+        cpp!(unsafe [
+            painter as "QPainterPtr*",
+            widget as "QWidget*",
+            size as "QSize",
+            dpr as "float",
+            initial_state as "int"
+        ] {
+            ensure_initialized();
+            QStyleOptionFrame option;
+            option.state |= QStyle::State(initial_state);
+            auto style = qApp->style();
+            option.lineWidth = style->pixelMetric(QStyle::PM_MenuPanelWidth, nullptr, nullptr);
+            option.midLineWidth = 0;
+            option.rect = QRect(QPoint(), size / dpr);
+            option.state |= QStyle::State_Enabled;
+            style->drawPrimitive(QStyle::PE_PanelMenu, &option, painter->get(), widget);
+            style->drawPrimitive(QStyle::PE_FrameMenu, &option, painter->get(), widget);
+        });
+    }
+}
+
+impl ItemConsts for NativeMenuFrame {
+    const cached_rendering_data_offset: const_field_offset::FieldOffset<Self, CachedRenderingData> =
+        Self::FIELD_OFFSETS.cached_rendering_data.as_unpinned_projection();
+}
+
+declare_item_vtable! {
+fn slint_get_NativeMenuFrameVTable() -> NativeMenuFrameVTable for NativeMenuFrame
+}
@@ -0,0 +1,137 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+use i_slint_core::input::FocusEventResult;
+
+use super::*;
+
+#[repr(C)]
+#[derive(FieldOffsets, Default, SlintElement)]
+#[pin]
+pub struct NativeProgressIndicator {
+    pub x: Property<LogicalLength>,
+    pub y: Property<LogicalLength>,
+    pub width: Property<LogicalLength>,
+    pub height: Property<LogicalLength>,
+    pub progress: Property<f32>,
+    pub enabled: Property<bool>,
+    pub cached_rendering_data: CachedRenderingData,
+}
+
+impl Item for NativeProgressIndicator {
+    fn init(self: Pin<&Self>, _window_adapter: &Rc<dyn WindowAdapter>) {}
+
+    fn geometry(self: Pin<&Self>) -> LogicalRect {
+        LogicalRect::new(
+            LogicalPoint::from_lengths(self.x(), self.y()),
+            LogicalSize::from_lengths(self.width(), self.height()),
+        )
+    }
+
+    fn layout_info(
+        self: Pin<&Self>,
+        orientation: Orientation,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+    ) -> LayoutInfo {
+        let s = cpp!(unsafe [] -> qttypes::QSize as "QSize" {
+            ensure_initialized();
+            QStyleOptionProgressBar option;
+            option.state |= QStyle::State_Horizontal;
+            option.minimum = 0;
+            option.maximum = 1000;
+            option.textVisible = false;
+            auto style = qApp->style();
+            int height = style->pixelMetric(QStyle::PM_ProgressBarChunkWidth, &option, nullptr);
+            return style->sizeFromContents(QStyle::CT_ProgressBar, &option, QSize(height, height), nullptr);
+        });
+        match orientation {
+            Orientation::Horizontal => LayoutInfo {
+                min: s.width as f32,
+                preferred: 200.,
+                stretch: 1.,
+                ..LayoutInfo::default()
+            },
+            Orientation::Vertical => LayoutInfo {
+                min: s.height as f32,
+                preferred: s.height as f32,
+                max: s.height as f32,
+                stretch: 0.,
+                ..LayoutInfo::default()
+            },
+        }
+    }
+
+    fn input_event_filter_before_children(
+        self: Pin<&Self>,
+        _: MouseEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> InputEventFilterResult {
+        InputEventFilterResult::ForwardAndIgnore
+    }
+
+    fn input_event(
+        self: Pin<&Self>,
+        _: MouseEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &i_slint_core::items::ItemRc,
+    ) -> InputEventResult {
+        InputEventResult::EventIgnored
+    }
+
+    fn key_event(
+        self: Pin<&Self>,
+        _: &KeyEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> KeyEventResult {
+        KeyEventResult::EventIgnored
+    }
+
+    fn focus_event(
+        self: Pin<&Self>,
+        _: &FocusEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> FocusEventResult {
+        FocusEventResult::FocusIgnored
+    }
+
+    fn_render! { this dpr size painter widget initial_state =>
+        let progress = (this.progress().clamp(0., 1.) * 1000.) as i32;
+        let enabled: bool = this.enabled();
+        cpp!(unsafe [
+            painter as "QPainterPtr*",
+            widget as "QWidget*",
+            size as "QSize",
+            dpr as "float",
+            progress as "int",
+            enabled as "bool",
+            initial_state as "int"
+        ] {
+            ensure_initialized();
+            QStyleOptionProgressBar option;
+            option.state |= QStyle::State(initial_state) | QStyle::State_Horizontal;
+            if (enabled) {
+                option.state |= QStyle::State_Enabled;
+            } else {
+                option.palette.setCurrentColorGroup(QPalette::Disabled);
+            }
+            option.rect = QRect(QPoint(), size / dpr);
+            option.minimum = 0;
+            option.maximum = 1000;
+            option.progress = progress;
+            option.textVisible = false;
+            qApp->style()->drawControl(QStyle::CE_ProgressBar, &option, painter->get(), widget);
+        });
+    }
+}
+
+impl ItemConsts for NativeProgressIndicator {
+    const cached_rendering_data_offset: const_field_offset::FieldOffset<Self, CachedRenderingData> =
+        Self::FIELD_OFFSETS.cached_rendering_data.as_unpinned_projection();
+}
+
+declare_item_vtable! {
+fn slint_get_NativeProgressIndicatorVTable() -> NativeProgressIndicatorVTable for NativeProgressIndicator
+}
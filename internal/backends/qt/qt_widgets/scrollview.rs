@@ -26,6 +26,9 @@ pub struct NativeScrollView {
     pub native_padding_bottom: Property<LogicalLength>,
     pub enabled: Property<bool>,
     pub has_focus: Property<bool>,
+    pub vertical_scrollbar_visible: Property<bool>,
+    pub horizontal_scrollbar_visible: Property<bool>,
+    pub overlay_scrollbars: Property<bool>,
     data: Property<NativeSliderData>,
 }
 
@@ -123,10 +126,13 @@ fn input_event(
         let mut data = self.data();
         let active_controls = data.active_controls;
         let pressed = data.pressed;
+        let vertical_visible = self.vertical_scrollbar_visible() && !self.overlay_scrollbars();
+        let horizontal_visible = self.horizontal_scrollbar_visible() && !self.overlay_scrollbars();
         let left = self.native_padding_left().get();
-        let right = self.native_padding_right().get();
+        // Without a scroll bar, only the frame remains, which has the same size on both sides
+        let right = if vertical_visible { self.native_padding_right().get() } else { left };
         let top = self.native_padding_top().get();
-        let bottom = self.native_padding_bottom().get();
+        let bottom = if horizontal_visible { self.native_padding_bottom().get() } else { top };
 
         let mut handle_scrollbar = |horizontal: bool,
                                     pos: qttypes::QPoint,
@@ -225,7 +231,9 @@ fn input_event(
 
         let pos = event.position().unwrap_or_default();
 
-        if pressed == 2 || (pressed == 0 && pos.x > (size.width as f32 - right)) {
+        if vertical_visible
+            && (pressed == 2 || (pressed == 0 && pos.x > (size.width as f32 - right)))
+        {
             handle_scrollbar(
                 false,
                 qttypes::QPoint {
@@ -240,7 +248,7 @@ fn input_event(
                 self.vertical_page_size().get() as i32,
                 self.vertical_max().get() as i32,
             )
-        } else if pressed == 1 || pos.y > (size.height as f32 - bottom) {
+        } else if horizontal_visible && (pressed == 1 || pos.y > (size.height as f32 - bottom)) {
             handle_scrollbar(
                 true,
                 qttypes::QPoint {
@@ -281,11 +289,18 @@ fn focus_event(
     fn_render! { this dpr size painter widget initial_state =>
 
         let data = this.data();
+        let vertical_visible = this.vertical_scrollbar_visible() && !this.overlay_scrollbars();
+        let horizontal_visible = this.horizontal_scrollbar_visible() && !this.overlay_scrollbars();
+        let left = this.native_padding_left().get();
+        let top = this.native_padding_top().get();
+        // Without a scroll bar, only the frame remains, which has the same size on both sides
+        let right = if vertical_visible { this.native_padding_right().get() } else { left };
+        let bottom = if horizontal_visible { this.native_padding_bottom().get() } else { top };
         let margins = qttypes::QMargins {
-            left: this.native_padding_left().get() as _,
-            top: this.native_padding_top().get() as _,
-            right: this.native_padding_right().get() as _,
-            bottom: this.native_padding_bottom().get() as _,
+            left: left as _,
+            top: top as _,
+            right: right as _,
+            bottom: bottom as _,
         };
         let enabled: bool = this.enabled();
         let has_focus: bool = this.has_focus();
@@ -395,36 +410,40 @@ fn focus_event(
 
         let scrollbars_width = (margins.right - margins.left) as f32;
         let scrollbars_height = (margins.bottom - margins.top) as f32;
-        draw_scrollbar(
-            false,
-            qttypes::QRectF {
-                x: ((size.width as f32 / dpr) - if frame_around_contents { scrollbars_width } else { margins.right as _ }) as _,
-                y: (if frame_around_contents { 0 } else { margins.top }) as _,
-                width: scrollbars_width as _,
-                height: ((size.height as f32 / dpr) - if frame_around_contents { scrollbars_height } else { (margins.bottom + margins.top) as f32 }) as _,
-            },
-            this.vertical_value().get() as i32,
-            this.vertical_page_size().get() as i32,
-            this.vertical_max().get() as i32,
-            data.active_controls,
-            data.pressed == 2,
-            initial_state
-        );
-        draw_scrollbar(
-            true,
-            qttypes::QRectF {
-                x: (if frame_around_contents { 0 } else { margins.left }) as _,
-                y: ((size.height as f32 / dpr) - if frame_around_contents { scrollbars_height } else { margins.bottom as _ }) as _,
-                width: ((size.width as f32 / dpr) - if frame_around_contents { scrollbars_width } else { (margins.left + margins.right) as _ }) as _,
-                height: (scrollbars_height) as _,
-            },
-            this.horizontal_value().get() as i32,
-            this.horizontal_page_size().get() as i32,
-            this.horizontal_max().get() as i32,
-            data.active_controls,
-            data.pressed == 1,
-            initial_state
-        );
+        if vertical_visible {
+            draw_scrollbar(
+                false,
+                qttypes::QRectF {
+                    x: ((size.width as f32 / dpr) - if frame_around_contents { scrollbars_width } else { margins.right as _ }) as _,
+                    y: (if frame_around_contents { 0 } else { margins.top }) as _,
+                    width: scrollbars_width as _,
+                    height: ((size.height as f32 / dpr) - if frame_around_contents { scrollbars_height } else { (margins.bottom + margins.top) as f32 }) as _,
+                },
+                this.vertical_value().get() as i32,
+                this.vertical_page_size().get() as i32,
+                this.vertical_max().get() as i32,
+                data.active_controls,
+                data.pressed == 2,
+                initial_state
+            );
+        }
+        if horizontal_visible {
+            draw_scrollbar(
+                true,
+                qttypes::QRectF {
+                    x: (if frame_around_contents { 0 } else { margins.left }) as _,
+                    y: ((size.height as f32 / dpr) - if frame_around_contents { scrollbars_height } else { margins.bottom as _ }) as _,
+                    width: ((size.width as f32 / dpr) - if frame_around_contents { scrollbars_width } else { (margins.left + margins.right) as _ }) as _,
+                    height: (scrollbars_height) as _,
+                },
+                this.horizontal_value().get() as i32,
+                this.horizontal_page_size().get() as i32,
+                this.horizontal_max().get() as i32,
+                data.active_controls,
+                data.pressed == 1,
+                initial_state
+            );
+        }
     }
 }
 
@@ -1,7 +1,7 @@
 // Copyright © SixtyFPS GmbH <info@slint-ui.com>
 // SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
 
-use i_slint_core::input::{FocusEventResult, KeyEventType};
+use i_slint_core::input::FocusEventResult;
 
 use super::*;
 
@@ -22,9 +22,9 @@ pub struct NativeSpinBox {
     pub height: Property<LogicalLength>,
     pub enabled: Property<bool>,
     pub has_focus: Property<bool>,
-    pub value: Property<i32>,
-    pub minimum: Property<i32>,
-    pub maximum: Property<i32>,
+    pub text: Property<SharedString>,
+    pub step_up: Callback<VoidArg>,
+    pub step_down: Callback<VoidArg>,
     pub cached_rendering_data: CachedRenderingData,
     data: Property<NativeSpinBoxData>,
 }
@@ -68,14 +68,12 @@ fn layout_info(
         orientation: Orientation,
         _window_adapter: &Rc<dyn WindowAdapter>,
     ) -> LayoutInfo {
-        //let value: i32 = self.value();
         let data = self.data();
         let active_controls = data.active_controls;
         let pressed = data.pressed;
         let enabled = self.enabled();
 
         let size = cpp!(unsafe [
-            //value as "int",
             active_controls as "int",
             pressed as "bool",
             enabled as "bool"
@@ -120,8 +118,8 @@ fn input_event_filter_before_children(
     fn input_event(
         self: Pin<&Self>,
         event: MouseEvent,
-        window_adapter: &Rc<dyn WindowAdapter>,
-        self_rc: &i_slint_core::items::ItemRc,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &i_slint_core::items::ItemRc,
     ) -> InputEventResult {
         let size: qttypes::QSize = get_size!(self);
         let enabled = self.enabled();
@@ -150,9 +148,16 @@ fn input_event(
 
             return style->hitTestComplexControl(QStyle::CC_SpinBox, &option, pos, nullptr);
         });
+        let up = cpp!(unsafe []->u32 as "int" { return QStyle::SC_SpinBoxUp;});
+        let down = cpp!(unsafe []->u32 as "int" { return QStyle::SC_SpinBoxDown;});
+        let on_button = new_control == up || new_control == down;
         let changed = new_control != active_controls
             || match event {
                 MouseEvent::Pressed { .. } => {
+                    if !on_button {
+                        // Let the clicks on the edit field reach the SpinBox below, which takes the focus
+                        return InputEventResult::EventIgnored;
+                    }
                     data.pressed = true;
                     true
                 }
@@ -161,88 +166,51 @@ fn input_event(
                     true
                 }
                 MouseEvent::Released { .. } => {
-                    data.pressed = false;
-                    if new_control == cpp!(unsafe []->u32 as "int" { return QStyle::SC_SpinBoxUp;})
-                        && enabled
-                    {
-                        let v = self.value();
-                        if v < self.maximum() {
-                            self.value.set(v + 1);
-                        }
-                    }
-                    if new_control
-                        == cpp!(unsafe []->u32 as "int" { return QStyle::SC_SpinBoxDown;})
-                        && enabled
-                    {
-                        let v = self.value();
-                        if v > self.minimum() {
-                            self.value.set(v - 1);
+                    if data.pressed && enabled {
+                        if new_control == up {
+                            Self::FIELD_OFFSETS.step_up.apply_pin(self).call(&());
+                        } else if new_control == down {
+                            Self::FIELD_OFFSETS.step_down.apply_pin(self).call(&());
                         }
                     }
+                    data.pressed = false;
                     true
                 }
                 MouseEvent::Moved { .. } => false,
-                MouseEvent::Wheel { .. } => false, // TODO
+                MouseEvent::Wheel { .. } => return InputEventResult::EventIgnored,
             };
         data.active_controls = new_control;
         if changed {
             self.data.set(data);
         }
 
-        if let MouseEvent::Pressed { .. } = event {
-            if !self.has_focus() {
-                WindowInner::from_pub(window_adapter.window()).set_focus_item(self_rc);
-            }
+        if on_button || data.pressed {
+            InputEventResult::EventAccepted
+        } else {
+            InputEventResult::EventIgnored
         }
-        InputEventResult::EventAccepted
     }
 
     fn key_event(
         self: Pin<&Self>,
-        event: &KeyEvent,
+        _: &KeyEvent,
         _window_adapter: &Rc<dyn WindowAdapter>,
         _self_rc: &ItemRc,
     ) -> KeyEventResult {
-        if !self.enabled() || event.event_type != KeyEventType::KeyPressed {
-            return KeyEventResult::EventIgnored;
-        }
-        if event.text.starts_with(i_slint_core::input::key_codes::UpArrow)
-            && self.value() < self.maximum()
-        {
-            self.value.set(self.value() + 1);
-            KeyEventResult::EventAccepted
-        } else if event.text.starts_with(i_slint_core::input::key_codes::DownArrow)
-            && self.value() > self.minimum()
-        {
-            self.value.set(self.value() - 1);
-            KeyEventResult::EventAccepted
-        } else {
-            KeyEventResult::EventIgnored
-        }
+        KeyEventResult::EventIgnored
     }
 
     fn focus_event(
         self: Pin<&Self>,
-        event: &FocusEvent,
+        _: &FocusEvent,
         _window_adapter: &Rc<dyn WindowAdapter>,
         _self_rc: &ItemRc,
     ) -> FocusEventResult {
-        match event {
-            FocusEvent::FocusIn => {
-                if self.enabled() {
-                    self.has_focus.set(true);
-                }
-            }
-            FocusEvent::FocusOut | FocusEvent::WindowLostFocus => {
-                self.has_focus.set(false);
-            }
-            FocusEvent::WindowReceivedFocus => self.has_focus.set(true),
-        }
-        FocusEventResult::FocusAccepted
+        FocusEventResult::FocusIgnored
     }
 
     fn_render! { this dpr size painter widget initial_state =>
-        let value: i32 = this.value();
+        let text: qttypes::QString = this.text().as_str().into();
         let enabled = this.enabled();
         let has_focus = this.has_focus();
         let data = this.data();
@@ -252,7 +220,7 @@ fn focus_event(
         cpp!(unsafe [
             painter as "QPainterPtr*",
             widget as "QWidget*",
-            value as "int",
+            text as "QString",
             enabled as "bool",
             has_focus as "bool",
             size as "QSize",
@@ -282,7 +250,7 @@ fn focus_event(
             QRect text_rect = qApp->style()->subElementRect(QStyle::SE_LineEditContents, &frame, widget);
             text_rect.adjust(1, 2, 1, 2);
             (*painter)->setPen(option.palette.color(QPalette::Text));
-            (*painter)->drawText(text_rect, QString::number(value));
+            (*painter)->drawText(text_rect, text);
         });
     }
 }
@@ -42,6 +42,9 @@ pub struct NativeStyleMetrics {
 
     pub placeholder_color: Property<Color>,
     pub placeholder_color_disabled: Property<Color>,
+    pub accent_color: Property<Color>,
+    pub accent_foreground_color: Property<Color>,
+    pub border_color: Property<Color>,
 
     pub dark_color_scheme: Property<bool>,
 
@@ -71,6 +74,9 @@ pub fn new() -> Pin<Rc<Self>> {
             textedit_text_color_disabled: Default::default(),
             placeholder_color: Default::default(),
             placeholder_color_disabled: Default::default(),
+            accent_color: Default::default(),
+            accent_foreground_color: Default::default(),
+            border_color: Default::default(),
             dark_color_scheme: Default::default(),
             tab_bar_alignment: Default::default(),
             style_change_listener: core::cell::Cell::new(core::ptr::null()),
@@ -148,6 +154,18 @@ fn init_impl(self: Pin<&Self>) {
             return qApp->palette().color(QPalette::Disabled, QPalette::PlaceholderText).rgba();
         });
         self.placeholder_color_disabled.set(Color::from_argb_encoded(placeholder_color_disabled));
+        let accent_color = cpp!(unsafe[] -> u32 as "QRgb" {
+            return qApp->palette().color(QPalette::Highlight).rgba();
+        });
+        self.accent_color.set(Color::from_argb_encoded(accent_color));
+        let accent_foreground_color = cpp!(unsafe[] -> u32 as "QRgb" {
+            return qApp->palette().color(QPalette::HighlightedText).rgba();
+        });
+        self.accent_foreground_color.set(Color::from_argb_encoded(accent_foreground_color));
+        let border_color = cpp!(unsafe[] -> u32 as "QRgb" {
+            return qApp->palette().color(QPalette::Mid).rgba();
+        });
+        self.border_color.set(Color::from_argb_encoded(border_color));
 
         self.dark_color_scheme.set(
             (window_background.red() as u32
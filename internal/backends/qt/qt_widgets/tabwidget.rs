@@ -203,24 +203,33 @@ fn layout_info(
             ),
         };
 
-        let size = cpp!(unsafe [content_size as "QSizeF", tabbar_size as "QSizeF"] -> qttypes::QSize as "QSize" {
-            ensure_initialized();
+        let size_from_contents = |tabbar_size: qttypes::QSizeF| {
+            cpp!(unsafe [content_size as "QSizeF", tabbar_size as "QSizeF"] -> qttypes::QSize as "QSize" {
+                ensure_initialized();
 
-            QStyleOptionTabWidgetFrame option;
-            auto style = qApp->style();
-            option.lineWidth = style->pixelMetric(QStyle::PM_DefaultFrameWidth, 0, nullptr);
-            option.shape = QTabBar::RoundedNorth;
-            option.tabBarSize = tabbar_size.toSize();
-            option.rightCornerWidgetSize = QSize(0, 0);
-            option.leftCornerWidgetSize = QSize(0, 0);
-            auto sz = QSize(qMax(content_size.width(), tabbar_size.width()),
-                content_size.height() + tabbar_size.height());
-            return style->sizeFromContents(QStyle::CT_TabWidget, &option, sz, nullptr);
+                QStyleOptionTabWidgetFrame option;
+                auto style = qApp->style();
+                option.lineWidth = style->pixelMetric(QStyle::PM_DefaultFrameWidth, 0, nullptr);
+                option.shape = QTabBar::RoundedNorth;
+                option.tabBarSize = tabbar_size.toSize();
+                option.rightCornerWidgetSize = QSize(0, 0);
+                option.leftCornerWidgetSize = QSize(0, 0);
+                auto sz = QSize(qMax(content_size.width(), tabbar_size.width()),
+                    content_size.height() + tabbar_size.height());
+                return style->sizeFromContents(QStyle::CT_TabWidget, &option, sz, nullptr);
+            })
+        };
+        let size = size_from_contents(tabbar_size);
+        // The tab bar scrolls when it is wider than the TabWidget, so it does not constrain
+        // the minimum width
+        let min_size = size_from_contents(match orientation {
+            Orientation::Horizontal => qttypes::QSizeF { width: 0., ..tabbar_size },
+            Orientation::Vertical => tabbar_size,
         });
         LayoutInfo {
             min: match orientation {
-                Orientation::Horizontal => size.width as f32,
-                Orientation::Vertical => size.height as f32,
+                Orientation::Horizontal => min_size.width as f32,
+                Orientation::Vertical => min_size.height as f32,
             },
             preferred: match orientation {
                 Orientation::Horizontal => size.width as f32,
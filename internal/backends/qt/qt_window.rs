@@ -16,18 +16,20 @@
 use i_slint_core::input::{KeyEvent, KeyEventType, MouseEvent};
 use i_slint_core::item_rendering::{ItemCache, ItemRenderer};
 use i_slint_core::items::{
-    self, FillRule, ImageRendering, InputType, ItemRc, ItemRef, Layer, MouseCursor, Opacity,
-    PointerEventButton, RenderingResult, TextOverflow, TextWrap, WindowItem,
+    self, DropShadow, Effect, FillRule, ImageRendering, InputType, ItemRc, ItemRef, Layer, LineCap,
+    LineJoin, MouseCursor, Opacity, PointerEventButton, RenderingResult, TextOverflow, TextWrap,
+    WindowItem,
 };
-use i_slint_core::layout::Orientation;
+use i_slint_core::layout::{LayoutInfo, Orientation};
 use i_slint_core::lengths::{
-    LogicalLength, LogicalPoint, LogicalRect, LogicalSize, LogicalVector, PhysicalPx, ScaleFactor,
+    LogicalBorderRadius, LogicalLength, LogicalPoint, LogicalRect, LogicalSize, LogicalVector,
+    PhysicalPx, ScaleFactor,
 };
 use i_slint_core::window::{WindowAdapter, WindowAdapterSealed, WindowInner};
 use i_slint_core::{ImageInner, PathData, Property, SharedString};
-use items::{ImageFit, TextHorizontalAlignment, TextVerticalAlignment};
+use items::{ImageFit, TextElidePosition, TextHorizontalAlignment, TextVerticalAlignment};
 
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::pin::Pin;
 use std::ptr::NonNull;
 use std::rc::{Rc, Weak};
@@ -40,6 +42,7 @@
     #include <QtWidgets/QtWidgets>
     #include <QtWidgets/QGraphicsScene>
     #include <QtWidgets/QGraphicsBlurEffect>
+    #include <QtWidgets/QGraphicsDropShadowEffect>
     #include <QtWidgets/QGraphicsPixmapItem>
     #include <QtGui/QAccessible>
     #include <QtGui/QPainter>
@@ -48,6 +51,10 @@
     #include <QtGui/QWindow>
     #include <QtGui/QResizeEvent>
     #include <QtGui/QTextLayout>
+    #include <QtGui/QTextDocument>
+    #include <QtGui/QTextBlock>
+    #include <QtGui/QTextCursor>
+    #include <QtGui/QAbstractTextDocumentLayout>
     #include <QtGui/QImageReader>
     #include <QtGui/QCursor>
     #include <QtCore/QBasicTimer>
@@ -63,6 +70,24 @@
 
     using QPainterPtr = std::unique_ptr<QPainter>;
 
+    // Returns the path of a rectangle where each corner can have a different radius
+    QPainterPath rounded_rect_path(const QRectF &rect, float top_left, float top_right,
+                                   float bottom_right, float bottom_left) {
+        auto clamp = [&](float radius) { return qMin<qreal>(radius, qMin(rect.width(), rect.height()) / 2); };
+        top_left = clamp(top_left);
+        top_right = clamp(top_right);
+        bottom_right = clamp(bottom_right);
+        bottom_left = clamp(bottom_left);
+        QPainterPath path;
+        path.moveTo(rect.left() + top_left, rect.top());
+        path.arcTo(rect.right() - 2 * top_right, rect.top(), 2 * top_right, 2 * top_right, 90, -90);
+        path.arcTo(rect.right() - 2 * bottom_right, rect.bottom() - 2 * bottom_right, 2 * bottom_right, 2 * bottom_right, 0, -90);
+        path.arcTo(rect.left(), rect.bottom() - 2 * bottom_left, 2 * bottom_left, 2 * bottom_left, 270, -90);
+        path.arcTo(rect.left(), rect.top(), 2 * top_left, 2 * top_left, 180, -90);
+        path.closeSubpath();
+        return path;
+    }
+
     struct TimerHandler : QObject {
         QBasicTimer timer;
         static TimerHandler& instance() {
@@ -84,11 +109,13 @@ struct TimerHandler : QObject {
     struct SlintWidget : QWidget {
         void *rust_window;
         bool isMouseButtonDown = false;
-        QPoint ime_position;
+        QRect ime_cursor_rect;
 
         SlintWidget() {
             setMouseTracking(true);
             setFocusPolicy(Qt::StrongFocus);
+            setAcceptDrops(true);
+            setAttribute(Qt::WA_AcceptTouchEvents);
         }
 
         void paintEvent(QPaintEvent *) override {
@@ -173,6 +200,62 @@ struct SlintWidget : QWidget {
             });
         }
 
+        void dragEnterEvent(QDragEnterEvent *event) override {
+            const QMimeData *mime_data = event->mimeData();
+            if (mime_data->formats().isEmpty()) {
+                return;
+            }
+            QString mime_type = mime_data->formats().first();
+            QString data = QString::fromUtf8(mime_data->data(mime_type));
+            QPoint pos = event->pos();
+            rust!(Slint_dragEnterEvent [rust_window: &QtWindow as "void*", mime_type: qttypes::QString as "QString", data: qttypes::QString as "QString", pos: qttypes::QPoint as "QPoint"] {
+                let position = LogicalPoint::new(pos.x as _, pos.y as _);
+                rust_window.drag_enter_event(mime_type.into(), data.into(), position)
+            });
+            event->acceptProposedAction();
+        }
+        void dragMoveEvent(QDragMoveEvent *event) override {
+            QPoint pos = event->pos();
+            rust!(Slint_dragMoveEvent [rust_window: &QtWindow as "void*", pos: qttypes::QPoint as "QPoint"] {
+                let position = LogicalPoint::new(pos.x as _, pos.y as _);
+                rust_window.drag_event(MouseEvent::Moved{position});
+            });
+            event->acceptProposedAction();
+        }
+        void dragLeaveEvent(QDragLeaveEvent *) override {
+            rust!(Slint_dragLeaveEvent [rust_window: &QtWindow as "void*"] {
+                rust_window.drag_event(MouseEvent::Exit);
+            });
+        }
+        void dropEvent(QDropEvent *event) override {
+            QPoint pos = event->pos();
+            bool accepted = rust!(Slint_dropEvent [rust_window: &QtWindow as "void*", pos: qttypes::QPoint as "QPoint"] -> bool as "bool" {
+                let position = LogicalPoint::new(pos.x as _, pos.y as _);
+                rust_window.drag_event(MouseEvent::Released{position, button: PointerEventButton::Left})
+            });
+            if (accepted) {
+                event->acceptProposedAction();
+            } else {
+                event->ignore();
+            }
+        }
+
+        void trayIconActivated() {
+            rust!(Slint_trayIconActivated [rust_window: &QtWindow as "void*"] {
+                rust_window.activate_system_tray_icon();
+            });
+        }
+        void screenChanged() {
+            rust!(Slint_screenChanged [rust_window: &QtWindow as "void*"] {
+                rust_window.update_monitor();
+            });
+        }
+        void menuEntryActivated(bool menu_bar, uint index) {
+            rust!(Slint_menuEntryActivated [rust_window: &QtWindow as "void*", menu_bar: bool as "bool", index: u32 as "uint"] {
+                rust_window.activate_menu_entry(menu_bar, index as usize);
+            });
+        }
+
         void keyPressEvent(QKeyEvent *event) override {
             uint modifiers = uint(event->modifiers());
             QString text =  event->text();
@@ -190,6 +273,54 @@ struct SlintWidget : QWidget {
             });
         }
 
+        bool event(QEvent *event) override {
+            switch (event->type()) {
+            case QEvent::TouchBegin:
+            case QEvent::TouchUpdate:
+            case QEvent::TouchEnd:
+            case QEvent::TouchCancel: {
+                auto touch_event = static_cast<QTouchEvent *>(event);
+                bool cancel = event->type() == QEvent::TouchCancel;
+        #if QT_VERSION >= QT_VERSION_CHECK(6, 0, 0)
+                const auto &points = touch_event->points();
+        #else
+                const auto &points = touch_event->touchPoints();
+        #endif
+                for (const auto &point : points) {
+        #if QT_VERSION >= QT_VERSION_CHECK(6, 0, 0)
+                    QPointF pos = point.position();
+        #else
+                    QPointF pos = point.pos();
+        #endif
+                    // QEventPoint::State has the same values as Qt::TouchPointState
+                    int state = int(point.state());
+                    bool pressed = state == Qt::TouchPointPressed;
+                    bool released = cancel || state == Qt::TouchPointReleased;
+                    bool stationary = state == Qt::TouchPointStationary;
+                    if (stationary && !released) {
+                        continue;
+                    }
+                    int id = point.id();
+                    rust!(Slint_touchEvent [rust_window: &QtWindow as "void*", id: i32 as "int", pos: qttypes::QPointF as "QPointF", pressed: bool as "bool", released: bool as "bool"] {
+                        let position = LogicalPoint::new(pos.x as _, pos.y as _);
+                        let event = if pressed {
+                            MouseEvent::Pressed { position, button: PointerEventButton::Left }
+                        } else if released {
+                            MouseEvent::Released { position, button: PointerEventButton::Left }
+                        } else {
+                            MouseEvent::Moved { position }
+                        };
+                        rust_window.touch_event(id as u64, event)
+                    });
+                }
+                event->accept();
+                return true;
+            }
+            default:
+                return QWidget::event(event);
+            }
+        }
+
         void customEvent(QEvent *event) override {
             if (event->type() == QEvent::User) {
                 rust!(Slint_updateWindowProps [rust_window: &QtWindow as "void*"] {
@@ -249,7 +380,7 @@ struct SlintWidget : QWidget {
         QVariant inputMethodQuery(Qt::InputMethodQuery query) const override {
             switch (query) {
             case Qt::ImCursorRectangle: {
-                return QRect(ime_position.x(), ime_position.y(), 1, 1);
+                return ime_cursor_rect;
             }
             default: break;
             }
@@ -516,10 +647,65 @@ fn adjust_rect_and_border_for_inner_drawing(rect: &mut qttypes::QRectF, border_w
 struct QtItemRenderer<'a> {
     painter: QPainterPtr,
     cache: &'a ItemCache<qttypes::QPixmap>,
+    filtered_layers: &'a RefCell<FilteredLayerCache>,
     window: &'a i_slint_core::api::Window,
     metrics: RenderingMetrics,
 }
 
+/// The layers of the `Effect` items filtered with their color matrix. The filter runs on the CPU,
+/// so its result is kept as long as the layer and the matrix don't change.
+#[derive(Default)]
+struct FilteredLayerCache {
+    /// The filtered image, and whether it was used in the current frame, keyed on the
+    /// `QPixmap::cacheKey()` of the layer and the bits of the matrix
+    entries: std::collections::HashMap<(i64, [u32; 20]), (qttypes::QImage, bool)>,
+}
+
+impl FilteredLayerCache {
+    fn get_or_filter(
+        &mut self,
+        layer: &qttypes::QPixmap,
+        color_matrix: &[f32; 20],
+    ) -> qttypes::QImage {
+        let cache_key = cpp!(unsafe [layer as "const QPixmap*"] -> i64 as "qint64" {
+            return layer->cacheKey();
+        });
+        let key = (cache_key, color_matrix.map(f32::to_bits));
+        let entry = self
+            .entries
+            .entry(key)
+            .or_insert_with(|| (apply_color_matrix(layer, color_matrix), false));
+        entry.1 = true;
+        entry.0.clone()
+    }
+
+    /// Drops the images that were not used since the last call.
+    fn end_frame(&mut self) {
+        self.entries.retain(|_, (_, used)| std::mem::take(used));
+    }
+}
+
+fn apply_color_matrix(layer: &qttypes::QPixmap, color_matrix: &[f32; 20]) -> qttypes::QImage {
+    let color_matrix_ptr = color_matrix.as_ptr();
+    cpp! { unsafe [layer as "const QPixmap*", color_matrix_ptr as "const float*"] -> qttypes::QImage as "QImage" {
+        QImage img = layer->toImage().convertToFormat(QImage::Format_ARGB32);
+        for (int y = 0; y < img.height(); ++y) {
+            QRgb *line = reinterpret_cast<QRgb *>(img.scanLine(y));
+            for (int x = 0; x < img.width(); ++x) {
+                float in[4] = { qRed(line[x]) / 255.f, qGreen(line[x]) / 255.f, qBlue(line[x]) / 255.f, qAlpha(line[x]) / 255.f };
+                int out[4];
+                for (int row = 0; row < 4; ++row) {
+                    const float *m = color_matrix_ptr + row * 5;
+                    float value = m[0] * in[0] + m[1] * in[1] + m[2] * in[2] + m[3] * in[3] + m[4];
+                    out[row] = qBound(0, qRound(value * 255), 255);
+                }
+                line[x] = qRgba(out[0], out[1], out[2], out[3]);
+            }
+        }
+        return img;
+    }}
+}
+
 impl ItemRenderer for QtItemRenderer<'_> {
     fn draw_rectangle(&mut self, rect_: Pin<&items::Rectangle>, _: &ItemRc) {
         let rect: qttypes::QRectF = get_geometry!(items::Rectangle, rect_);
@@ -537,7 +723,7 @@ fn draw_border_rectangle(&mut self, rect: std::pin::Pin<&items::BorderRectangle>
             rect.background(),
             rect.border_color(),
             rect.border_width().get(),
-            rect.border_radius().get(),
+            rect.border_radii(),
         );
     }
 
@@ -545,7 +731,7 @@ fn draw_image(&mut self, image: Pin<&items::ImageItem>, item_rc: &ItemRc) {
         let dest_rect: qttypes::QRectF = get_geometry!(items::ImageItem, image);
         self.draw_image_impl(
             item_rc,
-            items::ImageItem::FIELD_OFFSETS.source.apply_pin(image),
+            || image.displayed_source(),
             dest_rect,
             None,
             items::ImageItem::FIELD_OFFSETS.width.apply_pin(image),
@@ -566,7 +752,7 @@ fn draw_clipped_image(&mut self, image: Pin<&items::ClippedImage>, item_rc: &Ite
         };
         self.draw_image_impl(
             item_rc,
-            items::ClippedImage::FIELD_OFFSETS.source.apply_pin(image),
+            || image.displayed_source(),
             dest_rect,
             Some(source_rect),
             items::ClippedImage::FIELD_OFFSETS.width.apply_pin(image),
@@ -578,11 +764,12 @@ fn draw_clipped_image(&mut self, image: Pin<&items::ClippedImage>, item_rc: &Ite
     }
 
     fn draw_text(&mut self, text: std::pin::Pin<&items::Text>, _: &ItemRc) {
-        let rect: qttypes::QRectF = get_geometry!(items::Text, text);
+        let mut rect: qttypes::QRectF = get_geometry!(items::Text, text);
         let fill_brush: qttypes::QBrush = into_qbrush(text.color(), rect.width, rect.height);
         let mut string: qttypes::QString = text.text().as_str().into();
-        let font: QFont = get_font(text.font_request(WindowInner::from_pub(&self.window)));
-        let flags = match text.horizontal_alignment() {
+        let mut font: QFont = get_font(text.font_request(WindowInner::from_pub(&self.window)));
+        font.set_decorations(text.underline(), text.strikethrough());
+        let mut flags = match text.horizontal_alignment() {
             TextHorizontalAlignment::Left => key_generated::Qt_AlignmentFlag_AlignLeft,
             TextHorizontalAlignment::Center => key_generated::Qt_AlignmentFlag_AlignHCenter,
             TextHorizontalAlignment::Right => key_generated::Qt_AlignmentFlag_AlignRight,
@@ -595,11 +782,123 @@ fn draw_text(&mut self, text: std::pin::Pin<&items::Text>, _: &ItemRc) {
             TextWrap::WordWrap => key_generated::Qt_TextFlag_TextWordWrap,
         };
         let elide = text.overflow() == TextOverflow::Elide;
+        let elide_mode: i32 = match text.elide_position() {
+            TextElidePosition::End => 1,    // Qt::ElideRight
+            TextElidePosition::Start => 0,  // Qt::ElideLeft
+            TextElidePosition::Middle => 2, // Qt::ElideMiddle
+        };
+        let max_lines = text.max_lines();
+        let rtl = i_slint_core::textlayout::is_rtl(text.text().as_str(), text.text_direction());
         let painter: &mut QPainterPtr = &mut self.painter;
-        cpp! { unsafe [painter as "QPainterPtr*", rect as "QRectF", fill_brush as "QBrush", mut string as "QString", flags as "int", font as "QFont", elide as "bool"] {
+        if text.rich_text() {
+            // Our markup subset is valid HTML, so let QTextDocument do the layout
+            cpp! { unsafe [painter as "QPainterPtr*", rect as "QRectF", fill_brush as "QBrush", string as "QString", flags as "int", font as "QFont", rtl as "bool", elide as "bool", max_lines as "int"] {
+                QTextDocument doc;
+                doc.setDocumentMargin(0);
+                doc.setDefaultFont(font);
+                QTextOption options = doc.defaultTextOption();
+                options.setAlignment(Qt::Alignment(flags & Qt::AlignHorizontal_Mask) | Qt::AlignAbsolute);
+                options.setTextDirection(rtl ? Qt::RightToLeft : Qt::LeftToRight);
+                options.setWrapMode((flags & Qt::TextWordWrap) ? QTextOption::WordWrap : QTextOption::NoWrap);
+                doc.setDefaultTextOption(options);
+                doc.setHtml(string);
+                doc.setTextWidth(rect.width());
+
+                // Returns the number of lines, and the widest line
+                auto measure_lines = [&doc] {
+                    int line_count = 0;
+                    qreal width = 0;
+                    for (QTextBlock block = doc.begin(); block.isValid(); block = block.next()) {
+                        QTextLayout *layout = block.layout();
+                        for (int i = 0; i < layout->lineCount(); ++i) {
+                            width = qMax(width, layout->lineAt(i).naturalTextWidth());
+                            ++line_count;
+                        }
+                    }
+                    return std::make_pair(line_count, width);
+                };
+                // Find the lines that fit in the height and in max_lines. The first line is always shown.
+                int visible_lines = 0;
+                int visible_end = doc.characterCount() - 1;
+                for (QTextBlock block = doc.begin(); block.isValid(); block = block.next()) {
+                    QTextLayout *layout = block.layout();
+                    bool truncated = false;
+                    for (int i = 0; i < layout->lineCount(); ++i) {
+                        QTextLine line = layout->lineAt(i);
+                        qreal bottom = layout->position().y() + line.y() + line.height();
+                        if (visible_lines > 0 && ((max_lines > 0 && visible_lines >= max_lines) || bottom > rect.height())) {
+                            truncated = true;
+                            break;
+                        }
+                        visible_end = block.position() + line.textStart() + line.textLength();
+                        ++visible_lines;
+                    }
+                    if (truncated) {
+                        break;
+                    }
+                }
+                auto fits = [&] {
+                    auto [line_count, width] = measure_lines();
+                    return line_count <= visible_lines && width <= rect.width();
+                };
+                bool overflows = visible_end < doc.characterCount() - 1;
+                if (overflows || (elide && !fits())) {
+                    QTextCursor cursor(&doc);
+                    cursor.setPosition(visible_end);
+                    cursor.movePosition(QTextCursor::End, QTextCursor::KeepAnchor);
+                    cursor.removeSelectedText();
+                    if (elide) {
+                        // The markup is elided at the end, whatever the elide-position, by removing
+                        // characters before the ellipsis until the lines fit
+                        cursor.insertText(QString(QChar(0x2026)));
+                        cursor.movePosition(QTextCursor::Left);
+                        while (cursor.position() > 0 && !fits()) {
+                            cursor.deletePreviousChar();
+                        }
+                    }
+                }
+
+                qreal dy = 0;
+                if (flags & Qt::AlignVCenter) {
+                    dy = (rect.height() - doc.size().height()) / 2;
+                } else if (flags & Qt::AlignBottom) {
+                    dy = rect.height() - doc.size().height();
+                }
+                QAbstractTextDocumentLayout::PaintContext context;
+                context.palette.setBrush(QPalette::Text, fill_brush);
+                context.clip = QRectF(0, -dy, rect.width(), rect.height());
+                (*painter)->save();
+                (*painter)->translate(rect.x(), rect.y() + dy);
+                doc.documentLayout()->draw(painter->get(), context);
+                (*painter)->restore();
+            }}
+            return;
+        }
+        cpp! { unsafe [painter as "QPainterPtr*", mut rect as "QRectF", fill_brush as "QBrush", mut string as "QString", mut flags as "int", font as "QFont", elide as "bool", elide_mode as "int", max_lines as "int", rtl as "bool"] {
+            (*painter)->save();
+            (*painter)->setLayoutDirection(rtl ? Qt::RightToLeft : Qt::LeftToRight);
             (*painter)->setFont(font);
             (*painter)->setPen(QPen(fill_brush, 0));
             (*painter)->setBrush(Qt::NoBrush);
+            // The alignment is given in absolute terms, independent of the text direction
+            flags |= Qt::AlignAbsolute;
+            bool clipped = false;
+            if (max_lines > 0) {
+                // Restrict the area to the height of max_lines, keeping the vertical alignment
+                QFontMetricsF fm(font);
+                qreal max_height = max_lines * fm.height() + (max_lines - 1) * fm.leading();
+                if (max_height < rect.height()) {
+                    if (flags & Qt::AlignVCenter) {
+                        rect.moveTop(rect.top() + (rect.height() - max_height) / 2);
+                    } else if (flags & Qt::AlignBottom) {
+                        rect.moveTop(rect.bottom() - max_height);
+                    }
+                    rect.setHeight(max_height);
+                    (*painter)->save();
+                    (*painter)->setClipRect(rect, Qt::IntersectClip);
+                    clipped = true;
+                }
+            }
             if (!elide) {
                 (*painter)->drawText(rect, flags, string);
             } else if (!(flags & Qt::TextWordWrap)) {
@@ -608,11 +907,11 @@ fn draw_text(&mut self, text: std::pin::Pin<&items::Text>, _: &ItemRc) {
                 while (!string.isEmpty()) {
                     int pos = string.indexOf('\n');
                     if (pos < 0) {
-                        elided += fm.elidedText(string, Qt::ElideRight, rect.width());
+                        elided += fm.elidedText(string, Qt::TextElideMode(elide_mode), rect.width());
                         break;
                     }
                     QString line = string.left(pos);
-                    elided += fm.elidedText(line, Qt::ElideRight, rect.width());
+                    elided += fm.elidedText(line, Qt::TextElideMode(elide_mode), rect.width());
                     elided += '\n';
                     string = string.mid(pos + 1);
                 }
@@ -652,6 +951,10 @@ fn draw_text(&mut self, text: std::pin::Pin<&items::Text>, _: &ItemRc) {
                 }
                 (*painter)->drawText(rect, flags, elided);
             }
+            if (clipped) {
+                (*painter)->restore();
+            }
+            (*painter)->restore();
         }}
     }
 
@@ -659,9 +962,10 @@ fn draw_text_input(&mut self, text_input: std::pin::Pin<&items::TextInput>, _: &
         let rect: qttypes::QRectF = get_geometry!(items::TextInput, text_input);
         let fill_brush: qttypes::QBrush = into_qbrush(text_input.color(), rect.width, rect.height);
 
-        let font: QFont = get_font(
+        let mut font: QFont = get_font(
             text_input.font_request(&WindowInner::from_pub(&self.window).window_adapter()),
         );
+        font.set_decorations(text_input.underline(), text_input.strikethrough());
         let flags = match text_input.horizontal_alignment() {
             TextHorizontalAlignment::Left => key_generated::Qt_AlignmentFlag_AlignLeft,
             TextHorizontalAlignment::Center => key_generated::Qt_AlignmentFlag_AlignHCenter,
@@ -787,6 +1091,16 @@ fn draw_path(&mut self, path: Pin<&items::Path>, _: &ItemRc) {
         let fill_brush: qttypes::QBrush = into_qbrush(path.fill(), rect.width, rect.height);
         let stroke_brush: qttypes::QBrush = into_qbrush(path.stroke(), rect.width, rect.height);
         let stroke_width: f32 = path.stroke_width().get();
+        let cap_style: key_generated::Qt_PenCapStyle = match path.stroke_line_cap() {
+            LineCap::Butt => key_generated::Qt_PenCapStyle_FlatCap,
+            LineCap::Round => key_generated::Qt_PenCapStyle_RoundCap,
+            LineCap::Square => key_generated::Qt_PenCapStyle_SquareCap,
+        };
+        let join_style: key_generated::Qt_PenJoinStyle = match path.stroke_line_join() {
+            LineJoin::Miter => key_generated::Qt_PenJoinStyle_MiterJoin,
+            LineJoin::Round => key_generated::Qt_PenJoinStyle_RoundJoin,
+            LineJoin::Bevel => key_generated::Qt_PenJoinStyle_BevelJoin,
+        };
         let (offset, path_events) = path.fitted_path_events();
         let pos = qttypes::QPoint { x: offset.x as _, y: offset.y as _ };
         let mut painter_path = QPainterPath::default();
@@ -830,11 +1144,13 @@ fn to_qpointf(p: Point) -> qttypes::QPointF {
                 mut painter_path as "QPainterPath",
                 fill_brush as "QBrush",
                 stroke_brush as "QBrush",
-                stroke_width as "float"] {
+                stroke_width as "float",
+                cap_style as "Qt::PenCapStyle",
+                join_style as "Qt::PenJoinStyle"] {
             (*painter)->save();
             auto cleanup = qScopeGuard([&] { (*painter)->restore(); });
             (*painter)->translate(pos);
-            (*painter)->setPen(stroke_width > 0 ? QPen(stroke_brush, stroke_width) : Qt::NoPen);
+            (*painter)->setPen(stroke_width > 0 ? QPen(stroke_brush, stroke_width, Qt::SolidLine, cap_style, join_style) : Qt::NoPen);
             (*painter)->setBrush(fill_brush);
             (*painter)->drawPath(painter_path);
         }}
@@ -864,7 +1180,7 @@ fn draw_box_shadow(&mut self, box_shadow: Pin<&items::BoxShadow>, item_rc: &Item
                     Brush::SolidColor(box_shadow.color()),
                     Brush::default(),
                     0.,
-                    box_shadow.border_radius().get(),
+                    LogicalBorderRadius::new_uniform(box_shadow.border_radius()),
                 );
 
                 drop(painter_);
@@ -946,10 +1262,100 @@ fn visit_layer(&mut self, layer_item: Pin<&Layer>, self_rc: &ItemRc) -> Renderin
         }
     }
 
+    fn visit_effect(&mut self, effect_item: Pin<&Effect>, self_rc: &ItemRc) -> RenderingResult {
+        if effect_item.is_identity() {
+            self.cache.release(self_rc);
+            return RenderingResult::ContinueRenderingChildren;
+        }
+
+        let layer_image = self.render_children_layer(self_rc);
+        let blur_radius: f32 = effect_item.effect_blur().get().max(0.);
+        let img = match effect_item.color_matrix() {
+            Some(color_matrix) => {
+                self.filtered_layers.borrow_mut().get_or_filter(&layer_image, &color_matrix)
+            }
+            None => cpp!(unsafe [layer_image as "QPixmap"] -> qttypes::QImage as "QImage" {
+                return layer_image.toImage();
+            }),
+        };
+        let painter: &mut QPainterPtr = &mut self.painter;
+        cpp! { unsafe [
+                painter as "QPainterPtr*",
+                img as "QImage",
+                blur_radius as "float"
+            ] {
+            if (blur_radius <= 0) {
+                (*painter)->drawImage(0, 0, img);
+                return;
+            }
+
+            class PublicGraphicsBlurEffect : public QGraphicsBlurEffect {
+            public:
+                // Make public what's protected
+                using QGraphicsBlurEffect::draw;
+            };
+
+            // Need a scene for the effect source private to draw()
+            QGraphicsScene scene;
+            auto pixmap_item = scene.addPixmap(QPixmap::fromImage(img));
+            auto blur_effect = new PublicGraphicsBlurEffect;
+            blur_effect->setBlurRadius(blur_radius);
+            blur_effect->setBlurHints(QGraphicsBlurEffect::QualityHint);
+            // takes ownership of the effect and registers the item with the effect as source.
+            pixmap_item->setGraphicsEffect(blur_effect);
+            blur_effect->draw(painter->get());
+        }}
+        RenderingResult::ContinueRenderingWithoutChildren
+    }
+
+    fn visit_drop_shadow(
+        &mut self,
+        drop_shadow_item: Pin<&DropShadow>,
+        self_rc: &ItemRc,
+    ) -> RenderingResult {
+        if drop_shadow_item.is_invisible() {
+            self.cache.release(self_rc);
+            return RenderingResult::ContinueRenderingChildren;
+        }
+
+        let layer_image = self.render_children_layer(self_rc);
+        let offset_x: f32 = drop_shadow_item.drop_shadow_offset_x().get();
+        let offset_y: f32 = drop_shadow_item.drop_shadow_offset_y().get();
+        let blur_radius: f32 = drop_shadow_item.drop_shadow_blur().get().max(0.);
+        let color: u32 = drop_shadow_item.drop_shadow_color().as_argb_encoded();
+        let painter: &mut QPainterPtr = &mut self.painter;
+        cpp! { unsafe [
+                painter as "QPainterPtr*",
+                layer_image as "QPixmap",
+                offset_x as "float",
+                offset_y as "float",
+                blur_radius as "float",
+                color as "QRgb"
+            ] {
+            class PublicGraphicsDropShadowEffect : public QGraphicsDropShadowEffect {
+            public:
+                // Make public what's protected
+                using QGraphicsDropShadowEffect::draw;
+            };
+
+            // Need a scene for the effect source private to draw()
+            QGraphicsScene scene;
+            auto pixmap_item = scene.addPixmap(layer_image);
+            auto shadow_effect = new PublicGraphicsDropShadowEffect;
+            shadow_effect->setOffset(offset_x, offset_y);
+            shadow_effect->setBlurRadius(blur_radius);
+            shadow_effect->setColor(QColor::fromRgba(color));
+            // takes ownership of the effect and registers the item with the effect as source.
+            pixmap_item->setGraphicsEffect(shadow_effect);
+            shadow_effect->draw(painter->get());
+        }}
+        RenderingResult::ContinueRenderingWithoutChildren
+    }
+
     fn combine_clip(
         &mut self,
         rect: LogicalRect,
-        radius: LogicalLength,
+        radius: LogicalBorderRadius,
         border_width: LogicalLength,
     ) -> bool {
         let mut border_width: f32 = border_width.get();
@@ -960,14 +1366,19 @@ fn combine_clip(
             height: rect.height() as _,
         };
         adjust_rect_and_border_for_inner_drawing(&mut clip_rect, &mut border_width);
+        let is_zero = radius.is_zero();
+        let [top_left, top_right, bottom_right, bottom_left]: [f32; 4] = [
+            radius.top_left.get(),
+            radius.top_right.get(),
+            radius.bottom_right.get(),
+            radius.bottom_left.get(),
+        ];
         let painter: &mut QPainterPtr = &mut self.painter;
-        cpp! { unsafe [painter as "QPainterPtr*", clip_rect as "QRectF", radius as "float"] -> bool as "bool" {
-            if (radius <= 0) {
+        cpp! { unsafe [painter as "QPainterPtr*", clip_rect as "QRectF", is_zero as "bool", top_left as "float", top_right as "float", bottom_right as "float", bottom_left as "float"] -> bool as "bool" {
+            if (is_zero) {
                 (*painter)->setClipRect(clip_rect, Qt::IntersectClip);
             } else {
-                QPainterPath path;
-                path.addRoundedRect(clip_rect, radius, radius);
-                (*painter)->setClipPath(path, Qt::IntersectClip);
+                (*painter)->setClipPath(rounded_rect_path(clip_rect, top_left, top_right, bottom_right, bottom_left), Qt::IntersectClip);
             }
             return !(*painter)->clipBoundingRect().isEmpty();
         }}
@@ -1051,6 +1462,23 @@ fn rotate(&mut self, angle_in_degrees: f32) {
         }}
     }
 
+    fn scale(&mut self, x_factor: f32, y_factor: f32) {
+        let painter: &mut QPainterPtr = &mut self.painter;
+        cpp! { unsafe [painter as "QPainterPtr*", x_factor as "float", y_factor as "float"] {
+            (*painter)->scale(x_factor, y_factor);
+        }}
+    }
+
+    fn skew(&mut self, x_angle_in_degrees: f32, y_angle_in_degrees: f32) {
+        let x: f32 = x_angle_in_degrees.to_radians().tan();
+        let y: f32 = y_angle_in_degrees.to_radians().tan();
+        let painter: &mut QPainterPtr = &mut self.painter;
+        cpp! { unsafe [painter as "QPainterPtr*", x as "float", y as "float"] {
+            (*painter)->shear(x, 0);
+            (*painter)->shear(0, y);
+        }}
+    }
+
     fn apply_opacity(&mut self, opacity: f32) {
         let painter: &mut QPainterPtr = &mut self.painter;
         cpp! { unsafe [painter as "QPainterPtr*", opacity as "float"] {
@@ -1136,7 +1564,7 @@ impl QtItemRenderer<'_> {
     fn draw_image_impl(
         &mut self,
         item_rc: &ItemRc,
-        source_property: Pin<&Property<Image>>,
+        source: impl Fn() -> Image,
         dest_rect: qttypes::QRectF,
         source_rect: Option<qttypes::QRectF>,
         target_width: std::pin::Pin<&Property<LogicalLength>>,
@@ -1150,7 +1578,7 @@ fn draw_image_impl(
         debug_assert!(target_height.get() > LogicalLength::zero());
 
         let pixmap: qttypes::QPixmap = self.cache.get_or_update_cache_entry(item_rc, || {
-            let source = source_property.get();
+            let source = source();
             let origin = source.size();
             let source: &ImageInner = (&source).into();
 
@@ -1224,16 +1652,25 @@ fn draw_rectangle_impl(
         brush: Brush,
         border_color: Brush,
         mut border_width: f32,
-        border_radius: f32,
+        border_radius: LogicalBorderRadius,
     ) {
         let brush: qttypes::QBrush = into_qbrush(brush, rect.width, rect.height);
         let border_color: qttypes::QBrush = into_qbrush(border_color, rect.width, rect.height);
         adjust_rect_and_border_for_inner_drawing(&mut rect, &mut border_width);
-        cpp! { unsafe [painter as "QPainterPtr*", brush as "QBrush",  border_color as "QBrush", border_width as "float", border_radius as "float", rect as "QRectF"] {
+        let is_uniform = border_radius.as_uniform().is_some();
+        let [top_left, top_right, bottom_right, bottom_left]: [f32; 4] = [
+            border_radius.top_left.get(),
+            border_radius.top_right.get(),
+            border_radius.bottom_right.get(),
+            border_radius.bottom_left.get(),
+        ];
+        cpp! { unsafe [painter as "QPainterPtr*", brush as "QBrush",  border_color as "QBrush", border_width as "float", is_uniform as "bool", top_left as "float", top_right as "float", bottom_right as "float", bottom_left as "float", rect as "QRectF"] {
             (*painter)->setPen(border_width > 0 ? QPen(border_color, border_width) : Qt::NoPen);
             (*painter)->setBrush(brush);
-            if (border_radius > 0) {
-                (*painter)->drawRoundedRect(rect, border_radius, border_radius);
+            if (!is_uniform) {
+                (*painter)->drawPath(rounded_rect_path(rect, top_left, top_right, bottom_right, bottom_left));
+            } else if (top_left > 0) {
+                (*painter)->drawRoundedRect(rect, top_left, top_left);
             } else {
                 (*painter)->drawRect(rect);
             }
@@ -1274,9 +1711,10 @@ fn render_layer(
         })
     }
 
-    fn render_and_blend_layer(&mut self, alpha_tint: f32, self_rc: &ItemRc) -> RenderingResult {
+    /// Renders the children of the item into a layer that covers the item and its children.
+    fn render_children_layer(&mut self, self_rc: &ItemRc) -> qttypes::QPixmap {
         let current_clip = self.get_current_clip();
-        let mut layer_image = self.render_layer(self_rc, &|| {
+        self.render_layer(self_rc, &|| {
             // We don't need to include the size of the opacity item itself, since it has no content.
             let children_rect = i_slint_core::properties::evaluate_no_tracking(|| {
                 self_rc.geometry().union(
@@ -1291,7 +1729,11 @@ fn render_and_blend_layer(&mut self, alpha_tint: f32, self_rc: &ItemRc) -> Rende
                 width: children_rect.size.width as _,
                 height: children_rect.size.height as _,
             }
-        });
+        })
+    }
+
+    fn render_and_blend_layer(&mut self, alpha_tint: f32, self_rc: &ItemRc) -> RenderingResult {
+        let mut layer_image = self.render_children_layer(self_rc);
         self.save_state();
         self.apply_opacity(alpha_tint);
         {
@@ -1319,10 +1761,22 @@ pub struct QtWindow {
     rendering_metrics_collector: RefCell<Option<Rc<RenderingMetricsCollector>>>,
 
     cache: ItemCache<qttypes::QPixmap>,
+    filtered_layers: RefCell<FilteredLayerCache>,
 
     tree_structure_changed: RefCell<bool>,
 
     dark_color_scheme: OnceCell<Pin<Box<Property<bool>>>>,
+
+    /// The last constraints passed to apply_geometry_constraint, to re-apply them when the
+    /// `resizable` property changes
+    geometry_constraints: Cell<(LayoutInfo, LayoutInfo)>,
+
+    system_tray_icon: RefCell<Option<items::SystemTrayIconState>>,
+    /// The entries of the tray icon menu and its sub-menus. The index in this list identifies
+    /// the entry when it is activated.
+    menu_entries: RefCell<Vec<items::MenuEntry>>,
+    /// The entries of the menus of the menu bar, indexed like `menu_entries`
+    menu_bar_entries: RefCell<Vec<items::MenuEntry>>,
 }
 
 impl QtWindow {
@@ -1345,8 +1799,13 @@ pub fn new() -> Rc<Self> {
                 self_weak: self_weak.clone(),
                 rendering_metrics_collector: Default::default(),
                 cache: Default::default(),
+                filtered_layers: Default::default(),
                 tree_structure_changed: RefCell::new(false),
                 dark_color_scheme: Default::default(),
+                geometry_constraints: Default::default(),
+                system_tray_icon: Default::default(),
+                menu_entries: Default::default(),
+                menu_bar_entries: Default::default(),
             }
         });
         let widget_ptr = rc.widget_ptr();
@@ -1363,6 +1822,26 @@ fn widget_ptr(&self) -> NonNull<()> {
         unsafe { std::mem::transmute_copy::<QWidgetPtr, NonNull<_>>(&self.widget_ptr) }
     }
 
+    /// Set the min/max sizes on the QWidget. A window that is not resizable gets the size of its
+    /// `width` and `height` properties as fixed size, see [`WindowInner::size_limits`].
+    fn apply_size_constraints(&self, constraints_h: LayoutInfo, constraints_v: LayoutInfo) {
+        let widget_ptr = self.widget_ptr();
+        let (min_size, max_size) =
+            WindowInner::from_pub(&self.window).size_limits(constraints_h, constraints_v);
+        let min_width: f32 = min_size.width;
+        let min_height: f32 = min_size.height;
+        let mut max_width: f32 = max_size.width;
+        let mut max_height: f32 = max_size.height;
+        cpp! {unsafe [widget_ptr as "QWidget*",  min_width as "float", min_height as "float", mut max_width as "float", mut max_height as "float"] {
+            widget_ptr->setMinimumSize(QSize(min_width, min_height));
+            if (max_width > QWIDGETSIZE_MAX)
+                max_width = QWIDGETSIZE_MAX;
+            if (max_height > QWIDGETSIZE_MAX)
+                max_height = QWIDGETSIZE_MAX;
+            widget_ptr->setMaximumSize(QSize(max_width, max_height).expandedTo({1,1}));
+        }};
+    }
+
     fn paint_event(&self, painter: QPainterPtr) {
         let runtime_window = WindowInner::from_pub(&self.window);
         runtime_window.draw_contents(|components| {
@@ -1370,6 +1849,7 @@ fn paint_event(&self, painter: QPainterPtr) {
             let mut renderer = QtItemRenderer {
                 painter,
                 cache: &self.cache,
+                filtered_layers: &self.filtered_layers,
                 window: &self.window,
                 metrics: RenderingMetrics { layers_created: Some(0) },
             };
@@ -1381,6 +1861,7 @@ fn paint_event(&self, painter: QPainterPtr) {
                     *origin,
                 );
             }
+            self.filtered_layers.borrow_mut().end_frame();
 
             if let Some(collector) = &*self.rendering_metrics_collector.borrow() {
                 collector.measure_frame_rendered(&mut renderer);
@@ -1421,6 +1902,28 @@ fn mouse_event(&self, event: MouseEvent) {
         timer_event();
     }
 
+    fn touch_event(&self, id: u64, event: MouseEvent) {
+        WindowInner::from_pub(&self.window).process_touch_input(id, event);
+        timer_event();
+    }
+
+    fn drag_enter_event(
+        &self,
+        mime_type: SharedString,
+        data: SharedString,
+        position: LogicalPoint,
+    ) {
+        WindowInner::from_pub(&self.window).start_drag(mime_type, data);
+        self.drag_event(MouseEvent::Moved { position });
+    }
+
+    /// Returns true if the data was dropped on a `DropArea`
+    fn drag_event(&self, event: MouseEvent) -> bool {
+        let dropped = WindowInner::from_pub(&self.window).process_drag_event(event);
+        timer_event();
+        dropped
+    }
+
     fn key_event(&self, key: i32, text: qttypes::QString, qt_modifiers: u32, released: bool) {
         i_slint_core::animations::update_animations();
         let text: String = text.into();
@@ -1448,12 +1951,94 @@ fn close_popup(&self) {
         WindowInner::from_pub(&self.window).close_popup();
     }
 
+    /// Passes the screen that shows the window to the run-time window
+    fn update_monitor(&self) {
+        let widget_ptr = self.widget_ptr();
+        let mut name = qttypes::QString::default();
+        let mut size = qttypes::QSize::default();
+        let mut refresh_rate = 0.;
+        let (name_ref, size_ref, refresh_rate_ref) = (&mut name, &mut size, &mut refresh_rate);
+        let has_screen = cpp! {unsafe [widget_ptr as "QWidget*", name_ref as "QString*", size_ref as "QSize*", refresh_rate_ref as "qreal*"] -> bool as "bool" {
+            auto window_handle = widget_ptr->windowHandle();
+            auto screen = window_handle ? window_handle->screen() : nullptr;
+            if (!screen)
+                return false;
+            *name_ref = screen->name();
+            *size_ref = screen->size();
+            *refresh_rate_ref = screen->refreshRate();
+            return true;
+        }};
+        if has_screen {
+            WindowInner::from_pub(&self.window).set_monitor(i_slint_core::window::MonitorInfo {
+                name: name.to_string().into(),
+                size: LogicalSize::new(size.width as _, size.height as _),
+                refresh_rate: refresh_rate as f32,
+            });
+        }
+    }
+
+    fn activate_system_tray_icon(&self) {
+        let tray_icon = self.system_tray_icon.borrow().clone();
+        if let Some(tray_icon) = tray_icon {
+            tray_icon.activate();
+        }
+    }
+
+    fn activate_menu_entry(&self, menu_bar: bool, index: usize) {
+        let entries = if menu_bar { &self.menu_bar_entries } else { &self.menu_entries };
+        let entry = entries.borrow().get(index).cloned();
+        if let Some(entry) = entry {
+            entry.activate();
+        }
+    }
+
     fn free_graphics_resources(&self, component: ComponentRef) {
         // Invalidate caches:
         self.cache.component_destroyed(component);
     }
 }
 
+/// Adds the entries to the QMenu, and appends them and the entries of their sub-menus to `flat_entries`.
+/// `menu_bar` tells whether the entries are those of the menu bar or of the tray icon.
+fn fill_menu(
+    widget_ptr: NonNull<()>,
+    menu: *mut std::ffi::c_void,
+    entries: &[items::MenuEntry],
+    flat_entries: &mut Vec<items::MenuEntry>,
+    menu_bar: bool,
+) {
+    for entry in entries {
+        let index = flat_entries.len() as u32;
+        flat_entries.push(entry.clone());
+        let title: qttypes::QString = entry.title.as_str().into();
+        let enabled = entry.enabled;
+        if !entry.children.is_empty() {
+            let submenu = cpp!(unsafe [menu as "QMenu*", title as "QString", enabled as "bool"] -> *mut std::ffi::c_void as "QMenu*" {
+                auto submenu = menu->addMenu(title);
+                submenu->setEnabled(enabled);
+                return submenu;
+            });
+            fill_menu(widget_ptr, submenu, &entry.children, flat_entries, menu_bar);
+        } else {
+            let checkable = entry.checkable;
+            let checked = entry.checked;
+            cpp! {unsafe [widget_ptr as "SlintWidget*", menu as "QMenu*", title as "QString", enabled as "bool", checkable as "bool", checked as "bool", menu_bar as "bool", index as "uint"] {
+                if (title.isEmpty()) {
+                    menu->addSeparator();
+                    return;
+                }
+                auto action = menu->addAction(title);
+                action->setEnabled(enabled);
+                action->setCheckable(checkable);
+                action->setChecked(checked);
+                QObject::connect(action, &QAction::triggered, [widget_ptr, menu_bar, index] {
+                    widget_ptr->menuEntryActivated(menu_bar, index);
+                });
+            }};
+        }
+    }
+}
+
 impl WindowAdapter for QtWindow {
     fn window(&self) -> &i_slint_core::api::Window {
         &self.window
@@ -1481,9 +2066,13 @@ fn show(&self) {
         }
 
         let widget_ptr = self.widget_ptr();
-        cpp! {unsafe [widget_ptr as "QWidget*"] {
+        cpp! {unsafe [widget_ptr as "SlintWidget*"] {
             widget_ptr->show();
+            // Qt scales the window by itself, but the application can still show the screen information
+            QObject::connect(widget_ptr->windowHandle(), &QWindow::screenChanged, widget_ptr,
+                             &SlintWidget::screenChanged, Qt::UniqueConnection);
         }};
+        self.update_monitor();
         let qt_platform_name = cpp! {unsafe [] -> qttypes::QString as "QString" {
             return QGuiApplication::platformName();
         }};
@@ -1523,6 +2112,8 @@ fn apply_window_properties(&self, window_item: Pin<&items::WindowItem>) {
         let widget_ptr = self.widget_ptr();
         let title: qttypes::QString = window_item.title().as_str().into();
         let no_frame = window_item.no_frame();
+        let always_on_top = window_item.always_on_top();
+        let transparent = window_item.transparent();
         let mut size = qttypes::QSize {
             width: window_item.width().get().ceil() as _,
             height: window_item.height().get().ceil() as _,
@@ -1541,6 +2132,10 @@ fn apply_window_properties(&self, window_item: Pin<&items::WindowItem>) {
             }
         }
 
+        // The limits of a window that is not resizable follow its size, so apply them before resizing
+        let (constraints_h, constraints_v) = self.geometry_constraints.get();
+        self.apply_size_constraints(constraints_h, constraints_v);
+
         let background =
             into_qbrush(window_item.background(), size.width.into(), size.height.into());
 
@@ -1555,11 +2150,14 @@ fn apply_window_properties(&self, window_item: Pin<&items::WindowItem>) {
             }
         };
 
-        cpp! {unsafe [widget_ptr as "QWidget*",  title as "QString", size as "QSize", background as "QBrush", no_frame as "bool"] {
+        cpp! {unsafe [widget_ptr as "QWidget*",  title as "QString", size as "QSize", background as "QBrush", no_frame as "bool", always_on_top as "bool", transparent as "bool"] {
             if (size != widget_ptr->size()) {
                 widget_ptr->resize(size.expandedTo({1, 1}));
             }
             widget_ptr->setWindowFlag(Qt::FramelessWindowHint, no_frame);
+            widget_ptr->setWindowFlag(Qt::WindowStaysOnTopHint, always_on_top);
+            // Only has an effect before the window is shown
+            widget_ptr->setAttribute(Qt::WA_TranslucentBackground, transparent);
             widget_ptr->setWindowTitle(title);
             auto pal = widget_ptr->palette();
 
@@ -1579,25 +2177,101 @@ fn apply_window_properties(&self, window_item: Pin<&items::WindowItem>) {
         }};
     }
 
+    fn apply_system_tray_icon(&self, tray_icon: Option<items::SystemTrayIconState>) {
+        let widget_ptr = self.widget_ptr();
+        let tray_icon = match tray_icon {
+            Some(tray_icon) => tray_icon,
+            None => {
+                cpp! {unsafe [widget_ptr as "QWidget*"] {
+                    delete widget_ptr->findChild<QSystemTrayIcon*>(QString(), Qt::FindDirectChildrenOnly);
+                }};
+                self.system_tray_icon.replace(None);
+                self.menu_entries.borrow_mut().clear();
+                return;
+            }
+        };
+
+        let tooltip: qttypes::QString = tray_icon.tooltip.as_str().into();
+        let pixmap = image_to_pixmap((&tray_icon.icon).into(), None).unwrap_or_default();
+        let menu = cpp!(unsafe [widget_ptr as "SlintWidget*", pixmap as "QPixmap", tooltip as "QString"] -> *mut std::ffi::c_void as "QMenu*" {
+            auto tray_icon = widget_ptr->findChild<QSystemTrayIcon*>(QString(), Qt::FindDirectChildrenOnly);
+            if (!tray_icon) {
+                tray_icon = new QSystemTrayIcon(widget_ptr);
+                QObject::connect(tray_icon, &QSystemTrayIcon::activated, [widget_ptr](QSystemTrayIcon::ActivationReason reason) {
+                    if (reason == QSystemTrayIcon::Trigger || reason == QSystemTrayIcon::DoubleClick) {
+                        widget_ptr->trayIconActivated();
+                    }
+                });
+            }
+            tray_icon->setIcon(QIcon(pixmap));
+            tray_icon->setToolTip(tooltip);
+            // Re-create the menu, the old one may still be shown so delete it later.
+            if (auto old_menu = tray_icon->contextMenu()) {
+                old_menu->deleteLater();
+            }
+            auto menu = new QMenu(widget_ptr);
+            tray_icon->setContextMenu(menu);
+            tray_icon->show();
+            return menu;
+        });
+
+        let mut entries = Vec::new();
+        fill_menu(widget_ptr, menu, &tray_icon.menu, &mut entries, false);
+        *self.menu_entries.borrow_mut() = entries;
+        self.system_tray_icon.replace(Some(tray_icon));
+    }
+
+    fn apply_menu_bar(&self, menus: Option<Vec<items::MenuEntry>>) -> bool {
+        let widget_ptr = self.widget_ptr();
+        let menus = match menus {
+            Some(menus) => menus,
+            None => {
+                cpp! {unsafe [widget_ptr as "QWidget*"] {
+                    delete widget_ptr->findChild<QMenuBar*>(QString(), Qt::FindDirectChildrenOnly);
+                }};
+                self.menu_bar_entries.borrow_mut().clear();
+                return false;
+            }
+        };
+        // Only a menu bar integrated in the platform (the global menu bar of macOS, or the one
+        // of some Linux desktops) is used. Otherwise, the application shows its own in the window.
+        let menu_bar = cpp!(unsafe [widget_ptr as "QWidget*"] -> *mut std::ffi::c_void as "QMenuBar*" {
+            auto menu_bar = widget_ptr->findChild<QMenuBar*>(QString(), Qt::FindDirectChildrenOnly);
+            if (!menu_bar) {
+                menu_bar = new QMenuBar(widget_ptr);
+            }
+            if (!menu_bar->isNativeMenuBar()) {
+                delete menu_bar;
+                return nullptr;
+            }
+            menu_bar->clear();
+            return menu_bar;
+        });
+        let mut entries = Vec::new();
+        if !menu_bar.is_null() {
+            for menu in menus {
+                let title: qttypes::QString = menu.title.as_str().into();
+                let enabled = menu.enabled;
+                let qmenu = cpp!(unsafe [menu_bar as "QMenuBar*", title as "QString", enabled as "bool"] -> *mut std::ffi::c_void as "QMenu*" {
+                    auto menu = menu_bar->addMenu(title);
+                    menu->setEnabled(enabled);
+                    return menu;
+                });
+                fill_menu(widget_ptr, qmenu, &menu.children, &mut entries, true);
+            }
+        }
+        *self.menu_bar_entries.borrow_mut() = entries;
+        !menu_bar.is_null()
+    }
+
     /// Set the min/max sizes on the QWidget
     fn apply_geometry_constraint(
         &self,
         constraints_h: i_slint_core::layout::LayoutInfo,
         constraints_v: i_slint_core::layout::LayoutInfo,
     ) {
-        let widget_ptr = self.widget_ptr();
-        let min_width: f32 = constraints_h.min.min(constraints_h.max);
-        let min_height: f32 = constraints_v.min.min(constraints_v.max);
-        let mut max_width: f32 = constraints_h.max.max(constraints_h.min);
-        let mut max_height: f32 = constraints_v.max.max(constraints_v.min);
-        cpp! {unsafe [widget_ptr as "QWidget*",  min_width as "float", min_height as "float", mut max_width as "float", mut max_height as "float"] {
-            widget_ptr->setMinimumSize(QSize(min_width, min_height));
-            if (max_width > QWIDGETSIZE_MAX)
-                max_width = QWIDGETSIZE_MAX;
-            if (max_height > QWIDGETSIZE_MAX)
-                max_height = QWIDGETSIZE_MAX;
-            widget_ptr->setMaximumSize(QSize(max_width, max_height).expandedTo({1,1}));
-        }};
+        self.geometry_constraints.set((constraints_h, constraints_v));
+        self.apply_size_constraints(constraints_h, constraints_v);
     }
 
     fn register_component(&self) {
@@ -1630,6 +2304,54 @@ fn create_popup(&self, geometry: Rect) -> Option<Rc<dyn WindowAdapter>> {
         Some(popup_window as _)
     }
 
+    fn drag_threshold(&self) -> LogicalLength {
+        let distance = cpp! {unsafe [] -> i32 as "int" {
+            return QApplication::startDragDistance();
+        }};
+        LogicalLength::new(distance as _)
+    }
+
+    fn double_click_interval(&self) -> std::time::Duration {
+        let interval = cpp! {unsafe [] -> i32 as "int" {
+            return QApplication::doubleClickInterval();
+        }};
+        std::time::Duration::from_millis(interval.max(0) as u64)
+    }
+
+    fn start_drag(
+        &self,
+        mime_type: &str,
+        data: &str,
+        image: &i_slint_core::graphics::Image,
+    ) -> bool {
+        let widget_ptr = self.widget_ptr();
+        let mime_type: qttypes::QString = mime_type.into();
+        let data: qttypes::QString = data.into();
+        let image: &ImageInner = image.into();
+        let pixmap = match image {
+            ImageInner::None => None,
+            r => image_to_pixmap(r, None),
+        }
+        .unwrap_or_default();
+        // QDrag::exec runs its own event loop until the data is dropped. The drop on one of our
+        // own DropArea is then handled by SlintWidget::dropEvent.
+        cpp! {unsafe [widget_ptr as "QWidget*", mime_type as "QString", data as "QString", pixmap as "QPixmap"] {
+            auto mime_data = new QMimeData;
+            mime_data->setData(mime_type, data.toUtf8());
+            auto drag = new QDrag(widget_ptr);
+            drag->setMimeData(mime_data);
+            if (!pixmap.isNull()) {
+                drag->setPixmap(pixmap);
+            }
+            drag->exec(Qt::CopyAction | Qt::MoveAction);
+            // The release event was consumed by the drag operation
+            if (auto slint_widget = dynamic_cast<SlintWidget*>(widget_ptr)) {
+                slint_widget->isMouseButtonDown = false;
+            }
+        }};
+        true
+    }
+
     fn set_mouse_cursor(&self, cursor: MouseCursor) {
         let widget_ptr = self.widget_ptr();
         //unidirectional resize cursors are replaced with bidirectional ones
@@ -1669,6 +2391,27 @@ fn set_mouse_cursor(&self, cursor: MouseCursor) {
         }};
     }
 
+    fn set_custom_mouse_cursor(
+        &self,
+        image: &i_slint_core::graphics::Image,
+        hotspot: LogicalPoint,
+        fallback: MouseCursor,
+    ) {
+        let pixmap = match image.into() {
+            &ImageInner::None => None,
+            r => image_to_pixmap(r, None),
+        };
+        let pixmap = match pixmap {
+            Some(pixmap) => pixmap,
+            None => return self.set_mouse_cursor(fallback),
+        };
+        let widget_ptr = self.widget_ptr();
+        let hotspot = qttypes::QPoint { x: hotspot.x as _, y: hotspot.y as _ };
+        cpp! {unsafe [widget_ptr as "QWidget*", pixmap as "QPixmap", hotspot as "QPoint"] {
+            widget_ptr->setCursor(QCursor{pixmap, hotspot.x(), hotspot.y()});
+        }};
+    }
+
     fn enable_input_method(&self, input: i_slint_core::items::InputType) {
         let enable: bool = matches!(input, i_slint_core::items::InputType::Text);
         let widget_ptr = self.widget_ptr();
@@ -1684,15 +2427,27 @@ fn disable_input_method(&self) {
         }};
     }
 
-    fn set_ime_position(&self, position: LogicalPoint) {
-        let pos = qttypes::QPoint { x: position.x as _, y: position.y as _ };
+    fn set_ime_cursor_rect(&self, cursor_rect: LogicalRect) {
+        let rect = qttypes::QRectF {
+            x: cursor_rect.origin.x as _,
+            y: cursor_rect.origin.y as _,
+            width: cursor_rect.width() as _,
+            height: cursor_rect.height() as _,
+        };
         let widget_ptr = self.widget_ptr();
-        cpp! {unsafe [widget_ptr as "SlintWidget*", pos as "QPoint"]  {
-            widget_ptr->ime_position = pos;
+        cpp! {unsafe [widget_ptr as "SlintWidget*", rect as "QRectF"]  {
+            widget_ptr->ime_cursor_rect = rect.toAlignedRect();
             QGuiApplication::inputMethod()->update(Qt::ImQueryInput);
         }};
     }
 
+    fn tooltip_delay(&self) -> core::time::Duration {
+        let delay = cpp!(unsafe [] -> i32 as "int" {
+            return qApp->style()->styleHint(QStyle::SH_ToolTip_WakeUpDelay);
+        });
+        core::time::Duration::from_millis(delay.max(0) as u64)
+    }
+
     fn renderer(&self) -> &dyn Renderer {
         self
     }
@@ -1920,15 +2675,32 @@ fn accessible_item(item: Option<ItemRc>) -> Option<ItemRc> {
 }
 
 fn get_font(request: FontRequest) -> QFont {
-    let family: qttypes::QString = request.family.unwrap_or_default().as_str().into();
+    // The family lists are passed as newline separated strings
+    let families: qttypes::QString = request.families().collect::<Vec<_>>().join("\n").into();
+    let fallback_families: qttypes::QString = i_slint_core::graphics::fallback_font_families()
+        .iter()
+        .map(|family| family.as_str())
+        .collect::<Vec<_>>()
+        .join("\n")
+        .into();
     let pixel_size: f32 = request.pixel_size.map_or(0., |logical_size| logical_size.get());
     let weight: i32 = request.weight.unwrap_or(0);
     let letter_spacing: f32 =
         request.letter_spacing.map_or(0., |logical_spacing| logical_spacing.get());
-    cpp!(unsafe [family as "QString", pixel_size as "float", weight as "int", letter_spacing as "float"] -> QFont as "QFont" {
+    let word_spacing: f32 =
+        request.word_spacing.map_or(0., |logical_spacing| logical_spacing.get());
+    cpp!(unsafe [families as "QString", fallback_families as "QString", pixel_size as "float", weight as "int", letter_spacing as "float", word_spacing as "float"] -> QFont as "QFont" {
         QFont f;
-        if (!family.isEmpty())
-            f.setFamily(family);
+        QStringList family_list = families.isEmpty() ? QStringList() : families.split(QLatin1Char('\n'));
+        if (!family_list.isEmpty())
+            f.setFamily(family_list.first());
+    #if QT_VERSION >= QT_VERSION_CHECK(5, 13, 0)
+        // Qt tries the other families of the list for the characters that the first one doesn't support
+        if (family_list.size() > 1 || !fallback_families.isEmpty()) {
+            QStringList fallback_list = fallback_families.isEmpty() ? QStringList() : fallback_families.split(QLatin1Char('\n'));
+            f.setFamilies(QStringList(f.family()) + family_list.mid(1) + fallback_list);
+        }
+    #endif
         if (pixel_size > 0)
             f.setPixelSize(pixel_size);
         if (weight > 0) {
@@ -1939,6 +2711,7 @@ fn get_font(request: FontRequest) -> QFont {
     #endif
         }
         f.setLetterSpacing(QFont::AbsoluteSpacing, letter_spacing);
+        f.setWordSpacing(word_spacing);
         // Mark all font properties as resolved, to avoid inheriting font properties
         // from the widget hierarchy. Later we call QPainter::setFont, which would
         // merge in unset properties (such as bold, etc.) that it retrieved from
@@ -1968,6 +2741,13 @@ fn text_size(&self, text: &str, max_width: Option<f32>) -> LogicalSize {
         }};
         LogicalSize::new(size.width as _, size.height as _)
     }
+
+    fn set_decorations(&mut self, underline: bool, strikethrough: bool) {
+        cpp! { unsafe [self as "QFont*", underline as "bool", strikethrough as "bool"] {
+            self->setUnderline(underline);
+            self->setStrikeOut(strikethrough);
+        }}
+    }
 }
 
 thread_local! {
@@ -1979,15 +2759,19 @@ fn text_size(&self, text: &str, max_width: Option<f32>) -> LogicalSize {
 pub(crate) fn timer_event() {
     i_slint_core::platform::update_timers_and_animations();
 
-    let timeout = i_slint_core::timers::TimerList::next_timeout().map(|instant| {
-        let now = std::time::Instant::now();
-        let instant: std::time::Instant = instant.into();
-        if instant > now {
-            instant.duration_since(now).as_millis() as i32
-        } else {
-            0
-        }
-    });
+    let timeout = if i_slint_core::properties::ChangeTracker::has_pending_change_handlers() {
+        Some(0)
+    } else {
+        i_slint_core::timers::TimerList::next_timeout().map(|instant| {
+            let now = std::time::Instant::now();
+            let instant: std::time::Instant = instant.into();
+            if instant > now {
+                instant.duration_since(now).as_millis() as i32
+            } else {
+                0
+            }
+        })
+    };
     if let Some(timeout) = timeout {
         cpp! { unsafe [timeout as "int"] {
             ensure_initialized(true);
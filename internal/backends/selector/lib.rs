@@ -3,7 +3,14 @@
 
 #![doc = include_str!("README.md")]
 #![doc(html_logo_url = "https://slint-ui.com/logo/slint-logo-square-light.svg")]
-#![cfg_attr(not(any(feature = "i-slint-backend-qt", feature = "i-slint-backend-winit")), no_std)]
+#![cfg_attr(
+    not(any(
+        feature = "i-slint-backend-qt",
+        feature = "i-slint-backend-winit",
+        all(feature = "i-slint-backend-linuxkms", target_os = "linux")
+    )),
+    no_std
+)]
 
 extern crate alloc;
 
@@ -22,6 +29,11 @@ fn create_default_backend() -> Box<dyn Platform + 'static> {
         fn create_default_backend() -> Box<dyn Platform + 'static> {
             Box::new(i_slint_backend_winit::Backend::new(None))
         }
+    } else if #[cfg(all(feature = "i-slint-backend-linuxkms", target_os = "linux"))] {
+        use i_slint_backend_linuxkms as default_backend;
+        fn create_default_backend() -> Box<dyn Platform + 'static> {
+            Box::new(i_slint_backend_linuxkms::Backend::new())
+        }
     } else {
 
     }
@@ -30,7 +42,8 @@ fn create_default_backend() -> Box<dyn Platform + 'static> {
 cfg_if::cfg_if! {
     if #[cfg(any(
             all(feature = "i-slint-backend-qt", not(no_qt)),
-            feature = "i-slint-backend-winit"
+            feature = "i-slint-backend-winit",
+            all(feature = "i-slint-backend-linuxkms", target_os = "linux")
         ))] {
         pub fn create_backend() -> Box<dyn Platform + 'static>  {
 
@@ -57,6 +70,8 @@ pub fn create_backend() -> Box<dyn Platform + 'static>  {
                 "qt" => return Box::new(i_slint_backend_qt::Backend),
                 #[cfg(feature = "i-slint-backend-winit")]
                 "winit" => return Box::new(i_slint_backend_winit::Backend::new((!_renderer.is_empty()).then(|| _renderer))),
+                #[cfg(all(feature = "i-slint-backend-linuxkms", target_os = "linux"))]
+                "linuxkms" => return Box::new(i_slint_backend_linuxkms::Backend::new()),
                 _ => {},
             }
 
@@ -94,4 +109,6 @@ pub fn use_modules() {
     i_slint_backend_qt::use_modules();
     #[cfg(feature = "i-slint-backend-winit")]
     i_slint_backend_winit::use_modules();
+    #[cfg(all(feature = "i-slint-backend-linuxkms", target_os = "linux"))]
+    i_slint_backend_linuxkms::use_modules();
 }
@@ -4,26 +4,107 @@
 #![doc = include_str!("README.md")]
 #![doc(html_logo_url = "https://slint-ui.com/logo/slint-logo-square-light.svg")]
 
+use i_slint_core::api::EventLoopError;
 use i_slint_core::lengths::{LogicalLength, LogicalPoint, LogicalRect, LogicalSize, ScaleFactor};
+use i_slint_core::platform::EventLoopProxy;
 use i_slint_core::renderer::Renderer;
+use i_slint_core::software_renderer::SoftwareRenderer;
 use i_slint_core::window::WindowAdapter;
 use i_slint_core::window::WindowAdapterSealed;
+use std::cell::Cell;
+use std::collections::VecDeque;
 use std::pin::Pin;
 use std::rc::Rc;
-use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Condvar, Mutex};
+
+mod screenshots;
+pub use screenshots::*;
+
+#[derive(Default)]
+struct EventQueue {
+    events: VecDeque<Box<dyn FnOnce() + Send>>,
+    quit: bool,
+}
+
+/// The functions passed to `invoke_from_event_loop()`, which are run by `run_event_loop()`.
+///
+/// The event loop proxy is global, so there is a single queue for all the threads. Tests that
+/// run the event loop should not run in parallel with other such tests of the same process.
+static EVENT_QUEUE: once_cell::sync::Lazy<(Mutex<EventQueue>, Condvar)> =
+    once_cell::sync::Lazy::new(Default::default);
+
+/// The event loop proxy can only be set once per process, by the first backend
+static EVENT_LOOP_PROXY_CREATED: AtomicBool = AtomicBool::new(false);
+
+struct TestingEventLoopProxy;
+
+impl EventLoopProxy for TestingEventLoopProxy {
+    fn quit_event_loop(&self) -> Result<(), EventLoopError> {
+        let (queue, condvar) = &*EVENT_QUEUE;
+        queue.lock().unwrap().quit = true;
+        condvar.notify_all();
+        Ok(())
+    }
+
+    fn invoke_from_event_loop(
+        &self,
+        event: Box<dyn FnOnce() + Send>,
+    ) -> Result<(), EventLoopError> {
+        let (queue, condvar) = &*EVENT_QUEUE;
+        queue.lock().unwrap().events.push_back(event);
+        condvar.notify_all();
+        Ok(())
+    }
+}
 
 #[derive(Default)]
 pub struct TestingBackend {
     clipboard: Mutex<Option<String>>,
+    /// Whether the windows use the software renderer, so that they can be rendered into an image
+    software_renderer: bool,
 }
 
 impl i_slint_core::platform::Platform for TestingBackend {
     fn create_window_adapter(&self) -> Rc<dyn WindowAdapter> {
         Rc::new_cyclic(|self_weak| TestingWindow {
             window: i_slint_core::api::Window::new(self_weak.clone() as _),
+            shown: Default::default(),
+            position: Default::default(),
+            software_renderer: self
+                .software_renderer
+                .then(|| SoftwareRenderer::new(self_weak.clone() as _)),
         })
     }
 
+    /// Runs the functions passed to `invoke_from_event_loop()`, until `quit_event_loop()` is called.
+    fn run_event_loop(&self) {
+        let (queue, condvar) = &*EVENT_QUEUE;
+        loop {
+            let event = {
+                let mut queue = queue.lock().unwrap();
+                loop {
+                    if let Some(event) = queue.events.pop_front() {
+                        break event;
+                    }
+                    if std::mem::take(&mut queue.quit) {
+                        return;
+                    }
+                    queue = condvar.wait(queue).unwrap();
+                }
+            };
+            event();
+        }
+    }
+
+    fn new_event_loop_proxy(&self) -> Option<Box<dyn EventLoopProxy>> {
+        if EVENT_LOOP_PROXY_CREATED.swap(true, Ordering::Relaxed) {
+            // Already set by the backend of another thread, and the same for all the threads
+            return None;
+        }
+        Some(Box::new(TestingEventLoopProxy))
+    }
+
     fn duration_since_start(&self) -> core::time::Duration {
         // The slint::testing::mock_elapsed_time updates the animation tick directly
         core::time::Duration::from_millis(i_slint_core::animations::current_tick().0)
@@ -38,17 +119,35 @@ fn clipboard_text(&self) -> Option<String> {
     }
 }
 
+/// A window that isn't shown on any screen, so that components can be tested without windowing system.
 pub struct TestingWindow {
     window: i_slint_core::api::Window,
+    shown: Cell<bool>,
+    position: Cell<i_slint_core::api::PhysicalPosition>,
+    software_renderer: Option<SoftwareRenderer<0>>,
+}
+
+impl TestingWindow {
+    /// Returns true if the window was shown and not hidden since.
+    pub fn is_shown(&self) -> bool {
+        self.shown.get()
+    }
 }
 
 impl WindowAdapterSealed for TestingWindow {
     fn show(&self) {
-        unimplemented!("showing a testing window")
+        self.shown.set(true);
+    }
+
+    fn hide(&self) {
+        self.shown.set(false);
     }
 
     fn renderer(&self) -> &dyn Renderer {
-        self
+        match &self.software_renderer {
+            Some(renderer) => renderer,
+            None => self,
+        }
     }
 
     fn as_any(&self) -> &dyn std::any::Any {
@@ -56,11 +155,11 @@ fn as_any(&self) -> &dyn std::any::Any {
     }
 
     fn position(&self) -> i_slint_core::api::PhysicalPosition {
-        unimplemented!()
+        self.position.get()
     }
 
-    fn set_position(&self, _position: i_slint_core::api::WindowPosition) {
-        unimplemented!()
+    fn set_position(&self, position: i_slint_core::api::WindowPosition) {
+        self.position.set(position.to_physical(self.window.scale_factor()))
     }
 }
 
@@ -120,10 +219,23 @@ pub fn init() {
         .expect("platform already initialized");
 }
 
+/// Initialize the testing backend with windows that render with the software renderer, so that
+/// they can be compared to reference images with [`assert_screenshot()`].
+/// Must be called before any call that would otherwise initialize the rendering backend.
+pub fn init_with_software_renderer() {
+    i_slint_core::platform::set_platform(Box::new(TestingBackend {
+        software_renderer: true,
+        ..Default::default()
+    }))
+    .expect("platform already initialized");
+}
+
 /// This module contains functions useful for unit tests
 mod for_unit_test {
     use core::cell::Cell;
-    use i_slint_core::api::ComponentHandle;
+    use i_slint_core::accessibility::AccessibleStringProperty;
+    use i_slint_core::api::{ComponentHandle, LogicalPosition, LogicalSize, WindowEvent};
+    use i_slint_core::item_tree::{ItemRc, ItemWeak};
     pub use i_slint_core::tests::slint_mock_elapsed_time as mock_elapsed_time;
     use i_slint_core::window::WindowInner;
     use i_slint_core::SharedString;
@@ -149,6 +261,49 @@ pub fn send_mouse_click<
         );
     }
 
+    /// Simulate a finger starting to touch the window at the given position. `id` identifies the
+    /// finger in the subsequent calls to [`send_touch_move()`] and [`send_touch_release()`].
+    pub fn send_touch_press<
+        X: vtable::HasStaticVTable<i_slint_core::component::ComponentVTable>,
+        Component: Into<vtable::VRc<i_slint_core::component::ComponentVTable, X>> + ComponentHandle,
+    >(
+        component: &Component,
+        id: u64,
+        x: f32,
+        y: f32,
+    ) {
+        let position = LogicalPosition::new(x, y);
+        component.window().dispatch_event(WindowEvent::TouchPressed { id, position });
+    }
+
+    /// Simulate a finger touching the window moving to the given position.
+    pub fn send_touch_move<
+        X: vtable::HasStaticVTable<i_slint_core::component::ComponentVTable>,
+        Component: Into<vtable::VRc<i_slint_core::component::ComponentVTable, X>> + ComponentHandle,
+    >(
+        component: &Component,
+        id: u64,
+        x: f32,
+        y: f32,
+    ) {
+        let position = LogicalPosition::new(x, y);
+        component.window().dispatch_event(WindowEvent::TouchMoved { id, position });
+    }
+
+    /// Simulate a finger that stops touching the window at the given position.
+    pub fn send_touch_release<
+        X: vtable::HasStaticVTable<i_slint_core::component::ComponentVTable>,
+        Component: Into<vtable::VRc<i_slint_core::component::ComponentVTable, X>> + ComponentHandle,
+    >(
+        component: &Component,
+        id: u64,
+        x: f32,
+        y: f32,
+    ) {
+        let position = LogicalPosition::new(x, y);
+        component.window().dispatch_event(WindowEvent::TouchReleased { id, position });
+    }
+
     /// Simulate a change in keyboard modifiers being pressed
     pub fn set_current_keyboard_modifiers<
         X: vtable::HasStaticVTable<i_slint_core::component::ComponentVTable>,
@@ -160,6 +315,23 @@ pub fn set_current_keyboard_modifiers<
         KEYBOARD_MODIFIERS.with(|x| x.set(modifiers))
     }
 
+    /// Simulate pressing or releasing a key. `text` is the text of the key, or one of the special keys
+    /// converted to a string, like `Key::Shift.into()`.
+    pub fn send_key_event<
+        X: vtable::HasStaticVTable<i_slint_core::component::ComponentVTable>,
+        Component: Into<vtable::VRc<i_slint_core::component::ComponentVTable, X>> + ComponentHandle,
+    >(
+        component: &Component,
+        text: SharedString,
+        pressed: bool,
+    ) {
+        component.window().dispatch_event(if pressed {
+            WindowEvent::KeyPressed { text }
+        } else {
+            WindowEvent::KeyReleased { text }
+        })
+    }
+
     /// Simulate entering a sequence of ascii characters key by key.
     pub fn send_keyboard_string_sequence<
         X: vtable::HasStaticVTable<i_slint_core::component::ComponentVTable>,
@@ -186,6 +358,96 @@ pub fn set_window_scale_factor<
     ) {
         WindowInner::from_pub(component.window()).set_scale_factor(factor)
     }
+
+    /// Returns true if the window of the component is shown. Only works with the testing backend.
+    pub fn window_is_shown<
+        X: vtable::HasStaticVTable<i_slint_core::component::ComponentVTable>,
+        Component: Into<vtable::VRc<i_slint_core::component::ComponentVTable, X>> + ComponentHandle,
+    >(
+        component: &Component,
+    ) -> bool {
+        WindowInner::from_pub(component.window())
+            .window_adapter()
+            .as_any()
+            .downcast_ref::<super::TestingWindow>()
+            .map_or(false, |window| window.is_shown())
+    }
+
+    /// An element of a component, found with [`find_elements_by_accessible_label()`], to read its
+    /// state in a test.
+    ///
+    /// The state is read from the accessibility properties, such as `accessible-value`, which the
+    /// widgets set. The getters return `None` once the element was destroyed, for example when it
+    /// was in a repeater whose model changed.
+    #[derive(Clone)]
+    pub struct ElementHandle(ItemWeak);
+
+    impl ElementHandle {
+        /// Returns false if the element was destroyed.
+        pub fn is_valid(&self) -> bool {
+            self.0.upgrade().is_some()
+        }
+
+        /// The `accessible-label` of the element.
+        pub fn accessible_label(&self) -> Option<SharedString> {
+            self.string_property(AccessibleStringProperty::Label)
+        }
+
+        /// The `accessible-value` of the element, such as the text of a `LineEdit`.
+        pub fn accessible_value(&self) -> Option<SharedString> {
+            self.string_property(AccessibleStringProperty::Value)
+        }
+
+        /// The `accessible-checked` state of the element, for example of a `CheckBox`.
+        pub fn accessible_checked(&self) -> Option<bool> {
+            self.string_property(AccessibleStringProperty::Checked).map(|checked| checked == "true")
+        }
+
+        /// The position of the top-left corner of the element, relative to the window.
+        pub fn absolute_position(&self) -> Option<LogicalPosition> {
+            let item = self.0.upgrade()?;
+            let position = item.map_to_window(item.geometry().origin).cast::<f32>();
+            Some(LogicalPosition::new(position.x, position.y))
+        }
+
+        /// The size of the element.
+        pub fn size(&self) -> Option<LogicalSize> {
+            let size = self.0.upgrade()?.geometry().size.cast::<f32>();
+            Some(LogicalSize::new(size.width, size.height))
+        }
+
+        fn string_property(&self, property: AccessibleStringProperty) -> Option<SharedString> {
+            Some(self.0.upgrade()?.accessible_string_property(property))
+        }
+    }
+
+    /// Returns the elements of the component whose `accessible-label` is `label`, in the order of
+    /// the `.slint` file.
+    pub fn find_elements_by_accessible_label<
+        X: vtable::HasStaticVTable<i_slint_core::component::ComponentVTable>,
+        Component: Into<vtable::VRc<i_slint_core::component::ComponentVTable, X>> + ComponentHandle,
+    >(
+        component: &Component,
+        label: &str,
+    ) -> Vec<ElementHandle> {
+        fn visit(item: &ItemRc, label: &str, elements: &mut Vec<ElementHandle>) {
+            if item.is_accessible()
+                && item.accessible_string_property(AccessibleStringProperty::Label) == label
+            {
+                elements.push(ElementHandle(item.downgrade()));
+            }
+            let mut child = item.first_child();
+            while let Some(item) = child {
+                visit(&item, label, elements);
+                child = item.next_sibling();
+            }
+        }
+
+        let root = vtable::VRc::into_dyn(component.clone_strong().into());
+        let mut elements = Vec::new();
+        visit(&ItemRc::new(root, 0), label, &mut elements);
+        elements
+    }
 }
 
 pub use for_unit_test::*;
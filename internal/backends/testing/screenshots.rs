@@ -0,0 +1,262 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+/*!
+    Screenshot based regression tests.
+
+    The windows are rendered with the software renderer, at a fixed scale factor, and compared against
+    reference images. Pixels are compared with a perceptual metric, so that small differences due to
+    anti-aliasing don't fail the test. On failure, the rendered image and an image highlighting the
+    differences are written next to the reference image.
+
+    Set the `SLINT_CREATE_SCREENSHOTS` environment variable to write the rendered images as new
+    reference images instead of comparing them.
+*/
+
+use crate::TestingWindow;
+use i_slint_core::api::{ComponentHandle, LogicalSize, Window};
+use i_slint_core::component::ComponentRc;
+use i_slint_core::graphics::{Rgb8Pixel, SharedPixelBuffer};
+use i_slint_core::layout::Orientation;
+use i_slint_core::window::WindowInner;
+use std::path::{Path, PathBuf};
+
+/// The options of the comparison of a screenshot with its reference image.
+#[derive(Clone, Debug)]
+pub struct ScreenshotOptions {
+    /// The scale factor with which the window is rendered. The default is 1.
+    pub scale_factor: f32,
+    /// How much two pixels may differ before they are considered different, between 0 and 1.
+    /// The default is 0.1.
+    pub threshold: f32,
+    /// The number of pixels that may differ before the comparison fails. The default is 0.
+    pub max_differing_pixels: usize,
+}
+
+impl Default for ScreenshotOptions {
+    fn default() -> Self {
+        Self { scale_factor: 1., threshold: 0.1, max_differing_pixels: 0 }
+    }
+}
+
+/// The error returned when a screenshot could not be taken or doesn't match its reference.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ScreenshotError {
+    /// The window doesn't render with the software renderer. Call [`init_with_software_renderer()`](crate::init_with_software_renderer)
+    /// instead of [`init()`](crate::init).
+    NoSoftwareRenderer,
+    /// The reference image could not be read, or an image could not be written.
+    Image(image::ImageError),
+    /// The size of the rendered image is not the size of the reference image.
+    SizeMismatch {
+        /// The size of the reference image
+        expected: (u32, u32),
+        /// The size of the rendered image
+        actual: (u32, u32),
+    },
+    /// Too many pixels differ from the reference image.
+    Mismatch {
+        /// The number of pixels that differ
+        differing_pixels: usize,
+        /// The image where the differing pixels are highlighted in red
+        diff_image: PathBuf,
+    },
+}
+
+impl std::fmt::Display for ScreenshotError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ScreenshotError::NoSoftwareRenderer => {
+                f.write_str("the window doesn't use the software renderer")
+            }
+            ScreenshotError::Image(err) => err.fmt(f),
+            ScreenshotError::SizeMismatch { expected, actual } => write!(
+                f,
+                "the screenshot is {}x{} pixels but the reference is {}x{}",
+                actual.0, actual.1, expected.0, expected.1
+            ),
+            ScreenshotError::Mismatch { differing_pixels, diff_image } => write!(
+                f,
+                "{} pixels differ from the reference, see {}",
+                differing_pixels,
+                diff_image.display()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ScreenshotError {}
+
+impl From<image::ImageError> for ScreenshotError {
+    fn from(err: image::ImageError) -> Self {
+        ScreenshotError::Image(err)
+    }
+}
+
+/// Renders the window into an image, with the given scale factor. If the window has no size yet,
+/// it is resized to its preferred size first. The scale factor of the window is restored afterwards.
+pub fn render_screenshot(
+    window: &Window,
+    scale_factor: f32,
+) -> Result<SharedPixelBuffer<Rgb8Pixel>, ScreenshotError> {
+    let window_inner = WindowInner::from_pub(window);
+    let previous_scale_factor = window_inner.scale_factor();
+    // The window keeps its logical size, so its size in physical pixels depends on the scale factor
+    let logical_size = window.size().to_logical(previous_scale_factor);
+    window_inner.set_scale_factor(scale_factor);
+    if window.size().width > 0 && window.size().height > 0 {
+        window.set_size(logical_size);
+    }
+    let result = render_with_current_scale_factor(window);
+    let logical_size = window.size().to_logical(scale_factor);
+    window_inner.set_scale_factor(previous_scale_factor);
+    window.set_size(logical_size);
+    result
+}
+
+fn render_with_current_scale_factor(
+    window: &Window,
+) -> Result<SharedPixelBuffer<Rgb8Pixel>, ScreenshotError> {
+    let window_inner = WindowInner::from_pub(window);
+
+    let size = window.size();
+    if size.width == 0 || size.height == 0 {
+        let component_rc = window_inner.component();
+        let component = ComponentRc::borrow_pin(&component_rc);
+        let layout_info_h = component.as_ref().layout_info(Orientation::Horizontal);
+        let layout_info_v = component.as_ref().layout_info(Orientation::Vertical);
+        window.set_size(LogicalSize::new(
+            layout_info_h.preferred_bounded() as f32,
+            layout_info_v.preferred_bounded() as f32,
+        ));
+    }
+    let size = window.size();
+
+    let window_adapter = window_inner.window_adapter();
+    let renderer = window_adapter
+        .as_any()
+        .downcast_ref::<TestingWindow>()
+        .and_then(|window| window.software_renderer.as_ref())
+        .ok_or(ScreenshotError::NoSoftwareRenderer)?;
+
+    let mut buffer = SharedPixelBuffer::<Rgb8Pixel>::new(size.width, size.height);
+    let stride = buffer.stride() as usize;
+    renderer.render(buffer.make_mut_slice(), stride);
+    Ok(buffer)
+}
+
+/// Writes the screenshot to an image file, in the format given by the extension of `path`: PNG or JPEG.
+pub fn save_screenshot(
+    screenshot: &SharedPixelBuffer<Rgb8Pixel>,
+    path: &Path,
+) -> Result<(), ScreenshotError> {
+    to_image(screenshot).save(path)?;
+    Ok(())
+}
+
+/// Compares the screenshot with the reference image at `reference_path`.
+///
+/// On failure, the screenshot is written with the `.actual.png` extension next to the reference image, and
+/// an image where the differing pixels are red with the `.diff.png` extension.
+pub fn compare_screenshot(
+    screenshot: &SharedPixelBuffer<Rgb8Pixel>,
+    reference_path: &Path,
+    options: &ScreenshotOptions,
+) -> Result<(), ScreenshotError> {
+    let actual = to_image(screenshot);
+
+    if std::env::var_os("SLINT_CREATE_SCREENSHOTS").is_some() {
+        actual.save(reference_path)?;
+        return Ok(());
+    }
+
+    let reference = image::open(reference_path)?.into_rgb8();
+    if reference.dimensions() != actual.dimensions() {
+        actual.save(reference_path.with_extension("actual.png"))?;
+        return Err(ScreenshotError::SizeMismatch {
+            expected: reference.dimensions(),
+            actual: actual.dimensions(),
+        });
+    }
+
+    // The maximum value of `color_delta`, for two pixels that are red and cyan
+    const MAX_DELTA: f32 = 35215.;
+    let max_delta = MAX_DELTA * options.threshold * options.threshold;
+
+    let mut differing_pixels = 0;
+    let mut diff = image::RgbImage::new(actual.width(), actual.height());
+    for ((actual, reference), diff) in
+        actual.pixels().zip(reference.pixels()).zip(diff.pixels_mut())
+    {
+        if color_delta(actual.0, reference.0) > max_delta {
+            differing_pixels += 1;
+            *diff = image::Rgb([255, 0, 0]);
+        } else {
+            // Show the reference image faded, to see where the differences are
+            let gray = (255. - (255. - luma(reference.0)) * 0.1) as u8;
+            *diff = image::Rgb([gray, gray, gray]);
+        }
+    }
+
+    if differing_pixels > options.max_differing_pixels {
+        let diff_image = reference_path.with_extension("diff.png");
+        actual.save(reference_path.with_extension("actual.png"))?;
+        diff.save(&diff_image)?;
+        return Err(ScreenshotError::Mismatch { differing_pixels, diff_image });
+    }
+    Ok(())
+}
+
+/// Renders the window of the component and compares it with the reference image at `reference_path`,
+/// with the default [`ScreenshotOptions`]. Panics if the images differ.
+///
+/// The testing backend must have been initialized with [`init_with_software_renderer()`](crate::init_with_software_renderer).
+pub fn assert_screenshot(component: &impl ComponentHandle, reference_path: impl AsRef<Path>) {
+    let reference_path = reference_path.as_ref();
+    let options = ScreenshotOptions::default();
+    if let Err(err) = render_screenshot(component.window(), options.scale_factor)
+        .and_then(|screenshot| compare_screenshot(&screenshot, reference_path, &options))
+    {
+        panic!("screenshot {} doesn't match: {}", reference_path.display(), err)
+    }
+}
+
+fn to_image(screenshot: &SharedPixelBuffer<Rgb8Pixel>) -> image::RgbImage {
+    image::RgbImage::from_raw(
+        screenshot.width(),
+        screenshot.height(),
+        screenshot.as_bytes().to_vec(),
+    )
+    .expect("the buffer has the size of the image")
+}
+
+fn luma([r, g, b]: [u8; 3]) -> f32 {
+    r as f32 * 0.29889531 + g as f32 * 0.58662247 + b as f32 * 0.11448223
+}
+
+/// The squared distance between two colors in the YIQ color space, which is closer to the
+/// perceived difference than the distance of the RGB components.
+fn color_delta([r1, g1, b1]: [u8; 3], [r2, g2, b2]: [u8; 3]) -> f32 {
+    let (r1, g1, b1) = (r1 as f32, g1 as f32, b1 as f32);
+    let (r2, g2, b2) = (r2 as f32, g2 as f32, b2 as f32);
+    let y = (r1 - r2) * 0.29889531 + (g1 - g2) * 0.58662247 + (b1 - b2) * 0.11448223;
+    let i = (r1 - r2) * 0.59597799 - (g1 - g2) * 0.27417610 - (b1 - b2) * 0.32180189;
+    let q = (r1 - r2) * 0.21147017 - (g1 - g2) * 0.52261711 + (b1 - b2) * 0.31114694;
+    0.5053 * y * y + 0.299 * i * i + 0.1957 * q * q
+}
+
+#[test]
+fn color_delta_of_pixels() {
+    assert_eq!(color_delta([12, 34, 56], [12, 34, 56]), 0.);
+    // The maximum delta, used to interpret the threshold
+    assert!((color_delta([255, 0, 0], [0, 255, 255]) - 35215.).abs() < 1.);
+    assert!(color_delta([0, 0, 0], [255, 255, 255]) < 35215.);
+    assert_eq!(color_delta([0, 0, 0], [255, 0, 0]), color_delta([255, 0, 0], [0, 0, 0]));
+    // A change of green is perceived more than the same change of blue
+    assert!(color_delta([0, 0, 0], [0, 20, 0]) > color_delta([0, 0, 0], [0, 0, 20]));
+    // Slightly different colors are below the default threshold, black and white are not
+    let max_delta = 35215. * 0.1 * 0.1;
+    assert!(color_delta([100, 100, 100], [104, 102, 100]) < max_delta);
+    assert!(color_delta([0, 0, 0], [255, 255, 255]) > max_delta);
+}
@@ -0,0 +1,343 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+// cspell:ignore accesskit descendents
+
+//! This module exposes the accessible items of a window to the platform accessibility APIs,
+//! such as screen readers, with [AccessKit](https://accesskit.dev/).
+//!
+//! The tree of accessible items is rebuilt whenever one of the properties it was built from
+//! changes, and the items are identified by a node id that stays the same across updates.
+
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::num::NonZeroU128;
+use std::pin::Pin;
+use std::rc::Weak;
+use std::sync::Arc;
+
+use accesskit::{
+    Action, ActionRequest, CheckedState, DefaultActionVerb, Node, NodeId, Role, Tree, TreeUpdate,
+};
+use i_slint_core::accessibility::AccessibleStringProperty;
+use i_slint_core::input::MouseEvent;
+use i_slint_core::item_tree::{ItemRc, ItemWeak};
+use i_slint_core::items::{AccessibleRole, PointerEventButton};
+use i_slint_core::properties::PropertyTracker;
+use i_slint_core::window::{WindowAdapter, WindowInner};
+
+use crate::event_loop::CustomEvent;
+
+/// Identifies an item across tree updates: the address of its component, and its index in it
+type ItemKey = (usize, usize);
+
+fn item_key(item: &ItemRc) -> ItemKey {
+    let component = item.component();
+    (vtable::VRef::as_ptr(vtable::VRc::borrow(&component)).as_ptr() as usize, item.index())
+}
+
+/// The node of the root item of the window. It's also used for the placeholder tree sent
+/// before the items are exposed.
+fn root_node_id() -> NodeId {
+    NodeId(NonZeroU128::new(1).unwrap())
+}
+
+pub struct AccessKitAdapter {
+    inner: accesskit_winit::Adapter,
+    window_adapter_weak: Weak<dyn WindowAdapter>,
+    node_ids: RefCell<HashMap<ItemKey, NodeId>>,
+    items: RefCell<HashMap<NodeId, ItemWeak>>,
+    next_node_id: Cell<u128>,
+    tree_tracker: Pin<Box<PropertyTracker<Box<dyn Fn()>>>>,
+}
+
+impl AccessKitAdapter {
+    /// Creates the adapter for `winit_window`. The window must not be visible yet, so that the
+    /// accessibility tree is available when it is shown.
+    pub fn new(
+        window_adapter_weak: Weak<dyn WindowAdapter>,
+        winit_window: &winit::window::Window,
+        event_loop_proxy: winit::event_loop::EventLoopProxy<CustomEvent>,
+    ) -> Self {
+        let window_id = winit_window.id();
+        // Defer the update to the event loop, the tree can't be built while properties change.
+        let request_update: Box<dyn Fn()> = {
+            let event_loop_proxy = event_loop_proxy.clone();
+            Box::new(move || {
+                event_loop_proxy.send_event(CustomEvent::UpdateAccessibility(window_id)).ok();
+            })
+        };
+        // This is called when an assistive technology starts listening. The items can't be
+        // accessed from here, so send an empty window and request the full tree.
+        let initial_tree = {
+            let event_loop_proxy = event_loop_proxy.clone();
+            move || {
+                event_loop_proxy.send_event(CustomEvent::UpdateAccessibility(window_id)).ok();
+                TreeUpdate {
+                    nodes: vec![(
+                        root_node_id(),
+                        Arc::new(Node { role: Role::Window, ..Default::default() }),
+                    )],
+                    tree: Some(Tree::new(root_node_id())),
+                    focus: None,
+                }
+            }
+        };
+        Self {
+            inner: accesskit_winit::Adapter::new(winit_window, initial_tree, event_loop_proxy),
+            window_adapter_weak,
+            node_ids: Default::default(),
+            items: Default::default(),
+            next_node_id: Cell::new(2),
+            tree_tracker: Box::pin(PropertyTracker::new_with_dirty_handler(request_update)),
+        }
+    }
+
+    /// Forwards the window event to AccessKit. Returns true if the application should handle
+    /// the event too, which is always the case on platforms without a native adapter.
+    pub fn process_event(
+        &self,
+        winit_window: &winit::window::Window,
+        event: &winit::event::WindowEvent,
+    ) -> bool {
+        self.inner.on_event(winit_window, event)
+    }
+
+    /// Sends the full tree of accessible items to the platform, if an assistive technology
+    /// is listening.
+    pub fn update_tree(&self) {
+        self.inner.update_if_active(|| {
+            self.tree_tracker.as_ref().evaluate_as_dependency_root(|| self.build_tree_update())
+        });
+    }
+
+    /// Notifies the platform that the focus item changed
+    pub fn update_focus(&self) {
+        self.inner.update_if_active(|| TreeUpdate {
+            nodes: vec![],
+            tree: None,
+            focus: self.focus_node(),
+        });
+    }
+
+    /// Returns the item that is the target of the accessibility action, if it is still alive.
+    pub fn item_for_action(&self, request: &ActionRequest) -> Option<ItemRc> {
+        self.items.borrow().get(&request.target).and_then(|item| item.upgrade())
+    }
+
+    fn node_id_for_item(&self, item: &ItemRc) -> NodeId {
+        let key = item_key(item);
+        *self.node_ids.borrow_mut().entry(key).or_insert_with(|| {
+            let id = self.next_node_id.get();
+            self.next_node_id.set(id + 1);
+            NodeId(NonZeroU128::new(id).unwrap())
+        })
+    }
+
+    fn build_tree_update(&self) -> TreeUpdate {
+        let window_adapter = match self.window_adapter_weak.upgrade() {
+            Some(window_adapter) => window_adapter,
+            None => return TreeUpdate { nodes: vec![], tree: None, focus: None },
+        };
+        let window_inner = WindowInner::from_pub(window_adapter.window());
+        let root_item = ItemRc::new(window_inner.component(), 0);
+        let scale_factor = window_inner.scale_factor() as f64;
+
+        self.node_ids.borrow_mut().insert(item_key(&root_item), root_node_id());
+        let mut nodes = Vec::new();
+        let mut items = HashMap::new();
+        let root_id =
+            self.build_node(&root_item, Role::Window, scale_factor, &mut nodes, &mut items);
+
+        // Forget about the items that are gone
+        self.node_ids.borrow_mut().retain(|_, id| items.contains_key(id));
+        *self.items.borrow_mut() = items;
+
+        TreeUpdate { nodes, tree: Some(Tree::new(root_id)), focus: self.focus_node() }
+    }
+
+    fn build_node(
+        &self,
+        item: &ItemRc,
+        role: Role,
+        scale_factor: f64,
+        nodes: &mut Vec<(NodeId, Arc<Node>)>,
+        items: &mut HashMap<NodeId, ItemWeak>,
+    ) -> NodeId {
+        let id = self.node_id_for_item(item);
+        items.insert(id, item.downgrade());
+
+        let mut descendents = Vec::new();
+        i_slint_core::accessibility::accessible_descendents(item, &mut descendents);
+        let children = descendents
+            .iter()
+            .map(|child| {
+                let role = match child.accessible_role() {
+                    AccessibleRole::None => Role::Unknown,
+                    AccessibleRole::Button => Role::Button,
+                    AccessibleRole::Checkbox => Role::CheckBox,
+                    AccessibleRole::Combobox => Role::ComboBoxGrouping,
+                    AccessibleRole::Slider => Role::Slider,
+                    AccessibleRole::Spinbox => Role::SpinButton,
+                    AccessibleRole::Tab => Role::Tab,
+                    AccessibleRole::ProgressIndicator => Role::ProgressIndicator,
+                    AccessibleRole::Switch => Role::Switch,
+                    AccessibleRole::RadioButton => Role::RadioButton,
+                    AccessibleRole::Text => Role::StaticText,
+                };
+                self.build_node(child, role, scale_factor, nodes, items)
+            })
+            .collect();
+
+        let geometry = item.geometry();
+        let origin = item.map_to_window(geometry.origin);
+        let bounds = accesskit::kurbo::Rect::new(
+            origin.x as f64 * scale_factor,
+            origin.y as f64 * scale_factor,
+            (origin.x + geometry.width()) as f64 * scale_factor,
+            (origin.y + geometry.height()) as f64 * scale_factor,
+        );
+
+        let string_property = |what| {
+            let value = item.accessible_string_property(what);
+            (!value.is_empty()).then(|| Box::<str>::from(value.as_str()))
+        };
+        let number_property =
+            |what| item.accessible_string_property(what).as_str().parse::<f64>().ok();
+
+        let checked_state = (item.accessible_string_property(AccessibleStringProperty::Checkable)
+            == "true")
+            .then(|| {
+                if item.accessible_string_property(AccessibleStringProperty::Checked) == "true" {
+                    CheckedState::True
+                } else {
+                    CheckedState::False
+                }
+            });
+        let default_action_verb = matches!(
+            role,
+            Role::Button
+                | Role::CheckBox
+                | Role::ComboBoxGrouping
+                | Role::Tab
+                | Role::Switch
+                | Role::RadioButton
+        )
+        .then(|| DefaultActionVerb::Click);
+
+        let node = Node {
+            role,
+            bounds: Some(bounds),
+            children,
+            name: string_property(AccessibleStringProperty::Label),
+            description: string_property(AccessibleStringProperty::Description),
+            value: string_property(AccessibleStringProperty::Value),
+            numeric_value: number_property(AccessibleStringProperty::Value),
+            min_numeric_value: number_property(AccessibleStringProperty::ValueMinimum),
+            max_numeric_value: number_property(AccessibleStringProperty::ValueMaximum),
+            numeric_value_step: number_property(AccessibleStringProperty::ValueStep),
+            checked_state,
+            default_action_verb,
+            focusable: role != Role::StaticText && role != Role::Unknown,
+            ..Default::default()
+        };
+        nodes.push((id, Arc::new(node)));
+        id
+    }
+
+    /// The node of the accessible item that contains the focus item, taking the focus
+    /// delegation to one of the accessible descendents into account.
+    fn focus_node(&self) -> Option<NodeId> {
+        let window_adapter = self.window_adapter_weak.upgrade()?;
+        let mut item = WindowInner::from_pub(window_adapter.window()).focus_item();
+        while let Some(i) = &item {
+            if i.is_accessible() {
+                break;
+            }
+            item = i.parent_item();
+        }
+        let item = item?;
+
+        let delegate = item
+            .accessible_string_property(AccessibleStringProperty::DelegateFocus)
+            .as_str()
+            .parse::<usize>()
+            .ok()
+            .and_then(|index| {
+                let mut descendents = Vec::new();
+                i_slint_core::accessibility::accessible_descendents(&item, &mut descendents);
+                descendents.into_iter().nth(index)
+            });
+        let item = delegate.unwrap_or(item);
+        let key = item_key(&item);
+        self.node_ids.borrow().get(&key).copied()
+    }
+}
+
+/// Performs the action requested by an assistive technology on `item`.
+pub fn handle_action(item: &ItemRc, window: &i_slint_core::api::Window, action: Action) {
+    let window_inner = WindowInner::from_pub(window);
+    match action {
+        Action::Focus => window_inner.set_focus_item(item),
+        Action::Default => {
+            // Simulate a click in the middle of the item
+            let position = item.map_to_window(item.geometry().center());
+            window_inner.process_mouse_input(MouseEvent::Pressed {
+                position,
+                button: PointerEventButton::Left,
+            });
+            window_inner.process_mouse_input(MouseEvent::Released {
+                position,
+                button: PointerEventButton::Left,
+            });
+        }
+        _ => {}
+    }
+}
+
+#[cfg(all(
+    test,
+    any(
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd"
+    )
+))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn window_events_reach_the_application() {
+        use winit::platform::unix::EventLoopBuilderExtUnix;
+        // The tests don't run on the main thread, and there may be no display to connect to.
+        let event_loop = match std::panic::catch_unwind(|| {
+            winit::event_loop::EventLoopBuilder::<CustomEvent>::with_user_event()
+                .with_any_thread(true)
+                .build()
+        }) {
+            Ok(event_loop) => event_loop,
+            Err(_) => {
+                eprintln!("Skipping the test, the event loop can't be created");
+                return;
+            }
+        };
+        let winit_window =
+            winit::window::WindowBuilder::new().with_visible(false).build(&event_loop).unwrap();
+        let window_adapter_weak: Weak<dyn WindowAdapter> =
+            Weak::<i_slint_core::software_renderer::MinimalSoftwareWindow<0>>::new();
+        let adapter =
+            AccessKitAdapter::new(window_adapter_weak, &winit_window, event_loop.create_proxy());
+
+        // The event loop dispatches the events to the window only when this returns true
+        for event in [
+            winit::event::WindowEvent::Resized(winit::dpi::PhysicalSize::new(100, 50)),
+            winit::event::WindowEvent::Moved(winit::dpi::PhysicalPosition::new(10, 10)),
+            winit::event::WindowEvent::Focused(true),
+            winit::event::WindowEvent::CloseRequested,
+        ] {
+            assert!(adapter.process_event(&winit_window, &event), "{:?}", event);
+        }
+    }
+}
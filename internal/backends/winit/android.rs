@@ -0,0 +1,145 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+/*!
+    Glue for running inside of an Android activity.
+
+    On Android, the native window of the activity, and with it the GL surface, only exists between the
+    `Resumed` and `Suspended` events of the event loop. The windows are therefore unmapped when the activity
+    is suspended and shown again when it is resumed, and windows shown before the activity was resumed for
+    the first time are only mapped then.
+*/
+
+use crate::event_loop::WinitWindow;
+use i_slint_core::SharedString;
+use std::cell::{Cell, RefCell};
+use std::rc::{Rc, Weak};
+
+thread_local! {
+    /// True between the `Resumed` and `Suspended` events, when the native window exists.
+    static RESUMED: Cell<bool> = Cell::new(false);
+    /// The windows that will be shown when the activity is resumed.
+    static DEFERRED_WINDOWS: RefCell<Vec<Weak<dyn WinitWindow>>> = RefCell::new(Vec::new());
+}
+
+/// Returns true if the native window of the activity exists, so that windows can be mapped.
+pub fn is_resumed() -> bool {
+    RESUMED.with(|resumed| resumed.get())
+}
+
+/// Remembers the window to show it once the activity is resumed.
+pub fn defer_show(window: Weak<dyn WinitWindow>) {
+    DEFERRED_WINDOWS.with(|windows| {
+        let mut windows = windows.borrow_mut();
+        if !windows.iter().any(|w| w.ptr_eq(&window)) {
+            windows.push(window);
+        }
+    })
+}
+
+/// Forgets a window that was hidden while the activity was suspended.
+pub fn cancel_deferred_show(window: &Weak<dyn WinitWindow>) {
+    DEFERRED_WINDOWS.with(|windows| windows.borrow_mut().retain(|w| !w.ptr_eq(window)))
+}
+
+/// The number of windows that will be shown when the activity is resumed.
+pub fn deferred_window_count() -> usize {
+    DEFERRED_WINDOWS.with(|windows| windows.borrow().len())
+}
+
+/// Called on `Event::Suspended`: the native window is about to be destroyed, so release the rendering
+/// surfaces of all windows.
+pub fn suspend(windows: impl Iterator<Item = Rc<dyn WinitWindow>>) {
+    RESUMED.with(|resumed| resumed.set(false));
+    for window in windows {
+        window.release_surface();
+        defer_show(Rc::downgrade(&window));
+    }
+}
+
+/// Called on `Event::Resumed`: the native window was (re-)created, so map the windows again.
+pub fn resume() {
+    RESUMED.with(|resumed| resumed.set(true));
+    let windows = DEFERRED_WINDOWS.with(|windows| std::mem::take(&mut *windows.borrow_mut()));
+    for window in windows.iter().filter_map(Weak::upgrade) {
+        window.show();
+        window.window().request_redraw();
+    }
+}
+
+/// Shows or hides the soft keyboard of the activity.
+pub fn set_soft_input_visible(visible: bool) {
+    let activity = ndk_glue::native_activity();
+    if visible {
+        activity.show_soft_input(true);
+    } else {
+        activity.hide_soft_input(false);
+    }
+}
+
+/// The soft keyboard of the native activity only delivers key codes, without text. Map the keys of
+/// printable characters to their text.
+pub fn key_text(
+    key_code: winit::event::VirtualKeyCode,
+    modifiers: i_slint_core::input::KeyboardModifiers,
+) -> Option<SharedString> {
+    use winit::event::VirtualKeyCode;
+    let letter = |c: char| {
+        if modifiers.shift {
+            c.to_ascii_uppercase()
+        } else {
+            c
+        }
+    };
+    let c = match key_code {
+        VirtualKeyCode::A => letter('a'),
+        VirtualKeyCode::B => letter('b'),
+        VirtualKeyCode::C => letter('c'),
+        VirtualKeyCode::D => letter('d'),
+        VirtualKeyCode::E => letter('e'),
+        VirtualKeyCode::F => letter('f'),
+        VirtualKeyCode::G => letter('g'),
+        VirtualKeyCode::H => letter('h'),
+        VirtualKeyCode::I => letter('i'),
+        VirtualKeyCode::J => letter('j'),
+        VirtualKeyCode::K => letter('k'),
+        VirtualKeyCode::L => letter('l'),
+        VirtualKeyCode::M => letter('m'),
+        VirtualKeyCode::N => letter('n'),
+        VirtualKeyCode::O => letter('o'),
+        VirtualKeyCode::P => letter('p'),
+        VirtualKeyCode::Q => letter('q'),
+        VirtualKeyCode::R => letter('r'),
+        VirtualKeyCode::S => letter('s'),
+        VirtualKeyCode::T => letter('t'),
+        VirtualKeyCode::U => letter('u'),
+        VirtualKeyCode::V => letter('v'),
+        VirtualKeyCode::W => letter('w'),
+        VirtualKeyCode::X => letter('x'),
+        VirtualKeyCode::Y => letter('y'),
+        VirtualKeyCode::Z => letter('z'),
+        VirtualKeyCode::Key0 => '0',
+        VirtualKeyCode::Key1 => '1',
+        VirtualKeyCode::Key2 => '2',
+        VirtualKeyCode::Key3 => '3',
+        VirtualKeyCode::Key4 => '4',
+        VirtualKeyCode::Key5 => '5',
+        VirtualKeyCode::Key6 => '6',
+        VirtualKeyCode::Key7 => '7',
+        VirtualKeyCode::Key8 => '8',
+        VirtualKeyCode::Key9 => '9',
+        VirtualKeyCode::Space => ' ',
+        VirtualKeyCode::Comma => ',',
+        VirtualKeyCode::Period => '.',
+        VirtualKeyCode::Minus => '-',
+        VirtualKeyCode::Equals => '=',
+        VirtualKeyCode::Apostrophe => '\'',
+        VirtualKeyCode::Semicolon => ';',
+        VirtualKeyCode::Slash => '/',
+        VirtualKeyCode::Backslash => '\\',
+        VirtualKeyCode::At => '@',
+        VirtualKeyCode::Plus => '+',
+        _ => return None,
+    };
+    Some(SharedString::from(c.encode_utf8(&mut [0; 4]) as &str))
+}
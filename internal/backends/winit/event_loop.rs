@@ -31,6 +31,8 @@
 pub trait WinitWindow: WindowAdapter {
     fn currently_pressed_key_code(&self) -> &Cell<Option<winit::event::VirtualKeyCode>>;
     fn current_keyboard_modifiers(&self) -> &Cell<KeyboardModifiers>;
+    /// The monitor last passed to the run-time window by [`update_monitor()`]
+    fn current_monitor(&self) -> &RefCell<Option<winit::monitor::MonitorHandle>>;
     /// Returns true if during the drawing request_redraw() was called.
     fn draw(&self) -> bool;
     fn with_window_handle(&self, callback: &mut dyn FnMut(&winit::window::Window));
@@ -43,6 +45,13 @@ fn set_constraints(
     /// Called by the event loop when a WindowEvent::Resized is received.
     fn resize_event(&self, _size: winit::dpi::PhysicalSize<u32>) {}
 
+    /// Called by the event loop when the theme of the windowing system changes.
+    fn set_dark_color_scheme(&self, _dark: bool) {}
+
+    /// Called by the event loop when the native window is about to be destroyed by the system, for example
+    /// when an Android activity is suspended. The window must unmap itself and release its rendering surface.
+    fn release_surface(&self) {}
+
     /// Return true if the proxy element used for input method has the focus
     fn input_method_focused(&self) -> bool {
         false
@@ -50,6 +59,15 @@ fn input_method_focused(&self) -> bool {
     /// Returns true if request_redraw() was called since the last event loop iteration
     /// and resets the state back to false.
     fn take_pending_redraw(&self) -> bool;
+
+    /// Calls the callback with the adapter that exposes the window to the accessibility APIs,
+    /// if the window is mapped.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn with_accesskit_adapter(
+        &self,
+        _callback: &mut dyn FnMut(&crate::accesskit::AccessKitAdapter),
+    ) {
+    }
 }
 
 struct NotRunningEventLoop {
@@ -190,6 +208,11 @@ fn window_by_id(id: winit::window::WindowId) -> Option<Rc<dyn WinitWindow>> {
     ALL_WINDOWS.with(|windows| windows.borrow().get(&id).and_then(|weakref| weakref.upgrade()))
 }
 
+#[cfg(target_os = "android")]
+fn all_windows() -> Vec<Rc<dyn WinitWindow>> {
+    ALL_WINDOWS.with(|windows| windows.borrow().values().filter_map(Weak::upgrade).collect())
+}
+
 /// This enum captures run-time specific events that can be dispatched to the event loop in
 /// addition to the winit events.
 pub enum CustomEvent {
@@ -202,6 +225,19 @@ pub enum CustomEvent {
     /// Called from `GLWindow::hide` so that we can check if we should quit the event loop
     WindowHidden,
     Exit,
+    /// Sent when a property used by the accessibility tree of the window changed
+    #[cfg(not(target_arch = "wasm32"))]
+    UpdateAccessibility(winit::window::WindowId),
+    /// An action requested by an assistive technology, such as a screen reader
+    #[cfg(not(target_arch = "wasm32"))]
+    Accesskit(accesskit_winit::ActionRequestEvent),
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl From<accesskit_winit::ActionRequestEvent> for CustomEvent {
+    fn from(event: accesskit_winit::ActionRequestEvent) -> Self {
+        Self::Accesskit(event)
+    }
 }
 
 impl std::fmt::Debug for CustomEvent {
@@ -213,6 +249,10 @@ fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
             Self::UserEvent(_) => write!(f, "UserEvent"),
             Self::WindowHidden => write!(f, "WindowHidden"),
             Self::Exit => write!(f, "Exit"),
+            #[cfg(not(target_arch = "wasm32"))]
+            Self::UpdateAccessibility(e) => write!(f, "UpdateAccessibility({:?})", e),
+            #[cfg(not(target_arch = "wasm32"))]
+            Self::Accesskit(e) => write!(f, "Accesskit({:?})", e),
         }
     }
 }
@@ -233,11 +273,41 @@ pub fn winit_key_to_string(virtual_keycode: winit::event::VirtualKeyCode) -> Opt
     i_slint_common::for_each_special_keys!(winit_key_to_string_fn);
 }
 
+/// Returns the `file://` URI for the path of a file dragged from another application
+fn file_uri(path: &std::path::Path) -> SharedString {
+    let path = path.to_string_lossy().replace('\\', "/");
+    if path.starts_with('/') {
+        format!("file://{}", path).into()
+    } else {
+        format!("file:///{}", path).into()
+    }
+}
+
+/// The files dragged from another application. winit sends one event per file, so they are
+/// collected into a single `text/uri-list`, with one URI per line.
+///
+/// winit only supports receiving files: other data can't be dropped from other applications,
+/// and a `DragArea` can't start a drag operation to other applications.
+#[derive(Default)]
+struct FileDrag {
+    uris: Vec<SharedString>,
+    /// The window in which the files were dropped. The drop is processed at `MainEventsCleared`,
+    /// once the `DroppedFile` events of all the files were received.
+    dropped_in: Option<winit::window::WindowId>,
+}
+
+impl FileDrag {
+    fn uri_list(&self) -> SharedString {
+        self.uris.iter().map(|uri| uri.as_str()).collect::<Vec<_>>().join("\r\n").into()
+    }
+}
+
 fn process_window_event(
     window: Rc<dyn WinitWindow>,
     event: WindowEvent,
     cursor_pos: &mut LogicalPoint,
     pressed: &mut bool,
+    file_drag: &mut FileDrag,
 ) {
     fn key_event(
         event_type: KeyEventType,
@@ -256,6 +326,19 @@ fn key_event(
         event
     }
 
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let mut forward_to_application = true;
+        window.with_window_handle(&mut |winit_window| {
+            window.with_accesskit_adapter(&mut |adapter| {
+                forward_to_application = adapter.process_event(winit_window, &event)
+            })
+        });
+        if !forward_to_application {
+            return;
+        }
+    }
+
     let runtime_window = WindowInner::from_pub(window.window());
     match event {
         WindowEvent::Resized(size) => {
@@ -309,6 +392,20 @@ fn key_event(
                 winit::event::ElementState::Pressed => input.virtual_keycode,
                 _ => None,
             });
+            // The soft keyboard on Android doesn't send ReceivedCharacter events
+            #[cfg(target_os = "android")]
+            if let Some(text) = input.virtual_keycode.and_then(|key_code| {
+                crate::android::key_text(key_code, window.current_keyboard_modifiers().get())
+            }) {
+                if input.state == winit::event::ElementState::Pressed {
+                    let modifiers = window.current_keyboard_modifiers().get();
+                    let mut event = key_event(KeyEventType::KeyPressed, text, modifiers);
+                    runtime_window.process_key_input(&event);
+                    event.event_type = KeyEventType::KeyReleased;
+                    runtime_window.process_key_input(&event);
+                }
+                return;
+            }
             if let Some(text) = input.virtual_keycode.and_then(key_codes::winit_key_to_string) {
                 #[allow(unused_mut)]
                 let mut modifiers = window.current_keyboard_modifiers().get();
@@ -408,6 +505,16 @@ fn key_event(
             };
             runtime_window.process_mouse_input(ev);
         }
+        WindowEvent::HoveredFile(path) => {
+            // Each file hovered adds to the data of the drag operation
+            file_drag.uris.push(file_uri(&path));
+            runtime_window.start_drag("text/uri-list".into(), file_drag.uri_list());
+            runtime_window.process_drag_event(MouseEvent::Moved { position: *cursor_pos });
+        }
+        WindowEvent::HoveredFileCancelled => {
+            file_drag.uris.clear();
+            runtime_window.process_drag_event(MouseEvent::Exit);
+        }
         WindowEvent::Touch(touch) => {
             let location = touch.location.to_logical(runtime_window.scale_factor() as f64);
             let position = euclid::point2(location.x, location.y);
@@ -422,7 +529,7 @@ fn key_event(
                 }
                 winit::event::TouchPhase::Moved => MouseEvent::Moved { position },
             };
-            runtime_window.process_mouse_input(ev);
+            runtime_window.process_touch_input(touch.id, ev);
         }
         WindowEvent::ScaleFactorChanged { scale_factor, new_inner_size: size } => {
             if std::env::var("SLINT_SCALE_FACTOR").is_err() {
@@ -430,11 +537,52 @@ fn key_event(
                 runtime_window.set_window_item_geometry(LogicalSize::new(size.width, size.height));
                 runtime_window.set_scale_factor(scale_factor as f32);
             }
+            // The logical size of the monitor changed
+            window.current_monitor().replace(None);
+            update_monitor(&*window);
+        }
+        WindowEvent::Moved(_) => {
+            update_monitor(&*window);
+        }
+        WindowEvent::ThemeChanged(theme) => {
+            window.set_dark_color_scheme(theme == winit::window::Theme::Dark);
         }
         _ => {}
     }
 }
 
+/// Passes the monitor that currently shows the window to the run-time window, if it changed
+/// since the last call.
+pub(crate) fn update_monitor(window: &dyn WinitWindow) {
+    let mut monitor = None;
+    window.with_window_handle(&mut |winit_window| {
+        let current_monitor = winit_window.current_monitor();
+        if *window.current_monitor().borrow() == current_monitor {
+            return;
+        }
+        *window.current_monitor().borrow_mut() = current_monitor.clone();
+        monitor = current_monitor.map(|m| {
+            let size = m.size().to_logical(m.scale_factor());
+            // winit doesn't report the active video mode, so use the highest refresh rate at the
+            // current resolution, which is what the windowing systems typically pick.
+            let refresh_rate = m
+                .video_modes()
+                .filter(|mode| mode.size() == m.size())
+                .map(|mode| mode.refresh_rate())
+                .max()
+                .unwrap_or_default();
+            MonitorInfo {
+                name: m.name().unwrap_or_default().into(),
+                size: LogicalSize::new(size.width, size.height),
+                refresh_rate: refresh_rate as f32,
+            }
+        });
+    });
+    if let Some(monitor) = monitor {
+        WindowInner::from_pub(window.window()).set_monitor(monitor);
+    }
+}
+
 /// Runs the event loop and renders the items in the provided `component` in its
 /// own window.
 #[allow(unused_mut)] // mut need changes for wasm
@@ -480,11 +628,20 @@ pub fn run() {
     // last seen cursor position
     let mut cursor_pos = LogicalPoint::default();
     let mut pressed = false;
+    let mut file_drag = FileDrag::default();
 
     let mut run_fn = move |event: Event<CustomEvent>, control_flow: &mut ControlFlow| match event {
+        Event::WindowEvent { event: WindowEvent::DroppedFile(path), window_id } => {
+            if file_drag.dropped_in != Some(window_id) {
+                // The hovered files are sent again, this time as dropped
+                file_drag.uris.clear();
+                file_drag.dropped_in = Some(window_id);
+            }
+            file_drag.uris.push(file_uri(&path));
+        }
         Event::WindowEvent { event, window_id } => {
             if let Some(window) = window_by_id(window_id) {
-                process_window_event(window, event, &mut cursor_pos, &mut pressed);
+                process_window_event(window, event, &mut cursor_pos, &mut pressed, &mut file_drag);
             };
         }
 
@@ -512,6 +669,9 @@ pub fn run() {
         Event::UserEvent(CustomEvent::WindowHidden) => {
             if QUIT_ON_LAST_WINDOW_CLOSED.load(std::sync::atomic::Ordering::Relaxed) {
                 let window_count = ALL_WINDOWS.with(|windows| windows.borrow().len());
+                // The windows of a suspended Android activity are unmapped, but not hidden
+                #[cfg(target_os = "android")]
+                let window_count = window_count + crate::android::deferred_window_count();
                 if window_count == 0 {
                     *control_flow = ControlFlow::Exit;
                 }
@@ -526,11 +686,43 @@ pub fn run() {
             user();
         }
 
+        #[cfg(not(target_arch = "wasm32"))]
+        Event::UserEvent(CustomEvent::UpdateAccessibility(window_id)) => {
+            if let Some(window) = window_by_id(window_id) {
+                window.with_accesskit_adapter(&mut |adapter| adapter.update_tree());
+            }
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        Event::UserEvent(CustomEvent::Accesskit(event)) => {
+            if let Some(window) = window_by_id(event.window_id) {
+                let mut item = None;
+                window.with_accesskit_adapter(&mut |adapter| {
+                    item = adapter.item_for_action(&event.request)
+                });
+                // The adapter is not borrowed anymore when performing the action, which
+                // may run callbacks that hide the window.
+                if let Some(item) = item {
+                    crate::accesskit::handle_action(&item, window.window(), event.request.action);
+                }
+            }
+        }
+
         #[cfg(target_arch = "wasm32")]
         Event::UserEvent(CustomEvent::WakeEventLoopWorkaround) => {
             *control_flow = ControlFlow::Poll;
         }
 
+        #[cfg(target_os = "android")]
+        Event::Suspended => {
+            crate::android::suspend(all_windows().into_iter());
+        }
+
+        #[cfg(target_os = "android")]
+        Event::Resumed => {
+            crate::android::resume();
+        }
+
         Event::NewEvents(_) => {
             *control_flow = ControlFlow::Wait;
 
@@ -557,13 +749,31 @@ pub fn run() {
             {
                 WindowInner::from_pub(window.window()).update_window_properties();
             }
+
+            if let Some(window_id) = file_drag.dropped_in.take() {
+                if let Some(window) = window_by_id(window_id) {
+                    let runtime_window = WindowInner::from_pub(window.window());
+                    runtime_window.start_drag("text/uri-list".into(), file_drag.uri_list());
+                    runtime_window.process_drag_event(MouseEvent::Released {
+                        position: cursor_pos,
+                        button: PointerEventButton::Left,
+                    });
+                }
+                file_drag.uris.clear();
+            }
         }
 
         Event::RedrawEventsCleared => {
             if *control_flow != ControlFlow::Exit
                 && ALL_WINDOWS.with(|windows| {
                     windows.borrow().iter().any(|(_, w)| {
-                        w.upgrade().map_or(false, |w| w.window().has_active_animations())
+                        w.upgrade().map_or(false, |w| {
+                            // Mapped windows request their next frame when drawing, so only the
+                            // windows that are not shown need the event loop to keep spinning.
+                            let mut mapped = false;
+                            w.with_window_handle(&mut |_| mapped = true);
+                            w.window().has_active_animations() && !mapped
+                        })
                     })
                 })
             {
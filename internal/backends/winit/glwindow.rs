@@ -17,7 +17,7 @@
 use corelib::input::KeyboardModifiers;
 use corelib::items::{ItemRef, MouseCursor};
 use corelib::layout::Orientation;
-use corelib::lengths::{LogicalLength, LogicalPoint, LogicalSize};
+use corelib::lengths::{LogicalLength, LogicalPoint, LogicalRect, LogicalSize};
 use corelib::window::{WindowAdapter, WindowAdapterSealed, WindowInner};
 use corelib::Property;
 use corelib::{graphics::*, Coord};
@@ -85,12 +85,17 @@ pub(crate) struct GLWindow<Renderer: WinitCompatibleRenderer + 'static> {
     map_state: RefCell<GraphicsWindowBackendState<Renderer>>,
     keyboard_modifiers: std::cell::Cell<KeyboardModifiers>,
     currently_pressed_key_code: std::cell::Cell<Option<winit::event::VirtualKeyCode>>,
+    current_monitor: RefCell<Option<winit::monitor::MonitorHandle>>,
     pending_redraw: Cell<bool>,
+    /// Initialized with the platform setting when first queried, and updated when the theme changes
+    dark_color_scheme: once_cell::unsync::OnceCell<Pin<Box<Property<bool>>>>,
 
     renderer: Renderer,
 
     #[cfg(target_arch = "wasm32")]
     virtual_keyboard_helper: RefCell<Option<super::wasm_input_helper::WasmInputHelper>>,
+    #[cfg(target_os = "ios")]
+    ios_input_helper: RefCell<Option<crate::ios::IOSInputHelper>>,
 }
 
 impl<Renderer: WinitCompatibleRenderer + 'static> GLWindow<Renderer> {
@@ -110,7 +115,9 @@ pub(crate) fn new(#[cfg(target_arch = "wasm32")] canvas_id: String) -> Rc<dyn Wi
             }),
             keyboard_modifiers: Default::default(),
             currently_pressed_key_code: Default::default(),
+            current_monitor: Default::default(),
             pending_redraw: Cell::new(false),
+            dark_color_scheme: Default::default(),
             renderer: Renderer::new(
                 &(self_weak.clone() as _),
                 #[cfg(target_arch = "wasm32")]
@@ -118,6 +125,8 @@ pub(crate) fn new(#[cfg(target_arch = "wasm32")] canvas_id: String) -> Rc<dyn Wi
             ),
             #[cfg(target_arch = "wasm32")]
             virtual_keyboard_helper: Default::default(),
+            #[cfg(target_os = "ios")]
+            ios_input_helper: Default::default(),
         });
         self_rc as _
     }
@@ -152,9 +161,75 @@ fn unmap(&self) {
             crate::event_loop::unregister_window(winit_window.id());
         });
 
+        // The text field is a subview of the view of the window
+        #[cfg(target_os = "ios")]
+        self.ios_input_helper.replace(None);
+
         self.renderer.release_canvas(old_mapped.canvas);
     }
 
+    /// Sets the minimum and maximum size of the winit window from the last layout constraints, and
+    /// makes it resizable only when they differ. See [`WindowInner::size_limits`].
+    fn apply_size_limits(&self, winit_window: &winit::window::Window) {
+        // If we're in fullscreen state, don't try to resize the window but maintain the surface
+        // size we've been assigned to from the windowing system. Weston/Wayland don't like it
+        // when we create a surface that's bigger than the screen due to constraints (#532).
+        if winit_window.fullscreen().is_some() {
+            return;
+        }
+        let mapped_window = match self.borrow_mapped_window() {
+            Some(mapped_window) => mapped_window,
+            None => return,
+        };
+        let (constraints_horizontal, constraints_vertical) = mapped_window.constraints.get();
+        let size_limits = WindowInner::from_pub(self.window())
+            .size_limits(constraints_horizontal, constraints_vertical);
+        if mapped_window.size_limits.get() == Some(size_limits) {
+            return;
+        }
+        mapped_window.size_limits.set(Some(size_limits));
+        drop(mapped_window);
+
+        let (min_size, max_size) = size_limits;
+        let (min_width, min_height) = (min_size.width as f32, min_size.height as f32);
+        let (max_width, max_height) = (max_size.width as f32, max_size.height as f32);
+        let sf = self.window().scale_factor();
+
+        winit_window.set_resizable(true);
+        winit_window.set_min_inner_size(if min_width > 0. || min_height > 0. {
+            Some(winit::dpi::PhysicalSize::new(min_width * sf, min_height * sf))
+        } else {
+            None
+        });
+        winit_window.set_max_inner_size(
+            if max_width < i32::MAX as f32 || max_height < i32::MAX as f32 {
+                Some(winit::dpi::PhysicalSize::new(
+                    (max_width * sf).min(65535.),
+                    (max_height * sf).min(65535.),
+                ))
+            } else {
+                None
+            },
+        );
+        winit_window.set_resizable(min_width < max_width || min_height < max_height);
+
+        #[cfg(target_arch = "wasm32")]
+        {
+            // set_max_inner_size / set_min_inner_size don't work on wasm, so apply the size manually
+            let existing_size: winit::dpi::LogicalSize<f32> =
+                winit_window.inner_size().to_logical(sf as f64);
+            if !(min_width..=max_width).contains(&(existing_size.width))
+                || !(min_height..=max_height).contains(&(existing_size.height))
+            {
+                let new_size = winit::dpi::LogicalSize::new(
+                    existing_size.width.min(max_width).max(min_width),
+                    existing_size.height.min(max_height).max(min_height),
+                );
+                winit_window.set_inner_size(new_size);
+            }
+        }
+    }
+
     fn call_with_event_loop(&self, callback: fn(&Self)) {
         // With wasm, winit's `run()` consumes the event loop and access to it from outside the event handler yields
         // loop and thus ends up trying to create a new event loop instance, which panics in winit. Instead, forward
@@ -180,6 +255,15 @@ fn take_pending_redraw(&self) -> bool {
         self.pending_redraw.take()
     }
 
+    fn set_dark_color_scheme(&self, dark: bool) {
+        match self.dark_color_scheme.get() {
+            Some(property) => property.as_ref().set(dark),
+            None => {
+                let _ = self.dark_color_scheme.set(Box::pin(Property::new(dark)));
+            }
+        }
+    }
+
     fn currently_pressed_key_code(&self) -> &Cell<Option<winit::event::VirtualKeyCode>> {
         &self.currently_pressed_key_code
     }
@@ -188,6 +272,10 @@ fn current_keyboard_modifiers(&self) -> &Cell<KeyboardModifiers> {
         &self.keyboard_modifiers
     }
 
+    fn current_monitor(&self) -> &RefCell<Option<winit::monitor::MonitorHandle>> {
+        &self.current_monitor
+    }
+
     /// Draw the items of the specified `component` in the given window.
     fn draw(&self) -> bool {
         let window = match self.borrow_mapped_window() {
@@ -195,9 +283,19 @@ fn draw(&self) -> bool {
             None => return false, // caller bug, doesn't make sense to call draw() when not mapped
         };
 
+        // Advance the animations to the time of this frame before resetting the pending redraw flag,
+        // as changing the animated properties requests a redraw.
+        corelib::animations::update_animations();
+
         self.pending_redraw.set(false);
         self.renderer.render(&window.canvas, self);
 
+        if self.window().has_active_animations() {
+            // Let the windowing system tell us when to draw the next frame, so that the animations
+            // follow the refresh rate of the display instead of spinning the event loop.
+            window.canvas.with_window_handle(|winit_window| winit_window.request_redraw());
+        }
+
         self.pending_redraw.get()
     }
 
@@ -230,12 +328,42 @@ fn input_method_focused(&self) -> bool {
         }
     }
 
+    #[cfg(target_os = "ios")]
+    fn input_method_focused(&self) -> bool {
+        match self.ios_input_helper.try_borrow() {
+            Ok(helper) => helper.as_ref().map_or(false, |h| h.has_focus()),
+            Err(_) => true,
+        }
+    }
+
     fn resize_event(&self, size: winit::dpi::PhysicalSize<u32>) {
         if let Some(mapped_window) = self.borrow_mapped_window() {
             self.window().set_size(corelib::api::PhysicalSize::new(size.width, size.height));
-            mapped_window.canvas.resize_event()
+            mapped_window.canvas.resize_event();
+            // The safe area changes when the device is rotated
+            #[cfg(target_os = "ios")]
+            mapped_window.canvas.with_window_handle(|winit_window| {
+                crate::ios::update_safe_area_insets(
+                    winit_window,
+                    WindowInner::from_pub(self.window()),
+                )
+            });
         }
     }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn with_accesskit_adapter(
+        &self,
+        callback: &mut dyn FnMut(&crate::accesskit::AccessKitAdapter),
+    ) {
+        if let Some(mapped_window) = self.borrow_mapped_window() {
+            callback(&mapped_window.accesskit_adapter);
+        }
+    }
+
+    fn release_surface(&self) {
+        self.unmap();
+    }
 }
 
 impl<Renderer: WinitCompatibleRenderer + 'static> WindowAdapter for GLWindow<Renderer> {
@@ -293,6 +421,9 @@ fn apply_window_properties(&self, window_item: Pin<&i_slint_core::items::WindowI
             winit_window.set_title(&window_item.title());
             winit_window
                 .set_decorations(!window_item.no_frame() || winit_window.fullscreen().is_some());
+            winit_window.set_always_on_top(window_item.always_on_top());
+            // The limits of a window that is not resizable follow its size, so apply them before resizing
+            self.apply_size_limits(winit_window);
 
             if width <= 0. || height <= 0. {
                 must_resize = true;
@@ -335,58 +466,10 @@ fn apply_geometry_constraint(
         constraints_horizontal: corelib::layout::LayoutInfo,
         constraints_vertical: corelib::layout::LayoutInfo,
     ) {
-        self.with_window_handle(&mut |winit_window| {
-            // If we're in fullscreen state, don't try to resize the window but maintain the surface
-            // size we've been assigned to from the windowing system. Weston/Wayland don't like it
-            // when we create a surface that's bigger than the screen due to constraints (#532).
-            if winit_window.fullscreen().is_some() {
-                return;
-            }
-
-            if (constraints_horizontal, constraints_vertical) != self.constraints() {
-                let min_width = constraints_horizontal.min.min(constraints_horizontal.max) as f32;
-                let min_height = constraints_vertical.min.min(constraints_vertical.max) as f32;
-                let max_width = constraints_horizontal.max.max(constraints_horizontal.min) as f32;
-                let max_height = constraints_vertical.max.max(constraints_vertical.min) as f32;
-
-                let sf = self.window().scale_factor();
-
-                winit_window.set_resizable(true);
-                winit_window.set_min_inner_size(if min_width > 0. || min_height > 0. {
-                    Some(winit::dpi::PhysicalSize::new(min_width * sf, min_height * sf))
-                } else {
-                    None
-                });
-                winit_window.set_max_inner_size(
-                    if max_width < i32::MAX as f32 || max_height < i32::MAX as f32 {
-                        Some(winit::dpi::PhysicalSize::new(
-                            (max_width * sf).min(65535.),
-                            (max_height * sf).min(65535.),
-                        ))
-                    } else {
-                        None
-                    },
-                );
-                self.set_constraints((constraints_horizontal, constraints_vertical));
-                winit_window.set_resizable(min_width < max_width || min_height < max_height);
-
-                #[cfg(target_arch = "wasm32")]
-                {
-                    // set_max_inner_size / set_min_inner_size don't work on wasm, so apply the size manually
-                    let existing_size: winit::dpi::LogicalSize<f32> =
-                        winit_window.inner_size().to_logical(sf as f64);
-                    if !(min_width..=max_width).contains(&(existing_size.width))
-                        || !(min_height..=max_height).contains(&(existing_size.height))
-                    {
-                        let new_size = winit::dpi::LogicalSize::new(
-                            existing_size.width.min(max_width).max(min_width),
-                            existing_size.height.min(max_height).max(min_height),
-                        );
-                        winit_window.set_inner_size(new_size);
-                    }
-                }
-            }
-        });
+        if (constraints_horizontal, constraints_vertical) != self.constraints() {
+            self.set_constraints((constraints_horizontal, constraints_vertical));
+            self.with_window_handle(&mut |winit_window| self.apply_size_limits(winit_window));
+        }
     }
 
     fn show(&self) {
@@ -398,6 +481,13 @@ fn show(&self) {
                 GraphicsWindowBackendState::Mapped(_) => return,
             };
 
+            // The native window of the activity, needed to create the surface, only exists once resumed
+            #[cfg(target_os = "android")]
+            if !crate::android::is_resumed() {
+                crate::android::defer_show(self_.self_weak.clone() as _);
+                return;
+            }
+
             let mut window_builder = winit::window::WindowBuilder::new();
 
             let runtime_window = WindowInner::from_pub(self_.window());
@@ -410,10 +500,13 @@ fn show(&self) {
                 window_builder
                     .with_title(window_item.title().to_string())
                     .with_resizable(
-                        window_item.height() <= LogicalLength::zero()
-                            || window_item.width() <= LogicalLength::zero(),
+                        window_item.resizable()
+                            && (window_item.height() <= LogicalLength::zero()
+                                || window_item.width() <= LogicalLength::zero()),
                     )
                     .with_decorations(!window_item.no_frame())
+                    .with_always_on_top(window_item.always_on_top())
+                    .with_transparent(window_item.transparent())
                     .with_window_icon(icon_to_winit(window_item.icon()))
             } else {
                 window_builder.with_title("Slint Window".to_string())
@@ -495,12 +588,59 @@ fn show(&self) {
                 window_builder
             };
 
+            #[cfg(all(
+                any(
+                    target_os = "linux",
+                    target_os = "dragonfly",
+                    target_os = "freebsd",
+                    target_os = "netbsd",
+                    target_os = "openbsd"
+                ),
+                feature = "wayland"
+            ))]
+            let (window_builder, csd_theme) = crate::wayland::apply_csd_theme(window_builder);
+
+            // AccessKit must be set up before the window is shown, see AccessKitAdapter::new
+            #[cfg(not(target_arch = "wasm32"))]
+            let window_builder = window_builder.with_visible(false);
+
             let canvas = self_.renderer.create_canvas(window_builder);
 
+            #[cfg(not(target_arch = "wasm32"))]
+            let accesskit_adapter = canvas.with_window_handle(|winit_window| {
+                let event_loop_proxy = crate::event_loop::with_window_target(|event_loop| {
+                    event_loop.event_loop_proxy().clone()
+                });
+                let adapter = crate::accesskit::AccessKitAdapter::new(
+                    self_.self_weak.clone() as _,
+                    winit_window,
+                    event_loop_proxy,
+                );
+                winit_window.set_visible(true);
+                adapter
+            });
+
             let id = canvas.with_window_handle(|winit_window| {
                 WindowInner::from_pub(&self_.window).set_scale_factor(
                     scale_factor_override.unwrap_or_else(|| winit_window.scale_factor()) as _,
                 );
+                // Make the content of the window match its decorations
+                #[cfg(all(
+                    any(
+                        target_os = "linux",
+                        target_os = "dragonfly",
+                        target_os = "freebsd",
+                        target_os = "netbsd",
+                        target_os = "openbsd"
+                    ),
+                    feature = "wayland"
+                ))]
+                if let Some(theme) = csd_theme {
+                    use winit::platform::unix::WindowExtUnix;
+                    if winit_window.wayland_surface().is_some() {
+                        self_.set_dark_color_scheme(theme == winit::window::Theme::Dark);
+                    }
+                }
                 // On wasm, with_inner_size on the WindowBuilder don't have effect, so apply manually
                 #[cfg(target_arch = "wasm32")]
                 if s.width > 0 as Coord && s.height > 0 as Coord {
@@ -512,14 +652,32 @@ fn show(&self) {
             self_.map_state.replace(GraphicsWindowBackendState::Mapped(MappedWindow {
                 canvas,
                 constraints: Default::default(),
+                size_limits: Default::default(),
+                #[cfg(not(target_arch = "wasm32"))]
+                accesskit_adapter,
             }));
 
             crate::event_loop::register_window(id, self_.self_weak.upgrade().unwrap());
+            crate::event_loop::update_monitor(&*self_);
+            #[cfg(target_os = "ios")]
+            self_.with_window_handle(&mut |winit_window| {
+                crate::ios::update_safe_area_insets(
+                    winit_window,
+                    WindowInner::from_pub(self_.window()),
+                )
+            });
+
+            #[cfg(not(target_arch = "wasm32"))]
+            self_.with_accesskit_adapter(&mut |adapter| adapter.update_tree());
         });
     }
 
     fn hide(&self) {
         self.call_with_event_loop(|self_| {
+            #[cfg(target_os = "android")]
+            crate::android::cancel_deferred_show(
+                &(self_.self_weak.clone() as Weak<dyn WinitWindow>),
+            );
             self_.unmap();
 
             /* FIXME:
@@ -592,6 +750,21 @@ fn enable_input_method(&self, _it: corelib::items::InputType) {
         self.with_window_handle(&mut |winit_window| {
             winit_window.set_ime_allowed(matches!(_it, corelib::items::InputType::Text))
         });
+        #[cfg(target_os = "android")]
+        crate::android::set_soft_input_visible(true);
+        #[cfg(target_os = "ios")]
+        {
+            let mut helper = self.ios_input_helper.borrow_mut();
+            if helper.is_none() {
+                self.with_window_handle(&mut |winit_window| {
+                    *helper =
+                        Some(crate::ios::IOSInputHelper::new(self.self_weak.clone(), winit_window));
+                });
+            }
+            if let Some(helper) = &*helper {
+                helper.show();
+            }
+        }
     }
 
     fn disable_input_method(&self) {
@@ -601,11 +774,20 @@ fn disable_input_method(&self) {
         }
         #[cfg(not(target_arch = "wasm32"))]
         self.with_window_handle(&mut |winit_window| winit_window.set_ime_allowed(false));
+        #[cfg(target_os = "android")]
+        crate::android::set_soft_input_visible(false);
+        #[cfg(target_os = "ios")]
+        if let Some(helper) = &*self.ios_input_helper.borrow() {
+            helper.hide()
+        }
     }
 
-    fn set_ime_position(&self, ime_pos: LogicalPoint) {
+    fn set_ime_cursor_rect(&self, cursor_rect: LogicalRect) {
+        // winit places the candidate window at the given position, so pass the bottom of the
+        // cursor to not cover the text being composed.
+        let position = LogicalPoint::new(cursor_rect.min_x(), cursor_rect.max_y());
         self.with_window_handle(&mut |winit_window| {
-            winit_window.set_ime_position(winit::dpi::LogicalPosition::new(ime_pos.x, ime_pos.y))
+            winit_window.set_ime_position(winit::dpi::LogicalPosition::new(position.x, position.y))
         })
     }
 
@@ -660,7 +842,19 @@ fn set_size(&self, size: corelib::api::WindowSize) {
     }
 
     fn dark_color_scheme(&self) -> bool {
-        dark_light::detect() == dark_light::Mode::Dark
+        self.dark_color_scheme
+            .get_or_init(|| Box::pin(Property::new(dark_light::detect() == dark_light::Mode::Dark)))
+            .as_ref()
+            .get()
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn handle_focus_change(
+        &self,
+        _old: Option<corelib::items::ItemRc>,
+        _new: Option<corelib::items::ItemRc>,
+    ) {
+        self.with_accesskit_adapter(&mut |adapter| adapter.update_focus());
     }
 }
 
@@ -673,6 +867,10 @@ fn drop(&mut self) {
 struct MappedWindow<Renderer: WinitCompatibleRenderer> {
     canvas: Renderer::Canvas,
     constraints: Cell<(corelib::layout::LayoutInfo, corelib::layout::LayoutInfo)>,
+    /// The minimum and maximum size last applied to the winit window
+    size_limits: Cell<Option<(LogicalSize, LogicalSize)>>,
+    #[cfg(not(target_arch = "wasm32"))]
+    accesskit_adapter: crate::accesskit::AccessKitAdapter,
 }
 
 enum GraphicsWindowBackendState<Renderer: WinitCompatibleRenderer> {
@@ -0,0 +1,191 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+//! Integration with UIKit: the soft keyboard, and the safe area of the view.
+//!
+//! Winit doesn't deliver the key events on iOS, and doesn't show the soft keyboard. So we add
+//! a hidden `UITextField` to the view of the window, that gets the first responder when a
+//! `TextInput` has the focus, and forward the changes of its text as key events.
+//! The text field always contains a single space, so that deleting it is seen as a backspace.
+
+// cspell:ignore ivar
+
+use std::rc::Weak;
+
+use i_slint_core::input::{key_codes, KeyEvent, KeyEventType};
+use i_slint_core::lengths::LogicalLength;
+use i_slint_core::window::{SafeAreaInsets, WindowAdapter, WindowInner};
+use i_slint_core::SharedString;
+use objc::declare::ClassDecl;
+use objc::runtime::{Class, Object, Sel, BOOL, NO, YES};
+use objc::{class, msg_send, sel, sel_impl};
+use winit::platform::ios::WindowExtIOS;
+
+/// The text that the text field contains when nothing was typed
+const PLACEHOLDER: &str = " ";
+
+/// The value of `UIControlEventEditingChanged`
+const EDITING_CHANGED: u64 = 1 << 17;
+
+#[repr(C)]
+struct UIEdgeInsets {
+    top: f64,
+    left: f64,
+    bottom: f64,
+    right: f64,
+}
+
+/// Returns an autoreleased `NSString`
+fn ns_string(text: &str) -> *mut Object {
+    let text = std::ffi::CString::new(text).unwrap_or_default();
+    unsafe { msg_send![class!(NSString), stringWithUTF8String: text.as_ptr()] }
+}
+
+/// Converts a `NSString` to a SharedString
+fn from_ns_string(string: *mut Object) -> SharedString {
+    if string.is_null() {
+        return SharedString::default();
+    }
+    unsafe {
+        let utf8: *const std::os::raw::c_char = msg_send![string, UTF8String];
+        if utf8.is_null() {
+            return SharedString::default();
+        }
+        std::ffi::CStr::from_ptr(utf8).to_string_lossy().as_ref().into()
+    }
+}
+
+/// The class of the object receiving the editing events of the text field. Its `slintWindow`
+/// instance variable points to a `Box<Weak<dyn WindowAdapter>>`.
+fn target_class() -> &'static Class {
+    static REGISTER: std::sync::Once = std::sync::Once::new();
+    REGISTER.call_once(|| {
+        let mut decl = ClassDecl::new("SlintTextFieldTarget", class!(NSObject)).unwrap();
+        decl.add_ivar::<*mut std::ffi::c_void>("slintWindow");
+
+        extern "C" fn editing_changed(this: &Object, _: Sel, text_field: *mut Object) {
+            let window_adapter = unsafe {
+                let window: *mut std::ffi::c_void = *this.get_ivar("slintWindow");
+                match (window as *const Weak<dyn WindowAdapter>).as_ref() {
+                    Some(window) => window.upgrade(),
+                    None => return,
+                }
+            };
+            let text = from_ns_string(unsafe { msg_send![text_field, text] });
+            // Reset the field first, as processing the key events may hide the keyboard
+            unsafe {
+                let _: () = msg_send![text_field, setText: ns_string(PLACEHOLDER)];
+            }
+            let window_adapter = match window_adapter {
+                Some(window_adapter) => window_adapter,
+                None => return,
+            };
+
+            let text: SharedString = match text.strip_prefix(PLACEHOLDER) {
+                Some(typed) if !typed.is_empty() => typed.into(),
+                Some(_) => return,
+                // The placeholder was deleted
+                None => {
+                    let mut buffer = [0; 6];
+                    SharedString::from(&*key_codes::Backspace.encode_utf8(&mut buffer))
+                }
+            };
+            let window_inner = WindowInner::from_pub(window_adapter.window());
+            let mut event =
+                KeyEvent { text, event_type: KeyEventType::KeyPressed, ..Default::default() };
+            window_inner.process_key_input(&event);
+            event.event_type = KeyEventType::KeyReleased;
+            window_inner.process_key_input(&event);
+        }
+
+        unsafe {
+            decl.add_method(
+                sel!(editingChanged:),
+                editing_changed as extern "C" fn(&Object, Sel, *mut Object),
+            );
+        }
+        decl.register();
+    });
+    class!(SlintTextFieldTarget)
+}
+
+pub struct IOSInputHelper {
+    text_field: *mut Object,
+    target: *mut Object,
+    window_adapter: *mut Weak<dyn WindowAdapter>,
+}
+
+impl IOSInputHelper {
+    pub fn new(
+        window_adapter: Weak<dyn WindowAdapter>,
+        winit_window: &winit::window::Window,
+    ) -> Self {
+        let window_adapter = Box::into_raw(Box::new(window_adapter));
+        unsafe {
+            let view = winit_window.ui_view() as *mut Object;
+
+            let text_field: *mut Object = msg_send![class!(UITextField), new];
+            let _: () = msg_send![text_field, setHidden: YES];
+            // Otherwise everything would be capitalized as we need to clear the field
+            let _: () = msg_send![text_field, setAutocapitalizationType: 0i64];
+            let _: () = msg_send![text_field, setAutocorrectionType: 1i64];
+            let _: () = msg_send![text_field, setText: ns_string(PLACEHOLDER)];
+
+            let target: *mut Object = msg_send![target_class(), new];
+            (*target).set_ivar("slintWindow", window_adapter as *mut std::ffi::c_void);
+            let _: () = msg_send![text_field, addTarget: target action: sel!(editingChanged:) forControlEvents: EDITING_CHANGED];
+
+            let _: () = msg_send![view, addSubview: text_field];
+
+            Self { text_field, target, window_adapter }
+        }
+    }
+
+    /// Shows the soft keyboard
+    pub fn show(&self) {
+        unsafe {
+            let _: () = msg_send![self.text_field, setHidden: NO];
+            let _: BOOL = msg_send![self.text_field, becomeFirstResponder];
+        }
+    }
+
+    /// Hides the soft keyboard
+    pub fn hide(&self) {
+        unsafe {
+            let _: BOOL = msg_send![self.text_field, resignFirstResponder];
+            let _: () = msg_send![self.text_field, setHidden: YES];
+        }
+    }
+
+    /// Returns true if the text field has the focus
+    pub fn has_focus(&self) -> bool {
+        let focused: BOOL = unsafe { msg_send![self.text_field, isFirstResponder] };
+        focused == YES
+    }
+}
+
+impl Drop for IOSInputHelper {
+    fn drop(&mut self) {
+        unsafe {
+            let _: () = msg_send![self.text_field, removeFromSuperview];
+            let _: () = msg_send![self.text_field, release];
+            let _: () = msg_send![self.target, release];
+            drop(Box::from_raw(self.window_adapter));
+        }
+    }
+}
+
+/// Passes the safe area of the view of the window to the run-time window
+pub fn update_safe_area_insets(winit_window: &winit::window::Window, window: &WindowInner) {
+    let insets: UIEdgeInsets = unsafe {
+        let view = winit_window.ui_view() as *mut Object;
+        msg_send![view, safeAreaInsets]
+    };
+    // The insets are in points, which are the logical pixels
+    window.set_safe_area_insets(SafeAreaInsets {
+        top: LogicalLength::new(insets.top as _),
+        bottom: LogicalLength::new(insets.bottom as _),
+        left: LogicalLength::new(insets.left as _),
+        right: LogicalLength::new(insets.right as _),
+    });
+}
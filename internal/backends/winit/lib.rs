@@ -17,7 +17,24 @@
 mod glcontext;
 #[cfg(any(feature = "renderer-winit-femtovg", skia_backend_opengl))]
 use glcontext::*;
+#[cfg(not(target_arch = "wasm32"))]
+mod accesskit;
+#[cfg(target_os = "android")]
+mod android;
 pub(crate) mod event_loop;
+#[cfg(target_os = "ios")]
+mod ios;
+#[cfg(all(
+    any(
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd"
+    ),
+    feature = "wayland"
+))]
+mod wayland;
 mod renderer {
     use std::rc::Weak;
 
@@ -84,7 +101,7 @@ fn window_factory_fn<R: WinitCompatibleRenderer + 'static>() -> Rc<dyn WindowAda
     } else if #[cfg(feature = "renderer-winit-software")] {
         type DefaultRenderer = renderer::sw::SoftwareRenderer<0>;
     } else {
-        compile_error!("Please select a feature to build with the winit backend: `renderer-winit-femtovg`, `renderer-winit-skia`, `renderer-winit-skia-opengl` or `renderer-winit-software`");
+        compile_error!("Please select a feature to build with the winit backend: `renderer-winit-femtovg`, `renderer-winit-skia`, `renderer-winit-skia-opengl`, `renderer-winit-skia-vulkan` or `renderer-winit-software`");
     }
 }
 
@@ -234,6 +251,10 @@ fn winsys_name(&self) -> &'static str {
                 let winsys = "windows";
             } else if #[cfg(target_os = "macos")] {
                 let winsys = "macos";
+            } else if #[cfg(target_os = "android")] {
+                let winsys = "android";
+            } else if #[cfg(target_os = "ios")] {
+                let winsys = "ios";
             } else {
                 let winsys = "unknown";
             }
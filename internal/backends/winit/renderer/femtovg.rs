@@ -290,7 +290,8 @@ fn text_input_byte_offset_for_position(
             PhysicalSize::from_lengths(width, height),
             (text_input.horizontal_alignment(), text_input.vertical_alignment()),
             text_input.wrap(),
-            i_slint_core::items::TextOverflow::Clip,
+            (i_slint_core::items::TextOverflow::Clip, i_slint_core::items::TextElidePosition::End),
+            None,
             text_input.single_line(),
             paint,
             |line_text, line_pos, start, metrics| {
@@ -361,7 +362,8 @@ fn text_input_cursor_rect_for_byte_offset(
             PhysicalSize::from_lengths(width, height),
             (text_input.horizontal_alignment(), text_input.vertical_alignment()),
             text_input.wrap(),
-            i_slint_core::items::TextOverflow::Clip,
+            (i_slint_core::items::TextOverflow::Clip, i_slint_core::items::TextElidePosition::End),
+            None,
             text_input.single_line(),
             paint,
             |line_text, line_pos, start, metrics| {
@@ -6,13 +6,15 @@
 use femtovg::TextContext;
 use i_slint_core::graphics::euclid;
 use i_slint_core::graphics::FontRequest;
-use i_slint_core::items::{TextHorizontalAlignment, TextOverflow, TextVerticalAlignment, TextWrap};
+use i_slint_core::items::{
+    TextElidePosition, TextHorizontalAlignment, TextOverflow, TextVerticalAlignment, TextWrap,
+};
 use i_slint_core::lengths::{LogicalLength, LogicalSize, ScaleFactor, SizeLengths};
 use i_slint_core::{SharedString, SharedVector};
 use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
 
-use super::{PhysicalLength, PhysicalPoint, PhysicalSize};
+use super::{PhysicalLength, PhysicalPoint, PhysicalRect, PhysicalSize};
 
 pub const DEFAULT_FONT_SIZE: LogicalLength = LogicalLength::new(12.);
 pub const DEFAULT_FONT_WEIGHT: i32 = 400; // CSS normal
@@ -21,6 +23,7 @@
     target_family = "windows",
     target_os = "macos",
     target_os = "ios",
+    target_os = "android",
     target_arch = "wasm32"
 )))]
 mod fontconfig;
@@ -30,10 +33,9 @@
 /// font.
 pub fn register_font_from_memory(data: &'static [u8]) -> Result<(), Box<dyn std::error::Error>> {
     FONT_CACHE.with(|cache| {
-        cache
-            .borrow_mut()
-            .available_fonts
-            .load_font_source(fontdb::Source::Binary(std::sync::Arc::new(data)))
+        let mut cache = cache.borrow_mut();
+        cache.available_fonts.load_font_source(fontdb::Source::Binary(std::sync::Arc::new(data)));
+        cache.update_available_families();
     });
     Ok(())
 }
@@ -53,7 +55,10 @@ pub fn register_font_from_path(path: &std::path::Path) -> Result<(), Box<dyn std
             }
         }
 
-        cache.borrow_mut().available_fonts.load_font_file(requested_path).map_err(|e| e.into())
+        let mut cache = cache.borrow_mut();
+        cache.available_fonts.load_font_file(requested_path)?;
+        cache.update_available_families();
+        Ok(())
     })
 }
 
@@ -80,6 +85,10 @@ pub struct Font {
 }
 
 impl Font {
+    pub fn pixel_size(&self) -> PhysicalLength {
+        self.pixel_size
+    }
+
     pub fn init_paint(
         &self,
         letter_spacing: PhysicalLength,
@@ -171,11 +180,39 @@ enum GlyphCoverageCheckResult {
     Complete,
 }
 
+/// Key to identify the bitmap of a color glyph: the font face, the glyph id and the requested pixels per em.
+pub type ColorGlyphCacheKey = (fontdb::ID, u16, u16);
+
+/// The bitmap of a glyph of a color bitmap font, such as an emoji of a font with CBDT or sbix tables.
+/// FemtoVG doesn't render these glyphs, so they're drawn as images instead.
+pub struct ColorGlyph {
+    pub cache_key: ColorGlyphCacheKey,
+    pub png_data: Vec<u8>,
+    /// The geometry of the bitmap, relative to the pen position on the baseline.
+    pub rect: PhysicalRect,
+}
+
+/// Returns true if the character is an emoji that is presented in color by default. Such characters
+/// are looked up in color fonts only, otherwise a monochrome glyph of the primary font would be used.
+fn is_emoji_presentation(ch: char) -> bool {
+    matches!(ch,
+        '\u{1F000}'..='\u{1F0FF}' // Mahjong, domino and playing cards
+        | '\u{1F300}'..='\u{1F5FF}' // Miscellaneous symbols and pictographs
+        | '\u{1F600}'..='\u{1F64F}' // Emoticons
+        | '\u{1F680}'..='\u{1F6FF}' // Transport and map symbols
+        | '\u{1F900}'..='\u{1F9FF}' // Supplemental symbols and pictographs
+        | '\u{1FA70}'..='\u{1FAFF}' // Symbols and pictographs extended-A
+    )
+}
+
 pub struct FontCache {
     loaded_fonts: HashMap<FontCacheKey, LoadedFont>,
     // for a given fontdb face id, this tells us what we've learned about the script
     // coverage of the font.
     loaded_font_coverage: HashMap<fontdb::ID, GlyphCoverage>,
+    // for a given fontdb face id, whether the font has color bitmaps (CBDT or sbix tables)
+    color_bitmap_fonts: HashMap<fontdb::ID, bool>,
+    color_glyphs: HashMap<ColorGlyphCacheKey, Option<std::rc::Rc<ColorGlyph>>>,
     pub(crate) text_context: TextContext,
     pub(crate) available_fonts: fontdb::Database,
     available_families: HashSet<SharedString>,
@@ -220,6 +257,7 @@ fn default() -> Self {
                 target_family = "windows",
                 target_os = "macos",
                 target_os = "ios",
+                target_os = "android",
                 target_arch = "wasm32"
             )))]
             let default_sans_serif_family = {
@@ -229,6 +267,14 @@ fn default() -> Self {
                     .collect::<Vec<SharedString>>();
                 fontconfig_fallback_families.remove(0)
             };
+            // There is no fontconfig on Android, the fonts of the system are all in one directory
+            #[cfg(target_os = "android")]
+            let default_sans_serif_family = {
+                font_db.load_fonts_dir("/system/fonts");
+                fontconfig_fallback_families =
+                    vec!["Noto Sans CJK JP".into(), "Noto Color Emoji".into()];
+                "Roboto"
+            };
             font_db.set_sans_serif_family(default_sans_serif_family);
         }
         let available_families =
@@ -237,6 +283,8 @@ fn default() -> Self {
         Self {
             loaded_fonts: HashMap::new(),
             loaded_font_coverage: HashMap::new(),
+            color_bitmap_fonts: HashMap::new(),
+            color_glyphs: HashMap::new(),
             text_context: Default::default(),
             available_fonts: font_db,
             available_families,
@@ -333,16 +381,32 @@ pub fn font(
         let pixel_size = font_request.pixel_size.unwrap_or(DEFAULT_FONT_SIZE) * scale_factor;
         let weight = font_request.weight.unwrap_or(DEFAULT_FONT_WEIGHT);
 
-        let primary_font = self.load_single_font(font_request.family.as_ref(), weight);
+        // The first family of the list that is available is the primary font, the others are tried
+        // before the registered and the system fallbacks.
+        let mut requested_families = font_request
+            .families()
+            .map(SharedString::from)
+            .filter(|family| self.is_known_family(family))
+            .collect::<Vec<_>>();
+        let primary_family =
+            if requested_families.is_empty() { None } else { Some(requested_families.remove(0)) };
+
+        let primary_font = self.load_single_font(primary_family.as_ref(), weight);
 
         use unicode_script::{Script, UnicodeScript};
         // map from required script to sample character
         let mut scripts_required: HashMap<unicode_script::Script, char> = Default::default();
         let mut chars_required: HashSet<char> = Default::default();
+        // emoji that need to be covered by a color font
+        let mut emoji_required: HashSet<char> = Default::default();
         for ch in reference_text.chars() {
             if ch.is_control() || ch.is_whitespace() {
                 continue;
             }
+            if is_emoji_presentation(ch) {
+                emoji_required.insert(ch);
+                continue;
+            }
             let script = ch.script();
             if script == Script::Common || script == Script::Inherited || script == Script::Unknown
             {
@@ -352,9 +416,10 @@ pub fn font(
             }
         }
 
-        let mut coverage_result = self.check_and_update_script_coverage(
+        let mut coverage_result = self.check_and_update_coverage(
             &mut scripts_required,
             &mut chars_required,
+            &mut emoji_required,
             primary_font.fontdb_face_id,
         );
 
@@ -364,12 +429,20 @@ pub fn font(
         //);
 
         let fallbacks = if !matches!(coverage_result, GlyphCoverageCheckResult::Complete) {
-            self.font_fallbacks_for_request(
-                font_request.family.as_ref(),
+            let registered_fallbacks = i_slint_core::graphics::fallback_font_families()
+                .into_iter()
+                .filter(|family| self.is_known_family(family));
+            let system_fallbacks = self.font_fallbacks_for_request(
+                primary_family.as_ref(),
                 pixel_size,
                 &primary_font,
                 reference_text,
-            )
+            );
+            requested_families
+                .into_iter()
+                .chain(registered_fallbacks)
+                .chain(system_fallbacks)
+                .collect::<Vec<_>>()
         } else {
             Vec::new()
         };
@@ -382,9 +455,10 @@ pub fn font(
 
                 let fallback_font = self.load_single_font(Some(fallback_family), weight);
 
-                coverage_result = self.check_and_update_script_coverage(
+                coverage_result = self.check_and_update_coverage(
                     &mut scripts_required,
                     &mut chars_required,
+                    &mut emoji_required,
                     fallback_font.fontdb_face_id,
                 );
 
@@ -399,7 +473,7 @@ pub fn font(
         Font { fonts, text_context: self.text_context.clone(), pixel_size }
     }
 
-    #[cfg(target_os = "macos")]
+    #[cfg(any(target_os = "macos", target_os = "ios"))]
     fn font_fallbacks_for_request(
         &self,
         _family: Option<&SharedString>,
@@ -504,7 +578,12 @@ fn get_paragraph_reading_direction(
         fallback_fonts
     }
 
-    #[cfg(all(not(target_os = "macos"), not(target_os = "windows"), not(target_arch = "wasm32")))]
+    #[cfg(not(any(
+        target_os = "macos",
+        target_os = "ios",
+        target_os = "windows",
+        target_arch = "wasm32"
+    )))]
     fn font_fallbacks_for_request(
         &self,
         _family: Option<&SharedString>,
@@ -538,6 +617,120 @@ fn is_known_family(&self, family: &SharedString) -> bool {
         self.available_families.contains(family)
     }
 
+    fn update_available_families(&mut self) {
+        self.available_families = self
+            .available_fonts
+            .faces()
+            .iter()
+            .map(|face_info| face_info.family.as_str().into())
+            .collect();
+    }
+
+    // Same as check_and_update_script_coverage, but also removes the emoji covered by the face, if it is
+    // a color font.
+    fn check_and_update_coverage(
+        &mut self,
+        scripts_without_coverage: &mut HashMap<unicode_script::Script, char>,
+        chars_without_coverage: &mut HashSet<char>,
+        emoji_without_coverage: &mut HashSet<char>,
+        face_id: fontdb::ID,
+    ) -> GlyphCoverageCheckResult {
+        let old_uncovered_count = scripts_without_coverage.len()
+            + chars_without_coverage.len()
+            + emoji_without_coverage.len();
+
+        if !emoji_without_coverage.is_empty() && self.is_color_bitmap_font(face_id) {
+            self.check_and_update_script_coverage(
+                &mut HashMap::new(),
+                emoji_without_coverage,
+                face_id,
+            );
+        }
+        self.check_and_update_script_coverage(
+            scripts_without_coverage,
+            chars_without_coverage,
+            face_id,
+        );
+
+        let uncovered_count = scripts_without_coverage.len()
+            + chars_without_coverage.len()
+            + emoji_without_coverage.len();
+        if uncovered_count == 0 {
+            GlyphCoverageCheckResult::Complete
+        } else if uncovered_count < old_uncovered_count {
+            GlyphCoverageCheckResult::Improved
+        } else {
+            GlyphCoverageCheckResult::Incomplete
+        }
+    }
+
+    fn is_color_bitmap_font(&mut self, face_id: fontdb::ID) -> bool {
+        let available_fonts = &self.available_fonts;
+        *self.color_bitmap_fonts.entry(face_id).or_insert_with(|| {
+            available_fonts
+                .with_face_data(face_id, |face_data, face_index| {
+                    ttf_parser::Face::parse(face_data, face_index).map_or(false, |face| {
+                        let tables = face.tables();
+                        tables.cbdt.is_some() || tables.sbix.is_some()
+                    })
+                })
+                .unwrap_or(false)
+        })
+    }
+
+    /// Returns the bitmap of the glyph if the font is a color bitmap font, such as an emoji font.
+    pub fn color_glyph(
+        &mut self,
+        font_id: femtovg::FontId,
+        glyph_id: u16,
+        pixel_size: PhysicalLength,
+    ) -> Option<std::rc::Rc<ColorGlyph>> {
+        let face_id = self
+            .loaded_fonts
+            .values()
+            .find(|loaded_font| loaded_font.femtovg_font_id == font_id)?
+            .fontdb_face_id;
+        if !self.is_color_bitmap_font(face_id) {
+            return None;
+        }
+        let pixels_per_em = pixel_size.get().round().max(1.) as u16;
+        let cache_key = (face_id, glyph_id, pixels_per_em);
+        let available_fonts = &self.available_fonts;
+        self.color_glyphs
+            .entry(cache_key)
+            .or_insert_with(|| {
+                available_fonts
+                    .with_face_data(face_id, |face_data, face_index| {
+                        let face = ttf_parser::Face::parse(face_data, face_index).ok()?;
+                        // The font picks the strike that is the closest to the requested size
+                        let image =
+                            face.glyph_raster_image(ttf_parser::GlyphId(glyph_id), pixels_per_em)?;
+                        if image.format != ttf_parser::RasterImageFormat::PNG {
+                            return None;
+                        }
+                        let scale = pixel_size.get() / image.pixels_per_em as f32;
+                        // The offsets of the image are relative to the baseline, with y pointing up
+                        let rect = PhysicalRect::new(
+                            PhysicalPoint::new(
+                                image.x as f32 * scale,
+                                -(image.y as f32 + image.height as f32) * scale,
+                            ),
+                            PhysicalSize::new(
+                                image.width as f32 * scale,
+                                image.height as f32 * scale,
+                            ),
+                        );
+                        Some(std::rc::Rc::new(ColorGlyph {
+                            cache_key,
+                            png_data: image.data.to_vec(),
+                            rect,
+                        }))
+                    })
+                    .flatten()
+            })
+            .clone()
+    }
+
     // From the set of script without coverage, remove all entries that are known to be covered by
     // the given face_id. Any yet unknown script coverage for the face_id is updated (hence
     // mutable self).
@@ -618,6 +811,7 @@ fn check_and_update_script_coverage(
 /// Layout the given string in lines, and call the `layout_line` callback with the line to draw at position y.
 /// The signature of the `layout_line` function is: `(text, pos, start_index, line_metrics)`.
 /// start index is the starting byte of the text in the string.
+/// When `max_lines` is set, the text is laid out in at most that many lines.
 /// Returns the y coordinate of where to place the cursor if it is at the end of the text
 pub(crate) fn layout_text_lines(
     string: &str,
@@ -625,7 +819,8 @@ pub(crate) fn layout_text_lines(
     max_size: PhysicalSize,
     (horizontal_alignment, vertical_alignment): (TextHorizontalAlignment, TextVerticalAlignment),
     wrap: TextWrap,
-    overflow: TextOverflow,
+    (overflow, elide_position): (TextOverflow, TextElidePosition),
+    max_lines: Option<usize>,
     single_line: bool,
     paint: femtovg::Paint,
     mut layout_line: impl FnMut(&str, PhysicalPoint, usize, &femtovg::TextMetrics),
@@ -640,17 +835,20 @@ pub(crate) fn layout_text_lines(
     let font_metrics = text_context.measure_font(paint).unwrap();
     let font_height = PhysicalLength::new(font_metrics.height());
 
+    let max_lines_height = max_lines.map(|max_lines| font_height * max_lines as f32);
     let text_height = || {
         if single_line {
             font_height
         } else {
             // Note: this is kind of doing twice the layout because text_size also does it
-            font.text_size(
-                PhysicalLength::new(paint.letter_spacing()),
-                string,
-                if wrap { Some(max_width) } else { None },
-            )
-            .height_length()
+            let height = font
+                .text_size(
+                    PhysicalLength::new(paint.letter_spacing()),
+                    string,
+                    if wrap { Some(max_width) } else { None },
+                )
+                .height_length();
+            max_lines_height.map_or(height, |max| height.min(max))
         }
     };
 
@@ -673,6 +871,8 @@ pub(crate) fn layout_text_lines(
         TextVerticalAlignment::Center => max_height / 2. - text_height() / 2.,
         TextVerticalAlignment::Bottom => max_height - text_height(),
     };
+    // The bottom of the area in which lines are laid out
+    let max_height = max_lines_height.map_or(max_height, |max| max_height.min(baseline_y + max));
     let mut y = baseline_y;
     let mut start = 0;
     'lines: while start < string.len() && y + font_height <= max_height {
@@ -698,6 +898,45 @@ pub(crate) fn layout_text_lines(
             let text_metrics = text_context.measure_text(0., 0., line, paint).unwrap();
             let elide_last_line =
                 elide && index < string.len() && y + font_height * 2. > max_height;
+            if elide
+                && !elide_last_line
+                && elide_position != TextElidePosition::End
+                && text_metrics.width() > max_width.get()
+            {
+                let w = max_width.get()
+                    - text_context.measure_text(0., 0., "…", paint).unwrap().width();
+                let line = line.trim_end_matches('\n');
+                let glyphs = &text_metrics.glyphs;
+                let prefix_width =
+                    if elide_position == TextElidePosition::Middle { w / 2. } else { 0. };
+                let mut width = 0.;
+                let mut prefix_glyphs = 0;
+                while prefix_glyphs < glyphs.len()
+                    && width + glyphs[prefix_glyphs].advance_x <= prefix_width
+                {
+                    width += glyphs[prefix_glyphs].advance_x;
+                    prefix_glyphs += 1;
+                }
+                let mut suffix_start = glyphs.len();
+                while suffix_start > prefix_glyphs
+                    && width + glyphs[suffix_start - 1].advance_x <= w
+                {
+                    width += glyphs[suffix_start - 1].advance_x;
+                    suffix_start -= 1;
+                }
+                let byte_index = |glyph_index: usize| {
+                    glyphs.get(glyph_index).map_or(line.len(), |g| g.byte_index.min(line.len()))
+                };
+                let elided = format!(
+                    "{}…{}",
+                    &line[..byte_index(prefix_glyphs)],
+                    &line[byte_index(suffix_start)..]
+                );
+                process_line(&elided, y, start, &text_metrics);
+                y += font_height;
+                start = index;
+                continue 'lines;
+            }
             if text_metrics.width() > max_width.get() || elide_last_line {
                 let w = max_width
                     - if elide {
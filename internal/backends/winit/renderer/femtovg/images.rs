@@ -115,6 +115,19 @@ pub fn new_from_image(
                     return None;
                 }
             }
+            #[cfg(not(target_arch = "wasm32"))]
+            ImageInner::BorrowedOpenGLTexture(texture) => canvas
+                .borrow_mut()
+                .create_image_from_native_texture(
+                    glow::NativeTexture(texture.texture_id),
+                    femtovg::ImageInfo::new(
+                        image_flags | femtovg::ImageFlags::PREMULTIPLIED,
+                        texture.size.width as _,
+                        texture.size.height as _,
+                        femtovg::PixelFormat::Rgba8,
+                    ),
+                )
+                .ok()?,
             _ => {
                 let buffer = image.render_to_buffer(target_size_for_scalable_source)?;
                 let (image_source, flags) = image_buffer_to_image_source(&buffer);
@@ -156,7 +169,11 @@ pub fn new(
 // Cache used to avoid repeatedly decoding images from disk. Entries with a count
 // of 1 are drained after flushing the renderer commands to the screen.
 #[derive(Default)]
-pub struct TextureCache(HashMap<TextureCacheKey, Rc<Texture>>);
+pub struct TextureCache {
+    images: HashMap<TextureCacheKey, Rc<Texture>>,
+    // The bitmaps of color glyphs (emoji) are kept until the cache is cleared
+    color_glyphs: HashMap<super::fonts::ColorGlyphCacheKey, Rc<Texture>>,
+}
 
 impl TextureCache {
     // Look up the given image cache key in the image cache and upgrade the weak reference to a strong one if found,
@@ -166,7 +183,7 @@ pub(crate) fn lookup_image_in_cache_or_create(
         cache_key: TextureCacheKey,
         image_create_fn: impl Fn() -> Option<Rc<Texture>>,
     ) -> Option<Rc<Texture>> {
-        Some(match self.0.entry(cache_key) {
+        Some(match self.images.entry(cache_key) {
             std::collections::hash_map::Entry::Occupied(existing_entry) => {
                 existing_entry.get().clone()
             }
@@ -179,7 +196,7 @@ pub(crate) fn lookup_image_in_cache_or_create(
     }
 
     pub(crate) fn drain(&mut self) {
-        self.0.retain(|_, cached_image| {
+        self.images.retain(|_, cached_image| {
             // * Retain images that are used by elements, so that they can be effectively
             // shared (one image element refers to foo.png, another element is created
             // and refers to the same -> share).
@@ -193,7 +210,28 @@ pub(crate) fn drain(&mut self) {
     }
 
     pub(crate) fn clear(&mut self) {
-        self.0.clear();
+        self.images.clear();
+        self.color_glyphs.clear();
+    }
+
+    pub(crate) fn lookup_color_glyph_or_create(
+        &mut self,
+        glyph: &super::fonts::ColorGlyph,
+        canvas: &CanvasRc,
+        canvas_ref: &mut super::itemrenderer::Canvas,
+    ) -> Option<Rc<Texture>> {
+        Some(match self.color_glyphs.entry(glyph.cache_key) {
+            std::collections::hash_map::Entry::Occupied(existing_entry) => {
+                existing_entry.get().clone()
+            }
+            std::collections::hash_map::Entry::Vacant(vacant_entry) => {
+                // canvas_ref is the borrowed canvas, as the text is drawn while the canvas is borrowed
+                let image_id = canvas_ref
+                    .load_image_mem(&glyph.png_data, femtovg::ImageFlags::empty())
+                    .ok()?;
+                vacant_entry.insert(Texture::adopt(canvas, image_id)).clone()
+            }
+        })
     }
 }
 
@@ -9,15 +9,15 @@
 use i_slint_core::graphics::euclid::num::Zero;
 use i_slint_core::graphics::euclid::{self};
 use i_slint_core::graphics::rendering_metrics_collector::RenderingMetrics;
-use i_slint_core::graphics::{Image, IntRect, Point, Size};
+use i_slint_core::graphics::{FontRequest, Image, IntRect, Point, Size};
 use i_slint_core::item_rendering::{ItemCache, ItemRenderer};
 use i_slint_core::items::{
-    self, Clip, FillRule, ImageFit, ImageRendering, InputType, Item, ItemRc, Layer, Opacity,
-    RenderingResult,
+    self, Clip, DropShadow, Effect, FillRule, ImageFit, ImageRendering, InputType, Item, ItemRc,
+    Layer, LineCap, LineJoin, Opacity, RenderingResult,
 };
 use i_slint_core::lengths::{
-    LogicalLength, LogicalPoint, LogicalRect, LogicalSize, LogicalVector, PointLengths,
-    RectLengths, ScaleFactor,
+    BorderRadius, LogicalBorderRadius, LogicalLength, LogicalPoint, LogicalRect, LogicalSize,
+    LogicalTransform, LogicalVector, PointLengths, RectLengths, ScaleFactor,
 };
 use i_slint_core::window::WindowInner;
 use i_slint_core::{Brush, Color, ImageInner, Property, SharedString};
@@ -83,13 +83,31 @@ pub struct GLItemRenderer<'a> {
     metrics: RenderingMetrics,
 }
 
-fn rect_with_radius_to_path(rect: PhysicalRect, border_radius: PhysicalLength) -> femtovg::Path {
+fn rect_with_radius_to_path(
+    rect: PhysicalRect,
+    border_radius: BorderRadius<PhysicalLength>,
+) -> femtovg::Path {
     let mut path = femtovg::Path::new();
     let x = rect.origin.x;
     let y = rect.origin.y;
     let width = rect.size.width;
     let height = rect.size.height;
-    let border_radius = border_radius.get();
+    let border_radius = match border_radius.as_uniform() {
+        Some(radius) => radius.get(),
+        None => {
+            path.rounded_rect_varying(
+                x,
+                y,
+                width,
+                height,
+                border_radius.top_left.get(),
+                border_radius.top_right.get(),
+                border_radius.bottom_right.get(),
+                border_radius.bottom_left.get(),
+            );
+            return path;
+        }
+    };
     // If we're drawing a circle, use directly connected bezier curves instead of
     // ones with intermediate LineTo verbs, as `rounded_rect` creates, to avoid
     // rendering artifacts due to those edges.
@@ -101,8 +119,88 @@ fn rect_with_radius_to_path(rect: PhysicalRect, border_radius: PhysicalLength) -
     path
 }
 
+/// The outline and the lines that `fill_text` draws in addition to the glyphs.
+#[derive(Default)]
+struct TextDecorations {
+    underline: bool,
+    strikethrough: bool,
+    /// The paint to stroke the outline of the glyphs with, if any.
+    stroke: Option<femtovg::Paint>,
+    /// The thickness of the underline and strikethrough lines.
+    line_thickness: f32,
+}
+
+/// Draws the text with `Canvas::fill_text`, followed by the glyphs of color bitmap fonts, such as emoji,
+/// that femtovg doesn't render, and the decorations.
+fn fill_text(
+    canvas: &mut Canvas,
+    canvas_rc: &CanvasRc,
+    texture_cache: &RefCell<super::images::TextureCache>,
+    pixel_size: PhysicalLength,
+    pos: (f32, f32),
+    text: &str,
+    paint: femtovg::Paint,
+    decorations: &TextDecorations,
+) {
+    let metrics = match canvas.fill_text(pos.0, pos.1, text, paint) {
+        Ok(metrics) => metrics,
+        Err(_) => return,
+    };
+    if let Some(stroke) = decorations.stroke {
+        canvas.stroke_text(pos.0, pos.1, text, stroke).ok();
+    }
+    if decorations.underline || decorations.strikethrough {
+        let ascender = canvas.measure_font(paint).map_or(0., |m| m.ascender());
+        let mut lines = femtovg::Path::new();
+        if decorations.underline {
+            lines.rect(pos.0, pos.1 + ascender + 1., metrics.width(), decorations.line_thickness);
+        }
+        if decorations.strikethrough {
+            lines.rect(
+                pos.0,
+                pos.1 + ascender * 0.65 - decorations.line_thickness / 2.,
+                metrics.width(),
+                decorations.line_thickness,
+            );
+        }
+        canvas.fill_path(&mut lines, paint);
+    }
+    for glyph in &metrics.glyphs {
+        let color_glyph = fonts::FONT_CACHE.with(|cache| {
+            cache.borrow_mut().color_glyph(glyph.font_id, glyph.codepoint as u16, pixel_size)
+        });
+        let color_glyph = match color_glyph {
+            Some(color_glyph) => color_glyph,
+            None => continue,
+        };
+        let texture = match texture_cache.borrow_mut().lookup_color_glyph_or_create(
+            &color_glyph,
+            canvas_rc,
+            canvas,
+        ) {
+            Some(texture) => texture,
+            None => continue,
+        };
+        // The position of the glyph is the top-left corner of its outline: go back to the pen
+        // position on the baseline.
+        let origin = PhysicalPoint::new(glyph.x - glyph.bearing_x, glyph.y + glyph.bearing_y);
+        let rect = color_glyph.rect.translate(origin.to_vector());
+        let mut path = rect_to_path(rect);
+        let paint = femtovg::Paint::image(
+            texture.id,
+            rect.origin.x,
+            rect.origin.y,
+            rect.size.width,
+            rect.size.height,
+            0.,
+            1.,
+        );
+        canvas.fill_path(&mut path, paint);
+    }
+}
+
 fn rect_to_path(r: PhysicalRect) -> femtovg::Path {
-    rect_with_radius_to_path(r, PhysicalLength::default())
+    rect_with_radius_to_path(r, BorderRadius::default())
 }
 
 fn adjust_rect_and_border_for_inner_drawing(
@@ -141,7 +239,7 @@ fn path_bounding_box(canvas: &CanvasRc, path: &mut femtovg::Path) -> euclid::def
 // Return a femtovg::Path (in physical pixels) that represents the clip_rect, radius and border_width (all logical!)
 fn clip_path_for_rect_alike_item(
     clip_rect: LogicalRect,
-    mut radius: LogicalLength,
+    radius: LogicalBorderRadius,
     mut border_width: LogicalLength,
     scale_factor: ScaleFactor,
 ) -> femtovg::Path {
@@ -149,12 +247,12 @@ fn clip_path_for_rect_alike_item(
     // adjust_rect_and_border_for_inner_drawing adjusts the rect so that for drawing it
     // would be entirely an *inner* border. However for clipping we want the rect that's
     // entirely inside, hence the doubling of the width and consequently radius adjustment.
-    radius -= border_width * KAPPA90;
+    let radius = radius.map(|radius| radius - border_width * KAPPA90);
     border_width *= 2.;
 
     // Convert from logical to physical pixels
     let mut border_width = border_width * scale_factor;
-    let radius = radius * scale_factor;
+    let radius = radius.map(|radius| radius * scale_factor);
     let mut clip_rect = clip_rect * scale_factor;
 
     adjust_rect_and_border_for_inner_drawing(&mut clip_rect, &mut border_width);
@@ -163,6 +261,15 @@ fn clip_path_for_rect_alike_item(
 }
 
 impl<'a> GLItemRenderer<'a> {
+    /// Maps the scissor to the coordinates of the items that are rendered with the given
+    /// transformation, which maps their coordinates to the current ones.
+    fn transform_clip(&mut self, transform: LogicalTransform) {
+        let clip = &mut self.state.last_mut().unwrap().scissor;
+        *clip = transform
+            .inverse()
+            .map_or_else(LogicalRect::default, |inverse| inverse.outer_transformed_rect(clip));
+    }
+
     /// Draws a `Rectangle` using the `GLItemRenderer`.
     pub fn draw_rect(&mut self, rect: LogicalRect, brush: Brush) {
         let geometry = PhysicalRect::new(PhysicalPoint::default(), rect.size * self.scale_factor);
@@ -200,7 +307,10 @@ fn draw_border_rectangle(&mut self, rect: Pin<&items::BorderRectangle>, _: &Item
         // is adjusted accordingly.
         adjust_rect_and_border_for_inner_drawing(&mut geometry, &mut border_width);
 
-        let mut path = rect_with_radius_to_path(geometry, rect.border_radius() * self.scale_factor);
+        let mut path = rect_with_radius_to_path(
+            geometry,
+            rect.border_radii().map(|radius| radius * self.scale_factor),
+        );
 
         let fill_paint = self.brush_to_paint(rect.background(), &mut path);
 
@@ -221,7 +331,7 @@ fn draw_border_rectangle(&mut self, rect: Pin<&items::BorderRectangle>, _: &Item
     fn draw_image(&mut self, image: Pin<&items::ImageItem>, item_rc: &ItemRc) {
         self.draw_image_impl(
             item_rc,
-            items::ImageItem::FIELD_OFFSETS.source.apply_pin(image),
+            || image.displayed_source(),
             IntRect::default(),
             items::ImageItem::FIELD_OFFSETS.width.apply_pin(image),
             items::ImageItem::FIELD_OFFSETS.height.apply_pin(image),
@@ -239,7 +349,7 @@ fn draw_clipped_image(&mut self, clipped_image: Pin<&items::ClippedImage>, item_
 
         self.draw_image_impl(
             item_rc,
-            items::ClippedImage::FIELD_OFFSETS.source.apply_pin(clipped_image),
+            || clipped_image.displayed_source(),
             source_clip_rect,
             items::ClippedImage::FIELD_OFFSETS.width.apply_pin(clipped_image),
             items::ClippedImage::FIELD_OFFSETS.height.apply_pin(clipped_image),
@@ -257,22 +367,50 @@ fn draw_text(&mut self, text: Pin<&items::Text>, _: &ItemRc) {
             return;
         }
 
-        let string = text.text();
+        let rich_text = text.rich_text_layout();
+        let string = match &rich_text {
+            Some(rich_text) => SharedString::from(rich_text.text.as_str()),
+            None => text.text(),
+        };
         let string = string.as_str();
-        let font = fonts::FONT_CACHE.with(|cache| {
-            cache.borrow_mut().font(
-                text.font_request(WindowInner::from_pub(&self.window)),
-                self.scale_factor,
-                &text.text(),
-            )
-        });
+        let font_request = text.font_request(WindowInner::from_pub(&self.window));
+        let font = fonts::FONT_CACHE
+            .with(|cache| cache.borrow_mut().font(font_request.clone(), self.scale_factor, string));
 
+        let letter_spacing = text.letter_spacing() * self.scale_factor;
         let paint = match self
             .brush_to_paint(text.color(), &mut rect_to_path(item_rect(text, self.scale_factor)))
         {
-            Some(paint) => font.init_paint(text.letter_spacing() * self.scale_factor, paint),
+            Some(paint) => font.init_paint(letter_spacing, paint),
             None => return,
         };
+        let bold_paint =
+            rich_text.as_ref().filter(|rt| rt.spans.iter().any(|s| s.style.bold)).map(|_| {
+                let bold_font = fonts::FONT_CACHE.with(|cache| {
+                    cache.borrow_mut().font(
+                        FontRequest { weight: Some(700), ..font_request },
+                        self.scale_factor,
+                        string,
+                    )
+                });
+                bold_font.init_paint(letter_spacing, paint)
+            });
+        let decorations = TextDecorations {
+            underline: text.underline(),
+            strikethrough: text.strikethrough(),
+            stroke: self.text_stroke_paint(
+                text.stroke(),
+                text.stroke_width(),
+                &font,
+                letter_spacing,
+                item_rect(text, self.scale_factor),
+            ),
+            line_thickness: self.scale_factor.get().max(1.),
+        };
+
+        // FemtoVG lays out lines with a left-to-right base direction, so embed right-to-left
+        // paragraphs in a right-to-left embedding.
+        let rtl = i_slint_core::textlayout::is_rtl(string, text.text_direction());
 
         let mut canvas = self.canvas.borrow_mut();
         fonts::layout_text_lines(
@@ -281,11 +419,99 @@ fn draw_text(&mut self, text: Pin<&items::Text>, _: &ItemRc) {
             PhysicalSize::from_lengths(max_width, max_height),
             (text.horizontal_alignment(), text.vertical_alignment()),
             text.wrap(),
-            text.overflow(),
+            (text.overflow(), text.elide_position()),
+            (text.max_lines() > 0).then(|| text.max_lines() as usize),
             false,
             paint,
-            |to_draw, pos, _, _| {
-                canvas.fill_text(pos.x, pos.y, to_draw.trim_end(), paint).unwrap();
+            |to_draw, pos, start, _| {
+                let to_draw = to_draw.trim_end();
+                let rich_text = match &rich_text {
+                    Some(rich_text) => rich_text,
+                    None if rtl => {
+                        fill_text(
+                            &mut canvas,
+                            &self.canvas,
+                            self.texture_cache,
+                            font.pixel_size(),
+                            (pos.x, pos.y),
+                            &format!("\u{202B}{}\u{202C}", to_draw),
+                            paint,
+                            &decorations,
+                        );
+                        return;
+                    }
+                    None => {
+                        fill_text(
+                            &mut canvas,
+                            &self.canvas,
+                            self.texture_cache,
+                            font.pixel_size(),
+                            (pos.x, pos.y),
+                            to_draw,
+                            paint,
+                            &decorations,
+                        );
+                        return;
+                    }
+                };
+                let mut x = pos.x;
+                let mut drawn = 0;
+                for (range, style) in rich_text.segments(start..start + to_draw.len()) {
+                    // When the line was elided, the segment boundaries may not fall on a character
+                    // boundary of the drawn string: merge such a segment with the next one.
+                    let local_end = (range.end - start).min(to_draw.len());
+                    let segment = match to_draw.get(drawn..local_end) {
+                        Some(segment) if !segment.is_empty() => segment,
+                        _ => continue,
+                    };
+                    drawn += segment.len();
+                    let mut segment_paint = match style {
+                        Some(style) if style.bold => bold_paint.unwrap_or(paint),
+                        _ => paint,
+                    };
+                    if let Some(color) = style.and_then(|s| s.color) {
+                        segment_paint.set_color(to_femtovg_color(&color));
+                    }
+                    let ascender = canvas.measure_font(segment_paint).map_or(0., |m| m.ascender());
+                    let italic = style.map_or(false, |s| s.italic);
+                    if italic {
+                        // The font may not have an italic variant, so slant the glyphs around the baseline
+                        canvas.save();
+                        canvas.translate(x, pos.y + ascender);
+                        canvas.skew_x(-i_slint_core::textlayout::ITALIC_SLANT.atan());
+                        canvas.translate(-x, -pos.y - ascender);
+                    }
+                    fill_text(
+                        &mut canvas,
+                        &self.canvas,
+                        self.texture_cache,
+                        font.pixel_size(),
+                        (x, pos.y),
+                        segment,
+                        segment_paint,
+                        &decorations,
+                    );
+                    if italic {
+                        canvas.restore();
+                    }
+                    let width = canvas
+                        .measure_text(x, pos.y, segment, segment_paint)
+                        .map_or(0., |m| m.width());
+                    if style.map_or(false, |s| s.underline || s.link.is_some()) {
+                        let mut underline = femtovg::Path::new();
+                        underline.rect(
+                            x,
+                            pos.y + ascender + 1.,
+                            width,
+                            self.scale_factor.get().max(1.),
+                        );
+                        canvas.fill_path(&mut underline, segment_paint);
+                    }
+                    x += width;
+                    if drawn >= to_draw.len() {
+                        break;
+                    }
+                }
             },
         );
     }
@@ -312,10 +538,26 @@ fn draw_text_input(&mut self, text_input: Pin<&items::TextInput>, _: &ItemRc) {
             Some(paint) => font.init_paint(text_input.letter_spacing() * self.scale_factor, paint),
             None => return,
         };
+        let decorations = TextDecorations {
+            underline: text_input.underline(),
+            strikethrough: text_input.strikethrough(),
+            stroke: self.text_stroke_paint(
+                text_input.stroke(),
+                text_input.stroke_width(),
+                &font,
+                text_input.letter_spacing() * self.scale_factor,
+                item_rect(text_input, self.scale_factor),
+            ),
+            line_thickness: self.scale_factor.get().max(1.),
+        };
 
         let visual_representation = text_input.visual_representation();
 
-        let (mut min_select, mut max_select) = if !visual_representation.preedit_range.is_empty() {
+        // The pre-edit text is drawn like a selection, but underlined instead of highlighted.
+        let is_preedit = !visual_representation.preedit_range.is_empty();
+        let preedit_decorations = TextDecorations { underline: true, ..decorations };
+
+        let (mut min_select, mut max_select) = if is_preedit {
             (visual_representation.preedit_range.start, visual_representation.preedit_range.end)
         } else {
             text_input.selection_anchor_and_cursor()
@@ -347,7 +589,8 @@ fn draw_text_input(&mut self, text_input: Pin<&items::TextInput>, _: &ItemRc) {
             PhysicalSize::from_lengths(width, height),
             (text_input.horizontal_alignment(), text_input.vertical_alignment()),
             text_input.wrap(),
-            items::TextOverflow::Clip,
+            (items::TextOverflow::Clip, items::TextElidePosition::End),
+            None,
             text_input.single_line(),
             paint,
             |to_draw, pos, start, metrics| {
@@ -392,44 +635,62 @@ fn draw_text_input(&mut self, text_input: Pin<&items::TextInput>, _: &ItemRc) {
                             font_height,
                         ),
                     );
-                    canvas.fill_path(
-                        &mut rect_to_path(selection_rect),
-                        femtovg::Paint::color(to_femtovg_color(
-                            &text_input.selection_background_color(),
-                        )),
-                    );
                     let mut selected_paint = paint;
-                    selected_paint
-                        .set_color(to_femtovg_color(&text_input.selection_foreground_color()));
-                    canvas
-                        .fill_text(
-                            pos.x,
-                            pos.y,
-                            &to_draw[..min_select.saturating_sub(start)].trim_end(),
-                            paint,
-                        )
-                        .unwrap();
-                    canvas
-                        .fill_text(
-                            pos.x + selection_start_x.get(),
-                            pos.y,
-                            &to_draw[min_select.saturating_sub(start)
-                                ..(max_select - start).min(to_draw.len())]
-                                .trim_end(),
-                            selected_paint,
-                        )
-                        .unwrap();
-                    canvas
-                        .fill_text(
-                            pos.x + after_selection_x.get(),
-                            pos.y,
-                            &to_draw[(max_select - start).min(to_draw.len())..].trim_end(),
-                            paint,
-                        )
-                        .unwrap();
+                    if !is_preedit {
+                        canvas.fill_path(
+                            &mut rect_to_path(selection_rect),
+                            femtovg::Paint::color(to_femtovg_color(
+                                &text_input.selection_background_color(),
+                            )),
+                        );
+                        selected_paint
+                            .set_color(to_femtovg_color(&text_input.selection_foreground_color()));
+                    }
+                    let pixel_size = font.pixel_size();
+                    fill_text(
+                        &mut canvas,
+                        &self.canvas,
+                        self.texture_cache,
+                        pixel_size,
+                        (pos.x, pos.y),
+                        to_draw[..min_select.saturating_sub(start)].trim_end(),
+                        paint,
+                        &decorations,
+                    );
+                    fill_text(
+                        &mut canvas,
+                        &self.canvas,
+                        self.texture_cache,
+                        pixel_size,
+                        (pos.x + selection_start_x.get(), pos.y),
+                        to_draw[min_select.saturating_sub(start)
+                            ..(max_select - start).min(to_draw.len())]
+                            .trim_end(),
+                        selected_paint,
+                        if is_preedit { &preedit_decorations } else { &decorations },
+                    );
+                    fill_text(
+                        &mut canvas,
+                        &self.canvas,
+                        self.texture_cache,
+                        pixel_size,
+                        (pos.x + after_selection_x.get(), pos.y),
+                        to_draw[(max_select - start).min(to_draw.len())..].trim_end(),
+                        paint,
+                        &decorations,
+                    );
                 } else {
                     // no selection on this line
-                    canvas.fill_text(pos.x, pos.y, to_draw.trim_end(), paint).unwrap();
+                    fill_text(
+                        &mut canvas,
+                        &self.canvas,
+                        self.texture_cache,
+                        font.pixel_size(),
+                        (pos.x, pos.y),
+                        to_draw.trim_end(),
+                        paint,
+                        &decorations,
+                    );
                 };
                 if cursor_visible
                     && (range.contains(&cursor_pos)
@@ -566,6 +827,16 @@ fn add_point(&mut self, p: Point) {
         let border_paint =
             self.brush_to_paint(path.stroke(), &mut femtovg_path).map(|mut paint| {
                 paint.set_line_width((path.stroke_width() * self.scale_factor).get());
+                paint.set_line_cap(match path.stroke_line_cap() {
+                    LineCap::Butt => femtovg::LineCap::Butt,
+                    LineCap::Round => femtovg::LineCap::Round,
+                    LineCap::Square => femtovg::LineCap::Square,
+                });
+                paint.set_line_join(match path.stroke_line_join() {
+                    LineJoin::Miter => femtovg::LineJoin::Miter,
+                    LineJoin::Round => femtovg::LineJoin::Round,
+                    LineJoin::Bevel => femtovg::LineJoin::Bevel,
+                });
                 paint
             });
 
@@ -738,6 +1009,164 @@ fn visit_layer(&mut self, layer_item: Pin<&Layer>, self_rc: &ItemRc) -> Renderin
         }
     }
 
+    fn visit_drop_shadow(
+        &mut self,
+        drop_shadow_item: Pin<&DropShadow>,
+        item_rc: &ItemRc,
+    ) -> RenderingResult {
+        if drop_shadow_item.is_invisible() {
+            self.graphics_cache.release(item_rc);
+            return RenderingResult::ContinueRenderingChildren;
+        }
+
+        let (layer_image, layer_size) = match self
+            .render_children_layer(item_rc)
+            .and_then(|image| image.size().map(|size| (image, size)))
+        {
+            Some(layer) => layer,
+            None => return RenderingResult::ContinueRenderingWithoutChildren,
+        };
+
+        let mut layer_rect = femtovg::Path::new();
+        layer_rect.rect(0., 0., layer_size.width as _, layer_size.height as _);
+
+        // The shadow is the silhouette of the layer, padded so that the blur isn't cut at the edges.
+        let blur =
+            (drop_shadow_item.drop_shadow_blur() * self.scale_factor).max(PhysicalLength::zero());
+        let padding = blur.get().ceil();
+        let shadow_width = layer_size.width + 2 * padding as u32;
+        let shadow_height = layer_size.height + 2 * padding as u32;
+        let shadow_image =
+            match Texture::new_empty_on_gpu(&self.canvas, shadow_width, shadow_height) {
+                Some(image) => image,
+                None => return RenderingResult::ContinueRenderingWithoutChildren,
+            };
+        let mut shadow_rect = femtovg::Path::new();
+        shadow_rect.rect(0., 0., shadow_width as _, shadow_height as _);
+
+        {
+            let mut canvas = self.canvas.borrow_mut();
+            canvas.save();
+            canvas.set_render_target(shadow_image.as_render_target());
+            canvas.reset();
+            canvas.clear_rect(0, 0, shadow_width, shadow_height, femtovg::Color::rgba(0, 0, 0, 0));
+            canvas.translate(padding, padding);
+            canvas.fill_path(&mut layer_rect, layer_image.as_paint().with_anti_alias(false));
+            canvas.restore();
+        }
+
+        let shadow_image = if blur.get() > 0. {
+            let blurred_image =
+                shadow_image.filter(femtovg::ImageFilter::GaussianBlur { sigma: blur.get() / 2. });
+            self.layer_images_to_delete_after_flush.borrow_mut().push(shadow_image);
+            blurred_image
+        } else {
+            shadow_image
+        };
+
+        {
+            let mut canvas = self.canvas.borrow_mut();
+            canvas.save();
+            canvas.set_render_target(shadow_image.as_render_target());
+            canvas.reset();
+            canvas.global_composite_operation(femtovg::CompositeOperation::SourceIn);
+            canvas.fill_path(
+                &mut shadow_rect,
+                femtovg::Paint::color(to_femtovg_color(&drop_shadow_item.drop_shadow_color())),
+            );
+            canvas.restore();
+            canvas.set_render_target(self.current_render_target());
+        }
+        self.layer_images_to_delete_after_flush.borrow_mut().push(shadow_image.clone());
+
+        let offset = LogicalPoint::from_lengths(
+            drop_shadow_item.drop_shadow_offset_x(),
+            drop_shadow_item.drop_shadow_offset_y(),
+        ) * self.scale_factor;
+        let mut canvas = self.canvas.borrow_mut();
+        canvas.save_with(|canvas| {
+            canvas.translate(offset.x - padding, offset.y - padding);
+            canvas.fill_path(&mut shadow_rect, shadow_image.as_paint().with_anti_alias(false));
+        });
+        canvas.fill_path(&mut layer_rect, layer_image.as_paint().with_anti_alias(false));
+
+        RenderingResult::ContinueRenderingWithoutChildren
+    }
+
+    fn visit_effect(&mut self, effect_item: Pin<&Effect>, item_rc: &ItemRc) -> RenderingResult {
+        if effect_item.is_identity() {
+            self.graphics_cache.release(item_rc);
+            return RenderingResult::ContinueRenderingChildren;
+        }
+
+        let (layer_image, layer_size) = match self
+            .render_children_layer(item_rc)
+            .and_then(|image| image.size().map(|size| (image, size)))
+        {
+            Some(layer) => layer,
+            None => return RenderingResult::ContinueRenderingWithoutChildren,
+        };
+
+        // femtovg has no color matrix filter: the brightness and the tint are approximated by
+        // drawing a color over the opaque parts of the layer, and the saturation is not supported.
+        let brightness = effect_item.effect_brightness();
+        let tint = effect_item.effect_tint();
+        let overlays = [
+            (brightness < 1.).then(|| femtovg::Color::rgbaf(0., 0., 0., 1. - brightness.max(0.))),
+            (brightness > 1.).then(|| femtovg::Color::rgbaf(1., 1., 1., (brightness - 1.).min(1.))),
+            (tint.alpha() > 0).then(|| to_femtovg_color(&tint)),
+        ];
+        let has_overlays = overlays.iter().any(Option::is_some);
+
+        let mut layer_rect = femtovg::Path::new();
+        layer_rect.rect(0., 0., layer_size.width as _, layer_size.height as _);
+
+        let blur = effect_item.effect_blur() * self.scale_factor;
+        // The cached layer must not be modified, so the effects are applied on a copy of it.
+        let effect_image = if blur.get() > 0. {
+            Some(layer_image.filter(femtovg::ImageFilter::GaussianBlur { sigma: blur.get() / 2. }))
+        } else if has_overlays {
+            Texture::new_empty_on_gpu(&self.canvas, layer_size.width, layer_size.height).map(
+                |copy| {
+                    let mut canvas = self.canvas.borrow_mut();
+                    canvas.save();
+                    canvas.set_render_target(copy.as_render_target());
+                    canvas.reset();
+                    canvas
+                        .fill_path(&mut layer_rect, layer_image.as_paint().with_anti_alias(false));
+                    canvas.restore();
+                    copy
+                },
+            )
+        } else {
+            None
+        };
+
+        let effect_image = match effect_image {
+            Some(effect_image) => {
+                let mut canvas = self.canvas.borrow_mut();
+                canvas.save();
+                canvas.set_render_target(effect_image.as_render_target());
+                canvas.reset();
+                canvas.global_composite_operation(femtovg::CompositeOperation::SourceAtop);
+                for color in overlays.into_iter().flatten() {
+                    canvas.fill_path(&mut layer_rect, femtovg::Paint::color(color));
+                }
+                canvas.restore();
+                canvas.set_render_target(self.current_render_target());
+                self.layer_images_to_delete_after_flush.borrow_mut().push(effect_image.clone());
+                effect_image
+            }
+            None => layer_image,
+        };
+
+        self.canvas
+            .borrow_mut()
+            .fill_path(&mut layer_rect, effect_image.as_paint().with_anti_alias(false));
+
+        RenderingResult::ContinueRenderingWithoutChildren
+    }
+
     fn visit_clip(&mut self, clip_item: Pin<&Clip>, item_rc: &ItemRc) -> RenderingResult {
         if !clip_item.clip() {
             return RenderingResult::ContinueRenderingChildren;
@@ -751,10 +1180,10 @@ fn visit_clip(&mut self, clip_item: Pin<&Clip>, item_rc: &ItemRc) -> RenderingRe
             return RenderingResult::ContinueRenderingWithoutChildren;
         }
 
-        let radius = clip_item.border_radius();
+        let radius = clip_item.border_radii();
         let border_width = clip_item.border_width();
 
-        if radius.get() > 0. {
+        if !radius.is_zero() {
             if let Some(layer_image) =
                 self.render_layer(item_rc, &|| clip_item.as_ref().geometry().size)
             {
@@ -786,7 +1215,7 @@ fn visit_clip(&mut self, clip_item: Pin<&Clip>, item_rc: &ItemRc) -> RenderingRe
     fn combine_clip(
         &mut self,
         clip_rect: LogicalRect,
-        radius: LogicalLength,
+        radius: LogicalBorderRadius,
         border_width: LogicalLength,
     ) -> bool {
         let clip = &mut self.state.last_mut().unwrap().scissor;
@@ -815,7 +1244,7 @@ fn combine_clip(
 
         // femtovg only supports rectangular clipping. Non-rectangular clips must be handled via `apply_clip`,
         // which can render children into a layer.
-        debug_assert!(radius.get() == 0.);
+        debug_assert!(radius.is_zero());
 
         clip_region_valid
     }
@@ -924,6 +1353,29 @@ fn rotate(&mut self, angle_in_degrees: f32) {
         *clip = LogicalRect::new(origin, (end - origin).into());
     }
 
+    fn scale(&mut self, x_factor: f32, y_factor: f32) {
+        self.canvas.borrow_mut().scale(x_factor, y_factor);
+        self.transform_clip(LogicalTransform::scale(x_factor, y_factor));
+    }
+
+    fn skew(&mut self, x_angle_in_degrees: f32, y_angle_in_degrees: f32) {
+        let (x_angle, y_angle) = (x_angle_in_degrees.to_radians(), y_angle_in_degrees.to_radians());
+        let mut canvas = self.canvas.borrow_mut();
+        canvas.skew_x(x_angle);
+        canvas.skew_y(y_angle);
+        drop(canvas);
+        self.transform_clip(
+            LogicalTransform::new(1., y_angle.tan(), 0., 1., 0., 0.).then(&LogicalTransform::new(
+                1.,
+                0.,
+                x_angle.tan(),
+                1.,
+                0.,
+                0.,
+            )),
+        );
+    }
+
     fn apply_opacity(&mut self, opacity: f32) {
         let state = &mut self.state.last_mut().unwrap().global_alpha;
         *state *= opacity;
@@ -1046,22 +1498,27 @@ fn render_layer(
         cache_entry.map(|item_cache_entry| item_cache_entry.as_texture().clone())
     }
 
-    fn render_and_blend_layer(&mut self, alpha_tint: f32, item_rc: &ItemRc) -> RenderingResult {
+    /// Renders the children of the item into a layer that covers the item and its children.
+    fn render_children_layer(&mut self, item_rc: &ItemRc) -> Option<Rc<Texture>> {
         let current_clip = self.get_current_clip();
+        self.render_layer(item_rc, &|| {
+            // We don't need to include the size of the opacity item itself, since it has no content.
+            let children_rect = i_slint_core::properties::evaluate_no_tracking(|| {
+                item_rc.geometry().union(
+                    &i_slint_core::item_rendering::item_children_bounding_rect(
+                        &item_rc.component(),
+                        item_rc.index() as isize,
+                        &current_clip,
+                    ),
+                )
+            });
+            children_rect.size
+        })
+    }
+
+    fn render_and_blend_layer(&mut self, alpha_tint: f32, item_rc: &ItemRc) -> RenderingResult {
         if let Some((layer_image, layer_size)) = self
-            .render_layer(item_rc, &|| {
-                // We don't need to include the size of the opacity item itself, since it has no content.
-                let children_rect = i_slint_core::properties::evaluate_no_tracking(|| {
-                    item_rc.geometry().union(
-                        &i_slint_core::item_rendering::item_children_bounding_rect(
-                            &item_rc.component(),
-                            item_rc.index() as isize,
-                            &current_clip,
-                        ),
-                    )
-                });
-                children_rect.size
-            })
+            .render_children_layer(item_rc)
             .and_then(|image| image.size().map(|size| (image, size)))
         {
             let mut layer_path = femtovg::Path::new();
@@ -1155,7 +1612,7 @@ fn colorize_image(
     fn draw_image_impl(
         &mut self,
         item_rc: &ItemRc,
-        source_property: Pin<&Property<Image>>,
+        source: impl Fn() -> Image,
         source_clip_rect: IntRect,
         target_width: Pin<&Property<LogicalLength>>,
         target_height: Pin<&Property<LogicalLength>>,
@@ -1172,7 +1629,7 @@ fn draw_image_impl(
 
         let cached_image = loop {
             let image_cache_entry = self.graphics_cache.get_or_update_cache_entry(item_rc, || {
-                let image = source_property.get();
+                let image = source();
                 let image_inner: &ImageInner = (&image).into();
 
                 let target_size_for_scalable_source = if image_inner.is_svg() {
@@ -1308,6 +1765,24 @@ fn draw_image_impl(
         })
     }
 
+    /// Returns the paint to stroke the outline of the glyphs of a text with, or None if the text has no outline.
+    fn text_stroke_paint(
+        &self,
+        brush: Brush,
+        stroke_width: LogicalLength,
+        font: &fonts::Font,
+        letter_spacing: PhysicalLength,
+        rect: PhysicalRect,
+    ) -> Option<femtovg::Paint> {
+        let stroke_width = stroke_width * self.scale_factor;
+        if stroke_width.get() <= 0. {
+            return None;
+        }
+        let mut paint = self.brush_to_paint(brush, &mut rect_to_path(rect))?;
+        paint.set_line_width(stroke_width.get());
+        Some(font.init_paint(letter_spacing, paint))
+    }
+
     fn brush_to_paint(&self, brush: Brush, path: &mut femtovg::Path) -> Option<femtovg::Paint> {
         if brush.is_transparent() {
             return None;
@@ -28,7 +28,7 @@
 mod itemrenderer;
 mod textlayout;
 
-#[cfg(target_os = "macos")]
+#[cfg(any(target_os = "macos", target_os = "ios"))]
 mod metal_surface;
 
 #[cfg(target_family = "windows")]
@@ -38,6 +38,9 @@
     if #[cfg(skia_backend_opengl)] {
         mod opengl_surface;
         type DefaultSurface = opengl_surface::OpenGLSurface;
+    } else if #[cfg(skia_backend_vulkan)] {
+        mod vulkan_surface;
+        type DefaultSurface = vulkan_surface::VulkanSurface;
     } else if #[cfg(skia_backend_metal)] {
         type DefaultSurface = metal_surface::MetalSurface;
     } else if #[cfg(skia_backend_d3d)] {
@@ -179,6 +182,9 @@ fn text_size(
             Default::default(),
             Default::default(),
             Default::default(),
+            Default::default(),
+            None,
+            None,
             None,
         );
 
@@ -222,6 +228,9 @@ fn text_input_byte_offset_for_position(
             text_input.horizontal_alignment(),
             text_input.vertical_alignment(),
             i_slint_core::items::TextOverflow::Clip,
+            i_slint_core::items::TextDirection::Ltr,
+            None,
+            None,
             None,
         );
 
@@ -274,6 +283,9 @@ fn text_input_cursor_rect_for_byte_offset(
             text_input.horizontal_alignment(),
             text_input.vertical_alignment(),
             i_slint_core::items::TextOverflow::Clip,
+            i_slint_core::items::TextDirection::Ltr,
+            None,
+            None,
             None,
         );
 
@@ -33,6 +33,7 @@ pub(crate) fn as_skia_image(
     target_height: std::pin::Pin<&i_slint_core::Property<LogicalLength>>,
     image_fit: ImageFit,
     scale_factor: ScaleFactor,
+    _recording_context: Option<&mut skia_safe::gpu::RecordingContext>,
 ) -> Option<skia_safe::Image> {
     let image_inner: &ImageInner = (&image).into();
     match image_inner {
@@ -74,9 +75,39 @@ pub(crate) fn as_skia_image(
             )
         }
         ImageInner::StaticTextures(_) => todo!(),
+        ImageInner::AnimatedImage(_) => {
+            image_inner.render_to_buffer(None).as_ref().and_then(image_buffer_to_skia_image)
+        }
         ImageInner::BackendStorage(x) => {
             vtable::VRc::borrow(x).downcast::<SkiaCachedImage>().map(|x| x.image.clone())
         }
+        #[cfg(skia_backend_opengl)]
+        ImageInner::BorrowedOpenGLTexture(texture) => {
+            let texture_info = skia_safe::gpu::gl::TextureInfo {
+                format: skia_safe::gpu::gl::Format::RGBA8.into(),
+                ..skia_safe::gpu::gl::TextureInfo::from_target_and_id(
+                    glow::TEXTURE_2D,
+                    texture.texture_id.get(),
+                )
+            };
+            let backend_texture = unsafe {
+                skia_safe::gpu::BackendTexture::new_gl(
+                    (texture.size.width as _, texture.size.height as _),
+                    skia_safe::gpu::MipMapped::No,
+                    texture_info,
+                )
+            };
+            skia_safe::Image::from_texture(
+                _recording_context?,
+                &backend_texture,
+                skia_safe::gpu::SurfaceOrigin::TopLeft,
+                skia_safe::ColorType::RGBA8888,
+                skia_safe::AlphaType::Premul,
+                None,
+            )
+        }
+        #[cfg(not(skia_backend_opengl))]
+        ImageInner::BorrowedOpenGLTexture(..) => None,
     }
 }
 
@@ -8,9 +8,13 @@
 use i_slint_core::graphics::euclid::num::Zero;
 use i_slint_core::item_rendering::{ItemCache, ItemRenderer};
 use i_slint_core::items::Item;
-use i_slint_core::items::{ImageFit, ImageRendering, ItemRc, Layer, Opacity, RenderingResult};
+use i_slint_core::items::{
+    DropShadow, Effect, FillRule, ImageFit, ImageRendering, ItemRc, Layer, LineCap, LineJoin,
+    Opacity, RenderingResult,
+};
 use i_slint_core::lengths::{
-    LogicalLength, LogicalPoint, LogicalRect, LogicalSize, LogicalVector, RectLengths, ScaleFactor,
+    BorderRadius, LogicalBorderRadius, LogicalLength, LogicalPoint, LogicalRect, LogicalSize,
+    LogicalVector, RectLengths, ScaleFactor,
 };
 use i_slint_core::window::WindowInner;
 use i_slint_core::{items, Brush, Color, Property};
@@ -52,6 +56,24 @@ pub fn new(
         }
     }
 
+    /// Returns the paint to stroke the outline of the glyphs of a text with, or None if the text has no outline.
+    fn text_stroke_paint(
+        &self,
+        brush: Brush,
+        stroke_width: LogicalLength,
+        width: PhysicalLength,
+        height: PhysicalLength,
+    ) -> Option<skia_safe::Paint> {
+        let stroke_width = stroke_width * self.scale_factor;
+        if stroke_width.get() <= 0. {
+            return None;
+        }
+        let mut paint = self.brush_to_paint(brush, width, height)?;
+        paint.set_style(skia_safe::PaintStyle::Stroke);
+        paint.set_stroke_width(stroke_width.get());
+        Some(paint)
+    }
+
     fn brush_to_paint(
         &self,
         brush: Brush,
@@ -137,7 +159,7 @@ fn colorize_image(
     fn draw_image_impl(
         &mut self,
         item_rc: &ItemRc,
-        source_property: Pin<&Property<i_slint_core::graphics::Image>>,
+        source: impl Fn() -> i_slint_core::graphics::Image,
         mut dest_rect: PhysicalRect,
         source_rect: Option<skia_safe::Rect>,
         target_width: std::pin::Pin<&Property<LogicalLength>>,
@@ -146,15 +168,17 @@ fn draw_image_impl(
         rendering: ImageRendering,
         colorize_property: Option<Pin<&Property<Brush>>>,
     ) {
+        let mut recording_context = self.canvas.recording_context();
         // TODO: avoid doing creating an SkImage multiple times when the same source is used in multiple image elements
         let skia_image = self.image_cache.get_or_update_cache_entry(item_rc, || {
-            let image = source_property.get();
+            let image = source();
             super::cached_image::as_skia_image(
                 image,
                 target_width,
                 target_height,
                 image_fit,
                 self.scale_factor,
+                recording_context.as_mut(),
             )
             .and_then(|skia_image| {
                 match colorize_property.map(|p| p.get()).filter(|c| !c.is_transparent()) {
@@ -296,9 +320,8 @@ fn draw_border_rectangle(
         // is adjusted accordingly.
         adjust_rect_and_border_for_inner_drawing(&mut geometry, &mut border_width);
 
-        let radius = rect.border_radius() * self.scale_factor;
-        let rounded_rect =
-            skia_safe::RRect::new_rect_xy(to_skia_rect(&geometry), radius.get(), radius.get());
+        let radius = rect.border_radii().map(|radius| radius * self.scale_factor);
+        let rounded_rect = to_skia_rrect(&geometry, &radius);
 
         if let Some(mut fill_paint) = self.brush_to_paint(
             rect.background(),
@@ -334,7 +357,7 @@ fn draw_image(
 
         self.draw_image_impl(
             self_rc,
-            i_slint_core::items::ImageItem::FIELD_OFFSETS.source.apply_pin(image),
+            || image.displayed_source(),
             geometry,
             None,
             items::ImageItem::FIELD_OFFSETS.width.apply_pin(image),
@@ -364,7 +387,7 @@ fn draw_clipped_image(
 
         self.draw_image_impl(
             self_rc,
-            i_slint_core::items::ClippedImage::FIELD_OFFSETS.source.apply_pin(image),
+            || image.displayed_source(),
             geometry,
             Some(source_rect),
             items::ClippedImage::FIELD_OFFSETS.width.apply_pin(image),
@@ -387,9 +410,24 @@ fn draw_text(
             return;
         }
 
-        let string = text.text();
-        let string = string.as_str();
+        let rich_text = text.rich_text_layout();
         let font_request = text.font_request(WindowInner::from_pub(&self.window));
+        let mut string = text.plain_text();
+        if text.overflow() == items::TextOverflow::Elide
+            && text.elide_position() != items::TextElidePosition::End
+            && text.wrap() == items::TextWrap::NoWrap
+            && rich_text.is_none()
+        {
+            string = super::textlayout::elide_lines(
+                &font_request,
+                self.scale_factor,
+                string.as_str(),
+                max_width,
+                text.elide_position(),
+            )
+            .into();
+        }
+        let string = string.as_str();
 
         let paint = match self.brush_to_paint(text.color(), max_width, max_height) {
             Some(paint) => paint,
@@ -398,21 +436,46 @@ fn draw_text(
 
         let mut text_style = skia_safe::textlayout::TextStyle::new();
         text_style.set_foreground_color(paint);
+        set_text_decorations(&mut text_style, text.underline(), text.strikethrough());
 
-        let (layout, layout_top_left) = super::textlayout::create_layout(
-            font_request,
-            self.scale_factor,
-            string,
-            Some(text_style),
-            Some(max_width),
-            max_height,
-            text.horizontal_alignment(),
-            text.vertical_alignment(),
-            text.overflow(),
-            None,
-        );
+        let create_layout = |text_style, rich_text: Option<&i_slint_core::textlayout::RichText>| {
+            super::textlayout::create_layout(
+                font_request.clone(),
+                self.scale_factor,
+                string,
+                Some(text_style),
+                Some(max_width),
+                max_height,
+                text.horizontal_alignment(),
+                text.vertical_alignment(),
+                text.overflow(),
+                text.text_direction(),
+                (text.max_lines() > 0).then(|| text.max_lines() as usize),
+                None,
+                rich_text,
+            )
+        };
+
+        let (layout, layout_top_left) = create_layout(text_style.clone(), rich_text.as_ref());
+        let stroke_layout = self
+            .text_stroke_paint(text.stroke(), text.stroke_width(), max_width, max_height)
+            .map(|stroke_paint| {
+                text_style.set_foreground_color(stroke_paint);
+                set_text_decorations(&mut text_style, false, false);
+                // The stroke of the spans has the stroke color, not the color of the span
+                let stroke_rich_text = rich_text.clone().map(|mut rich_text| {
+                    for span in &mut rich_text.spans {
+                        span.style.color = None;
+                    }
+                    rich_text
+                });
+                create_layout(text_style, stroke_rich_text.as_ref())
+            });
 
         layout.paint(&mut self.canvas, to_skia_point(layout_top_left));
+        if let Some((stroke_layout, stroke_layout_top_left)) = stroke_layout {
+            stroke_layout.paint(&mut self.canvas, to_skia_point(stroke_layout_top_left));
+        }
     }
 
     fn draw_text_input(
@@ -437,6 +500,7 @@ fn draw_text_input(
 
         let mut text_style = skia_safe::textlayout::TextStyle::new();
         text_style.set_foreground_color(paint);
+        set_text_decorations(&mut text_style, text_input.underline(), text_input.strikethrough());
 
         let visual_representation = text_input.visual_representation();
 
@@ -458,20 +522,43 @@ fn draw_text_input(
             None
         };
 
-        let (layout, layout_top_left) = super::textlayout::create_layout(
-            font_request,
-            self.scale_factor,
-            &visual_representation.text,
-            Some(text_style),
-            Some(max_width),
-            max_height,
-            text_input.horizontal_alignment(),
-            text_input.vertical_alignment(),
-            i_slint_core::items::TextOverflow::Clip,
-            selection.as_ref(),
-        );
+        let create_layout = |text_style, selection| {
+            super::textlayout::create_layout(
+                font_request.clone(),
+                self.scale_factor,
+                &visual_representation.text,
+                Some(text_style),
+                Some(max_width),
+                max_height,
+                text_input.horizontal_alignment(),
+                text_input.vertical_alignment(),
+                i_slint_core::items::TextOverflow::Clip,
+                i_slint_core::items::TextDirection::Ltr,
+                None,
+                selection,
+                None,
+            )
+        };
+
+        let (layout, layout_top_left) = create_layout(text_style.clone(), selection.as_ref());
+        // The outline is drawn without the selection, so that its background doesn't cover the glyphs.
+        let stroke_layout = self
+            .text_stroke_paint(
+                text_input.stroke(),
+                text_input.stroke_width(),
+                max_width,
+                max_height,
+            )
+            .map(|stroke_paint| {
+                text_style.set_foreground_color(stroke_paint);
+                set_text_decorations(&mut text_style, false, false);
+                create_layout(text_style, None)
+            });
 
         layout.paint(&mut self.canvas, to_skia_point(layout_top_left));
+        if let Some((stroke_layout, stroke_layout_top_left)) = stroke_layout {
+            stroke_layout.paint(&mut self.canvas, to_skia_point(stroke_layout_top_left));
+        }
 
         if let Some(cursor_position) = visual_representation.cursor_position {
             let cursor_rect = super::textlayout::cursor_rect(
@@ -505,6 +592,10 @@ fn draw_path(
         let (offset, path_events) = path.fitted_path_events();
 
         let mut skpath = skia_safe::Path::new();
+        skpath.set_fill_type(match path.fill_rule() {
+            FillRule::Nonzero => skia_safe::PathFillType::Winding,
+            FillRule::Evenodd => skia_safe::PathFillType::EvenOdd,
+        });
 
         for x in path_events.iter() {
             match x {
@@ -551,6 +642,16 @@ fn draw_path(
         {
             border_paint.set_anti_alias(true);
             border_paint.set_stroke_width((path.stroke_width() * self.scale_factor).get());
+            border_paint.set_stroke_cap(match path.stroke_line_cap() {
+                LineCap::Butt => skia_safe::paint::Cap::Butt,
+                LineCap::Round => skia_safe::paint::Cap::Round,
+                LineCap::Square => skia_safe::paint::Cap::Square,
+            });
+            border_paint.set_stroke_join(match path.stroke_line_join() {
+                LineJoin::Miter => skia_safe::paint::Join::Miter,
+                LineJoin::Round => skia_safe::paint::Join::Round,
+                LineJoin::Bevel => skia_safe::paint::Join::Bevel,
+            });
             border_paint.set_stroke(true);
             self.canvas.draw_path(&skpath, &border_paint);
         }
@@ -631,7 +732,7 @@ fn draw_box_shadow(
     fn combine_clip(
         &mut self,
         rect: LogicalRect,
-        radius: LogicalLength,
+        radius: LogicalBorderRadius,
         border_width: LogicalLength,
     ) -> bool {
         let mut rect = rect * self.scale_factor;
@@ -642,9 +743,8 @@ fn combine_clip(
         // is adjusted accordingly.
         adjust_rect_and_border_for_inner_drawing(&mut rect, &mut border_width);
 
-        let radius = radius * self.scale_factor;
-        let rounded_rect =
-            skia_safe::RRect::new_rect_xy(to_skia_rect(&rect), radius.get(), radius.get());
+        let radius = radius.map(|radius| radius * self.scale_factor);
+        let rounded_rect = to_skia_rrect(&rect, &radius);
         self.canvas.clip_rrect(rounded_rect, None, true);
         self.canvas.local_clip_bounds().is_some()
     }
@@ -662,6 +762,15 @@ fn rotate(&mut self, angle_in_degrees: f32) {
         self.canvas.rotate(angle_in_degrees, None);
     }
 
+    fn scale(&mut self, x_factor: f32, y_factor: f32) {
+        self.canvas.scale((x_factor, y_factor));
+    }
+
+    fn skew(&mut self, x_angle_in_degrees: f32, y_angle_in_degrees: f32) {
+        self.canvas.skew((x_angle_in_degrees.to_radians().tan(), 0.));
+        self.canvas.skew((0., y_angle_in_degrees.to_radians().tan()));
+    }
+
     fn apply_opacity(&mut self, opacity: f32) {
         self.current_state.alpha *= opacity;
     }
@@ -758,6 +867,77 @@ fn visit_layer(&mut self, layer_item: Pin<&Layer>, self_rc: &ItemRc) -> Renderin
             RenderingResult::ContinueRenderingChildren
         }
     }
+
+    fn visit_drop_shadow(
+        &mut self,
+        drop_shadow_item: Pin<&DropShadow>,
+        item_rc: &ItemRc,
+    ) -> RenderingResult {
+        if drop_shadow_item.is_invisible() {
+            return RenderingResult::ContinueRenderingChildren;
+        }
+
+        let offset = LogicalVector::from_lengths(
+            drop_shadow_item.drop_shadow_offset_x(),
+            drop_shadow_item.drop_shadow_offset_y(),
+        ) * self.scale_factor;
+        let blur = (drop_shadow_item.drop_shadow_blur() * self.scale_factor).get().max(0.);
+        let mut shadow_paint = skia_safe::Paint::default();
+        shadow_paint.set_image_filter(skia_safe::image_filters::drop_shadow(
+            (offset.x, offset.y),
+            (blur / 2., blur / 2.),
+            to_skia_color(&drop_shadow_item.drop_shadow_color()),
+            None,
+            None,
+        ));
+
+        self.canvas.save_layer(&skia_safe::canvas::SaveLayerRec::default().paint(&shadow_paint));
+        self.state_stack.push(self.current_state);
+
+        i_slint_core::item_rendering::render_item_children(
+            self,
+            &item_rc.component(),
+            item_rc.index() as isize,
+        );
+
+        self.current_state = self.state_stack.pop().unwrap();
+        self.canvas.restore();
+        RenderingResult::ContinueRenderingWithoutChildren
+    }
+
+    fn visit_effect(&mut self, effect_item: Pin<&Effect>, item_rc: &ItemRc) -> RenderingResult {
+        if effect_item.is_identity() {
+            return RenderingResult::ContinueRenderingChildren;
+        }
+
+        let mut effect_paint = skia_safe::Paint::default();
+        let blur = effect_item.effect_blur() * self.scale_factor;
+        if blur.get() > 0. {
+            effect_paint.set_image_filter(skia_safe::image_filters::blur(
+                (blur.get() / 2., blur.get() / 2.),
+                None,
+                None,
+                None,
+            ));
+        }
+        if let Some(color_matrix) = effect_item.color_matrix() {
+            effect_paint
+                .set_color_filter(skia_safe::color_filters::matrix_row_major(&color_matrix));
+        }
+
+        self.canvas.save_layer(&skia_safe::canvas::SaveLayerRec::default().paint(&effect_paint));
+        self.state_stack.push(self.current_state);
+
+        i_slint_core::item_rendering::render_item_children(
+            self,
+            &item_rc.component(),
+            item_rc.index() as isize,
+        );
+
+        self.current_state = self.state_stack.pop().unwrap();
+        self.canvas.restore();
+        RenderingResult::ContinueRenderingWithoutChildren
+    }
 }
 
 pub fn from_skia_rect(rect: &skia_safe::Rect) -> PhysicalRect {
@@ -770,6 +950,26 @@ pub fn to_skia_rect(rect: &PhysicalRect) -> skia_safe::Rect {
     skia_safe::Rect::from_xywh(rect.origin.x, rect.origin.y, rect.size.width, rect.size.height)
 }
 
+pub fn to_skia_rrect(
+    rect: &PhysicalRect,
+    radius: &BorderRadius<PhysicalLength>,
+) -> skia_safe::RRect {
+    if let Some(radius) = radius.as_uniform() {
+        skia_safe::RRect::new_rect_xy(to_skia_rect(rect), radius.get(), radius.get())
+    } else {
+        let to_vector = |radius: PhysicalLength| skia_safe::Vector::new(radius.get(), radius.get());
+        skia_safe::RRect::new_rect_radii(
+            to_skia_rect(rect),
+            &[
+                to_vector(radius.top_left),
+                to_vector(radius.top_right),
+                to_vector(radius.bottom_right),
+                to_vector(radius.bottom_left),
+            ],
+        )
+    }
+}
+
 pub fn to_skia_point(point: PhysicalPoint) -> skia_safe::Point {
     skia_safe::Point::new(point.x, point.y)
 }
@@ -834,3 +1034,18 @@ fn adjust_to_image_fit(
         }
     };
 }
+
+fn set_text_decorations(
+    text_style: &mut skia_safe::textlayout::TextStyle,
+    underline: bool,
+    strikethrough: bool,
+) {
+    let mut decoration = skia_safe::textlayout::TextDecoration::NO_DECORATION;
+    if underline {
+        decoration |= skia_safe::textlayout::TextDecoration::UNDERLINE;
+    }
+    if strikethrough {
+        decoration |= skia_safe::textlayout::TextDecoration::LINE_THROUGH;
+    }
+    text_style.decoration_mut().ty = decoration;
+}
@@ -1,15 +1,21 @@
 // Copyright © SixtyFPS GmbH <info@slint-ui.com>
 // SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
 
+#[cfg(target_os = "macos")]
 use cocoa::{appkit::NSView, base::id as cocoa_id};
 use core_graphics_types::geometry::CGSize;
 use foreign_types::ForeignTypeRef;
 use metal::MTLPixelFormat;
-use objc::{rc::autoreleasepool, runtime::YES};
+use objc::rc::autoreleasepool;
+#[cfg(target_os = "macos")]
+use objc::runtime::YES;
 
 use skia_safe::gpu::mtl;
 
 use std::cell::RefCell;
+#[cfg(target_os = "ios")]
+use winit::platform::ios::WindowExtIOS;
+#[cfg(target_os = "macos")]
 use winit::platform::macos::WindowExtMacOS;
 
 pub struct MetalSurface {
@@ -37,12 +43,27 @@ fn new(window_builder: winit::window::WindowBuilder) -> Self {
         let size = window.inner_size();
         layer.set_drawable_size(CGSize::new(size.width as f64, size.height as f64));
 
+        #[cfg(target_os = "macos")]
         unsafe {
             let view = window.ns_view() as cocoa_id;
             view.setWantsLayer(YES);
             view.setLayer(layer.as_ref() as *const _ as _);
         }
 
+        // The layer of a UIView can't be replaced, so add the metal layer on top of it
+        #[cfg(target_os = "ios")]
+        unsafe {
+            use objc::runtime::Object;
+            use objc::{msg_send, sel, sel_impl};
+            let view = window.ui_view() as *mut Object;
+            let view_layer: *mut Object = msg_send![view, layer];
+            let metal_layer = layer.as_ref() as *const _ as *mut Object;
+            let bounds: core_graphics_types::geometry::CGRect = msg_send![view_layer, bounds];
+            let _: () = msg_send![metal_layer, setFrame: bounds];
+            let _: () = msg_send![metal_layer, setContentsScale: window.scale_factor()];
+            let _: () = msg_send![view_layer, addSublayer: metal_layer];
+        }
+
         let command_queue = device.new_command_queue();
 
         let backend = unsafe {
@@ -73,6 +94,17 @@ fn with_graphics_api(&self, _cb: impl FnOnce(i_slint_core::api::GraphicsAPI<'_>)
     fn resize_event(&self) {
         let size = self.window.inner_size();
         self.layer.set_drawable_size(CGSize::new(size.width as f64, size.height as f64));
+        // The sublayer doesn't follow the size of the view, for example when the device is rotated
+        #[cfg(target_os = "ios")]
+        unsafe {
+            use objc::runtime::Object;
+            use objc::{msg_send, sel, sel_impl};
+            let view = self.window.ui_view() as *mut Object;
+            let view_layer: *mut Object = msg_send![view, layer];
+            let bounds: core_graphics_types::geometry::CGRect = msg_send![view_layer, bounds];
+            let metal_layer = self.layer.as_ref() as *const _ as *mut Object;
+            let _: () = msg_send![metal_layer, setFrame: bounds];
+        }
     }
 
     fn render(
@@ -39,6 +39,9 @@ struct FontCache {
         // to pick up the custom font.
         font_collection.set_asset_font_manager(Some(type_face_font_provider.clone().into()));
         font_collection.set_dynamic_font_manager(font_mgr.clone());
+        // Characters that the requested families don't support, such as emoji, are looked up in the
+        // fonts of the system. Skia renders color fonts (COLR, CBDT and sbix) itself.
+        font_collection.enable_font_fallback();
         FontCache { font_collection, font_mgr, type_face_font_provider: RefCell::new(type_face_font_provider), custom_fonts: Default::default() }
     }
 }
@@ -60,19 +63,32 @@ pub fn create_layout(
     h_align: items::TextHorizontalAlignment,
     v_align: TextVerticalAlignment,
     overflow: items::TextOverflow,
+    text_direction: items::TextDirection,
+    max_lines: Option<usize>,
     selection: Option<&Selection>,
+    rich_text: Option<&i_slint_core::textlayout::RichText>,
 ) -> (skia_safe::textlayout::Paragraph, PhysicalPoint) {
     let mut text_style = text_style.unwrap_or_default();
 
-    if let Some(family_name) = font_request.family {
-        text_style.set_font_families(&[family_name.as_str()]);
+    // Skia picks the first family of the list that supports a character, before falling back to the
+    // fonts of the system.
+    let mut families = font_request.families().map(String::from).collect::<Vec<_>>();
+    if families.is_empty() {
+        families = text_style.font_families().iter().map(String::from).collect();
     }
+    families.extend(
+        i_slint_core::graphics::fallback_font_families().iter().map(|family| family.to_string()),
+    );
+    text_style.set_font_families(&families);
 
     let pixel_size = font_request.pixel_size.unwrap_or(DEFAULT_FONT_SIZE) * scale_factor;
 
     if let Some(letter_spacing) = font_request.letter_spacing {
         text_style.set_letter_spacing((letter_spacing * scale_factor).get());
     }
+    if let Some(word_spacing) = font_request.word_spacing {
+        text_style.set_word_spacing((word_spacing * scale_factor).get());
+    }
     text_style.set_font_size(pixel_size.get());
     text_style.set_font_style(skia_safe::FontStyle::new(
         font_request.weight.map_or(skia_safe::font_style::Weight::NORMAL, |w| w.into()),
@@ -82,16 +98,27 @@ pub fn create_layout(
 
     let mut style = skia_safe::textlayout::ParagraphStyle::new();
 
+    // Skia's paragraph only supports eliding at the end, see elide_lines() for the other positions
     if overflow == items::TextOverflow::Elide {
         style.set_ellipsis("…");
     }
 
+    if let Some(max_lines) = max_lines {
+        style.set_max_lines(max_lines);
+    }
+
     style.set_text_align(match h_align {
         items::TextHorizontalAlignment::Left => skia_safe::textlayout::TextAlign::Left,
         items::TextHorizontalAlignment::Center => skia_safe::textlayout::TextAlign::Center,
         items::TextHorizontalAlignment::Right => skia_safe::textlayout::TextAlign::Right,
     });
 
+    style.set_text_direction(if i_slint_core::textlayout::is_rtl(text, text_direction) {
+        skia_safe::textlayout::TextDirection::RTL
+    } else {
+        skia_safe::textlayout::TextDirection::LTR
+    });
+
     style.set_text_style(&text_style);
 
     let mut builder = FONT_CACHE.with(|font_cache| {
@@ -117,7 +144,7 @@ pub fn create_layout(
         }
 
         if selection.underline {
-            selection_style.decoration_mut().ty = skia_safe::textlayout::TextDecoration::UNDERLINE;
+            selection_style.decoration_mut().ty |= skia_safe::textlayout::TextDecoration::UNDERLINE;
             selection_style.decoration_mut().color =
                 text_style.foreground().map_or(skia_safe::Color::BLACK, |paint| paint.color());
         }
@@ -129,6 +156,44 @@ pub fn create_layout(
 
         let after_selection = &text[selection.range.end..];
         builder.add_text(after_selection);
+    } else if let Some(rich_text) = rich_text {
+        for (range, span_style) in rich_text.segments(0..text.len()) {
+            let span_style = match span_style {
+                Some(span_style) => span_style,
+                None => {
+                    builder.add_text(&text[range]);
+                    continue;
+                }
+            };
+            let mut style = text_style.clone();
+            let font_style = text_style.font_style();
+            style.set_font_style(skia_safe::FontStyle::new(
+                if span_style.bold {
+                    skia_safe::font_style::Weight::BOLD
+                } else {
+                    font_style.weight()
+                },
+                font_style.width(),
+                if span_style.italic {
+                    skia_safe::font_style::Slant::Italic
+                } else {
+                    font_style.slant()
+                },
+            ));
+            if let Some(color) = span_style.color {
+                let mut paint = skia_safe::Paint::default();
+                paint.set_color(to_skia_color(&color));
+                style.set_foreground_color(paint);
+            }
+            if span_style.underline || span_style.link.is_some() {
+                style.decoration_mut().ty |= skia_safe::textlayout::TextDecoration::UNDERLINE;
+                style.decoration_mut().color =
+                    style.foreground().map_or(skia_safe::Color::BLACK, |paint| paint.color());
+            }
+            builder.push_style(&style);
+            builder.add_text(&text[range]);
+            builder.pop();
+        }
     } else {
         builder.add_text(text);
     }
@@ -147,6 +212,70 @@ pub fn create_layout(
     (paragraph, PhysicalPoint::from_lengths(Default::default(), layout_top_y))
 }
 
+/// Elides each line of the text that is wider than `max_width`, at the start or in the middle of
+/// the line. Skia's paragraph only supports eliding at the end, so the text is elided before
+/// the layout is created.
+pub fn elide_lines(
+    font_request: &FontRequest,
+    scale_factor: ScaleFactor,
+    text: &str,
+    max_width: PhysicalLength,
+    elide_position: items::TextElidePosition,
+) -> String {
+    use unicode_segmentation::UnicodeSegmentation;
+
+    let fits = |line: &str| {
+        let (layout, _) = create_layout(
+            font_request.clone(),
+            scale_factor,
+            line,
+            None,
+            None,
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            None,
+            None,
+            None,
+        );
+        layout.max_intrinsic_width() <= max_width.get()
+    };
+
+    let elide_line = |line: &str| -> String {
+        if fits(line) {
+            return line.into();
+        }
+        let graphemes = line.graphemes(true).collect::<Vec<_>>();
+        // The line with only `kept` graphemes of the original line, and the ellipsis
+        let elided = |kept: usize| -> String {
+            let (prefix, suffix) = match elide_position {
+                items::TextElidePosition::Start => (0, kept),
+                items::TextElidePosition::Middle => (kept - kept / 2, kept / 2),
+                items::TextElidePosition::End => (kept, 0),
+            };
+            let mut result = graphemes[..prefix].concat();
+            result.push('…');
+            result.push_str(&graphemes[graphemes.len() - suffix..].concat());
+            result
+        };
+        // Binary search for the largest number of graphemes that fits
+        let (mut low, mut high) = (0, graphemes.len());
+        while low < high {
+            let mid = (low + high + 1) / 2;
+            if fits(&elided(mid)) {
+                low = mid;
+            } else {
+                high = mid - 1;
+            }
+        }
+        elided(low)
+    };
+
+    text.split('\n').map(elide_line).collect::<Vec<_>>().join("\n")
+}
+
 fn register_font(source: CustomFontSource) -> Result<(), Box<dyn std::error::Error>> {
     FONT_CACHE.with(|font_cache| {
         if font_cache
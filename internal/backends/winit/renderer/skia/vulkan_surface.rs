@@ -0,0 +1,488 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+use std::cell::{Cell, RefCell};
+
+use ash::extensions::khr;
+use ash::vk::{self, Handle};
+use raw_window_handle::{HasRawDisplayHandle, HasRawWindowHandle};
+use skia_safe::gpu::vk as skia_vk;
+
+struct Swapchain {
+    swapchain: vk::SwapchainKHR,
+    images: Vec<vk::Image>,
+    /// Signaled when the rendering into the image with the same index is done, and waited for by
+    /// the presentation. Acquiring the image again means that the presentation is done, so that the
+    /// semaphore can be reused.
+    render_finished: Vec<vk::Semaphore>,
+    extent: vk::Extent2D,
+}
+
+pub struct VulkanSurface {
+    gr_context: RefCell<skia_safe::gpu::DirectContext>,
+    swapchain: RefCell<Swapchain>,
+    needs_swapchain_update: Cell<bool>,
+    surface_format: vk::SurfaceFormatKHR,
+    present_mode: vk::PresentModeKHR,
+    acquire_fence: vk::Fence,
+    queue: vk::Queue,
+    queue_family_index: u32,
+    swapchain_loader: khr::Swapchain,
+    device: ash::Device,
+    physical_device: vk::PhysicalDevice,
+    surface: vk::SurfaceKHR,
+    surface_loader: khr::Surface,
+    instance: ash::Instance,
+    _entry: ash::Entry,
+    window: winit::window::Window,
+}
+
+impl super::Surface for VulkanSurface {
+    const SUPPORTS_GRAPHICS_API: bool = false;
+
+    fn new(window_builder: winit::window::WindowBuilder) -> Self {
+        let window = crate::event_loop::with_window_target(|event_loop| {
+            window_builder.build(event_loop.event_loop_target()).unwrap()
+        });
+
+        let entry = unsafe { ash::Entry::load() }.expect("Unable to load the Vulkan library");
+
+        let instance = {
+            let app_info = vk::ApplicationInfo::builder()
+                .application_name(std::ffi::CStr::from_bytes_with_nul(b"Slint\0").unwrap())
+                .api_version(vk::make_api_version(0, 1, 1, 0));
+            let extensions = ash_window::enumerate_required_extensions(window.raw_display_handle())
+                .expect("Unable to query the Vulkan surface extensions");
+            let create_info = vk::InstanceCreateInfo::builder()
+                .application_info(&app_info)
+                .enabled_extension_names(extensions);
+            unsafe { entry.create_instance(&create_info, None) }
+                .expect("Unable to create the Vulkan instance")
+        };
+
+        let surface_loader = khr::Surface::new(&entry, &instance);
+        let surface = unsafe {
+            ash_window::create_surface(
+                &entry,
+                &instance,
+                window.raw_display_handle(),
+                window.raw_window_handle(),
+                None,
+            )
+        }
+        .expect("Unable to create a Vulkan surface for the window");
+
+        let (physical_device, queue_family_index) =
+            unsafe { instance.enumerate_physical_devices() }
+                .expect("Unable to enumerate the Vulkan devices")
+                .into_iter()
+                .find_map(|physical_device| {
+                    unsafe { instance.get_physical_device_queue_family_properties(physical_device) }
+                        .iter()
+                        .enumerate()
+                        .position(|(index, properties)| {
+                            properties.queue_flags.contains(vk::QueueFlags::GRAPHICS)
+                                && unsafe {
+                                    surface_loader.get_physical_device_surface_support(
+                                        physical_device,
+                                        index as u32,
+                                        surface,
+                                    )
+                                }
+                                .unwrap_or(false)
+                        })
+                        .map(|index| (physical_device, index as u32))
+                })
+                .expect("No Vulkan device found that can render to the window");
+
+        let device = {
+            let queue_info = [vk::DeviceQueueCreateInfo::builder()
+                .queue_family_index(queue_family_index)
+                .queue_priorities(&[1.0])
+                .build()];
+            let extensions = [khr::Swapchain::name().as_ptr()];
+            let create_info = vk::DeviceCreateInfo::builder()
+                .queue_create_infos(&queue_info)
+                .enabled_extension_names(&extensions);
+            unsafe { instance.create_device(physical_device, &create_info, None) }
+                .expect("Unable to create the Vulkan device")
+        };
+        let queue = unsafe { device.get_device_queue(queue_family_index, 0) };
+
+        let surface_formats =
+            unsafe { surface_loader.get_physical_device_surface_formats(physical_device, surface) }
+                .expect("Unable to query the Vulkan surface formats");
+        let surface_format = surface_formats
+            .iter()
+            .copied()
+            .find(|format| {
+                matches!(format.format, vk::Format::B8G8R8A8_UNORM | vk::Format::R8G8B8A8_UNORM)
+            })
+            .expect("The Vulkan surface does not support any 8-bit RGBA format");
+
+        let present_mode = unsafe {
+            surface_loader.get_physical_device_surface_present_modes(physical_device, surface)
+        }
+        .unwrap_or_default()
+        .into_iter()
+        .find(|mode| *mode == vk::PresentModeKHR::MAILBOX)
+        .unwrap_or(vk::PresentModeKHR::FIFO);
+
+        let swapchain_loader = khr::Swapchain::new(&instance, &device);
+
+        let acquire_fence = unsafe { device.create_fence(&Default::default(), None) }
+            .expect("Unable to create a Vulkan fence");
+
+        let gr_context = {
+            let get_proc = |of| unsafe {
+                match of {
+                    skia_vk::GetProcOf::Instance(instance, name) => {
+                        entry.get_instance_proc_addr(vk::Instance::from_raw(instance as _), name)
+                    }
+                    skia_vk::GetProcOf::Device(device, name) => {
+                        (instance.fp_v1_0().get_device_proc_addr)(
+                            vk::Device::from_raw(device as _),
+                            name,
+                        )
+                    }
+                }
+                .map(|f| f as _)
+                .unwrap_or(std::ptr::null())
+            };
+
+            let backend = unsafe {
+                skia_vk::BackendContext::new(
+                    instance.handle().as_raw() as _,
+                    physical_device.as_raw() as _,
+                    device.handle().as_raw() as _,
+                    (queue.as_raw() as _, queue_family_index as usize),
+                    &get_proc,
+                )
+            };
+
+            skia_safe::gpu::DirectContext::new_vulkan(&backend, None)
+                .expect("Unable to create the Skia Vulkan context")
+        };
+
+        let this = Self {
+            gr_context: RefCell::new(gr_context),
+            swapchain: RefCell::new(Swapchain {
+                swapchain: vk::SwapchainKHR::null(),
+                images: Vec::new(),
+                render_finished: Vec::new(),
+                extent: vk::Extent2D::default(),
+            }),
+            needs_swapchain_update: Cell::new(false),
+            surface_format,
+            present_mode,
+            acquire_fence,
+            queue,
+            queue_family_index,
+            swapchain_loader,
+            device,
+            physical_device,
+            surface,
+            surface_loader,
+            instance,
+            _entry: entry,
+            window,
+        };
+        if let Err(err) = this.update_swapchain(&mut this.swapchain.borrow_mut()) {
+            i_slint_core::debug_log!("Unable to create the Vulkan swapchain: {}", err);
+            this.needs_swapchain_update.set(true);
+        }
+        this
+    }
+
+    fn name(&self) -> &'static str {
+        "vulkan"
+    }
+
+    fn with_graphics_api(&self, _cb: impl FnOnce(i_slint_core::api::GraphicsAPI<'_>)) {
+        // Not called: set_rendering_notifier() returns SetRenderingNotifierError::Unsupported
+        // because SUPPORTS_GRAPHICS_API is false.
+    }
+
+    fn with_window_handle<T>(&self, callback: impl FnOnce(&winit::window::Window) -> T) -> T {
+        callback(&self.window)
+    }
+
+    fn resize_event(&self) {
+        self.needs_swapchain_update.set(true);
+    }
+
+    fn render(
+        &self,
+        callback: impl FnOnce(&mut skia_safe::Canvas, &mut skia_safe::gpu::DirectContext),
+    ) {
+        let mut swapchain = self.swapchain.borrow_mut();
+
+        let size = self.window.inner_size();
+        if self.needs_swapchain_update.take()
+            || size.width != swapchain.extent.width
+            || size.height != swapchain.extent.height
+        {
+            if let Err(err) = self.update_swapchain(&mut swapchain) {
+                // Try again on the next frame, for example when the surface is usable again.
+                i_slint_core::debug_log!("Unable to create the Vulkan swapchain: {}", err);
+                self.needs_swapchain_update.set(true);
+                return;
+            }
+        }
+
+        if swapchain.extent.width == 0
+            || swapchain.extent.height == 0
+            || swapchain.images.is_empty()
+        {
+            return;
+        }
+
+        let image_index = match unsafe {
+            self.swapchain_loader.acquire_next_image(
+                swapchain.swapchain,
+                u64::MAX,
+                vk::Semaphore::null(),
+                self.acquire_fence,
+            )
+        } {
+            Ok((image_index, suboptimal)) => {
+                if suboptimal {
+                    self.needs_swapchain_update.set(true);
+                }
+                image_index
+            }
+            Err(vk::Result::ERROR_OUT_OF_DATE_KHR) => {
+                self.needs_swapchain_update.set(true);
+                self.window.request_redraw();
+                return;
+            }
+            Err(err) => {
+                i_slint_core::debug_log!(
+                    "Unable to acquire the next Vulkan swapchain image: {}",
+                    err
+                );
+                self.needs_swapchain_update.set(true);
+                return;
+            }
+        };
+
+        // Wait until the presentation engine has released the image, before Skia renders into it.
+        if let Err(err) = unsafe {
+            self.device
+                .wait_for_fences(&[self.acquire_fence], true, u64::MAX)
+                .and_then(|()| self.device.reset_fences(&[self.acquire_fence]))
+        } {
+            i_slint_core::debug_log!("Unable to wait for the Vulkan swapchain image: {}", err);
+            self.needs_swapchain_update.set(true);
+            return;
+        }
+
+        let (skia_format, color_type) = match self.surface_format.format {
+            vk::Format::R8G8B8A8_UNORM => {
+                (skia_vk::Format::R8G8B8A8_UNORM, skia_safe::ColorType::RGBA8888)
+            }
+            _ => (skia_vk::Format::B8G8R8A8_UNORM, skia_safe::ColorType::BGRA8888),
+        };
+
+        let gr_context = &mut self.gr_context.borrow_mut();
+
+        let mut surface = unsafe {
+            let image_info = skia_vk::ImageInfo::new(
+                swapchain.images[image_index as usize].as_raw() as _,
+                skia_vk::Alloc::default(),
+                skia_vk::ImageTiling::OPTIMAL,
+                skia_vk::ImageLayout::UNDEFINED,
+                skia_format,
+                1,
+                self.queue_family_index,
+                None,
+                None,
+                None,
+            );
+
+            let backend_render_target = skia_safe::gpu::BackendRenderTarget::new_vulkan(
+                (swapchain.extent.width as i32, swapchain.extent.height as i32),
+                1,
+                &image_info,
+            );
+
+            skia_safe::Surface::from_backend_render_target(
+                gr_context,
+                &backend_render_target,
+                skia_safe::gpu::SurfaceOrigin::TopLeft,
+                color_type,
+                None,
+                None,
+            )
+            .unwrap()
+        };
+
+        callback(surface.canvas(), gr_context);
+
+        // Let Skia transition the image into the layout expected by the presentation engine.
+        gr_context.flush_surface_with_access(
+            &mut surface,
+            skia_safe::surface::BackendSurfaceAccess::Present,
+            &Default::default(),
+        );
+        drop(surface);
+        gr_context.submit(None);
+
+        // Skia submitted its commands to the same queue. The signal operation of an empty batch
+        // submitted after them waits for all the previously submitted commands, so the
+        // presentation waits for the rendering without blocking the CPU.
+        let render_finished = [swapchain.render_finished[image_index as usize]];
+        let submit_info = vk::SubmitInfo::builder().signal_semaphores(&render_finished).build();
+        if let Err(err) =
+            unsafe { self.device.queue_submit(self.queue, &[submit_info], vk::Fence::null()) }
+        {
+            i_slint_core::debug_log!("Unable to submit the Vulkan rendering: {}", err);
+            self.needs_swapchain_update.set(true);
+            return;
+        }
+
+        let swapchains = [swapchain.swapchain];
+        let image_indices = [image_index];
+        let present_info = vk::PresentInfoKHR::builder()
+            .wait_semaphores(&render_finished)
+            .swapchains(&swapchains)
+            .image_indices(&image_indices);
+        match unsafe { self.swapchain_loader.queue_present(self.queue, &present_info) } {
+            Ok(false) => {}
+            Ok(true) | Err(vk::Result::ERROR_OUT_OF_DATE_KHR) => {
+                self.needs_swapchain_update.set(true)
+            }
+            Err(err) => {
+                i_slint_core::debug_log!("Unable to present the Vulkan swapchain image: {}", err);
+                self.needs_swapchain_update.set(true)
+            }
+        }
+    }
+
+    fn bits_per_pixel(&self) -> u8 {
+        // Only 8-bit RGBA formats are selected in new()
+        32
+    }
+}
+
+impl VulkanSurface {
+    /// (Re-)creates the swapchain for the current size of the window. On error, the swapchain is
+    /// left empty, and nothing is rendered until it is successfully created.
+    fn update_swapchain(&self, swapchain: &mut Swapchain) -> Result<(), vk::Result> {
+        unsafe { self.device.device_wait_idle() }?;
+
+        // The previous semaphores may still be waited for by a presentation of the old swapchain,
+        // which is done after device_wait_idle.
+        for semaphore in swapchain.render_finished.drain(..) {
+            unsafe { self.device.destroy_semaphore(semaphore, None) };
+        }
+        swapchain.images.clear();
+
+        let capabilities = unsafe {
+            self.surface_loader
+                .get_physical_device_surface_capabilities(self.physical_device, self.surface)
+        }?;
+
+        let extent = match capabilities.current_extent.width {
+            u32::MAX => {
+                let size = self.window.inner_size();
+                vk::Extent2D {
+                    width: size.width.clamp(
+                        capabilities.min_image_extent.width,
+                        capabilities.max_image_extent.width,
+                    ),
+                    height: size.height.clamp(
+                        capabilities.min_image_extent.height,
+                        capabilities.max_image_extent.height,
+                    ),
+                }
+            }
+            _ => capabilities.current_extent,
+        };
+
+        let old_swapchain = swapchain.swapchain;
+        swapchain.extent = extent;
+
+        if extent.width == 0 || extent.height == 0 {
+            // Minimized windows can't have a swapchain; try again on the next resize.
+            return Ok(());
+        }
+
+        let mut min_image_count = capabilities.min_image_count + 1;
+        if capabilities.max_image_count > 0 {
+            min_image_count = min_image_count.min(capabilities.max_image_count);
+        }
+
+        // Skia renders into color attachments, and uses the transfer usages when available.
+        let image_usage = vk::ImageUsageFlags::COLOR_ATTACHMENT
+            | ((vk::ImageUsageFlags::TRANSFER_SRC | vk::ImageUsageFlags::TRANSFER_DST)
+                & capabilities.supported_usage_flags);
+        let composite_alpha = [
+            vk::CompositeAlphaFlagsKHR::OPAQUE,
+            vk::CompositeAlphaFlagsKHR::INHERIT,
+            vk::CompositeAlphaFlagsKHR::PRE_MULTIPLIED,
+            vk::CompositeAlphaFlagsKHR::POST_MULTIPLIED,
+        ]
+        .into_iter()
+        .find(|mode| capabilities.supported_composite_alpha.contains(*mode))
+        .unwrap_or(vk::CompositeAlphaFlagsKHR::OPAQUE);
+
+        let create_info = vk::SwapchainCreateInfoKHR::builder()
+            .surface(self.surface)
+            .min_image_count(min_image_count)
+            .image_format(self.surface_format.format)
+            .image_color_space(self.surface_format.color_space)
+            .image_extent(extent)
+            .image_array_layers(1)
+            .image_usage(image_usage)
+            .image_sharing_mode(vk::SharingMode::EXCLUSIVE)
+            .pre_transform(capabilities.current_transform)
+            .composite_alpha(composite_alpha)
+            .present_mode(self.present_mode)
+            .clipped(true)
+            .old_swapchain(old_swapchain);
+
+        // The old swapchain is retired even if the creation fails.
+        let result = unsafe { self.swapchain_loader.create_swapchain(&create_info, None) };
+        if old_swapchain != vk::SwapchainKHR::null() {
+            unsafe { self.swapchain_loader.destroy_swapchain(old_swapchain, None) };
+        }
+        swapchain.swapchain = match result {
+            Ok(new_swapchain) => new_swapchain,
+            Err(err) => {
+                swapchain.swapchain = vk::SwapchainKHR::null();
+                return Err(err);
+            }
+        };
+
+        let images = unsafe { self.swapchain_loader.get_swapchain_images(swapchain.swapchain) }?;
+        for _ in &images {
+            let semaphore = unsafe { self.device.create_semaphore(&Default::default(), None) }?;
+            swapchain.render_finished.push(semaphore);
+        }
+        // Only set once there is a semaphore for each image
+        swapchain.images = images;
+        Ok(())
+    }
+}
+
+impl Drop for VulkanSurface {
+    fn drop(&mut self) {
+        unsafe {
+            let _ = self.device.device_wait_idle();
+            // Skia must release its resources before the device goes away.
+            self.gr_context.get_mut().abandon();
+            let swapchain = self.swapchain.get_mut();
+            for semaphore in swapchain.render_finished.drain(..) {
+                self.device.destroy_semaphore(semaphore, None);
+            }
+            if swapchain.swapchain != vk::SwapchainKHR::null() {
+                self.swapchain_loader.destroy_swapchain(swapchain.swapchain, None);
+            }
+            self.device.destroy_fence(self.acquire_fence, None);
+            self.device.destroy_device(None);
+            self.surface_loader.destroy_surface(self.surface, None);
+            self.instance.destroy_instance(None);
+        }
+    }
+}
@@ -0,0 +1,46 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+/*!
+    Wayland specific window setup.
+
+    Wayland compositors usually don't draw the window decorations, so winit draws them itself. These
+    client-side decorations come in a light and a dark variant. Since there is no reliable way to query
+    the color scheme of the desktop, the variant is chosen from the `SLINT_WAYLAND_CSD_THEME` environment
+    variable, or else from the `GTK_THEME` environment variable. The same choice is then used as the
+    color scheme of the window, so that the decorations and the content of the window look alike.
+*/
+
+use winit::window::Theme;
+
+/// Returns the theme to use for the client-side decorations, if there is a preference.
+fn csd_theme() -> Option<Theme> {
+    if let Ok(theme) = std::env::var("SLINT_WAYLAND_CSD_THEME") {
+        return match theme.to_ascii_lowercase().as_str() {
+            "dark" => Some(Theme::Dark),
+            "light" => Some(Theme::Light),
+            _ => {
+                i_slint_core::debug_log!(
+                    "slint winit: unrecognized SLINT_WAYLAND_CSD_THEME {}, expected dark or light",
+                    theme
+                );
+                None
+            }
+        };
+    }
+    // For example `Adwaita:dark`
+    let gtk_theme = std::env::var("GTK_THEME").ok()?;
+    Some(if gtk_theme.to_ascii_lowercase().ends_with(":dark") { Theme::Dark } else { Theme::Light })
+}
+
+/// Applies the theme of the decorations to the builder of a window that may be shown on Wayland,
+/// and returns it.
+pub fn apply_csd_theme(
+    window_builder: winit::window::WindowBuilder,
+) -> (winit::window::WindowBuilder, Option<Theme>) {
+    use winit::platform::unix::WindowBuilderExtUnix;
+    match csd_theme() {
+        Some(theme) => (window_builder.with_wayland_csd_theme(theme), Some(theme)),
+        None => (window_builder, None),
+    }
+}
@@ -57,6 +57,27 @@ enum TextOverflow {
                 Elide,
             }
 
+            /// This enum describes where the `…` is placed when the text is elided.
+            enum TextElidePosition {
+                /// The end of the text is replaced by `…`.
+                End,
+                /// The start of the text is replaced by `…`.
+                Start,
+                /// The middle of the text is replaced by `…`, keeping both the start and the end visible.
+                Middle,
+            }
+
+            /// This enum describes the base direction of a paragraph of text, which determines in which
+            /// order runs of left-to-right and right-to-left text, such as Latin and Arabic, are placed.
+            enum TextDirection {
+                /// The direction is detected from the first character with a strong direction in the text.
+                Auto,
+                /// The text is laid out from left to right.
+                Ltr,
+                /// The text is laid out from right to left.
+                Rtl,
+            }
+
             /// This enum describes whether an event was rejected or accepted by an event handler.
             enum EventResult {
                 /// The event is rejected by this event handler and may then be handled by the parent item
@@ -73,6 +94,26 @@ enum FillRule {
                 Evenodd,
             }
 
+            /// This enum describes the shape at the end of the open sub-paths when a path is stroked.
+            enum LineCap {
+                /// The stroke ends exactly at the end point of the sub-path.
+                Butt,
+                /// The stroke ends with a semi-circle centered on the end point of the sub-path.
+                Round,
+                /// The stroke ends with a square that extends half the stroke width beyond the end point of the sub-path.
+                Square,
+            }
+
+            /// This enum describes the shape used at the corners where two segments of a stroked path meet.
+            enum LineJoin {
+                /// The outer edges of the stroke are extended until they meet in a sharp corner.
+                Miter,
+                /// The corner is rounded with a circular arc.
+                Round,
+                /// The corner is cut off with a straight line.
+                Bevel,
+            }
+
             enum StandardButtonKind {
                 Ok,
                 Cancel,
@@ -120,6 +161,16 @@ enum PointerEventButton {
                 Middle,
             }
 
+            /// This enum describes whether the colors of the application are dark or light.
+            enum ColorScheme {
+                /// The color scheme is not known or not set: the platform setting is used.
+                Unknown,
+                /// Dark text on a light background.
+                Light,
+                /// Light text on a dark background.
+                Dark,
+            }
+
             /// This enum represents different types of mouse cursors. It is a subset of the mouse cursors available in CSS.
             /// For details and pictograms see the [MDN Documentation for cursor](https://developer.mozilla.org/en-US/docs/Web/CSS/cursor#values).
             /// Depending on the backend and used OS unidirectional resize cursors may be replaced with bidirectional ones.
@@ -211,6 +262,26 @@ enum InputType {
                 Password,
             }
 
+            /// This enum describes how an element can receive the keyboard focus.
+            enum FocusPolicy {
+                /// The element receives the focus when clicked, and with the Tab key.
+                TabAndClick,
+                /// The element receives the focus with the Tab key, but not when clicked.
+                TabOnly,
+                /// The element receives the focus when clicked, but is skipped by the Tab key.
+                ClickOnly,
+                /// The element never receives the focus, even when calling `focus()`.
+                NoFocus,
+            }
+
+            /// This enum describes what happens when a `Flickable` is scrolled past the edges of its viewport.
+            enum OvershootBehavior {
+                /// The viewport stops at its edges.
+                Clamp,
+                /// The viewport can be dragged or flicked past its edges and bounces back.
+                Bounce,
+            }
+
             /// Enum representing the alignment property of a BoxLayout or HorizontalLayout
             enum LayoutAlignment {
                 Stretch,
@@ -221,6 +292,16 @@ enum LayoutAlignment {
                 SpaceAround,
             }
 
+            /// This enum describes the direction in which the children of a horizontal layout or a grid
+            /// layout are laid out.
+            enum LayoutDirection {
+                /// The children are laid out from left to right.
+                Ltr,
+                /// The children are laid out from right to left: the order of the items, the alignment and
+                /// the left and right paddings are mirrored.
+                Rtl,
+            }
+
             /// PathEvent is a low-level data structure describing the composition of a path. Typically it is
             /// generated at compile time from a higher-level description, such as SVG commands.
             enum PathEvent {
@@ -255,6 +336,12 @@ enum AccessibleRole {
                 Spinbox,
                 /// The element is a Tab or behaves like one.
                 Tab,
+                /// The element is a ProgressIndicator or a Spinner, or behaves like one.
+                ProgressIndicator,
+                /// The element is a Switch or behaves like one.
+                Switch,
+                /// The element is a segment of a SegmentedControl, or behaves like a radio button.
+                RadioButton,
                 /// The role for a Text element. It is automatically applied.
                 Text,
             }
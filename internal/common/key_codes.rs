@@ -18,6 +18,11 @@ macro_rules! for_each_special_keys {
 '\u{0019}'  # Backtab     # Qt_Key_Key_Backtab      #              ;
 '\u{007f}'  # Delete      # Qt_Key_Key_Delete       # Delete       ;
 
+'\u{0010}'  # Shift       # Qt_Key_Key_Shift        # LShift|RShift ;
+'\u{0011}'  # Control     # Qt_Key_Key_Control      # LControl|RControl ;
+'\u{0012}'  # Alt         # Qt_Key_Key_Alt          # LAlt|RAlt    ;
+'\u{0017}'  # Meta        # Qt_Key_Key_Meta         # LWin|RWin    ;
+
 '\u{F700}'	# UpArrow     # Qt_Key_Key_Up           # Up           ;
 '\u{F701}'	# DownArrow   # Qt_Key_Key_Down         # Down         ;
 '\u{F702}'	# LeftArrow   # Qt_Key_Key_Left         # Left         ;
@@ -0,0 +1,145 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+//! Parsing of the key combinations used by the `Shortcut` element, such as `Ctrl+Shift+S`.
+//!
+//! The `Ctrl` modifier maps to the command key on macOS, like the `control` field of the
+//! keyboard modifiers reported by the backends.
+
+/// A key combination: modifiers and a single key
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct KeySequence {
+    pub control: bool,
+    pub alt: bool,
+    pub shift: bool,
+    pub meta: bool,
+    /// The key: a lowercase character, or one of the codes of [`crate::for_each_special_keys`]
+    pub key: char,
+}
+
+/// The reason why a key sequence can't be parsed
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KeySequenceError<'a> {
+    Empty,
+    UnknownModifier(&'a str),
+    DuplicateModifier(&'a str),
+    UnknownKey(&'a str),
+}
+
+impl core::fmt::Display for KeySequenceError<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            KeySequenceError::Empty => write!(f, "The key sequence is empty"),
+            KeySequenceError::UnknownModifier(m) => write!(f, "Unknown modifier '{}'", m),
+            KeySequenceError::DuplicateModifier(m) => write!(f, "Duplicated modifier '{}'", m),
+            KeySequenceError::UnknownKey(k) => write!(f, "Unknown key '{}'", k),
+        }
+    }
+}
+
+/// Parse a key sequence like `Ctrl+S`, `Alt+F4`, `Ctrl++` or `Escape`.
+/// Modifiers and key names are case insensitive.
+pub fn parse_key_sequence(text: &str) -> Result<KeySequence, KeySequenceError<'_>> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Err(KeySequenceError::Empty);
+    }
+    let (modifiers, key) = if text == "+" {
+        ("", text)
+    } else if let Some(modifiers) = text.strip_suffix("++") {
+        (modifiers, "+")
+    } else {
+        text.rsplit_once('+').unwrap_or(("", text))
+    };
+
+    let mut sequence = KeySequence { key: parse_key(key.trim())?, ..Default::default() };
+    for modifier in modifiers.split('+').map(str::trim).filter(|m| !m.is_empty()) {
+        let is_one_of = |names: &[&str]| names.iter().any(|n| modifier.eq_ignore_ascii_case(n));
+        let flag = if is_one_of(&["Ctrl", "Control", "Cmd", "Command"]) {
+            &mut sequence.control
+        } else if is_one_of(&["Alt", "Option"]) {
+            &mut sequence.alt
+        } else if is_one_of(&["Shift"]) {
+            &mut sequence.shift
+        } else if is_one_of(&["Meta", "Super", "Win"]) {
+            &mut sequence.meta
+        } else {
+            return Err(KeySequenceError::UnknownModifier(modifier));
+        };
+        if core::mem::replace(flag, true) {
+            return Err(KeySequenceError::DuplicateModifier(modifier));
+        }
+    }
+    Ok(sequence)
+}
+
+fn parse_key(key: &str) -> Result<char, KeySequenceError<'_>> {
+    let mut chars = key.chars();
+    match (chars.next(), chars.next()) {
+        (None, _) => return Err(KeySequenceError::Empty),
+        (Some(c), None) => return Ok(c.to_lowercase().next().unwrap_or(c)),
+        _ => {}
+    }
+
+    macro_rules! special_key_lookup {
+        ($($char:literal # $name:ident # $($_qt:ident)|* # $($_winit:ident)|* ;)*) => {
+            $(if key.eq_ignore_ascii_case(stringify!($name)) {
+                return Ok($char);
+            })*
+        };
+    }
+    crate::for_each_special_keys!(special_key_lookup);
+
+    let aliases = [
+        ("Esc", '\u{001b}'),
+        ("Enter", '\u{000a}'),
+        ("Del", '\u{007f}'),
+        ("Space", ' '),
+        ("Up", '\u{F700}'),
+        ("Down", '\u{F701}'),
+        ("Left", '\u{F702}'),
+        ("Right", '\u{F703}'),
+        ("PgUp", '\u{F72C}'),
+        ("PgDown", '\u{F72D}'),
+        ("Plus", '+'),
+    ];
+    aliases
+        .iter()
+        .find(|(name, _)| key.eq_ignore_ascii_case(name))
+        .map(|(_, c)| *c)
+        .ok_or(KeySequenceError::UnknownKey(key))
+}
+
+#[test]
+fn test_parse_key_sequence() {
+    let ctrl_s = KeySequence { control: true, key: 's', ..Default::default() };
+    assert_eq!(parse_key_sequence("Ctrl+S"), Ok(ctrl_s));
+    assert_eq!(parse_key_sequence("cmd + s"), Ok(ctrl_s));
+    assert_eq!(
+        parse_key_sequence("Ctrl+Shift+Z"),
+        Ok(KeySequence { control: true, shift: true, key: 'z', ..Default::default() })
+    );
+    assert_eq!(
+        parse_key_sequence("Alt+F4"),
+        Ok(KeySequence { alt: true, key: '\u{F707}', ..Default::default() })
+    );
+    assert_eq!(
+        parse_key_sequence("escape"),
+        Ok(KeySequence { key: '\u{1b}', ..Default::default() })
+    );
+    assert_eq!(parse_key_sequence("Esc"), Ok(KeySequence { key: '\u{1b}', ..Default::default() }));
+    assert_eq!(
+        parse_key_sequence("Ctrl++"),
+        Ok(KeySequence { control: true, key: '+', ..Default::default() })
+    );
+    assert_eq!(parse_key_sequence("+"), Ok(KeySequence { key: '+', ..Default::default() }));
+}
+
+#[test]
+fn test_parse_key_sequence_errors() {
+    assert_eq!(parse_key_sequence(""), Err(KeySequenceError::Empty));
+    assert_eq!(parse_key_sequence("Ctrl+"), Err(KeySequenceError::Empty));
+    assert_eq!(parse_key_sequence("Hyper+S"), Err(KeySequenceError::UnknownModifier("Hyper")));
+    assert_eq!(parse_key_sequence("Ctrl+Ctrl+S"), Err(KeySequenceError::DuplicateModifier("Ctrl")));
+    assert_eq!(parse_key_sequence("Ctrl+Foo"), Err(KeySequenceError::UnknownKey("Foo")));
+}
@@ -7,3 +7,4 @@
 
 pub mod enums;
 pub mod key_codes;
+pub mod key_sequence;
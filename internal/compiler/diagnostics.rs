@@ -7,12 +7,14 @@
 
 /// Span represent an error location within a file.
 ///
-/// Currently, it is just an offset in byte within the file.
+/// Currently, it is just an offset in byte within the file, and the length in byte of the
+/// location, which is 0 if it is unknown.
 ///
 /// When the `proc_macro_span` feature is enabled, it may also hold a proc_macro span.
 #[derive(Debug, Clone)]
 pub struct Span {
     pub offset: usize,
+    pub length: usize,
     #[cfg(feature = "proc_macro_span")]
     pub span: Option<proc_macro::Span>,
 }
@@ -26,12 +28,17 @@ pub fn is_valid(&self) -> bool {
     pub fn new(offset: usize) -> Self {
         Self { offset, ..Default::default() }
     }
+
+    pub fn new_with_length(offset: usize, length: usize) -> Self {
+        Self { length, ..Self::new(offset) }
+    }
 }
 
 impl Default for Span {
     fn default() -> Self {
         Span {
             offset: usize::MAX,
+            length: 0,
             #[cfg(feature = "proc_macro_span")]
             span: Default::default(),
         }
@@ -123,14 +130,20 @@ pub fn load_from_path(path: &Path) -> Result<String, Diagnostic> {
     } else {
         std::fs::read_to_string(path)
     })
-    .map_err(|err| Diagnostic {
+    .map_err(|err| load_error(path, err))
+}
+
+/// Returns the diagnostic reporting that the file at `path` could not be loaded
+pub fn load_error(path: &Path, err: std::io::Error) -> Diagnostic {
+    Diagnostic {
         message: format!("Could not load {}: {}", path.display(), err),
         span: SourceLocation {
             source_file: Some(SourceFileInner::from_path_only(path.to_owned())),
             span: Default::default(),
         },
         level: DiagnosticLevel::Error,
-    })
+        code: DiagnosticCode::FileAccess,
+    }
 }
 
 #[derive(Debug, Clone, Default)]
@@ -185,15 +198,61 @@ fn from(l: DiagnosticLevel) -> Self {
     }
 }
 
+/// This enum describes the kind of problem that a diagnostic reports, so that tools can handle some
+/// of them specifically without parsing the message.
+///
+/// The codes are stable, but new codes may be added, and the diagnostics that don't have a more
+/// specific code yet use [`DiagnosticCode::Other`].
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash)]
+#[non_exhaustive]
+pub enum DiagnosticCode {
+    /// The code is not valid .slint syntax.
+    Syntax,
+    /// A file could not be read.
+    FileAccess,
+    /// A file to import could not be found.
+    ImportNotFound,
+    /// An identifier doesn't refer to a known property, element, or callback.
+    UnknownIdentifier,
+    /// A type or an element doesn't exist.
+    UnknownType,
+    /// A value doesn't have the type expected in this context.
+    TypeMismatch,
+    /// Any other problem.
+    Other,
+}
+
+impl Default for DiagnosticCode {
+    fn default() -> Self {
+        Self::Other
+    }
+}
+
+impl DiagnosticCode {
+    /// Returns a short string identifying the code, such as `"syntax"`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Syntax => "syntax",
+            Self::FileAccess => "file-access",
+            Self::ImportNotFound => "import-not-found",
+            Self::UnknownIdentifier => "unknown-identifier",
+            Self::UnknownType => "unknown-type",
+            Self::TypeMismatch => "type-mismatch",
+            Self::Other => "other",
+        }
+    }
+}
+
 /// This structure represent a diagnostic emitted while compiling .slint code.
 ///
 /// It is basically a message, a level (warning or error), attached to a
-/// position in the code
+/// range in the code, and a code identifying the kind of problem.
 #[derive(Debug, Clone)]
 pub struct Diagnostic {
     message: String,
     span: SourceLocation,
     level: DiagnosticLevel,
+    code: DiagnosticCode,
 }
 
 impl Diagnostic {
@@ -207,9 +266,27 @@ pub fn message(&self) -> &str {
         &self.message
     }
 
+    /// Return the code identifying the kind of problem
+    pub fn code(&self) -> DiagnosticCode {
+        self.code
+    }
+
     /// Returns a tuple with the line (starting at 1) and column number (starting at 0)
     pub fn line_column(&self) -> (usize, usize) {
-        let offset = self.span.span.offset;
+        self.line_column_for_offset(self.span.span.offset)
+    }
+
+    /// Returns a tuple with the line (starting at 1) and column number (starting at 0) of the end of
+    /// the code this diagnostic is attached to. This is the same as [`Self::line_column()`] if the
+    /// length of the code is not known.
+    pub fn end_line_column(&self) -> (usize, usize) {
+        if !self.span.span.is_valid() {
+            return self.line_column();
+        }
+        self.line_column_for_offset(self.span.span.offset + self.span.span.length)
+    }
+
+    fn line_column_for_offset(&self, offset: usize) -> (usize, usize) {
         let line_offsets = match &self.span.source_file {
             None => return (0, 0),
             Some(sl) => sl.line_offsets(),
@@ -268,13 +345,38 @@ pub fn push_diagnostic_with_span(
         message: String,
         span: SourceLocation,
         level: DiagnosticLevel,
+    ) {
+        self.push_diagnostic_with_span_and_code(message, span, level, DiagnosticCode::Other)
+    }
+    fn push_diagnostic_with_span_and_code(
+        &mut self,
+        message: String,
+        span: SourceLocation,
+        level: DiagnosticLevel,
+        code: DiagnosticCode,
     ) {
         debug_assert!(
             !message.as_str().ends_with('.'),
             "Error message should not end with a period: ({:?})",
             message
         );
-        self.inner.push(Diagnostic { message, span, level });
+        self.inner.push(Diagnostic { message, span, level, code });
+    }
+    pub fn push_error_with_span_and_code(
+        &mut self,
+        message: String,
+        code: DiagnosticCode,
+        span: SourceLocation,
+    ) {
+        self.push_diagnostic_with_span_and_code(message, span, DiagnosticLevel::Error, code)
+    }
+    pub fn push_error_with_code(
+        &mut self,
+        message: String,
+        code: DiagnosticCode,
+        source: &dyn Spanned,
+    ) {
+        self.push_error_with_span_and_code(message, code, source.to_source_location());
     }
     pub fn push_error_with_span(&mut self, message: String, span: SourceLocation) {
         self.push_diagnostic_with_span(message, span, DiagnosticLevel::Error)
@@ -1,7 +1,7 @@
 // Copyright © SixtyFPS GmbH <info@slint-ui.com>
 // SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
 
-use crate::diagnostics::{BuildDiagnostics, SourceLocation, Spanned};
+use crate::diagnostics::{BuildDiagnostics, DiagnosticCode, SourceLocation, Spanned};
 use crate::langtype::{BuiltinElement, EnumerationValue, Type};
 use crate::layout::Orientation;
 use crate::object_tree::*;
@@ -19,6 +19,7 @@
 pub enum BuiltinFunction {
     GetWindowScaleFactor,
     AnimationTick,
+    ReducedMotion,
     Debug,
     Mod,
     Round,
@@ -35,18 +36,38 @@ pub enum BuiltinFunction {
     Log,
     Pow,
     SetFocusItem,
+    /// Scrolls the Flickables containing the element so that the element is visible
+    EnsureItemVisible,
+    /// `flickable.scroll-to(x, y)`
+    FlickableScrollTo,
+    /// `toast-queue.push(text, action-text, duration)`
+    ToastQueuePush,
+    /// `toast-queue.dismiss()`
+    ToastQueueDismiss,
     ShowPopupWindow,
+    ClosePopupWindow,
     /// the "42".to_float()
     StringToFloat,
     /// the "42".is_float()
     StringIsFloat,
+    /// the "Abc".to_lowercase()
+    StringToLowercase,
+    /// the "abc".contains("b")
+    StringContains,
     ColorBrighter,
     ColorDarker,
     ImageSize,
+    /// Load the image at the path (first argument) in the background, and return the placeholder
+    /// (second argument) until it is decoded
+    LoadImageInBackground,
     ArrayLength,
     Rgb,
     DarkColorScheme,
     ImplicitLayoutInfo(Orientation),
+    /// The size that the laid out text of a Text element occupies in the given orientation
+    TextContentSize(Orientation),
+    /// Whether some of the text of a Text element is not visible
+    TextTruncated,
     RegisterCustomFontByPath,
     RegisterCustomFontByMemory,
     RegisterBitmapFont,
@@ -83,6 +104,9 @@ pub fn ty(&self) -> Type {
             BuiltinFunction::AnimationTick => {
                 Type::Function { return_type: Type::Duration.into(), args: vec![] }
             }
+            BuiltinFunction::ReducedMotion => {
+                Type::Function { return_type: Box::new(Type::Bool), args: vec![] }
+            }
             BuiltinFunction::Debug => {
                 Type::Function { return_type: Box::new(Type::Void), args: vec![Type::String] }
             }
@@ -106,24 +130,53 @@ pub fn ty(&self) -> Type {
                 return_type: Box::new(Type::Float32),
                 args: vec![Type::Float32, Type::Float32],
             },
-            BuiltinFunction::SetFocusItem => Type::Function {
+            BuiltinFunction::SetFocusItem | BuiltinFunction::EnsureItemVisible => Type::Function {
                 return_type: Box::new(Type::Void),
                 args: vec![Type::ElementReference],
             },
-            BuiltinFunction::ShowPopupWindow => Type::Function {
+            BuiltinFunction::FlickableScrollTo => Type::Function {
+                return_type: Box::new(Type::Void),
+                args: vec![Type::ElementReference, Type::LogicalLength, Type::LogicalLength],
+            },
+            BuiltinFunction::ToastQueuePush => Type::Function {
+                return_type: Box::new(Type::Void),
+                args: vec![Type::ElementReference, Type::String, Type::String, Type::Duration],
+            },
+            BuiltinFunction::ToastQueueDismiss => Type::Function {
                 return_type: Box::new(Type::Void),
                 args: vec![Type::ElementReference],
             },
+            BuiltinFunction::ShowPopupWindow | BuiltinFunction::ClosePopupWindow => {
+                Type::Function {
+                    return_type: Box::new(Type::Void),
+                    args: vec![Type::ElementReference],
+                }
+            }
             BuiltinFunction::StringToFloat => {
                 Type::Function { return_type: Box::new(Type::Float32), args: vec![Type::String] }
             }
             BuiltinFunction::StringIsFloat => {
                 Type::Function { return_type: Box::new(Type::Bool), args: vec![Type::String] }
             }
+            BuiltinFunction::StringToLowercase => {
+                Type::Function { return_type: Box::new(Type::String), args: vec![Type::String] }
+            }
+            BuiltinFunction::StringContains => Type::Function {
+                return_type: Box::new(Type::Bool),
+                args: vec![Type::String, Type::String],
+            },
             BuiltinFunction::ImplicitLayoutInfo(_) => Type::Function {
                 return_type: Box::new(crate::layout::layout_info_type()),
                 args: vec![Type::ElementReference],
             },
+            BuiltinFunction::TextContentSize(_) => Type::Function {
+                return_type: Box::new(Type::LogicalLength),
+                args: vec![Type::ElementReference],
+            },
+            BuiltinFunction::TextTruncated => Type::Function {
+                return_type: Box::new(Type::Bool),
+                args: vec![Type::ElementReference],
+            },
             BuiltinFunction::ColorBrighter => Type::Function {
                 return_type: Box::new(Type::Brush),
                 args: vec![Type::Brush, Type::Float32],
@@ -132,6 +185,10 @@ pub fn ty(&self) -> Type {
                 return_type: Box::new(Type::Brush),
                 args: vec![Type::Brush, Type::Float32],
             },
+            BuiltinFunction::LoadImageInBackground => Type::Function {
+                return_type: Box::new(Type::Image),
+                args: vec![Type::String, Type::Image],
+            },
             BuiltinFunction::ImageSize => Type::Function {
                 return_type: Box::new(Type::Struct {
                     fields: IntoIterator::into_iter([
@@ -171,6 +228,7 @@ fn is_pure(&self) -> bool {
         match self {
             BuiltinFunction::GetWindowScaleFactor => false,
             BuiltinFunction::AnimationTick => false,
+            BuiltinFunction::ReducedMotion => false,
             BuiltinFunction::DarkColorScheme => false,
             // Even if it is not pure, we optimize it away anyway
             BuiltinFunction::Debug => true,
@@ -189,8 +247,11 @@ fn is_pure(&self) -> bool {
             | BuiltinFunction::Pow
             | BuiltinFunction::ATan => true,
             BuiltinFunction::SetFocusItem => false,
-            BuiltinFunction::ShowPopupWindow => false,
+            BuiltinFunction::EnsureItemVisible | BuiltinFunction::FlickableScrollTo => false,
+            BuiltinFunction::ToastQueuePush | BuiltinFunction::ToastQueueDismiss => false,
+            BuiltinFunction::ShowPopupWindow | BuiltinFunction::ClosePopupWindow => false,
             BuiltinFunction::StringToFloat | BuiltinFunction::StringIsFloat => true,
+            BuiltinFunction::StringToLowercase | BuiltinFunction::StringContains => true,
             BuiltinFunction::ColorBrighter | BuiltinFunction::ColorDarker => true,
             // ImageSize is pure, except when loading images via the network. Then the initial size will be 0/0 and
             // we need to make sure that calls to this function stay within a binding, so that the property
@@ -200,9 +261,12 @@ fn is_pure(&self) -> bool {
             BuiltinFunction::ImageSize => true,
             #[cfg(target_arch = "wasm32")]
             BuiltinFunction::ImageSize => false,
+            // The result changes when the image is loaded
+            BuiltinFunction::LoadImageInBackground => false,
             BuiltinFunction::ArrayLength => true,
             BuiltinFunction::Rgb => true,
             BuiltinFunction::ImplicitLayoutInfo(_) => false,
+            BuiltinFunction::TextContentSize(_) | BuiltinFunction::TextTruncated => false,
             BuiltinFunction::RegisterCustomFontByPath
             | BuiltinFunction::RegisterCustomFontByMemory
             | BuiltinFunction::RegisterBitmapFont => false,
@@ -1028,7 +1092,11 @@ pub fn maybe_convert_to(
                 if let Some(t) = fields.remove(f) {
                     new_values.insert(f.clone(), v.clone().maybe_convert_to(t, node, diag));
                 } else {
-                    diag.push_error(format!("Cannot convert {} to {}", ty, target_type), node);
+                    diag.push_error_with_code(
+                        format!("Cannot convert {} to {}", ty, target_type),
+                        DiagnosticCode::TypeMismatch,
+                        node,
+                    );
                     return self;
                 }
             }
@@ -1060,7 +1128,7 @@ pub fn maybe_convert_to(
                     );
                 }
             }
-            diag.push_error(message, node);
+            diag.push_error_with_code(message, DiagnosticCode::TypeMismatch, node);
             self
         }
     }
@@ -61,6 +61,7 @@ pub fn generate(
     format: OutputFormat,
     destination: &mut impl std::io::Write,
     doc: &Document,
+    compiler_config: &crate::CompilerConfiguration,
 ) -> std::io::Result<()> {
     #![allow(unused_variables)]
     #![allow(unreachable_code)]
@@ -73,7 +74,7 @@ pub fn generate(
     match format {
         #[cfg(feature = "cpp")]
         OutputFormat::Cpp => {
-            let output = cpp::generate(doc);
+            let output = cpp::generate(doc, compiler_config.cpp_namespace.as_deref());
             write!(destination, "{}", output)?;
         }
         #[cfg(feature = "rust")]
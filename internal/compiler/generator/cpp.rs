@@ -78,6 +78,8 @@ fn indent(f: &mut Formatter<'_>) -> Result<(), Error> {
     #[derive(Default, Debug)]
     pub struct File {
         pub includes: Vec<String>,
+        /// The namespace in which the declarations and definitions are, if not the global namespace
+        pub namespace: Option<String>,
         pub declarations: Vec<Declaration>,
         pub definitions: Vec<Declaration>,
     }
@@ -87,12 +89,18 @@ fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
             for i in &self.includes {
                 writeln!(f, "#include {}", i)?;
             }
+            if let Some(namespace) = &self.namespace {
+                write!(f, "\nnamespace {} {{\n", namespace)?;
+            }
             for d in &self.declarations {
                 write!(f, "\n{}", d)?;
             }
             for d in &self.definitions {
                 write!(f, "\n{}", d)?;
             }
+            if let Some(namespace) = &self.namespace {
+                writeln!(f, "\n}} // namespace {}", namespace)?;
+            }
             Ok(())
         }
     }
@@ -469,8 +477,8 @@ fn handle_property_init(
 }
 
 /// Returns the text of the C++ code produced by the given root component
-pub fn generate(doc: &Document) -> impl std::fmt::Display {
-    let mut file = File::default();
+pub fn generate(doc: &Document, namespace: Option<&str>) -> impl std::fmt::Display {
+    let mut file = File { namespace: namespace.map(String::from), ..Default::default() };
 
     file.includes.push("<array>".into());
     file.includes.push("<limits>".into());
@@ -1086,45 +1094,80 @@ fn generate_item_tree(
         init_parent_parameters = ", parent";
     }
 
-    let mut create_code = vec![
-        format!(
-            "auto self_rc = vtable::VRc<slint::private_api::ComponentVTable, {0}>::make();",
-            target_struct.name
-        ),
-        format!("auto self = const_cast<{0} *>(&*self_rc);", target_struct.name),
-        "self->self_weak = vtable::VWeak(self_rc).into_dyn();".into(),
-    ];
+    let create_code = |share_globals_code: Vec<String>| {
+        let mut create_code = vec![
+            format!(
+                "auto self_rc = vtable::VRc<slint::private_api::ComponentVTable, {0}>::make();",
+                target_struct.name
+            ),
+            format!("auto self = const_cast<{0} *>(&*self_rc);", target_struct.name),
+            "self->self_weak = vtable::VWeak(self_rc).into_dyn();".into(),
+        ];
 
-    if parent_ctx.is_none() {
-        create_code.extend([format!(
-            "{}->m_window.window_handle().set_component(*self_rc);",
-            root_access
-        )]);
-    }
+        if parent_ctx.is_none() {
+            create_code.extend([format!(
+                "{}->m_window.window_handle().set_component(*self_rc);",
+                root_access
+            )]);
+        }
 
-    create_code.extend([
-        format!(
-            "{}->m_window.window_handle().register_component(self, self->item_array());",
-            root_access
+        create_code.extend(share_globals_code);
+
+        create_code.extend([
+            format!(
+                "{}->m_window.window_handle().register_component(self, self->item_array());",
+                root_access
+            ),
+            format!(
+                "self->init({}, self->self_weak, 0, 1 {});",
+                root_access, init_parent_parameters
+            ),
+            format!("return slint::ComponentHandle<{0}>{{ self_rc }};", target_struct.name),
+        ]);
+        create_code
+    };
+
+    let mut create_functions = vec![Function {
+        name: "create".into(),
+        signature: format!(
+            "({}) -> slint::ComponentHandle<{}>",
+            create_parameters.join(","),
+            target_struct.name
         ),
-        format!("self->init({}, self->self_weak, 0, 1 {});", root_access, init_parent_parameters),
-        format!("return slint::ComponentHandle<{0}>{{ self_rc }};", target_struct.name),
-    ]);
+        statements: Some(create_code(vec![])),
+        is_static: true,
+        ..Default::default()
+    }];
 
-    target_struct.members.push((
-        Access::Public,
-        Declaration::Function(Function {
-            name: "create".into(),
+    if parent_ctx.is_none() {
+        // Creates another instance with its own window, but the same globals as `other`.
+        // The globals keep the list of the instances using them, so `other` can be destroyed first.
+        create_functions.push(Function {
+            name: "create_sharing_globals_with".into(),
             signature: format!(
-                "({}) -> slint::ComponentHandle<{}>",
-                create_parameters.join(","),
+                "(const slint::ComponentHandle<{0}> &other) -> slint::ComponentHandle<{0}>",
                 target_struct.name
             ),
-            statements: Some(create_code),
+            statements: Some(create_code(
+                root.globals
+                    .iter()
+                    .flat_map(|glob| {
+                        let global_id = format!("global_{}", ident(&glob.name));
+                        std::iter::once(format!("self->{0} = other->{0};", global_id)).chain(
+                            (!glob.is_builtin)
+                                .then(|| format!("self->{}->roots.push_back(self);", global_id)),
+                        )
+                    })
+                    .collect(),
+            )),
             is_static: true,
             ..Default::default()
-        }),
-    ));
+        });
+    }
+
+    target_struct
+        .members
+        .extend(create_functions.into_iter().map(|f| (Access::Public, Declaration::Function(f))));
 
     let mut destructor = vec!["auto self = this;".to_owned()];
 
@@ -1133,6 +1176,15 @@ fn generate_item_tree(
         root_access
     ));
 
+    if parent_ctx.is_none() {
+        destructor.extend(
+            root.globals
+                .iter()
+                .filter(|glob| !glob.is_builtin)
+                .map(|glob| format!("std::erase(global_{}->roots, self);", ident(&glob.name))),
+        );
+    }
+
     target_struct.members.push((
         Access::Public,
         Declaration::Function(Function {
@@ -1729,9 +1781,9 @@ fn generate_global(file: &mut File, global: &llr::GlobalComponent, root: &llr::P
         ));
     }
 
-    let mut init = vec!["(void)this->root;".into()];
+    let mut init = vec!["(void)this->roots;".into()];
 
-    let ctx = EvaluationContext::new_global(root, global, "this->root".into());
+    let ctx = EvaluationContext::new_global(root, global, "this->root()".into());
 
     for (property_index, expression) in global.init_values.iter().enumerate() {
         if global.properties[property_index].use_count.get() == 0 {
@@ -1756,14 +1808,30 @@ fn generate_global(file: &mut File, global: &llr::GlobalComponent, root: &llr::P
             signature: format!("({} root)", root_ptr_type),
             is_constructor_or_destructor: true,
             statements: Some(init),
-            constructor_member_initializers: vec!["root(root)".into()],
+            constructor_member_initializers: vec!["roots{root}".into()],
             ..Default::default()
         }),
     ));
+    // The instances of the component using this global, when it is shared between several of them.
+    // The first one provides the window to the bindings of the global.
     global_struct.members.push((
         Access::Private,
-        Declaration::Var(Var { ty: root_ptr_type, name: "root".to_owned(), ..Default::default() }),
+        Declaration::Var(Var {
+            ty: format!("std::vector<{}>", root_ptr_type),
+            name: "roots".to_owned(),
+            ..Default::default()
+        }),
     ));
+    global_struct.members.push((
+        Access::Private,
+        Declaration::Function(Function {
+            name: "root".into(),
+            signature: format!("() const -> {}", root_ptr_type),
+            statements: Some(vec!["return roots.front();".into()]),
+            ..Default::default()
+        }),
+    ));
+    global_struct.friends.push(ident(&root.item_tree.root.name));
 
     let declarations = generate_public_api_for_properties(&global.public_properties, &ctx);
     global_struct.members.extend(declarations.into_iter().map(|decl| (Access::Public, decl)));
@@ -2356,6 +2424,7 @@ fn compile_builtin_function_call(
             format!("{}.scale_factor()", window)
         }
         BuiltinFunction::AnimationTick => "slint::cbindgen_private::slint_animation_tick()".into(),
+        BuiltinFunction::ReducedMotion => "slint::cbindgen_private::slint_reduced_motion()".into(),
         BuiltinFunction::Debug => {
             format!("std::cout << {} << std::endl;", a.join("<<"))
         }
@@ -2386,6 +2455,44 @@ fn compile_builtin_function_call(
                 panic!("internal error: invalid args to SetFocusItem {:?}", arguments)
             }
         }
+        BuiltinFunction::EnsureItemVisible => {
+            if let [llr::Expression::PropertyReference(pr)] = arguments {
+                let item = access_item_rc(pr, ctx);
+                format!("slint::private_api::ensure_item_visible({});", item)
+            } else {
+                panic!("internal error: invalid args to EnsureItemVisible {:?}", arguments)
+            }
+        }
+        BuiltinFunction::FlickableScrollTo => {
+            if let [llr::Expression::PropertyReference(pr), x, y] = arguments {
+                let item = access_member(pr, ctx);
+                let x = compile_expression(x, ctx);
+                let y = compile_expression(y, ctx);
+                format!("slint::cbindgen_private::slint_flickable_scroll_to(&{item}, {x}, {y});")
+            } else {
+                panic!("internal error: invalid args to FlickableScrollTo {:?}", arguments)
+            }
+        }
+        BuiltinFunction::ToastQueuePush => {
+            if let [llr::Expression::PropertyReference(pr), text, action_text, duration] = arguments
+            {
+                let item = access_member(pr, ctx);
+                let text = compile_expression(text, ctx);
+                let action_text = compile_expression(action_text, ctx);
+                let duration = compile_expression(duration, ctx);
+                format!("slint::cbindgen_private::slint_toast_queue_push(&{item}, {text}, {action_text}, {duration});")
+            } else {
+                panic!("internal error: invalid args to ToastQueuePush {:?}", arguments)
+            }
+        }
+        BuiltinFunction::ToastQueueDismiss => {
+            if let [llr::Expression::PropertyReference(pr)] = arguments {
+                let item = access_member(pr, ctx);
+                format!("slint::cbindgen_private::slint_toast_queue_dismiss(&{item});")
+            } else {
+                panic!("internal error: invalid args to ToastQueueDismiss {:?}", arguments)
+            }
+        }
         /*  std::from_chars is unfortunately not yet implemented in gcc
         BuiltinFunction::StringIsFloat => {
             "[](const auto &a){ double v; auto r = std::from_chars(std::begin(a), std::end(a), v); return r.ptr == std::end(a); }"
@@ -2401,6 +2508,16 @@ fn compile_builtin_function_call(
         BuiltinFunction::StringToFloat => {
             format!("[](const auto &a){{ auto e1 = std::end(a); auto e2 = const_cast<char*>(e1); auto r = std::strtod(std::begin(a), &e2); return e1 == e2 ? r : 0; }}({})", a.next().unwrap())
         }
+        BuiltinFunction::StringToLowercase => {
+            format!("{}.to_lowercase()", a.next().unwrap())
+        }
+        BuiltinFunction::StringContains => {
+            format!(
+                "(std::string_view({}).find(std::string_view({})) != std::string_view::npos)",
+                a.next().unwrap(),
+                a.next().unwrap()
+            )
+        }
         BuiltinFunction::ColorBrighter => {
             format!("{}.brighter({})", a.next().unwrap(), a.next().unwrap())
         }
@@ -2410,6 +2527,13 @@ fn compile_builtin_function_call(
         BuiltinFunction::ImageSize => {
             format!("{}.size()", a.next().unwrap())
         }
+        BuiltinFunction::LoadImageInBackground => {
+            format!(
+                "slint::private_api::load_image_from_path_in_background({}, {})",
+                a.next().unwrap(),
+                a.next().unwrap()
+            )
+        }
         BuiltinFunction::ArrayLength => {
             format!("[](const auto &model){{ (*model).track_row_count_changes(); return (*model).row_count(); }}({})", a.next().unwrap())
         }
@@ -2425,7 +2549,7 @@ fn compile_builtin_function_call(
             format!("{}.dark_color_scheme()", access_window_field(ctx))
         }
         BuiltinFunction::ShowPopupWindow => {
-            if let [llr::Expression::NumberLiteral(popup_index), x, y, llr::Expression::PropertyReference(parent_ref)] =
+            if let [llr::Expression::NumberLiteral(popup_index), x, y, close_on_click, close_on_click_outside, close_on_escape, modal, llr::Expression::PropertyReference(parent_ref), llr::Expression::PropertyReference(anchor_ref)] =
                 arguments
             {
                 let mut parent_ctx = ctx;
@@ -2442,16 +2566,23 @@ fn compile_builtin_function_call(
                 let current_sub_component = parent_ctx.current_sub_component.unwrap();
                 let popup_window_id =
                     ident(&current_sub_component.popup_windows[*popup_index as usize].root.name);
-                let parent_component = access_item_rc(parent_ref, ctx);
+                let anchor_item = access_item_rc(anchor_ref, ctx);
                 let x = compile_expression(x, ctx);
                 let y = compile_expression(y, ctx);
+                let close_on_click = compile_expression(close_on_click, ctx);
+                let close_on_click_outside = compile_expression(close_on_click_outside, ctx);
+                let close_on_escape = compile_expression(close_on_escape, ctx);
+                let modal = compile_expression(modal, ctx);
                 format!(
-                    "{window}.show_popup<{popup_window_id}>({component_access}, {{ static_cast<float>({x}), static_cast<float>({y}) }}, {{ {parent_component} }})"
+                    "{window}.show_popup<{popup_window_id}>({component_access}, {{ static_cast<float>({x}), static_cast<float>({y}) }}, {{ {close_on_click}, {close_on_click_outside}, {close_on_escape}, {modal} }}, {{ {anchor_item} }})"
                 )
             } else {
                 panic!("internal error: invalid args to ShowPopupWindow {:?}", arguments)
             }
         }
+        BuiltinFunction::ClosePopupWindow => {
+            format!("{}.close_popup()", access_window_field(ctx))
+        }
         BuiltinFunction::RegisterCustomFontByPath => {
             if let [llr::Expression::StringLiteral(path)] = arguments {
                 let window = access_window_field(ctx);
@@ -2490,6 +2621,29 @@ fn compile_builtin_function_call(
                 panic!("internal error: invalid args to ImplicitLayoutInfo {:?}", arguments)
             }
         }
+        BuiltinFunction::TextContentSize(orient) => {
+            if let [llr::Expression::PropertyReference(pr)] = arguments {
+                format!(
+                    "slint::cbindgen_private::slint_text_content_size(&{i}, {o}, &{window})",
+                    i = access_member(pr, ctx),
+                    o = to_cpp_orientation(orient),
+                    window = access_window_field(ctx)
+                )
+            } else {
+                panic!("internal error: invalid args to TextContentSize {:?}", arguments)
+            }
+        }
+        BuiltinFunction::TextTruncated => {
+            if let [llr::Expression::PropertyReference(pr)] = arguments {
+                format!(
+                    "slint::cbindgen_private::slint_text_truncated(&{i}, &{window})",
+                    i = access_member(pr, ctx),
+                    window = access_window_field(ctx)
+                )
+            } else {
+                panic!("internal error: invalid args to TextTruncated {:?}", arguments)
+            }
+        }
     }
 }
 
@@ -331,13 +331,42 @@ fn generate_public_component(llr: &llr::PublicComponent) -> TokenStream {
         llr.globals.iter().map(|g| format_ident!("global_{}", ident(&g.name))).collect::<Vec<_>>();
     let global_types = llr.globals.iter().map(global_inner_name).collect::<Vec<_>>();
 
+    let builder_id = format_ident!("{}Builder", public_component_id);
+    // A property called `build` would conflict with the `build` function of the builder
+    let (builder_props, builder_types): (Vec<_>, Vec<_>) = llr
+        .public_properties
+        .iter()
+        .filter(|(p, (ty, _))| !matches!(ty, Type::Callback { .. }) && p.as_str() != "build")
+        .map(|(p, (ty, _))| (ident(p), rust_primitive_type(ty).unwrap()))
+        .unzip();
+    let builder_setters =
+        builder_props.iter().map(|p| format_ident!("set_{}", p)).collect::<Vec<_>>();
+
     quote!(
         #component
         pub struct #public_component_id(vtable::VRc<slint::private_unstable_api::re_exports::ComponentVTable, #inner_component_id>);
 
         impl #public_component_id {
             pub fn new() -> Self {
-                let inner = #inner_component_id::new();
+                let inner = #inner_component_id::new(Default::default());
+                #(inner.globals.#global_names.clone().init(&inner);)*
+                Self(inner)
+            }
+
+            /// Returns a builder to create a new instance of this component with initial values
+            /// for its properties. The values are set before the window is shown, so that it never
+            /// shows the default values.
+            #[allow(dead_code)]
+            pub fn builder() -> #builder_id {
+                Default::default()
+            }
+
+            /// Creates a new instance of this component, with its own window, which shares the global
+            /// singletons with `other`. Use this to show several windows of the same application.
+            ///
+            /// The new instance holds a reference to the globals, so `other` can be dropped before it.
+            pub fn new_sharing_globals_with(other: &Self) -> Self {
+                let inner = #inner_component_id::new(other.0.globals.clone());
                 #(inner.globals.#global_names.clone().init(&inner);)*
                 Self(inner)
             }
@@ -345,6 +374,35 @@ pub fn new() -> Self {
             #property_and_callback_accessors
         }
 
+        /// Creates an instance of the component with initial values for its properties.
+        /// Returned by the `builder()` function of the component.
+        #[derive(Default)]
+        #[must_use]
+        pub struct #builder_id {
+            #(#builder_props: Option<#builder_types>,)*
+        }
+
+        impl #builder_id {
+            #(
+                #[allow(dead_code)]
+                pub fn #builder_props(mut self, value: #builder_types) -> Self {
+                    self.#builder_props = Some(value);
+                    self
+                }
+            )*
+
+            /// Creates the instance of the component and sets the properties.
+            pub fn build(self) -> #public_component_id {
+                let instance = #public_component_id::new();
+                #(
+                    if let Some(value) = self.#builder_props {
+                        instance.#builder_setters(value);
+                    }
+                )*
+                instance
+            }
+        }
+
         impl From<#public_component_id> for vtable::VRc<slint::private_unstable_api::re_exports::ComponentVTable, #inner_component_id> {
             fn from(value: #public_component_id) -> Self {
                 value.0
@@ -389,6 +447,7 @@ fn global<'a, T: slint::Global<'a, Self>>(&'a self) -> T {
         }
 
         #[allow(dead_code)] // FIXME: some global are unused because of optimization, we should then remove them completely
+        #[derive(Clone)]
         struct #global_container_id {
             #(#global_names : ::core::pin::Pin<slint::private_unstable_api::re_exports::Rc<#global_types>>,)*
         }
@@ -552,6 +611,20 @@ pub fn #on_ident(&self, mut f: impl FnMut(#(#callback_args),*) -> #return_type +
                     )
                 }
             ));
+            if matches!(ty, Type::Callback { return_type: None, .. }) {
+                let on_async_ident = format_ident!("on_{}_async", prop_ident);
+                property_and_callback_accessors.push(quote!(
+                    #[allow(dead_code)]
+                    pub fn #on_async_ident<Fut: core::future::Future<Output = ()> + 'static>(
+                        &self,
+                        mut f: impl FnMut(#(#callback_args),*) -> Fut + 'static,
+                    ) {
+                        self.#on_ident(move |#(#args_name),*| {
+                            slint::private_unstable_api::spawn_callback_future(f(#(#args_name),*));
+                        })
+                    }
+                ));
+            }
         } else {
             let rust_property_type = rust_primitive_type(ty).unwrap();
 
@@ -1037,11 +1110,7 @@ fn generate_global(global: &llr::GlobalComponent, root: &llr::PublicComponent) -
             concat!(stringify!(#inner_component_id), ".", stringify!(#declared_property_vars)).into());)*
     ));
 
-    let ctx = EvaluationContext::new_global(
-        root,
-        global,
-        quote!(_self.root.get().unwrap().upgrade().unwrap()),
-    );
+    let ctx = EvaluationContext::new_global(root, global, quote!(_self.root()));
 
     for (property_index, expression) in global.init_values.iter().enumerate() {
         if global.properties[property_index].use_count.get() == 0 {
@@ -1102,7 +1171,8 @@ fn get(component: &'a #root_component_id) -> Self {
         struct #inner_component_id {
             #(#declared_property_vars: slint::private_unstable_api::re_exports::Property<#declared_property_types>,)*
             #(#declared_callbacks: slint::private_unstable_api::re_exports::Callback<(#(#declared_callbacks_types,)*), #declared_callbacks_ret>,)*
-            root : slint::private_unstable_api::re_exports::OnceCell<slint::private_unstable_api::re_exports::VWeak<slint::private_unstable_api::re_exports::ComponentVTable, #root_component_id>>,
+            // The instances of the component using this global, when it is shared between several of them
+            roots : ::core::cell::RefCell<slint::private_unstable_api::re_exports::Vec<slint::private_unstable_api::re_exports::VWeak<slint::private_unstable_api::re_exports::ComponentVTable, #root_component_id>>>,
         }
 
         impl #inner_component_id {
@@ -1111,11 +1181,25 @@ fn new() -> ::core::pin::Pin<slint::private_unstable_api::re_exports::Rc<Self>>
             }
             fn init(self: ::core::pin::Pin<slint::private_unstable_api::re_exports::Rc<Self>>, root: &slint::private_unstable_api::re_exports::VRc<slint::private_unstable_api::re_exports::ComponentVTable, #root_component_id>) {
                 #![allow(unused)]
-                self.root.set(VRc::downgrade(root));
+                let first_root = {
+                    let mut roots = self.roots.borrow_mut();
+                    let first_root = roots.is_empty();
+                    roots.retain(|r| r.upgrade().is_some());
+                    roots.push(VRc::downgrade(root));
+                    first_root
+                };
+                if !first_root {
+                    return;
+                }
                 let self_rc = self;
                 let _self = self_rc.as_ref();
                 #(#init)*
             }
+            /// The first instance using this global that is still alive, which provides the window
+            /// to the bindings of the global
+            fn root(&self) -> slint::private_unstable_api::re_exports::VRc<slint::private_unstable_api::re_exports::ComponentVTable, #root_component_id> {
+                self.roots.borrow().iter().find_map(|r| r.upgrade()).unwrap()
+            }
         }
 
         #public_interface
@@ -1147,6 +1231,10 @@ fn generate_item_tree(
     } else {
         quote!(&self_rc)
     };
+    // The root component is created with its globals, which may be shared with another instance
+    let globals_type = parent_ctx
+        .is_none()
+        .then(|| format_ident!("Globals_{}", public_component_id(&root.item_tree.root)));
     let (create_window_adapter, init_window) = if parent_ctx.is_none() {
         (
             Some(
@@ -1230,7 +1318,7 @@ fn generate_item_tree(
         #sub_comp
 
         impl #inner_component_id {
-            pub fn new(#(parent: #parent_component_type)*)
+            pub fn new(#(parent: #parent_component_type)* #(globals: #globals_type)*)
                 -> vtable::VRc<slint::private_unstable_api::re_exports::ComponentVTable, Self>
             {
                 #![allow(unused)]
@@ -1238,6 +1326,7 @@ pub fn new(#(parent: #parent_component_type)*)
                 #create_window_adapter // We must create the window first to initialize the backend before using the style
                 let mut _self = Self::default();
                 #(_self.parent = parent.clone() as #parent_component_type;)*
+                #(_self.globals = globals as #globals_type;)*
                 let self_rc = VRc::new(_self);
                 let _self = self_rc.as_pin_ref();
                 #init_window
@@ -2038,8 +2127,52 @@ fn compile_builtin_function_call(
                 panic!("internal error: invalid args to SetFocusItem {:?}", arguments)
             }
         }
+        BuiltinFunction::EnsureItemVisible => {
+            if let [Expression::PropertyReference(pr)] = arguments {
+                let item = access_item_rc(pr, ctx);
+                quote!(
+                    slint::private_unstable_api::re_exports::ensure_item_visible(#item);
+                )
+            } else {
+                panic!("internal error: invalid args to EnsureItemVisible {:?}", arguments)
+            }
+        }
+        BuiltinFunction::FlickableScrollTo => {
+            if let [Expression::PropertyReference(pr), x, y] = arguments {
+                let item = access_member(pr, ctx);
+                let x = compile_expression(x, ctx);
+                let y = compile_expression(y, ctx);
+                quote!(
+                    #item.scroll_to(
+                        slint::private_unstable_api::re_exports::LogicalLength::new(#x as _),
+                        slint::private_unstable_api::re_exports::LogicalLength::new(#y as _),
+                    );
+                )
+            } else {
+                panic!("internal error: invalid args to FlickableScrollTo {:?}", arguments)
+            }
+        }
+        BuiltinFunction::ToastQueuePush => {
+            if let [Expression::PropertyReference(pr), text, action_text, duration] = arguments {
+                let item = access_member(pr, ctx);
+                let text = compile_expression(text, ctx);
+                let action_text = compile_expression(action_text, ctx);
+                let duration = compile_expression(duration, ctx);
+                quote!(#item.push(#text, #action_text, #duration as _);)
+            } else {
+                panic!("internal error: invalid args to ToastQueuePush {:?}", arguments)
+            }
+        }
+        BuiltinFunction::ToastQueueDismiss => {
+            if let [Expression::PropertyReference(pr)] = arguments {
+                let item = access_member(pr, ctx);
+                quote!(#item.dismiss();)
+            } else {
+                panic!("internal error: invalid args to ToastQueueDismiss {:?}", arguments)
+            }
+        }
         BuiltinFunction::ShowPopupWindow => {
-            if let [Expression::NumberLiteral(popup_index), x, y, Expression::PropertyReference(parent_ref)] =
+            if let [Expression::NumberLiteral(popup_index), x, y, close_on_click, close_on_click_outside, close_on_escape, modal, Expression::PropertyReference(parent_ref), Expression::PropertyReference(anchor_ref)] =
                 arguments
             {
                 let mut parent_ctx = ctx;
@@ -2055,21 +2188,37 @@ fn compile_builtin_function_call(
                 let popup_window_id = inner_component_id(
                     &current_sub_component.popup_windows[*popup_index as usize].root,
                 );
-                let parent_component = access_item_rc(parent_ref, ctx);
+                let anchor_item = access_item_rc(anchor_ref, ctx);
                 let x = compile_expression(x, ctx);
                 let y = compile_expression(y, ctx);
+                let close_on_click = compile_expression(close_on_click, ctx);
+                let close_on_click_outside = compile_expression(close_on_click_outside, ctx);
+                let close_on_escape = compile_expression(close_on_escape, ctx);
+                let modal = compile_expression(modal, ctx);
                 let window_adapter_tokens = access_window_adapter_field(ctx);
                 quote!(
                     slint::private_unstable_api::re_exports::WindowInner::from_pub(#window_adapter_tokens.window()).show_popup(
                         &VRc::into_dyn(#popup_window_id::new(#component_access_tokens.self_weak.get().unwrap().clone()).into()),
                         Point::new(#x as slint::private_unstable_api::re_exports::Coord, #y as slint::private_unstable_api::re_exports::Coord),
-                        #parent_component
+                        slint::private_unstable_api::re_exports::PopupWindowOptions {
+                            close_on_click: #close_on_click,
+                            close_on_click_outside: #close_on_click_outside,
+                            close_on_escape: #close_on_escape,
+                            modal: #modal,
+                        },
+                        #anchor_item
                     );
                 )
             } else {
                 panic!("internal error: invalid args to ShowPopupWindow {:?}", arguments)
             }
         }
+        BuiltinFunction::ClosePopupWindow => {
+            let window_adapter_tokens = access_window_adapter_field(ctx);
+            quote!(
+                slint::private_unstable_api::re_exports::WindowInner::from_pub(#window_adapter_tokens.window()).close_popup()
+            )
+        }
         BuiltinFunction::ImplicitLayoutInfo(orient) => {
             if let [Expression::PropertyReference(pr)] = arguments {
                 let item = access_member(pr, ctx);
@@ -2081,6 +2230,24 @@ fn compile_builtin_function_call(
                 panic!("internal error: invalid args to ImplicitLayoutInfo {:?}", arguments)
             }
         }
+        BuiltinFunction::TextContentSize(orient) => {
+            if let [Expression::PropertyReference(pr)] = arguments {
+                let item = access_member(pr, ctx);
+                let window_adapter_tokens = access_window_adapter_field(ctx);
+                quote!(#item.content_size(#orient, #window_adapter_tokens).get())
+            } else {
+                panic!("internal error: invalid args to TextContentSize {:?}", arguments)
+            }
+        }
+        BuiltinFunction::TextTruncated => {
+            if let [Expression::PropertyReference(pr)] = arguments {
+                let item = access_member(pr, ctx);
+                let window_adapter_tokens = access_window_adapter_field(ctx);
+                quote!(#item.is_truncated(#window_adapter_tokens))
+            } else {
+                panic!("internal error: invalid args to TextTruncated {:?}", arguments)
+            }
+        }
         BuiltinFunction::RegisterCustomFontByPath => {
             if let [Expression::StringLiteral(path)] = arguments {
                 let window_adapter_tokens = access_window_adapter_field(ctx);
@@ -2116,6 +2283,9 @@ fn compile_builtin_function_call(
         BuiltinFunction::AnimationTick => {
             quote!(slint::private_unstable_api::re_exports::animation_tick())
         }
+        BuiltinFunction::ReducedMotion => {
+            quote!(slint::private_unstable_api::re_exports::reduced_motion())
+        }
         BuiltinFunction::Debug => quote!(slint::private_unstable_api::debug(#(#a)*)),
         BuiltinFunction::Mod => quote!((#(#a as f64)%*)),
         BuiltinFunction::Round => quote!((#(#a)* as f64).round()),
@@ -2141,6 +2311,13 @@ fn compile_builtin_function_call(
             quote!(#(#a)*.as_str().parse::<f64>().unwrap_or_default())
         }
         BuiltinFunction::StringIsFloat => quote!(#(#a)*.as_str().parse::<f64>().is_ok()),
+        BuiltinFunction::StringToLowercase => {
+            quote!(slint::private_unstable_api::re_exports::SharedString::from(#(#a)*.as_str().to_lowercase()))
+        }
+        BuiltinFunction::StringContains => {
+            let (a1, a2) = (a.next().unwrap(), a.next().unwrap());
+            quote!(#a1.as_str().contains(#a2.as_str()))
+        }
         BuiltinFunction::ColorBrighter => {
             let x = a.next().unwrap();
             let factor = a.next().unwrap();
@@ -2152,6 +2329,10 @@ fn compile_builtin_function_call(
             quote!(#x.darker(#factor as f32))
         }
         BuiltinFunction::ImageSize => quote!( #(#a)*.size()),
+        BuiltinFunction::LoadImageInBackground => {
+            let (path, placeholder) = (a.next().unwrap(), a.next().unwrap());
+            quote!(slint::private_unstable_api::re_exports::load_image_from_path_in_background(&#path, #placeholder))
+        }
         BuiltinFunction::ArrayLength => {
             quote!(match &#(#a)* { x => {
                 x.model_tracker().track_row_count_changes();
@@ -2172,7 +2353,7 @@ fn compile_builtin_function_call(
         }
         BuiltinFunction::DarkColorScheme => {
             let window_adapter_tokens = access_window_adapter_field(ctx);
-            quote!(#window_adapter_tokens.dark_color_scheme())
+            quote!(slint::private_unstable_api::re_exports::WindowInner::from_pub(#window_adapter_tokens.window()).dark_color_scheme())
         }
     }
 }
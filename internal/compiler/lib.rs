@@ -12,6 +12,7 @@
 use core::future::Future;
 use core::pin::Pin;
 use std::cell::RefCell;
+use std::collections::HashMap;
 use std::rc::Rc;
 
 pub mod builtin_macros;
@@ -35,6 +36,8 @@
 
 mod passes;
 
+pub use passes::resolve_standalone_expression;
+
 /// Specify how the resources are embedded by the compiler
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum EmbedResourcesKind {
@@ -46,6 +49,52 @@ pub enum EmbedResourcesKind {
     EmbedTextures,
 }
 
+/// Restrictions on the files that the .slint code may access, to compile code that is not trusted.
+#[derive(Clone, Debug, Default)]
+pub struct ResourceSandbox {
+    /// The directories, including their sub-directories, in which the imported files, the fonts and
+    /// the images must be. The files built into the compiler, such as the widgets of the styles,
+    /// are always allowed.
+    pub allowed_directories: Vec<std::path::PathBuf>,
+    /// The maximum width and height, in pixels, of the images referenced with `@image-url`.
+    /// Vector images are not limited, since they are rendered at the size they are displayed at.
+    /// An image whose size can't be read from its file is rejected. The interpreter also applies
+    /// this limit when it decodes the images at run-time.
+    pub max_image_size: Option<(u32, u32)>,
+}
+
+impl ResourceSandbox {
+    /// Returns true if the file at `path` is in one of the allowed directories.
+    ///
+    /// The `..` components of the path are resolved without accessing the file system, so that
+    /// they can't be used to escape from the allowed directories.
+    pub fn allows_path(&self, path: &std::path::Path) -> bool {
+        use std::path::Component;
+        if path.starts_with("builtin:") {
+            return true;
+        }
+        if path.to_string_lossy().contains("://") {
+            return false;
+        }
+        let normalize = |path: &std::path::Path| {
+            let path = dunce::canonicalize(path).unwrap_or_else(|_| path.to_owned());
+            let mut normalized = std::path::PathBuf::new();
+            for component in path.components() {
+                match component {
+                    Component::ParentDir => {
+                        normalized.pop();
+                    }
+                    Component::CurDir => {}
+                    component => normalized.push(component),
+                }
+            }
+            normalized
+        };
+        let path = normalize(path);
+        self.allowed_directories.iter().any(|dir| path.starts_with(normalize(dir)))
+    }
+}
+
 /// CompilationConfiguration allows configuring different aspects of the compiler.
 #[derive(Clone)]
 pub struct CompilerConfiguration {
@@ -54,8 +103,17 @@ pub struct CompilerConfiguration {
     pub embed_resources: EmbedResourcesKind,
     /// The compiler will look in these paths for components used in the file to compile.
     pub include_paths: Vec<std::path::PathBuf>,
+    /// The paths of the libraries, by name. The files imported with `@name/file.slint` are looked
+    /// up in the directory of the library `name`. A library path can also be a file, which is then
+    /// imported with `@name`.
+    pub library_paths: HashMap<String, std::path::PathBuf>,
     /// the name of the style. (eg: "native")
     pub style: Option<String>,
+    /// The style used instead of the `native` style when the native style is not available,
+    /// for example because Qt was not found. When `None`, the `native` style is used as is.
+    ///
+    /// See [`native_style_lookalike`]
+    pub native_style_fallback: Option<String>,
 
     /// Callback to load import files which is called if the file could not be found
     ///
@@ -76,6 +134,35 @@ pub struct CompilerConfiguration {
 
     /// expose the accessible role and properties
     pub accessibility: bool,
+
+    /// When set, restricts the files that the code may access
+    pub sandbox: Option<ResourceSandbox>,
+
+    /// The C++ namespace in which the C++ code generator puts the generated types, instead of
+    /// the global namespace.
+    pub cpp_namespace: Option<String>,
+
+    /// When set, the compilations that share this cache read and parse the files they import
+    /// only once.
+    pub parsed_files_cache: Option<Rc<typeloader::ParsedFilesCache>>,
+}
+
+/// Returns the builtin style that looks the most like the native widgets of the given operating
+/// system, named like `std::env::consts::OS` or `CARGO_CFG_TARGET_OS`: `cupertino` on macOS and
+/// iOS, `fluent` on Windows, and `material` on the other systems.
+///
+/// The `SLINT_NATIVE_STYLE_FALLBACK` environment variable overrides this selection.
+pub fn native_style_lookalike(target_os: &str) -> String {
+    if let Some(style) = std::env::var("SLINT_NATIVE_STYLE_FALLBACK").ok().filter(|s| !s.is_empty())
+    {
+        return style;
+    }
+    match target_os {
+        "macos" | "ios" => "cupertino",
+        "windows" => "fluent",
+        _ => "material",
+    }
+    .into()
 }
 
 impl CompilerConfiguration {
@@ -119,11 +206,16 @@ pub fn new(output_format: crate::generator::OutputFormat) -> Self {
         Self {
             embed_resources,
             include_paths: Default::default(),
+            library_paths: Default::default(),
             style: Default::default(),
+            native_style_fallback: Default::default(),
             open_import_fallback: Default::default(),
             inline_all_elements,
             scale_factor,
             accessibility: true,
+            sandbox: None,
+            cpp_namespace: None,
+            parsed_files_cache: None,
         }
     }
 }
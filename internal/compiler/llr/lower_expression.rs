@@ -117,6 +117,13 @@ pub fn lower_expression(
             tree_Expression::BuiltinFunctionReference(BuiltinFunction::ShowPopupWindow, _) => {
                 lower_show_popup(arguments, ctx)
             }
+            tree_Expression::BuiltinFunctionReference(BuiltinFunction::ClosePopupWindow, _) => {
+                // Only one popup can be shown at a time, so the element isn't needed
+                llr_Expression::BuiltinFunctionCall {
+                    function: BuiltinFunction::ClosePopupWindow,
+                    arguments: vec![],
+                }
+            }
             tree_Expression::BuiltinFunctionReference(f, _) => {
                 let arguments = arguments.iter().map(|e| lower_expression(e, ctx)).collect::<_>();
                 llr_Expression::BuiltinFunctionCall { function: *f, arguments }
@@ -342,15 +349,27 @@ fn lower_show_popup(args: &[tree_Expression], ctx: &ExpressionContext) -> llr_Ex
             .enumerate()
             .find(|(_, p)| Rc::ptr_eq(&p.component, &pop_comp))
             .unwrap();
-        let x = llr_Expression::PropertyReference(ctx.map_property_reference(&popup.x));
-        let y = llr_Expression::PropertyReference(ctx.map_property_reference(&popup.y));
+        let property =
+            |nr: &NamedReference| llr_Expression::PropertyReference(ctx.map_property_reference(nr));
         let item_ref = lower_expression(
             &tree_Expression::ElementReference(Rc::downgrade(&popup.parent_element)),
             ctx,
         );
+        let anchor_ref =
+            lower_expression(&tree_Expression::ElementReference(Rc::downgrade(&popup.anchor)), ctx);
         llr_Expression::BuiltinFunctionCall {
             function: BuiltinFunction::ShowPopupWindow,
-            arguments: vec![llr_Expression::NumberLiteral(popup_index as _), x, y, item_ref],
+            arguments: vec![
+                llr_Expression::NumberLiteral(popup_index as _),
+                property(&popup.x),
+                property(&popup.y),
+                property(&popup.close_on_click),
+                property(&popup.close_on_click_outside),
+                property(&popup.close_on_escape),
+                property(&popup.modal),
+                item_ref,
+                anchor_ref,
+            ],
         }
     } else {
         panic!("invalid arguments to ShowPopupWindow");
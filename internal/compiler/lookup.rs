@@ -680,6 +680,16 @@ fn for_each_entry<R>(
                     .into(),
                 )
             })
+            .or_else(|| {
+                f(
+                    "reduced-motion",
+                    Expression::BuiltinFunctionReference(
+                        BuiltinFunction::ReducedMotion,
+                        ctx.current_token.as_ref().map(|t| t.to_source_location()),
+                    )
+                    .into(),
+                )
+            })
     }
 }
 
@@ -790,6 +800,8 @@ fn for_each_entry<R>(
         };
         None.or_else(|| f("is-float", member_function(BuiltinFunction::StringIsFloat)))
             .or_else(|| f("to-float", member_function(BuiltinFunction::StringToFloat)))
+            .or_else(|| f("to-lowercase", member_function(BuiltinFunction::StringToLowercase)))
+            .or_else(|| f("contains", member_function(BuiltinFunction::StringContains)))
     }
 }
 struct ColorExpression<'a>(&'a Expression);
@@ -9,7 +9,7 @@
 
 use itertools::Either;
 
-use crate::diagnostics::{BuildDiagnostics, SourceLocation, Spanned};
+use crate::diagnostics::{BuildDiagnostics, DiagnosticCode, SourceLocation, Spanned};
 use crate::expression_tree::{self, BindingExpression, Expression, Unit};
 use crate::langtype::{BuiltinElement, NativeClass, Type};
 use crate::langtype::{ElementType, PropertyLookupResult};
@@ -178,7 +178,26 @@ pub struct PopupWindow {
     pub component: Rc<Component>,
     pub x: NamedReference,
     pub y: NamedReference,
+    pub close_on_click: NamedReference,
+    pub close_on_click_outside: NamedReference,
+    pub close_on_escape: NamedReference,
+    pub modal: NamedReference,
     pub parent_element: ElementRc,
+    /// The element the popup is positioned relative to: the `anchor` of the popup, or its parent element
+    pub anchor: ElementRc,
+}
+
+impl PopupWindow {
+    /// Visit the references to the properties of the parent element that hold the position and
+    /// the options of the popup
+    pub fn visit_named_references(&mut self, vis: &mut impl FnMut(&mut NamedReference)) {
+        vis(&mut self.x);
+        vis(&mut self.y);
+        vis(&mut self.close_on_click);
+        vis(&mut self.close_on_click_outside);
+        vis(&mut self.close_on_escape);
+        vis(&mut self.modal);
+    }
 }
 
 type ChildrenInsertionPoint = (ElementRc, syntax_nodes::ChildrenPlaceholder);
@@ -240,6 +259,9 @@ pub struct Component {
 
     /// True when this component was declared with the `:=` symbol instead of the `component` keyword
     pub is_legacy_syntax: bool,
+
+    /// The `///` doc comment preceding the component declaration
+    pub doc_comment: Option<String>,
 }
 
 impl Component {
@@ -265,6 +287,11 @@ pub fn from_node(
             ),
             child_insertion_point: RefCell::new(child_insertion_point),
             is_legacy_syntax: node.child_token(SyntaxKind::ColonEqual).is_some(),
+            doc_comment: node
+                .parent()
+                .filter(|p| p.kind() == SyntaxKind::ExportsList)
+                .and_then(|p| p.doc_comment())
+                .or_else(|| node.doc_comment()),
             ..Default::default()
         };
         let c = Rc::new(c);
@@ -370,6 +397,11 @@ pub fn type_node(&self) -> Option<SyntaxNode> {
             )
         })
     }
+
+    /// The `///` doc comment preceding the declaration of this property or callback
+    pub fn doc_comment(&self) -> Option<String> {
+        self.node.as_ref().and_then(|x| x.as_ref().either(|x| x.doc_comment(), |x| x.doc_comment()))
+    }
 }
 
 impl From<Type> for PropertyDeclaration {
@@ -1338,7 +1370,11 @@ pub fn type_from_node(
         let prop_type = tr.lookup_qualified(&qualified_type.members);
 
         if prop_type == Type::Invalid && tr.lookup_element(&qualified_type.to_string()).is_err() {
-            diag.push_error(format!("Unknown type '{}'", qualified_type), &qualified_type_node);
+            diag.push_error_with_code(
+                format!("Unknown type '{}'", qualified_type),
+                DiagnosticCode::UnknownType,
+                &qualified_type_node,
+            );
         } else if !prop_type.is_property_type() {
             diag.push_error(
                 format!("'{}' is not a valid type", qualified_type),
@@ -1765,10 +1801,11 @@ pub fn visit_all_named_references(
             if !Weak::ptr_eq(parent_compo, &compo) {
                 let compo = compo.upgrade().unwrap();
                 compo.root_constraints.borrow_mut().visit_named_references(vis);
-                compo.popup_windows.borrow_mut().iter_mut().for_each(|p| {
-                    vis(&mut p.x);
-                    vis(&mut p.y);
-                });
+                compo
+                    .popup_windows
+                    .borrow_mut()
+                    .iter_mut()
+                    .for_each(|p| p.visit_named_references(vis));
             }
             compo
         },
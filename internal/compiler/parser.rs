@@ -638,14 +638,18 @@ fn consume(&mut self) {
     fn error(&mut self, e: impl Into<String>) {
         let current_token = self.current_token();
         #[allow(unused_mut)]
-        let mut span = crate::diagnostics::Span::new(current_token.offset);
+        let mut span = crate::diagnostics::Span::new_with_length(
+            current_token.offset,
+            current_token.text.len(),
+        );
         #[cfg(feature = "proc_macro_span")]
         {
             span.span = current_token.span;
         }
 
-        self.diags.push_error_with_span(
+        self.diags.push_error_with_span_and_code(
             e.into(),
+            crate::diagnostics::DiagnosticCode::Syntax,
             crate::diagnostics::SourceLocation {
                 source_file: Some(self.source_file.clone()),
                 span,
@@ -714,6 +718,21 @@ pub fn next_token(&self) -> Option<SyntaxToken> {
             })?;
         Some(SyntaxToken { token, source_file: self.source_file.clone() })
     }
+    pub fn prev_token(&self) -> Option<SyntaxToken> {
+        // Same as next_token: rowan::SyntaxToken::prev_token doesn't skip the nodes without tokens
+        let mut element: rowan::SyntaxElement<Language> = self.token.clone().into();
+        let token = loop {
+            match element.prev_sibling_or_token() {
+                Some(rowan::NodeOrToken::Token(t)) => break t,
+                Some(rowan::NodeOrToken::Node(n)) => match n.last_token() {
+                    Some(t) => break t,
+                    None => element = n.into(),
+                },
+                None => element = element.parent()?.into(),
+            }
+        };
+        Some(SyntaxToken { token, source_file: self.source_file.clone() })
+    }
 }
 
 impl std::fmt::Display for SyntaxToken {
@@ -771,6 +790,35 @@ pub fn first_token(&self) -> Option<SyntaxToken> {
             .first_token()
             .map(|token| SyntaxToken { token, source_file: self.source_file.clone() })
     }
+    /// Returns the content of the `///` comments directly preceding this node, without the
+    /// slashes, or None if there are none. A blank line or any other comment ends the doc comment.
+    pub fn doc_comment(&self) -> Option<String> {
+        let first = self.node.descendants_with_tokens().find_map(|e| {
+            e.into_token()
+                .filter(|t| !matches!(t.kind(), SyntaxKind::Whitespace | SyntaxKind::Comment))
+        })?;
+        let mut token = SyntaxToken { token: first, source_file: self.source_file.clone() };
+        let mut lines = Vec::new();
+        while let Some(prev) = token.prev_token() {
+            match prev.kind() {
+                SyntaxKind::Whitespace if prev.text().matches('\n').count() > 1 => break,
+                SyntaxKind::Whitespace => (),
+                SyntaxKind::Comment => match prev.text().strip_prefix("///") {
+                    Some(line) if !line.starts_with('/') => {
+                        lines.push(line.strip_prefix(' ').unwrap_or(line).trim_end().to_owned())
+                    }
+                    _ => break,
+                },
+                _ => break,
+            }
+            token = prev;
+        }
+        if lines.is_empty() {
+            return None;
+        }
+        lines.reverse();
+        Some(lines.join("\n"))
+    }
 }
 
 #[derive(Debug, Clone, derive_more::From)]
@@ -818,7 +866,8 @@ pub fn into_node(self) -> Option<SyntaxNode> {
 
 impl Spanned for SyntaxNode {
     fn span(&self) -> crate::diagnostics::Span {
-        crate::diagnostics::Span::new(self.node.text_range().start().into())
+        let range = self.node.text_range();
+        crate::diagnostics::Span::new_with_length(range.start().into(), range.len().into())
     }
 
     fn source_file(&self) -> Option<&SourceFile> {
@@ -838,7 +887,8 @@ fn source_file(&self) -> Option<&SourceFile> {
 
 impl Spanned for SyntaxToken {
     fn span(&self) -> crate::diagnostics::Span {
-        crate::diagnostics::Span::new(self.token.text_range().start().into())
+        let range = self.token.text_range();
+        crate::diagnostics::Span::new_with_length(range.start().into(), range.len().into())
     }
 
     fn source_file(&self) -> Option<&SourceFile> {
@@ -912,6 +962,30 @@ pub fn parse(
     SyntaxNode { node: rowan::SyntaxNode::new_root(p.builder.finish()), source_file }
 }
 
+/// Parses a single expression, such as `foo.width * 2`, that is not part of a document.
+///
+/// The returned node is a `BindingExpression` that contains the `Expression`.
+pub fn parse_standalone_expression(
+    source: String,
+    build_diagnostics: &mut BuildDiagnostics,
+) -> SyntaxNode {
+    let mut p = DefaultParser::new(&source, build_diagnostics);
+    {
+        let mut p = p.start_node(SyntaxKind::BindingExpression);
+        expressions::parse_expression(&mut *p);
+        if p.nth(0).kind() != SyntaxKind::Eof {
+            p.error("Syntax error: expected the end of the expression");
+            while p.nth(0).kind() != SyntaxKind::Eof {
+                p.consume();
+            }
+        }
+    }
+    SyntaxNode {
+        node: rowan::SyntaxNode::new_root(p.builder.finish()),
+        source_file: Default::default(),
+    }
+}
+
 pub fn parse_file<P: AsRef<std::path::Path>>(
     path: P,
     build_diagnostics: &mut BuildDiagnostics,
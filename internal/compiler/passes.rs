@@ -3,10 +3,11 @@
 
 mod apply_default_properties_from_style;
 mod binding_analysis;
+mod border_radius;
 mod check_aliases;
 mod check_expressions;
 mod check_public_api;
-mod check_rotation;
+mod check_shortcuts;
 mod clip;
 mod collect_custom_fonts;
 mod collect_globals;
@@ -22,6 +23,7 @@
 mod flickable;
 mod focus_item;
 mod generate_item_indices;
+mod image_placeholder;
 mod infer_aliases_types;
 mod inlining;
 mod lower_accessibility;
@@ -39,10 +41,14 @@
 mod repeater_component;
 mod resolve_native_classes;
 mod resolving;
+mod text_metrics;
 mod unique_id;
+mod viewport_visible;
 mod visible;
 mod z_order;
 
+pub use resolving::resolve_standalone_expression;
+
 use crate::expression_tree::Expression;
 use crate::langtype::ElementType;
 use crate::namedreference::NamedReference;
@@ -77,7 +83,7 @@ pub async fn run_passes(
     infer_aliases_types::resolve_aliases(doc, diag);
     resolving::resolve_expressions(doc, type_loader, diag);
     check_expressions::check_expressions(doc, diag);
-    check_rotation::check_rotation(doc, diag);
+    check_shortcuts::check_shortcuts(doc, diag);
     unique_id::check_unique_id(doc, diag);
     check_public_api::check_public_api(doc, diag);
 
@@ -99,6 +105,11 @@ pub async fn run_passes(
     inlining::inline(doc, inlining::InlineSelection::InlineOnlyRequiredComponents);
     collect_subcomponents::collect_subcomponents(root_component);
 
+    for component in (root_component.used_types.borrow().sub_components.iter())
+        .chain(std::iter::once(root_component))
+    {
+        image_placeholder::handle_image_placeholders(component);
+    }
     embed_images::embed_images(
         root_component,
         compiler_config.embed_resources,
@@ -126,6 +137,7 @@ pub async fn run_passes(
         lower_popups::lower_popups(component, &doc.local_registry, diag);
         lower_layout::lower_layouts(component, type_loader, diag).await;
         default_geometry::default_geometry(component, diag);
+        text_metrics::handle_text_metrics(component);
         z_order::reorder_by_z_order(component, diag);
         lower_property_to_element::lower_property_to_element(
             component,
@@ -145,31 +157,56 @@ pub async fn run_passes(
             &global_type_registry.borrow(),
             diag,
         );
-        lower_shadows::lower_shadow_properties(component, &doc.local_registry, diag);
-        lower_property_to_element::lower_property_to_element(
+        lower_property_to_element::lower_properties_to_element(
+            component,
+            &crate::typeregister::RESERVED_EFFECT_PROPERTIES
+                .iter()
+                .map(|(prop_name, _)| *prop_name)
+                .collect::<Vec<_>>(),
+            None,
+            "Effect",
+            &global_type_registry.borrow(),
+            diag,
+        );
+        lower_property_to_element::lower_properties_to_element(
             component,
-            crate::typeregister::RESERVED_ROTATION_PROPERTIES[0].0,
-            crate::typeregister::RESERVED_ROTATION_PROPERTIES[1..]
+            &crate::typeregister::RESERVED_TRANSFORM_PROPERTIES
                 .iter()
-                .map(|(prop_name, _)| *prop_name),
-            Some(&|e, prop| Expression::BinaryExpression {
-                lhs: Expression::PropertyReference(NamedReference::new(
-                    e,
-                    match prop {
-                        "rotation-origin-x" => "width",
-                        "rotation-origin-y" => "height",
-                        "rotation-angle" => return Expression::Invalid,
-                        _ => unreachable!(),
-                    },
-                ))
-                .into(),
-                op: '/',
-                rhs: Expression::NumberLiteral(2., Default::default()).into(),
+                .map(|(prop_name, _)| *prop_name)
+                .collect::<Vec<_>>(),
+            Some(&|e, prop| {
+                Some(Expression::BinaryExpression {
+                    lhs: Expression::PropertyReference(NamedReference::new(
+                        e,
+                        match prop {
+                            "rotation-origin-x" => "width",
+                            "rotation-origin-y" => "height",
+                            _ => return None,
+                        },
+                    ))
+                    .into(),
+                    op: '/',
+                    rhs: Expression::NumberLiteral(2., Default::default()).into(),
+                })
             }),
-            "Rotate",
+            "Transform",
+            &global_type_registry.borrow(),
+            diag,
+        );
+        lower_shadows::lower_shadow_properties(component, &doc.local_registry, diag);
+        lower_property_to_element::lower_properties_to_element(
+            component,
+            &crate::typeregister::RESERVED_DROP_SHADOW_PROPERTIES
+                .iter()
+                .map(|(prop_name, _)| *prop_name)
+                .collect::<Vec<_>>(),
+            None,
+            "DropShadow",
             &global_type_registry.borrow(),
             diag,
         );
+        viewport_visible::handle_viewport_visible(component, &global_type_registry.borrow(), diag);
+        border_radius::handle_border_radius(component);
         clip::handle_clip(component, &global_type_registry.borrow(), diag);
         visible::handle_visible(component, &global_type_registry.borrow());
         if compiler_config.accessibility {
@@ -263,6 +300,6 @@ pub fn run_import_passes(
     infer_aliases_types::resolve_aliases(doc, diag);
     resolving::resolve_expressions(doc, type_loader, diag);
     check_expressions::check_expressions(doc, diag);
-    check_rotation::check_rotation(doc, diag);
+    check_shortcuts::check_shortcuts(doc, diag);
     unique_id::check_unique_id(doc, diag);
 }
@@ -8,7 +8,7 @@
 use crate::diagnostics::BuildDiagnostics;
 use crate::expression_tree::{Expression, NamedReference};
 use crate::langtype::Type;
-use crate::object_tree::Component;
+use crate::object_tree::{Component, Element};
 use std::rc::Rc;
 
 /// Ideally we would be able to write this in builtin.slint, but the StyleMetrics is not available there
@@ -58,6 +58,13 @@ pub fn apply_default_properties_from_style(
                         .into(),
                         to: Type::Brush,
                     });
+                    apply_tooltip_style(&mut elem, style_metrics);
+                    elem.set_binding_if_not_set("default-font-size".into(), || {
+                        Expression::PropertyReference(NamedReference::new(
+                            &style_metrics.root_element,
+                            "default-font-size",
+                        ))
+                    });
                 }
 
                 _ => {}
@@ -65,3 +72,21 @@ pub fn apply_default_properties_from_style(
         },
     )
 }
+
+/// The tooltip of the window takes the colors of the style, unless they are set on the window
+pub(crate) fn apply_tooltip_style(window: &mut Element, style_metrics: &Rc<Component>) {
+    for (prop, style_prop) in [
+        ("tooltip-background", "window-background"),
+        ("tooltip-color", "default-text-color"),
+        ("tooltip-border-color", "default-text-color"),
+    ] {
+        window.set_binding_if_not_set(prop.into(), || Expression::Cast {
+            from: Expression::PropertyReference(NamedReference::new(
+                &style_metrics.root_element,
+                style_prop,
+            ))
+            .into(),
+            to: Type::Brush,
+        });
+    }
+}
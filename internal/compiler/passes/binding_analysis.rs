@@ -365,12 +365,11 @@ fn recurse_expression(expr: &Expression, vis: &mut impl FnMut(&PropertyPath)) {
                 g.visit_named_references(&mut |nr| vis(&nr.clone().into()))
             }
         }
-        Expression::FunctionCall { function, arguments, .. } => {
-            if let Expression::BuiltinFunctionReference(
+        Expression::FunctionCall { function, arguments, .. } => match &**function {
+            Expression::BuiltinFunctionReference(
                 BuiltinFunction::ImplicitLayoutInfo(orientation),
                 _,
-            ) = &**function
-            {
+            ) => {
                 if let [Expression::ElementReference(item)] = arguments.as_slice() {
                     visit_implicit_layout_info_dependencies(
                         *orientation,
@@ -379,7 +378,16 @@ fn recurse_expression(expr: &Expression, vis: &mut impl FnMut(&PropertyPath)) {
                     );
                 }
             }
-        }
+            Expression::BuiltinFunctionReference(
+                BuiltinFunction::TextContentSize(_) | BuiltinFunction::TextTruncated,
+                _,
+            ) => {
+                if let [Expression::ElementReference(item)] = arguments.as_slice() {
+                    visit_text_metrics_dependencies(&item.upgrade().unwrap(), vis);
+                }
+            }
+            _ => {}
+        },
         _ => {}
     }
 }
@@ -435,6 +443,7 @@ fn visit_implicit_layout_info_dependencies(
             vis(&NamedReference::new(item, "font-size").into());
             vis(&NamedReference::new(item, "font-weight").into());
             vis(&NamedReference::new(item, "letter-spacing").into());
+            vis(&NamedReference::new(item, "word-spacing").into());
             vis(&NamedReference::new(item, "wrap").into());
             let wrap_set = item.borrow().is_binding_set("wrap", false)
                 || item
@@ -457,6 +466,25 @@ fn visit_implicit_layout_info_dependencies(
     }
 }
 
+/// The text metrics are computed by native code from the properties of the Text element
+fn visit_text_metrics_dependencies(item: &ElementRc, vis: &mut impl FnMut(&PropertyPath)) {
+    for prop in [
+        "text",
+        "rich-text",
+        "font-family",
+        "font-size",
+        "font-weight",
+        "letter-spacing",
+        "word-spacing",
+        "wrap",
+        "max-lines",
+        "width",
+        "height",
+    ] {
+        vis(&NamedReference::new(item, prop).into());
+    }
+}
+
 /// Make sure that the is_set property analysis is set to any property which has a two way binding
 /// to a property that is, itself, is set
 ///
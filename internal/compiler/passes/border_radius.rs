@@ -0,0 +1,52 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+//! Pass that makes the per-corner radius properties of the Rectangle default to `border-radius`
+
+use std::rc::Rc;
+
+use crate::expression_tree::{Expression, NamedReference};
+use crate::object_tree::{Component, ElementRc};
+
+const BORDER_CORNER_RADIUS_PROPERTIES: [&str; 4] = [
+    "border-top-left-radius",
+    "border-top-right-radius",
+    "border-bottom-right-radius",
+    "border-bottom-left-radius",
+];
+
+pub fn handle_border_radius(component: &Rc<Component>) {
+    crate::object_tree::recurse_elem_including_sub_components(
+        component,
+        &(),
+        &mut |elem_rc: &ElementRc, _| {
+            let elem = elem_rc.borrow();
+            if elem.builtin_type().map_or(true, |ty| ty.name != "Rectangle") {
+                return;
+            }
+            if !elem.is_binding_set("border-radius", false)
+                && !elem.property_analysis.borrow().get("border-radius").map_or(false, |a| a.is_set)
+            {
+                return;
+            }
+            drop(elem);
+
+            let border_radius =
+                Expression::PropertyReference(NamedReference::new(elem_rc, "border-radius"));
+            for corner in BORDER_CORNER_RADIUS_PROPERTIES {
+                if elem_rc
+                    .borrow()
+                    .property_analysis
+                    .borrow()
+                    .get(corner)
+                    .map_or(false, |a| a.is_set)
+                {
+                    continue;
+                }
+                elem_rc
+                    .borrow_mut()
+                    .set_binding_if_not_set(corner.into(), || border_radius.clone());
+            }
+        },
+    );
+}
@@ -0,0 +1,56 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+//! Check the `keys` of the `Shortcut` elements that are set with a string literal
+
+use crate::diagnostics::BuildDiagnostics;
+use crate::expression_tree::Expression;
+use i_slint_common::key_sequence::{parse_key_sequence, KeySequence};
+
+/// Report the key sequences that can't be parsed, and the window-wide shortcuts of a
+/// component that use the same key sequence
+pub fn check_shortcuts(doc: &crate::object_tree::Document, diag: &mut BuildDiagnostics) {
+    for cmp in &doc.inner_components {
+        let mut window_shortcuts: Vec<KeySequence> = Vec::new();
+        crate::object_tree::recurse_elem(&cmp.root_element, &(), &mut |elem, _| {
+            let e = elem.borrow();
+            if !matches!(e.native_class(), Some(native) if native.class_name == "Shortcut") {
+                return;
+            }
+            let binding = match e.bindings.get("keys") {
+                Some(binding) => binding.borrow(),
+                None => return,
+            };
+            let keys = match &binding.expression {
+                Expression::StringLiteral(keys) => keys,
+                _ => return,
+            };
+            match parse_key_sequence(keys) {
+                Ok(sequence) => {
+                    // Only the shortcuts that are known to be window-wide can conflict
+                    let local = match e.bindings.get("local") {
+                        None => Some(false),
+                        Some(local) => match local.borrow().expression {
+                            Expression::BoolLiteral(local) => Some(local),
+                            _ => None,
+                        },
+                    };
+                    if local != Some(false) {
+                        return;
+                    }
+                    if window_shortcuts.contains(&sequence) {
+                        diag.push_warning(
+                            format!("The shortcut '{}' is already used by another Shortcut", keys),
+                            &*binding,
+                        );
+                    } else {
+                        window_shortcuts.push(sequence);
+                    }
+                }
+                Err(err) => {
+                    diag.push_error(format!("Invalid shortcut '{}': {}", keys, err), &*binding)
+                }
+            }
+        });
+    }
+}
@@ -42,7 +42,13 @@ pub fn compile_paths(
             _ => return,
         };
 
-        let element_types = &accepted_type.additional_accepted_child_types;
+        // Only the non-item types are path elements, the other accepted children remain children
+        let path_element_type = |name: &str| {
+            accepted_type
+                .additional_accepted_child_types
+                .get(name)
+                .filter(|t| matches!(t, ElementType::Builtin(b) if b.is_non_item_type))
+        };
 
         let mut elem = elem_.borrow_mut();
 
@@ -50,8 +56,8 @@ pub fn compile_paths(
             elem.bindings.remove("commands").map(RefCell::into_inner)
         {
             if let Some(path_child) = elem.children.iter().find(|child| {
-                element_types
-                    .contains_key(&child.borrow().base_type.as_builtin().native_class.class_name)
+                path_element_type(&child.borrow().base_type.as_builtin().native_class.class_name)
+                    .is_some()
             }) {
                 diag.push_error(
                     "Path elements cannot be mixed with the use of the SVG commands property"
@@ -93,7 +99,7 @@ pub fn compile_paths(
                 let element_name =
                     &child.borrow().base_type.as_builtin().native_class.class_name.clone();
 
-                if let Some(path_element) = element_types.get(element_name) {
+                if let Some(path_element) = path_element_type(element_name) {
                     let element_type = match path_element {
                         ElementType::Builtin(b) => b.clone(),
                         _ => panic!(
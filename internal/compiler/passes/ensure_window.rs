@@ -104,4 +104,8 @@ pub fn ensure_window(
             to: Type::Brush,
         }
     });
+    super::apply_default_properties_from_style::apply_tooltip_style(
+        &mut component.root_element.borrow_mut(),
+        style_metrics,
+    );
 }
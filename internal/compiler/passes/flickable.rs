@@ -6,15 +6,15 @@
 //! The Flickable element is special in the sense that it has a viewport
 //! which is not exposed. This passes create the viewport and fixes all property access
 //!
-//! It will also initialize proper geometry
+//! It will also initialize proper geometry, and create the overlay scroll bars
 //! This pass must be called before the materialize_fake_properties as it going to be generate
 //! binding reference to fake properties
 
 use std::cell::RefCell;
 use std::rc::Rc;
 
-use crate::expression_tree::{BindingExpression, Expression, NamedReference};
-use crate::langtype::{ElementType, NativeClass};
+use crate::expression_tree::{BindingExpression, Expression, NamedReference, Unit};
+use crate::langtype::{ElementType, NativeClass, Type};
 use crate::object_tree::{Component, Element, ElementRc};
 use crate::typeregister::TypeRegister;
 
@@ -38,6 +38,9 @@ pub fn handle_flickable(root_component: &Rc<Component>, tr: &TypeRegister) {
 
             fixup_geometry(elem);
             create_viewport_element(elem, &native_empty);
+            if elem.borrow().bindings.contains_key("overlay-scrollbars") {
+                create_overlay_scrollbars(elem, tr);
+            }
         },
     )
 }
@@ -84,6 +87,101 @@ fn create_viewport_element(flickable_elem: &ElementRc, native_empty: &Rc<NativeC
     flickable.children.push(viewport);
 }
 
+/// Thickness of the overlay scroll bars
+const SCROLLBAR_THICKNESS: f64 = 6.;
+/// Distance between the overlay scroll bars and the edges of the Flickable
+const SCROLLBAR_MARGIN: f64 = 2.;
+/// Color of the overlay scroll bars (semi-transparent gray, as ARGB)
+const SCROLLBAR_COLOR: u32 = 0x80808080;
+
+/// Adds two Rectangles to the Flickable, above the viewport, that show the scroll position when
+/// `overlay-scrollbars` is true and the viewport is larger than the Flickable.
+fn create_overlay_scrollbars(flickable_elem: &ElementRc, tr: &TypeRegister) {
+    let viewport = flickable_elem.borrow().children.last().unwrap().clone();
+    let prop = |elem: &ElementRc, name: &str| {
+        Expression::PropertyReference(NamedReference::new(elem, name))
+    };
+    let px = |value: f64| Expression::NumberLiteral(value, Unit::Px);
+    let binary = |lhs: Expression, op: char, rhs: Expression| Expression::BinaryExpression {
+        lhs: Box::new(lhs),
+        rhs: Box::new(rhs),
+        op,
+    };
+    let color = |argb: u32| Expression::Cast {
+        from: Box::new(Expression::NumberLiteral(argb as f64, Unit::None)),
+        to: Type::Color,
+    };
+
+    for (orientation, pos, size, cross_pos, cross_size) in
+        [("vertical", "y", "height", "x", "width"), ("horizontal", "x", "width", "y", "height")]
+    {
+        // The length available to the scroll bar, and the length of the content it represents
+        let track = || binary(prop(flickable_elem, size), '-', px(2. * SCROLLBAR_MARGIN));
+        let content = || {
+            crate::builtin_macros::min_max_expression(
+                prop(&viewport, size),
+                prop(flickable_elem, size),
+                '>',
+            )
+        };
+        let bindings = [
+            (
+                pos,
+                binary(
+                    px(SCROLLBAR_MARGIN),
+                    '+',
+                    binary(
+                        binary(
+                            Expression::UnaryOp { sub: Box::new(prop(&viewport, pos)), op: '-' },
+                            '*',
+                            track(),
+                        ),
+                        '/',
+                        content(),
+                    ),
+                ),
+            ),
+            (size, binary(binary(track(), '*', prop(flickable_elem, size)), '/', content())),
+            (
+                cross_pos,
+                binary(
+                    prop(flickable_elem, cross_size),
+                    '-',
+                    px(SCROLLBAR_THICKNESS + SCROLLBAR_MARGIN),
+                ),
+            ),
+            (cross_size, px(SCROLLBAR_THICKNESS)),
+            ("border-radius", px(SCROLLBAR_THICKNESS / 2.)),
+            (
+                "background",
+                Expression::Cast {
+                    from: Box::new(Expression::Condition {
+                        condition: Box::new(binary(
+                            prop(flickable_elem, "overlay-scrollbars"),
+                            '&',
+                            binary(prop(&viewport, size), '>', prop(flickable_elem, size)),
+                        )),
+                        true_expr: Box::new(color(SCROLLBAR_COLOR)),
+                        false_expr: Box::new(color(0)),
+                    }),
+                    to: Type::Brush,
+                },
+            ),
+        ];
+
+        let mut scrollbar = Element {
+            id: format!("{}-{}-scrollbar", flickable_elem.borrow().id, orientation),
+            base_type: tr.lookup_element("Rectangle").unwrap(),
+            enclosing_component: flickable_elem.borrow().enclosing_component.clone(),
+            ..Element::default()
+        };
+        for (name, expression) in bindings {
+            scrollbar.set_binding_if_not_set(name.into(), || expression);
+        }
+        flickable_elem.borrow_mut().children.push(Rc::new(RefCell::new(scrollbar)));
+    }
+}
+
 fn fixup_geometry(flickable_elem: &ElementRc) {
     let forward_minmax_of = |prop: &str, op: char| {
         set_binding_if_not_explicit(flickable_elem, prop, || {
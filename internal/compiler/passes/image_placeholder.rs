@@ -0,0 +1,64 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+//! Pass that handles the `placeholder` property of the Image element
+//!
+//! When an Image has a binding for `placeholder`, the file references in its `source` binding are
+//! replaced by a call to `LoadImageInBackground`, so that the image is decoded by the runtime
+//! outside of the UI thread while the placeholder is shown.
+//! This pass must run before embed_images, since these images are always loaded from the disk.
+
+use crate::expression_tree::{BuiltinFunction, Expression, ImageReference, NamedReference};
+use crate::object_tree::{Component, ElementRc};
+use std::rc::Rc;
+
+pub fn handle_image_placeholders(component: &Rc<Component>) {
+    crate::object_tree::recurse_elem_including_sub_components(
+        component,
+        &(),
+        &mut |elem: &ElementRc, _| {
+            if !is_image_element(elem) || !elem.borrow().bindings.contains_key("placeholder") {
+                return;
+            }
+            let placeholder = NamedReference::new(elem, "placeholder");
+            if let Some(source) = elem.borrow().bindings.get("source") {
+                source
+                    .borrow_mut()
+                    .expression
+                    .visit_recursive_mut(&mut |e| load_in_background(e, &placeholder));
+            }
+        },
+    )
+}
+
+fn is_image_element(elem: &ElementRc) -> bool {
+    let mut native_class = elem.borrow().native_class();
+    while let Some(n) = native_class {
+        if n.class_name == "ImageItem" {
+            return true;
+        }
+        native_class = n.parent.clone();
+    }
+    false
+}
+
+fn load_in_background(e: &mut Expression, placeholder: &NamedReference) {
+    let path =
+        match e {
+            Expression::ImageReference {
+                resource_ref: ImageReference::AbsolutePath(path), ..
+            } if !path.starts_with("builtin:/") => std::mem::take(path),
+            _ => return,
+        };
+    *e = Expression::FunctionCall {
+        function: Box::new(Expression::BuiltinFunctionReference(
+            BuiltinFunction::LoadImageInBackground,
+            None,
+        )),
+        arguments: vec![
+            Expression::StringLiteral(path),
+            Expression::PropertyReference(placeholder.clone()),
+        ],
+        source_location: None,
+    };
+}
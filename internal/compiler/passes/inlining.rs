@@ -171,8 +171,7 @@ fn inline_element(
         visit_element_expressions(e, |expr, _, _| fixup_element_references(expr, &mapping));
     }
     for p in root_component.popup_windows.borrow_mut().iter_mut() {
-        fixup_reference(&mut p.x, &mapping);
-        fixup_reference(&mut p.y, &mapping);
+        p.visit_named_references(&mut |nr| fixup_reference(nr, &mapping));
     }
 }
 
@@ -285,8 +284,7 @@ fn duplicate_sub_component(
         .map(|p| duplicate_popup(p, mapping, priority_delta))
         .collect();
     for p in new_component.popup_windows.borrow_mut().iter_mut() {
-        fixup_reference(&mut p.x, mapping);
-        fixup_reference(&mut p.y, mapping);
+        p.visit_named_references(&mut |nr| fixup_reference(nr, mapping));
     }
     new_component
         .root_constraints
@@ -307,11 +305,20 @@ fn duplicate_popup(
     PopupWindow {
         x: p.x.clone(),
         y: p.y.clone(),
+        close_on_click: p.close_on_click.clone(),
+        close_on_click_outside: p.close_on_click_outside.clone(),
+        close_on_escape: p.close_on_escape.clone(),
+        modal: p.modal.clone(),
         component: duplicate_sub_component(&p.component, &parent, mapping, priority_delta),
         parent_element: mapping
             .get(&element_key(p.parent_element.clone()))
             .expect("Parent element must be in the mapping")
             .clone(),
+        // The anchor may be an element of an enclosing component, which is not duplicated
+        anchor: mapping
+            .get(&element_key(p.anchor.clone()))
+            .cloned()
+            .unwrap_or_else(|| p.anchor.clone()),
     }
 }
 
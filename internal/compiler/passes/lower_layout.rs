@@ -32,6 +32,10 @@ pub async fn lower_layouts(
     *component.root_constraints.borrow_mut() =
         LayoutConstraints::new(&component.root_element, diag);
 
+    recurse_elem_including_sub_components(component, &None, &mut |elem, parent_direction| {
+        inherit_layout_direction(elem, parent_direction)
+    });
+
     recurse_elem_including_sub_components(component, &(), &mut |elem, _| {
         let component = elem.borrow().enclosing_component.upgrade().unwrap();
         lower_element_layout(
@@ -64,7 +68,7 @@ fn lower_element_layout(
         "VerticalLayout" => lower_box_layout(elem, diag, Orientation::Vertical),
         "PathLayout" => lower_path_layout(elem, diag),
         "Dialog" => {
-            lower_dialog_layout(elem, style_metrics, diag);
+            lower_dialog_layout(elem, type_register, style_metrics, diag);
             return; // the Dialog stays in the tree as a Dialog
         }
         _ => return,
@@ -91,6 +95,27 @@ fn lower_element_layout(
     }
 }
 
+/// Layouts that don't set their `layout-direction` get it from the closest parent layout, or Window,
+/// that sets it. Returns the property that the children inherit.
+fn inherit_layout_direction(
+    elem: &ElementRc,
+    parent_direction: &Option<NamedReference>,
+) -> Option<NamedReference> {
+    let has_layout_direction = matches!(elem.borrow().base_type, ElementType::Builtin(_))
+        && elem.borrow().lookup_property("layout-direction").is_valid();
+    if !has_layout_direction {
+        return parent_direction.clone();
+    }
+    if !elem.borrow().bindings.contains_key("layout-direction") {
+        let parent_direction = parent_direction.as_ref()?;
+        elem.borrow_mut().bindings.insert(
+            "layout-direction".into(),
+            RefCell::new(Expression::PropertyReference(parent_direction.clone()).into()),
+        );
+    }
+    Some(NamedReference::new(elem, "layout-direction"))
+}
+
 pub fn is_layout_element(element: &ElementRc) -> bool {
     matches!(&element.borrow().base_type, ElementType::Builtin(n) if n.name == "GridLayout" || n.name == "HorizontalLayout" || n.name == "VerticalLayout" || n.name == "PathLayout")
 }
@@ -266,6 +291,7 @@ fn add_element_with_coord(
             if !layout_item.item.constraints.fixed_height {
                 set_prop_from_cache(e, "height", layout_cache_prop_v, index * 2 + 1, &None, diag);
             }
+            mirror_x_for_layout_direction(e, &layout_cache_prop_h.element());
 
             self.elems.push(GridLayoutElement {
                 col,
@@ -353,6 +379,7 @@ fn lower_box_layout(
                     .bindings
                     .insert(ortho.into(), RefCell::new(size_expr.clone().into()));
             }
+            mirror_x_for_layout_direction(actual_elem, layout_element);
             layout.elems.push(item.item);
         }
     }
@@ -390,6 +417,7 @@ fn lower_box_layout(
 
 fn lower_dialog_layout(
     dialog_element: &ElementRc,
+    type_register: &TypeRegister,
     style_metrics: &Option<Rc<Component>>,
     diag: &mut BuildDiagnostics,
 ) {
@@ -411,9 +439,29 @@ fn lower_dialog_layout(
     let layout_info_prop_h = create_new_prop(dialog_element, "layoutinfo-h", layout_info_type());
     let layout_info_prop_v = create_new_prop(dialog_element, "layoutinfo-v", layout_info_type());
 
+    let is_root = Rc::ptr_eq(
+        dialog_element,
+        &dialog_element.borrow().enclosing_component.upgrade().unwrap().root_element,
+    );
+    let finished_ty = dialog_element.borrow().lookup_property("finished").property_type;
+    dialog_element.borrow_mut().property_declarations.insert(
+        "finished".into(),
+        PropertyDeclaration {
+            property_type: finished_ty,
+            node: None,
+            expose_in_public_api: is_root,
+            is_alias: None,
+            visibility: PropertyVisibility::InOut,
+        },
+    );
+
     let mut main_widget = None;
     let mut button_roles = vec![];
     let mut seen_buttons = HashSet::new();
+    // The first StandardButton that accepts, and the first one that rejects the dialog
+    let mut default_button = None;
+    let mut escape_button = None;
+    let mut clicked_handlers = vec![];
     let layout_children = std::mem::take(&mut dialog_element.borrow_mut().children);
     for layout_child in &layout_children {
         let dialog_button_role_binding =
@@ -468,36 +516,50 @@ fn lower_dialog_layout(
                         button_roles.push(role.into());
                         if !seen_buttons.insert(val.value) {
                             diag.push_error("Duplicated `kind`: There are two StandardButton in this Dialog with the same kind".into(), binding);
-                        } else if Rc::ptr_eq(
-                            dialog_element,
-                            &dialog_element
-                                .borrow()
-                                .enclosing_component
-                                .upgrade()
-                                .unwrap()
-                                .root_element,
-                        ) {
+                        } else {
+                            let mut handler = vec![];
                             let clicked_ty =
                                 layout_child.borrow().lookup_property("clicked").property_type;
-                            if matches!(&clicked_ty, Type::Callback { .. })
-                                && layout_child.borrow().bindings.get("clicked").map_or(true, |c| {
-                                    matches!(c.borrow().expression, Expression::Invalid)
-                                })
+                            let has_handler =
+                                layout_child.borrow().bindings.get("clicked").map_or(false, |c| {
+                                    !matches!(c.borrow().expression, Expression::Invalid)
+                                });
+                            if is_root
+                                && !has_handler
+                                && matches!(&clicked_ty, Type::Callback { .. })
                             {
+                                let callback_name = format!("{}-clicked", kind);
                                 dialog_element
                                     .borrow_mut()
                                     .property_declarations
-                                    .entry(format!("{}-clicked", kind))
+                                    .entry(callback_name.clone())
                                     .or_insert_with(|| PropertyDeclaration {
                                         property_type: clicked_ty,
                                         node: None,
                                         expose_in_public_api: true,
-                                        is_alias: Some(NamedReference::new(
-                                            layout_child,
-                                            "clicked",
-                                        )),
+                                        is_alias: None,
                                         visibility: PropertyVisibility::InOut,
                                     });
+                                handler.push(callback_call(
+                                    NamedReference::new(dialog_element, &callback_name),
+                                    vec![],
+                                ));
+                            }
+                            if matches!(role, "accept" | "reject") {
+                                handler.push(callback_call(
+                                    NamedReference::new(dialog_element, "finished"),
+                                    vec![Expression::EnumerationValue(val.clone())],
+                                ));
+                                let slot = if role == "accept" {
+                                    &mut default_button
+                                } else {
+                                    &mut escape_button
+                                };
+                                slot.get_or_insert_with(|| layout_child.clone());
+                            }
+                            if !handler.is_empty() {
+                                // layout_child is still borrowed, so this is done after the loop
+                                clicked_handlers.push((layout_child.clone(), handler));
                             }
                         }
                     } else {
@@ -534,6 +596,20 @@ fn lower_dialog_layout(
     }
     dialog_element.borrow_mut().children = layout_children;
 
+    for (button, handler) in clicked_handlers {
+        append_to_handler(&button, "clicked", handler);
+    }
+
+    let shortcut_type = type_register.lookup_element("Shortcut").unwrap();
+    for (button, keys) in default_button
+        .iter()
+        .map(|b| (b, "Return"))
+        .chain(escape_button.iter().map(|b| (b, "Escape")))
+    {
+        let shortcut = create_dialog_shortcut(dialog_element, button, keys, &shortcut_type);
+        dialog_element.borrow_mut().children.push(shortcut);
+    }
+
     if let Some(main_widget) = main_widget {
         grid.add_element_with_coord(
             &main_widget,
@@ -590,6 +666,67 @@ fn lower_dialog_layout(
     dialog_element.borrow_mut().layout_info_prop = Some((layout_info_prop_h, layout_info_prop_v));
 }
 
+fn callback_call(callback: NamedReference, arguments: Vec<Expression>) -> Expression {
+    Expression::FunctionCall {
+        function: Box::new(Expression::CallbackReference(callback)),
+        arguments,
+        source_location: None,
+    }
+}
+
+/// Make the handler of the callback run `handler` after its current code, if any
+fn append_to_handler(elem: &ElementRc, callback: &str, mut handler: Vec<Expression>) {
+    let span = elem.borrow().to_source_location();
+    let mut elem = elem.borrow_mut();
+    let binding = elem
+        .bindings
+        .entry(callback.into())
+        .or_insert_with(|| BindingExpression::new_with_span(Expression::Invalid, span).into())
+        .get_mut();
+    let previous = std::mem::take(&mut binding.expression);
+    if !matches!(previous, Expression::Invalid) {
+        handler.insert(0, previous);
+    }
+    binding.expression = Expression::CodeBlock(handler);
+}
+
+/// Create a `Shortcut` that clicks the button of the dialog when the keys are pressed
+fn create_dialog_shortcut(
+    dialog_element: &ElementRc,
+    button: &ElementRc,
+    keys: &str,
+    shortcut_type: &ElementType,
+) -> ElementRc {
+    let mut shortcut = Element {
+        id: format!("{}-{}-shortcut", dialog_element.borrow().id, keys.to_lowercase()),
+        base_type: shortcut_type.clone(),
+        enclosing_component: dialog_element.borrow().enclosing_component.clone(),
+        ..Default::default()
+    };
+    shortcut
+        .bindings
+        .insert("keys".into(), RefCell::new(Expression::StringLiteral(keys.into()).into()));
+    if button.borrow().lookup_property("enabled").property_type == Type::Bool {
+        shortcut.bindings.insert(
+            "enabled".into(),
+            RefCell::new(
+                Expression::PropertyReference(NamedReference::new(button, "enabled")).into(),
+            ),
+        );
+    }
+    shortcut.bindings.insert(
+        "activated".into(),
+        RefCell::new(
+            Expression::CodeBlock(vec![callback_call(
+                NamedReference::new(button, "clicked"),
+                vec![],
+            )])
+            .into(),
+        ),
+    );
+    Rc::new(RefCell::new(shortcut))
+}
+
 fn lower_path_layout(layout_element: &ElementRc, diag: &mut BuildDiagnostics) {
     let layout_cache_prop = create_new_prop(layout_element, "layout-cache", Type::LayoutCache);
 
@@ -745,6 +882,48 @@ fn set_prop_from_cache(
     }
 }
 
+/// If the layout has a `layout-direction`, mirror the x position of the element within the layout
+/// when the direction is right-to-left.
+fn mirror_x_for_layout_direction(elem: &ElementRc, layout_element: &ElementRc) {
+    if !layout_element.borrow().bindings.contains_key("layout-direction") {
+        return;
+    }
+    let x = match elem.borrow_mut().bindings.get_mut("x") {
+        Some(binding) => std::mem::take(&mut binding.get_mut().expression),
+        None => return,
+    };
+    let rtl = crate::typeregister::BUILTIN_ENUMS
+        .with(|e| e.LayoutDirection.clone())
+        .try_value_from_string("rtl")
+        .unwrap();
+    // x = layout.width - x - elem.width when the direction is rtl
+    let mirrored = Expression::BinaryExpression {
+        lhs: Box::new(Expression::BinaryExpression {
+            lhs: Box::new(Expression::PropertyReference(NamedReference::new(
+                layout_element,
+                "width",
+            ))),
+            rhs: Box::new(x.clone()),
+            op: '-',
+        }),
+        rhs: Box::new(Expression::PropertyReference(NamedReference::new(elem, "width"))),
+        op: '-',
+    };
+    let expression = Expression::Condition {
+        condition: Box::new(Expression::BinaryExpression {
+            lhs: Box::new(Expression::PropertyReference(NamedReference::new(
+                layout_element,
+                "layout-direction",
+            ))),
+            rhs: Box::new(Expression::EnumerationValue(rtl)),
+            op: '=',
+        }),
+        true_expr: Box::new(mirrored),
+        false_expr: Box::new(x),
+    };
+    elem.borrow_mut().bindings.get_mut("x").unwrap().get_mut().expression = expression;
+}
+
 fn eval_const_expr(
     expression: &Expression,
     name: &str,
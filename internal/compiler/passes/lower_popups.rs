@@ -85,6 +85,44 @@ fn lower_popup_window(
     let coord_x = create_coordinate(&popup_comp, parent_element, "x");
     let coord_y = create_coordinate(&popup_comp, parent_element, "y");
 
+    // The options are read when the popup is shown, so move them to the parent as well. The
+    // popup becomes a Window, which doesn't have these properties.
+    let create_option = |name: &str, default: bool| {
+        let binding = popup_comp.root_element.borrow_mut().bindings.remove(name);
+        let expression =
+            binding.map(|b| b.into_inner().expression).unwrap_or(Expression::BoolLiteral(default));
+        create_property(
+            popup_comp.root_element.borrow().id.as_str(),
+            parent_element,
+            name,
+            Type::Bool,
+            expression,
+        )
+    };
+    let close_on_click = create_option("close-on-click", true);
+    let close_on_click_outside = create_option("close-on-click-outside", true);
+    let close_on_escape = create_option("close-on-escape", true);
+    let modal = create_option("modal", false);
+
+    let anchor =
+        popup_comp.root_element.borrow_mut().bindings.remove("anchor").and_then(|b| {
+            match b.into_inner().expression {
+                Expression::ElementReference(anchor) => anchor.upgrade(),
+                _ => None,
+            }
+        });
+    let anchor = match anchor {
+        Some(anchor) if !is_in_component_or_parents(&anchor, &parent_component) => {
+            diag.push_error(
+                "The anchor of a PopupWindow must not be in a repeated or conditional element that doesn't contain the PopupWindow".into(),
+                &*popup_window_element.borrow(),
+            );
+            parent_element.clone()
+        }
+        Some(anchor) => anchor,
+        None => parent_element.clone(),
+    };
+
     // Throw error when accessing the popup from outside
     // FIXME:
     // - the span is the span of the PopupWindow, that's wrong, we should have the span of the reference
@@ -105,10 +143,34 @@ fn lower_popup_window(
         component: popup_comp,
         x: coord_x,
         y: coord_y,
+        close_on_click,
+        close_on_click_outside,
+        close_on_escape,
+        modal,
         parent_element: parent_element.clone(),
+        anchor,
     });
 }
 
+/// Returns true if the element is in the component, or in one of the components that contain it
+fn is_in_component_or_parents(element: &ElementRc, component: &Rc<Component>) -> bool {
+    let enclosing = element.borrow().enclosing_component.clone();
+    let mut component = component.clone();
+    loop {
+        if std::rc::Weak::ptr_eq(&enclosing, &Rc::downgrade(&component)) {
+            return true;
+        }
+        let parent = component
+            .parent_element
+            .upgrade()
+            .and_then(|parent| parent.borrow().enclosing_component.upgrade());
+        match parent {
+            Some(parent) => component = parent,
+            None => return false,
+        }
+    }
+}
+
 fn create_coordinate(
     popup_comp: &Rc<Component>,
     parent_element: &ElementRc,
@@ -121,11 +183,25 @@ fn create_coordinate(
         .get(coord)
         .map(|e| e.borrow().expression.clone())
         .unwrap_or(Expression::NumberLiteral(0., crate::expression_tree::Unit::Phx));
-    let property_name = format!("{}-popup-{}", popup_comp.root_element.borrow().id, coord);
-    parent_element
-        .borrow_mut()
-        .property_declarations
-        .insert(property_name.clone(), Type::LogicalLength.into());
+    create_property(
+        popup_comp.root_element.borrow().id.as_str(),
+        parent_element,
+        coord,
+        Type::LogicalLength,
+        expression,
+    )
+}
+
+/// Declare a property `<popup_id>-popup-<name>` in the parent element, set to the expression
+fn create_property(
+    popup_id: &str,
+    parent_element: &ElementRc,
+    name: &str,
+    ty: Type,
+    expression: Expression,
+) -> NamedReference {
+    let property_name = format!("{}-popup-{}", popup_id, name);
+    parent_element.borrow_mut().property_declarations.insert(property_name.clone(), ty.into());
     parent_element
         .borrow_mut()
         .bindings
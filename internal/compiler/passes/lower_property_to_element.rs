@@ -3,6 +3,7 @@
 
 //! Pass that lowers synthetic properties such as `opacity` and `layer` properties to their corresponding elements.
 //! For example `f := Foo { opacity: <some float>; }` is mapped to `Opacity { opacity <=> f.opacity; f := Foo { ... } }`
+//! Properties that form a group, such as the `effect-*` properties, are lowered to a single element.
 
 use std::cell::RefCell;
 use std::rc::Rc;
@@ -16,24 +17,70 @@
 /// If any element in `component` declares a binding to `property_name`, then a new
 /// element of type `element_name` is created, injected as a parent to the element and bindings
 /// to property_name and all properties in  extra_properties are mapped.
-/// Default balue for the property extra_properties is queried witht the `default_value_for_extra_properties`
+/// Default balue for the property extra_properties is queried witht the `default_value_for_extra_properties`,
+/// the properties for which it returns None keep the default value of the element.
 pub(crate) fn lower_property_to_element(
     component: &Rc<Component>,
     property_name: &'static str,
     extra_properties: impl Iterator<Item = &'static str> + Clone,
-    default_value_for_extra_properties: Option<&dyn Fn(&ElementRc, &str) -> Expression>,
+    default_value_for_extra_properties: Option<&dyn Fn(&ElementRc, &str) -> Option<Expression>>,
     element_name: &str,
     type_register: &TypeRegister,
     diag: &mut BuildDiagnostics,
 ) {
-    if let Some(b) = component.root_element.borrow().bindings.get(property_name) {
-        diag.push_warning(
-            format!(
-                "The {} property cannot be used on the root element, it will not be applied",
-                property_name
-            ),
-            &*b.borrow(),
-        );
+    lower_properties_to_element_impl(
+        component,
+        &[property_name],
+        core::iter::once(property_name).chain(extra_properties),
+        default_value_for_extra_properties,
+        element_name,
+        type_register,
+        diag,
+    )
+}
+
+/// If any element in `component` declares a binding to any of the `property_names`, then a new
+/// element of type `element_name` is created, injected as a parent to the element and bindings
+/// to all the properties that are set are mapped. The default value of the other properties is queried with
+/// `default_value_for_extra_properties`, or they keep the default value of the element.
+pub(crate) fn lower_properties_to_element(
+    component: &Rc<Component>,
+    property_names: &[&'static str],
+    default_value_for_extra_properties: Option<&dyn Fn(&ElementRc, &str) -> Option<Expression>>,
+    element_name: &str,
+    type_register: &TypeRegister,
+    diag: &mut BuildDiagnostics,
+) {
+    lower_properties_to_element_impl(
+        component,
+        property_names,
+        property_names.iter().copied(),
+        default_value_for_extra_properties,
+        element_name,
+        type_register,
+        diag,
+    )
+}
+
+fn lower_properties_to_element_impl(
+    component: &Rc<Component>,
+    trigger_properties: &[&'static str],
+    properties: impl Iterator<Item = &'static str> + Clone,
+    default_value_for_extra_properties: Option<&dyn Fn(&ElementRc, &str) -> Option<Expression>>,
+    element_name: &str,
+    type_register: &TypeRegister,
+    diag: &mut BuildDiagnostics,
+) {
+    for property_name in trigger_properties {
+        if let Some(b) = component.root_element.borrow().bindings.get(*property_name) {
+            diag.push_warning(
+                format!(
+                    "The {} property cannot be used on the root element, it will not be applied",
+                    property_name
+                ),
+                &*b.borrow(),
+            );
+        }
     }
 
     object_tree::recurse_elem_including_sub_components_no_borrow(component, &(), &mut |elem, _| {
@@ -48,13 +95,15 @@ pub(crate) fn lower_property_to_element(
         };
 
         let has_property_binding = |e: &ElementRc| {
-            e.borrow().base_type.lookup_property(property_name).property_type != Type::Invalid
-                && (e.borrow().bindings.contains_key(property_name)
-                    || e.borrow()
-                        .property_analysis
-                        .borrow()
-                        .get(property_name)
-                        .map_or(false, |a| a.is_set))
+            trigger_properties.iter().any(|property_name| {
+                e.borrow().base_type.lookup_property(property_name).property_type != Type::Invalid
+                    && (e.borrow().bindings.contains_key(*property_name)
+                        || e.borrow()
+                            .property_analysis
+                            .borrow()
+                            .get(*property_name)
+                            .map_or(false, |a| a.is_set))
+            })
         };
 
         for mut child in old_children {
@@ -65,8 +114,8 @@ pub(crate) fn lower_property_to_element(
                         &child,
                         create_property_element(
                             &root_elem,
-                            property_name,
-                            extra_properties.clone(),
+                            trigger_properties[0],
+                            properties.clone(),
                             default_value_for_extra_properties,
                             element_name,
                             type_register,
@@ -76,8 +125,8 @@ pub(crate) fn lower_property_to_element(
             } else if has_property_binding(&child) {
                 let new_child = create_property_element(
                     &child,
-                    property_name,
-                    extra_properties.clone(),
+                    trigger_properties[0],
+                    properties.clone(),
                     default_value_for_extra_properties,
                     element_name,
                     type_register,
@@ -94,14 +143,13 @@ pub(crate) fn lower_property_to_element(
 
 fn create_property_element(
     child: &ElementRc,
-    property_name: &'static str,
-    extra_properties: impl Iterator<Item = &'static str>,
-    default_value_for_extra_properties: Option<&dyn Fn(&ElementRc, &str) -> Expression>,
+    id_suffix: &'static str,
+    properties: impl Iterator<Item = &'static str>,
+    default_value_for_extra_properties: Option<&dyn Fn(&ElementRc, &str) -> Option<Expression>>,
     element_name: &str,
     type_register: &TypeRegister,
 ) -> ElementRc {
-    let bindings = core::iter::once(property_name)
-        .chain(extra_properties)
+    let bindings = properties
         .filter_map(|property_name| {
             if child.borrow().bindings.contains_key(property_name) {
                 Some((
@@ -110,18 +158,18 @@ fn create_property_element(
                         .into(),
                 ))
             } else {
-                default_value_for_extra_properties.map(|f| {
-                    (
+                default_value_for_extra_properties.and_then(|f| {
+                    Some((
                         property_name.to_string(),
-                        BindingExpression::from(f(child, property_name)).into(),
-                    )
+                        BindingExpression::from(f(child, property_name)?).into(),
+                    ))
                 })
             }
         })
         .collect();
 
     let element = Element {
-        id: format!("{}-{}", child.borrow().id, property_name),
+        id: format!("{}-{}", child.borrow().id, id_suffix),
         base_type: type_register.lookup_element(element_name).unwrap(),
         enclosing_component: child.borrow().enclosing_component.clone(),
         bindings,
@@ -2,8 +2,8 @@
 // SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
 
 //! Pass that lowers synthetic `drop-shadow-*` properties to proper shadow elements
-// At the moment only shadows on `Rectangle` elements are supported, i.e. the drop shadow
-// of a rectangle is a box shadow.
+// The drop shadow of a rectangle is a box shadow. The drop shadow of other elements is rendered
+// by the `DropShadow` element that this pass leaves to the `lower_property_to_element` pass.
 
 use crate::diagnostics::BuildDiagnostics;
 use crate::expression_tree::BindingExpression;
@@ -13,29 +13,19 @@
 use std::collections::HashMap;
 use std::rc::Rc;
 
+/// Returns true if the drop shadow of the element can be rendered as a box shadow.
+fn is_box_shadow_element(element: &ElementRc) -> bool {
+    !matches!(element.borrow().native_class(), Some(native)
+       if native.class_name != "Rectangle" && native.class_name != "BorderRectangle" && native.class_name != "Clip")
+}
+
 // Creates a new element for the drop shadow properties that'll be a sibling to the specified
 // sibling element.
 fn create_box_shadow_element(
     shadow_property_bindings: HashMap<String, BindingExpression>,
     sibling_element: &ElementRc,
     type_register: &TypeRegister,
-    diag: &mut BuildDiagnostics,
-) -> Option<Element> {
-    if matches!(sibling_element.borrow().native_class(), Some(native)
-       if native.class_name != "Rectangle" && native.class_name != "BorderRectangle" && native.class_name != "Clip")
-    {
-        for (shadow_prop_name, shadow_prop_binding) in shadow_property_bindings {
-            diag.push_error(
-                format!(
-                    "The {} property is only supported on Rectangle and Clip elements right now",
-                    shadow_prop_name
-                ),
-                &shadow_prop_binding,
-            );
-        }
-        return None;
-    }
-
+) -> Element {
     let mut element = Element {
         id: format!("{}-shadow", sibling_element.borrow().id),
         base_type: type_register.lookup_element("BoxShadow").unwrap(),
@@ -63,7 +53,7 @@ fn create_box_shadow_element(
         );
     }
 
-    Some(element)
+    element
 }
 
 // For a repeated element, this function creates a new element for the drop shadow properties that
@@ -72,20 +62,15 @@ fn inject_shadow_element_in_repeated_element(
     shadow_property_bindings: HashMap<String, BindingExpression>,
     repeated_element: &ElementRc,
     type_register: &TypeRegister,
-    diag: &mut BuildDiagnostics,
 ) {
     let element_with_shadow_property =
         &repeated_element.borrow().base_type.as_component().root_element.clone();
 
-    let shadow_element = match create_box_shadow_element(
+    let shadow_element = create_box_shadow_element(
         shadow_property_bindings,
         element_with_shadow_property,
         type_register,
-        diag,
-    ) {
-        Some(element) => element,
-        None => return,
-    };
+    );
 
     crate::object_tree::inject_element_as_repeated_element(
         repeated_element,
@@ -131,15 +116,16 @@ pub fn lower_shadow_properties(
         if elem.borrow().repeated.is_some() {
             let component = elem.borrow().base_type.as_component().clone(); // CHECK if clone can be removed if we change borrow
 
-            let drop_shadow_properties = take_shadow_property_bindings(&component.root_element);
-            if !drop_shadow_properties.is_empty() {
-                drop(component);
-                inject_shadow_element_in_repeated_element(
-                    drop_shadow_properties,
-                    elem,
-                    type_register,
-                    diag,
-                );
+            if is_box_shadow_element(&component.root_element) {
+                let drop_shadow_properties = take_shadow_property_bindings(&component.root_element);
+                if !drop_shadow_properties.is_empty() {
+                    drop(component);
+                    inject_shadow_element_in_repeated_element(
+                        drop_shadow_properties,
+                        elem,
+                        type_register,
+                    );
+                }
             }
         }
 
@@ -153,17 +139,14 @@ pub fn lower_shadow_properties(
         // shadow element and insert it *before* the element that had the `drop-shadow` property, to ensure
         // that it is rendered underneath.
         for child in old_children {
-            let drop_shadow_properties = take_shadow_property_bindings(&child);
+            let drop_shadow_properties = if is_box_shadow_element(&child) {
+                take_shadow_property_bindings(&child)
+            } else {
+                HashMap::new()
+            };
             if !drop_shadow_properties.is_empty() {
-                let mut shadow_elem = match create_box_shadow_element(
-                    drop_shadow_properties,
-                    &child,
-                    type_register,
-                    diag,
-                ) {
-                    Some(element) => element,
-                    None => continue,
-                };
+                let mut shadow_elem =
+                    create_box_shadow_element(drop_shadow_properties, &child, type_register);
 
                 // Install bindings from the remaining properties of the shadow element to the
                 // original, such as x/y/width/height.
@@ -78,6 +78,7 @@ fn process_tabwidget(
         let index = tabs.len();
         child.borrow_mut().base_type = rectangle_type.clone();
         child.borrow_mut().property_declarations.insert("title".to_owned(), Type::String.into());
+        child.borrow_mut().property_declarations.insert("closable".to_owned(), Type::Bool.into());
         set_geometry_prop(elem, child, "x", diag);
         set_geometry_prop(elem, child, "y", diag);
         set_geometry_prop(elem, child, "width", diag);
@@ -108,6 +109,14 @@ fn process_tabwidget(
             "title".to_owned(),
             BindingExpression::new_two_way(NamedReference::new(child, "title")).into(),
         );
+        tab.bindings.insert(
+            "closable".to_owned(),
+            BindingExpression::new_two_way(NamedReference::new(child, "closable")).into(),
+        );
+        tab.bindings.insert(
+            "close-requested".to_owned(),
+            BindingExpression::new_two_way(NamedReference::new(elem, "close-requested")).into(),
+        );
         tab.bindings.insert(
             "current".to_owned(),
             BindingExpression::new_two_way(NamedReference::new(elem, "current-index")).into(),
@@ -143,9 +152,11 @@ fn process_tabwidget(
         "num-tabs".to_owned(),
         RefCell::new(Expression::NumberLiteral(num_tabs as _, Unit::None).into()),
     );
-    elem.borrow_mut().bindings.insert(
-        "current-index".to_owned(),
-        BindingExpression::new_two_way(NamedReference::new(&tabbar, "current")).into(),
+    // The binding is set on the tab bar so that the `current-index` of the TabWidget keeps the
+    // binding it may have, such as a two-way binding to a property of the application
+    tabbar.borrow_mut().bindings.insert(
+        "current".to_owned(),
+        BindingExpression::new_two_way(NamedReference::new(elem, "current-index")).into(),
     );
     elem.borrow_mut().bindings.insert(
         "current-focused".to_owned(),
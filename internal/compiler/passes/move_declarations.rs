@@ -79,8 +79,7 @@ fn do_move_declarations(component: &Rc<Component>) {
 
     component.root_constraints.borrow_mut().visit_named_references(&mut fixup_reference);
     component.popup_windows.borrow_mut().iter_mut().for_each(|p| {
-        fixup_reference(&mut p.x);
-        fixup_reference(&mut p.y);
+        p.visit_named_references(&mut fixup_reference);
         visit_all_named_references(&p.component, &mut fixup_reference)
     });
     for pd in decl.property_declarations.values_mut() {
@@ -6,17 +6,43 @@
 //! Rectangles which do not draw anything and have no x or y don't need to be in
 //! the item tree, we can just remove them.
 
+use crate::expression_tree::{BuiltinFunction, Expression};
 use crate::langtype::ElementType;
 use crate::object_tree::*;
+use std::collections::HashSet;
 use std::rc::Rc;
 
 pub fn optimize_useless_rectangles(root_component: &Rc<Component>) {
+    // The elements passed to `ensure-visible()` and the anchors of popups must stay in the item tree
+    let mut referenced_elements = HashSet::new();
+    visit_all_expressions(root_component, |expr, _| {
+        expr.visit_recursive(&mut |expr| {
+            if let Expression::FunctionCall { function, arguments, .. } = expr {
+                if let (
+                    Expression::BuiltinFunctionReference(BuiltinFunction::EnsureItemVisible, _),
+                    [Expression::ElementReference(target)],
+                ) = (function.as_ref(), arguments.as_slice())
+                {
+                    referenced_elements.insert(target.as_ptr());
+                }
+            }
+        })
+    });
+    recurse_elem_including_sub_components(root_component, &(), &mut |elem, _| {
+        if let Some(component) = elem.borrow().enclosing_component.upgrade() {
+            if Rc::ptr_eq(&component.root_element, elem) {
+                referenced_elements
+                    .extend(component.popup_windows.borrow().iter().map(|p| Rc::as_ptr(&p.anchor)));
+            }
+        }
+    });
+
     recurse_elem_including_sub_components(root_component, &(), &mut |parent, _| {
         let mut parent = parent.borrow_mut();
         let children = std::mem::take(&mut parent.children);
 
         for elem in children {
-            if !can_optimize(&elem) {
+            if !can_optimize(&elem) || referenced_elements.contains(&Rc::as_ptr(&elem)) {
                 parent.children.push(elem);
                 continue;
             }
@@ -8,7 +8,7 @@
 //!
 //! Most of the code for the resolving actually lies in the expression_tree module
 
-use crate::diagnostics::{BuildDiagnostics, Spanned};
+use crate::diagnostics::{BuildDiagnostics, DiagnosticCode, Spanned};
 use crate::expression_tree::*;
 use crate::langtype::{ElementType, Type};
 use crate::lookup::{LookupCtx, LookupObject, LookupResult};
@@ -127,6 +127,47 @@ pub fn resolve_expressions(
     }
 }
 
+/// Parses the `source` of an expression that is not part of the document, and resolves it in the
+/// scope of the root element of `component`, like a binding of that element. The globals used by the
+/// component can be referenced by their name.
+///
+/// This is used to evaluate expressions in a running instance of an already compiled component.
+pub fn resolve_standalone_expression(
+    source: String,
+    component: &Rc<Component>,
+    diag: &mut BuildDiagnostics,
+) -> Expression {
+    let node = crate::parser::parse_standalone_expression(source, diag);
+    if diag.has_error() {
+        return Expression::Invalid;
+    }
+
+    let mut type_register = TypeRegister::new(&TypeRegister::builtin());
+    for global in component.used_types.borrow().globals.iter() {
+        type_register.add(global.clone());
+    }
+    let component_scope = [component.root_element.clone()];
+    let mut lookup_ctx = LookupCtx {
+        property_name: None,
+        property_type: Type::Invalid,
+        component_scope: &component_scope,
+        diag,
+        arguments: vec![],
+        type_register: &type_register,
+        type_loader: None,
+        current_token: None,
+    };
+    let expr = node.child_node(SyntaxKind::Expression).map_or(Expression::Invalid, |n| {
+        Expression::from_expression_node(n.into(), &mut lookup_ctx)
+    });
+    expr.visit_recursive(&mut |e| {
+        if let Expression::MemberFunction { base_node, .. } = e {
+            diag.push_error("Member function must be called".into(), base_node);
+        }
+    });
+    expr
+}
+
 impl Expression {
     pub fn from_binding_expression_node(node: SyntaxNode, ctx: &mut LookupCtx) -> Self {
         debug_assert_eq!(node.kind(), SyntaxKind::BindingExpression);
@@ -224,7 +265,7 @@ fn from_callback_connection(
         )
     }
 
-    fn from_expression_node(node: syntax_nodes::Expression, ctx: &mut LookupCtx) -> Self {
+    pub fn from_expression_node(node: syntax_nodes::Expression, ctx: &mut LookupCtx) -> Self {
         node.Expression()
             .map(|n| Self::from_expression_node(n, ctx))
             .or_else(|| node.AtImageUrl().map(|n| Self::from_at_image_url_node(n, ctx)))
@@ -332,6 +373,51 @@ fn from_at_image_url_node(node: syntax_nodes::AtImageUrl, ctx: &mut LookupCtx) -
             }
         };
 
+        if let Some(sandbox) =
+            ctx.type_loader.and_then(|loader| loader.compiler_config.sandbox.as_ref())
+        {
+            if !sandbox.allows_path(std::path::Path::new(&absolute_source_path)) {
+                ctx.diag.push_error(
+                    format!(
+                        "Cannot load image \"{}\": the file is outside of the allowed directories",
+                        absolute_source_path
+                    ),
+                    &node,
+                );
+                return Self::Invalid;
+            }
+            // SVG images are rendered at the size they are displayed at, so they are not limited.
+            // On wasm, the file can't be read here and the runtime applies the limit instead.
+            #[cfg(not(target_arch = "wasm32"))]
+            if let Some((max_width, max_height)) = sandbox.max_image_size.filter(|_| {
+                !absolute_source_path.ends_with(".svg") && !absolute_source_path.ends_with(".svgz")
+            }) {
+                match image::image_dimensions(&absolute_source_path) {
+                    Ok((width, height)) if width <= max_width && height <= max_height => {}
+                    Ok((width, height)) => {
+                        ctx.diag.push_error(
+                            format!(
+                                "The image \"{}\" is {}x{} pixels, more than the maximum of {}x{}",
+                                absolute_source_path, width, height, max_width, max_height
+                            ),
+                            &node,
+                        );
+                        return Self::Invalid;
+                    }
+                    Err(err) => {
+                        ctx.diag.push_error(
+                            format!(
+                                "Cannot load image \"{}\": its size can't be read: {}",
+                                absolute_source_path, err
+                            ),
+                            &node,
+                        );
+                        return Self::Invalid;
+                    }
+                }
+            }
+        }
+
         Expression::ImageReference {
             resource_ref: ImageReference::AbsolutePath(absolute_source_path),
             source_location: Some(node.to_source_location()),
@@ -529,7 +615,7 @@ fn from_qualified_name_node(node: syntax_nodes::QualifiedName, ctx: &mut LookupC
                         .lookup(ctx, &crate::parser::normalize_identifier(first_str))
                         .is_some()
                     {
-                        ctx.diag.push_error(format!("Unknown unqualified identifier '{}'. Use space before the '-' if you meant a subtraction", first.text()), &node);
+                        ctx.diag.push_error_with_code(format!("Unknown unqualified identifier '{}'. Use space before the '-' if you meant a subtraction", first.text()), DiagnosticCode::UnknownIdentifier, &node);
                         return Expression::Invalid;
                     }
                 }
@@ -538,7 +624,7 @@ fn from_qualified_name_node(node: syntax_nodes::QualifiedName, ctx: &mut LookupC
                 {
                     if let Some(e) = e {
                         if e.lookup(ctx, &first_str).is_some() {
-                            ctx.diag.push_error(format!("Unknown unqualified identifier '{0}'. Did you mean '{prefix}.{0}'?", first.text()), &node);
+                            ctx.diag.push_error_with_code(format!("Unknown unqualified identifier '{0}'. Did you mean '{prefix}.{0}'?", first.text()), DiagnosticCode::UnknownIdentifier, &node);
                             return Expression::Invalid;
                         }
                     }
@@ -547,8 +633,9 @@ fn from_qualified_name_node(node: syntax_nodes::QualifiedName, ctx: &mut LookupC
                 if it.next().is_some() {
                     ctx.diag.push_error(format!("Cannot access id '{}'", first.text()), &node);
                 } else {
-                    ctx.diag.push_error(
+                    ctx.diag.push_error_with_code(
                         format!("Unknown unqualified identifier '{}'", first.text()),
+                        DiagnosticCode::UnknownIdentifier,
                         &node,
                     );
                 }
@@ -1083,8 +1170,9 @@ fn continue_lookup_within_element(
                     return;
                 }
             };
-            ctx.diag.push_error(
+            ctx.diag.push_error_with_code(
                 format!("{} does not have a property '{}'{}", what, second.text(), extra),
+                DiagnosticCode::UnknownIdentifier,
                 &second,
             );
         };
@@ -0,0 +1,52 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+//! Pass that sets the bindings of the `truncated`, `content-width` and `content-height`
+//! output properties of the Text element.
+//!
+//! These properties are computed by the runtime from the laid out text, so their binding
+//! calls a builtin function on the item. The binding is only set when the property is used.
+
+use crate::expression_tree::{BuiltinFunction, Expression};
+use crate::layout::Orientation;
+use crate::object_tree::{Component, ElementRc};
+use std::rc::Rc;
+
+pub fn handle_text_metrics(component: &Rc<Component>) {
+    crate::object_tree::recurse_elem_including_sub_components(
+        component,
+        &(),
+        &mut |elem: &ElementRc, _| {
+            if !is_text_element(elem) {
+                return;
+            }
+            for (prop, function) in [
+                ("truncated", BuiltinFunction::TextTruncated),
+                ("content-width", BuiltinFunction::TextContentSize(Orientation::Horizontal)),
+                ("content-height", BuiltinFunction::TextContentSize(Orientation::Vertical)),
+            ] {
+                if !elem.borrow().named_references.is_referenced(prop) {
+                    continue;
+                }
+                elem.borrow_mut().set_binding_if_not_set(prop.into(), || {
+                    Expression::FunctionCall {
+                        function: Box::new(Expression::BuiltinFunctionReference(function, None)),
+                        arguments: vec![Expression::ElementReference(Rc::downgrade(elem))],
+                        source_location: None,
+                    }
+                });
+            }
+        },
+    )
+}
+
+fn is_text_element(elem: &ElementRc) -> bool {
+    let mut native_class = elem.borrow().native_class();
+    while let Some(n) = native_class {
+        if n.class_name == "Text" {
+            return true;
+        }
+        native_class = n.parent.clone();
+    }
+    false
+}
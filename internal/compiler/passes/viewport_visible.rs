@@ -0,0 +1,102 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+//! Pass that lowers the synthetic `viewport-visible` property and the `viewport-entered` and
+//! `viewport-exited` callbacks to a ViewportObserver element injected as the parent of the element.
+//! For example `f := Foo { viewport-entered => {...} }` is mapped to
+//! `ViewportObserver { viewport-visible <=> f.viewport-visible; viewport-entered => {...}  f := Foo { ... } }`
+
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::rc::Rc;
+
+use crate::diagnostics::BuildDiagnostics;
+use crate::expression_tree::{BindingExpression, NamedReference};
+use crate::object_tree::{self, BindingsMap, Component, Element, ElementRc};
+use crate::typeregister::TypeRegister;
+
+const VIEWPORT_VISIBLE: &str = "viewport-visible";
+const VIEWPORT_CALLBACKS: [&str; 2] = ["viewport-entered", "viewport-exited"];
+
+pub fn handle_viewport_visible(
+    component: &Rc<Component>,
+    type_register: &TypeRegister,
+    diag: &mut BuildDiagnostics,
+) {
+    // `viewport-visible` is an output property, so the elements that need an observer are
+    // the ones for which the property is read somewhere.
+    let mut observed_elements = HashSet::new();
+    object_tree::visit_all_named_references(component, &mut |nr| {
+        if nr.name() == VIEWPORT_VISIBLE {
+            observed_elements.insert(Rc::as_ptr(&nr.element()));
+        }
+    });
+
+    let needs_observer = |e: &ElementRc| {
+        observed_elements.contains(&Rc::as_ptr(e))
+            || VIEWPORT_CALLBACKS.iter().any(|c| e.borrow().bindings.contains_key(*c))
+    };
+
+    if needs_observer(&component.root_element) {
+        let root = component.root_element.borrow();
+        let message = "The viewport-visible property and the viewport-entered and viewport-exited callbacks cannot be used on the root element, they will not be applied".to_owned();
+        match VIEWPORT_CALLBACKS.iter().find_map(|c| root.bindings.get(*c)) {
+            Some(b) => diag.push_warning(message, &*b.borrow()),
+            None => diag.push_warning(message, &*root),
+        }
+    }
+
+    object_tree::recurse_elem_including_sub_components_no_borrow(component, &(), &mut |elem, _| {
+        if elem.borrow().base_type.to_string() == "ViewportObserver" {
+            return;
+        }
+
+        let old_children = {
+            let mut elem = elem.borrow_mut();
+            let new_children = Vec::with_capacity(elem.children.len());
+            std::mem::replace(&mut elem.children, new_children)
+        };
+
+        for mut child in old_children {
+            if child.borrow().repeated.is_some() {
+                let root_elem = child.borrow().base_type.as_component().root_element.clone();
+                if needs_observer(&root_elem) {
+                    object_tree::inject_element_as_repeated_element(
+                        &child,
+                        create_viewport_observer(&root_elem, type_register),
+                    )
+                }
+            } else if needs_observer(&child) {
+                let new_child = create_viewport_observer(&child, type_register);
+                object_tree::adjust_geometry_for_injected_parent(&new_child, &child);
+                new_child.borrow_mut().children.push(child);
+                child = new_child;
+            }
+
+            elem.borrow_mut().children.push(child);
+        }
+    });
+}
+
+fn create_viewport_observer(child: &ElementRc, type_register: &TypeRegister) -> ElementRc {
+    let mut bindings = BindingsMap::new();
+    bindings.insert(
+        VIEWPORT_VISIBLE.to_string(),
+        BindingExpression::new_two_way(NamedReference::new(child, VIEWPORT_VISIBLE)).into(),
+    );
+    // The handlers only refer to elements through named references, so they can be moved as is
+    for callback in VIEWPORT_CALLBACKS.iter() {
+        if let Some(handler) = child.borrow_mut().bindings.remove(*callback) {
+            bindings.insert(callback.to_string(), handler);
+        }
+    }
+
+    let element = Element {
+        id: format!("{}-viewport-observer", child.borrow().id),
+        base_type: type_register.lookup_element("ViewportObserver").unwrap(),
+        enclosing_component: child.borrow().enclosing_component.clone(),
+        bindings,
+        ..Default::default()
+    };
+    Rc::new(RefCell::new(element))
+}
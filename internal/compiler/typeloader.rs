@@ -6,7 +6,7 @@
 use std::path::{Path, PathBuf};
 use std::rc::Rc;
 
-use crate::diagnostics::{BuildDiagnostics, Spanned};
+use crate::diagnostics::{BuildDiagnostics, DiagnosticCode, Spanned};
 use crate::object_tree::{self, Document};
 use crate::parser::{syntax_nodes, NodeOrToken, SyntaxKind, SyntaxToken};
 use crate::typeregister::TypeRegister;
@@ -21,6 +21,15 @@ pub struct LoadedDocuments {
     currently_loading: HashSet<PathBuf>,
 }
 
+/// A cache of the parsed files that several compilations can share, with
+/// [`CompilerConfiguration::parsed_files_cache`], so that the files they import are only read and
+/// parsed once.
+#[derive(Default)]
+pub struct ParsedFilesCache {
+    /// maps from the canonical file name to the files parsed without error
+    files: RefCell<HashMap<PathBuf, syntax_nodes::Document>>,
+}
+
 pub struct ImportedTypes {
     pub import_token: SyntaxToken,
     pub imported_types: syntax_nodes::ImportSpecifier,
@@ -97,6 +106,11 @@ pub fn new(
             String::from("fluent")
         });
 
+        let style = match &compiler_config.native_style_fallback {
+            Some(fallback) if style == "native" => fallback.clone(),
+            _ => style,
+        };
+
         let myself = Self {
             global_type_registry,
             compiler_config,
@@ -148,11 +162,20 @@ pub async fn load_dependencies_recursively(
                 );
                 }
             } else {
-                import.file = self
+                let path = self
                     .resolve_import_path(Some(&import.import_token.clone().into()), &import.file)
-                    .0
-                    .to_string_lossy()
-                    .to_string();
+                    .0;
+                if !self.is_allowed_by_sandbox(&path) {
+                    diagnostics.push_error(
+                        format!(
+                            "Cannot import \"{}\": the file is outside of the allowed directories",
+                            import.file
+                        ),
+                        &import.import_token,
+                    );
+                    continue;
+                }
+                import.file = path.to_string_lossy().to_string();
                 foreign_imports.push(import);
             }
         }
@@ -188,6 +211,10 @@ pub fn resolve_import_path(
         import_token: Option<&NodeOrToken>,
         maybe_relative_path_or_url: &str,
     ) -> (std::path::PathBuf, Option<&'static [u8]>) {
+        if let Some(library_path) = self.resolve_library_import(maybe_relative_path_or_url) {
+            return (library_path, None);
+        }
+
         let referencing_file_or_url =
             import_token.and_then(|tok| tok.source_file().map(|s| s.path()));
 
@@ -205,9 +232,11 @@ pub fn resolve_import_path(
                                         .map(|url| url.to_string().into())
                                 })
                             } else {
-                                base_path_or_url.parent().and_then(|base_dir| {
-                                    dunce::canonicalize(base_dir.join(maybe_relative_path_or_url))
-                                        .ok()
+                                base_path_or_url.parent().map(|base_dir| {
+                                    let path = base_dir.join(maybe_relative_path_or_url);
+                                    // The file may not exist on disk, but be provided by the
+                                    // `open_import_fallback`
+                                    dunce::canonicalize(&path).unwrap_or(path)
                                 })
                             }
                         })
@@ -227,6 +256,17 @@ async fn ensure_document_loaded<'b>(
 
         let path_canon = dunce::canonicalize(&path).unwrap_or_else(|_| path.to_owned());
 
+        if !self.is_allowed_by_sandbox(&path_canon) {
+            diagnostics.push_error(
+                format!(
+                    "Cannot import \"{}\": the file is outside of the allowed directories",
+                    file_to_import
+                ),
+                &import_token,
+            );
+            return None;
+        }
+
         if self.all_documents.docs.get(path_canon.as_path()).is_some() {
             return Some(path_canon);
         }
@@ -241,6 +281,18 @@ async fn ensure_document_loaded<'b>(
             return None;
         }
 
+        let cached_doc = self
+            .compiler_config
+            .parsed_files_cache
+            .as_ref()
+            .and_then(|cache| cache.files.borrow().get(&path_canon).cloned());
+        if let Some(doc_node) = cached_doc {
+            self.load_parsed_file(&path_canon, doc_node, is_builtin, diagnostics).await;
+            let _ok = self.all_documents.currently_loading.remove(path_canon.as_path());
+            assert!(_ok);
+            return Some(path_canon);
+        }
+
         let source_code_result = if let Some(builtin) = builtin {
             Ok(String::from_utf8(builtin)
                 .expect("internal error: embedded file is not UTF-8 source code"))
@@ -254,11 +306,12 @@ async fn ensure_document_loaded<'b>(
         let source_code = match source_code_result {
             Ok(source) => source,
             Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
-                diagnostics.push_error(
+                diagnostics.push_error_with_code(
                     format!(
                         "Cannot find requested import \"{}\" in the include search path",
                         file_to_import
                     ),
+                    DiagnosticCode::ImportNotFound,
                     &import_token,
                 );
                 return None;
@@ -289,9 +342,28 @@ pub async fn load_file(
         is_builtin: bool,
         diagnostics: &mut BuildDiagnostics,
     ) {
+        let mut parse_diagnostics = BuildDiagnostics::default();
         let dependency_doc: syntax_nodes::Document =
-            crate::parser::parse(source_code, Some(source_path), diagnostics).into();
+            crate::parser::parse(source_code, Some(source_path), &mut parse_diagnostics).into();
+        if let Some(cache) = &self.compiler_config.parsed_files_cache {
+            if parse_diagnostics.is_empty() {
+                cache.files.borrow_mut().insert(path.to_owned(), dependency_doc.clone());
+            }
+        }
+        for diagnostic in parse_diagnostics {
+            diagnostics.push_compiler_error(diagnostic);
+        }
+        self.load_parsed_file(path, dependency_doc, is_builtin, diagnostics).await;
+    }
 
+    /// Load an already parsed file, and its dependency not run the passes.
+    async fn load_parsed_file(
+        &mut self,
+        path: &Path,
+        dependency_doc: syntax_nodes::Document,
+        is_builtin: bool,
+        diagnostics: &mut BuildDiagnostics,
+    ) {
         let dependency_registry =
             Rc::new(RefCell::new(TypeRegister::new(&self.global_type_registry)));
         dependency_registry.borrow_mut().expose_internal_types = is_builtin;
@@ -394,6 +466,27 @@ fn load_dependency<'b>(
         })
     }
 
+    /// Returns false if the compiler configuration has a sandbox that doesn't allow accessing the
+    /// file at `path`.
+    pub fn is_allowed_by_sandbox(&self, path: &Path) -> bool {
+        self.compiler_config.sandbox.as_ref().map_or(true, |sandbox| sandbox.allows_path(path))
+    }
+
+    /// Returns the path of an import from a library, such as `@mylib/widgets.slint`, or None if the
+    /// import doesn't name one of the library paths of the compiler configuration.
+    fn resolve_library_import(&self, file_to_import: &str) -> Option<PathBuf> {
+        let library_import = file_to_import.strip_prefix('@')?;
+        let (library, path_in_library) = match library_import.split_once('/') {
+            Some((library, path_in_library)) => (library, Some(path_in_library)),
+            None => (library_import, None),
+        };
+        let library_path = self.compiler_config.library_paths.get(library)?;
+        Some(match path_in_library {
+            Some(path_in_library) => library_path.join(path_in_library),
+            None => library_path.clone(),
+        })
+    }
+
     /// Lookup a filename and try to find the absolute filename based on the include path or
     /// the current file directory
     pub fn find_file_in_include_path(
@@ -515,6 +608,48 @@ fn test_dependency_loading() {
     assert!(!build_diagnostics.has_error());
 }
 
+#[test]
+fn test_sandbox() {
+    let test_source_path: std::path::PathBuf =
+        [env!("CARGO_MANIFEST_DIR"), "tests", "typeloader"].iter().collect();
+
+    let mut incdir = test_source_path.clone();
+    incdir.push("incpath");
+
+    let mut compiler_config =
+        CompilerConfiguration::new(crate::generator::OutputFormat::Interpreter);
+    compiler_config.include_paths = vec![incdir.clone()];
+    compiler_config.style = Some("fluent".into());
+    compiler_config.sandbox = Some(crate::ResourceSandbox {
+        allowed_directories: vec![incdir.join("..").join("incpath")],
+        max_image_size: None,
+    });
+
+    let mut main_test_path = test_source_path;
+    main_test_path.push("dependency_test_main.slint");
+
+    let mut test_diags = crate::diagnostics::BuildDiagnostics::default();
+    let doc_node = crate::parser::parse_file(main_test_path, &mut test_diags).unwrap();
+    let doc_node: syntax_nodes::Document = doc_node.into();
+    let global_registry = TypeRegister::builtin();
+    let registry = Rc::new(RefCell::new(TypeRegister::new(&global_registry)));
+    let mut build_diagnostics = BuildDiagnostics::default();
+    let mut loader = TypeLoader::new(global_registry, compiler_config, &mut build_diagnostics);
+    spin_on::spin_on(loader.load_dependencies_recursively(
+        &doc_node,
+        &mut build_diagnostics,
+        &registry,
+    ));
+
+    // The file imported from the include path is allowed, but not the one next to the main file
+    let messages =
+        build_diagnostics.into_iter().map(|d| d.message().to_owned()).collect::<Vec<_>>();
+    assert_eq!(
+        messages,
+        ["Cannot import \"./dependency_local.slint\": the file is outside of the allowed directories"]
+    );
+}
+
 #[test]
 fn test_load_from_callback_ok() {
     let ok = Rc::new(core::cell::Cell::new(false));
@@ -579,6 +714,90 @@ fn test_manual_import() {
     assert!(maybe_button_type.is_some());
 }
 
+#[test]
+fn test_parsed_files_cache() {
+    let test_source_path: std::path::PathBuf =
+        [env!("CARGO_MANIFEST_DIR"), "tests", "typeloader"].iter().collect();
+
+    let cache = Rc::new(ParsedFilesCache::default());
+
+    let load = || {
+        let mut compiler_config =
+            CompilerConfiguration::new(crate::generator::OutputFormat::Interpreter);
+        compiler_config.include_paths = vec![test_source_path.join("incpath")];
+        compiler_config.style = Some("fluent".into());
+        compiler_config.parsed_files_cache = Some(cache.clone());
+
+        let mut test_diags = crate::diagnostics::BuildDiagnostics::default();
+        let doc_node = crate::parser::parse_file(
+            test_source_path.join("dependency_test_main.slint"),
+            &mut test_diags,
+        )
+        .unwrap();
+        let doc_node: syntax_nodes::Document = doc_node.into();
+        let global_registry = TypeRegister::builtin();
+        let registry = Rc::new(RefCell::new(TypeRegister::new(&global_registry)));
+        let mut build_diagnostics = BuildDiagnostics::default();
+        let mut loader = TypeLoader::new(global_registry, compiler_config, &mut build_diagnostics);
+        spin_on::spin_on(loader.load_dependencies_recursively(
+            &doc_node,
+            &mut build_diagnostics,
+            &registry,
+        ));
+        assert!(!test_diags.has_error());
+        assert!(!build_diagnostics.has_error());
+        assert!(registry.borrow().lookup_element("AnotherType").is_ok());
+    };
+
+    load();
+    assert!(cache
+        .files
+        .borrow()
+        .keys()
+        .any(|path| path.ends_with("dependency_from_incpath.slint")));
+    // The second compilation uses the parsed files of the first one
+    load();
+}
+
+#[test]
+fn test_library_import() {
+    let test_source_path: std::path::PathBuf =
+        [env!("CARGO_MANIFEST_DIR"), "tests", "typeloader"].iter().collect();
+
+    let library_dir = test_source_path.join("library");
+
+    let mut compiler_config =
+        CompilerConfiguration::new(crate::generator::OutputFormat::Interpreter);
+    compiler_config.library_paths = [
+        ("libdir".to_string(), library_dir.clone()),
+        ("libfile".to_string(), library_dir.join("lib.slint")),
+    ]
+    .into_iter()
+    .collect();
+    compiler_config.style = Some("fluent".into());
+
+    let mut main_test_path = test_source_path;
+    main_test_path.push("library_import_test.slint");
+
+    let mut test_diags = crate::diagnostics::BuildDiagnostics::default();
+    let doc_node = crate::parser::parse_file(main_test_path, &mut test_diags).unwrap();
+    let doc_node: syntax_nodes::Document = doc_node.into();
+    let global_registry = TypeRegister::builtin();
+    let registry = Rc::new(RefCell::new(TypeRegister::new(&global_registry)));
+    let mut build_diagnostics = BuildDiagnostics::default();
+    let mut loader = TypeLoader::new(global_registry, compiler_config, &mut build_diagnostics);
+    spin_on::spin_on(loader.load_dependencies_recursively(
+        &doc_node,
+        &mut build_diagnostics,
+        &registry,
+    ));
+
+    assert!(!test_diags.has_error());
+    assert!(!build_diagnostics.has_error());
+    assert!(registry.borrow().lookup_element("LibraryWidget").is_ok());
+    assert!(registry.borrow().lookup_element("LibraryHelper").is_ok());
+}
+
 #[test]
 fn test_builtin_style() {
     let test_source_path: std::path::PathBuf =
@@ -638,3 +857,18 @@ fn test_unknown_style() {
     assert_eq!(diags.len(), 1);
     assert!(diags[0].starts_with("Style FooBar in not known. Use one of the builtin styles ["));
 }
+
+#[test]
+fn test_native_style_fallback() {
+    let mut compiler_config =
+        CompilerConfiguration::new(crate::generator::OutputFormat::Interpreter);
+    compiler_config.style = Some("native".into());
+    compiler_config.native_style_fallback = Some("material".into());
+
+    let global_registry = TypeRegister::builtin();
+    let mut build_diagnostics = BuildDiagnostics::default();
+    let loader = TypeLoader::new(global_registry, compiler_config, &mut build_diagnostics);
+
+    assert!(!build_diagnostics.has_error());
+    assert_eq!(loader.style, "material");
+}
@@ -88,10 +88,27 @@ fn fill_register(&self, register: &mut TypeRegister) {
     ("drop-shadow-color", Type::Color),
 ];
 
-pub const RESERVED_ROTATION_PROPERTIES: &[(&str, Type)] = &[
+pub const RESERVED_TRANSFORM_PROPERTIES: &[(&str, Type)] = &[
     ("rotation-angle", Type::Angle),
     ("rotation-origin-x", Type::LogicalLength),
     ("rotation-origin-y", Type::LogicalLength),
+    ("transform-scale-x", Type::Float32),
+    ("transform-scale-y", Type::Float32),
+    ("transform-skew-x", Type::Angle),
+    ("transform-skew-y", Type::Angle),
+];
+
+pub const RESERVED_EFFECT_PROPERTIES: &[(&str, Type)] = &[
+    ("effect-blur", Type::LogicalLength),
+    ("effect-brightness", Type::Float32),
+    ("effect-saturation", Type::Float32),
+    ("effect-tint", Type::Color),
+];
+
+pub const RESERVED_VIEWPORT_PROPERTIES: &[(&str, Type)] = &[
+    ("viewport-visible", Type::Bool),
+    ("viewport-entered", Type::Callback { return_type: None, args: Vec::new() }),
+    ("viewport-exited", Type::Callback { return_type: None, args: Vec::new() }),
 ];
 
 pub const RESERVED_ACCESSIBILITY_PROPERTIES: &[(&str, Type)] = &[
@@ -114,12 +131,15 @@ pub fn reserved_properties() -> impl Iterator<Item = (&'static str, Type)> {
         .chain(RESERVED_LAYOUT_PROPERTIES.iter())
         .chain(RESERVED_OTHER_PROPERTIES.iter())
         .chain(RESERVED_DROP_SHADOW_PROPERTIES.iter())
-        .chain(RESERVED_ROTATION_PROPERTIES.iter())
+        .chain(RESERVED_TRANSFORM_PROPERTIES.iter())
+        .chain(RESERVED_EFFECT_PROPERTIES.iter())
+        .chain(RESERVED_VIEWPORT_PROPERTIES.iter())
         .chain(RESERVED_ACCESSIBILITY_PROPERTIES.iter())
         .map(|(k, v)| (*k, v.clone()))
         .chain(IntoIterator::into_iter([
             ("forward-focus", Type::ElementReference),
             ("focus", BuiltinFunction::SetFocusItem.ty()),
+            ("ensure-visible", BuiltinFunction::EnsureItemVisible.ty()),
             (
                 "dialog-button-role",
                 Type::Enumeration(BUILTIN_ENUMS.with(|e| e.DialogButtonRole.clone())),
@@ -139,7 +159,11 @@ pub fn reserved_property(name: &str) -> PropertyLookupResult {
                 property_type: t,
                 resolved_name: name.into(),
                 is_local_to_component: false,
-                property_visibility: crate::object_tree::PropertyVisibility::InOut,
+                property_visibility: if name == "viewport-visible" {
+                    crate::object_tree::PropertyVisibility::Output
+                } else {
+                    crate::object_tree::PropertyVisibility::InOut
+                },
             };
         }
     }
@@ -173,6 +197,10 @@ pub fn reserved_property(name: &str) -> PropertyLookupResult {
 pub fn reserved_member_function(name: &str) -> Expression {
     for (m, e) in [
         ("focus", Expression::BuiltinFunctionReference(BuiltinFunction::SetFocusItem, None)), // match for callable "focus" property
+        (
+            "ensure-visible",
+            Expression::BuiltinFunctionReference(BuiltinFunction::EnsureItemVisible, None),
+        ),
     ]
     .iter()
     {
@@ -256,6 +284,55 @@ pub fn builtin() -> Rc<RefCell<Self>> {
                     "show".into(),
                     Expression::BuiltinFunctionReference(BuiltinFunction::ShowPopupWindow, None),
                 );
+                Rc::get_mut(b).unwrap().properties.insert(
+                    "close".into(),
+                    BuiltinPropertyInfo::new(BuiltinFunction::ClosePopupWindow.ty()),
+                );
+                Rc::get_mut(b).unwrap().member_functions.insert(
+                    "close".into(),
+                    Expression::BuiltinFunctionReference(BuiltinFunction::ClosePopupWindow, None),
+                );
+                // The anchor is an element reference, which can't be declared in builtins.slint
+                Rc::get_mut(b)
+                    .unwrap()
+                    .properties
+                    .insert("anchor".into(), BuiltinPropertyInfo::new(Type::ElementReference));
+            }
+            _ => unreachable!(),
+        };
+
+        match &mut register.elements.get_mut("Flickable").unwrap() {
+            ElementType::Builtin(ref mut b) => {
+                Rc::get_mut(b).unwrap().properties.insert(
+                    "scroll-to".into(),
+                    BuiltinPropertyInfo::new(BuiltinFunction::FlickableScrollTo.ty()),
+                );
+                Rc::get_mut(b).unwrap().member_functions.insert(
+                    "scroll-to".into(),
+                    Expression::BuiltinFunctionReference(BuiltinFunction::FlickableScrollTo, None),
+                );
+            }
+            _ => unreachable!(),
+        };
+
+        match &mut register.elements.get_mut("ToastQueue").unwrap() {
+            ElementType::Builtin(ref mut b) => {
+                Rc::get_mut(b).unwrap().properties.insert(
+                    "push".into(),
+                    BuiltinPropertyInfo::new(BuiltinFunction::ToastQueuePush.ty()),
+                );
+                Rc::get_mut(b).unwrap().member_functions.insert(
+                    "push".into(),
+                    Expression::BuiltinFunctionReference(BuiltinFunction::ToastQueuePush, None),
+                );
+                Rc::get_mut(b).unwrap().properties.insert(
+                    "dismiss".into(),
+                    BuiltinPropertyInfo::new(BuiltinFunction::ToastQueueDismiss.ty()),
+                );
+                Rc::get_mut(b).unwrap().member_functions.insert(
+                    "dismiss".into(),
+                    Expression::BuiltinFunctionReference(BuiltinFunction::ToastQueueDismiss, None),
+                );
             }
             _ => unreachable!(),
         };
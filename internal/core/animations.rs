@@ -217,6 +217,8 @@ pub struct AnimationDriver {
     /// Indicate whether there are any active animations that require a future call to update_animations.
     active_animations: Cell<bool>,
     global_instant: core::pin::Pin<Box<crate::Property<Instant>>>,
+    /// Set when the user asked the platform to reduce the motion in the user interface
+    reduced_motion: core::pin::Pin<Box<crate::Property<bool>>>,
 }
 
 impl Default for AnimationDriver {
@@ -227,6 +229,10 @@ fn default() -> Self {
                 Instant::default(),
                 "i_slint_core::AnimationDriver::global_instant",
             )),
+            reduced_motion: Box::pin(crate::Property::new_named(
+                false,
+                "i_slint_core::AnimationDriver::reduced_motion",
+            )),
         }
     }
 }
@@ -256,6 +262,23 @@ pub fn set_has_active_animations(&self) {
     pub fn current_tick(&self) -> Instant {
         self.global_instant.as_ref().get()
     }
+
+    /// Returns true if animations should be skipped, because the user asked the platform to reduce motion.
+    /// Using this function registers the current binding as a dependency.
+    pub fn reduced_motion(&self) -> bool {
+        self.reduced_motion.as_ref().get()
+    }
+
+    /// Same as [`Self::reduced_motion`], without registering a dependency.
+    pub fn reduced_motion_untracked(&self) -> bool {
+        self.reduced_motion.as_ref().get_untracked()
+    }
+
+    /// Sets whether animations should be skipped. When set, the property animations jump to their
+    /// final value.
+    pub fn set_reduced_motion(&self, reduced: bool) {
+        self.reduced_motion.as_ref().set(reduced)
+    }
 }
 
 #[cfg(all(not(feature = "std"), feature = "unsafe-single-threaded"))]
@@ -282,6 +305,12 @@ pub fn animation_tick() -> u64 {
     })
 }
 
+/// Returns true if the user asked the platform to reduce motion, in which case animations are skipped.
+/// Using this function registers the current binding as a dependency.
+pub fn reduced_motion() -> bool {
+    CURRENT_ANIMATION_DRIVER.with(|driver| driver.reduced_motion())
+}
+
 /// map a value between 0 and 1 to another value between 0 and 1 according to the curve
 pub fn easing_curve(curve: &EasingCurve, value: f32) -> f32 {
     match curve {
@@ -334,14 +363,27 @@ fn test_curve(name: &str, curve: &EasingCurve) {
 
 /// Update the global animation time to the current time
 pub fn update_animations() {
+    update_animations_for_frame(Instant::duration_since_start())
+}
+
+/// Update the global animation time to the time at which the next frame will be presented, expressed
+/// as a duration since the start of the platform.
+///
+/// Backends that know when frames are presented, for example from a vsync or frame callback, should call this
+/// before rendering a frame, so that the animations advance in step with the display refresh instead of the
+/// time at which the event loop happened to wake up.
+pub fn update_animations_for_frame(frame_time: core::time::Duration) {
     CURRENT_ANIMATION_DRIVER.with(|driver| {
         #[allow(unused_mut)]
-        let mut duration = Instant::duration_since_start().as_millis() as u64;
+        let mut duration = frame_time.as_millis() as u64;
         #[cfg(feature = "std")]
         if let Ok(val) = std::env::var("SLINT_SLOW_ANIMATIONS") {
             let factor = val.parse().unwrap_or(2);
             duration /= factor;
         };
-        driver.update_animations(Instant(duration))
+        // Never go back in time, as a timer may have already advanced the animations past the frame time
+        if Instant(duration) > driver.global_instant.as_ref().get_untracked() {
+            driver.update_animations(Instant(duration))
+        }
     });
 }
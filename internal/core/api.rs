@@ -11,6 +11,7 @@
 
 use crate::component::ComponentVTable;
 use crate::window::{WindowAdapter, WindowInner};
+use crate::SharedString;
 
 /// A position represented in the coordinate space of logical pixels. That is the space before applying
 /// a display device specific scale factor.
@@ -357,6 +358,33 @@ pub fn hide(&self) {
 
     /// This function allows registering a callback that's invoked during the different phases of
     /// rendering. This allows custom rendering on top or below of the scene.
+    ///
+    /// With [`RenderingState::BeforeRendering`], the callback is invoked after the back buffer was
+    /// cleared with the window's background and before any item is drawn, so that a 3D scene rendered
+    /// there appears underneath the UI without a copy into a texture. With
+    /// [`RenderingState::AfterRendering`], content drawn by the callback appears on top of the UI.
+    /// The [`GraphicsAPI`] parameter provides access to the graphics context that is current at
+    /// that point.
+    ///
+    /// Only one notifier can be registered per window. Renderers that don't expose their graphics
+    /// context, such as the Qt backend, return [`SetRenderingNotifierError::Unsupported`].
+    ///
+    /// ```no_run
+    /// # i_slint_backend_testing::init();
+    /// use slint::{ComponentHandle, GraphicsAPI, RenderingState};
+    /// slint::slint!{ export App := Window {} }
+    /// let app = App::new();
+    /// app.window()
+    ///     .set_rendering_notifier(|state, graphics_api| {
+    ///         if let (RenderingState::BeforeRendering, GraphicsAPI::NativeOpenGL { get_proc_address }) =
+    ///             (state, graphics_api)
+    ///         {
+    ///             // Load the OpenGL functions with get_proc_address and draw the scene
+    ///             # let _ = get_proc_address;
+    ///         }
+    ///     })
+    ///     .expect("The renderer does not support rendering notifiers");
+    /// ```
     pub fn set_rendering_notifier(
         &self,
         callback: impl FnMut(RenderingState, &GraphicsAPI) + 'static,
@@ -381,6 +409,19 @@ pub fn scale_factor(&self) -> f32 {
         self.0.scale_factor()
     }
 
+    /// Returns the color scheme used by the window. This is the color scheme of the platform,
+    /// unless it was overridden with [`Self::set_color_scheme()`].
+    pub fn color_scheme(&self) -> ColorScheme {
+        self.0.color_scheme()
+    }
+
+    /// Overrides the color scheme of the platform for this window, for example to let the user choose
+    /// a dark or light theme. The styles that support both follow it. Set it to [`ColorScheme::Unknown`]
+    /// to follow the color scheme of the platform again.
+    pub fn set_color_scheme(&self, scheme: ColorScheme) {
+        self.0.set_color_scheme(scheme)
+    }
+
     /// Returns the position of the window on the screen, in physical screen coordinates and including
     /// a window frame (if present).
     pub fn position(&self) -> PhysicalPosition {
@@ -420,8 +461,67 @@ pub fn set_size(&self, size: impl Into<WindowSize>) {
     ///
     /// Any position fields in the event must be in the logical pixel coordinate system relative to
     /// the top left corner of the window.
+    ///
+    /// Key events carry the text of the key, or one of the [`Key`](crate::platform::Key) codes for the
+    /// keys that don't produce text. The state of the keyboard modifiers is tracked from the key events
+    /// of the modifier keys.
     pub fn dispatch_event(&self, event: WindowEvent) {
-        self.0.process_mouse_input(event.into())
+        match event {
+            WindowEvent::KeyPressed { text } => {
+                self.0.process_platform_key_input(text, crate::input::KeyEventType::KeyPressed)
+            }
+            WindowEvent::KeyReleased { text } => {
+                self.0.process_platform_key_input(text, crate::input::KeyEventType::KeyReleased)
+            }
+            WindowEvent::TouchPressed { id, position }
+            | WindowEvent::TouchMoved { id, position }
+            | WindowEvent::TouchReleased { id, position } => {
+                let position = position.to_euclid().cast();
+                self.0.process_touch_input(
+                    id,
+                    match event {
+                        WindowEvent::TouchPressed { .. } => crate::input::MouseEvent::Pressed {
+                            position,
+                            button: PointerEventButton::Left,
+                        },
+                        WindowEvent::TouchMoved { .. } => {
+                            crate::input::MouseEvent::Moved { position }
+                        }
+                        _ => crate::input::MouseEvent::Released {
+                            position,
+                            button: PointerEventButton::Left,
+                        },
+                    },
+                )
+            }
+            WindowEvent::PointerPressed { position, button } => {
+                self.0.process_mouse_input(crate::input::MouseEvent::Pressed {
+                    position: position.to_euclid().cast(),
+                    button,
+                })
+            }
+            WindowEvent::PointerReleased { position, button } => {
+                self.0.process_mouse_input(crate::input::MouseEvent::Released {
+                    position: position.to_euclid().cast(),
+                    button,
+                })
+            }
+            WindowEvent::PointerMoved { position } => {
+                self.0.process_mouse_input(crate::input::MouseEvent::Moved {
+                    position: position.to_euclid().cast(),
+                })
+            }
+            WindowEvent::PointerScrolled { position, delta_x, delta_y } => {
+                self.0.process_mouse_input(crate::input::MouseEvent::Wheel {
+                    position: position.to_euclid().cast(),
+                    delta_x,
+                    delta_y,
+                })
+            }
+            WindowEvent::PointerExited => {
+                self.0.process_mouse_input(crate::input::MouseEvent::Exit)
+            }
+        }
     }
 
     /// Returns true if there is an animation currently active on any property in the Window; false otherwise.
@@ -433,6 +533,8 @@ pub fn has_active_animations(&self) -> bool {
 
 pub use crate::input::PointerEventButton;
 
+pub use crate::items::ColorScheme;
+
 /// A event that describes user input.
 ///
 /// Slint backends typically receive events from the windowing system, translate them to this
@@ -442,8 +544,10 @@ pub fn has_active_animations(&self) -> bool {
 /// or a contact point on a touch-enabled surface.
 ///
 /// All position fields are in logical window coordinates.
+///
+/// The events are not `Copy`, since the key events hold the text of the key: clone them instead.
 #[allow(missing_docs)]
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 #[non_exhaustive]
 pub enum WindowEvent {
     /// A pointer was pressed.
@@ -470,6 +574,18 @@ pub enum WindowEvent {
     },
     /// The pointer exited the window.
     PointerExited,
+    /// A finger started touching the window. `id` identifies the touch point in the
+    /// subsequent `TouchMoved` and `TouchReleased` events.
+    TouchPressed { id: u64, position: LogicalPosition },
+    /// A finger touching the window moved.
+    TouchMoved { id: u64, position: LogicalPosition },
+    /// A finger stopped touching the window, or the touch was cancelled.
+    TouchReleased { id: u64, position: LogicalPosition },
+    /// A key was pressed. `text` is the text produced by the key, or the [`Key`](crate::platform::Key)
+    /// code of a key that doesn't produce text, converted to a string.
+    KeyPressed { text: SharedString },
+    /// A key was released. `text` is the same as in the corresponding `KeyPressed` event.
+    KeyReleased { text: SharedString },
 }
 
 impl WindowEvent {
@@ -481,6 +597,10 @@ pub fn position(&self) -> Option<LogicalPosition> {
             WindowEvent::PointerMoved { position } => Some(*position),
             WindowEvent::PointerScrolled { position, .. } => Some(*position),
             WindowEvent::PointerExited => None,
+            WindowEvent::TouchPressed { position, .. } => Some(*position),
+            WindowEvent::TouchMoved { position, .. } => Some(*position),
+            WindowEvent::TouchReleased { position, .. } => Some(*position),
+            WindowEvent::KeyPressed { .. } | WindowEvent::KeyReleased { .. } => None,
         }
     }
 }
@@ -572,6 +692,69 @@ fn as_weak(&self) -> Weak<Self>
     fn global<'a, T: Global<'a, Self>>(&'a self) -> T
     where
         Self: Sized;
+
+    /// Calls `on_changed` with the new value every time the value returned by `getter` changes.
+    ///
+    /// `getter` typically reads one or several properties of the component. It is only re-evaluated
+    /// when one of the properties it read changed, and `on_changed` is called from the event loop,
+    /// after the property changes, if the value is different. This allows reacting to the properties
+    /// modified by the user interface, without declaring a callback for each of them.
+    ///
+    /// The observation stops when the returned [`PropertyObserver`] is dropped, or when the component
+    /// is destroyed.
+    ///
+    /// ```rust,no_run
+    /// slint::slint!{
+    ///     export MyApp := Window {
+    ///         property <int> counter;
+    ///         TouchArea { clicked => { counter += 1; } }
+    ///     }
+    /// }
+    /// # use slint::ComponentHandle;
+    /// let app = MyApp::new();
+    /// let _observer = app.observe(|app| app.get_counter(), |_app, counter| {
+    ///     println!("The counter is now {}", counter);
+    /// });
+    /// app.run();
+    /// ```
+    fn observe<T: PartialEq + Clone + 'static>(
+        &self,
+        getter: impl Fn(&Self) -> T + 'static,
+        mut on_changed: impl FnMut(&Self, &T) + 'static,
+    ) -> PropertyObserver
+    where
+        Self: Sized + 'static,
+    {
+        let weak_for_getter = self.as_weak();
+        let weak_for_notify = self.as_weak();
+        PropertyObserver::new(
+            move || weak_for_getter.upgrade().map(|component| getter(&component)),
+            move |value| {
+                if let (Some(component), Some(value)) = (weak_for_notify.upgrade(), value) {
+                    on_changed(&component, value)
+                }
+            },
+        )
+    }
+}
+
+/// Observes a value computed from properties, and calls a function when it changes.
+///
+/// Use [`ComponentHandle::observe()`] to create one. The observation stops when this is dropped.
+#[must_use = "the observation stops when the PropertyObserver is dropped"]
+pub struct PropertyObserver(crate::properties::ChangeTracker);
+
+impl PropertyObserver {
+    /// Creates an observer that calls `on_changed` from the event loop every time the value returned by
+    /// `getter` changes. `getter` is called once right away, to get the initial value.
+    pub fn new<T: PartialEq + Clone + 'static>(
+        getter: impl Fn() -> T + 'static,
+        on_changed: impl FnMut(&T) + 'static,
+    ) -> Self {
+        let mut tracker = crate::properties::ChangeTracker::default();
+        tracker.init(getter, on_changed);
+        Self(tracker)
+    }
 }
 
 mod weak_handle {
@@ -680,6 +863,41 @@ pub fn upgrade_in_event_loop(
                 }
             })
         }
+
+        /// Like [`Self::upgrade_in_event_loop()`], but returns the result of the functor.
+        ///
+        /// The returned [`EventLoopFuture`] can be awaited, or blocked on with [`EventLoopFuture::wait()`]
+        /// from a worker thread. Its output is None if the component was dropped before the functor
+        /// could be called.
+        ///
+        /// # Example
+        /// ```rust
+        /// # i_slint_backend_testing::init();
+        /// slint::slint! { MyApp := Window { property <int> counter; /* ... */ } }
+        /// let handle = MyApp::new();
+        /// let handle_weak = handle.as_weak();
+        /// let thread = std::thread::spawn(move || {
+        ///     # return; // don't call in the event loop in our examples
+        ///     let counter = handle_weak
+        ///         .call_in_event_loop(|handle| handle.get_counter())
+        ///         .unwrap()
+        ///         .wait();
+        ///     // ... use the counter in the thread
+        /// });
+        /// # thread.join().unwrap(); return; // don't run the event loop in examples
+        /// handle.run();
+        /// ```
+        #[cfg(feature = "std")]
+        pub fn call_in_event_loop<R: Send + 'static>(
+            &self,
+            func: impl FnOnce(T) -> R + Send + 'static,
+        ) -> Result<EventLoopFuture<R>, EventLoopError>
+        where
+            T: 'static,
+        {
+            let weak_handle = self.clone();
+            EventLoopFuture::new(move || weak_handle.upgrade().map(func))
+        }
     }
 
     // Safety: we make sure in upgrade that the thread is the proper one,
@@ -725,6 +943,10 @@ pub fn invoke_from_event_loop(func: impl FnOnce() + Send + 'static) -> Result<()
         .invoke_from_event_loop(alloc::boxed::Box::new(func))
 }
 
+#[cfg(feature = "std")]
+pub use crate::future::EventLoopFuture;
+pub use crate::future::{spawn_local, JoinHandle};
+
 /// Schedules the main event loop for termination. This function is meant
 /// to be called from callbacks triggered by the UI. After calling the function,
 /// it will return immediately and once control is passed back to the event loop,
@@ -735,6 +957,27 @@ pub fn quit_event_loop() -> Result<(), EventLoopError> {
         .quit_event_loop()
 }
 
+/// Registers a font family that is used to render the characters that the fonts of an element's
+/// `font-family` don't support, such as CJK characters or emoji in a latin font.
+///
+/// The registered families are tried in the order in which they were registered, before the fallback
+/// fonts of the system. Only the families that support the script of the text are used, so families
+/// for different scripts can be registered next to each other. The family must be installed on the
+/// system or be imported in a `.slint` file.
+///
+/// Call this function before showing the window, as text that was already laid out isn't updated.
+///
+/// Note that the software renderer doesn't support font fallback.
+///
+/// # Example
+/// ```rust
+/// slint::register_font_fallback_family("Noto Sans CJK JP");
+/// slint::register_font_fallback_family("Noto Color Emoji");
+/// ```
+pub fn register_font_fallback_family(family: impl Into<crate::SharedString>) {
+    crate::graphics::register_fallback_font_family(family.into())
+}
+
 #[derive(Debug, Clone, Eq, PartialEq)]
 #[non_exhaustive]
 /// Error returned from the [`invoke_from_event_loop()`] and [`quit_event_loop()`] function
@@ -744,6 +987,9 @@ pub enum EventLoopError {
     /// The event could not be sent because the Slint platform abstraction was not yet initialized,
     /// or the platform does not support event loop.
     NoEventLoopProvider,
+    /// The function must be called from the thread that runs the event loop, such as
+    /// [`spawn_local()`].
+    NotInEventLoopThread,
 }
 
 #[test]
@@ -47,6 +47,14 @@ pub fn call(&self, a: &Arg) -> Ret {
     pub fn set_handler(&self, mut f: impl FnMut(&Arg) -> Ret + 'static) {
         self.handler.set(Some(Box::new(move |a: &Arg, r: &mut Ret| *r = f(a))));
     }
+
+    /// Returns true if a handler was set with [`Self::set_handler`]
+    pub fn has_handler(&self) -> bool {
+        let handler = self.handler.take();
+        let result = handler.is_some();
+        self.handler.set(handler);
+        result
+    }
 }
 
 #[test]
@@ -96,7 +96,6 @@ pub struct ComponentVTable {
     pub dealloc: unsafe fn(&ComponentVTable, ptr: *mut u8, layout: vtable::Layout),
 }
 
-#[cfg(test)]
 pub(crate) use ComponentVTable_static;
 
 /// Alias for `vtable::VRef<ComponentVTable>` which represent a pointer to a `dyn Component` with
@@ -0,0 +1,272 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+/*!
+    A minimal executor that runs futures in the Slint event loop.
+
+    The futures are kept in a thread local map, indexed by an id. The waker only holds that id, and
+    wakes the task by queuing a poll of the future with [`invoke_from_event_loop`], so it can be
+    woken from any thread even though the futures themselves don't need to be `Send`.
+*/
+
+use crate::api::{invoke_from_event_loop, EventLoopError};
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::rc::Rc;
+use core::cell::{Cell, RefCell};
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+
+#[cfg(all(not(feature = "std"), feature = "unsafe-single-threaded"))]
+use crate::unsafe_single_threaded::thread_local;
+
+trait Task {
+    /// Polls the future of the task, and returns true if it is finished.
+    fn poll(&self, cx: &mut Context) -> bool;
+}
+
+struct TaskState<T> {
+    future: RefCell<Option<Pin<Box<dyn Future<Output = T>>>>>,
+    result: RefCell<Option<T>>,
+    /// The future completed. It stays false if the task was aborted.
+    finished: Cell<bool>,
+    /// The waker of the task awaiting the JoinHandle
+    join_waker: RefCell<Option<Waker>>,
+}
+
+impl<T> Task for TaskState<T> {
+    fn poll(&self, cx: &mut Context) -> bool {
+        let mut future = self.future.borrow_mut();
+        let poll = match future.as_mut() {
+            Some(future) => future.as_mut().poll(cx),
+            None => return true,
+        };
+        match poll {
+            Poll::Ready(result) => {
+                *future = None;
+                *self.result.borrow_mut() = Some(result);
+                self.finished.set(true);
+                if let Some(waker) = self.join_waker.borrow_mut().take() {
+                    waker.wake();
+                }
+                true
+            }
+            Poll::Pending => false,
+        }
+    }
+}
+
+thread_local!(static TASKS: RefCell<BTreeMap<usize, Rc<dyn Task>>> = Default::default());
+thread_local!(static NEXT_TASK_ID: Cell<usize> = Cell::new(0));
+
+fn poll_task(id: usize) {
+    // Don't keep TASKS borrowed while polling, since the future may spawn other tasks
+    let task = match TASKS.with(|tasks| tasks.borrow().get(&id).cloned()) {
+        Some(task) => task,
+        None => return, // The task was already finished or aborted
+    };
+    let waker = task_waker(id);
+    if task.poll(&mut Context::from_waker(&waker)) {
+        TASKS.with(|tasks| tasks.borrow_mut().remove(&id));
+    }
+}
+
+fn task_waker(id: usize) -> Waker {
+    fn clone(data: *const ()) -> RawWaker {
+        RawWaker::new(data, &VTABLE)
+    }
+    fn wake(data: *const ()) {
+        let id = data as usize;
+        // If the event loop is gone, there is nothing left to run the task anyway
+        invoke_from_event_loop(move || poll_task(id)).ok();
+    }
+    fn drop_waker(_: *const ()) {}
+    static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, wake, wake, drop_waker);
+
+    // Safety: the data of the waker is the id of the task and not an actual pointer, so the
+    // functions of the vtable are trivially thread safe and never dereference it.
+    #[allow(unsafe_code)]
+    unsafe {
+        Waker::from_raw(RawWaker::new(id as *const (), &VTABLE))
+    }
+}
+
+/// The handle returned by [`spawn_local()`] to await the result of the future or to abort it.
+///
+/// Dropping the handle doesn't abort the future.
+pub struct JoinHandle<T> {
+    id: usize,
+    task: Rc<TaskState<T>>,
+}
+
+impl<T> JoinHandle<T> {
+    /// Drops the future, if it is not yet finished. Awaiting the handle afterwards never completes.
+    pub fn abort(&self) {
+        TASKS.with(|tasks| tasks.borrow_mut().remove(&self.id));
+        // Drop the future here in case it is being polled and holds a reference to its task
+        let future = self.task.future.try_borrow_mut().ok().and_then(|mut future| future.take());
+        drop(future);
+    }
+
+    /// Returns true if the future has completed, and false if it is still running or was aborted
+    /// before completing.
+    pub fn is_finished(&self) -> bool {
+        self.task.finished.get()
+    }
+}
+
+impl<T> Future for JoinHandle<T> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T> {
+        match self.task.result.borrow_mut().take() {
+            Some(result) => Poll::Ready(result),
+            None => {
+                *self.task.join_waker.borrow_mut() = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+/// Spawns a future to run in the Slint event loop, and returns a [`JoinHandle`] to get its result.
+///
+/// Since the future runs in the thread of the event loop, it can access the components and
+/// doesn't need to be `Send`. It is first polled when the control returns to the event loop.
+/// This function must be called from the thread that runs the event loop, for example from a
+/// callback, otherwise it returns [`EventLoopError::NotInEventLoopThread`].
+///
+/// The futures that rely on a specific runtime, such as the network functions of `tokio`, still need that
+/// runtime to be running in another thread. Their result can be awaited from the event loop.
+///
+/// ```rust,no_run
+/// slint::slint! { MyApp := Window { property <string> status; callback refresh(); } }
+/// # async fn fetch_status() -> slint::SharedString { Default::default() }
+/// let app = MyApp::new();
+/// let app_weak = app.as_weak();
+/// app.on_refresh(move || {
+///     let app_weak = app_weak.clone();
+///     slint::spawn_local(async move {
+///         let status = fetch_status().await;
+///         app_weak.unwrap().set_status(status);
+///     })
+///     .unwrap();
+/// });
+/// app.run();
+/// ```
+pub fn spawn_local<F>(future: F) -> Result<JoinHandle<F::Output>, EventLoopError>
+where
+    F: Future + 'static,
+    F::Output: 'static,
+{
+    if crate::platform::event_loop_proxy().is_none() {
+        return Err(EventLoopError::NoEventLoopProvider);
+    }
+    // The platform is created in the thread of the event loop. The future is kept in a thread
+    // local, so it would never be polled if it was spawned from another thread.
+    if !crate::platform::PLATFORM_INSTANCE.with(|platform| platform.get().is_some()) {
+        return Err(EventLoopError::NotInEventLoopThread);
+    }
+    let id = NEXT_TASK_ID.with(|next_id| {
+        let id = next_id.get();
+        next_id.set(id.wrapping_add(1));
+        id
+    });
+    invoke_from_event_loop(move || poll_task(id))?;
+    let task = Rc::new(TaskState {
+        future: RefCell::new(Some(Box::pin(future))),
+        result: RefCell::new(None),
+        finished: Cell::new(false),
+        join_waker: RefCell::new(None),
+    });
+    TASKS.with(|tasks| tasks.borrow_mut().insert(id, task.clone() as Rc<dyn Task>));
+    Ok(JoinHandle { id, task })
+}
+
+#[cfg(feature = "std")]
+struct EventLoopFutureState<R> {
+    /// The result, and the waker of the task awaiting the future
+    state: std::sync::Mutex<(Option<Option<R>>, Option<Waker>)>,
+    condvar: std::sync::Condvar,
+}
+
+#[cfg(feature = "std")]
+impl<R> EventLoopFutureState<R> {
+    fn complete(&self, result: Option<R>) {
+        let mut state = self.state.lock().unwrap();
+        if state.0.is_some() {
+            return;
+        }
+        state.0 = Some(result);
+        let waker = state.1.take();
+        drop(state);
+        self.condvar.notify_all();
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+/// The result of a function called in the event loop with
+/// [`Weak::call_in_event_loop()`](crate::api::Weak::call_in_event_loop).
+///
+/// It can be awaited, or waited for with [`Self::wait()`] from another thread. The output is None if the
+/// component was dropped, or if the event loop stopped, before the function was called.
+#[cfg(feature = "std")]
+pub struct EventLoopFuture<R> {
+    state: std::sync::Arc<EventLoopFutureState<R>>,
+}
+
+#[cfg(feature = "std")]
+impl<R: Send + 'static> EventLoopFuture<R> {
+    /// Calls `func` in the event loop, and completes with its result.
+    pub(crate) fn new(
+        func: impl FnOnce() -> Option<R> + Send + 'static,
+    ) -> Result<Self, EventLoopError> {
+        /// Completes the future with None if the function is dropped without being called
+        struct CompleteOnDrop<R>(std::sync::Arc<EventLoopFutureState<R>>);
+        impl<R> Drop for CompleteOnDrop<R> {
+            fn drop(&mut self) {
+                self.0.complete(None);
+            }
+        }
+
+        let state = std::sync::Arc::new(EventLoopFutureState {
+            state: std::sync::Mutex::new((None, None)),
+            condvar: Default::default(),
+        });
+        let guard = CompleteOnDrop(state.clone());
+        invoke_from_event_loop(move || guard.0.complete(func()))?;
+        Ok(Self { state })
+    }
+
+    /// Blocks the current thread until the function was called in the event loop, and returns its result.
+    ///
+    /// This must not be called from the thread of the event loop, as it would never complete.
+    pub fn wait(self) -> Option<R> {
+        let mut state = self.state.state.lock().unwrap();
+        loop {
+            if let Some(result) = state.0.take() {
+                return result;
+            }
+            state = self.state.condvar.wait(state).unwrap();
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<R> Future for EventLoopFuture<R> {
+    type Output = Option<R>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<R>> {
+        let mut state = self.state.state.lock().unwrap();
+        match state.0.take() {
+            Some(result) => Poll::Ready(result),
+            None => {
+                state.1 = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
@@ -142,6 +142,59 @@ pub struct FontRequest {
     /// The additional spacing (or shrinking if negative) between glyphs. This is usually not submitted to
     /// the font-subsystem but collected here for API convenience
     pub letter_spacing: Option<LogicalLength>,
+    /// The additional spacing (or shrinking if negative) added to the space between words, on top of the
+    /// letter spacing.
+    pub word_spacing: Option<LogicalLength>,
+}
+
+impl FontRequest {
+    /// Returns the names of the families of the request, in order of preference. The `family` can be a
+    /// comma separated list of names, such as `"Noto Sans, 'Noto Sans CJK JP'"`.
+    pub fn families(&self) -> impl Iterator<Item = &str> {
+        self.family
+            .as_ref()
+            .map_or("", |family| family.as_str())
+            .split(',')
+            .map(|family| family.trim().trim_matches(|c| c == '"' || c == '\''))
+            .filter(|family| !family.is_empty())
+    }
+}
+
+#[cfg(all(not(feature = "std"), feature = "unsafe-single-threaded"))]
+use crate::unsafe_single_threaded::thread_local;
+
+thread_local! {
+    static FALLBACK_FONT_FAMILIES: core::cell::RefCell<alloc::vec::Vec<SharedString>> = Default::default()
+}
+
+/// Adds a font family to the families that are used to render the characters that the fonts of an
+/// element's `font-family` don't support. See [`crate::api::register_font_fallback_family`].
+pub fn register_fallback_font_family(family: SharedString) {
+    FALLBACK_FONT_FAMILIES.with(|families| {
+        let mut families = families.borrow_mut();
+        if !families.contains(&family) {
+            families.push(family);
+        }
+    })
+}
+
+/// Returns the font families registered with [`register_fallback_font_family`], in the order in which they
+/// were registered.
+pub fn fallback_font_families() -> alloc::vec::Vec<SharedString> {
+    FALLBACK_FONT_FAMILIES.with(|families| families.borrow().clone())
+}
+
+#[test]
+fn font_request_families() {
+    let request = FontRequest {
+        family: Some("Noto Sans, 'Noto Sans CJK JP' ,\"Noto Color Emoji\",".into()),
+        ..Default::default()
+    };
+    assert_eq!(
+        request.families().collect::<alloc::vec::Vec<_>>(),
+        ["Noto Sans", "Noto Sans CJK JP", "Noto Color Emoji"]
+    );
+    assert_eq!(FontRequest::default().families().count(), 0);
 }
 
 #[cfg(feature = "ffi")]
@@ -12,6 +12,8 @@
 use super::{IntRect, IntSize};
 use crate::items::ImageFit;
 
+#[cfg(feature = "image-decoders")]
+mod animated;
 #[cfg(feature = "image-decoders")]
 pub mod cache;
 #[cfg(target_arch = "wasm32")]
@@ -37,6 +39,12 @@ pub struct OpaqueImageVTable {
     pub static PARSED_SVG_VT for svg::ParsedSVG
 }
 
+#[cfg(feature = "image-decoders")]
+OpaqueImageVTable_static! {
+    /// VTable for RC wrapped animated image helper struct.
+    pub static ANIMATED_IMAGE_VT for animated::AnimatedImage
+}
+
 #[cfg(target_arch = "wasm32")]
 OpaqueImageVTable_static! {
     /// VTable for RC wrapped HtmlImage helper struct.
@@ -316,6 +324,9 @@ pub fn new(resource: &ImageInner) -> Option<Self> {
             #[cfg(target_arch = "wasm32")]
             ImageInner::HTMLImage(htmlimage) => Self::URL(htmlimage.source().into()),
             ImageInner::BackendStorage(x) => vtable::VRc::borrow(x).cache_key(),
+            ImageInner::BorrowedOpenGLTexture(..) => return None,
+            #[cfg(feature = "image-decoders")]
+            ImageInner::AnimatedImage(animated) => animated.cache_key().clone(),
         };
         if matches!(key, ImageCacheKey::Invalid) {
             None
@@ -330,6 +341,17 @@ pub fn from_embedded_image_data(data: &'static [u8]) -> Self {
     }
 }
 
+/// An OpenGL texture that is owned by the application and that the renderer samples from directly,
+/// without copying it. See [`Image::from_borrowed_gl_2d_rgba_texture`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[repr(C)]
+pub struct BorrowedOpenGLTexture {
+    /// The id of the OpenGL texture, as returned by `glGenTextures`.
+    pub texture_id: core::num::NonZeroU32,
+    /// The size of the texture in pixels.
+    pub size: IntSize,
+}
+
 /// A resource is a reference to binary data, for example images. They can be accessible on the file
 /// system or embedded in the resulting binary. Or they might be URLs to a web server and a downloaded
 /// is necessary before they can be used.
@@ -350,6 +372,9 @@ pub enum ImageInner {
     #[cfg(target_arch = "wasm32")]
     HTMLImage(vtable::VRc<OpaqueImageVTable, htmlimage::HTMLImage>),
     BackendStorage(vtable::VRc<OpaqueImageVTable>),
+    BorrowedOpenGLTexture(BorrowedOpenGLTexture),
+    #[cfg(feature = "image-decoders")]
+    AnimatedImage(vtable::VRc<OpaqueImageVTable, animated::AnimatedImage>),
 }
 
 impl ImageInner {
@@ -364,6 +389,8 @@ pub fn render_to_buffer(
     ) -> Option<SharedImageBuffer> {
         match self {
             ImageInner::EmbeddedImage { buffer, .. } => Some(buffer.clone()),
+            #[cfg(feature = "image-decoders")]
+            ImageInner::AnimatedImage(animated) => Some(animated.frame(0).buffer.clone()),
             #[cfg(feature = "svg")]
             ImageInner::Svg(svg) => {
                 match svg.render(_target_size_for_scalable_source.unwrap_or_default()) {
@@ -461,6 +488,9 @@ fn eq(&self, other: &Self) -> bool {
             (Self::StaticTextures(l0), Self::StaticTextures(r0)) => l0 == r0,
             #[cfg(target_arch = "wasm32")]
             (Self::HTMLImage(l0), Self::HTMLImage(r0)) => vtable::VRc::ptr_eq(l0, r0),
+            (Self::BorrowedOpenGLTexture(l0), Self::BorrowedOpenGLTexture(r0)) => l0 == r0,
+            #[cfg(feature = "image-decoders")]
+            (Self::AnimatedImage(l0), Self::AnimatedImage(r0)) => vtable::VRc::ptr_eq(l0, r0),
             _ => core::mem::discriminant(self) == core::mem::discriminant(other),
         }
     }
@@ -564,7 +594,11 @@ fn from(other: &'a Image) -> Self {
 
 impl Image {
     #[cfg(feature = "image-decoders")]
-    /// Load an Image from a path to a file containing an image
+    /// Load an Image from a path to a file containing an image.
+    ///
+    /// Supported formats are those of the [image](https://crates.io/crates/image) crate, as well
+    /// as SVG (`.svg` and `.svgz` files) when the `svg` feature is enabled. SVG images are
+    /// rendered at the size they are displayed at, so they stay crisp when scaled.
     pub fn load_from_path(path: &std::path::Path) -> Result<Self, LoadImageError> {
         self::cache::IMAGE_CACHE.with(|global_cache| {
             let path: SharedString = path.to_str().ok_or(LoadImageError(()))?.into();
@@ -572,6 +606,78 @@ pub fn load_from_path(path: &std::path::Path) -> Result<Self, LoadImageError> {
         })
     }
 
+    #[cfg(feature = "image-decoders")]
+    /// Load an Image from a path to a file containing an image, without blocking the event loop
+    /// while the image is decoded.
+    ///
+    /// The image is decoded by one of a few background threads, and `callback` is invoked from the
+    /// event loop with the result once it's done. Until then, the application can show a
+    /// placeholder, for example a smaller thumbnail or an image embedded with `@image-url`. The
+    /// callback is also invoked from the event loop if the image is already in the cache.
+    ///
+    /// This function must be called from the thread that runs the event loop. To load the
+    /// `source` of an `Image` element in the background, set its `placeholder` property instead.
+    ///
+    /// ```no_run
+    /// # use i_slint_core::graphics::Image;
+    /// # fn show_image(_: Image) {}
+    /// # let placeholder = Image::default();
+    /// show_image(placeholder);
+    /// Image::load_from_path_async(std::path::Path::new("photo.jpg"), |result| {
+    ///     if let Ok(image) = result {
+    ///         show_image(image);
+    ///     }
+    /// });
+    /// ```
+    pub fn load_from_path_async(
+        path: &std::path::Path,
+        callback: impl FnOnce(Result<Self, LoadImageError>) + 'static,
+    ) {
+        let path: SharedString = match path.to_str() {
+            Some(path) => path.into(),
+            None => {
+                crate::timers::Timer::single_shot(Default::default(), move || {
+                    callback(Err(LoadImageError(())))
+                });
+                return;
+            }
+        };
+        self::cache::IMAGE_CACHE.with(|global_cache| {
+            global_cache.borrow_mut().load_image_from_path_async(path, move |image| {
+                callback(image.ok_or(LoadImageError(())))
+            })
+        })
+    }
+
+    #[cfg(feature = "image-decoders")]
+    /// Sets the maximum amount of memory, in bytes, used to keep decoded images around after they
+    /// are no longer displayed, so that loading them again is fast. Images that exceed the new
+    /// limit are evicted from the cache, least recently used first. The default limit is 5 MiB.
+    ///
+    /// The cache is per thread, so this only affects the images loaded in the calling thread.
+    pub fn set_cache_size_limit(bytes: usize) {
+        self::cache::IMAGE_CACHE
+            .with(|global_cache| global_cache.borrow_mut().set_size_limit(bytes))
+    }
+
+    #[cfg(feature = "image-decoders")]
+    /// Removes all decoded images from the cache. Images that are still in use stay valid, but
+    /// loading them again decodes them again.
+    pub fn clear_cache() {
+        self::cache::IMAGE_CACHE.with(|global_cache| global_cache.borrow_mut().clear())
+    }
+
+    #[cfg(feature = "image-decoders")]
+    /// Removes the image loaded from the given path from the cache, for example because the file
+    /// has changed on disk. Returns true if the image was in the cache.
+    pub fn remove_from_cache(path: &std::path::Path) -> bool {
+        let path: SharedString = match path.to_str() {
+            Some(path) => path.into(),
+            None => return false,
+        };
+        self::cache::IMAGE_CACHE.with(|global_cache| global_cache.borrow_mut().remove_path(&path))
+    }
+
     /// Creates a new Image from the specified shared pixel buffer, where each pixel has three color
     /// channels (red, green and blue) encoded as u8.
     pub fn from_rgb8(buffer: SharedPixelBuffer<Rgb8Pixel>) -> Self {
@@ -602,6 +708,28 @@ pub fn from_rgba8_premultiplied(buffer: SharedPixelBuffer<Rgba8Pixel>) -> Self {
         })
     }
 
+    /// Creates a new Image from an existing OpenGL texture. The texture must be a 2D texture with
+    /// the RGBA format and pre-multiplied alpha, and it remains owned by the caller. The renderer samples
+    /// the texture directly when drawing the image, which allows showing video frames or camera
+    /// previews without copying them.
+    ///
+    /// Call [`crate::api::Window::request_redraw()`] after changing the contents of the texture, for
+    /// the renderer to pick up the new contents.
+    ///
+    /// Only the OpenGL based renderers (FemtoVG, and Skia with OpenGL) can render such an image. Other
+    /// renderers draw nothing.
+    ///
+    /// # Safety
+    ///
+    /// The texture must be valid in the OpenGL context of the window the image is shown in, and it
+    /// must not be deleted as long as the image is in use.
+    pub unsafe fn from_borrowed_gl_2d_rgba_texture(
+        texture_id: core::num::NonZeroU32,
+        size: IntSize,
+    ) -> Self {
+        Image(ImageInner::BorrowedOpenGLTexture(BorrowedOpenGLTexture { texture_id, size }))
+    }
+
     /// Returns the size of the Image in pixels.
     pub fn size(&self) -> IntSize {
         match &self.0 {
@@ -613,6 +741,30 @@ pub fn size(&self) -> IntSize {
             #[cfg(target_arch = "wasm32")]
             ImageInner::HTMLImage(htmlimage) => htmlimage.size().unwrap_or_default(),
             ImageInner::BackendStorage(x) => vtable::VRc::borrow(x).size(),
+            ImageInner::BorrowedOpenGLTexture(texture) => texture.size,
+            #[cfg(feature = "image-decoders")]
+            ImageInner::AnimatedImage(animated) => animated.size(),
+        }
+    }
+
+    /// Returns the number of frames of an animated image, or 1 for images that are not animated.
+    pub(crate) fn frame_count(&self) -> usize {
+        match &self.0 {
+            #[cfg(feature = "image-decoders")]
+            ImageInner::AnimatedImage(animated) => animated.frame_count(),
+            _ => 1,
+        }
+    }
+
+    /// Returns the frame at the given index of an animated image, together with the time
+    /// it is shown for. Images that are not animated have a single frame that is shown forever.
+    pub(crate) fn frame(&self, _index: usize) -> (Image, Option<core::time::Duration>) {
+        match &self.0 {
+            #[cfg(feature = "image-decoders")]
+            ImageInner::AnimatedImage(animated) => {
+                (animated.frame_image(_index), Some(animated.frame(_index).duration))
+            }
+            _ => (self.clone(), None),
         }
     }
 
@@ -634,11 +786,45 @@ pub fn path(&self) -> Option<&std::path::Path> {
                 ImageCacheKey::Path(path) => Some(std::path::Path::new(path.as_str())),
                 _ => None,
             },
+            ImageInner::AnimatedImage(animated) => match animated.cache_key() {
+                ImageCacheKey::Path(path) => Some(std::path::Path::new(path.as_str())),
+                _ => None,
+            },
             _ => None,
         }
     }
 }
 
+/// Load an image from a path in a background thread, and return the placeholder until it is
+/// decoded. The bindings that call this function are evaluated again once the image is ready.
+/// This is called by the generated code for the `source` of the `Image` elements that have a
+/// `placeholder`.
+#[cfg(feature = "image-decoders")]
+pub fn load_image_from_path_in_background(path: &SharedString, placeholder: Image) -> Image {
+    self::cache::IMAGE_CACHE
+        .with(|global_cache| global_cache.borrow_mut().load_image_from_path_in_background(path))
+        .unwrap_or(placeholder)
+}
+
+/// Load an image from a path, unless it is a raster image larger than `max_size`, in pixels, or an
+/// image whose size can't be read from its header. The decoder is limited to that size as well.
+/// SVG images are not limited, since they are rendered at the size they are displayed at.
+/// This is called by the interpreter to apply the image size limit of its sandbox.
+#[doc(hidden)]
+#[cfg(feature = "image-decoders")]
+pub fn load_image_from_path_with_max_size(
+    path: &std::path::Path,
+    max_size: (u32, u32),
+) -> Result<Image, LoadImageError> {
+    self::cache::IMAGE_CACHE.with(|global_cache| {
+        let path: SharedString = path.to_str().ok_or(LoadImageError(()))?.into();
+        global_cache
+            .borrow_mut()
+            .load_image_from_path_with_max_size(&path, max_size)
+            .ok_or(LoadImageError(()))
+    })
+}
+
 /// Load an image from an image embedded in the binary.
 /// This is called by the generated code.
 #[cfg(feature = "image-decoders")]
@@ -663,6 +849,48 @@ fn test_image_size_from_buffer_without_backend() {
         let image = Image::from_rgb8(buffer);
         assert_eq!(image.size(), [320, 200].into())
     }
+    {
+        let texture_id = core::num::NonZeroU32::new(42).unwrap();
+        let image = unsafe { Image::from_borrowed_gl_2d_rgba_texture(texture_id, [64, 48].into()) };
+        assert_eq!(image.size(), [64, 48].into());
+        assert_eq!(ImageCacheKey::new((&image).into()), None);
+    }
+}
+
+#[cfg(feature = "image-decoders")]
+#[test]
+fn test_image_cache_control() {
+    let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("../../examples/printerdemo/ui/images/cat.jpg");
+    let image = Image::load_from_path(&path).unwrap();
+    assert!(Image::remove_from_cache(&path));
+    assert!(!Image::remove_from_cache(&path));
+    // Images that are in use stay valid when they are evicted from the cache
+    assert_eq!(image.size(), [320, 480].into());
+
+    Image::load_from_path(&path).unwrap();
+    Image::set_cache_size_limit(1);
+    assert!(!Image::remove_from_cache(&path));
+    Image::set_cache_size_limit(5 * 1024 * 1024);
+}
+
+#[cfg(all(feature = "image-decoders", not(target_arch = "wasm32")))]
+#[test]
+fn test_load_image_with_max_size() {
+    let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("../../examples/gallery/thumbsup.png");
+    assert!(load_image_from_path_with_max_size(&path, (100, 200)).is_err());
+    assert!(load_image_from_path_with_max_size(&path, (200, 100)).is_err());
+    let image = load_image_from_path_with_max_size(&path, (160, 160)).unwrap();
+    assert_eq!(image.size(), [160, 160].into());
+    // The size of the cached image is checked too
+    assert!(load_image_from_path_with_max_size(&path, (100, 100)).is_err());
+
+    let broken =
+        std::env::temp_dir().join(format!("slint_broken_image_{}.png", std::process::id()));
+    std::fs::write(&broken, "not an image").unwrap();
+    assert!(load_image_from_path_with_max_size(&broken, (100, 100)).is_err());
+    std::fs::remove_file(&broken).ok();
 }
 
 /// Return an size that can be used to render an image in a buffer that matches a given ImageFit
@@ -713,6 +941,15 @@ struct Rgba8Pixel {
         )
     }
 
+    #[no_mangle]
+    pub unsafe extern "C" fn slint_image_load_from_path_in_background(
+        path: &SharedString,
+        placeholder: &Image,
+        image: *mut Image,
+    ) {
+        std::ptr::write(image, super::load_image_from_path_in_background(path, placeholder.clone()))
+    }
+
     #[no_mangle]
     pub unsafe extern "C" fn slint_image_load_from_embedded_data(
         data: Slice<'static, u8>,
@@ -722,6 +959,21 @@ struct Rgba8Pixel {
         std::ptr::write(image, super::load_image_from_embedded_data(data, format));
     }
 
+    #[no_mangle]
+    pub unsafe extern "C" fn slint_image_from_borrowed_gl_2d_rgba_texture(
+        texture_id: u32,
+        size: IntSize,
+        image: *mut Image,
+    ) {
+        std::ptr::write(
+            image,
+            match core::num::NonZeroU32::new(texture_id) {
+                Some(texture_id) => Image::from_borrowed_gl_2d_rgba_texture(texture_id, size),
+                None => Image::default(),
+            },
+        )
+    }
+
     #[no_mangle]
     pub unsafe extern "C" fn slint_image_size(image: &Image) -> IntSize {
         image.size()
@@ -0,0 +1,199 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+/*!
+This module contains the support for decoding animated images, such as animated GIF, PNG and WebP files.
+*/
+
+#![cfg(feature = "image-decoders")]
+
+use super::{Image, ImageCacheKey, ImageInner, SharedImageBuffer, SharedPixelBuffer};
+use alloc::vec::Vec;
+use image::AnimationDecoder;
+
+/// A single frame of an animated image.
+pub struct AnimationFrame {
+    /// The pixels of the frame, already composed with the previous frames.
+    pub buffer: SharedImageBuffer,
+    /// For how long the frame is shown before moving on to the next one.
+    pub duration: core::time::Duration,
+}
+
+/// The decoded frames of an animated image.
+pub struct AnimatedImage {
+    frames: Vec<AnimationFrame>,
+    cache_key: ImageCacheKey,
+}
+
+impl super::OpaqueImage for AnimatedImage {
+    fn size(&self) -> crate::graphics::IntSize {
+        self.size()
+    }
+    fn cache_key(&self) -> ImageCacheKey {
+        self.cache_key.clone()
+    }
+}
+
+impl core::fmt::Debug for AnimatedImage {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("AnimatedImage").field("frame_count", &self.frames.len()).finish()
+    }
+}
+
+impl AnimatedImage {
+    /// Returns the size of the first frame. All frames of an animation have the same size.
+    pub fn size(&self) -> crate::graphics::IntSize {
+        self.frames.first().map_or_else(Default::default, |frame| frame.buffer.size())
+    }
+
+    pub fn cache_key(&self) -> &ImageCacheKey {
+        &self.cache_key
+    }
+
+    /// Returns the number of frames in the animation. This is always at least two.
+    pub fn frame_count(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Returns the frame at the given index, wrapping around at the end of the animation.
+    pub fn frame(&self, index: usize) -> &AnimationFrame {
+        &self.frames[index % self.frames.len()]
+    }
+
+    /// Returns an Image that shows the frame at the given index.
+    pub fn frame_image(&self, index: usize) -> Image {
+        // The frames don't have a cache key of their own, they are owned by the animated image.
+        Image(ImageInner::EmbeddedImage {
+            cache_key: ImageCacheKey::Invalid,
+            buffer: self.frame(index).buffer.clone(),
+        })
+    }
+
+    /// Returns the memory used by the decoded frames, in bytes.
+    pub(crate) fn size_in_bytes(&self) -> usize {
+        self.frames
+            .iter()
+            .map(|frame| match &frame.buffer {
+                SharedImageBuffer::RGB8(pixels) => pixels.as_bytes().len(),
+                SharedImageBuffer::RGBA8(pixels) => pixels.as_bytes().len(),
+                SharedImageBuffer::RGBA8Premultiplied(pixels) => pixels.as_bytes().len(),
+            })
+            .sum()
+    }
+}
+
+/// The result of decoding a file that may contain an animation.
+pub enum DecodedImage {
+    /// The file contains more than one frame.
+    Animated(AnimatedImage),
+    /// The file contains a single frame only.
+    Still(SharedImageBuffer),
+}
+
+/// Decodes the image in `reader` as an animation, if the format supports animations and the
+/// file is animated. Returns `Ok(None)` if the image should be decoded as a still image instead.
+fn decode<R: std::io::BufRead + std::io::Seek>(
+    reader: R,
+    format: image::ImageFormat,
+    cache_key: ImageCacheKey,
+) -> image::ImageResult<Option<DecodedImage>> {
+    let frames = match format {
+        image::ImageFormat::Gif => image::codecs::gif::GifDecoder::new(reader)?.into_frames(),
+        image::ImageFormat::Png => {
+            let decoder = image::codecs::png::PngDecoder::new(reader)?;
+            if !decoder.is_apng() {
+                return Ok(None);
+            }
+            decoder.apng().into_frames()
+        }
+        image::ImageFormat::WebP => {
+            let decoder = image::codecs::webp::WebPDecoder::new(reader)?;
+            if !decoder.has_animation() {
+                return Ok(None);
+            }
+            decoder.into_frames()
+        }
+        _ => return Ok(None),
+    };
+
+    let mut frames = frames
+        .map(|frame| {
+            let frame = frame?;
+            let (numerator, denominator) = frame.delay().numer_denom_ms();
+            let duration = core::time::Duration::from_millis(
+                (numerator as u64).checked_div(denominator as u64).unwrap_or_default(),
+            );
+            let buffer = frame.into_buffer();
+            Ok(AnimationFrame {
+                buffer: SharedImageBuffer::RGBA8(SharedPixelBuffer::clone_from_slice(
+                    buffer.as_raw(),
+                    buffer.width(),
+                    buffer.height(),
+                )),
+                duration,
+            })
+        })
+        .collect::<image::ImageResult<Vec<_>>>()?;
+
+    Ok(match frames.len() {
+        0 => None,
+        1 => Some(DecodedImage::Still(frames.remove(0).buffer)),
+        _ => Some(DecodedImage::Animated(AnimatedImage { frames, cache_key })),
+    })
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn load_from_path(
+    path: &std::path::Path,
+    cache_key: ImageCacheKey,
+) -> image::ImageResult<Option<DecodedImage>> {
+    let format = match image::ImageFormat::from_path(path) {
+        Ok(format) => format,
+        Err(_) => return Ok(None),
+    };
+    if !matches!(
+        format,
+        image::ImageFormat::Gif | image::ImageFormat::Png | image::ImageFormat::WebP
+    ) {
+        return Ok(None);
+    }
+    let file = std::io::BufReader::new(std::fs::File::open(path)?);
+    decode(file, format, cache_key)
+}
+
+pub fn load_from_data(
+    data: &[u8],
+    format: image::ImageFormat,
+    cache_key: ImageCacheKey,
+) -> image::ImageResult<Option<DecodedImage>> {
+    decode(std::io::Cursor::new(data), format, cache_key)
+}
+
+#[test]
+fn test_decode_animated_gif() {
+    let mut data = Vec::new();
+    {
+        let mut encoder = image::codecs::gif::GifEncoder::new(&mut data);
+        let frames = [[255, 0, 0, 255], [0, 0, 255, 255]].into_iter().map(|color| {
+            image::Frame::from_parts(
+                image::RgbaImage::from_pixel(4, 2, image::Rgba(color)),
+                0,
+                0,
+                image::Delay::from_numer_denom_ms(40, 1),
+            )
+        });
+        encoder.encode_frames(frames).unwrap();
+    }
+
+    let cache_key = ImageCacheKey::EmbeddedData(data.as_ptr() as usize);
+    let animated = match load_from_data(&data, image::ImageFormat::Gif, cache_key).unwrap() {
+        Some(DecodedImage::Animated(animated)) => animated,
+        _ => panic!("expected an animated image"),
+    };
+    assert_eq!(animated.frame_count(), 2);
+    assert_eq!(animated.size(), [4, 2].into());
+    assert_eq!(animated.frame(1).duration, core::time::Duration::from_millis(40));
+    // Indices wrap around at the end of the animation
+    assert_eq!(animated.frame_image(2), animated.frame_image(0));
+    assert_ne!(animated.frame_image(1), animated.frame_image(0));
+}
@@ -25,6 +25,8 @@ fn weight(&self, _: &ImageCacheKey, value: &ImageInner) -> usize {
             ImageInner::HTMLImage(_) => 512, // Something... the web browser maintainers its own cache. The purpose of this cache is to reduce the amount of DOM elements.
             ImageInner::StaticTextures(_) => 0,
             ImageInner::BackendStorage(x) => vtable::VRc::borrow(x).size().area() as usize,
+            ImageInner::BorrowedOpenGLTexture(..) => 0, // Owned by the application
+            ImageInner::AnimatedImage(animated) => animated.size_in_bytes(),
         }
     }
 }
@@ -56,7 +58,7 @@ impl ImageCache {
     fn lookup_image_in_cache_or_create(
         &mut self,
         cache_key: ImageCacheKey,
-        image_create_fn: impl Fn(ImageCacheKey) -> Option<ImageInner>,
+        image_create_fn: impl FnOnce(ImageCacheKey) -> Option<ImageInner>,
     ) -> Option<Image> {
         Some(Image(if let Some(entry) = self.0.get(&cache_key) {
             entry.clone()
@@ -80,35 +82,144 @@ pub(crate) fn load_image_from_path(&mut self, path: &SharedString) -> Option<Ima
         });
         #[cfg(not(target_arch = "wasm32"))]
         return self.lookup_image_in_cache_or_create(cache_key, |cache_key| {
-            if cfg!(feature = "svg") {
-                if path.ends_with(".svg") || path.ends_with(".svgz") {
-                    return Some(ImageInner::Svg(vtable::VRc::new(
-                        super::svg::load_from_path(path, cache_key).map_or_else(
-                            |err| {
-                                eprintln!("Error loading SVG from {}: {}", &path, err);
-                                None
-                            },
-                            Some,
-                        )?,
-                    )));
-                }
+            #[cfg(feature = "svg")]
+            if is_svg_path(path) {
+                return Some(ImageInner::Svg(vtable::VRc::new(
+                    super::svg::load_from_path(path, cache_key).map_or_else(
+                        |err| {
+                            eprintln!("Error loading SVG from {}: {}", &path, err);
+                            None
+                        },
+                        Some,
+                    )?,
+                )));
             }
 
-            image::open(std::path::Path::new(&path.as_str())).map_or_else(
-                |decode_err| {
-                    eprintln!("Error loading image from {}: {}", &path, decode_err);
-                    None
-                },
-                |image| {
-                    Some(ImageInner::EmbeddedImage {
-                        cache_key,
-                        buffer: dynamic_image_to_shared_image_buffer(image),
-                    })
-                },
-            )
+            decode_image_from_path(path, cache_key.clone(), None)
+                .map(|decoded| decoded_image_to_image_inner(decoded, cache_key))
         });
     }
 
+    /// Loads the image at the given path like [`Self::load_image_from_path`], unless it is a raster
+    /// image larger than `max_size`, or whose size can't be read. See
+    /// [`super::load_image_from_path_with_max_size`].
+    pub(crate) fn load_image_from_path_with_max_size(
+        &mut self,
+        path: &SharedString,
+        max_size: (u32, u32),
+    ) -> Option<Image> {
+        if path.is_empty() {
+            return None;
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            // The browser decodes the image, so its size can't be checked before
+            let _ = max_size;
+            eprintln!("Cannot load image from {}: its size can't be checked", &path);
+            return None;
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            #[cfg(feature = "svg")]
+            if is_svg_path(path) {
+                return self.load_image_from_path(path);
+            }
+            let image = self.lookup_image_in_cache_or_create(
+                ImageCacheKey::Path(path.clone()),
+                |cache_key| {
+                    decode_image_from_path(path, cache_key.clone(), Some(max_size))
+                        .map(|decoded| decoded_image_to_image_inner(decoded, cache_key))
+                },
+            )?;
+            // The image may have been decoded without a limit before, and be in the cache
+            let size = image.size();
+            if size.width > max_size.0 || size.height > max_size.1 {
+                eprintln!(
+                    "Cannot load image from {}: it is {}x{} pixels, more than the maximum of {}x{}",
+                    &path, size.width, size.height, max_size.0, max_size.1
+                );
+                return None;
+            }
+            Some(image)
+        }
+    }
+
+    /// Loads the image at the given path like [`Self::load_image_from_path`], but decodes it in
+    /// a background thread. The callback is invoked from the event loop once the image is loaded.
+    pub(crate) fn load_image_from_path_async(
+        &mut self,
+        path: SharedString,
+        callback: impl FnOnce(Option<Image>) + 'static,
+    ) {
+        if let Some(entry) = self.0.get(&ImageCacheKey::Path(path.clone())) {
+            let image = Image(entry.clone());
+            crate::timers::Timer::single_shot(Default::default(), move || callback(Some(image)));
+            return;
+        }
+
+        if path.is_empty() || !can_load_in_background(&path) {
+            crate::timers::Timer::single_shot(Default::default(), move || {
+                callback(IMAGE_CACHE.with(|cache| cache.borrow_mut().load_image_from_path(&path)))
+            });
+            return;
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        start_background_load(path, Some(Box::new(callback)));
+    }
+
+    /// Returns the image at the given path if it was already loaded, or an empty image if it
+    /// can't be loaded. Otherwise, starts decoding it in a background thread and returns None.
+    /// When called from a binding, the binding is evaluated again once the image is decoded.
+    pub(crate) fn load_image_from_path_in_background(
+        &mut self,
+        path: &SharedString,
+    ) -> Option<Image> {
+        if let Some(entry) = self.0.get(&ImageCacheKey::Path(path.clone())) {
+            return Some(Image(entry.clone()));
+        }
+        if path.is_empty() || !can_load_in_background(path) {
+            return Some(self.load_image_from_path(path).unwrap_or_default());
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let failed = BACKGROUND_LOADS.with(|loads| loads.borrow().failed.contains(path));
+            if failed {
+                return Some(Image::default());
+            }
+            start_background_load(path.clone(), None);
+            BACKGROUND_LOAD_COUNT.with(|count| count.as_ref().get());
+        }
+        None
+    }
+
+    /// Sets the maximum weight of the cache, in bytes, discarding the least recently used
+    /// images that exceed the new limit.
+    pub(crate) fn set_size_limit(&mut self, bytes: usize) {
+        self.0.resize(core::num::NonZeroUsize::new(bytes.max(1)).unwrap());
+    }
+
+    /// Returns the memory used by the images in the cache, in bytes.
+    pub(crate) fn size_in_bytes(&self) -> usize {
+        self.0.weight()
+    }
+
+    /// Removes all images from the cache, and forgets the images that failed to load in the
+    /// background, so that they are tried again.
+    pub(crate) fn clear(&mut self) {
+        self.0.clear();
+        #[cfg(not(target_arch = "wasm32"))]
+        BACKGROUND_LOADS.with(|loads| loads.borrow_mut().failed.clear());
+    }
+
+    /// Removes the image loaded from the given path from the cache, and returns true if it was cached.
+    pub(crate) fn remove_path(&mut self, path: &SharedString) -> bool {
+        #[cfg(not(target_arch = "wasm32"))]
+        BACKGROUND_LOADS.with(|loads| loads.borrow_mut().failed.remove(path));
+        self.0.pop(&ImageCacheKey::Path(path.clone())).is_some()
+    }
+
     pub(crate) fn load_image_from_embedded_data(
         &mut self,
         data: Slice<'static, u8>,
@@ -132,6 +243,19 @@ pub(crate) fn load_image_from_embedded_data(
             let format = std::str::from_utf8(format.as_slice())
                 .ok()
                 .and_then(image::ImageFormat::from_extension);
+            if let Some(format) = format {
+                match super::animated::load_from_data(data.as_slice(), format, cache_key.clone()) {
+                    Ok(Some(decoded)) => {
+                        return Some(decoded_image_to_image_inner(decoded, cache_key))
+                    }
+                    Ok(None) => {}
+                    Err(decode_err) => {
+                        eprintln!("Error decoding embedded image: {}", decode_err);
+                        return None;
+                    }
+                }
+            }
+
             let maybe_image = if let Some(format) = format {
                 image::load_from_memory_with_format(data.as_slice(), format)
             } else {
@@ -152,6 +276,199 @@ pub(crate) fn load_image_from_embedded_data(
     }
 }
 
+/// Returns true if the image at the given path is decoded in a background thread by
+/// [`ImageCache::load_image_from_path_async`] and [`ImageCache::load_image_from_path_in_background`].
+/// SVG files are only parsed when loaded and rendered later, so there is nothing to gain from
+/// loading them in a thread. Neither are there threads in the browser.
+fn can_load_in_background(_path: &str) -> bool {
+    #[cfg(all(feature = "svg", not(target_arch = "wasm32")))]
+    if is_svg_path(_path) {
+        return false;
+    }
+    cfg!(not(target_arch = "wasm32"))
+}
+
+/// A job of the [`DECODER_POOL`]
+#[cfg(not(target_arch = "wasm32"))]
+type DecodeJob = Box<dyn FnOnce() + Send>;
+
+/// The threads that decode the images loaded in the background. There are only a few of them, so
+/// that loading many images at once, for example when scrolling through a grid of photos, queues
+/// the images instead of starting a thread for each.
+#[cfg(not(target_arch = "wasm32"))]
+static DECODER_POOL: once_cell::sync::Lazy<std::sync::Mutex<std::sync::mpsc::Sender<DecodeJob>>> =
+    once_cell::sync::Lazy::new(|| {
+        let (sender, receiver) = std::sync::mpsc::channel::<DecodeJob>();
+        let receiver = std::sync::Arc::new(std::sync::Mutex::new(receiver));
+        let thread_count = std::thread::available_parallelism().map_or(2, |n| n.get()).min(4);
+        for i in 0..thread_count {
+            let receiver = receiver.clone();
+            let spawned = std::thread::Builder::new()
+                .name(format!("slint image decoder {}", i))
+                .spawn(move || loop {
+                    // The lock is released before running the job
+                    let job = receiver.lock().unwrap().recv();
+                    match job {
+                        Ok(job) => job(),
+                        Err(_) => return,
+                    }
+                });
+            if let Err(err) = spawned {
+                crate::debug_log!("Cannot start a thread to decode images: {}", err);
+            }
+        }
+        std::sync::Mutex::new(sender)
+    });
+
+/// The images being decoded in the background, for the thread that runs the event loop
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Default)]
+struct BackgroundLoads {
+    /// The paths being decoded, with the callbacks to invoke once they are loaded
+    pending: std::collections::HashMap<SharedString, Vec<Box<dyn FnOnce(Option<Image>)>>>,
+    /// The paths that failed to load, so that they are not decoded again on every evaluation of
+    /// the bindings that use them
+    failed: std::collections::HashSet<SharedString>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+thread_local!(static BACKGROUND_LOADS: core::cell::RefCell<BackgroundLoads> = Default::default());
+
+/// Incremented each time an image loaded in the background is ready, so that the bindings that
+/// returned a placeholder in the meantime are evaluated again
+#[cfg(not(target_arch = "wasm32"))]
+thread_local!(static BACKGROUND_LOAD_COUNT: core::pin::Pin<Box<crate::Property<u32>>> =
+    Box::pin(crate::Property::new(0)));
+
+/// Decodes the image at the given path with the [`DECODER_POOL`], unless it is already being
+/// decoded, and invokes the callback from the event loop once it is in the cache.
+#[cfg(not(target_arch = "wasm32"))]
+fn start_background_load(path: SharedString, callback: Option<Box<dyn FnOnce(Option<Image>)>>) {
+    let already_pending = BACKGROUND_LOADS.with(|loads| {
+        let mut loads = loads.borrow_mut();
+        let already_pending = loads.pending.contains_key(&path);
+        loads.pending.entry(path.clone()).or_default().extend(callback);
+        already_pending
+    });
+    if already_pending {
+        return;
+    }
+    let job = Box::new(move || {
+        let cache_key = ImageCacheKey::Path(path.clone());
+        let decoded = decode_image_from_path(&path, cache_key.clone(), None);
+        crate::api::invoke_from_event_loop(move || {
+            finish_background_load(path, cache_key, decoded)
+        })
+        .ok();
+    });
+    if let Err(err) = DECODER_POOL.lock().unwrap().send(job) {
+        // There is no thread to decode the image, so decode it right away
+        (err.0)();
+    }
+}
+
+/// Puts an image decoded in the background in the cache, and notifies the callbacks and the
+/// bindings waiting for it
+#[cfg(not(target_arch = "wasm32"))]
+fn finish_background_load(
+    path: SharedString,
+    cache_key: ImageCacheKey,
+    decoded: Option<super::animated::DecodedImage>,
+) {
+    let image = IMAGE_CACHE.with(|cache| {
+        cache.borrow_mut().lookup_image_in_cache_or_create(cache_key, |cache_key| {
+            decoded.map(|decoded| decoded_image_to_image_inner(decoded, cache_key))
+        })
+    });
+    let callbacks = BACKGROUND_LOADS.with(|loads| {
+        let mut loads = loads.borrow_mut();
+        if image.is_none() {
+            loads.failed.insert(path.clone());
+        }
+        loads.pending.remove(&path).unwrap_or_default()
+    });
+    BACKGROUND_LOAD_COUNT.with(|count| count.as_ref().set(count.as_ref().get() + 1));
+    for callback in callbacks {
+        callback(image.clone());
+    }
+}
+
+/// Decodes the raster image at the given path. This doesn't access the cache, so that it
+/// can be called from any thread.
+///
+/// With a `max_size`, the size of the image is read from its header first, and the image is not
+/// decoded if it is larger or if its size can't be read. The decoder is limited to that size too.
+#[cfg(not(target_arch = "wasm32"))]
+fn decode_image_from_path(
+    path: &SharedString,
+    cache_key: ImageCacheKey,
+    max_size: Option<(u32, u32)>,
+) -> Option<super::animated::DecodedImage> {
+    let std_path = std::path::Path::new(path.as_str());
+    if let Some((max_width, max_height)) = max_size {
+        match image::image_dimensions(std_path) {
+            Ok((width, height)) if width <= max_width && height <= max_height => {}
+            Ok((width, height)) => {
+                eprintln!(
+                    "Cannot load image from {}: it is {}x{} pixels, more than the maximum of {}x{}",
+                    &path, width, height, max_width, max_height
+                );
+                return None;
+            }
+            Err(err) => {
+                eprintln!("Cannot load image from {}: its size can't be read: {}", &path, err);
+                return None;
+            }
+        }
+    }
+
+    match super::animated::load_from_path(std_path, cache_key) {
+        Ok(Some(decoded)) => return Some(decoded),
+        Ok(None) => {}
+        Err(decode_err) => {
+            eprintln!("Error loading image from {}: {}", &path, decode_err);
+            return None;
+        }
+    }
+
+    image::io::Reader::open(std_path)
+        .map_err(image::ImageError::IoError)
+        .and_then(|mut reader| {
+            if let Some((max_width, max_height)) = max_size {
+                let mut limits = image::io::Limits::default();
+                limits.max_image_width = Some(max_width);
+                limits.max_image_height = Some(max_height);
+                reader.limits(limits);
+            }
+            reader.decode()
+        })
+        .map_or_else(
+            |decode_err| {
+                eprintln!("Error loading image from {}: {}", &path, decode_err);
+                None
+            },
+            |image| {
+                Some(super::animated::DecodedImage::Still(dynamic_image_to_shared_image_buffer(
+                    image,
+                )))
+            },
+        )
+}
+
+fn decoded_image_to_image_inner(
+    decoded: super::animated::DecodedImage,
+    cache_key: ImageCacheKey,
+) -> ImageInner {
+    match decoded {
+        super::animated::DecodedImage::Animated(animated) => {
+            ImageInner::AnimatedImage(vtable::VRc::new(animated))
+        }
+        super::animated::DecodedImage::Still(buffer) => {
+            ImageInner::EmbeddedImage { cache_key, buffer }
+        }
+    }
+}
+
 fn dynamic_image_to_shared_image_buffer(dynamic_image: image::DynamicImage) -> SharedImageBuffer {
     if dynamic_image.color().has_alpha() {
         let rgba8image = dynamic_image.to_rgba8();
@@ -178,3 +495,12 @@ pub fn replace_cached_image(key: ImageCacheKey, value: ImageInner) {
     let _ =
         IMAGE_CACHE.with(|global_cache| global_cache.borrow_mut().0.put_with_weight(key, value));
 }
+
+/// Returns true if the file extension of the given path denotes an SVG or compressed SVG file.
+#[cfg(all(feature = "svg", not(target_arch = "wasm32")))]
+fn is_svg_path(path: &str) -> bool {
+    std::path::Path::new(path)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map_or(false, |ext| ext.eq_ignore_ascii_case("svg") || ext.eq_ignore_ascii_case("svgz"))
+}
@@ -96,6 +96,8 @@ pub fn new(window_adapter: Weak<dyn WindowAdapter>, winsys_info: &str) -> Option
             return None;
         }
 
+        crate::profiling::set_enabled(true);
+
         let collector = Rc::new(Self {
             collected_frame_data_since_second_ago: Default::default(),
             update_timer: Default::default(),
@@ -135,6 +137,9 @@ pub fn new(window_adapter: Weak<dyn WindowAdapter>, winsys_info: &str) -> Option
                         last_frame_details.insert_str(0, "details from last frame: ");
                     }
                 }
+                if let Some(statistics) = crate::profiling::last_frame_statistics() {
+                    last_frame_details = format!("{} [{}]", last_frame_details, statistics);
+                }
 
                 if this.output_console {
                     eprintln!(
@@ -180,10 +185,12 @@ pub fn measure_frame_rendered(
         self.trim_frame_data_to_second_boundary();
 
         if self.output_overlay {
-            renderer.draw_string(
-                &format!("FPS: {}", self.collected_frame_data_since_second_ago.borrow().len()),
-                crate::Color::from_rgb_u8(0, 128, 128),
-            );
+            let fps = self.collected_frame_data_since_second_ago.borrow().len();
+            let text = match crate::profiling::last_frame_statistics() {
+                Some(statistics) => format!("FPS: {} - {}", fps, statistics),
+                None => format!("FPS: {}", fps),
+            };
+            renderer.draw_string(&text, crate::Color::from_rgb_u8(0, 128, 128));
         }
     }
 }
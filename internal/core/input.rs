@@ -53,38 +53,58 @@ pub fn position(&self) -> Option<LogicalPoint> {
         }
     }
 
-    /// Translate the position by the given value
-    pub fn translate(&mut self, vec: LogicalVector) {
-        let pos = match self {
+    fn position_mut(&mut self) -> Option<&mut LogicalPoint> {
+        match self {
             MouseEvent::Pressed { position, .. } => Some(position),
             MouseEvent::Released { position, .. } => Some(position),
             MouseEvent::Moved { position } => Some(position),
             MouseEvent::Wheel { position, .. } => Some(position),
             MouseEvent::Exit => None,
-        };
-        if let Some(pos) = pos {
+        }
+    }
+
+    /// Translate the position by the given value
+    pub fn translate(&mut self, vec: LogicalVector) {
+        if let Some(pos) = self.position_mut() {
             *pos += vec;
         }
     }
 }
 
-impl From<crate::api::WindowEvent> for MouseEvent {
-    fn from(event: crate::api::WindowEvent) -> Self {
-        match event {
-            crate::api::WindowEvent::PointerPressed { position, button } => {
-                MouseEvent::Pressed { position: position.to_euclid().cast(), button }
-            }
-            crate::api::WindowEvent::PointerReleased { position, button } => {
-                MouseEvent::Released { position: position.to_euclid().cast(), button }
-            }
-            crate::api::WindowEvent::PointerMoved { position } => {
-                MouseEvent::Moved { position: position.to_euclid().cast() }
-            }
-            crate::api::WindowEvent::PointerScrolled { position, delta_x, delta_y } => {
-                MouseEvent::Wheel { position: position.to_euclid().cast(), delta_x, delta_y }
-            }
-            crate::api::WindowEvent::PointerExited => MouseEvent::Exit,
+/// Maps a position in the coordinates of the parent of the item to the coordinates of the children
+/// of the item, taking into account the transformation applied by a `Transform` element, if the
+/// renderer draws it. Returns None if the children can't be reached because the transformation
+/// can't be inverted.
+fn map_to_children(
+    item_rc: &ItemRc,
+    position: LogicalPoint,
+    window_adapter: &Rc<dyn WindowAdapter>,
+) -> Option<LogicalPoint> {
+    let position = position - item_rc.geometry().origin.to_vector();
+    match ItemRef::downcast_pin::<crate::items::Transform>(item_rc.borrow()) {
+        Some(transform) if window_adapter.renderer().supports_transformations() => {
+            transform.map_to_children(position)
         }
+        _ => Some(position),
+    }
+}
+
+/// Same as [`map_to_children`] for the position of the event. Returns false if the event can't
+/// be mapped.
+fn map_event_to_children(
+    item_rc: &ItemRc,
+    event: &mut MouseEvent,
+    window_adapter: &Rc<dyn WindowAdapter>,
+) -> bool {
+    match event.position_mut() {
+        Some(position) => match map_to_children(item_rc, *position, window_adapter) {
+            Some(mapped) => {
+                *position = mapped;
+                true
+            }
+            None => false,
+        },
+        None => true,
     }
 }
 
@@ -446,8 +466,7 @@ fn handle_mouse_grab(
             item.borrow().as_ref().input_event(MouseEvent::Exit, window_adapter, &item);
             return false;
         }
-        let g = item.geometry();
-        event.translate(-g.origin.to_vector());
+        map_event_to_children(&item, &mut event, window_adapter);
 
         let interested = matches!(
             it.1,
@@ -489,9 +508,7 @@ fn send_exit_events(
         let item = if let Some(item) = it.0.upgrade() { item } else { break };
         let g = item.geometry();
         let contains = pos.map_or(false, |p| g.contains(p));
-        if let Some(p) = pos.as_mut() {
-            *p -= g.origin.to_vector();
-        }
+        pos = pos.and_then(|p| map_to_children(&item, p, window_adapter));
         if !contains {
             item.borrow().as_ref().input_event(MouseEvent::Exit, window_adapter, &item);
         }
@@ -570,9 +587,10 @@ fn send_mouse_event_to_item(
 ) -> VisitChildrenResult {
     let item = item_rc.borrow();
     let geom = item_rc.geometry();
-    // translated in our coordinate
+    // translated in the coordinates of the children
     let mut event_for_children = mouse_event;
-    event_for_children.translate(-geom.origin.to_vector());
+    let children_reachable =
+        map_event_to_children(&item_rc, &mut event_for_children, window_adapter);
 
     let filter_result = if mouse_event.position().map_or(false, |p| geom.contains(p))
         || crate::item_rendering::is_clipping_item(item)
@@ -617,7 +635,7 @@ fn send_mouse_event_to_item(
     };
 
     result.item_stack.push((item_rc.downgrade(), filter_result));
-    if forward_to_children {
+    if forward_to_children && children_reachable {
         let mut actual_visitor =
             |component: &ComponentRc, index: usize, _: Pin<ItemRef>| -> VisitChildrenResult {
                 send_mouse_event_to_item(
@@ -673,6 +691,310 @@ fn send_mouse_event_to_item(
     }
 }
 
+/// The state of a drag and drop operation in progress over a window
+#[derive(Default)]
+pub struct DragState {
+    /// The mime type of the dragged data
+    pub mime_type: SharedString,
+    /// The dragged data
+    pub data: SharedString,
+    /// The DropArea that currently contains the drag, if any
+    drop_area: Option<ItemWeak>,
+}
+
+impl DragState {
+    /// Create the state for a new drag operation
+    pub fn new(mime_type: SharedString, data: SharedString) -> Self {
+        Self { mime_type, data, drop_area: None }
+    }
+}
+
+/// Dispatch a pointer event of a drag and drop operation to the `DropArea` under the pointer.
+/// `MouseEvent::Released` drops the data, and `MouseEvent::Exit` cancels the operation.
+/// Returns true if the data was dropped on a `DropArea`.
+pub(crate) fn process_drag_event(
+    component: ComponentRc,
+    mouse_event: MouseEvent,
+    state: &mut DragState,
+    window_adapter: &Rc<dyn WindowAdapter>,
+) -> bool {
+    let mut target = None;
+    if let Some(position) = mouse_event.position() {
+        let is_drop_area = |item: Pin<ItemRef>| {
+            ItemRef::downcast_pin::<crate::items::DropArea>(item).map_or(false, |d| d.enabled())
+        };
+        find_item_at(
+            &ItemRc::new(component, 0),
+            position,
+            &is_drop_area,
+            &mut target,
+            window_adapter,
+        );
+    }
+    let previous = state.drop_area.take().and_then(|weak| weak.upgrade());
+    if let Some(previous) = previous {
+        if target.as_ref().map_or(true, |(target, _)| *target != previous) {
+            if let Some(drop_area) = previous.downcast::<crate::items::DropArea>() {
+                drop_area.as_pin_ref().drag_left();
+            }
+        }
+    }
+    let (target, position) = match target {
+        Some(target) => target,
+        None => return false,
+    };
+    let drop_area = match target.downcast::<crate::items::DropArea>() {
+        Some(drop_area) => drop_area,
+        None => return false,
+    };
+    let event = crate::items::DropEvent {
+        mime_type: state.mime_type.clone(),
+        data: state.data.clone(),
+        position: position.to_untyped(),
+    };
+    match mouse_event {
+        MouseEvent::Released { .. } => {
+            drop_area.as_pin_ref().handle_drop(event);
+            true
+        }
+        MouseEvent::Exit => false,
+        _ => {
+            drop_area.as_pin_ref().drag_moved(event);
+            state.drop_area = Some(target.downgrade());
+            false
+        }
+    }
+}
+
+/// Find the top-most item at the given position (relative to the item's parent) for which
+/// `predicate` returns true. The position relative to that item is also returned.
+fn find_item_at(
+    item_rc: &ItemRc,
+    position: LogicalPoint,
+    predicate: &dyn Fn(Pin<ItemRef>) -> bool,
+    found: &mut Option<(ItemRc, LogicalPoint)>,
+    window_adapter: &Rc<dyn WindowAdapter>,
+) -> VisitChildrenResult {
+    let item = item_rc.borrow();
+    let geom = item_rc.geometry();
+    let contains = geom.contains(position);
+    if !contains && crate::item_rendering::is_clipping_item(item) {
+        return VisitChildrenResult::CONTINUE;
+    }
+    let local_position = position - geom.origin.to_vector();
+
+    if let Some(children_position) = map_to_children(item_rc, position, window_adapter) {
+        let mut actual_visitor =
+            |component: &ComponentRc, index: usize, _: Pin<ItemRef>| -> VisitChildrenResult {
+                find_item_at(
+                    &ItemRc::new(component.clone(), index),
+                    children_position,
+                    predicate,
+                    found,
+                    window_adapter,
+                )
+            };
+        vtable::new_vref!(let mut actual_visitor : VRefMut<crate::item_tree::ItemVisitorVTable> for crate::item_tree::ItemVisitor = &mut actual_visitor);
+        let r = vtable::VRc::borrow_pin(&item_rc.component()).as_ref().visit_children_item(
+            item_rc.index() as isize,
+            crate::item_tree::TraversalOrder::FrontToBack,
+            actual_visitor,
+        );
+        if r.has_aborted() {
+            return r;
+        }
+    }
+
+    if contains && predicate(item) {
+        *found = Some((item_rc.clone(), local_position));
+        return VisitChildrenResult::abort(item_rc.index(), 0);
+    }
+    VisitChildrenResult::CONTINUE
+}
+
+/// The touch points currently in contact with a window, and the gesture they form, if any
+#[derive(Default)]
+pub struct TouchState {
+    /// The id and position of the touch points, in the order they were pressed
+    points: Vec<(u64, LogicalPoint)>,
+    gesture: Option<GestureState>,
+    /// Set when a gesture was recognized, until all the fingers are released, so that the
+    /// remaining finger doesn't interact with the items
+    suppress_pointer: bool,
+}
+
+struct GestureState {
+    area: ItemWeak,
+    /// The ids of the two touch points that form the gesture
+    ids: [u64; 2],
+    /// The offset to map window coordinates to coordinates relative to the GestureArea
+    offset: LogicalVector,
+    initial_distance: f32,
+    initial_angle: f32,
+    initial_center: LogicalPoint,
+    last_center: LogicalPoint,
+    last_time: crate::animations::Instant,
+    last_event: crate::items::GestureEvent,
+}
+
+impl GestureState {
+    fn new(
+        area: ItemWeak,
+        ids: [u64; 2],
+        offset: LogicalVector,
+        points: [LogicalPoint; 2],
+    ) -> Self {
+        let (distance, angle, center) = Self::measure(points);
+        let mut state = Self {
+            area,
+            ids,
+            offset,
+            initial_distance: distance,
+            initial_angle: angle,
+            initial_center: center,
+            last_center: center,
+            last_time: crate::animations::current_tick(),
+            last_event: Default::default(),
+        };
+        state.last_event = crate::items::GestureEvent {
+            center: (center + offset).to_untyped(),
+            scale: 1.,
+            ..Default::default()
+        };
+        state
+    }
+
+    /// Returns the distance between the points, the angle (in degrees) of the line between
+    /// them, and the point in the middle.
+    fn measure(points: [LogicalPoint; 2]) -> (f32, f32, LogicalPoint) {
+        #[cfg(not(feature = "std"))]
+        use num_traits::Float;
+        let delta = (points[1] - points[0]).cast::<f32>();
+        (delta.length(), delta.y.atan2(delta.x).to_degrees(), midpoint(points))
+    }
+
+    fn update(&mut self, points: [LogicalPoint; 2]) -> crate::items::GestureEvent {
+        let (distance, angle, center) = Self::measure(points);
+        let now = crate::animations::current_tick();
+        let elapsed = now.duration_since(self.last_time).as_secs_f32();
+        let velocity = if elapsed > 0. {
+            (center - self.last_center).cast::<f32>().to_untyped() / elapsed
+        } else {
+            self.last_event.velocity.to_vector().cast::<f32>()
+        };
+        let mut rotation = (angle - self.initial_angle) % 360.;
+        if rotation > 180. {
+            rotation -= 360.;
+        } else if rotation <= -180. {
+            rotation += 360.;
+        }
+        self.last_center = center;
+        self.last_time = now;
+        self.last_event = crate::items::GestureEvent {
+            center: (center + self.offset).to_untyped(),
+            scale: if self.initial_distance > 0. { distance / self.initial_distance } else { 1. },
+            rotation,
+            translation: (center - self.initial_center).to_point().to_untyped(),
+            velocity: velocity.cast::<crate::Coord>().to_point(),
+        };
+        self.last_event
+    }
+}
+
+fn midpoint(points: [LogicalPoint; 2]) -> LogicalPoint {
+    ((points[0].to_vector() + points[1].to_vector()) / (2 as crate::Coord)).to_point()
+}
+
+/// Process an event of the touch point identified by `id`. `event` is one of `MouseEvent::Pressed`,
+/// `MouseEvent::Moved` or `MouseEvent::Released`.
+///
+/// When two fingers touch a `GestureArea`, the gesture is delivered to its callbacks. Otherwise,
+/// the event of the first touch point is returned so that it can be processed like a mouse event.
+pub(crate) fn process_touch_event(
+    component: ComponentRc,
+    id: u64,
+    event: MouseEvent,
+    state: &mut TouchState,
+    window_adapter: &Rc<dyn WindowAdapter>,
+) -> Option<MouseEvent> {
+    let position = event.position()?;
+    let is_primary = state.points.first().map_or(true, |(first, _)| *first == id);
+    match event {
+        MouseEvent::Pressed { .. } => {
+            state.points.retain(|(p, _)| *p != id);
+            state.points.push((id, position));
+            if state.points.len() == 2 && state.gesture.is_none() {
+                let points = [state.points[0].1, state.points[1].1];
+                let center = midpoint(points);
+                let is_gesture_area = |item: Pin<ItemRef>| {
+                    ItemRef::downcast_pin::<crate::items::GestureArea>(item)
+                        .map_or(false, |g| g.enabled())
+                };
+                let mut target = None;
+                find_item_at(
+                    &ItemRc::new(component, 0),
+                    center,
+                    &is_gesture_area,
+                    &mut target,
+                    window_adapter,
+                );
+                if let Some((area, local_center)) = target {
+                    let ids = [state.points[0].0, state.points[1].0];
+                    let gesture =
+                        GestureState::new(area.downgrade(), ids, local_center - center, points);
+                    if let Some(g) = area.downcast::<crate::items::GestureArea>() {
+                        g.as_pin_ref().start_gesture(gesture.last_event);
+                    }
+                    state.gesture = Some(gesture);
+                    let was_suppressed = core::mem::replace(&mut state.suppress_pointer, true);
+                    // Cancel what the first finger was doing
+                    return (!was_suppressed).then(|| MouseEvent::Exit);
+                }
+            }
+            (is_primary && !state.suppress_pointer).then(|| event)
+        }
+        MouseEvent::Moved { .. } => {
+            if let Some(p) = state.points.iter_mut().find(|(p, _)| *p == id) {
+                p.1 = position;
+            }
+            if let Some(gesture) = state.gesture.as_mut().filter(|g| g.ids.contains(&id)) {
+                let points = gesture.ids.map(|id| {
+                    state.points.iter().find(|(p, _)| *p == id).map_or(position, |(_, pos)| *pos)
+                });
+                let event = gesture.update(points);
+                if let Some(g) = gesture
+                    .area
+                    .upgrade()
+                    .and_then(|area| area.downcast::<crate::items::GestureArea>())
+                {
+                    g.as_pin_ref().update_gesture(event);
+                }
+                return None;
+            }
+            (is_primary && !state.suppress_pointer).then(|| event)
+        }
+        MouseEvent::Released { .. } => {
+            state.points.retain(|(p, _)| *p != id);
+            if state.gesture.as_ref().map_or(false, |g| g.ids.contains(&id)) {
+                let gesture = state.gesture.take().unwrap();
+                if let Some(g) = gesture
+                    .area
+                    .upgrade()
+                    .and_then(|area| area.downcast::<crate::items::GestureArea>())
+                {
+                    g.as_pin_ref().finish_gesture(gesture.last_event);
+                }
+            }
+            let result = (is_primary && !state.suppress_pointer).then(|| event);
+            if state.points.is_empty() {
+                state.suppress_pointer = false;
+            }
+            result
+        }
+        _ => None,
+    }
+}
+
 /// The TextCursorBlinker takes care of providing a toggled boolean property
 /// that can be used to animate a blinking cursor. It's typically stored in the
 /// Window using a Weak and set_binding() can be used to set up a binding on a given
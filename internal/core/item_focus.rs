@@ -7,7 +7,9 @@
 This module contains the code moving the keyboard focus between items
 */
 
-use crate::item_tree::ComponentItemTree;
+use crate::item_tree::{ComponentItemTree, ItemRc};
+use crate::items::{FocusScope, TextInput};
+use alloc::vec::Vec;
 
 pub fn step_out_of_node(
     index: usize,
@@ -59,6 +61,63 @@ pub fn default_previous_in_local_focus_chain(
     }
 }
 
+/// Returns the `tab-index` of the item, or None if its `focus-policy` excludes it from the
+/// tab focus chain.
+fn tab_index(item: &ItemRc) -> Option<i32> {
+    if let Some(scope) = item.downcast::<FocusScope>() {
+        let scope = scope.as_pin_ref();
+        scope.focus_policy().accepts_tab_focus().then(|| scope.tab_index())
+    } else if let Some(input) = item.downcast::<TextInput>() {
+        let input = input.as_pin_ref();
+        input.focus_policy().accepts_tab_focus().then(|| input.tab_index())
+    } else {
+        Some(0)
+    }
+}
+
+fn is_ancestor_of(ancestor: &ItemRc, item: &ItemRc) -> bool {
+    let mut item = item.parent_item();
+    while let Some(i) = item {
+        if i == *ancestor {
+            return true;
+        }
+        item = i.parent_item();
+    }
+    false
+}
+
+/// Returns the closest `FocusScope` with `trap-focus` set that contains the item, if any.
+/// The Tab key only moves the focus within that scope.
+pub fn focus_trap(item: &ItemRc) -> Option<ItemRc> {
+    let mut current = Some(item.clone());
+    while let Some(i) = current {
+        if i.downcast::<FocusScope>().map_or(false, |scope| scope.as_pin_ref().trap_focus()) {
+            return Some(i);
+        }
+        current = i.parent_item();
+    }
+    None
+}
+
+/// Returns the items of the tab focus chain within `root` (including `root` itself): first the
+/// items with a positive `tab-index` in increasing order, then the others in tree order.
+pub fn tab_chain(root: &ItemRc) -> Vec<ItemRc> {
+    let mut chain = Vec::new();
+    let mut item = root.clone();
+    loop {
+        if let Some(index) = tab_index(&item) {
+            chain.push((index, item.clone()));
+        }
+        item = item.next_focus_item();
+        if item == *root || !is_ancestor_of(root, &item) {
+            break;
+        }
+    }
+    // sort_by_key is stable, so items with the same tab-index remain in tree order
+    chain.sort_by_key(|(index, _)| if *index > 0 { (false, *index) } else { (true, 0) });
+    chain.into_iter().map(|(_, item)| item).collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
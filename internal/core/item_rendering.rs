@@ -12,7 +12,8 @@
     ItemRc, ItemVisitor, ItemVisitorResult, ItemVisitorVTable, VisitChildrenResult,
 };
 use crate::lengths::{
-    LogicalLength, LogicalPoint, LogicalPx, LogicalRect, LogicalSize, LogicalVector,
+    LogicalBorderRadius, LogicalLength, LogicalPoint, LogicalPx, LogicalRect, LogicalSize,
+    LogicalVector,
 };
 use crate::Coord;
 use alloc::boxed::Box;
@@ -282,6 +283,18 @@ fn visit_layer(&mut self, _layer_item: Pin<&Layer>, _self_rc: &ItemRc) -> Render
         // Not supported
         RenderingResult::ContinueRenderingChildren
     }
+    fn visit_effect(&mut self, _effect_item: Pin<&Effect>, _self_rc: &ItemRc) -> RenderingResult {
+        // Not supported: the children are rendered without the effect
+        RenderingResult::ContinueRenderingChildren
+    }
+    fn visit_drop_shadow(
+        &mut self,
+        _drop_shadow_item: Pin<&DropShadow>,
+        _self_rc: &ItemRc,
+    ) -> RenderingResult {
+        // Not supported: the children are rendered without the shadow
+        RenderingResult::ContinueRenderingChildren
+    }
 
     // Apply the bounds of the Clip element, if enabled. The default implementation calls
     // combine_clip, but the render may choose an alternate way of implementing the clip.
@@ -292,7 +305,7 @@ fn visit_clip(&mut self, clip_item: Pin<&Clip>, _self_rc: &ItemRc) -> RenderingR
 
             let clip_region_valid = self.combine_clip(
                 LogicalRect::new(LogicalPoint::default(), geometry.size),
-                clip_item.border_radius(),
+                clip_item.border_radii(),
                 clip_item.border_width(),
             );
 
@@ -306,14 +319,15 @@ fn visit_clip(&mut self, clip_item: Pin<&Clip>, _self_rc: &ItemRc) -> RenderingR
     }
 
     /// Clip the further call until restore_state.
-    /// radius/border_width can be used for border rectangle clip.
+    /// radius/border_width can be used for border rectangle clip. Each corner of the rectangle
+    /// can have a different radius.
     /// (FIXME: consider removing radius/border_width and have another  function that take a path instead)
     /// Returns a boolean indicating the state of the new clip region: true if the clip region covers
     /// an area; false if the clip region is empty.
     fn combine_clip(
         &mut self,
         rect: LogicalRect,
-        radius: LogicalLength,
+        radius: LogicalBorderRadius,
         border_width: LogicalLength,
     ) -> bool;
     /// Get the current clip bounding box in the current transformed coordinate.
@@ -321,6 +335,10 @@ fn combine_clip(
 
     fn translate(&mut self, distance: LogicalVector);
     fn rotate(&mut self, angle_in_degrees: f32);
+    /// Scale the following items by the given factors.
+    fn scale(&mut self, x_factor: f32, y_factor: f32);
+    /// Skew the following items along the x axis and then along the y axis by the given angles.
+    fn skew(&mut self, x_angle_in_degrees: f32, y_angle_in_degrees: f32);
     /// Apply the opacity (between 0 and 1) for all following items until the next call to restore_state.
     fn apply_opacity(&mut self, opacity: f32);
 
@@ -369,8 +387,124 @@ fn metrics(&self) -> crate::graphics::rendering_metrics_collector::RenderingMetr
 /// The cache that needs to be held by the Window for the partial rendering
 pub type PartialRenderingCache = RenderingCache<LogicalRect>;
 
-/// FIXME: Should actually be a region and not just a rectangle
-pub type DirtyRegion = euclid::Box2D<Coord, LogicalPx>;
+/// The maximum number of rectangles in a [`Region`]
+pub(crate) const REGION_MAX_COUNT: usize = 3;
+
+/// A region made of a few rectangles that don't overlap.
+///
+/// A rectangle added to the region is merged with the rectangles that it overlaps. When the region
+/// already has three rectangles, it is merged with the one that grows the least.
+pub struct Region<T, U> {
+    rectangles: [euclid::Box2D<T, U>; REGION_MAX_COUNT],
+    count: usize,
+}
+
+impl<T: Copy, U> Clone for Region<T, U> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: Copy, U> Copy for Region<T, U> {}
+
+impl<T: Copy + euclid::num::Zero, U> Default for Region<T, U> {
+    fn default() -> Self {
+        Self { rectangles: [euclid::Box2D::zero(); REGION_MAX_COUNT], count: 0 }
+    }
+}
+
+impl<T: core::fmt::Debug, U> core::fmt::Debug for Region<T, U> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(self.rectangles[..self.count].iter()).finish()
+    }
+}
+
+impl<T: Copy + PartialOrd + euclid::num::Zero + num_traits::NumCast, U> Region<T, U> {
+    /// Returns the rectangles of the region
+    pub fn iter(&self) -> impl Iterator<Item = euclid::Box2D<T, U>> + '_ {
+        self.rectangles[..self.count].iter().copied()
+    }
+
+    /// Returns true if the region doesn't contain any rectangle
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    /// Returns the smallest box that contains all the rectangles of the region
+    pub fn bounding_box(&self) -> euclid::Box2D<T, U> {
+        self.iter().reduce(|a, b| a.union(&b)).unwrap_or_else(euclid::Box2D::zero)
+    }
+
+    /// Adds a box to the region
+    pub fn add_box(&mut self, mut b: euclid::Box2D<T, U>) {
+        if b.is_empty() {
+            return;
+        }
+        loop {
+            if self.iter().any(|r| r.contains_box(&b)) {
+                return;
+            }
+            let overlapping = self.iter().position(|r| r.intersects(&b));
+            if let Some(i) = overlapping {
+                b = b.union(&self.rectangles[i]);
+                self.remove(i);
+            } else if self.count < REGION_MAX_COUNT {
+                self.rectangles[self.count] = b;
+                self.count += 1;
+                return;
+            } else {
+                let area = |r: euclid::Box2D<T, U>| r.cast::<f32>().area();
+                let growth = |r: euclid::Box2D<T, U>| area(r.union(&b)) - area(r);
+                let i = (0..self.count)
+                    .min_by(|i, j| {
+                        growth(self.rectangles[*i])
+                            .partial_cmp(&growth(self.rectangles[*j]))
+                            .unwrap_or(core::cmp::Ordering::Equal)
+                    })
+                    .unwrap();
+                b = b.union(&self.rectangles[i]);
+                self.remove(i);
+            }
+        }
+    }
+
+    /// Returns the union of the two regions
+    pub fn union(&self, other: &Self) -> Self {
+        let mut result = *self;
+        for b in other.iter() {
+            result.add_box(b);
+        }
+        result
+    }
+
+    /// Returns the part of the region that is within `clip`
+    pub fn intersection(&self, clip: &euclid::Box2D<T, U>) -> Self {
+        let mut result = Self::default();
+        for b in self.iter().filter_map(|b| b.intersection(clip)) {
+            result.add_box(b);
+        }
+        result
+    }
+
+    fn remove(&mut self, index: usize) {
+        self.rectangles.copy_within(index + 1..self.count, index);
+        self.count -= 1;
+        self.rectangles[self.count] = euclid::Box2D::zero();
+    }
+}
+
+impl<T: Copy + PartialOrd + euclid::num::Zero + num_traits::NumCast, U> From<euclid::Box2D<T, U>>
+    for Region<T, U>
+{
+    fn from(b: euclid::Box2D<T, U>) -> Self {
+        let mut region = Self::default();
+        region.add_box(b);
+        region
+    }
+}
+
+/// The region of the window that needs to be redrawn
+pub type DirtyRegion = Region<Coord, LogicalPx>;
 
 /// Put this structure in the renderer to help with partial rendering
 pub struct PartialRenderer<'a, T> {
@@ -429,7 +563,7 @@ pub fn compute_dirty_regions(&mut self, component: &ComponentRc, origin: Logical
 
     fn mark_dirty_rect(&mut self, rect: LogicalRect, offset: euclid::Vector2D<Coord, LogicalPx>) {
         if !rect.is_empty() {
-            self.dirty_region = self.dirty_region.union(&rect.translate(offset).to_box2d());
+            self.dirty_region.add_box(rect.translate(offset).to_box2d());
         }
     }
 
@@ -519,7 +653,7 @@ fn filter_item(&mut self, item: Pin<ItemRef>) -> (bool, LogicalRect) {
     fn combine_clip(
         &mut self,
         rect: LogicalRect,
-        radius: LogicalLength,
+        radius: LogicalBorderRadius,
         border_width: LogicalLength,
     ) -> bool {
         self.actual_renderer.combine_clip(rect, radius, border_width)
@@ -537,6 +671,14 @@ fn rotate(&mut self, angle_in_degrees: f32) {
         self.actual_renderer.rotate(angle_in_degrees)
     }
 
+    fn scale(&mut self, x_factor: f32, y_factor: f32) {
+        self.actual_renderer.scale(x_factor, y_factor)
+    }
+
+    fn skew(&mut self, x_angle_in_degrees: f32, y_angle_in_degrees: f32) {
+        self.actual_renderer.skew(x_angle_in_degrees, y_angle_in_degrees)
+    }
+
     fn apply_opacity(&mut self, opacity: f32) {
         self.actual_renderer.apply_opacity(opacity)
     }
@@ -29,7 +29,8 @@
 pub use crate::item_tree::ItemRc;
 use crate::layout::{LayoutInfo, Orientation};
 use crate::lengths::{
-    LogicalLength, LogicalPoint, LogicalRect, LogicalSize, LogicalVector, PointLengths,
+    LogicalBorderRadius, LogicalLength, LogicalPoint, LogicalRect, LogicalSize, LogicalTransform,
+    LogicalVector, PointLengths, SizeLengths,
 };
 #[cfg(feature = "rtti")]
 use crate::rtti::*;
@@ -42,10 +43,20 @@
 use i_slint_core_macros::*;
 use vtable::*;
 
+mod drag_and_drop;
+pub use drag_and_drop::*;
 mod flickable;
 pub use flickable::*;
+mod gesture_area;
+pub use gesture_area::*;
+mod shortcut;
+pub use shortcut::*;
+mod system_tray_icon;
+pub use system_tray_icon::*;
 mod text;
 pub use text::*;
+mod toast_queue;
+pub use toast_queue::*;
 mod image;
 pub use self::image::*;
 #[cfg(feature = "std")]
@@ -61,7 +72,11 @@
 pub type VoidArg = ();
 pub type KeyEventArg = (KeyEvent,);
 type PointerEventArg = (PointerEvent,);
+type PointerScrollEventArg = (PointerScrollEvent,);
 type PointArg = (Point,);
+type StringArg = (SharedString,);
+type DropEventArg = (DropEvent,);
+type GestureEventArg = (GestureEvent,);
 
 #[cfg(all(feature = "ffi", windows))]
 #[macro_export]
@@ -359,6 +374,10 @@ pub struct BorderRectangle {
     pub height: Property<LogicalLength>,
     pub border_width: Property<LogicalLength>,
     pub border_radius: Property<LogicalLength>,
+    pub border_top_left_radius: Property<LogicalLength>,
+    pub border_top_right_radius: Property<LogicalLength>,
+    pub border_bottom_right_radius: Property<LogicalLength>,
+    pub border_bottom_left_radius: Property<LogicalLength>,
     pub border_color: Property<Brush>,
     pub cached_rendering_data: CachedRenderingData,
 }
@@ -427,6 +446,19 @@ fn render(
     }
 }
 
+impl BorderRectangle {
+    /// Returns the radius of each corner. The compiler binds the radius of the corners that are not set
+    /// to the `border-radius`.
+    pub fn border_radii(self: Pin<&Self>) -> LogicalBorderRadius {
+        LogicalBorderRadius {
+            top_left: self.border_top_left_radius(),
+            top_right: self.border_top_right_radius(),
+            bottom_right: self.border_bottom_right_radius(),
+            bottom_left: self.border_bottom_left_radius(),
+        }
+    }
+}
+
 impl ItemConsts for BorderRectangle {
     const cached_rendering_data_offset: const_field_offset::FieldOffset<
         BorderRectangle,
@@ -460,13 +492,35 @@ pub struct TouchArea {
     pub mouse_x: Property<LogicalLength>,
     pub mouse_y: Property<LogicalLength>,
     pub mouse_cursor: Property<MouseCursor>,
+    pub mouse_cursor_image: Property<crate::graphics::Image>,
+    pub mouse_cursor_hotspot_x: Property<LogicalLength>,
+    pub mouse_cursor_hotspot_y: Property<LogicalLength>,
+    pub tooltip: Property<SharedString>,
+    /// The delay, in milliseconds, after which a press is a long press. 0 disables long presses.
+    pub long_press_duration: Property<i64>,
     pub clicked: Callback<VoidArg>,
+    pub double_clicked: Callback<VoidArg>,
+    pub long_pressed: Callback<VoidArg>,
+    pub drag_started: Callback<VoidArg>,
     pub moved: Callback<VoidArg>,
     pub pointer_event: Callback<PointerEventArg>,
+    pub scroll_event: Callback<PointerScrollEventArg, EventResult>,
     /// FIXME: remove this
     pub cached_rendering_data: CachedRenderingData,
     /// true when we are currently grabbing the mouse
     grabbed: Cell<bool>,
+    /// The tick at which the left button was last pressed, to identify the press when the
+    /// long press timer fires
+    press_time: Cell<u64>,
+    /// true when the pointer moved further than the drag threshold during the current press
+    press_moved: Cell<bool>,
+    /// true when the current press was handled as a long press or a drag, so it is no longer a click
+    press_consumed: Cell<bool>,
+    /// The number of consecutive clicks, and the time and position of the last one
+    click_count: Cell<u32>,
+    last_click_time: Cell<u64>,
+    last_click_x: Cell<Coord>,
+    last_click_y: Cell<Coord>,
 }
 
 impl Item for TouchArea {
@@ -491,7 +545,7 @@ fn input_event_filter_before_children(
         self: Pin<&Self>,
         event: MouseEvent,
         window_adapter: &Rc<dyn WindowAdapter>,
-        _self_rc: &ItemRc,
+        self_rc: &ItemRc,
     ) -> InputEventFilterResult {
         if !self.enabled() {
             return InputEventFilterResult::ForwardAndIgnore;
@@ -499,11 +553,31 @@ fn input_event_filter_before_children(
         if let Some(pos) = event.position() {
             Self::FIELD_OFFSETS.mouse_x.apply_pin(self).set(pos.x_length());
             Self::FIELD_OFFSETS.mouse_y.apply_pin(self).set(pos.y_length());
+            let tooltip = self.tooltip();
+            if !tooltip.is_empty() {
+                WindowInner::from_pub(window_adapter.window()).hover_tooltip(
+                    self_rc,
+                    tooltip,
+                    self_rc.map_to_window(self.geometry().origin + pos.to_vector()),
+                );
+            }
         }
         let hovering = !matches!(event, MouseEvent::Exit);
         Self::FIELD_OFFSETS.has_hover.apply_pin(self).set(hovering);
         if hovering {
-            window_adapter.set_mouse_cursor(self.mouse_cursor());
+            let image = self.mouse_cursor_image();
+            if image.size().is_empty() {
+                window_adapter.set_mouse_cursor(self.mouse_cursor());
+            } else {
+                window_adapter.set_custom_mouse_cursor(
+                    &image,
+                    LogicalPoint::from_lengths(
+                        self.mouse_cursor_hotspot_x(),
+                        self.mouse_cursor_hotspot_y(),
+                    ),
+                    self.mouse_cursor(),
+                );
+            }
         }
         InputEventFilterResult::ForwardAndInterceptGrab
     }
@@ -512,17 +586,19 @@ fn input_event(
         self: Pin<&Self>,
         event: MouseEvent,
         window_adapter: &Rc<dyn WindowAdapter>,
-        _self_rc: &ItemRc,
+        self_rc: &ItemRc,
     ) -> InputEventResult {
         if matches!(event, MouseEvent::Exit) {
             Self::FIELD_OFFSETS.has_hover.apply_pin(self).set(false);
             window_adapter.set_mouse_cursor(MouseCursor::Default);
+            WindowInner::from_pub(window_adapter.window()).hide_tooltip(self_rc, false);
         }
         if !self.enabled() {
             return InputEventResult::EventIgnored;
         }
         let result = if let MouseEvent::Released { position, button } = event {
             if button == PointerEventButton::Left
+                && !self.press_consumed.get()
                 && LogicalRect::new(
                     LogicalPoint::default(),
                     LogicalSize::from_lengths(self.width(), self.height()),
@@ -530,6 +606,7 @@ fn input_event(
                 .contains(position)
             {
                 Self::FIELD_OFFSETS.clicked.apply_pin(self).call(&());
+                self.handle_click(position, window_adapter);
             }
             InputEventResult::EventAccepted
         } else {
@@ -539,10 +616,14 @@ fn input_event(
         match event {
             MouseEvent::Pressed { position, button } => {
                 self.grabbed.set(true);
+                WindowInner::from_pub(window_adapter.window()).hide_tooltip(self_rc, true);
                 if button == PointerEventButton::Left {
                     Self::FIELD_OFFSETS.pressed_x.apply_pin(self).set(position.x_length());
                     Self::FIELD_OFFSETS.pressed_y.apply_pin(self).set(position.y_length());
                     Self::FIELD_OFFSETS.pressed.apply_pin(self).set(true);
+                    self.press_moved.set(false);
+                    self.press_consumed.set(false);
+                    self.start_long_press_timer(self_rc);
                 }
                 Self::FIELD_OFFSETS
                     .pointer_event
@@ -568,17 +649,41 @@ fn input_event(
                     .apply_pin(self)
                     .call(&(PointerEvent { button, kind: PointerEventKind::Up },));
             }
-            MouseEvent::Moved { .. } => {
+            MouseEvent::Moved { position } => {
                 return if self.grabbed.get() {
+                    if self.pressed() && !self.press_moved.get() {
+                        let pressed_position =
+                            LogicalPoint::from_lengths(self.pressed_x(), self.pressed_y());
+                        let threshold = window_adapter.drag_threshold().get();
+                        if (position - pressed_position).square_length() > threshold * threshold {
+                            self.press_moved.set(true);
+                            self.click_count.set(0);
+                            // Only turn the press into a drag if it is handled, so that
+                            // moving the pointer slightly still results in a click otherwise
+                            if !self.press_consumed.get() && self.drag_started.has_handler() {
+                                self.press_consumed.set(true);
+                                Self::FIELD_OFFSETS.drag_started.apply_pin(self).call(&());
+                            }
+                        }
+                    }
                     Self::FIELD_OFFSETS.moved.apply_pin(self).call(&());
                     InputEventResult::GrabMouse
                 } else {
                     InputEventResult::EventAccepted
-                }
+                };
             }
-            MouseEvent::Wheel { .. } => {
+            MouseEvent::Wheel { delta_x, delta_y, .. } => {
                 return if self.grabbed.get() {
                     InputEventResult::GrabMouse
+                } else if self.scroll_event.has_handler() {
+                    let result = Self::FIELD_OFFSETS.scroll_event.apply_pin(self).call(&(
+                        PointerScrollEvent { delta_x: delta_x as Coord, delta_y: delta_y as Coord },
+                    ));
+                    if result == EventResult::Accept {
+                        InputEventResult::EventAccepted
+                    } else {
+                        InputEventResult::EventIgnored
+                    }
                 } else {
                     InputEventResult::EventAccepted
                 }
@@ -614,6 +719,65 @@ fn render(
     }
 }
 
+impl TouchArea {
+    /// Count consecutive clicks, and emit `double-clicked` for the second one
+    fn handle_click(
+        self: Pin<&Self>,
+        position: LogicalPoint,
+        window_adapter: &Rc<dyn WindowAdapter>,
+    ) {
+        let now = crate::animations::current_tick().0;
+        let threshold = window_adapter.drag_threshold().get();
+        let last_position = LogicalPoint::new(self.last_click_x.get(), self.last_click_y.get());
+        let is_consecutive = self.click_count.get() > 0
+            && now - self.last_click_time.get()
+                <= window_adapter.double_click_interval().as_millis() as u64
+            && (position - last_position).square_length() <= threshold * threshold;
+        self.last_click_time.set(now);
+        self.last_click_x.set(position.x);
+        self.last_click_y.set(position.y);
+        if is_consecutive {
+            self.click_count.set(0);
+            Self::FIELD_OFFSETS.double_clicked.apply_pin(self).call(&());
+        } else {
+            self.click_count.set(1);
+        }
+    }
+
+    /// Start the timer that emits `long-pressed` if the button is still pressed after
+    /// `long-press-duration`, and the pointer was not dragged.
+    fn start_long_press_timer(self: Pin<&Self>, self_rc: &ItemRc) {
+        let press_time = crate::animations::current_tick().0;
+        self.press_time.set(press_time);
+        let duration = self.long_press_duration();
+        if duration <= 0 || !self.long_pressed.has_handler() {
+            return;
+        }
+        let self_weak = self_rc.downgrade();
+        crate::timers::Timer::single_shot(
+            core::time::Duration::from_millis(duration as u64),
+            move || {
+                let self_rc = match self_weak.upgrade() {
+                    Some(self_rc) => self_rc,
+                    None => return,
+                };
+                if let Some(touch_area) = self_rc.downcast::<TouchArea>() {
+                    let touch_area = touch_area.as_pin_ref();
+                    if touch_area.pressed()
+                        && !touch_area.press_moved.get()
+                        && !touch_area.press_consumed.get()
+                        && touch_area.press_time.get() == press_time
+                    {
+                        touch_area.press_consumed.set(true);
+                        touch_area.click_count.set(0);
+                        Self::FIELD_OFFSETS.long_pressed.apply_pin(touch_area).call(&());
+                    }
+                }
+            },
+        );
+    }
+}
+
 impl ItemConsts for TouchArea {
     const cached_rendering_data_offset: const_field_offset::FieldOffset<
         TouchArea,
@@ -636,6 +800,9 @@ pub struct FocusScope {
     pub height: Property<LogicalLength>,
     pub enabled: Property<bool>,
     pub has_focus: Property<bool>,
+    pub focus_policy: Property<FocusPolicy>,
+    pub tab_index: Property<i32>,
+    pub trap_focus: Property<bool>,
     pub key_pressed: Callback<KeyEventArg, EventResult>,
     pub key_released: Callback<KeyEventArg, EventResult>,
     /// FIXME: remove this
@@ -675,7 +842,11 @@ fn input_event(
         window_adapter: &Rc<dyn WindowAdapter>,
         self_rc: &ItemRc,
     ) -> InputEventResult {
-        if self.enabled() && matches!(event, MouseEvent::Pressed { .. }) && !self.has_focus() {
+        if self.enabled()
+            && matches!(event, MouseEvent::Pressed { .. })
+            && !self.has_focus()
+            && self.focus_policy().accepts_click_focus()
+        {
             WindowInner::from_pub(window_adapter.window()).set_focus_item(self_rc);
         }
         InputEventResult::EventIgnored
@@ -710,7 +881,9 @@ fn focus_event(
         _window_adapter: &Rc<dyn WindowAdapter>,
         _self_rc: &ItemRc,
     ) -> FocusEventResult {
-        if !self.enabled() {
+        if !self.enabled()
+            || (*event == FocusEvent::FocusIn && self.focus_policy() == FocusPolicy::NoFocus)
+        {
             return FocusEventResult::FocusIgnored;
         }
 
@@ -745,6 +918,38 @@ impl ItemConsts for FocusScope {
     fn slint_get_FocusScopeVTable() -> FocusScopeVTable for FocusScope
 }
 
+declare_item_vtable! {
+    fn slint_get_DragAreaVTable() -> DragAreaVTable for DragArea
+}
+
+declare_item_vtable! {
+    fn slint_get_DropAreaVTable() -> DropAreaVTable for DropArea
+}
+
+declare_item_vtable! {
+    fn slint_get_GestureAreaVTable() -> GestureAreaVTable for GestureArea
+}
+
+declare_item_vtable! {
+    fn slint_get_ShortcutVTable() -> ShortcutVTable for Shortcut
+}
+
+declare_item_vtable! {
+    fn slint_get_ToastQueueVTable() -> ToastQueueVTable for ToastQueue
+}
+
+declare_item_vtable! {
+    fn slint_get_SystemTrayIconVTable() -> SystemTrayIconVTable for SystemTrayIcon
+}
+
+declare_item_vtable! {
+    fn slint_get_PlatformMenuItemVTable() -> PlatformMenuItemVTable for PlatformMenuItem
+}
+
+declare_item_vtable! {
+    fn slint_get_PlatformMenuBarVTable() -> PlatformMenuBarVTable for PlatformMenuBar
+}
+
 #[repr(C)]
 #[derive(FieldOffsets, Default, SlintElement)]
 #[pin]
@@ -755,6 +960,10 @@ pub struct Clip {
     pub width: Property<LogicalLength>,
     pub height: Property<LogicalLength>,
     pub border_radius: Property<LogicalLength>,
+    pub border_top_left_radius: Property<LogicalLength>,
+    pub border_top_right_radius: Property<LogicalLength>,
+    pub border_bottom_right_radius: Property<LogicalLength>,
+    pub border_bottom_left_radius: Property<LogicalLength>,
     pub border_width: Property<LogicalLength>,
     pub cached_rendering_data: CachedRenderingData,
     pub clip: Property<bool>,
@@ -833,6 +1042,18 @@ fn render(
     }
 }
 
+impl Clip {
+    /// Returns the radius of each corner of the clip region.
+    pub fn border_radii(self: Pin<&Self>) -> LogicalBorderRadius {
+        LogicalBorderRadius {
+            top_left: self.border_top_left_radius(),
+            top_right: self.border_top_right_radius(),
+            bottom_right: self.border_bottom_right_radius(),
+            bottom_left: self.border_bottom_left_radius(),
+        }
+    }
+}
+
 impl ItemConsts for Clip {
     const cached_rendering_data_offset: const_field_offset::FieldOffset<Clip, CachedRenderingData> =
         Clip::FIELD_OFFSETS.cached_rendering_data.as_unpinned_projection();
@@ -1048,19 +1269,249 @@ fn slint_get_LayerVTable() -> LayerVTable for Layer
 #[repr(C)]
 #[derive(FieldOffsets, Default, SlintElement)]
 #[pin]
-/// The implementation of the `Rotate` element
-pub struct Rotate {
+/// The Effect Item is not meant to be used directly by the .slint code, instead, the `effect-*` properties should be used
+pub struct Effect {
+    // FIXME: this element shouldn't need these geometry property
+    pub x: Property<LogicalLength>,
+    pub y: Property<LogicalLength>,
+    pub width: Property<LogicalLength>,
+    pub height: Property<LogicalLength>,
+    pub effect_blur: Property<LogicalLength>,
+    pub effect_brightness: Property<f32>,
+    pub effect_saturation: Property<f32>,
+    pub effect_tint: Property<Color>,
+    pub cached_rendering_data: CachedRenderingData,
+}
+
+impl Item for Effect {
+    fn init(self: Pin<&Self>, _window_adapter: &Rc<dyn WindowAdapter>) {}
+
+    fn geometry(self: Pin<&Self>) -> LogicalRect {
+        LogicalRect::new(
+            LogicalPoint::from_lengths(self.x(), self.y()),
+            LogicalSize::from_lengths(self.width(), self.height()),
+        )
+    }
+
+    fn layout_info(
+        self: Pin<&Self>,
+        _orientation: Orientation,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+    ) -> LayoutInfo {
+        LayoutInfo { stretch: 1., ..LayoutInfo::default() }
+    }
+
+    fn input_event_filter_before_children(
+        self: Pin<&Self>,
+        _: MouseEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> InputEventFilterResult {
+        InputEventFilterResult::ForwardAndIgnore
+    }
+
+    fn input_event(
+        self: Pin<&Self>,
+        _: MouseEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> InputEventResult {
+        InputEventResult::EventIgnored
+    }
+
+    fn key_event(
+        self: Pin<&Self>,
+        _: &KeyEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> KeyEventResult {
+        KeyEventResult::EventIgnored
+    }
+
+    fn focus_event(
+        self: Pin<&Self>,
+        _: &FocusEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> FocusEventResult {
+        FocusEventResult::FocusIgnored
+    }
+
+    fn render(
+        self: Pin<&Self>,
+        backend: &mut ItemRendererRef,
+        self_rc: &ItemRc,
+    ) -> RenderingResult {
+        backend.visit_effect(self, self_rc)
+    }
+}
+
+impl Effect {
+    /// Returns the row-major 4x5 matrix that applies the saturation, brightness and tint adjustments
+    /// (in that order) to unpremultiplied RGBA colors with components in the range 0 to 1, or None if
+    /// the colors are unchanged.
+    pub fn color_matrix(self: Pin<&Self>) -> Option<[f32; 20]> {
+        let saturation = self.effect_saturation();
+        let brightness = self.effect_brightness();
+        let tint = self.effect_tint();
+        if saturation == 1. && brightness == 1. && tint.alpha() == 0 {
+            return None;
+        }
+
+        // Rec. 709 luma coefficients
+        const LUMA: [f32; 3] = [0.2126, 0.7152, 0.0722];
+        let tint_alpha = tint.alpha() as f32 / 255.;
+
+        let mut matrix = [0.; 20];
+        for (row, tint_component) in [tint.red(), tint.green(), tint.blue()].into_iter().enumerate()
+        {
+            for (column, luma) in LUMA.iter().enumerate() {
+                let saturated =
+                    luma * (1. - saturation) + if row == column { saturation } else { 0. };
+                matrix[row * 5 + column] = saturated * brightness * (1. - tint_alpha);
+            }
+            matrix[row * 5 + 4] = tint_component as f32 / 255. * tint_alpha;
+        }
+        matrix[18] = 1.;
+        Some(matrix)
+    }
+
+    /// Returns true if the effect doesn't change the rendering of the children.
+    pub fn is_identity(self: Pin<&Self>) -> bool {
+        self.effect_blur().get() <= 0 as Coord && self.color_matrix().is_none()
+    }
+}
+
+impl ItemConsts for Effect {
+    const cached_rendering_data_offset: const_field_offset::FieldOffset<
+        Effect,
+        CachedRenderingData,
+    > = Effect::FIELD_OFFSETS.cached_rendering_data.as_unpinned_projection();
+}
+
+declare_item_vtable! {
+    fn slint_get_EffectVTable() -> EffectVTable for Effect
+}
+
+#[repr(C)]
+#[derive(FieldOffsets, Default, SlintElement)]
+#[pin]
+/// The DropShadow Item is not meant to be used directly by the .slint code, instead, the `drop-shadow-*` properties
+/// should be used on elements that are not rectangles. It renders the shadow of the opaque parts of its children.
+pub struct DropShadow {
+    // FIXME: this element shouldn't need these geometry property
+    pub x: Property<LogicalLength>,
+    pub y: Property<LogicalLength>,
+    pub width: Property<LogicalLength>,
+    pub height: Property<LogicalLength>,
+    pub drop_shadow_offset_x: Property<LogicalLength>,
+    pub drop_shadow_offset_y: Property<LogicalLength>,
+    pub drop_shadow_color: Property<Color>,
+    pub drop_shadow_blur: Property<LogicalLength>,
+    pub cached_rendering_data: CachedRenderingData,
+}
+
+impl Item for DropShadow {
+    fn init(self: Pin<&Self>, _window_adapter: &Rc<dyn WindowAdapter>) {}
+
+    fn geometry(self: Pin<&Self>) -> LogicalRect {
+        LogicalRect::new(
+            LogicalPoint::from_lengths(self.x(), self.y()),
+            LogicalSize::from_lengths(self.width(), self.height()),
+        )
+    }
+
+    fn layout_info(
+        self: Pin<&Self>,
+        _orientation: Orientation,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+    ) -> LayoutInfo {
+        LayoutInfo { stretch: 1., ..LayoutInfo::default() }
+    }
+
+    fn input_event_filter_before_children(
+        self: Pin<&Self>,
+        _: MouseEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> InputEventFilterResult {
+        InputEventFilterResult::ForwardAndIgnore
+    }
+
+    fn input_event(
+        self: Pin<&Self>,
+        _: MouseEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> InputEventResult {
+        InputEventResult::EventIgnored
+    }
+
+    fn key_event(
+        self: Pin<&Self>,
+        _: &KeyEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> KeyEventResult {
+        KeyEventResult::EventIgnored
+    }
+
+    fn focus_event(
+        self: Pin<&Self>,
+        _: &FocusEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> FocusEventResult {
+        FocusEventResult::FocusIgnored
+    }
+
+    fn render(
+        self: Pin<&Self>,
+        backend: &mut ItemRendererRef,
+        self_rc: &ItemRc,
+    ) -> RenderingResult {
+        backend.visit_drop_shadow(self, self_rc)
+    }
+}
+
+impl DropShadow {
+    /// Returns true if the shadow is not visible
+    pub fn is_invisible(self: Pin<&Self>) -> bool {
+        self.drop_shadow_color().alpha() == 0
+    }
+}
+
+impl ItemConsts for DropShadow {
+    const cached_rendering_data_offset: const_field_offset::FieldOffset<
+        DropShadow,
+        CachedRenderingData,
+    > = DropShadow::FIELD_OFFSETS.cached_rendering_data.as_unpinned_projection();
+}
+
+declare_item_vtable! {
+    fn slint_get_DropShadowVTable() -> DropShadowVTable for DropShadow
+}
+
+#[repr(C)]
+#[derive(FieldOffsets, Default, SlintElement)]
+#[pin]
+/// The implementation of the `Transform` element, that rotates, scales and skews its children
+pub struct Transform {
     pub x: Property<LogicalLength>,
     pub y: Property<LogicalLength>,
     pub rotation_angle: Property<f32>,
     pub rotation_origin_x: Property<LogicalLength>,
     pub rotation_origin_y: Property<LogicalLength>,
+    pub transform_scale_x: Property<f32>,
+    pub transform_scale_y: Property<f32>,
+    pub transform_skew_x: Property<f32>,
+    pub transform_skew_y: Property<f32>,
     pub width: Property<LogicalLength>,
     pub height: Property<LogicalLength>,
     pub cached_rendering_data: CachedRenderingData,
 }
 
-impl Item for Rotate {
+impl Item for Transform {
     fn init(self: Pin<&Self>, _window_adapter: &Rc<dyn WindowAdapter>) {}
 
     fn geometry(self: Pin<&Self>) -> LogicalRect {
@@ -1123,20 +1574,157 @@ fn render(
             LogicalVector::from_lengths(self.rotation_origin_x(), self.rotation_origin_y());
         (*backend).translate(origin);
         (*backend).rotate(self.rotation_angle());
+        (*backend).skew(self.transform_skew_x(), self.transform_skew_y());
+        (*backend).scale(self.transform_scale_x(), self.transform_scale_y());
         (*backend).translate(-origin);
         RenderingResult::ContinueRenderingChildren
     }
 }
 
-impl ItemConsts for Rotate {
+impl Transform {
+    /// Returns the transformation that maps the coordinates of the children to the coordinates
+    /// of this element. This is the same transformation that is applied when rendering.
+    pub fn children_transform(self: Pin<&Self>) -> LogicalTransform {
+        #[cfg(not(feature = "std"))]
+        use num_traits::Float;
+        let origin =
+            LogicalVector::from_lengths(self.rotation_origin_x(), self.rotation_origin_y()).cast();
+        let skew = |angle: f32| angle.to_radians().tan();
+        LogicalTransform::translation(-origin.x, -origin.y)
+            .then_scale(self.transform_scale_x(), self.transform_scale_y())
+            .then(&LogicalTransform::new(1., skew(self.transform_skew_y()), 0., 1., 0., 0.))
+            .then(&LogicalTransform::new(1., 0., skew(self.transform_skew_x()), 1., 0., 0.))
+            .then_rotate(euclid::Angle::degrees(self.rotation_angle()))
+            .then_translate(origin)
+    }
+
+    /// Maps a position in the coordinates of this element to the coordinates of its children.
+    /// Returns None if the transformation can't be inverted, for example when scaled to zero.
+    pub fn map_to_children(self: Pin<&Self>, position: LogicalPoint) -> Option<LogicalPoint> {
+        self.children_transform()
+            .inverse()
+            .map(|inverse| inverse.transform_point(position.cast()).cast())
+    }
+}
+
+impl ItemConsts for Transform {
+    const cached_rendering_data_offset: const_field_offset::FieldOffset<
+        Transform,
+        CachedRenderingData,
+    > = Transform::FIELD_OFFSETS.cached_rendering_data.as_unpinned_projection();
+}
+
+declare_item_vtable! {
+    fn slint_get_TransformVTable() -> TransformVTable for Transform
+}
+
+#[repr(C)]
+#[derive(FieldOffsets, Default, SlintElement)]
+#[pin]
+/// The ViewportObserver Item is not meant to be used directly by the .slint code, instead, the `viewport-visible`
+/// property and the `viewport-entered` and `viewport-exited` callbacks should be used
+pub struct ViewportObserver {
+    // FIXME: this element shouldn't need these geometry property
+    pub x: Property<LogicalLength>,
+    pub y: Property<LogicalLength>,
+    pub width: Property<LogicalLength>,
+    pub height: Property<LogicalLength>,
+    pub viewport_visible: Property<bool>,
+    pub viewport_entered: Callback<VoidArg>,
+    pub viewport_exited: Callback<VoidArg>,
+    pub cached_rendering_data: CachedRenderingData,
+}
+
+impl Item for ViewportObserver {
+    fn init(self: Pin<&Self>, window_adapter: &Rc<dyn WindowAdapter>) {
+        WindowInner::from_pub(window_adapter.window()).register_viewport_observer();
+    }
+
+    fn geometry(self: Pin<&Self>) -> LogicalRect {
+        LogicalRect::new(
+            LogicalPoint::from_lengths(self.x(), self.y()),
+            LogicalSize::from_lengths(self.width(), self.height()),
+        )
+    }
+
+    fn layout_info(
+        self: Pin<&Self>,
+        _orientation: Orientation,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+    ) -> LayoutInfo {
+        LayoutInfo { stretch: 1., ..LayoutInfo::default() }
+    }
+
+    fn input_event_filter_before_children(
+        self: Pin<&Self>,
+        _: MouseEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> InputEventFilterResult {
+        InputEventFilterResult::ForwardAndIgnore
+    }
+
+    fn input_event(
+        self: Pin<&Self>,
+        _: MouseEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> InputEventResult {
+        InputEventResult::EventIgnored
+    }
+
+    fn key_event(
+        self: Pin<&Self>,
+        _: &KeyEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> KeyEventResult {
+        KeyEventResult::EventIgnored
+    }
+
+    fn focus_event(
+        self: Pin<&Self>,
+        _: &FocusEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> FocusEventResult {
+        FocusEventResult::FocusIgnored
+    }
+
+    fn render(
+        self: Pin<&Self>,
+        _backend: &mut ItemRendererRef,
+        _self_rc: &ItemRc,
+    ) -> RenderingResult {
+        RenderingResult::ContinueRenderingChildren
+    }
+}
+
+impl ViewportObserver {
+    /// Updates the `viewport-visible` property and emits `viewport-entered` or `viewport-exited`
+    /// if the visibility changed.
+    pub fn set_visible_in_viewport(self: Pin<&Self>, visible: bool) {
+        if Self::FIELD_OFFSETS.viewport_visible.apply_pin(self).get_untracked() == visible {
+            return;
+        }
+        self.viewport_visible.set(visible);
+        if visible {
+            Self::FIELD_OFFSETS.viewport_entered.apply_pin(self).call(&());
+        } else {
+            Self::FIELD_OFFSETS.viewport_exited.apply_pin(self).call(&());
+        }
+    }
+}
+
+impl ItemConsts for ViewportObserver {
     const cached_rendering_data_offset: const_field_offset::FieldOffset<
-        Rotate,
+        ViewportObserver,
         CachedRenderingData,
-    > = Rotate::FIELD_OFFSETS.cached_rendering_data.as_unpinned_projection();
+    > = ViewportObserver::FIELD_OFFSETS.cached_rendering_data.as_unpinned_projection();
 }
 
 declare_item_vtable! {
-    fn slint_get_RotateVTable() -> RotateVTable for Rotate
+    fn slint_get_ViewportObserverVTable() -> ViewportObserverVTable for ViewportObserver
 }
 
 declare_item_vtable! {
@@ -1176,15 +1764,71 @@ pub struct WindowItem {
     pub background: Property<Brush>,
     pub title: Property<SharedString>,
     pub no_frame: Property<bool>,
+    pub always_on_top: Property<bool>,
+    pub resizable: Property<bool>,
+    pub transparent: Property<bool>,
     pub icon: Property<crate::graphics::Image>,
     pub default_font_family: Property<SharedString>,
     pub default_font_size: Property<LogicalLength>,
     pub default_font_weight: Property<i32>,
+    pub tooltip_background: Property<Brush>,
+    pub tooltip_color: Property<Brush>,
+    pub tooltip_border_color: Property<Brush>,
+    pub tooltip_border_radius: Property<LogicalLength>,
+    pub layout_direction: Property<LayoutDirection>,
+    pub scale_factor: Property<f32>,
+    pub monitor_name: Property<SharedString>,
+    pub monitor_width: Property<LogicalLength>,
+    pub monitor_height: Property<LogicalLength>,
+    pub monitor_refresh_rate: Property<f32>,
+    pub safe_area_inset_top: Property<LogicalLength>,
+    pub safe_area_inset_bottom: Property<LogicalLength>,
+    pub safe_area_inset_left: Property<LogicalLength>,
+    pub safe_area_inset_right: Property<LogicalLength>,
     pub cached_rendering_data: CachedRenderingData,
 }
 
 impl Item for WindowItem {
-    fn init(self: Pin<&Self>, _window_adapter: &Rc<dyn WindowAdapter>) {}
+    fn init(self: Pin<&Self>, window_adapter: &Rc<dyn WindowAdapter>) {
+        // The output properties follow the values that the backend sets on the window
+        bind_to_window(Self::FIELD_OFFSETS.scale_factor.apply_pin(self), window_adapter, |w| {
+            w.scale_factor()
+        });
+        bind_to_window(Self::FIELD_OFFSETS.monitor_name.apply_pin(self), window_adapter, |w| {
+            w.monitor().name
+        });
+        bind_to_window(Self::FIELD_OFFSETS.monitor_width.apply_pin(self), window_adapter, |w| {
+            w.monitor().size.width_length()
+        });
+        bind_to_window(Self::FIELD_OFFSETS.monitor_height.apply_pin(self), window_adapter, |w| {
+            w.monitor().size.height_length()
+        });
+        bind_to_window(
+            Self::FIELD_OFFSETS.monitor_refresh_rate.apply_pin(self),
+            window_adapter,
+            |w| w.monitor().refresh_rate,
+        );
+        bind_to_window(
+            Self::FIELD_OFFSETS.safe_area_inset_top.apply_pin(self),
+            window_adapter,
+            |w| w.safe_area_insets().top,
+        );
+        bind_to_window(
+            Self::FIELD_OFFSETS.safe_area_inset_bottom.apply_pin(self),
+            window_adapter,
+            |w| w.safe_area_insets().bottom,
+        );
+        bind_to_window(
+            Self::FIELD_OFFSETS.safe_area_inset_left.apply_pin(self),
+            window_adapter,
+            |w| w.safe_area_insets().left,
+        );
+        bind_to_window(
+            Self::FIELD_OFFSETS.safe_area_inset_right.apply_pin(self),
+            window_adapter,
+            |w| w.safe_area_insets().right,
+        );
+    }
 
     fn geometry(self: Pin<&Self>) -> LogicalRect {
         LogicalRect::new(
@@ -1246,6 +1890,21 @@ fn render(
     }
 }
 
+/// Binds the property to a value of the window, as returned by `f`
+fn bind_to_window<T: Clone + Default + 'static>(
+    property: Pin<&Property<T>>,
+    window_adapter: &Rc<dyn WindowAdapter>,
+    f: fn(&WindowInner) -> T,
+) {
+    let window_adapter_weak = Rc::downgrade(window_adapter);
+    property.set_binding(move || {
+        window_adapter_weak
+            .upgrade()
+            .map(|window_adapter| f(WindowInner::from_pub(window_adapter.window())))
+            .unwrap_or_default()
+    });
+}
+
 impl WindowItem {
     pub fn font_family(self: Pin<&Self>) -> Option<SharedString> {
         let maybe_family = self.default_font_family();
@@ -1420,6 +2079,18 @@ fn default() -> Self {
 
 i_slint_common::for_each_enums!(declare_enums);
 
+impl FocusPolicy {
+    /// Returns true if an element with this policy takes the focus when clicked
+    pub fn accepts_click_focus(self) -> bool {
+        matches!(self, FocusPolicy::TabAndClick | FocusPolicy::ClickOnly)
+    }
+
+    /// Returns true if an element with this policy is part of the tab focus chain
+    pub fn accepts_tab_focus(self) -> bool {
+        matches!(self, FocusPolicy::TabAndClick | FocusPolicy::TabOnly)
+    }
+}
+
 /// Represents a Pointer event sent by the windowing system.
 #[derive(Debug, Clone, PartialEq, Default)]
 #[repr(C)]
@@ -1427,3 +2098,13 @@ pub struct PointerEvent {
     pub button: PointerEventButton,
     pub kind: PointerEventKind,
 }
+
+/// Represents a scroll event of the mouse wheel or of the touchpad, sent to a `TouchArea`.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[repr(C)]
+pub struct PointerScrollEvent {
+    /// The amount of logical pixels to scroll in horizontal direction
+    pub delta_x: Coord,
+    /// The amount of logical pixels to scroll in vertical direction
+    pub delta_y: Coord,
+}
@@ -0,0 +1,296 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+//! The `DragArea` and `DropArea` items
+
+use super::{
+    DropEventArg, Item, ItemConsts, ItemRc, ItemRendererRef, KeyEventResult, PointerEventButton,
+    RenderingResult, VoidArg,
+};
+use crate::graphics::{Image, Point};
+use crate::input::{
+    FocusEvent, FocusEventResult, InputEventFilterResult, InputEventResult, KeyEvent, MouseEvent,
+};
+use crate::item_rendering::CachedRenderingData;
+use crate::layout::{LayoutInfo, Orientation};
+use crate::lengths::{LogicalLength, LogicalPoint, LogicalRect, LogicalSize};
+#[cfg(feature = "rtti")]
+use crate::rtti::*;
+use crate::window::{WindowAdapter, WindowInner};
+use crate::{Callback, Coord, Property, SharedString};
+use alloc::rc::Rc;
+use const_field_offset::FieldOffsets;
+use core::cell::Cell;
+use core::pin::Pin;
+use i_slint_core_macros::*;
+
+/// The payload of a drag and drop operation, as delivered to the callbacks of `DropArea`.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[repr(C)]
+pub struct DropEvent {
+    pub mime_type: SharedString,
+    pub data: SharedString,
+    /// The position of the pointer, relative to the `DropArea`
+    pub position: Point,
+}
+
+/// The implementation of the `DragArea` element
+#[repr(C)]
+#[derive(FieldOffsets, Default, SlintElement)]
+#[pin]
+pub struct DragArea {
+    pub x: Property<LogicalLength>,
+    pub y: Property<LogicalLength>,
+    pub width: Property<LogicalLength>,
+    pub height: Property<LogicalLength>,
+    pub enabled: Property<bool>,
+    pub mime_type: Property<SharedString>,
+    pub data: Property<SharedString>,
+    pub drag_image: Property<Image>,
+    pub dragging: Property<bool>,
+    pub drag_started: Callback<VoidArg>,
+    pub drag_finished: Callback<VoidArg>,
+    /// FIXME: remove this
+    pub cached_rendering_data: CachedRenderingData,
+    /// true when the left button was pressed on this item and is still down
+    pressed: Cell<bool>,
+    pressed_x: Cell<Coord>,
+    pressed_y: Cell<Coord>,
+}
+
+impl Item for DragArea {
+    fn init(self: Pin<&Self>, _window_adapter: &Rc<dyn WindowAdapter>) {}
+
+    fn geometry(self: Pin<&Self>) -> LogicalRect {
+        LogicalRect::new(
+            LogicalPoint::from_lengths(self.x(), self.y()),
+            LogicalSize::from_lengths(self.width(), self.height()),
+        )
+    }
+
+    fn layout_info(
+        self: Pin<&Self>,
+        _orientation: Orientation,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+    ) -> LayoutInfo {
+        LayoutInfo::default()
+    }
+
+    fn input_event_filter_before_children(
+        self: Pin<&Self>,
+        _: MouseEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> InputEventFilterResult {
+        if !self.enabled() {
+            return InputEventFilterResult::ForwardAndIgnore;
+        }
+        InputEventFilterResult::ForwardAndInterceptGrab
+    }
+
+    fn input_event(
+        self: Pin<&Self>,
+        event: MouseEvent,
+        window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> InputEventResult {
+        if !self.enabled() {
+            return InputEventResult::EventIgnored;
+        }
+        match event {
+            MouseEvent::Pressed { position, button: PointerEventButton::Left } => {
+                self.pressed.set(true);
+                self.pressed_x.set(position.x);
+                self.pressed_y.set(position.y);
+                InputEventResult::GrabMouse
+            }
+            MouseEvent::Moved { position } if self.pressed.get() => {
+                if !self.dragging() {
+                    let delta =
+                        position - LogicalPoint::new(self.pressed_x.get(), self.pressed_y.get());
+                    let threshold = window_adapter.drag_threshold().get();
+                    if delta.square_length() > threshold * threshold {
+                        self.start_drag(window_adapter);
+                    }
+                }
+                InputEventResult::GrabMouse
+            }
+            MouseEvent::Released { .. } | MouseEvent::Exit => {
+                self.pressed.set(false);
+                if self.dragging() {
+                    Self::FIELD_OFFSETS.dragging.apply_pin(self).set(false);
+                    Self::FIELD_OFFSETS.drag_finished.apply_pin(self).call(&());
+                }
+                InputEventResult::EventAccepted
+            }
+            _ => InputEventResult::EventIgnored,
+        }
+    }
+
+    fn key_event(
+        self: Pin<&Self>,
+        _: &KeyEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> KeyEventResult {
+        KeyEventResult::EventIgnored
+    }
+
+    fn focus_event(
+        self: Pin<&Self>,
+        _: &FocusEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> FocusEventResult {
+        FocusEventResult::FocusIgnored
+    }
+
+    fn render(
+        self: Pin<&Self>,
+        _backend: &mut ItemRendererRef,
+        _self_rc: &ItemRc,
+    ) -> RenderingResult {
+        RenderingResult::ContinueRenderingChildren
+    }
+}
+
+impl ItemConsts for DragArea {
+    const cached_rendering_data_offset: const_field_offset::FieldOffset<
+        DragArea,
+        CachedRenderingData,
+    > = DragArea::FIELD_OFFSETS.cached_rendering_data.as_unpinned_projection();
+}
+
+impl DragArea {
+    fn start_drag(self: Pin<&Self>, window_adapter: &Rc<dyn WindowAdapter>) {
+        Self::FIELD_OFFSETS.dragging.apply_pin(self).set(true);
+        Self::FIELD_OFFSETS.drag_started.apply_pin(self).call(&());
+        let (mime_type, data) = (self.mime_type(), self.data());
+        // Let the platform run the drag operation if it supports it, so that the data can also
+        // be dropped in other applications.
+        if window_adapter.start_drag(&mime_type, &data, &self.drag_image()) {
+            // The platform drag operation is finished, and the pointer release was consumed by it.
+            self.pressed.set(false);
+            Self::FIELD_OFFSETS.dragging.apply_pin(self).set(false);
+            Self::FIELD_OFFSETS.drag_finished.apply_pin(self).call(&());
+        } else {
+            WindowInner::from_pub(window_adapter.window()).start_drag(mime_type, data);
+        }
+    }
+}
+
+/// The implementation of the `DropArea` element
+#[repr(C)]
+#[derive(FieldOffsets, Default, SlintElement)]
+#[pin]
+pub struct DropArea {
+    pub x: Property<LogicalLength>,
+    pub y: Property<LogicalLength>,
+    pub width: Property<LogicalLength>,
+    pub height: Property<LogicalLength>,
+    pub enabled: Property<bool>,
+    pub contains_drag: Property<bool>,
+    pub drag_enter: Callback<DropEventArg>,
+    pub drag_move: Callback<DropEventArg>,
+    pub drag_leave: Callback<VoidArg>,
+    pub dropped: Callback<DropEventArg>,
+    /// FIXME: remove this
+    pub cached_rendering_data: CachedRenderingData,
+}
+
+impl Item for DropArea {
+    fn init(self: Pin<&Self>, _window_adapter: &Rc<dyn WindowAdapter>) {}
+
+    fn geometry(self: Pin<&Self>) -> LogicalRect {
+        LogicalRect::new(
+            LogicalPoint::from_lengths(self.x(), self.y()),
+            LogicalSize::from_lengths(self.width(), self.height()),
+        )
+    }
+
+    fn layout_info(
+        self: Pin<&Self>,
+        _orientation: Orientation,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+    ) -> LayoutInfo {
+        LayoutInfo::default()
+    }
+
+    fn input_event_filter_before_children(
+        self: Pin<&Self>,
+        _: MouseEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> InputEventFilterResult {
+        // The drag events are dispatched separately, see `crate::input::process_drag_event`
+        InputEventFilterResult::ForwardAndIgnore
+    }
+
+    fn input_event(
+        self: Pin<&Self>,
+        _: MouseEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> InputEventResult {
+        InputEventResult::EventIgnored
+    }
+
+    fn key_event(
+        self: Pin<&Self>,
+        _: &KeyEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> KeyEventResult {
+        KeyEventResult::EventIgnored
+    }
+
+    fn focus_event(
+        self: Pin<&Self>,
+        _: &FocusEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> FocusEventResult {
+        FocusEventResult::FocusIgnored
+    }
+
+    fn render(
+        self: Pin<&Self>,
+        _backend: &mut ItemRendererRef,
+        _self_rc: &ItemRc,
+    ) -> RenderingResult {
+        RenderingResult::ContinueRenderingChildren
+    }
+}
+
+impl ItemConsts for DropArea {
+    const cached_rendering_data_offset: const_field_offset::FieldOffset<
+        DropArea,
+        CachedRenderingData,
+    > = DropArea::FIELD_OFFSETS.cached_rendering_data.as_unpinned_projection();
+}
+
+impl DropArea {
+    /// Called when a drag operation enters or moves within this area
+    pub(crate) fn drag_moved(self: Pin<&Self>, event: DropEvent) {
+        if !self.contains_drag() {
+            Self::FIELD_OFFSETS.contains_drag.apply_pin(self).set(true);
+            Self::FIELD_OFFSETS.drag_enter.apply_pin(self).call(&(event,));
+        } else {
+            Self::FIELD_OFFSETS.drag_move.apply_pin(self).call(&(event,));
+        }
+    }
+
+    /// Called when a drag operation leaves this area, or is cancelled
+    pub(crate) fn drag_left(self: Pin<&Self>) {
+        if self.contains_drag() {
+            Self::FIELD_OFFSETS.contains_drag.apply_pin(self).set(false);
+            Self::FIELD_OFFSETS.drag_leave.apply_pin(self).call(&());
+        }
+    }
+
+    /// Called when the data is dropped on this area
+    pub(crate) fn handle_drop(self: Pin<&Self>, event: DropEvent) {
+        Self::FIELD_OFFSETS.contains_drag.apply_pin(self).set(false);
+        Self::FIELD_OFFSETS.dropped.apply_pin(self).call(&(event,));
+    }
+}
@@ -16,10 +16,11 @@
     FocusEvent, FocusEventResult, InputEventFilterResult, InputEventResult, KeyEvent, MouseEvent,
 };
 use crate::item_rendering::CachedRenderingData;
-use crate::items::{Empty, PropertyAnimation};
+use crate::items::{Empty, OvershootBehavior, PropertyAnimation};
 use crate::layout::{LayoutInfo, Orientation};
 use crate::lengths::{
-    LogicalLength, LogicalPoint, LogicalRect, LogicalSize, LogicalVector, PointLengths,
+    LogicalBorderRadius, LogicalLength, LogicalPoint, LogicalPx, LogicalRect, LogicalSize,
+    LogicalVector, PointLengths,
 };
 #[cfg(feature = "rtti")]
 use crate::rtti::*;
@@ -51,6 +52,9 @@ pub struct Flickable {
     pub height: Property<LogicalLength>,
     pub viewport: Empty,
     pub interactive: Property<bool>,
+    pub deceleration: Property<f32>,
+    pub overshoot_behavior: Property<OvershootBehavior>,
+    pub overlay_scrollbars: Property<bool>,
     data: FlickableDataBox,
 
     /// FIXME: remove this
@@ -145,13 +149,23 @@ fn render(
         let geometry = self.geometry();
         (*backend).combine_clip(
             LogicalRect::new(LogicalPoint::default(), geometry.size),
-            LogicalLength::zero(),
+            LogicalBorderRadius::default(),
             LogicalLength::zero(),
         );
         RenderingResult::ContinueRenderingChildren
     }
 }
 
+impl Flickable {
+    /// Scrolls the viewport so that the point `(x, y)` of the viewport is at the top left corner
+    /// of the Flickable, as far as the size of the viewport allows.
+    pub fn scroll_to(self: Pin<&Self>, x: LogicalLength, y: LogicalLength) {
+        let pos = ensure_in_bound(self, LogicalPoint::from_lengths(-x, -y));
+        set_viewport_pos(self, pos);
+        self.data.inner.borrow_mut().animation_end = None;
+    }
+}
+
 impl ItemConsts for Flickable {
     const cached_rendering_data_offset: const_field_offset::FieldOffset<Self, CachedRenderingData> =
         Self::FIELD_OFFSETS.cached_rendering_data.as_unpinned_projection();
@@ -185,6 +199,13 @@ fn deref(&self) -> &Self::Target {
 const DISTANCE_THRESHOLD: Coord = 8 as _;
 /// Time required before we stop caring about child event if the mouse hasn't been moved
 const DURATION_THRESHOLD: Duration = Duration::from_millis(500);
+/// Wheel events that scroll by at least this distance come from a mouse wheel (and not from a
+/// touchpad, which produces its own kinetic scrolling), and are animated.
+const WHEEL_ANIMATION_THRESHOLD: Coord = 40 as _;
+/// Duration in milliseconds of the animation of a mouse wheel step
+const WHEEL_ANIMATION_DURATION: i32 = 120;
+/// Duration in milliseconds of the animation that brings the viewport back within its bounds
+const BOUNCE_BACK_DURATION: i32 = 300;
 
 #[derive(Default, Debug)]
 struct FlickableDataInner {
@@ -194,6 +215,16 @@ struct FlickableDataInner {
     pressed_viewport_pos: LogicalPoint,
     /// Set to true if the flickable is flicking and capturing all mouse event, not forwarding back to the children
     capture_events: bool,
+    /// The time at which the current flick, wheel or bounce animation of the viewport is over
+    animation_end: Option<Instant>,
+    /// The position that the current mouse wheel animation scrolls to
+    wheel_target: Option<LogicalPoint>,
+}
+
+impl FlickableDataInner {
+    fn is_animating(&self) -> bool {
+        self.animation_end.map_or(false, |end| end > crate::animations::current_tick())
+    }
 }
 
 #[derive(Default, Debug)]
@@ -210,16 +241,17 @@ pub fn handle_mouse_filter(
         let mut inner = self.inner.borrow_mut();
         match event {
             MouseEvent::Pressed { position, button: PointerEventButton::Left } => {
+                if inner.is_animating() {
+                    // Stop the viewport where it is, and don't let the press that stopped it
+                    // through to the children.
+                    set_viewport_pos(flick, viewport_pos(flick));
+                    inner.capture_events = true;
+                }
+                inner.animation_end = None;
+                inner.wheel_target = None;
                 inner.pressed_pos = position;
                 inner.pressed_time = Some(crate::animations::current_tick());
-                inner.pressed_viewport_pos = LogicalPoint::from_lengths(
-                    (Flickable::FIELD_OFFSETS.viewport + Empty::FIELD_OFFSETS.x)
-                        .apply_pin(flick)
-                        .get(),
-                    (Flickable::FIELD_OFFSETS.viewport + Empty::FIELD_OFFSETS.y)
-                        .apply_pin(flick)
-                        .get(),
-                );
+                inner.pressed_viewport_pos = viewport_pos(flick);
                 if inner.capture_events {
                     InputEventFilterResult::Intercept
                 } else {
@@ -287,16 +319,16 @@ pub fn handle_mouse(&self, flick: Pin<&Flickable>, event: MouseEvent) -> InputEv
             MouseEvent::Moved { position } => {
                 if inner.pressed_time.is_some() {
                     inner.capture_events = true;
-                    let new_pos = ensure_in_bound(
-                        flick,
-                        inner.pressed_viewport_pos + (position - inner.pressed_pos),
-                    );
-                    (Flickable::FIELD_OFFSETS.viewport + Empty::FIELD_OFFSETS.x)
-                        .apply_pin(flick)
-                        .set(new_pos.x_length());
-                    (Flickable::FIELD_OFFSETS.viewport + Empty::FIELD_OFFSETS.y)
-                        .apply_pin(flick)
-                        .set(new_pos.y_length());
+                    let new_pos = inner.pressed_viewport_pos + (position - inner.pressed_pos);
+                    let bounded_pos = ensure_in_bound(flick, new_pos);
+                    let new_pos = match flick.overshoot_behavior() {
+                        OvershootBehavior::Clamp => bounded_pos,
+                        // Resist dragging past the edges
+                        OvershootBehavior::Bounce => {
+                            bounded_pos + (new_pos - bounded_pos) / (2 as Coord)
+                        }
+                    };
+                    set_viewport_pos(flick, new_pos);
                     InputEventResult::GrabMouse
                 } else {
                     inner.capture_events = false;
@@ -304,24 +336,30 @@ pub fn handle_mouse(&self, flick: Pin<&Flickable>, event: MouseEvent) -> InputEv
                 }
             }
             MouseEvent::Wheel { delta_x, delta_y, .. } => {
-                let old_pos = LogicalPoint::from_lengths(
-                    (Flickable::FIELD_OFFSETS.viewport + Empty::FIELD_OFFSETS.x)
-                        .apply_pin(flick)
-                        .get(),
-                    (Flickable::FIELD_OFFSETS.viewport + Empty::FIELD_OFFSETS.y)
-                        .apply_pin(flick)
-                        .get(),
-                );
+                let delta = LogicalVector::new(delta_x as _, delta_y as _);
+                // Continue from the target of the ongoing wheel animation, so that quickly turning
+                // the wheel accumulates the steps.
+                let wheel_target = inner.wheel_target.filter(|_| inner.is_animating());
                 let new_pos = ensure_in_bound(
                     flick,
-                    old_pos + LogicalVector::new(delta_x as _, delta_y as _),
+                    wheel_target.unwrap_or_else(|| viewport_pos(flick)) + delta,
                 );
-                (Flickable::FIELD_OFFSETS.viewport + Empty::FIELD_OFFSETS.x)
-                    .apply_pin(flick)
-                    .set(new_pos.x_length());
-                (Flickable::FIELD_OFFSETS.viewport + Empty::FIELD_OFFSETS.y)
-                    .apply_pin(flick)
-                    .set(new_pos.y_length());
+                if wheel_target.is_some()
+                    || delta.x.abs() >= WHEEL_ANIMATION_THRESHOLD
+                    || delta.y.abs() >= WHEEL_ANIMATION_THRESHOLD
+                {
+                    inner.animation_end = Some(animate_viewport_to(
+                        flick,
+                        new_pos,
+                        WHEEL_ANIMATION_DURATION,
+                        EasingCurve::CubicBezier([0.0, 0.0, 0.58, 1.0]),
+                    ));
+                    inner.wheel_target = Some(new_pos);
+                } else {
+                    set_viewport_pos(flick, new_pos);
+                    inner.animation_end = None;
+                    inner.wheel_target = None;
+                }
                 InputEventResult::EventAccepted
             }
         }
@@ -335,30 +373,131 @@ fn mouse_released(inner: &mut FlickableDataInner, flick: Pin<&Flickable>, event:
             if dist.square_length() > (DISTANCE_THRESHOLD * DISTANCE_THRESHOLD) as f32 && millis > 1
             {
                 let speed = dist / (millis as f32);
-
-                let duration = 250;
-                let final_pos = ensure_in_bound(
+                inner.animation_end = flick_with_speed(flick, speed);
+            }
+        }
+        if inner.animation_end.is_none() {
+            // Bring the viewport back within its bounds if it was dragged past its edges
+            let pos = viewport_pos(flick);
+            let bounded_pos = ensure_in_bound(flick, pos);
+            if bounded_pos != pos {
+                inner.animation_end = Some(animate_viewport_to(
                     flick,
-                    (inner.pressed_viewport_pos.cast() + dist + speed * (duration as f32)).cast(),
-                );
-                let anim = PropertyAnimation {
-                    duration,
-                    easing: EasingCurve::CubicBezier([0.0, 0.0, 0.58, 1.0]),
-                    ..PropertyAnimation::default()
-                };
-                (Flickable::FIELD_OFFSETS.viewport + Empty::FIELD_OFFSETS.x)
-                    .apply_pin(flick)
-                    .set_animated_value(final_pos.x_length(), anim.clone());
-                (Flickable::FIELD_OFFSETS.viewport + Empty::FIELD_OFFSETS.y)
-                    .apply_pin(flick)
-                    .set_animated_value(final_pos.y_length(), anim);
+                    bounded_pos,
+                    BOUNCE_BACK_DURATION,
+                    EasingCurve::CubicBezier([0.0, 0.0, 0.58, 1.0]),
+                ));
             }
         }
-        inner.capture_events = false; // FIXME: should only be set to false once the flick animation is over
+        // A press during the animation stops it and is intercepted in handle_mouse_filter
+        inner.capture_events = false;
         inner.pressed_time = None;
     }
 }
 
+/// Continues the movement of the viewport after a flick at the given speed (in logical pixels per
+/// millisecond), slowing it down with the Flickable's deceleration. Returns the time at which the
+/// animation is over, or None if the viewport doesn't move.
+fn flick_with_speed(
+    flick: Pin<&Flickable>,
+    speed: euclid::Vector2D<f32, LogicalPx>,
+) -> Option<Instant> {
+    // The deceleration property is in logical pixels per second squared
+    let deceleration = flick.deceleration() / 1_000_000.;
+    let speed_length = speed.length();
+    if deceleration <= 0. || speed_length <= 0. {
+        return None;
+    }
+    let start_pos = viewport_pos(flick).cast::<f32>();
+    let target_pos = start_pos + speed * (speed_length / (2. * deceleration));
+    let final_pos = ensure_in_bound(flick, target_pos.cast()).cast::<f32>();
+    // With a constant deceleration, the distance d is covered in sqrt(2 * d / deceleration)
+    let duration = (2. * (final_pos - start_pos).length() / deceleration).sqrt() as i32;
+    if duration <= 0 {
+        return None;
+    }
+    let easing =
+        if final_pos != target_pos && flick.overshoot_behavior() == OvershootBehavior::Bounce {
+            // Overshoot the edge a little before settling
+            EasingCurve::CubicBezier([0.34, 1.56, 0.64, 1.0])
+        } else {
+            // The curve of a constant deceleration
+            EasingCurve::CubicBezier([1. / 3., 2. / 3., 2. / 3., 1.0])
+        };
+    Some(animate_viewport_to(flick, final_pos.cast(), duration, easing))
+}
+
+/// Scrolls the Flickables that contain the given item, so that the item becomes visible.
+pub fn ensure_item_visible(item: &ItemRc) {
+    // The geometry of the item, relative to the parent of `current`
+    let mut rect = item.geometry();
+    let mut current = item.clone();
+    while let Some(parent) = current.parent_item() {
+        if let Some(flick) = parent.downcast::<Flickable>() {
+            // The viewport is a child of the Flickable, so `rect` is relative to the Flickable
+            let flick = flick.as_pin_ref();
+            let scroll_delta = |min: Coord, max: Coord, size: Coord| -> Coord {
+                if min <= 0 as Coord && max >= size {
+                    // The item already covers the whole visible area
+                    0 as _
+                } else if min < 0 as Coord || max - min > size {
+                    -min
+                } else if max > size {
+                    size - max
+                } else {
+                    0 as _
+                }
+            };
+            let old_pos = viewport_pos(flick);
+            let delta = LogicalVector::new(
+                scroll_delta(rect.min_x(), rect.max_x(), flick.width().get()),
+                scroll_delta(rect.min_y(), rect.max_y(), flick.height().get()),
+            );
+            let new_pos = ensure_in_bound(flick, old_pos + delta);
+            if new_pos != old_pos {
+                set_viewport_pos(flick, new_pos);
+                flick.data.inner.borrow_mut().animation_end = None;
+                rect = rect.translate(new_pos - old_pos);
+            }
+        }
+        rect = rect.translate(parent.geometry().origin.to_vector());
+        current = parent;
+    }
+}
+
+fn viewport_pos(flick: Pin<&Flickable>) -> LogicalPoint {
+    LogicalPoint::from_lengths(
+        (Flickable::FIELD_OFFSETS.viewport + Empty::FIELD_OFFSETS.x).apply_pin(flick).get(),
+        (Flickable::FIELD_OFFSETS.viewport + Empty::FIELD_OFFSETS.y).apply_pin(flick).get(),
+    )
+}
+
+fn set_viewport_pos(flick: Pin<&Flickable>, pos: LogicalPoint) {
+    (Flickable::FIELD_OFFSETS.viewport + Empty::FIELD_OFFSETS.x)
+        .apply_pin(flick)
+        .set(pos.x_length());
+    (Flickable::FIELD_OFFSETS.viewport + Empty::FIELD_OFFSETS.y)
+        .apply_pin(flick)
+        .set(pos.y_length());
+}
+
+/// Animates the viewport to the given position, and returns the time at which the animation is over
+fn animate_viewport_to(
+    flick: Pin<&Flickable>,
+    pos: LogicalPoint,
+    duration: i32,
+    easing: EasingCurve,
+) -> Instant {
+    let anim = PropertyAnimation { duration, easing, ..PropertyAnimation::default() };
+    (Flickable::FIELD_OFFSETS.viewport + Empty::FIELD_OFFSETS.x)
+        .apply_pin(flick)
+        .set_animated_value(pos.x_length(), anim.clone());
+    (Flickable::FIELD_OFFSETS.viewport + Empty::FIELD_OFFSETS.y)
+        .apply_pin(flick)
+        .set_animated_value(pos.y_length(), anim);
+    crate::animations::current_tick() + Duration::from_millis(duration as u64)
+}
+
 /// Make sure that the point is within the bounds
 fn ensure_in_bound(flick: Pin<&Flickable>, p: LogicalPoint) -> LogicalPoint {
     let w = flick.width();
@@ -389,3 +528,15 @@ fn ensure_in_bound(flick: Pin<&Flickable>, p: LogicalPoint) -> LogicalPoint {
 pub unsafe extern "C" fn slint_flickable_data_free(data: *mut FlickableDataBox) {
     core::ptr::drop_in_place(data);
 }
+
+#[cfg(feature = "ffi")]
+#[no_mangle]
+pub extern "C" fn slint_flickable_scroll_to(flick: Pin<&Flickable>, x: Coord, y: Coord) {
+    flick.scroll_to(LogicalLength::new(x), LogicalLength::new(y))
+}
+
+#[cfg(feature = "ffi")]
+#[no_mangle]
+pub extern "C" fn slint_ensure_item_visible(item: &ItemRc) {
+    ensure_item_visible(item)
+}
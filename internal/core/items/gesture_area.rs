@@ -0,0 +1,146 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+//! The `GestureArea` item, which receives the multi-touch gestures recognized by
+//! [`crate::input::process_touch_event`]
+
+use super::{
+    GestureEventArg, Item, ItemConsts, ItemRc, ItemRendererRef, KeyEventResult, RenderingResult,
+};
+use crate::graphics::Point;
+use crate::input::{
+    FocusEvent, FocusEventResult, InputEventFilterResult, InputEventResult, KeyEvent, MouseEvent,
+};
+use crate::item_rendering::CachedRenderingData;
+use crate::layout::{LayoutInfo, Orientation};
+use crate::lengths::{LogicalLength, LogicalPoint, LogicalRect, LogicalSize};
+#[cfg(feature = "rtti")]
+use crate::rtti::*;
+use crate::window::WindowAdapter;
+use crate::{Callback, Property};
+use alloc::rc::Rc;
+use const_field_offset::FieldOffsets;
+use core::pin::Pin;
+use i_slint_core_macros::*;
+
+/// The state of a two-finger gesture, as delivered to the callbacks of `GestureArea`.
+/// All the values are relative to the start of the gesture.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[repr(C)]
+pub struct GestureEvent {
+    /// The point between the two fingers, relative to the `GestureArea`
+    pub center: Point,
+    /// The ratio between the current and the initial distance of the fingers
+    pub scale: f32,
+    /// The rotation of the line between the fingers, in degrees, clockwise
+    pub rotation: f32,
+    /// How far the center moved
+    pub translation: Point,
+    /// The speed of the center, in logical pixels per second
+    pub velocity: Point,
+}
+
+/// The implementation of the `GestureArea` element
+#[repr(C)]
+#[derive(FieldOffsets, Default, SlintElement)]
+#[pin]
+pub struct GestureArea {
+    pub x: Property<LogicalLength>,
+    pub y: Property<LogicalLength>,
+    pub width: Property<LogicalLength>,
+    pub height: Property<LogicalLength>,
+    pub enabled: Property<bool>,
+    pub active: Property<bool>,
+    pub gesture_started: Callback<GestureEventArg>,
+    pub gesture_updated: Callback<GestureEventArg>,
+    pub gesture_finished: Callback<GestureEventArg>,
+    /// FIXME: remove this
+    pub cached_rendering_data: CachedRenderingData,
+}
+
+impl Item for GestureArea {
+    fn init(self: Pin<&Self>, _window_adapter: &Rc<dyn WindowAdapter>) {}
+
+    fn geometry(self: Pin<&Self>) -> LogicalRect {
+        LogicalRect::new(
+            LogicalPoint::from_lengths(self.x(), self.y()),
+            LogicalSize::from_lengths(self.width(), self.height()),
+        )
+    }
+
+    fn layout_info(
+        self: Pin<&Self>,
+        _orientation: Orientation,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+    ) -> LayoutInfo {
+        LayoutInfo::default()
+    }
+
+    fn input_event_filter_before_children(
+        self: Pin<&Self>,
+        _: MouseEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> InputEventFilterResult {
+        // The gestures are dispatched separately, see `crate::input::process_touch_event`
+        InputEventFilterResult::ForwardAndIgnore
+    }
+
+    fn input_event(
+        self: Pin<&Self>,
+        _: MouseEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> InputEventResult {
+        InputEventResult::EventIgnored
+    }
+
+    fn key_event(
+        self: Pin<&Self>,
+        _: &KeyEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> KeyEventResult {
+        KeyEventResult::EventIgnored
+    }
+
+    fn focus_event(
+        self: Pin<&Self>,
+        _: &FocusEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> FocusEventResult {
+        FocusEventResult::FocusIgnored
+    }
+
+    fn render(
+        self: Pin<&Self>,
+        _backend: &mut ItemRendererRef,
+        _self_rc: &ItemRc,
+    ) -> RenderingResult {
+        RenderingResult::ContinueRenderingChildren
+    }
+}
+
+impl ItemConsts for GestureArea {
+    const cached_rendering_data_offset: const_field_offset::FieldOffset<
+        GestureArea,
+        CachedRenderingData,
+    > = GestureArea::FIELD_OFFSETS.cached_rendering_data.as_unpinned_projection();
+}
+
+impl GestureArea {
+    pub(crate) fn start_gesture(self: Pin<&Self>, event: GestureEvent) {
+        Self::FIELD_OFFSETS.active.apply_pin(self).set(true);
+        Self::FIELD_OFFSETS.gesture_started.apply_pin(self).call(&(event,));
+    }
+
+    pub(crate) fn update_gesture(self: Pin<&Self>, event: GestureEvent) {
+        Self::FIELD_OFFSETS.gesture_updated.apply_pin(self).call(&(event,));
+    }
+
+    pub(crate) fn finish_gesture(self: Pin<&Self>, event: GestureEvent) {
+        Self::FIELD_OFFSETS.active.apply_pin(self).set(false);
+        Self::FIELD_OFFSETS.gesture_finished.apply_pin(self).call(&(event,));
+    }
+}
@@ -22,6 +22,7 @@
 use crate::{Brush, Coord, Property};
 use alloc::rc::Rc;
 use const_field_offset::FieldOffsets;
+use core::cell::Cell;
 use core::pin::Pin;
 use i_slint_core_macros::*;
 
@@ -31,13 +32,21 @@
 /// The implementation of the `Image` element
 pub struct ImageItem {
     pub source: Property<crate::graphics::Image>,
+    pub placeholder: Property<crate::graphics::Image>,
     pub x: Property<LogicalLength>,
     pub y: Property<LogicalLength>,
     pub width: Property<LogicalLength>,
     pub height: Property<LogicalLength>,
     pub image_fit: Property<ImageFit>,
     pub image_rendering: Property<ImageRendering>,
+    pub playing: Property<bool>,
+    pub loop_count: Property<i32>,
+    pub current_frame: Property<i32>,
     pub cached_rendering_data: CachedRenderingData,
+    /// true while a timer is running to move on to the next frame of an animated image
+    frame_timer_pending: Cell<bool>,
+    /// The number of times the animation was played since `playing` was last set to true
+    completed_loops: Cell<u32>,
 }
 
 impl Item for ImageItem {
@@ -109,11 +118,20 @@ fn render(
         backend: &mut &mut dyn ItemRenderer,
         self_rc: &ItemRc,
     ) -> RenderingResult {
+        update_animation::<Self>(self, self_rc);
         (*backend).draw_image(self, self_rc);
         RenderingResult::ContinueRenderingChildren
     }
 }
 
+impl ImageItem {
+    /// Returns the image to draw: the current frame of the source if it is animated, or
+    /// the source itself otherwise.
+    pub fn displayed_source(self: Pin<&Self>) -> crate::graphics::Image {
+        displayed_source(self.source(), self.current_frame())
+    }
+}
+
 impl ItemConsts for ImageItem {
     const cached_rendering_data_offset: const_field_offset::FieldOffset<
         ImageItem,
@@ -127,6 +145,7 @@ impl ItemConsts for ImageItem {
 /// The implementation of the `ClippedImage` element
 pub struct ClippedImage {
     pub source: Property<crate::graphics::Image>,
+    pub placeholder: Property<crate::graphics::Image>,
     pub x: Property<LogicalLength>,
     pub y: Property<LogicalLength>,
     pub width: Property<LogicalLength>,
@@ -138,7 +157,14 @@ pub struct ClippedImage {
     pub source_clip_y: Property<i32>,
     pub source_clip_width: Property<i32>,
     pub source_clip_height: Property<i32>,
+    pub playing: Property<bool>,
+    pub loop_count: Property<i32>,
+    pub current_frame: Property<i32>,
     pub cached_rendering_data: CachedRenderingData,
+    /// true while a timer is running to move on to the next frame of an animated image
+    frame_timer_pending: Cell<bool>,
+    /// The number of times the animation was played since `playing` was last set to true
+    completed_loops: Cell<u32>,
 }
 
 impl Item for ClippedImage {
@@ -210,14 +236,145 @@ fn render(
         backend: &mut &mut dyn ItemRenderer,
         self_rc: &ItemRc,
     ) -> RenderingResult {
+        update_animation::<Self>(self, self_rc);
         (*backend).draw_clipped_image(self, self_rc);
         RenderingResult::ContinueRenderingChildren
     }
 }
 
+impl ClippedImage {
+    /// Returns the image to draw: the current frame of the source if it is animated, or
+    /// the source itself otherwise.
+    pub fn displayed_source(self: Pin<&Self>) -> crate::graphics::Image {
+        displayed_source(self.source(), self.current_frame())
+    }
+}
+
 impl ItemConsts for ClippedImage {
     const cached_rendering_data_offset: const_field_offset::FieldOffset<
         ClippedImage,
         CachedRenderingData,
     > = ClippedImage::FIELD_OFFSETS.cached_rendering_data.as_unpinned_projection();
 }
+
+/// Implemented by the image items to play animated images.
+trait AnimatedImageItem: vtable::HasStaticVTable<super::ItemVTable> {
+    fn source(self: Pin<&Self>) -> crate::graphics::Image;
+    fn playing(self: Pin<&Self>) -> bool;
+    fn loop_count(self: Pin<&Self>) -> i32;
+    fn current_frame_property(self: Pin<&Self>) -> Pin<&Property<i32>>;
+    fn frame_timer_pending(&self) -> &Cell<bool>;
+    fn completed_loops(&self) -> &Cell<u32>;
+}
+
+impl AnimatedImageItem for ImageItem {
+    fn source(self: Pin<&Self>) -> crate::graphics::Image {
+        self.source()
+    }
+    fn playing(self: Pin<&Self>) -> bool {
+        self.playing()
+    }
+    fn loop_count(self: Pin<&Self>) -> i32 {
+        self.loop_count()
+    }
+    fn current_frame_property(self: Pin<&Self>) -> Pin<&Property<i32>> {
+        Self::FIELD_OFFSETS.current_frame.apply_pin(self)
+    }
+    fn frame_timer_pending(&self) -> &Cell<bool> {
+        &self.frame_timer_pending
+    }
+    fn completed_loops(&self) -> &Cell<u32> {
+        &self.completed_loops
+    }
+}
+
+impl AnimatedImageItem for ClippedImage {
+    fn source(self: Pin<&Self>) -> crate::graphics::Image {
+        self.source()
+    }
+    fn playing(self: Pin<&Self>) -> bool {
+        self.playing()
+    }
+    fn loop_count(self: Pin<&Self>) -> i32 {
+        self.loop_count()
+    }
+    fn current_frame_property(self: Pin<&Self>) -> Pin<&Property<i32>> {
+        Self::FIELD_OFFSETS.current_frame.apply_pin(self)
+    }
+    fn frame_timer_pending(&self) -> &Cell<bool> {
+        &self.frame_timer_pending
+    }
+    fn completed_loops(&self) -> &Cell<u32> {
+        &self.completed_loops
+    }
+}
+
+fn displayed_source(source: crate::graphics::Image, current_frame: i32) -> crate::graphics::Image {
+    let frame_count = source.frame_count();
+    if frame_count <= 1 {
+        return source;
+    }
+    source.frame(current_frame.rem_euclid(frame_count as i32) as usize).0
+}
+
+/// Starts a timer to show the next frame of an animated image, if the image is playing.
+/// This is called when the item is rendered, so that the animation only runs while it is visible.
+fn update_animation<T: AnimatedImageItem + 'static>(item: Pin<&T>, self_rc: &ItemRc) {
+    if !item.playing() {
+        item.completed_loops().set(0);
+        return;
+    }
+    if item.frame_timer_pending().get() {
+        return;
+    }
+    let source = item.source();
+    let frame_count = source.frame_count();
+    if frame_count <= 1 {
+        return;
+    }
+    let current_frame = item.current_frame_property().get().rem_euclid(frame_count as i32);
+    let loop_count = item.loop_count();
+    if loop_count > 0
+        && item.completed_loops().get() >= loop_count as u32
+        && current_frame as usize == frame_count - 1
+    {
+        return;
+    }
+    let duration = match source.frame(current_frame as usize).1 {
+        // Like web browsers, treat very short delays as unspecified
+        Some(duration) if duration.as_millis() > 10 => duration,
+        _ => core::time::Duration::from_millis(100),
+    };
+    item.frame_timer_pending().set(true);
+    let self_weak = self_rc.downgrade();
+    crate::timers::Timer::single_shot(duration, move || {
+        let self_rc = match self_weak.upgrade() {
+            Some(self_rc) => self_rc,
+            None => return,
+        };
+        if let Some(item) = self_rc.downcast::<T>() {
+            let item = item.as_pin_ref();
+            item.frame_timer_pending().set(false);
+            if !item.playing() {
+                return;
+            }
+            let frame_count = item.source().frame_count() as i32;
+            if frame_count <= 1 {
+                return;
+            }
+            let current_frame = item.current_frame_property();
+            let mut next_frame = current_frame.get().rem_euclid(frame_count) + 1;
+            if next_frame == frame_count {
+                let completed_loops = item.completed_loops().get() + 1;
+                item.completed_loops().set(completed_loops);
+                let loop_count = item.loop_count();
+                if loop_count > 0 && completed_loops >= loop_count as u32 {
+                    // Stay on the last frame once the animation is finished
+                    return;
+                }
+                next_frame = 0;
+            }
+            current_frame.set(next_frame);
+        }
+    });
+}
@@ -8,7 +8,9 @@
 Lookup the [`crate::items`] module documentation.
 */
 
-use super::{FillRule, Item, ItemConsts, ItemRc, ItemRendererRef, RenderingResult};
+use super::{
+    FillRule, Item, ItemConsts, ItemRc, ItemRendererRef, LineCap, LineJoin, RenderingResult,
+};
 use crate::graphics::{Brush, PathData, PathDataIterator};
 use crate::input::{
     FocusEvent, FocusEventResult, InputEventFilterResult, InputEventResult, KeyEvent,
@@ -18,7 +20,8 @@
 
 use crate::layout::{LayoutInfo, Orientation};
 use crate::lengths::{
-    LogicalLength, LogicalPoint, LogicalRect, LogicalSize, LogicalVector, PointLengths,
+    LogicalBorderRadius, LogicalLength, LogicalPoint, LogicalRect, LogicalSize, LogicalVector,
+    PointLengths,
 };
 #[cfg(feature = "rtti")]
 use crate::rtti::*;
@@ -44,6 +47,9 @@ pub struct Path {
     pub fill_rule: Property<FillRule>,
     pub stroke: Property<Brush>,
     pub stroke_width: Property<LogicalLength>,
+    pub stroke_line_cap: Property<LineCap>,
+    pub stroke_line_join: Property<LineJoin>,
+    pub hit_test_fill: Property<bool>,
     pub viewbox_x: Property<f32>,
     pub viewbox_y: Property<f32>,
     pub viewbox_width: Property<f32>,
@@ -85,6 +91,9 @@ fn input_event_filter_before_children(
             {
                 return InputEventFilterResult::Intercept;
             }
+            if self.hit_test_fill() && !self.fill_contains(pos) {
+                return InputEventFilterResult::Intercept;
+            }
         }
         InputEventFilterResult::ForwardAndIgnore
     }
@@ -124,7 +133,11 @@ fn render(
         let clip = self.clip();
         if clip {
             (*backend).save_state();
-            (*backend).combine_clip(self.geometry(), LogicalLength::zero(), LogicalLength::zero());
+            (*backend).combine_clip(
+                self.geometry(),
+                LogicalBorderRadius::default(),
+                LogicalLength::zero(),
+            );
         }
         (*backend).draw_path(self, self_rc);
         if clip {
@@ -162,6 +175,23 @@ pub fn fitted_path_events(self: Pin<&Self>) -> (LogicalVector, PathDataIterator)
         elements_iter.fit(bounds_width.get() as _, bounds_height.get() as _, maybe_viewbox);
         (offset, elements_iter)
     }
+
+    /// Returns true if the given point, relative to the path item, is within the filled area of the
+    /// path according to the fill rule.
+    pub fn fill_contains(self: Pin<&Self>, pos: LogicalPoint) -> bool {
+        let (offset, path_events) = self.fitted_path_events();
+        let pos = pos - offset;
+        let fill_rule = match self.fill_rule() {
+            FillRule::Nonzero => lyon_path::FillRule::NonZero,
+            FillRule::Evenodd => lyon_path::FillRule::EvenOdd,
+        };
+        lyon_algorithms::hit_test::hit_test_path(
+            &lyon_path::math::point(pos.x as _, pos.y as _),
+            path_events.iter(),
+            fill_rule,
+            0.1,
+        )
+    }
 }
 
 impl ItemConsts for Path {
@@ -0,0 +1,227 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+//! The `Shortcut` item
+
+use super::{Item, ItemConsts, ItemRc, ItemRendererRef, KeyEventResult, RenderingResult, VoidArg};
+use crate::input::{
+    FocusEvent, FocusEventResult, InputEventFilterResult, InputEventResult, KeyEvent, MouseEvent,
+};
+use crate::item_rendering::CachedRenderingData;
+use crate::layout::{LayoutInfo, Orientation};
+use crate::lengths::{LogicalLength, LogicalPoint, LogicalRect, LogicalSize};
+#[cfg(feature = "rtti")]
+use crate::rtti::*;
+use crate::window::WindowAdapter;
+use crate::{Callback, Property, SharedString};
+use alloc::boxed::Box;
+use alloc::rc::Rc;
+use const_field_offset::FieldOffsets;
+use core::cell::RefCell;
+use core::pin::Pin;
+use i_slint_common::key_sequence::{parse_key_sequence, KeySequence};
+use i_slint_core_macros::*;
+
+/// The implementation of the `Shortcut` element.
+///
+/// Shortcuts don't receive key events themselves: the window activates them when no focused
+/// item accepted a key press, see `WindowInner::process_key_input`.
+#[repr(C)]
+#[derive(FieldOffsets, Default, SlintElement)]
+#[pin]
+pub struct Shortcut {
+    pub x: Property<LogicalLength>,
+    pub y: Property<LogicalLength>,
+    pub width: Property<LogicalLength>,
+    pub height: Property<LogicalLength>,
+    pub keys: Property<SharedString>,
+    pub enabled: Property<bool>,
+    /// When true, the shortcut is only active while the focus is within the parent of the shortcut
+    pub local: Property<bool>,
+    pub activated: Callback<VoidArg>,
+    /// FIXME: remove this
+    pub cached_rendering_data: CachedRenderingData,
+    data: ShortcutDataBox,
+}
+
+impl Item for Shortcut {
+    fn init(self: Pin<&Self>, _window_adapter: &Rc<dyn WindowAdapter>) {}
+
+    fn geometry(self: Pin<&Self>) -> LogicalRect {
+        LogicalRect::new(
+            LogicalPoint::from_lengths(self.x(), self.y()),
+            LogicalSize::from_lengths(self.width(), self.height()),
+        )
+    }
+
+    fn layout_info(
+        self: Pin<&Self>,
+        _orientation: Orientation,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+    ) -> LayoutInfo {
+        LayoutInfo::default()
+    }
+
+    fn input_event_filter_before_children(
+        self: Pin<&Self>,
+        _: MouseEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> InputEventFilterResult {
+        InputEventFilterResult::ForwardAndIgnore
+    }
+
+    fn input_event(
+        self: Pin<&Self>,
+        _: MouseEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> InputEventResult {
+        InputEventResult::EventIgnored
+    }
+
+    fn key_event(
+        self: Pin<&Self>,
+        _: &KeyEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> KeyEventResult {
+        KeyEventResult::EventIgnored
+    }
+
+    fn focus_event(
+        self: Pin<&Self>,
+        _: &FocusEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> FocusEventResult {
+        FocusEventResult::FocusIgnored
+    }
+
+    fn render(
+        self: Pin<&Self>,
+        _backend: &mut ItemRendererRef,
+        _self_rc: &ItemRc,
+    ) -> RenderingResult {
+        RenderingResult::ContinueRenderingChildren
+    }
+}
+
+impl ItemConsts for Shortcut {
+    const cached_rendering_data_offset: const_field_offset::FieldOffset<
+        Shortcut,
+        CachedRenderingData,
+    > = Shortcut::FIELD_OFFSETS.cached_rendering_data.as_unpinned_projection();
+}
+
+impl Shortcut {
+    /// Returns true if the shortcut is enabled and its keys match the key press
+    pub(crate) fn matches(self: Pin<&Self>, event: &KeyEvent) -> bool {
+        if !self.enabled() {
+            return false;
+        }
+        let keys = self.keys();
+        let mut sequence = self.data.sequence.borrow_mut();
+        if sequence.as_ref().map_or(true, |(parsed_keys, _)| *parsed_keys != keys) {
+            let parsed = parse_key_sequence(&keys).ok();
+            *sequence = Some((keys, parsed));
+        }
+        match sequence.as_ref() {
+            Some((_, Some(sequence))) => key_sequence_matches(sequence, event),
+            _ => false,
+        }
+    }
+
+    pub(crate) fn activate(self: Pin<&Self>) {
+        Self::FIELD_OFFSETS.activated.apply_pin(self).call(&());
+    }
+}
+
+#[derive(Default)]
+/// The key sequence of a `Shortcut`, parsed when its `keys` change
+pub struct ShortcutData {
+    /// The `keys` that were parsed, and the parsed sequence if they are valid
+    sequence: RefCell<Option<(SharedString, Option<KeySequence>)>>,
+}
+
+#[repr(C)]
+/// Wraps the internal data structure for the Shortcut
+pub struct ShortcutDataBox(core::ptr::NonNull<ShortcutData>);
+
+impl Default for ShortcutDataBox {
+    fn default() -> Self {
+        ShortcutDataBox(Box::leak(Box::new(ShortcutData::default())).into())
+    }
+}
+impl Drop for ShortcutDataBox {
+    fn drop(&mut self) {
+        // Safety: the self.0 was constructed from a Box::leak in ShortcutDataBox::default
+        drop(unsafe { Box::from_raw(self.0.as_ptr()) });
+    }
+}
+
+impl core::ops::Deref for ShortcutDataBox {
+    type Target = ShortcutData;
+    fn deref(&self) -> &Self::Target {
+        // Safety: initialized in ShortcutDataBox::default
+        unsafe { self.0.as_ref() }
+    }
+}
+
+/// # Safety
+/// This must be called using a non-null pointer pointing to a chunk of memory big enough to
+/// hold a ShortcutDataBox
+#[cfg(feature = "ffi")]
+#[no_mangle]
+pub unsafe extern "C" fn slint_shortcut_data_init(data: *mut ShortcutDataBox) {
+    core::ptr::write(data, ShortcutDataBox::default());
+}
+
+/// # Safety
+/// This must be called using a non-null pointer pointing to an initialized ShortcutDataBox
+#[cfg(feature = "ffi")]
+#[no_mangle]
+pub unsafe extern "C" fn slint_shortcut_data_free(data: *mut ShortcutDataBox) {
+    core::ptr::drop_in_place(data);
+}
+
+fn key_sequence_matches(sequence: &KeySequence, event: &KeyEvent) -> bool {
+    let mut chars = event.text.chars();
+    let key = match (chars.next(), chars.next()) {
+        (Some(key), None) => key,
+        _ => return false,
+    };
+    if key.to_lowercase().next() != Some(sequence.key) {
+        return false;
+    }
+    let modifiers = &event.modifiers;
+    // For symbols such as `?`, shift is part of how the character is typed, so it is only
+    // checked when the sequence explicitly requires it.
+    let is_symbol =
+        !key.is_alphabetic() && !key.is_control() && !('\u{f700}'..='\u{f7ff}').contains(&key);
+    modifiers.control == sequence.control
+        && modifiers.alt == sequence.alt
+        && modifiers.meta == sequence.meta
+        && (modifiers.shift == sequence.shift || (is_symbol && !sequence.shift))
+}
+
+#[test]
+fn test_key_sequence_matches() {
+    use crate::input::KeyboardModifiers;
+    let event = |text: &str, control, shift| KeyEvent {
+        text: text.into(),
+        modifiers: KeyboardModifiers { control, shift, ..Default::default() },
+        ..Default::default()
+    };
+    let ctrl_s = parse_key_sequence("Ctrl+S").unwrap();
+    assert!(key_sequence_matches(&ctrl_s, &event("s", true, false)));
+    assert!(!key_sequence_matches(&ctrl_s, &event("s", false, false)));
+    assert!(!key_sequence_matches(&ctrl_s, &event("S", true, true)));
+    let ctrl_shift_s = parse_key_sequence("Ctrl+Shift+S").unwrap();
+    assert!(key_sequence_matches(&ctrl_shift_s, &event("S", true, true)));
+    let question = parse_key_sequence("Ctrl+?").unwrap();
+    assert!(key_sequence_matches(&question, &event("?", true, true)));
+    let f5 = parse_key_sequence("F5").unwrap();
+    assert!(key_sequence_matches(&f5, &event("\u{F708}", false, false)));
+    assert!(!key_sequence_matches(&f5, &event("\u{F708}", false, true)));
+}
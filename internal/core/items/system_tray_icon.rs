@@ -0,0 +1,374 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+//! The `SystemTrayIcon`, `PlatformMenuBar` and `PlatformMenuItem` items
+//!
+//! These items are not rendered: the window collects them in [`SystemTrayIconState`] and
+//! [`MenuEntry`] and passes them to [`crate::window::WindowAdapterSealed::apply_system_tray_icon`]
+//! and [`crate::window::WindowAdapterSealed::apply_menu_bar`], so that the backend can show them
+//! with the native APIs.
+
+use super::{Item, ItemConsts, ItemRc, ItemRendererRef, KeyEventResult, RenderingResult, VoidArg};
+use crate::graphics::Image;
+use crate::input::{
+    FocusEvent, FocusEventResult, InputEventFilterResult, InputEventResult, KeyEvent, MouseEvent,
+};
+use crate::item_rendering::CachedRenderingData;
+use crate::item_tree::ItemWeak;
+use crate::layout::{LayoutInfo, Orientation};
+use crate::lengths::{LogicalLength, LogicalPoint, LogicalRect, LogicalSize};
+#[cfg(feature = "rtti")]
+use crate::rtti::*;
+use crate::window::WindowAdapter;
+use crate::{Callback, Property, SharedString};
+use alloc::rc::Rc;
+use alloc::vec::Vec;
+use const_field_offset::FieldOffsets;
+use core::pin::Pin;
+use i_slint_core_macros::*;
+
+/// The implementation of the `SystemTrayIcon` element
+#[repr(C)]
+#[derive(FieldOffsets, Default, SlintElement)]
+#[pin]
+pub struct SystemTrayIcon {
+    pub x: Property<LogicalLength>,
+    pub y: Property<LogicalLength>,
+    pub width: Property<LogicalLength>,
+    pub height: Property<LogicalLength>,
+    pub icon: Property<Image>,
+    pub tooltip: Property<SharedString>,
+    pub activated: Callback<VoidArg>,
+    /// FIXME: remove this
+    pub cached_rendering_data: CachedRenderingData,
+}
+
+impl Item for SystemTrayIcon {
+    fn init(self: Pin<&Self>, _window_adapter: &Rc<dyn WindowAdapter>) {}
+
+    fn geometry(self: Pin<&Self>) -> LogicalRect {
+        LogicalRect::new(
+            LogicalPoint::from_lengths(self.x(), self.y()),
+            LogicalSize::from_lengths(self.width(), self.height()),
+        )
+    }
+
+    fn layout_info(
+        self: Pin<&Self>,
+        _orientation: Orientation,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+    ) -> LayoutInfo {
+        LayoutInfo::default()
+    }
+
+    fn input_event_filter_before_children(
+        self: Pin<&Self>,
+        _: MouseEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> InputEventFilterResult {
+        InputEventFilterResult::ForwardAndIgnore
+    }
+
+    fn input_event(
+        self: Pin<&Self>,
+        _: MouseEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> InputEventResult {
+        InputEventResult::EventIgnored
+    }
+
+    fn key_event(
+        self: Pin<&Self>,
+        _: &KeyEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> KeyEventResult {
+        KeyEventResult::EventIgnored
+    }
+
+    fn focus_event(
+        self: Pin<&Self>,
+        _: &FocusEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> FocusEventResult {
+        FocusEventResult::FocusIgnored
+    }
+
+    fn render(
+        self: Pin<&Self>,
+        _backend: &mut ItemRendererRef,
+        _self_rc: &ItemRc,
+    ) -> RenderingResult {
+        // The menu items within are shown by the backend
+        RenderingResult::ContinueRenderingWithoutChildren
+    }
+}
+
+impl ItemConsts for SystemTrayIcon {
+    const cached_rendering_data_offset: const_field_offset::FieldOffset<
+        SystemTrayIcon,
+        CachedRenderingData,
+    > = SystemTrayIcon::FIELD_OFFSETS.cached_rendering_data.as_unpinned_projection();
+}
+
+impl SystemTrayIcon {
+    pub(crate) fn state(self: Pin<&Self>, self_rc: &ItemRc) -> SystemTrayIconState {
+        SystemTrayIconState {
+            icon: self.icon(),
+            tooltip: self.tooltip(),
+            menu: menu_entries(self_rc),
+            item: self_rc.downgrade(),
+        }
+    }
+}
+
+/// The implementation of the `PlatformMenuItem` element
+#[repr(C)]
+#[derive(FieldOffsets, Default, SlintElement)]
+#[pin]
+pub struct PlatformMenuItem {
+    pub x: Property<LogicalLength>,
+    pub y: Property<LogicalLength>,
+    pub width: Property<LogicalLength>,
+    pub height: Property<LogicalLength>,
+    pub title: Property<SharedString>,
+    pub enabled: Property<bool>,
+    pub checkable: Property<bool>,
+    pub checked: Property<bool>,
+    pub activated: Callback<VoidArg>,
+    /// FIXME: remove this
+    pub cached_rendering_data: CachedRenderingData,
+}
+
+impl Item for PlatformMenuItem {
+    fn init(self: Pin<&Self>, _window_adapter: &Rc<dyn WindowAdapter>) {}
+
+    fn geometry(self: Pin<&Self>) -> LogicalRect {
+        LogicalRect::new(
+            LogicalPoint::from_lengths(self.x(), self.y()),
+            LogicalSize::from_lengths(self.width(), self.height()),
+        )
+    }
+
+    fn layout_info(
+        self: Pin<&Self>,
+        _orientation: Orientation,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+    ) -> LayoutInfo {
+        LayoutInfo::default()
+    }
+
+    fn input_event_filter_before_children(
+        self: Pin<&Self>,
+        _: MouseEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> InputEventFilterResult {
+        InputEventFilterResult::ForwardAndIgnore
+    }
+
+    fn input_event(
+        self: Pin<&Self>,
+        _: MouseEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> InputEventResult {
+        InputEventResult::EventIgnored
+    }
+
+    fn key_event(
+        self: Pin<&Self>,
+        _: &KeyEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> KeyEventResult {
+        KeyEventResult::EventIgnored
+    }
+
+    fn focus_event(
+        self: Pin<&Self>,
+        _: &FocusEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> FocusEventResult {
+        FocusEventResult::FocusIgnored
+    }
+
+    fn render(
+        self: Pin<&Self>,
+        _backend: &mut ItemRendererRef,
+        _self_rc: &ItemRc,
+    ) -> RenderingResult {
+        // The menu items within are shown by the backend
+        RenderingResult::ContinueRenderingWithoutChildren
+    }
+}
+
+impl ItemConsts for PlatformMenuItem {
+    const cached_rendering_data_offset: const_field_offset::FieldOffset<
+        PlatformMenuItem,
+        CachedRenderingData,
+    > = PlatformMenuItem::FIELD_OFFSETS.cached_rendering_data.as_unpinned_projection();
+}
+
+/// The implementation of the `PlatformMenuBar` element
+#[repr(C)]
+#[derive(FieldOffsets, Default, SlintElement)]
+#[pin]
+pub struct PlatformMenuBar {
+    pub x: Property<LogicalLength>,
+    pub y: Property<LogicalLength>,
+    pub width: Property<LogicalLength>,
+    pub height: Property<LogicalLength>,
+    /// Set by the window when the backend shows the menu bar natively
+    pub native: Property<bool>,
+    /// FIXME: remove this
+    pub cached_rendering_data: CachedRenderingData,
+}
+
+impl Item for PlatformMenuBar {
+    fn init(self: Pin<&Self>, _window_adapter: &Rc<dyn WindowAdapter>) {}
+
+    fn geometry(self: Pin<&Self>) -> LogicalRect {
+        LogicalRect::new(
+            LogicalPoint::from_lengths(self.x(), self.y()),
+            LogicalSize::from_lengths(self.width(), self.height()),
+        )
+    }
+
+    fn layout_info(
+        self: Pin<&Self>,
+        _orientation: Orientation,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+    ) -> LayoutInfo {
+        LayoutInfo::default()
+    }
+
+    fn input_event_filter_before_children(
+        self: Pin<&Self>,
+        _: MouseEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> InputEventFilterResult {
+        InputEventFilterResult::ForwardAndIgnore
+    }
+
+    fn input_event(
+        self: Pin<&Self>,
+        _: MouseEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> InputEventResult {
+        InputEventResult::EventIgnored
+    }
+
+    fn key_event(
+        self: Pin<&Self>,
+        _: &KeyEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> KeyEventResult {
+        KeyEventResult::EventIgnored
+    }
+
+    fn focus_event(
+        self: Pin<&Self>,
+        _: &FocusEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> FocusEventResult {
+        FocusEventResult::FocusIgnored
+    }
+
+    fn render(
+        self: Pin<&Self>,
+        _backend: &mut ItemRendererRef,
+        _self_rc: &ItemRc,
+    ) -> RenderingResult {
+        // The menus within are shown by the backend
+        RenderingResult::ContinueRenderingWithoutChildren
+    }
+}
+
+impl ItemConsts for PlatformMenuBar {
+    const cached_rendering_data_offset: const_field_offset::FieldOffset<
+        PlatformMenuBar,
+        CachedRenderingData,
+    > = PlatformMenuBar::FIELD_OFFSETS.cached_rendering_data.as_unpinned_projection();
+}
+
+/// The state of a `SystemTrayIcon`, as shown by the backend
+#[derive(Clone)]
+pub struct SystemTrayIconState {
+    pub icon: Image,
+    pub tooltip: SharedString,
+    /// The entries of the context menu of the icon
+    pub menu: Vec<MenuEntry>,
+    item: ItemWeak,
+}
+
+impl SystemTrayIconState {
+    /// Call this when the user clicks on the icon
+    pub fn activate(&self) {
+        if let Some(tray_icon) = self.item.upgrade().and_then(|i| i.downcast::<SystemTrayIcon>()) {
+            SystemTrayIcon::FIELD_OFFSETS.activated.apply_pin(tray_icon.as_pin_ref()).call(&());
+        }
+    }
+}
+
+/// An entry in a menu, as shown by the backend. An entry with an empty title is a separator.
+#[derive(Clone)]
+pub struct MenuEntry {
+    pub title: SharedString,
+    pub enabled: bool,
+    pub checkable: bool,
+    pub checked: bool,
+    /// The entries of the sub-menu, if this entry has `PlatformMenuItem` children
+    pub children: Vec<MenuEntry>,
+    item: ItemWeak,
+}
+
+impl MenuEntry {
+    /// Call this when the user selects the entry. This toggles the `checked` property of checkable
+    /// entries, and invokes the `activated` callback.
+    pub fn activate(&self) {
+        if let Some(menu_item) = self.item.upgrade().and_then(|i| i.downcast::<PlatformMenuItem>())
+        {
+            let menu_item = menu_item.as_pin_ref();
+            if !menu_item.enabled() {
+                return;
+            }
+            if menu_item.checkable() {
+                PlatformMenuItem::FIELD_OFFSETS
+                    .checked
+                    .apply_pin(menu_item)
+                    .set(!menu_item.checked());
+            }
+            PlatformMenuItem::FIELD_OFFSETS.activated.apply_pin(menu_item).call(&());
+        }
+    }
+}
+
+/// Returns the entries for the `PlatformMenuItem` children of `parent`
+pub fn menu_entries(parent: &ItemRc) -> Vec<MenuEntry> {
+    let mut entries = Vec::new();
+    let mut child = parent.first_child();
+    while let Some(c) = child {
+        if let Some(menu_item) = c.downcast::<PlatformMenuItem>() {
+            let menu_item = menu_item.as_pin_ref();
+            entries.push(MenuEntry {
+                title: menu_item.title(),
+                enabled: menu_item.enabled(),
+                checkable: menu_item.checkable(),
+                checked: menu_item.checked(),
+                children: menu_entries(&c),
+                item: c.downgrade(),
+            });
+        }
+        child = c.next_sibling();
+    }
+    entries
+}
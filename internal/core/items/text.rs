@@ -9,9 +9,9 @@
 */
 
 use super::{
-    InputType, Item, ItemConsts, ItemRc, KeyEventResult, KeyEventType, PointArg,
-    PointerEventButton, RenderingResult, TextHorizontalAlignment, TextOverflow,
-    TextVerticalAlignment, TextWrap, VoidArg,
+    FocusPolicy, InputType, Item, ItemConsts, ItemRc, KeyEventResult, KeyEventType, MouseCursor,
+    PointArg, PointerEventButton, RenderingResult, StringArg, TextDirection, TextElidePosition,
+    TextHorizontalAlignment, TextOverflow, TextVerticalAlignment, TextWrap, VoidArg,
 };
 use crate::graphics::{Brush, Color, FontRequest};
 use crate::input::{
@@ -25,12 +25,15 @@
 use crate::rtti::*;
 use crate::window::{WindowAdapter, WindowInner};
 use crate::{Callback, Coord, Property, SharedString};
+use alloc::boxed::Box;
 use alloc::rc::Rc;
 use alloc::string::String;
+use alloc::vec::Vec;
 use const_field_offset::FieldOffsets;
+use core::cell::RefCell;
 use core::pin::Pin;
 #[allow(unused)]
-use euclid::num::Ceil;
+use euclid::num::{Ceil, Floor, Round};
 use i_slint_core_macros::*;
 use unicode_segmentation::UnicodeSegmentation;
 
@@ -48,12 +51,30 @@ pub struct Text {
     pub vertical_alignment: Property<TextVerticalAlignment>,
     pub wrap: Property<TextWrap>,
     pub overflow: Property<TextOverflow>,
+    pub elide_position: Property<TextElidePosition>,
+    pub max_lines: Property<i32>,
     pub letter_spacing: Property<LogicalLength>,
+    pub word_spacing: Property<LogicalLength>,
+    pub underline: Property<bool>,
+    pub strikethrough: Property<bool>,
+    pub stroke: Property<Brush>,
+    pub stroke_width: Property<LogicalLength>,
+    pub text_direction: Property<TextDirection>,
+    pub rich_text: Property<bool>,
+    pub truncated: Property<bool>,
+    pub content_width: Property<LogicalLength>,
+    pub content_height: Property<LogicalLength>,
     pub x: Property<LogicalLength>,
     pub y: Property<LogicalLength>,
     pub width: Property<LogicalLength>,
     pub height: Property<LogicalLength>,
+    pub link_activated: Callback<StringArg>,
     pub cached_rendering_data: CachedRenderingData,
+    /// Set when the left button was pressed over a link
+    link_pressed: core::cell::Cell<bool>,
+    /// The byte offset in the plain text where the link was pressed
+    link_pressed_offset: core::cell::Cell<usize>,
+    data: TextDataBox,
 }
 
 impl Item for Text {
@@ -72,10 +93,11 @@ fn layout_info(
         window_adapter: &Rc<dyn WindowAdapter>,
     ) -> LayoutInfo {
         let window_inner = WindowInner::from_pub(window_adapter.window());
+        let text = self.plain_text();
         let implicit_size = |max_width| {
             window_adapter.renderer().text_size(
                 self.font_request(window_inner),
-                self.text().as_str(),
+                text.as_str(),
                 max_width,
                 ScaleFactor::new(window_adapter.window().scale_factor()),
             )
@@ -111,11 +133,23 @@ fn layout_info(
                 }
             }
             Orientation::Vertical => {
-                let h = match self.wrap() {
+                let mut h = match self.wrap() {
                     TextWrap::NoWrap => implicit_size(None).height,
                     TextWrap::WordWrap => implicit_size(Some(self.width())).height,
+                };
+                if self.max_lines() > 0 {
+                    let line_height = window_adapter
+                        .renderer()
+                        .text_size(
+                            self.font_request(window_inner),
+                            " ",
+                            None,
+                            ScaleFactor::new(window_inner.scale_factor()),
+                        )
+                        .height;
+                    h = h.min(line_height * self.max_lines() as Coord);
                 }
-                .ceil();
+                let h = h.ceil();
                 LayoutInfo { min: h, preferred: h, ..LayoutInfo::default() }
             }
         }
@@ -127,15 +161,62 @@ fn input_event_filter_before_children(
         _window_adapter: &Rc<dyn WindowAdapter>,
         _self_rc: &ItemRc,
     ) -> InputEventFilterResult {
-        InputEventFilterResult::ForwardAndIgnore
+        if self.rich_text() {
+            InputEventFilterResult::ForwardEvent
+        } else {
+            InputEventFilterResult::ForwardAndIgnore
+        }
     }
 
     fn input_event(
         self: Pin<&Self>,
-        _: MouseEvent,
-        _window_adapter: &Rc<dyn WindowAdapter>,
+        event: MouseEvent,
+        window_adapter: &Rc<dyn WindowAdapter>,
         _self_rc: &ItemRc,
     ) -> InputEventResult {
+        if !self.rich_text() {
+            return InputEventResult::EventIgnored;
+        }
+        let rich_text = self.parsed_rich_text();
+        let offset_at =
+            |position| self.byte_offset_for_position(&rich_text.text, position, window_adapter);
+        let link_at = |offset: Option<usize>| offset.and_then(|o| rich_text.link_at(o));
+        match event {
+            MouseEvent::Pressed { position, button: PointerEventButton::Left } => {
+                let offset = offset_at(position);
+                if link_at(offset).is_some() {
+                    self.link_pressed.set(true);
+                    self.link_pressed_offset.set(offset.unwrap_or_default());
+                    return InputEventResult::GrabMouse;
+                }
+            }
+            MouseEvent::Released { position, button: PointerEventButton::Left } => {
+                if self.link_pressed.replace(false) {
+                    let pressed_link = link_at(Some(self.link_pressed_offset.get()));
+                    if let Some(link) =
+                        link_at(offset_at(position)).filter(|l| Some(*l) == pressed_link)
+                    {
+                        Self::FIELD_OFFSETS.link_activated.apply_pin(self).call(&(link.clone(),));
+                    }
+                    return InputEventResult::EventAccepted;
+                }
+            }
+            MouseEvent::Moved { position } => {
+                if link_at(offset_at(position)).is_some() {
+                    window_adapter.set_mouse_cursor(MouseCursor::Pointer);
+                    return InputEventResult::EventAccepted;
+                }
+                window_adapter.set_mouse_cursor(MouseCursor::Default);
+                if self.link_pressed.get() {
+                    return InputEventResult::GrabMouse;
+                }
+            }
+            MouseEvent::Exit => {
+                self.link_pressed.set(false);
+                window_adapter.set_mouse_cursor(MouseCursor::Default);
+            }
+            _ => {}
+        }
         InputEventResult::EventIgnored
     }
 
@@ -173,6 +254,146 @@ impl ItemConsts for Text {
 }
 
 impl Text {
+    /// Returns the text to display, that is the `text` property without the markup if
+    /// `rich-text` is set.
+    pub fn plain_text(self: Pin<&Self>) -> SharedString {
+        if self.rich_text() {
+            self.parsed_rich_text().text.as_str().into()
+        } else {
+            self.text()
+        }
+    }
+
+    /// Returns the parsed markup if `rich-text` is set.
+    pub fn rich_text_layout(self: Pin<&Self>) -> Option<crate::textlayout::RichText> {
+        self.rich_text().then(|| (*self.parsed_rich_text()).clone())
+    }
+
+    /// Returns the `text` property parsed as markup. The result is cached until the text changes.
+    fn parsed_rich_text(self: Pin<&Self>) -> Rc<crate::textlayout::RichText> {
+        let text = self.text();
+        let mut cache = self.data.get().rich_text.borrow_mut();
+        match &*cache {
+            Some((markup, rich_text)) if *markup == text => rich_text.clone(),
+            _ => {
+                let rich_text = Rc::new(crate::textlayout::parse_rich_text(text.as_str()));
+                *cache = Some((text, rich_text.clone()));
+                rich_text
+            }
+        }
+    }
+
+    /// Returns the size in the given orientation that the laid out text occupies within the item.
+    /// This is the value of the `content-width` and `content-height` properties.
+    pub fn content_size(
+        self: Pin<&Self>,
+        orientation: Orientation,
+        window_adapter: &Rc<dyn WindowAdapter>,
+    ) -> LogicalLength {
+        let (size, _) = self.text_metrics(window_adapter);
+        match orientation {
+            Orientation::Horizontal => size.width_length(),
+            Orientation::Vertical => size.height_length(),
+        }
+    }
+
+    /// Returns whether some of the text is not visible. This is the value of the `truncated` property.
+    pub fn is_truncated(self: Pin<&Self>, window_adapter: &Rc<dyn WindowAdapter>) -> bool {
+        self.text_metrics(window_adapter).1
+    }
+
+    /// Returns the size that the laid out text occupies within the item, and whether some of
+    /// the text is not visible because it was elided, clipped or exceeds `max-lines`.
+    fn text_metrics(
+        self: Pin<&Self>,
+        window_adapter: &Rc<dyn WindowAdapter>,
+    ) -> (LogicalSize, bool) {
+        let window_inner = WindowInner::from_pub(window_adapter.window());
+        let renderer = window_adapter.renderer();
+        let scale_factor = ScaleFactor::new(window_inner.scale_factor());
+        let text = self.plain_text();
+        let width = self.width();
+        let size = renderer.text_size(
+            self.font_request(window_inner),
+            text.as_str(),
+            (self.wrap() == TextWrap::WordWrap).then(|| width),
+            scale_factor,
+        );
+        let line_height =
+            renderer.text_size(self.font_request(window_inner), " ", None, scale_factor).height;
+        if line_height <= 0 as Coord {
+            return (size, false);
+        }
+        let line_count = (size.height / line_height).round() as i32;
+        let mut visible_lines = ((self.height().get() / line_height).floor() as i32).max(0);
+        if self.max_lines() > 0 {
+            visible_lines = visible_lines.min(self.max_lines());
+        }
+        let visible_lines = visible_lines.min(line_count);
+        let truncated = line_count > visible_lines || size.width > width.get();
+        (
+            LogicalSize::new(size.width.min(width.get()), line_height * visible_lines as Coord),
+            truncated,
+        )
+    }
+
+    /// Returns the byte offset in `plain_text` of the character at the given position,
+    /// or None if the position is not over any character.
+    ///
+    /// The lines are measured with the renderer's `text_size`, so this does not take into account
+    /// the breaks introduced by word wrapping.
+    fn byte_offset_for_position(
+        self: Pin<&Self>,
+        plain_text: &str,
+        pos: LogicalPoint,
+        window_adapter: &Rc<dyn WindowAdapter>,
+    ) -> Option<usize> {
+        let window_inner = WindowInner::from_pub(window_adapter.window());
+        let font_request = self.font_request(window_inner);
+        let scale_factor = window_inner.scale_factor();
+        let mut cache = self.data.get().glyph_positions.borrow_mut();
+        let positions = match &mut *cache {
+            Some(positions) if positions.matches(plain_text, &font_request, scale_factor) => {
+                positions
+            }
+            cache => cache.insert(GlyphPositions::measure(
+                plain_text,
+                font_request,
+                scale_factor,
+                window_adapter,
+            )),
+        };
+
+        if positions.line_height <= 0 as Coord {
+            return None;
+        }
+        let y = pos.y
+            - match self.vertical_alignment() {
+                TextVerticalAlignment::Top => 0 as Coord,
+                TextVerticalAlignment::Center => {
+                    (self.height().get() - positions.total_height) / 2 as Coord
+                }
+                TextVerticalAlignment::Bottom => self.height().get() - positions.total_height,
+            };
+        if y < 0 as Coord {
+            return None;
+        }
+        let line = positions.lines.get((y / positions.line_height) as usize)?;
+        let x = pos.x
+            - match self.horizontal_alignment() {
+                TextHorizontalAlignment::Left => 0 as Coord,
+                TextHorizontalAlignment::Center => (self.width().get() - line.width) / 2 as Coord,
+                TextHorizontalAlignment::Right => self.width().get() - line.width,
+            };
+        if x < 0 as Coord || x >= line.width {
+            return None;
+        }
+        line.char_ends
+            .iter()
+            .position(|(_, width)| *width > x)
+            .map(|i| line.start + if i == 0 { 0 } else { line.char_ends[i - 1].0 })
+    }
+
     pub fn font_request(self: Pin<&Self>, window: &WindowInner) -> FontRequest {
         let window_item = window.window_item();
 
@@ -202,10 +423,155 @@ pub fn font_request(self: Pin<&Self>, window: &WindowInner) -> FontRequest {
                 }
             },
             letter_spacing: Some(self.letter_spacing()),
+            word_spacing: Some(self.word_spacing()),
+        }
+    }
+}
+
+/// The position of the characters of the plain text of a `Text`, as measured by the renderer
+struct GlyphPositions {
+    plain_text: String,
+    font_request: FontRequest,
+    scale_factor: f32,
+    total_height: Coord,
+    line_height: Coord,
+    lines: Vec<LinePositions>,
+}
+
+struct LinePositions {
+    /// The byte offset of the line in the plain text
+    start: usize,
+    width: Coord,
+    /// The byte offset of the end of each grapheme in the line, with the width of the line up to it
+    char_ends: Vec<(usize, Coord)>,
+}
+
+impl GlyphPositions {
+    fn measure(
+        plain_text: &str,
+        font_request: FontRequest,
+        scale_factor: f32,
+        window_adapter: &Rc<dyn WindowAdapter>,
+    ) -> Self {
+        let renderer = window_adapter.renderer();
+        let measure = |s: &str| {
+            renderer.text_size(font_request.clone(), s, None, ScaleFactor::new(scale_factor))
+        };
+        let total_height = measure(plain_text).height;
+        let line_count = plain_text.split('\n').count().max(1);
+        let mut start = 0;
+        let lines = plain_text
+            .split('\n')
+            .map(|line| {
+                // Measuring each grapheme on its own, rather than each prefix of the line, ignores
+                // the kerning between them, but keeps the cost linear in the length of the text
+                let mut width = 0 as Coord;
+                let char_ends = line
+                    .grapheme_indices(true)
+                    .map(|(offset, grapheme)| {
+                        width += measure(grapheme).width;
+                        (offset + grapheme.len(), width)
+                    })
+                    .collect::<Vec<_>>();
+                let positions = LinePositions {
+                    start,
+                    width: char_ends.last().map_or(0 as Coord, |(_, width)| *width),
+                    char_ends,
+                };
+                start += line.len() + 1;
+                positions
+            })
+            .collect();
+        Self {
+            plain_text: plain_text.into(),
+            font_request,
+            scale_factor,
+            total_height,
+            line_height: total_height / line_count as Coord,
+            lines,
+        }
+    }
+
+    fn matches(&self, plain_text: &str, font_request: &FontRequest, scale_factor: f32) -> bool {
+        self.plain_text == plain_text
+            && self.font_request == *font_request
+            && self.scale_factor == scale_factor
+    }
+}
+
+#[derive(Default)]
+/// The parsed markup and the glyph positions of a `Text`, cached for the handling of the links
+pub struct TextData {
+    rich_text: RefCell<Option<(SharedString, Rc<crate::textlayout::RichText>)>>,
+    glyph_positions: RefCell<Option<GlyphPositions>>,
+}
+
+#[repr(C)]
+/// Wraps the internal data structure for the Text. It is only allocated when the text has
+/// markup, the first time the data is accessed.
+pub struct TextDataBox(core::cell::Cell<*mut TextData>);
+
+impl Default for TextDataBox {
+    fn default() -> Self {
+        TextDataBox(core::cell::Cell::new(core::ptr::null_mut()))
+    }
+}
+impl Drop for TextDataBox {
+    fn drop(&mut self) {
+        let data = self.0.get();
+        if !data.is_null() {
+            // Safety: the data was constructed from a Box::leak in TextDataBox::get
+            drop(unsafe { Box::from_raw(data) });
         }
     }
 }
 
+impl TextDataBox {
+    fn get(&self) -> &TextData {
+        if self.0.get().is_null() {
+            self.0.set(Box::leak(Box::new(TextData::default())));
+        }
+        // Safety: allocated above, and only freed in Drop
+        unsafe { &*self.0.get() }
+    }
+}
+
+/// # Safety
+/// This must be called using a non-null pointer pointing to a chunk of memory big enough to
+/// hold a TextDataBox
+#[cfg(feature = "ffi")]
+#[no_mangle]
+pub unsafe extern "C" fn slint_text_data_init(data: *mut TextDataBox) {
+    core::ptr::write(data, TextDataBox::default());
+}
+
+/// # Safety
+/// This must be called using a non-null pointer pointing to an initialized TextDataBox
+#[cfg(feature = "ffi")]
+#[no_mangle]
+pub unsafe extern "C" fn slint_text_data_free(data: *mut TextDataBox) {
+    core::ptr::drop_in_place(data);
+}
+
+#[cfg(feature = "ffi")]
+#[no_mangle]
+pub extern "C" fn slint_text_content_size(
+    text: Pin<&Text>,
+    orientation: Orientation,
+    window_adapter: &crate::window::WindowAdapterRc,
+) -> Coord {
+    text.content_size(orientation, window_adapter).get()
+}
+
+#[cfg(feature = "ffi")]
+#[no_mangle]
+pub extern "C" fn slint_text_truncated(
+    text: Pin<&Text>,
+    window_adapter: &crate::window::WindowAdapterRc,
+) -> bool {
+    text.is_truncated(window_adapter)
+}
+
 /// The implementation of the `TextInput` element
 #[repr(C)]
 #[derive(FieldOffsets, Default, SlintElement)]
@@ -223,6 +589,11 @@ pub struct TextInput {
     pub wrap: Property<TextWrap>,
     pub input_type: Property<InputType>,
     pub letter_spacing: Property<LogicalLength>,
+    pub word_spacing: Property<LogicalLength>,
+    pub underline: Property<bool>,
+    pub strikethrough: Property<bool>,
+    pub stroke: Property<Brush>,
+    pub stroke_width: Property<LogicalLength>,
     pub x: Property<LogicalLength>,
     pub y: Property<LogicalLength>,
     pub width: Property<LogicalLength>,
@@ -232,6 +603,8 @@ pub struct TextInput {
     pub text_cursor_width: Property<LogicalLength>,
     pub cursor_visible: Property<bool>,
     pub has_focus: Property<bool>,
+    pub focus_policy: Property<FocusPolicy>,
+    pub tab_index: Property<i32>,
     pub enabled: Property<bool>,
     pub accepted: Callback<VoidArg>,
     pub cursor_position_changed: Callback<PointArg>,
@@ -333,7 +706,7 @@ fn input_event(
                 self.as_ref().pressed.set(true);
                 self.as_ref().anchor_position.set(clicked_offset);
                 self.set_cursor_position(clicked_offset, true, window_adapter, self_rc);
-                if !self.has_focus() {
+                if !self.has_focus() && self.focus_policy().accepts_click_focus() {
                     WindowInner::from_pub(window_adapter.window()).set_focus_item(self_rc);
                 }
             }
@@ -519,13 +892,17 @@ fn focus_event(
         self: Pin<&Self>,
         event: &FocusEvent,
         window_adapter: &Rc<dyn WindowAdapter>,
-        _self_rc: &ItemRc,
+        self_rc: &ItemRc,
     ) -> FocusEventResult {
+        if *event == FocusEvent::FocusIn && self.focus_policy() == FocusPolicy::NoFocus {
+            return FocusEventResult::FocusIgnored;
+        }
         match event {
             FocusEvent::FocusIn | FocusEvent::WindowReceivedFocus => {
                 self.has_focus.set(true);
                 self.show_cursor(window_adapter);
                 window_adapter.enable_input_method(self.input_type());
+                self.update_ime_position(window_adapter, self_rc);
             }
             FocusEvent::FocusOut | FocusEvent::WindowLostFocus => {
                 self.has_focus.set(false);
@@ -802,13 +1179,12 @@ fn update_ime_position(
         self_rc: &ItemRc,
     ) {
         let cursor_position = self.cursor_position();
-        let cursor_point_relative = window_adapter
+        let cursor_rect = window_adapter
             .renderer()
-            .text_input_cursor_rect_for_byte_offset(self, cursor_position as usize)
-            .to_box2d()
-            .max;
-        let cursor_point_absolute = self_rc.map_to_window(cursor_point_relative);
-        window_adapter.set_ime_position(cursor_point_absolute);
+            .text_input_cursor_rect_for_byte_offset(self, cursor_position as usize);
+        let cursor_rect_absolute =
+            LogicalRect::new(self_rc.map_to_window(cursor_rect.origin), cursor_rect.size);
+        window_adapter.set_ime_cursor_rect(cursor_rect_absolute);
     }
 
     fn select_and_delete(
@@ -950,6 +1326,7 @@ pub fn font_request(self: Pin<&Self>, window_adapter: &Rc<dyn WindowAdapter>) ->
                 }
             },
             letter_spacing: Some(self.letter_spacing()),
+            word_spacing: Some(self.word_spacing()),
         }
     }
 
@@ -0,0 +1,247 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+//! The `ToastQueue` item, used by the `Toast` widget to show its messages one after the other
+
+use super::{Item, ItemConsts, ItemRc, ItemRendererRef, KeyEventResult, RenderingResult};
+use crate::input::{
+    FocusEvent, FocusEventResult, InputEventFilterResult, InputEventResult, KeyEvent, MouseEvent,
+};
+use crate::item_rendering::CachedRenderingData;
+use crate::layout::{LayoutInfo, Orientation};
+use crate::lengths::{LogicalLength, LogicalPoint, LogicalRect, LogicalSize};
+#[cfg(feature = "rtti")]
+use crate::rtti::*;
+use crate::timers::{Timer, TimerMode};
+use crate::window::WindowAdapter;
+use crate::{Property, SharedString};
+use alloc::boxed::Box;
+use alloc::collections::VecDeque;
+use alloc::rc::Rc;
+use const_field_offset::FieldOffsets;
+use core::cell::RefCell;
+use core::pin::Pin;
+use i_slint_core_macros::*;
+
+/// The implementation of the `ToastQueue` element.
+///
+/// The messages pushed with [`ToastQueue::push`] are shown one at a time: the current one is
+/// exposed in the `text` and `action-text` properties until its duration elapsed, or until it is
+/// dismissed, then the next one in the queue is shown.
+#[repr(C)]
+#[derive(FieldOffsets, Default, SlintElement)]
+#[pin]
+pub struct ToastQueue {
+    pub x: Property<LogicalLength>,
+    pub y: Property<LogicalLength>,
+    pub width: Property<LogicalLength>,
+    pub height: Property<LogicalLength>,
+    pub text: Property<SharedString>,
+    pub action_text: Property<SharedString>,
+    /// True while a message is shown
+    pub active: Property<bool>,
+    /// The number of messages waiting after the current one
+    pub pending_count: Property<i32>,
+    data: ToastQueueDataBox,
+    /// FIXME: remove this
+    pub cached_rendering_data: CachedRenderingData,
+}
+
+impl Item for ToastQueue {
+    fn init(self: Pin<&Self>, _window_adapter: &Rc<dyn WindowAdapter>) {}
+
+    fn geometry(self: Pin<&Self>) -> LogicalRect {
+        LogicalRect::new(
+            LogicalPoint::from_lengths(self.x(), self.y()),
+            LogicalSize::from_lengths(self.width(), self.height()),
+        )
+    }
+
+    fn layout_info(
+        self: Pin<&Self>,
+        _orientation: Orientation,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+    ) -> LayoutInfo {
+        LayoutInfo::default()
+    }
+
+    fn input_event_filter_before_children(
+        self: Pin<&Self>,
+        _: MouseEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> InputEventFilterResult {
+        InputEventFilterResult::ForwardAndIgnore
+    }
+
+    fn input_event(
+        self: Pin<&Self>,
+        _: MouseEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> InputEventResult {
+        InputEventResult::EventIgnored
+    }
+
+    fn key_event(
+        self: Pin<&Self>,
+        _: &KeyEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> KeyEventResult {
+        KeyEventResult::EventIgnored
+    }
+
+    fn focus_event(
+        self: Pin<&Self>,
+        _: &FocusEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> FocusEventResult {
+        FocusEventResult::FocusIgnored
+    }
+
+    fn render(
+        self: Pin<&Self>,
+        _backend: &mut ItemRendererRef,
+        _self_rc: &ItemRc,
+    ) -> RenderingResult {
+        RenderingResult::ContinueRendering
+    }
+}
+
+impl ItemConsts for ToastQueue {
+    const cached_rendering_data_offset: const_field_offset::FieldOffset<
+        ToastQueue,
+        CachedRenderingData,
+    > = ToastQueue::FIELD_OFFSETS.cached_rendering_data.as_unpinned_projection();
+}
+
+impl ToastQueue {
+    /// Adds a message to the queue, and shows it right away if no other message is shown.
+    ///
+    /// The message is hidden after `duration` milliseconds, or stays until [`Self::dismiss`] is
+    /// called if the duration is not positive.
+    pub fn push(self: Pin<&Self>, text: SharedString, action_text: SharedString, duration: i64) {
+        self.data.pending.borrow_mut().push_back(PendingToast { text, action_text, duration });
+        if self.active() {
+            Self::FIELD_OFFSETS
+                .pending_count
+                .apply_pin(self)
+                .set(self.data.pending.borrow().len() as i32);
+        } else {
+            self.show_next();
+        }
+    }
+
+    /// Hides the current message and shows the next one in the queue, if any.
+    pub fn dismiss(self: Pin<&Self>) {
+        self.data.timer.stop();
+        self.show_next();
+    }
+
+    fn show_next(self: Pin<&Self>) {
+        let next = self.data.pending.borrow_mut().pop_front();
+        Self::FIELD_OFFSETS
+            .pending_count
+            .apply_pin(self)
+            .set(self.data.pending.borrow().len() as i32);
+        let next = if let Some(next) = next {
+            next
+        } else {
+            Self::FIELD_OFFSETS.active.apply_pin(self).set(false);
+            Self::FIELD_OFFSETS.text.apply_pin(self).set(Default::default());
+            Self::FIELD_OFFSETS.action_text.apply_pin(self).set(Default::default());
+            return;
+        };
+        Self::FIELD_OFFSETS.text.apply_pin(self).set(next.text);
+        Self::FIELD_OFFSETS.action_text.apply_pin(self).set(next.action_text);
+        Self::FIELD_OFFSETS.active.apply_pin(self).set(true);
+        if next.duration > 0 {
+            let self_ptr = self.get_ref() as *const Self;
+            self.data.timer.start(
+                TimerMode::SingleShot,
+                core::time::Duration::from_millis(next.duration as u64),
+                move || {
+                    // Safety: the timer is owned by the item, and it is stopped when the item is
+                    // dropped, so the item is still alive when the timer fires. The item is pinned.
+                    let this = unsafe { Pin::new_unchecked(&*self_ptr) };
+                    this.show_next();
+                },
+            );
+        } else {
+            self.data.timer.stop();
+        }
+    }
+}
+
+struct PendingToast {
+    text: SharedString,
+    action_text: SharedString,
+    duration: i64,
+}
+
+#[derive(Default)]
+/// The messages waiting to be shown, and the timer hiding the current one
+pub struct ToastQueueData {
+    pending: RefCell<VecDeque<PendingToast>>,
+    timer: Timer,
+}
+
+#[repr(C)]
+/// Wraps the internal data structure for the ToastQueue
+pub struct ToastQueueDataBox(core::ptr::NonNull<ToastQueueData>);
+
+impl Default for ToastQueueDataBox {
+    fn default() -> Self {
+        ToastQueueDataBox(Box::leak(Box::new(ToastQueueData::default())).into())
+    }
+}
+impl Drop for ToastQueueDataBox {
+    fn drop(&mut self) {
+        // Safety: the self.0 was constructed from a Box::leak in ToastQueueDataBox::default
+        drop(unsafe { Box::from_raw(self.0.as_ptr()) });
+    }
+}
+
+impl core::ops::Deref for ToastQueueDataBox {
+    type Target = ToastQueueData;
+    fn deref(&self) -> &Self::Target {
+        // Safety: initialized in ToastQueueDataBox::default
+        unsafe { self.0.as_ref() }
+    }
+}
+
+/// # Safety
+/// This must be called using a non-null pointer pointing to a chunk of memory big enough to
+/// hold a ToastQueueDataBox
+#[cfg(feature = "ffi")]
+#[no_mangle]
+pub unsafe extern "C" fn slint_toast_queue_data_init(data: *mut ToastQueueDataBox) {
+    core::ptr::write(data, ToastQueueDataBox::default());
+}
+
+/// # Safety
+/// This must be called using a non-null pointer pointing to an initialized ToastQueueDataBox
+#[cfg(feature = "ffi")]
+#[no_mangle]
+pub unsafe extern "C" fn slint_toast_queue_data_free(data: *mut ToastQueueDataBox) {
+    core::ptr::drop_in_place(data);
+}
+
+#[cfg(feature = "ffi")]
+#[no_mangle]
+pub extern "C" fn slint_toast_queue_push(
+    queue: Pin<&ToastQueue>,
+    text: &SharedString,
+    action_text: &SharedString,
+    duration: i64,
+) {
+    queue.push(text.clone(), action_text.clone(), duration)
+}
+
+#[cfg(feature = "ffi")]
+#[no_mangle]
+pub extern "C" fn slint_toast_queue_dismiss(queue: Pin<&ToastQueue>) {
+    queue.dismiss()
+}
@@ -391,6 +391,12 @@ pub struct GridLayoutCellData {
 
 /// return, an array which is of size `data.cells.len() * 2` which for each cell we give the pos, size
 pub fn solve_grid_layout(data: &GridLayoutData) -> SharedVector<Coord> {
+    crate::profiling::measure(crate::profiling::Measurement::Layout, || {
+        solve_grid_layout_impl(data)
+    })
+}
+
+fn solve_grid_layout_impl(data: &GridLayoutData) -> SharedVector<Coord> {
     let mut layout_data =
         grid_internal::to_layout_data(data.cells.as_slice(), data.spacing, Some(data.size));
 
@@ -456,6 +462,15 @@ pub struct BoxLayoutCellData {
 
 /// Solve a BoxLayout
 pub fn solve_box_layout(data: &BoxLayoutData, repeater_indexes: Slice<u32>) -> SharedVector<Coord> {
+    crate::profiling::measure(crate::profiling::Measurement::Layout, || {
+        solve_box_layout_impl(data, repeater_indexes)
+    })
+}
+
+fn solve_box_layout_impl(
+    data: &BoxLayoutData,
+    repeater_indexes: Slice<u32>,
+) -> SharedVector<Coord> {
     let mut result = SharedVector::<Coord>::default();
     result.resize(data.cells.len() * 2 + repeater_indexes.len(), 0 as _);
 
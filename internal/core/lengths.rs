@@ -17,6 +17,52 @@
 
 pub type ScaleFactor = euclid::Scale<f32, LogicalPx, PhysicalPx>;
 
+/// A 2D transformation in logical coordinates, such as the one applied by the `Transform` element
+pub type LogicalTransform = euclid::Transform2D<f32, LogicalPx, LogicalPx>;
+
+/// The radii of the four corners of a rectangle.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct BorderRadius<Length> {
+    pub top_left: Length,
+    pub top_right: Length,
+    pub bottom_right: Length,
+    pub bottom_left: Length,
+}
+
+impl<Length: Copy + PartialEq> BorderRadius<Length> {
+    /// Creates a border radius with the same radius for all the corners.
+    pub fn new_uniform(radius: Length) -> Self {
+        Self { top_left: radius, top_right: radius, bottom_right: radius, bottom_left: radius }
+    }
+
+    /// Returns the radius of the corners if they all have the same radius.
+    pub fn as_uniform(&self) -> Option<Length> {
+        (self.top_left == self.top_right
+            && self.top_left == self.bottom_right
+            && self.top_left == self.bottom_left)
+            .then(|| self.top_left)
+    }
+
+    /// Applies `f` to the radius of each corner.
+    pub fn map<Output>(self, f: impl Fn(Length) -> Output) -> BorderRadius<Output> {
+        BorderRadius {
+            top_left: f(self.top_left),
+            top_right: f(self.top_right),
+            bottom_right: f(self.bottom_right),
+            bottom_left: f(self.bottom_left),
+        }
+    }
+}
+
+impl<Length: Copy + PartialEq + Default> BorderRadius<Length> {
+    /// Returns true if none of the corners is rounded.
+    pub fn is_zero(&self) -> bool {
+        self.as_uniform() == Some(Length::default())
+    }
+}
+
+pub type LogicalBorderRadius = BorderRadius<LogicalLength>;
+
 pub trait SizeLengths {
     type LengthType;
     fn width_length(&self) -> Self::LengthType;
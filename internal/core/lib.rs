@@ -21,6 +21,7 @@
 pub mod api;
 pub mod callbacks;
 pub mod component;
+pub mod future;
 pub mod graphics;
 pub mod input;
 pub mod item_focus;
@@ -31,6 +32,7 @@
 pub mod lengths;
 pub mod model;
 pub mod platform;
+pub mod profiling;
 pub mod properties;
 pub mod renderer;
 pub mod sharedvector;
@@ -40,6 +42,7 @@
 pub mod tests;
 pub mod textlayout;
 pub mod timers;
+mod tooltip;
 pub mod window;
 
 #[cfg(feature = "rtti")]
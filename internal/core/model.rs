@@ -23,9 +23,11 @@
 use once_cell::unsync::OnceCell;
 use pin_project::pin_project;
 use pin_weak::rc::{PinWeak, Rc};
+pub use vec_model_handle::VecModelHandle;
 
 mod adapters;
 mod model_peer;
+mod vec_model_handle;
 
 type ComponentRc<C> = vtable::VRc<crate::component::ComponentVTable, C>;
 
@@ -560,6 +562,13 @@ fn from(model: Rc<dyn Model<Data = T> + 'static>) -> Self {
     }
 }
 
+/// Collects the items into a [`VecModel`].
+impl<T: Clone + 'static> core::iter::FromIterator<T> for ModelRc<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::new(VecModel::from(iter.into_iter().collect::<Vec<T>>()))
+    }
+}
+
 impl<T> TryInto<Rc<dyn Model<Data = T>>> for ModelRc<T> {
     type Error = ();
 
@@ -1113,6 +1122,36 @@ fn from(other: SharedString) -> Self {
     }
 }
 
+/// Represent an item in a TreeView
+///
+/// The TreeView shows a flat model in which the children of an item are the rows that follow it
+/// with a higher `level`. The children of an item can be populated lazily: insert them in the
+/// model when the item is expanded, and remove them when it is collapsed.
+#[repr(C)]
+#[derive(Clone, Default, Debug, PartialEq)]
+pub struct StandardTreeViewItem {
+    /// The text content of the item
+    pub text: crate::SharedString,
+    /// The depth of the item in the tree, 0 for the top level items
+    pub level: i32,
+    /// Whether the item can be expanded, even if its children are not in the model yet
+    pub has_children: bool,
+    /// Whether the item is expanded and its children follow it in the model
+    pub expanded: bool,
+}
+
+impl From<&str> for StandardTreeViewItem {
+    fn from(other: &str) -> Self {
+        return Self { text: other.into(), ..Default::default() };
+    }
+}
+
+impl From<SharedString> for StandardTreeViewItem {
+    fn from(other: SharedString) -> Self {
+        return Self { text: other, ..Default::default() };
+    }
+}
+
 #[test]
 fn test_tracking_model_handle() {
     let model: Rc<VecModel<u8>> = Rc::new(Default::default());
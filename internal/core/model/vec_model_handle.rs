@@ -0,0 +1,149 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+//! This module contains the [`VecModelHandle`], to modify a [`VecModel`] from other threads.
+
+use super::*;
+use crate::api::{invoke_from_event_loop, EventLoopError};
+use alloc::collections::BTreeMap;
+use alloc::rc::Weak;
+use core::marker::PhantomData;
+
+#[cfg(all(not(feature = "std"), feature = "unsafe-single-threaded"))]
+use crate::unsafe_single_threaded::thread_local;
+
+thread_local! {
+    /// The models for which a handle was created, indexed by the id of the handle
+    static MODELS: RefCell<BTreeMap<u64, Weak<dyn core::any::Any>>> = Default::default();
+}
+// A u64 doesn't wrap around, so the id of a dropped model is never reused
+thread_local!(static NEXT_MODEL_ID: Cell<u64> = Cell::new(0));
+
+/// A handle to a [`VecModel`] that can be sent to other threads, to modify the model from there.
+///
+/// The modifications are queued with [`invoke_from_event_loop()`] and applied in the thread of the
+/// event loop, where the model was created. They are ignored if the model was dropped in the meantime,
+/// and so are the modifications of rows that are out of range when they are applied, since the model
+/// may have changed after the index was computed in the other thread.
+/// Create the handle with [`VecModel::handle()`].
+///
+/// ## Example
+///
+/// ```rust
+/// # i_slint_backend_testing::init();
+/// # use std::rc::Rc;
+/// use slint::VecModel;
+/// let model = Rc::new(VecModel::<i32>::default());
+/// // ... set `slint::ModelRc::from(model.clone())` on a property of a component
+/// let handle = model.handle();
+/// let thread = std::thread::spawn(move || {
+///     # return; // don't modify the model in the event loop in our examples
+///     for value in 0..10 {
+///         // ... compute the value in the thread
+///         handle.push(value).unwrap();
+///     }
+/// });
+/// # thread.join().unwrap(); return; // don't run the event loop in examples
+/// slint::run_event_loop();
+/// ```
+pub struct VecModelHandle<T> {
+    id: u64,
+    _phantom: PhantomData<fn(T)>,
+}
+
+impl<T> Clone for VecModelHandle<T> {
+    fn clone(&self) -> Self {
+        Self { id: self.id, _phantom: PhantomData }
+    }
+}
+
+impl<T: Clone + Send + 'static> VecModelHandle<T> {
+    pub(super) fn new(model: &Rc<VecModel<T>>) -> Self {
+        let id = NEXT_MODEL_ID.with(|next_id| {
+            let id = next_id.get();
+            next_id.set(id + 1);
+            id
+        });
+        let model = model.clone() as Rc<dyn core::any::Any>;
+        MODELS.with(|models| {
+            let mut models = models.borrow_mut();
+            models.retain(|_, model| model.strong_count() > 0);
+            models.insert(id, Rc::downgrade(&model));
+        });
+        Self { id, _phantom: PhantomData }
+    }
+
+    /// Calls `func` with the model in the thread of the event loop, if the model still exists.
+    pub fn update(
+        &self,
+        func: impl FnOnce(&VecModel<T>) + Send + 'static,
+    ) -> Result<(), EventLoopError> {
+        let id = self.id;
+        invoke_from_event_loop(move || {
+            let model = MODELS.with(|models| models.borrow().get(&id).and_then(|m| m.upgrade()));
+            if let Some(model) = model {
+                if let Some(model) = model.downcast_ref::<VecModel<T>>() {
+                    func(model);
+                }
+            }
+        })
+    }
+
+    /// Adds a row at the end of the model. See [`VecModel::push()`].
+    pub fn push(&self, value: T) -> Result<(), EventLoopError> {
+        self.update(move |model| model.push(value))
+    }
+
+    /// Inserts a row at position index. See [`VecModel::insert()`].
+    ///
+    /// The row isn't inserted if the index is greater than the number of rows of the model.
+    pub fn insert(&self, index: usize, value: T) -> Result<(), EventLoopError> {
+        self.update(move |model| {
+            if index <= model.row_count() {
+                model.insert(index, value)
+            } else {
+                crate::debug_log!("VecModelHandle::insert: index {} is out of range", index);
+            }
+        })
+    }
+
+    /// Removes the row at the given index. See [`VecModel::remove()`].
+    ///
+    /// Nothing is removed if the index is out of range.
+    pub fn remove(&self, index: usize) -> Result<(), EventLoopError> {
+        self.update(move |model| {
+            if index < model.row_count() {
+                model.remove(index);
+            } else {
+                crate::debug_log!("VecModelHandle::remove: index {} is out of range", index);
+            }
+        })
+    }
+
+    /// Replaces the data of the row at the given index. See [`Model::set_row_data()`].
+    ///
+    /// Nothing is changed if the index is out of range.
+    pub fn set_row_data(&self, row: usize, data: T) -> Result<(), EventLoopError> {
+        self.update(move |model| {
+            if row < model.row_count() {
+                model.set_row_data(row, data)
+            } else {
+                crate::debug_log!("VecModelHandle::set_row_data: row {} is out of range", row);
+            }
+        })
+    }
+
+    /// Replaces all the rows of the model. See [`VecModel::set_vec()`].
+    pub fn set_vec(&self, new: Vec<T>) -> Result<(), EventLoopError> {
+        self.update(move |model| model.set_vec(new))
+    }
+}
+
+impl<T: Clone + Send + 'static> VecModel<T> {
+    /// Returns a handle that can be sent to other threads to modify this model from the event loop.
+    ///
+    /// This must be called from the thread of the event loop. See [`VecModelHandle`].
+    pub fn handle(self: &Rc<Self>) -> VecModelHandle<T> {
+        VecModelHandle::new(self)
+    }
+}
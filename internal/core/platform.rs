@@ -2,11 +2,24 @@
 // SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
 
 /*!
-The backend is the abstraction for crates that need to do the actual drawing and event loop
+The backend is the abstraction for crates that need to do the actual drawing and event loop.
+
+To run Slint on a platform that none of the provided backends support, such as a micro-controller
+without an operating system:
+ 1. Implement the [`Platform`] trait, and pass it to [`set_platform()`] before creating any component.
+    [`Platform::create_window_adapter()`] typically returns a
+    [`MinimalSoftwareWindow`](software_renderer::MinimalSoftwareWindow).
+ 2. In the event loop, call [`update_timers_and_animations()`], forward the input of the touch screen
+    or of the keyboard to the window with [`Window::dispatch_event()`](crate::api::Window::dispatch_event),
+    and render the window with [`MinimalSoftwareWindow::draw_if_needed()`](software_renderer::MinimalSoftwareWindow::draw_if_needed).
+ 3. Sleep until the next input, at most for [`duration_until_next_timer_update()`], unless
+    [`Window::has_active_animations()`](crate::api::Window::has_active_animations) returns true.
 */
 
 #![warn(missing_docs)]
 
+pub use crate::api::{PointerEventButton, WindowEvent};
+pub use crate::profiling::FrameStatistics;
 pub use crate::software_renderer;
 #[cfg(all(not(feature = "std"), feature = "unsafe-single-threaded"))]
 use crate::unsafe_single_threaded::{thread_local, OnceCell};
@@ -147,13 +160,58 @@ pub fn set_platform(platform: Box<dyn Platform + 'static>) -> Result<(), SetPlat
 }
 
 /// Call this function to update and potentially activate any pending timers, as well
-/// as advance the state of any active animtaions.
+/// as advance the state of any active animtaions and notify the property change observers.
 ///
 /// This function should be called before rendering or processing input event, at the
 /// beginning of each event loop iteration.
 pub fn update_timers_and_animations() {
-    crate::timers::TimerList::maybe_activate_timers(crate::animations::Instant::now());
-    crate::animations::update_animations();
+    crate::profiling::measure_timers_and_animations(|| {
+        crate::timers::TimerList::maybe_activate_timers(crate::animations::Instant::now());
+        crate::animations::update_animations();
+        crate::properties::ChangeTracker::run_change_handlers();
+    })
+}
+
+/// Call this function before rendering a frame, to advance the state of the active animations
+/// to the time at which the frame is going to be shown, as a duration since [`Platform::duration_since_start()`].
+///
+/// Platforms that get notified of the display refresh, for example through a vsync or frame callback, should use
+/// this instead of relying on [`update_timers_and_animations()`] alone, so that animations are smooth.
+pub fn update_animations_for_frame(frame_time: core::time::Duration) {
+    crate::animations::update_animations_for_frame(frame_time);
+}
+
+/// Tells Slint whether the user asked to reduce motion in the user interface, for example in the
+/// accessibility settings of the platform. When enabled, the property animations jump directly to their
+/// final value and the `reduced-motion()` function returns true in the `.slint` code.
+///
+/// Applications can also call this function to offer their own setting.
+pub fn set_reduced_motion(reduced: bool) {
+    crate::animations::CURRENT_ANIMATION_DRIVER.with(|driver| driver.set_reduced_motion(reduced));
+}
+
+/// Returns true if animations are reduced, as set by [`set_reduced_motion()`].
+pub fn reduced_motion() -> bool {
+    crate::animations::CURRENT_ANIMATION_DRIVER.with(|driver| driver.reduced_motion_untracked())
+}
+
+/// Enables or disables the collection of [`FrameStatistics`] for the frames rendered
+/// in the current thread.
+///
+/// When enabled, the time spent evaluating the bindings, computing the layouts and rendering is
+/// measured for each frame, and can be queried with [`last_frame_statistics()`]. This adds a small
+/// overhead to each binding evaluation, so it is disabled by default.
+///
+/// It is also enabled by the `SLINT_DEBUG_PERFORMANCE` environment variable, with the backends
+/// that support it.
+pub fn set_frame_profiling_enabled(enabled: bool) {
+    crate::profiling::set_enabled(enabled)
+}
+
+/// Returns the statistics of the last frame rendered since the frame profiling was enabled
+/// with [`set_frame_profiling_enabled()`], or None if it is not enabled.
+pub fn last_frame_statistics() -> Option<FrameStatistics> {
+    crate::profiling::last_frame_statistics()
 }
 
 /// Returns the duration before the next timer is expected to be activated. This is the
@@ -164,6 +222,9 @@ pub fn update_timers_and_animations() {
 /// Only go to sleep if [`Window::has_active_animations()`](crate::api::Window::has_active_animations())
 /// returns false.
 pub fn duration_until_next_timer_update() -> Option<core::time::Duration> {
+    if crate::properties::ChangeTracker::has_pending_change_handlers() {
+        return Some(core::time::Duration::ZERO);
+    }
     crate::timers::TimerList::next_timeout().map(|timeout| {
         let duration_since_start = crate::platform::PLATFORM_INSTANCE
             .with(|p| p.get().map(|p| p.duration_since_start()))
@@ -173,3 +234,35 @@ pub fn duration_until_next_timer_update() -> Option<core::time::Duration> {
         )
     })
 }
+
+macro_rules! declare_key_enum {
+    ($($char:literal # $name:ident # $($_qt:ident)|* # $($_winit:ident)|* ;)*) => {
+        /// The keys that don't produce text.
+        ///
+        /// Convert them to a [`SharedString`](crate::SharedString) to pass them in the `text` of the
+        /// [`WindowEvent::KeyPressed`] and [`WindowEvent::KeyReleased`] events.
+        #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+        #[non_exhaustive]
+        #[allow(missing_docs)]
+        pub enum Key {
+            $($name,)*
+        }
+
+        impl From<Key> for char {
+            fn from(key: Key) -> char {
+                match key {
+                    $(Key::$name => $char,)*
+                }
+            }
+        }
+    };
+}
+
+i_slint_common::for_each_special_keys!(declare_key_enum);
+
+impl From<Key> for crate::SharedString {
+    fn from(key: Key) -> Self {
+        let mut buffer = [0; 6];
+        crate::SharedString::from(&*char::from(key).encode_utf8(&mut buffer))
+    }
+}
@@ -0,0 +1,215 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+/*!
+Opt-in instrumentation measuring where the time is spent while producing a frame.
+
+The measurements are only taken when enabled with [`set_enabled()`], so that the
+cost when disabled is limited to reading a thread local flag.
+*/
+
+#![warn(missing_docs)]
+
+#[cfg(all(not(feature = "std"), feature = "unsafe-single-threaded"))]
+use crate::unsafe_single_threaded::thread_local;
+use core::cell::{Cell, RefCell};
+use core::time::Duration;
+
+/// Statistics about a rendered frame, collected when enabled with
+/// [`set_frame_profiling_enabled()`](crate::platform::set_frame_profiling_enabled).
+#[derive(Clone, Debug, Default, PartialEq)]
+#[non_exhaustive]
+pub struct FrameStatistics {
+    /// The time spent in [`update_timers_and_animations()`](crate::platform::update_timers_and_animations)
+    /// before the frame, activating the timers, advancing the animations and notifying the property observers.
+    pub timers_and_animations: Duration,
+    /// The time spent evaluating the bindings of the properties while rendering the frame.
+    /// This includes the time spent computing the layouts.
+    pub property_evaluation: Duration,
+    /// The time spent computing the layouts while rendering the frame.
+    pub layout: Duration,
+    /// The time spent by the renderer to draw and submit the frame, excluding the property evaluation.
+    pub rendering: Duration,
+    /// The number of bindings that were evaluated while rendering the frame.
+    pub bindings_evaluated: usize,
+    /// The number of layouts that were computed while rendering the frame.
+    pub layouts_computed: usize,
+    /// The memory used by the decoded images kept in the image cache, in bytes.
+    pub image_cache_size: usize,
+}
+
+impl core::fmt::Display for FrameStatistics {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "timers: {:.2}ms, properties: {:.2}ms ({} bindings), layout: {:.2}ms ({} layouts), rendering: {:.2}ms, image cache: {}KiB",
+            self.timers_and_animations.as_secs_f32() * 1000.,
+            self.property_evaluation.as_secs_f32() * 1000.,
+            self.bindings_evaluated,
+            self.layout.as_secs_f32() * 1000.,
+            self.layouts_computed,
+            self.rendering.as_secs_f32() * 1000.,
+            self.image_cache_size / 1024,
+        )
+    }
+}
+
+#[derive(Clone, Copy)]
+pub(crate) enum Measurement {
+    BindingEvaluation,
+    Layout,
+}
+
+#[derive(Default)]
+struct Profiler {
+    enabled: Cell<bool>,
+    /// Number of nested binding evaluations, since only the outermost one is measured
+    binding_depth: Cell<usize>,
+    /// Number of nested layout computations, since only the outermost one is measured
+    layout_depth: Cell<usize>,
+    frame_start: Cell<Option<Duration>>,
+    current_frame: RefCell<FrameStatistics>,
+    last_frame: RefCell<Option<FrameStatistics>>,
+}
+
+impl Profiler {
+    fn depth(&self, measurement: Measurement) -> &Cell<usize> {
+        match measurement {
+            Measurement::BindingEvaluation => &self.binding_depth,
+            Measurement::Layout => &self.layout_depth,
+        }
+    }
+}
+
+thread_local!(static PROFILER: Profiler = Default::default());
+
+fn now() -> Duration {
+    crate::platform::PLATFORM_INSTANCE
+        .with(|p| p.get().map(|p| p.duration_since_start()))
+        .unwrap_or_default()
+}
+
+/// Enables or disables the collection of the frame statistics.
+pub fn set_enabled(enabled: bool) {
+    PROFILER.with(|profiler| {
+        profiler.enabled.set(enabled);
+        profiler.frame_start.set(None);
+        *profiler.current_frame.borrow_mut() = Default::default();
+        if !enabled {
+            *profiler.last_frame.borrow_mut() = None;
+        }
+    })
+}
+
+/// Returns true if the collection of the frame statistics is enabled.
+pub fn is_enabled() -> bool {
+    PROFILER.with(|profiler| profiler.enabled.get())
+}
+
+/// Returns the statistics of the last frame rendered since the profiling was enabled.
+pub fn last_frame_statistics() -> Option<FrameStatistics> {
+    PROFILER.with(|profiler| profiler.last_frame.borrow().clone())
+}
+
+/// Calls `f` and, if the profiling is enabled, accounts the time it took to the given measurement
+/// of the current frame.
+pub(crate) fn measure<R>(measurement: Measurement, f: impl FnOnce() -> R) -> R {
+    let start = PROFILER.with(|profiler| {
+        if !profiler.enabled.get() {
+            return None;
+        }
+        let depth = profiler.depth(measurement);
+        depth.set(depth.get() + 1);
+        Some((depth.get() == 1).then(now))
+    });
+    let result = f();
+    if let Some(start) = start {
+        PROFILER.with(|profiler| {
+            let depth = profiler.depth(measurement);
+            depth.set(depth.get().saturating_sub(1));
+            let mut current = profiler.current_frame.borrow_mut();
+            let duration = match measurement {
+                Measurement::BindingEvaluation => {
+                    current.bindings_evaluated += 1;
+                    &mut current.property_evaluation
+                }
+                Measurement::Layout => {
+                    current.layouts_computed += 1;
+                    &mut current.layout
+                }
+            };
+            if let Some(start) = start {
+                *duration += now().saturating_sub(start);
+            }
+        });
+    }
+    result
+}
+
+/// Called by [`update_timers_and_animations()`](crate::platform::update_timers_and_animations)
+pub(crate) fn measure_timers_and_animations(f: impl FnOnce()) {
+    if !is_enabled() {
+        return f();
+    }
+    let start = now();
+    f();
+    PROFILER.with(|profiler| {
+        profiler.current_frame.borrow_mut().timers_and_animations += now().saturating_sub(start);
+    })
+}
+
+/// Called by the window before the renderer draws its items.
+pub(crate) fn begin_frame() {
+    PROFILER.with(|profiler| {
+        if !profiler.enabled.get() {
+            return;
+        }
+        // The statistics accumulated between frames, except the timers and animations update
+        // that prepares this frame, are not relevant for this frame.
+        let mut current = profiler.current_frame.borrow_mut();
+        *current = FrameStatistics {
+            timers_and_animations: current.timers_and_animations,
+            ..Default::default()
+        };
+        profiler.frame_start.set(Some(now()));
+    })
+}
+
+/// Called by the window after the renderer drew its items.
+pub(crate) fn end_frame() {
+    PROFILER.with(|profiler| {
+        let start = match profiler.frame_start.take() {
+            Some(start) => start,
+            None => return,
+        };
+        let mut frame = core::mem::take(&mut *profiler.current_frame.borrow_mut());
+        frame.rendering = now().saturating_sub(start).saturating_sub(frame.property_evaluation);
+        #[cfg(feature = "image-decoders")]
+        {
+            frame.image_cache_size = crate::graphics::cache::IMAGE_CACHE
+                .with(|cache| cache.try_borrow().map_or(0, |cache| cache.size_in_bytes()));
+        }
+        *profiler.last_frame.borrow_mut() = Some(frame);
+    })
+}
+
+#[test]
+fn nested_measurements() {
+    set_enabled(true);
+    begin_frame();
+    measure(Measurement::BindingEvaluation, || {
+        measure(Measurement::Layout, || {
+            measure(Measurement::BindingEvaluation, || ());
+        })
+    });
+    end_frame();
+    let stats = last_frame_statistics().unwrap();
+    assert_eq!(stats.bindings_evaluated, 2);
+    assert_eq!(stats.layouts_computed, 1);
+    PROFILER.with(|profiler| {
+        assert_eq!(profiler.binding_depth.get(), 0);
+        assert_eq!(profiler.layout_depth.get(), 0);
+    });
+    set_enabled(false);
+    assert_eq!(last_frame_statistics(), None);
+}
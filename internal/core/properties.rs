@@ -571,9 +571,14 @@ unsafe fn update<T>(&self, value: *mut T) {
                 if binding.dirty.get() {
                     // clear all the nodes so that we can start from scratch
                     binding.dep_nodes.set(Default::default());
-                    let r = (binding.vtable.evaluate)(
-                        binding.as_mut().get_unchecked_mut() as *mut BindingHolder,
-                        value as *mut (),
+                    let r = crate::profiling::measure(
+                        crate::profiling::Measurement::BindingEvaluation,
+                        || {
+                            (binding.vtable.evaluate)(
+                                binding.as_mut().get_unchecked_mut() as *mut BindingHolder,
+                                value as *mut (),
+                            )
+                        },
                     );
                     binding.dirty.set(false);
                     if r == BindingResult::RemoveBinding {
@@ -1094,6 +1099,9 @@ fn property_two_ways_test_binding() {
 mod properties_animations;
 pub use properties_animations::*;
 
+mod change_tracker;
+pub use change_tracker::ChangeTracker;
+
 /// Value of the state property
 ///
 /// A state is just the current state, but also has information about the previous state and the moment it changed
@@ -0,0 +1,167 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+//! The ChangeTracker calls a function when the value computed from properties changes
+
+use super::*;
+use alloc::rc::Weak;
+use alloc::vec::Vec;
+
+#[cfg(all(not(feature = "std"), feature = "unsafe-single-threaded"))]
+use crate::unsafe_single_threaded::thread_local;
+
+thread_local!(
+    /// The trackers for which one of the dependencies changed since the last call to
+    /// `ChangeTracker::run_change_handlers()`
+    static DIRTY_CHANGE_TRACKERS: RefCell<Vec<Weak<ChangeTrackerInner>>> = RefCell::default()
+);
+
+struct ChangeTrackerDirtyHandler(Weak<ChangeTrackerInner>);
+
+impl PropertyDirtyHandler for ChangeTrackerDirtyHandler {
+    fn notify(&self) {
+        // The properties are locked at this point, so only record that the tracker needs to be evaluated
+        DIRTY_CHANGE_TRACKERS.with(|dirty| dirty.borrow_mut().push(self.0.clone()));
+    }
+}
+
+struct ChangeTrackerInner {
+    tracker: PropertyTracker<ChangeTrackerDirtyHandler>,
+    /// Evaluates the value and returns true if it is different from the previous one
+    evaluate: RefCell<Box<dyn FnMut() -> bool>>,
+    notify: RefCell<Box<dyn FnMut()>>,
+}
+
+impl ChangeTrackerInner {
+    /// Returns true if the value changed
+    fn evaluate(&self) -> bool {
+        // Safety: the tracker is never moved out of the Rc
+        let tracker = unsafe { Pin::new_unchecked(&self.tracker) };
+        tracker.evaluate_as_dependency_root(|| (self.evaluate.borrow_mut())())
+    }
+}
+
+/// A ChangeTracker evaluates a function that reads properties, and calls a notification function
+/// every time the returned value changes.
+///
+/// The change of the value is detected by the dependency tracking of the properties, so nothing
+/// is evaluated as long as none of the properties read by the function change.
+/// The notifications are not sent right away when a property is set, but later, when
+/// [`ChangeTracker::run_change_handlers()`] is called, which happens from
+/// [`crate::platform::update_timers_and_animations()`]. So setting a property several
+/// times in a row results in at most one notification.
+///
+/// The tracker stops when it is dropped.
+#[derive(Default)]
+pub struct ChangeTracker {
+    inner: Option<Rc<ChangeTrackerInner>>,
+}
+
+impl ChangeTracker {
+    /// Starts tracking the value returned by `eval`, calling `notify` with the new value when it changes.
+    ///
+    /// `eval` is called once right away to record the initial value, without calling `notify`.
+    /// If the tracker was already initialized, the previous functions are replaced.
+    pub fn init<T: PartialEq + Clone + 'static>(
+        &mut self,
+        eval: impl Fn() -> T + 'static,
+        mut notify: impl FnMut(&T) + 'static,
+    ) {
+        let value: Rc<RefCell<Option<T>>> = Default::default();
+        let value_for_notify = value.clone();
+        self.init_impl(
+            Box::new(move || {
+                let new_value = eval();
+                let mut value = value.borrow_mut();
+                let changed = value.as_ref() != Some(&new_value);
+                *value = Some(new_value);
+                changed
+            }),
+            Box::new(move || {
+                let value = value_for_notify.borrow().clone();
+                if let Some(value) = value {
+                    notify(&value)
+                }
+            }),
+        )
+    }
+
+    /// Same as [`Self::init`], but with type-erased functions. `evaluate` must return true if the value
+    /// is different from the one returned by the previous call.
+    pub(crate) fn init_impl(
+        &mut self,
+        evaluate: Box<dyn FnMut() -> bool>,
+        notify: Box<dyn FnMut()>,
+    ) {
+        let inner = Rc::new_cyclic(|weak| ChangeTrackerInner {
+            tracker: PropertyTracker::new_with_dirty_handler(ChangeTrackerDirtyHandler(
+                weak.clone(),
+            )),
+            evaluate: RefCell::new(evaluate),
+            notify: RefCell::new(notify),
+        });
+        inner.evaluate();
+        self.inner = Some(inner);
+    }
+
+    /// Returns true if some trackers have pending changes, meaning that
+    /// [`Self::run_change_handlers()`] should be called soon.
+    pub fn has_pending_change_handlers() -> bool {
+        DIRTY_CHANGE_TRACKERS.with(|dirty| !dirty.borrow().is_empty())
+    }
+
+    /// Evaluates the trackers for which the properties changed, and calls the notification
+    /// function of the ones whose value changed.
+    pub fn run_change_handlers() {
+        // The handlers may change properties that other trackers depend on, so loop a few times,
+        // but not forever in case two handlers keep changing each other's properties.
+        for _ in 0..10 {
+            let dirty =
+                DIRTY_CHANGE_TRACKERS.with(|dirty| core::mem::take(&mut *dirty.borrow_mut()));
+            if dirty.is_empty() {
+                return;
+            }
+            for inner in dirty.iter().filter_map(|weak| weak.upgrade()) {
+                if inner.evaluate() {
+                    (inner.notify.borrow_mut())();
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn change_tracker() {
+    let prop = Rc::pin(Property::new(1));
+    let notified = Rc::new(RefCell::new(Vec::new()));
+
+    let mut tracker = ChangeTracker::default();
+    tracker.init(
+        {
+            let prop = prop.clone();
+            move || prop.as_ref().get() * 2
+        },
+        {
+            let notified = notified.clone();
+            move |value| notified.borrow_mut().push(*value)
+        },
+    );
+    ChangeTracker::run_change_handlers();
+    assert!(notified.borrow().is_empty());
+
+    prop.set(2);
+    prop.set(3);
+    assert!(notified.borrow().is_empty());
+    ChangeTracker::run_change_handlers();
+    assert_eq!(*notified.borrow(), vec![6]);
+
+    // Setting the same value doesn't notify
+    prop.set(3);
+    ChangeTracker::run_change_handlers();
+    assert_eq!(*notified.borrow(), vec![6]);
+
+    drop(tracker);
+    prop.set(4);
+    ChangeTracker::run_change_handlers();
+    assert_eq!(*notified.borrow(), vec![6]);
+}
@@ -451,8 +451,67 @@ pub struct PropertyTrackerOpaque {
     core::ptr::drop_in_place(handle as *mut PropertyTracker);
 }
 
+#[repr(C)]
+/// Opaque type representing the ChangeTracker
+pub struct ChangeTrackerOpaque {
+    inner: *const c_void,
+}
+
+static_assertions::assert_eq_align!(ChangeTrackerOpaque, ChangeTracker);
+static_assertions::assert_eq_size!(ChangeTrackerOpaque, ChangeTracker);
+
+/// Construct a ChangeTracker that doesn't track anything yet.
+/// `out` is assumed to be uninitialized
+/// slint_change_tracker_drop need to be called after that
+#[no_mangle]
+pub unsafe extern "C" fn slint_change_tracker_construct(out: *mut ChangeTrackerOpaque) {
+    core::ptr::write(out as *mut ChangeTracker, ChangeTracker::default());
+}
+
+/// Start tracking: `eval_fn` is called to evaluate the value and must return true if the value changed
+/// since the previous call. `notify_fn` is called after `eval_fn` returned true, from the event loop.
+/// `drop_user_data` is called when the tracker is dropped or initialized again.
+#[no_mangle]
+pub unsafe extern "C" fn slint_change_tracker_init(
+    handle: *mut ChangeTrackerOpaque,
+    user_data: *mut c_void,
+    drop_user_data: Option<extern "C" fn(user_data: *mut c_void)>,
+    eval_fn: extern "C" fn(user_data: *mut c_void) -> bool,
+    notify_fn: extern "C" fn(user_data: *mut c_void),
+) {
+    struct UserData {
+        user_data: *mut c_void,
+        drop_user_data: Option<extern "C" fn(*mut c_void)>,
+    }
+    impl Drop for UserData {
+        fn drop(&mut self) {
+            if let Some(x) = self.drop_user_data {
+                x(self.user_data)
+            }
+        }
+    }
+    let user_data = Rc::new(UserData { user_data, drop_user_data });
+    let user_data_for_notify = user_data.clone();
+    (*(handle as *mut ChangeTracker)).init_impl(
+        Box::new(move || eval_fn(user_data.user_data)),
+        Box::new(move || notify_fn(user_data_for_notify.user_data)),
+    );
+}
+
+/// Destroy the change tracker
+#[no_mangle]
+pub unsafe extern "C" fn slint_change_tracker_drop(handle: *mut ChangeTrackerOpaque) {
+    core::ptr::drop_in_place(handle as *mut ChangeTracker);
+}
+
 /// return the current animation tick for the `animation-tick` function
 #[no_mangle]
 pub extern "C" fn slint_animation_tick() -> u64 {
     crate::animations::animation_tick()
 }
+
+/// return whether animations are reduced, for the `reduced-motion` function
+#[no_mangle]
+pub extern "C" fn slint_reduced_motion() -> bool {
+    crate::animations::reduced_motion()
+}
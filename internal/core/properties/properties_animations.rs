@@ -26,6 +26,11 @@ pub fn new(from_value: T, to_value: T, details: PropertyAnimation) -> Self {
     }
 
     pub fn compute_interpolated_value(&mut self) -> (T, bool) {
+        if crate::animations::CURRENT_ANIMATION_DRIVER
+            .with(|driver| driver.reduced_motion_untracked())
+        {
+            self.state = AnimationState::Done;
+        }
         let new_tick = crate::animations::current_tick();
         let mut time_progress = new_tick.duration_since(self.start_time).as_millis() as u64;
 
@@ -443,6 +448,31 @@ fn properties_test_animation_triggered_by_set() {
         compo.width.handle.access(|binding| assert!(binding.is_none()));
     }
 
+    #[test]
+    fn properties_test_animation_with_reduced_motion() {
+        let compo = Component::new_test_component();
+
+        let animation_details = PropertyAnimation {
+            duration: DURATION.as_millis() as _,
+            iteration_count: 1.,
+            ..PropertyAnimation::default()
+        };
+
+        compo.width.set(100);
+        assert_eq!(get_prop_value(&compo.width), 100);
+
+        crate::animations::CURRENT_ANIMATION_DRIVER.with(|driver| driver.set_reduced_motion(true));
+
+        compo.width.set_animated_value(200, animation_details);
+        assert_eq!(get_prop_value(&compo.width), 200);
+        assert_eq!(get_prop_value(&compo.width_times_two), 400);
+
+        // the binding should be removed
+        compo.width.handle.access(|binding| assert!(binding.is_none()));
+
+        crate::animations::CURRENT_ANIMATION_DRIVER.with(|driver| driver.set_reduced_motion(false));
+    }
+
     #[test]
     fn properties_test_delayed_animation_triggered_by_set() {
         let compo = Component::new_test_component();
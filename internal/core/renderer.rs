@@ -43,6 +43,13 @@ fn free_graphics_resources(
     ) {
     }
 
+    /// Returns whether the renderer draws the rotation, scaling and skewing of the `Transform`
+    /// element. When it doesn't, pointer events are delivered according to the untransformed
+    /// geometry, so that they reach the items where they are drawn.
+    fn supports_transformations(&self) -> bool {
+        true
+    }
+
     /// Mark a given region as dirty regardless whether the items actually are dirty.
     ///
     /// Example: when a PopupWindow disapear, the region under the popup needs to be redrawn
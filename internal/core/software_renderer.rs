@@ -13,8 +13,8 @@
 use crate::item_rendering::ItemRenderer;
 use crate::items::{ImageFit, Item, ItemRc};
 use crate::lengths::{
-    LogicalLength, LogicalPoint, LogicalRect, LogicalSize, LogicalVector, PhysicalPx, PointLengths,
-    RectLengths, ScaleFactor, SizeLengths,
+    LogicalBorderRadius, LogicalLength, LogicalPoint, LogicalRect, LogicalSize, LogicalVector,
+    PhysicalPx, PointLengths, RectLengths, ScaleFactor, SizeLengths,
 };
 use crate::renderer::Renderer;
 use crate::textlayout::{FontMetrics as _, TextParagraphLayout};
@@ -26,14 +26,96 @@
 use core::pin::Pin;
 use euclid::num::Zero;
 
-pub use draw_functions::{PremultipliedRgbaColor, Rgb565Pixel, TargetPixel};
+pub use draw_functions::{
+    Bgra8888Pixel, PremultipliedRgbaColor, Rgb332Pixel, Rgb565Pixel, Rgb565SwappedPixel,
+    TargetPixel, TargetPixelFormat,
+};
 
 type PhysicalLength = euclid::Length<i16, PhysicalPx>;
 type PhysicalRect = euclid::Rect<i16, PhysicalPx>;
 type PhysicalSize = euclid::Size2D<i16, PhysicalPx>;
 type PhysicalPoint = euclid::Point2D<i16, PhysicalPx>;
+type PhysicalBorderRadius = crate::lengths::BorderRadius<PhysicalLength>;
+
+type PhysicalBox = euclid::Box2D<i16, PhysicalPx>;
+type DirtyRegion = crate::item_rendering::Region<i16, PhysicalPx>;
+
+/// Represents the region of the screen that was rendered, in physical pixels.
+///
+/// This is returned by [`SoftwareRenderer::render()`] and [`SoftwareRenderer::render_by_line()`],
+/// so that the platform only needs to send these pixels to the display.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct PhysicalRegion(DirtyRegion);
+
+impl PhysicalRegion {
+    /// Returns the position of the top-left corner of the bounding box of the region
+    pub fn bounding_box_origin(&self) -> crate::api::PhysicalPosition {
+        let bounding_box = self.0.bounding_box();
+        crate::api::PhysicalPosition::new(bounding_box.min.x as _, bounding_box.min.y as _)
+    }
+
+    /// Returns the size of the bounding box of the region
+    pub fn bounding_box_size(&self) -> crate::api::PhysicalSize {
+        let bounding_box = self.0.bounding_box();
+        crate::api::PhysicalSize::new(bounding_box.width() as _, bounding_box.height() as _)
+    }
+
+    /// Returns the rectangles of the region, as the position of their top-left corner and their size.
+    ///
+    /// The rectangles don't overlap. Areas that changed far apart from each other are in different
+    /// rectangles, so that the pixels between them don't need to be sent to the display.
+    pub fn iter(
+        &self,
+    ) -> impl Iterator<Item = (crate::api::PhysicalPosition, crate::api::PhysicalSize)> + '_ {
+        self.0.iter().map(|b| {
+            (
+                crate::api::PhysicalPosition::new(b.min.x as _, b.min.y as _),
+                crate::api::PhysicalSize::new(b.width() as _, b.height() as _),
+            )
+        })
+    }
+
+    /// Returns true if nothing was rendered
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
 
-type DirtyRegion = PhysicalRect;
+/// Converts the dirty region computed by the [`PartialRenderer`](crate::item_rendering::PartialRenderer)
+/// to physical pixels
+fn to_physical_region(
+    region: &crate::item_rendering::DirtyRegion,
+    factor: ScaleFactor,
+) -> DirtyRegion {
+    let mut physical_region = DirtyRegion::default();
+    for b in region.iter() {
+        let rect: PhysicalRect = (b.to_rect().cast() * factor).round_out().cast();
+        physical_region.add_box(rect.to_box2d());
+    }
+    physical_region
+}
+
+/// Returns the ranges of columns of `region` on the given line, from left to right
+fn line_ranges(region: &DirtyRegion, line: i16) -> impl Iterator<Item = core::ops::Range<usize>> {
+    let mut ranges = [(0, 0); crate::item_rendering::REGION_MAX_COUNT];
+    let mut count = 0;
+    for b in region.iter().filter(|b| b.min.y <= line && line < b.max.y) {
+        ranges[count] = (b.min.x, b.max.x);
+        count += 1;
+    }
+    ranges[..count].sort_unstable();
+    // Merge the ranges of rectangles that touch each other
+    let mut merged = 0;
+    for i in 0..count {
+        if merged > 0 && ranges[merged - 1].1 >= ranges[i].0 {
+            ranges[merged - 1].1 = ranges[merged - 1].1.max(ranges[i].1);
+        } else {
+            ranges[merged] = ranges[i];
+            merged += 1;
+        }
+    }
+    (0..merged).map(move |i| ranges[i].0 as usize..ranges[i].1 as usize)
+}
 
 /// This trait defines a bi-directional interface between Slint and your code to send lines to your screen, when using
 /// the [`SoftwareRenderer::render_by_line`] function.
@@ -48,10 +130,12 @@ pub trait LineBufferProvider {
     /// The pixel type of the buffer
     type TargetPixel: TargetPixel;
 
-    /// Called once per line, you will have to call the render_fn back with the buffer.
+    /// Called for each part of a line that needs to be rendered, you will have to call the render_fn back with the buffer.
     ///
     /// The `line` is the y position of the line to be drawn.
-    /// The `range` is the range within the line that is going to be rendered (eg, within the dirty region)
+    /// The `range` is the range within the line that is going to be rendered (eg, within the dirty region).
+    /// When the dirty region is made of several rectangles, this is called once for each of the
+    /// rectangles that cover the line, from left to right.
     /// The `render_fn` function should be called to render the line, passing the buffer
     /// corresponding to the specified line and range.
     fn process_line(
@@ -72,6 +156,9 @@ fn process_line(
 ///     is only useful if the device does not have enough memory to render the whole window
 ///     in one single buffer
 ///
+/// Both can also be split in two steps with [`prepare_frame()`](Self::prepare_frame()), to
+/// render the pixels in another thread than the one running the event loop.
+///
 /// ### `MAX_BUFFER_AGE`
 ///
 /// The `MAX_BUFFER_AGE` parameter specifies how many buffers are being re-used.
@@ -118,8 +205,9 @@ fn apply_dirty_region(
         dirty_region: DirtyRegion,
         screen_size: PhysicalSize,
     ) -> DirtyRegion {
+        let screen = PhysicalBox::from_size(screen_size);
         if MAX_BUFFER_AGE == 0 {
-            PhysicalRect { origin: euclid::point2(0, 0), size: screen_size }
+            DirtyRegion::from(screen)
         } else if MAX_BUFFER_AGE == 1 {
             dirty_region
         } else if MAX_BUFFER_AGE == 2 {
@@ -133,8 +221,7 @@ fn apply_dirty_region(
             }
             union
         }
-        .intersection(&PhysicalRect { origin: euclid::point2(0, 0), size: screen_size })
-        .unwrap_or_default()
+        .intersection(&screen)
     }
 
     /// Render the window to the given frame buffer.
@@ -143,8 +230,9 @@ fn apply_dirty_region(
     /// which are dirty. The `extra_draw_region` is an extra regin which will also
     /// be rendered. (eg: the previous dirty region in case of double buffering)
     ///
-    /// returns the dirty region for this frame (not including the extra_draw_region)
-    pub fn render(&self, buffer: &mut [impl TargetPixel], buffer_stride: usize) {
+    /// Returns the region that was rendered, which includes the region that was dirty in the previous
+    /// frames, according to `MAX_BUFFER_AGE`. Only the pixels within that region changed in the buffer.
+    pub fn render(&self, buffer: &mut [impl TargetPixel], buffer_stride: usize) -> PhysicalRegion {
         let window = self.window.upgrade().expect("render() called on a destroyed Window");
         let window_inner = WindowInner::from_pub(window.window());
         let factor = ScaleFactor::new(window_inner.scale_factor());
@@ -175,29 +263,40 @@ pub fn render(&self, buffer: &mut [impl TargetPixel], buffer_stride: usize) {
             buffer_renderer,
         );
 
+        let mut rendered_region = PhysicalRegion::default();
         window_inner.draw_contents(|components| {
             for (component, origin) in components {
                 renderer.compute_dirty_regions(component, *origin);
             }
 
-            let dirty_region = (renderer.dirty_region.to_rect().cast() * factor).round_out().cast();
+            let dirty_region = to_physical_region(&renderer.dirty_region, factor);
 
             let to_draw = self.apply_dirty_region(dirty_region, size);
-
-            renderer.combine_clip(
-                (to_draw.cast() / factor).cast(),
-                LogicalLength::zero(),
-                LogicalLength::zero(),
-            );
-
-            if background.is_transparent() {
-                // FIXME: gradient
-                renderer.actual_renderer.processor.process_rectangle(to_draw, background.color());
-            }
-            for (component, origin) in components {
-                crate::item_rendering::render_component_items(component, &mut renderer, *origin);
+            rendered_region = PhysicalRegion(to_draw);
+
+            for rect in to_draw.iter().map(|b| b.to_rect()) {
+                renderer.save_state();
+                renderer.combine_clip(
+                    (rect.cast() / factor).cast(),
+                    LogicalBorderRadius::default(),
+                    LogicalLength::zero(),
+                );
+
+                if background.is_transparent() {
+                    // FIXME: gradient
+                    renderer.actual_renderer.processor.process_rectangle(rect, background.color());
+                }
+                for (component, origin) in components {
+                    crate::item_rendering::render_component_items(
+                        component,
+                        &mut renderer,
+                        *origin,
+                    );
+                }
+                renderer.restore_state();
             }
         });
+        rendered_region
     }
 
     /// Render the window, line by line, into the line buffer provided by the `line_callback` function.
@@ -206,6 +305,8 @@ pub fn render(&self, buffer: &mut [impl TargetPixel], buffer_stride: usize) {
     /// which are dirty, depending on the dirty tracking policy set in [`SoftwareRenderer::new`]
     ///
     /// The line callback will be called for each line and should provide a buffer to draw into.
+    /// Returns the region that was rendered: the line callback was only called for the lines of the
+    /// rectangles of that region, with their horizontal range.
     ///
     /// As an example, let's imagine we want to render into a plain buffer.
     /// (You wouldn't normally use `render_by_line` for that because the [`Self::render`] would
@@ -232,8 +333,37 @@ pub fn render(&self, buffer: &mut [impl TargetPixel], buffer_stride: usize) {
     /// renderer.render_by_line(FrameBuffer{ frame_buffer: the_frame_buffer, stride: display_width });
     /// # }
     /// ```
-    pub fn render_by_line(&self, line_buffer: impl LineBufferProvider) {
-        let window = self.window.upgrade().expect("render() called on a destroyed Window");
+    pub fn render_by_line(&self, line_buffer: impl LineBufferProvider) -> PhysicalRegion {
+        self.prepare_frame().render_by_line(line_buffer)
+    }
+
+    /// Collect the items to draw in the next frame, without drawing any pixel.
+    ///
+    /// This is the part of the rendering that reads the properties of the items, so it
+    /// must be called from the thread running the event loop. The returned [`PreparedFrame`]
+    /// no longer references the items and implements `Send`: it can be rendered into a buffer in
+    /// another thread, while the event loop continues to process events. The state of the items
+    /// at the time of the call is rendered, even if properties change in the meantime.
+    ///
+    /// The dirty region is computed and consumed by this call, so the frames must be rendered
+    /// in the order in which they were prepared, into the same buffers as with [`Self::render()`].
+    ///
+    /// ```rust
+    /// # use i_slint_core::software_renderer::{SoftwareRenderer, Rgb565Pixel};
+    /// # fn xxx(renderer: &SoftwareRenderer<1>) {
+    /// const DISPLAY_WIDTH: usize = 320;
+    /// let frame = renderer.prepare_frame();
+    /// let worker = std::thread::spawn(move || {
+    ///     let mut buffer = vec![Rgb565Pixel::default(); DISPLAY_WIDTH * 240];
+    ///     frame.render(&mut buffer, DISPLAY_WIDTH);
+    ///     buffer
+    /// });
+    /// // ... process events, then send the buffer to the display
+    /// let buffer = worker.join().unwrap();
+    /// # }
+    /// ```
+    pub fn prepare_frame(&self) -> PreparedFrame {
+        let window = self.window.upgrade().expect("prepare_frame() called on a destroyed Window");
         let window_inner = WindowInner::from_pub(window.window());
         let component_rc = window_inner.component();
         let component = crate::component::ComponentRc::borrow_pin(&component_rc);
@@ -243,17 +373,190 @@ pub fn render_by_line(&self, line_buffer: impl LineBufferProvider) {
             let factor = ScaleFactor::new(window_inner.scale_factor());
             let size = LogicalSize::from_lengths(window_item.width(), window_item.height()).cast()
                 * factor;
-            render_window_frame_by_line(
-                window_inner,
-                window_item.background(),
-                size.cast(),
-                &self,
-                line_buffer,
-            );
+            PreparedFrame {
+                scene: prepare_scene(window_inner, size.cast(), self),
+                // FIXME gradient
+                background: window_item.background().color().into(),
+            }
+        } else {
+            PreparedFrame {
+                scene: Scene::new(
+                    Vec::new(),
+                    Vec::new(),
+                    Vec::new(),
+                    Vec::new(),
+                    DirtyRegion::default(),
+                ),
+                background: Default::default(),
+            }
+        }
+    }
+
+    /// Render the window in bands of several lines, using `band_buffer` as the only pixel storage.
+    ///
+    /// This is a variant of [`Self::render_by_line()`] for displays that are more efficiently
+    /// updated with a few lines at a time, for example with DMA transfers to the display
+    /// controller, when there isn't enough RAM for a whole frame buffer.
+    ///
+    /// The number of lines in a band is `band_buffer.len() / screen_width`. When a band is rendered,
+    /// `flush_band` is called with the first line of the band, the range of columns that were
+    /// rendered, and the pixels of the band: the lines follow each other, each with `range.len()`
+    /// pixels. Only the lines and columns of the rectangles of the dirty region are rendered, so
+    /// the lines of a band all have the same range of columns.
+    ///
+    /// Returns the region that was rendered.
+    ///
+    /// ```rust
+    /// # use i_slint_core::software_renderer::{SoftwareRenderer, Rgb565Pixel};
+    /// # fn send_to_display(_: usize, _: core::ops::Range<usize>, _: &[Rgb565Pixel]) {}
+    /// # fn xxx(renderer: &SoftwareRenderer<1>) {
+    /// const DISPLAY_WIDTH: usize = 320;
+    /// // Render 16 lines at a time
+    /// let mut band_buffer = [Rgb565Pixel::default(); DISPLAY_WIDTH * 16];
+    /// renderer.render_by_band(&mut band_buffer, DISPLAY_WIDTH, |first_line, range, pixels| {
+    ///     send_to_display(first_line, range, pixels);
+    /// });
+    /// # }
+    /// ```
+    pub fn render_by_band<P: TargetPixel>(
+        &self,
+        band_buffer: &mut [P],
+        screen_width: usize,
+        flush_band: impl FnMut(usize, core::ops::Range<usize>, &mut [P]),
+    ) -> PhysicalRegion {
+        let band_height = band_buffer.len() / screen_width.max(1);
+        assert!(band_height > 0, "the band buffer must be able to contain at least one line");
+        let mut band = BandBuffer {
+            buffer: band_buffer,
+            band_height,
+            first_line: 0,
+            line_count: 0,
+            range: 0..0,
+            flush_band,
+        };
+        let region = self.render_by_line(&mut band);
+        band.flush();
+        region
+    }
+
+    /// Render the window to a frame buffer whose pixel format is only known at run-time.
+    ///
+    /// This is like [`Self::render()`], but the buffer is a slice of bytes, with `buffer_stride`
+    /// pixels per line, each stored in `format`. Use one of the [`TargetPixel`] types with
+    /// [`Self::render()`] instead when the format is known at compile time.
+    ///
+    /// The lines are composed with 8 bits per component before they are converted. When
+    /// `dithering` is true, an ordered dithering is applied to the formats with fewer bits per
+    /// component, such as [`TargetPixelFormat::Rgb565`] and [`TargetPixelFormat::Rgb332`], so that
+    /// gradients and semi-transparent colors don't show bands.
+    ///
+    /// Returns the region that was rendered.
+    pub fn render_with_format(
+        &self,
+        buffer: &mut [u8],
+        buffer_stride: usize,
+        format: TargetPixelFormat,
+        dithering: bool,
+    ) -> PhysicalRegion {
+        self.render_by_line(FormattedBuffer {
+            buffer,
+            stride: buffer_stride,
+            format,
+            dithering,
+            line: Vec::new(),
+        })
+    }
+}
+
+/// A [`LineBufferProvider`] that composes each line with [`Rgb8Pixel`](crate::graphics::Rgb8Pixel)
+/// and converts it to the format of the buffer
+struct FormattedBuffer<'a> {
+    buffer: &'a mut [u8],
+    /// The number of pixels per line
+    stride: usize,
+    format: TargetPixelFormat,
+    dithering: bool,
+    /// The line being rendered
+    line: Vec<crate::graphics::Rgb8Pixel>,
+}
+
+impl<'a> LineBufferProvider for FormattedBuffer<'a> {
+    type TargetPixel = crate::graphics::Rgb8Pixel;
+
+    fn process_line(
+        &mut self,
+        line: usize,
+        range: core::ops::Range<usize>,
+        render_fn: impl FnOnce(&mut [Self::TargetPixel]),
+    ) {
+        let bytes_per_pixel = self.format.bytes_per_pixel();
+        let start = (line * self.stride + range.start) * bytes_per_pixel;
+        let bytes = &mut self.buffer[start..start + range.len() * bytes_per_pixel];
+        let format = self.format;
+        self.line.clear();
+        self.line.extend(bytes.chunks_exact(bytes_per_pixel).map(|pix| format.read(pix)));
+        render_fn(&mut self.line[..]);
+        for (x, (pix, bytes)) in
+            range.zip(self.line.iter().zip(bytes.chunks_exact_mut(bytes_per_pixel)))
+        {
+            let pix =
+                if self.dithering { draw_functions::dither(*pix, format, x, line) } else { *pix };
+            format.write(bytes, pix);
+        }
+    }
+}
+
+/// Adapts a [`LineBufferProvider`] to render several consecutive lines in one buffer
+struct BandBuffer<'a, P, F: FnMut(usize, core::ops::Range<usize>, &mut [P])> {
+    buffer: &'a mut [P],
+    band_height: usize,
+    /// The first line in the buffer
+    first_line: usize,
+    /// The number of lines rendered in the buffer
+    line_count: usize,
+    /// The columns of the lines in the buffer
+    range: core::ops::Range<usize>,
+    flush_band: F,
+}
+
+impl<'a, P, F: FnMut(usize, core::ops::Range<usize>, &mut [P])> BandBuffer<'a, P, F> {
+    fn flush(&mut self) {
+        if self.line_count > 0 {
+            let len = self.line_count * self.range.len();
+            (self.flush_band)(self.first_line, self.range.clone(), &mut self.buffer[..len]);
+            self.line_count = 0;
         }
     }
 }
 
+impl<'a, P: TargetPixel, F: FnMut(usize, core::ops::Range<usize>, &mut [P])> LineBufferProvider
+    for &mut BandBuffer<'a, P, F>
+{
+    type TargetPixel = P;
+
+    fn process_line(
+        &mut self,
+        line: usize,
+        range: core::ops::Range<usize>,
+        render_fn: impl FnOnce(&mut [Self::TargetPixel]),
+    ) {
+        if self.line_count == self.band_height
+            || (self.line_count > 0
+                && (line != self.first_line + self.line_count || range != self.range))
+        {
+            self.flush();
+        }
+        if self.line_count == 0 {
+            self.first_line = line;
+            self.range = range.clone();
+        }
+        let width = range.len();
+        let start = self.line_count * width;
+        render_fn(&mut self.buffer[start..start + width]);
+        self.line_count += 1;
+    }
+}
+
 #[doc(hidden)]
 impl<const MAX_BUFFER_AGE: usize> Renderer for SoftwareRenderer<MAX_BUFFER_AGE> {
     fn text_size(
@@ -266,6 +569,10 @@ fn text_size(
         fonts::text_size(font_request, text, max_width, scale_factor)
     }
 
+    fn supports_transformations(&self) -> bool {
+        false
+    }
+
     fn text_input_byte_offset_for_position(
         &self,
         _text_input: Pin<&crate::items::TextInput>,
@@ -301,90 +608,151 @@ fn register_bitmap_font(&self, font_data: &'static crate::graphics::BitmapFont)
     }
 }
 
-fn render_window_frame_by_line<const MAX_BUFFER_AGE: usize>(
-    window: &WindowInner,
-    background: Brush,
-    size: PhysicalSize,
-    renderer: &SoftwareRenderer<MAX_BUFFER_AGE>,
-    mut line_buffer: impl LineBufferProvider,
-) {
-    let mut scene = prepare_scene(window, size, renderer);
-
-    let dirty_region = scene.dirty_region;
-
-    debug_assert!(scene.current_line >= dirty_region.origin.y_length());
-
-    // FIXME gradient
-    let background_color = background.color().into();
-
-    while scene.current_line < dirty_region.origin.y_length() + dirty_region.size.height_length() {
-        line_buffer.process_line(
-            scene.current_line.get() as usize,
-            dirty_region.min_x() as usize..dirty_region.max_x() as usize,
-            |line_buffer| {
-                let offset = dirty_region.min_x() as usize;
-
-                TargetPixel::blend_slice(line_buffer, background_color);
-                for span in scene.items[0..scene.current_items_index].iter().rev() {
-                    debug_assert!(scene.current_line >= span.pos.y_length());
-                    debug_assert!(
-                        scene.current_line < span.pos.y_length() + span.size.height_length(),
-                    );
-                    match span.command {
-                        SceneCommand::Rectangle { color } => {
-                            TargetPixel::blend_slice(
-                                &mut line_buffer[span.pos.x as usize - offset
-                                    ..(span.pos.x_length() + span.size.width_length()).get()
-                                        as usize
-                                        - offset],
-                                color,
-                            );
-                        }
-                        SceneCommand::Texture { texture_index } => {
-                            let texture = &scene.textures[texture_index as usize];
-                            draw_functions::draw_texture_line(
-                                &PhysicalRect {
-                                    origin: span.pos - euclid::vec2(offset as i16, 0),
-                                    size: span.size,
-                                },
-                                scene.current_line,
-                                texture,
-                                line_buffer,
-                            );
-                        }
-                        SceneCommand::SharedBuffer { shared_buffer_index } => {
-                            let texture =
-                                scene.shared_buffers[shared_buffer_index as usize].as_texture();
-                            draw_functions::draw_texture_line(
-                                &PhysicalRect {
-                                    origin: span.pos - euclid::vec2(offset as i16, 0),
-                                    size: span.size,
-                                },
-                                scene.current_line,
-                                &texture,
-                                line_buffer,
-                            );
-                        }
-                        SceneCommand::RoundedRectangle { rectangle_index } => {
-                            let rr = &scene.rounded_rectangles[rectangle_index as usize];
-                            draw_functions::draw_rounded_rectangle_line(
-                                &PhysicalRect {
-                                    origin: span.pos - euclid::vec2(offset as i16, 0),
-                                    size: span.size,
-                                },
-                                scene.current_line,
-                                rr,
-                                line_buffer,
+/// A frame collected by [`SoftwareRenderer::prepare_frame()`], ready to be rendered.
+///
+/// It does not reference the items of the window and can be sent to another thread.
+pub struct PreparedFrame {
+    scene: Scene,
+    background: PremultipliedRgbaColor,
+}
+
+#[test]
+fn prepared_frame_is_send() {
+    fn assert_send<T: Send>() {}
+    assert_send::<PreparedFrame>();
+}
+
+impl PreparedFrame {
+    /// Returns the region that will be rendered by this frame.
+    pub fn dirty_region(&self) -> PhysicalRegion {
+        PhysicalRegion(self.scene.dirty_region)
+    }
+
+    /// Render the frame to the given frame buffer.
+    ///
+    /// Returns the region that was rendered. See [`SoftwareRenderer::render()`].
+    pub fn render(self, buffer: &mut [impl TargetPixel], buffer_stride: usize) -> PhysicalRegion {
+        struct RenderToBufferLines<'a, T> {
+            buffer: &'a mut [T],
+            stride: usize,
+        }
+        impl<'a, T: TargetPixel> LineBufferProvider for RenderToBufferLines<'a, T> {
+            type TargetPixel = T;
+            fn process_line(
+                &mut self,
+                line: usize,
+                range: core::ops::Range<usize>,
+                render_fn: impl FnOnce(&mut [Self::TargetPixel]),
+            ) {
+                render_fn(&mut self.buffer[line * self.stride..][range]);
+            }
+        }
+        self.render_by_line(RenderToBufferLines { buffer, stride: buffer_stride })
+    }
+
+    /// Render the frame, line by line, into the line buffer provided by the `line_buffer`.
+    ///
+    /// Returns the region that was rendered. See [`SoftwareRenderer::render_by_line()`].
+    pub fn render_by_line(self, mut line_buffer: impl LineBufferProvider) -> PhysicalRegion {
+        let mut scene = self.scene;
+        let background_color = self.background;
+        let dirty_region = scene.dirty_region;
+        let bounding_box = dirty_region.bounding_box();
+
+        debug_assert!(scene.current_line.get() >= bounding_box.min.y);
+
+        while scene.current_line.get() < bounding_box.max.y {
+            for range in line_ranges(&dirty_region, scene.current_line.get()) {
+                line_buffer.process_line(
+                    scene.current_line.get() as usize,
+                    range.clone(),
+                    |line_buffer| {
+                        let offset = range.start as i16;
+
+                        TargetPixel::blend_slice(line_buffer, background_color);
+                        for span in scene.items[0..scene.current_items_index].iter().rev() {
+                            debug_assert!(scene.current_line >= span.pos.y_length());
+                            debug_assert!(
+                                scene.current_line
+                                    < span.pos.y_length() + span.size.height_length(),
                             );
+                            // The part of the span within the range
+                            let begin = span.pos.x.max(offset);
+                            let end = (span.pos.x + span.size.width).min(range.end as i16);
+                            if begin >= end {
+                                continue;
+                            }
+                            let extra_left_clip = begin - span.pos.x;
+                            let extra_right_clip = span.pos.x + span.size.width - end;
+                            match span.command {
+                                SceneCommand::Rectangle { color } => {
+                                    TargetPixel::blend_slice(
+                                        &mut line_buffer
+                                            [(begin - offset) as usize..(end - offset) as usize],
+                                        color,
+                                    );
+                                }
+                                SceneCommand::Texture { texture_index } => {
+                                    let texture = &scene.textures[texture_index as usize];
+                                    draw_functions::draw_texture_line(
+                                        &PhysicalRect {
+                                            origin: span.pos - euclid::vec2(offset, 0),
+                                            size: span.size,
+                                        },
+                                        scene.current_line,
+                                        texture,
+                                        line_buffer,
+                                        extra_left_clip,
+                                        extra_right_clip,
+                                    );
+                                }
+                                SceneCommand::SharedBuffer { shared_buffer_index } => {
+                                    let texture = scene.shared_buffers
+                                        [shared_buffer_index as usize]
+                                        .as_texture();
+                                    draw_functions::draw_texture_line(
+                                        &PhysicalRect {
+                                            origin: span.pos - euclid::vec2(offset, 0),
+                                            size: span.size,
+                                        },
+                                        scene.current_line,
+                                        &texture,
+                                        line_buffer,
+                                        extra_left_clip,
+                                        extra_right_clip,
+                                    );
+                                }
+                                SceneCommand::RoundedRectangle { rectangle_index } => {
+                                    let rr = &scene.rounded_rectangles[rectangle_index as usize];
+                                    // The columns outside of the range are clipped like the
+                                    // parts of the rectangle that are outside of the clip
+                                    draw_functions::draw_rounded_rectangle_line(
+                                        &PhysicalRect {
+                                            origin: euclid::point2(begin - offset, span.pos.y),
+                                            size: euclid::size2(end - begin, span.size.height),
+                                        },
+                                        scene.current_line,
+                                        &RoundedRectangle {
+                                            left_clip: rr.left_clip
+                                                + PhysicalLength::new(extra_left_clip),
+                                            right_clip: rr.right_clip
+                                                + PhysicalLength::new(extra_right_clip),
+                                            ..*rr
+                                        },
+                                        line_buffer,
+                                    );
+                                }
+                            }
                         }
-                    }
-                }
-            },
-        );
+                    },
+                );
+            }
 
-        if scene.current_line < dirty_region.origin.y_length() + dirty_region.size.height_length() {
-            scene.next_line();
+            if scene.current_line.get() < bounding_box.max.y {
+                scene.next_line();
+            }
         }
+        PhysicalRegion(dirty_region)
     }
 }
 
@@ -416,7 +784,7 @@ pub fn new(
         shared_buffers: Vec<SharedBufferCommand>,
         dirty_region: DirtyRegion,
     ) -> Self {
-        let current_line = dirty_region.origin.y_length();
+        let current_line = PhysicalLength::new(dirty_region.bounding_box().min.y);
         items.retain(|i| i.pos.y_length() + i.size.height_length() > current_line);
         items.sort_unstable_by(|a, b| compare_scene_item(a, b));
         let current_items_index = items.partition_point(|i| i.pos.y_length() <= current_line);
@@ -632,9 +1000,9 @@ fn as_texture(&self) -> SceneTexture<'_> {
     }
 }
 
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug)]
 struct RoundedRectangle {
-    radius: PhysicalLength,
+    radius: PhysicalBorderRadius,
     /// the border's width
     width: PhysicalLength,
     border_color: PremultipliedRgbaColor,
@@ -661,18 +1029,19 @@ fn prepare_scene<const MAX_BUFFER_AGE: usize>(
         prepare_scene,
     );
 
-    let mut dirty_region = PhysicalRect::default();
+    let mut dirty_region = DirtyRegion::default();
     window.draw_contents(|components| {
         for (component, origin) in components {
             renderer.compute_dirty_regions(component, *origin);
         }
 
-        dirty_region = (renderer.dirty_region.to_rect().cast() * factor).round_out().cast();
+        dirty_region = to_physical_region(&renderer.dirty_region, factor);
         dirty_region = software_renderer.apply_dirty_region(dirty_region, size);
 
+        // The items are clipped to the lines and columns of each rectangle when they are drawn
         renderer.combine_clip(
-            (dirty_region.cast() / factor).cast(),
-            LogicalLength::zero(),
+            (dirty_region.bounding_box().to_rect().cast() / factor).cast(),
+            LogicalBorderRadius::default(),
             LogicalLength::zero(),
         );
         for (component, origin) in components {
@@ -710,6 +1079,8 @@ fn process_texture(&mut self, geometry: PhysicalRect, texture: SceneTexture<'sta
                 PhysicalLength::new(line),
                 &texture,
                 &mut self.buffer[line as usize * self.stride..],
+                0,
+                0,
             );
         }
     }
@@ -722,6 +1093,8 @@ fn process_shared_image_buffer(&mut self, geometry: PhysicalRect, buffer: Shared
                 PhysicalLength::new(line),
                 &texture,
                 &mut self.buffer[line as usize * self.stride..],
+                0,
+                0,
             );
         }
     }
@@ -1017,13 +1390,12 @@ fn draw_border_rectangle(&mut self, rect: Pin<&crate::items::BorderRectangle>, _
         let geom = LogicalRect::new(LogicalPoint::default(), rect.geometry().size_length());
         if self.should_draw(&geom) {
             let border = rect.border_width();
-            let radius = rect.border_radius();
+            let radius = rect.border_radii();
             // FIXME: gradients
             let color = rect.background().color();
-            if radius.get() > 0 as _ {
-                let radius = radius
-                    .min(geom.width_length() / 2 as Coord)
-                    .min(geom.height_length() / 2 as Coord);
+            if !radius.is_zero() {
+                let max_radius = geom.width_length().min(geom.height_length()) / 2 as Coord;
+                let radius = radius.map(|r| r.max(LogicalLength::zero()).min(max_radius));
                 if let Some(clipped) = geom.intersection(&self.current_state.clip) {
                     let geom2 = geom.cast() * self.scale_factor;
                     let clipped2 = clipped.cast() * self.scale_factor;
@@ -1035,7 +1407,7 @@ fn draw_border_rectangle(&mut self, rect: Pin<&crate::items::BorderRectangle>, _
                             .round()
                             .cast(),
                         RoundedRectangle {
-                            radius: (radius.cast() * self.scale_factor).cast(),
+                            radius: radius.map(|r| (r.cast() * self.scale_factor).cast()),
                             width: (border.cast() * self.scale_factor).cast(),
                             border_color: rect.border_color().color().into(),
                             inner_color: color.into(),
@@ -1101,7 +1473,7 @@ fn draw_image(&mut self, image: Pin<&crate::items::ImageItem>, _: &ItemRc) {
         let geom =
             LogicalRect::new(LogicalPoint::default(), image.as_ref().geometry().size_length());
         if self.should_draw(&geom) {
-            let source = image.source();
+            let source = image.displayed_source();
             self.draw_image_impl(
                 geom,
                 &source,
@@ -1115,7 +1487,7 @@ fn draw_image(&mut self, image: Pin<&crate::items::ImageItem>, _: &ItemRc) {
     fn draw_clipped_image(&mut self, image: Pin<&crate::items::ClippedImage>, _: &ItemRc) {
         let geom = LogicalRect::new(LogicalPoint::default(), image.geometry().size_length());
         if self.should_draw(&geom) {
-            let source = image.source();
+            let source = image.displayed_source();
 
             let source_clip_x = image.source_clip_x();
             let source_clip_y = image.source_clip_y();
@@ -1141,7 +1513,11 @@ fn draw_clipped_image(&mut self, image: Pin<&crate::items::ClippedImage>, _: &It
     }
 
     fn draw_text(&mut self, text: Pin<&crate::items::Text>, _: &ItemRc) {
-        let string = text.text();
+        let rich_text = text.rich_text_layout();
+        let string = match &rich_text {
+            Some(rich_text) => rich_text.text.as_str().into(),
+            None => text.text(),
+        };
         if string.trim().is_empty() {
             return;
         }
@@ -1156,6 +1532,8 @@ fn draw_text(&mut self, text: Pin<&crate::items::Text>, _: &ItemRc) {
 
         let color = text.color().color();
         let max_size = (geom.size.cast() * self.scale_factor).cast();
+        // The bitmap fonts don't have outlines, so the stroke is not supported
+        let (underline, strikethrough) = (text.underline(), text.strikethrough());
 
         let paragraph = TextParagraphLayout {
             string: &string,
@@ -1166,7 +1544,10 @@ fn draw_text(&mut self, text: Pin<&crate::items::Text>, _: &ItemRc) {
             vertical_alignment: text.vertical_alignment(),
             wrap: text.wrap(),
             overflow: text.overflow(),
+            elide_position: text.elide_position(),
+            max_lines: (text.max_lines() > 0).then(|| text.max_lines() as usize),
             single_line: false,
+            text_direction: text.text_direction(),
         };
 
         // Clip glyphs not only against the global clip but also against the Text's geometry to avoid drawing outside
@@ -1183,35 +1564,77 @@ fn draw_text(&mut self, text: Pin<&crate::items::Text>, _: &ItemRc) {
         paragraph.layout_lines(|glyphs, line_x, line_y| {
             let baseline_y = line_y + font.ascent();
             while let Some(positioned_glyph) = glyphs.next() {
-                let src_rect = PhysicalRect::new(
-                    PhysicalPoint::from_lengths(
-                        line_x + positioned_glyph.x + positioned_glyph.platform_glyph.x(),
-                        baseline_y
-                            - positioned_glyph.platform_glyph.y()
-                            - positioned_glyph.platform_glyph.height(),
-                    ),
-                    positioned_glyph.platform_glyph.size(),
-                )
-                .cast();
-
-                if let Some(clipped_src) = src_rect.intersection(&physical_clip) {
-                    let geometry = clipped_src.translate(offset).round();
-                    let origin = (geometry.origin - offset.round()).cast::<usize>();
-                    let actual_x = origin.x - src_rect.origin.x as usize;
-                    let actual_y = origin.y - src_rect.origin.y as usize;
-                    let stride = positioned_glyph.platform_glyph.width().get() as u16;
-                    let geometry = geometry.cast();
-                    self.processor.process_texture(
-                        geometry,
-                        SceneTexture {
-                            data: &positioned_glyph.platform_glyph.data().as_slice()
-                                [actual_x + actual_y * stride as usize..],
-                            stride,
-                            source_size: geometry.size,
-                            format: PixelFormat::AlphaMap,
-                            color,
-                        },
-                    );
+                // The bitmap fonts don't have bold variants, so bold spans use the regular glyphs
+                let style = rich_text
+                    .as_ref()
+                    .and_then(|rt| rt.style_at(positioned_glyph.text_byte_offset));
+                let color = style.and_then(|s| s.color).unwrap_or(color);
+                let underline =
+                    underline || style.map_or(false, |s| s.underline || s.link.is_some());
+                let decoration_lines = [
+                    underline.then(|| baseline_y + PhysicalLength::new(1)),
+                    strikethrough.then(|| baseline_y - font.ascent() / 3),
+                ];
+                for line_y in decoration_lines.into_iter().flatten() {
+                    let line = PhysicalRect::new(
+                        PhysicalPoint::from_lengths(line_x + positioned_glyph.x, line_y),
+                        PhysicalSize::new(
+                            positioned_glyph.platform_glyph.width().get()
+                                + positioned_glyph.platform_glyph.x().get(),
+                            1,
+                        ),
+                    )
+                    .cast();
+                    if let Some(clipped) = line.intersection(&physical_clip) {
+                        self.processor
+                            .process_rectangle(clipped.translate(offset).round().cast(), color);
+                    }
+                }
+                let glyph_origin = PhysicalPoint::from_lengths(
+                    line_x + positioned_glyph.x + positioned_glyph.platform_glyph.x(),
+                    baseline_y
+                        - positioned_glyph.platform_glyph.y()
+                        - positioned_glyph.platform_glyph.height(),
+                );
+                let glyph_size = positioned_glyph.platform_glyph.size();
+                // The bitmap fonts have no italic variant, so italic is synthesized by drawing each
+                // row of the glyph shifted to the right according to its height above the baseline
+                let italic = style.map_or(false, |s| s.italic);
+                let (rows_per_piece, piece_count) =
+                    if italic { (1, glyph_size.height) } else { (glyph_size.height, 1) };
+                for piece in 0..piece_count {
+                    let first_row = piece * rows_per_piece;
+                    let shift = if italic {
+                        ((baseline_y.get() - glyph_origin.y - first_row) as f32
+                            * crate::textlayout::ITALIC_SLANT) as i16
+                    } else {
+                        0
+                    };
+                    let src_rect = PhysicalRect::new(
+                        glyph_origin + euclid::vec2(shift, first_row),
+                        PhysicalSize::new(glyph_size.width, rows_per_piece),
+                    )
+                    .cast();
+
+                    if let Some(clipped_src) = src_rect.intersection(&physical_clip) {
+                        let geometry = clipped_src.translate(offset).round();
+                        let origin = (geometry.origin - offset.round()).cast::<usize>();
+                        let actual_x = origin.x - src_rect.origin.x as usize;
+                        let actual_y = origin.y - src_rect.origin.y as usize + first_row as usize;
+                        let stride = glyph_size.width as u16;
+                        let geometry = geometry.cast();
+                        self.processor.process_texture(
+                            geometry,
+                            SceneTexture {
+                                data: &positioned_glyph.platform_glyph.data().as_slice()
+                                    [actual_x + actual_y * stride as usize..],
+                                stride,
+                                source_size: geometry.size,
+                                format: PixelFormat::AlphaMap,
+                                color,
+                            },
+                        );
+                    }
                 }
             }
         });
@@ -1236,7 +1659,7 @@ fn draw_box_shadow(&mut self, box_shadow: Pin<&crate::items::BoxShadow>, _: &Ite
     fn combine_clip(
         &mut self,
         other: LogicalRect,
-        _radius: LogicalLength,
+        _radius: LogicalBorderRadius,
         _border_width: LogicalLength,
     ) -> bool {
         match self.current_state.clip.intersection(&other) {
@@ -1262,7 +1685,15 @@ fn translate(&mut self, distance: LogicalVector) {
     }
 
     fn rotate(&mut self, _angle_in_degrees: f32) {
-        todo!()
+        // FIXME: transformations are not supported, the items are rendered untransformed
+    }
+
+    fn scale(&mut self, _x_factor: f32, _y_factor: f32) {
+        // FIXME: transformations are not supported
+    }
+
+    fn skew(&mut self, _x_angle_in_degrees: f32, _y_angle_in_degrees: f32) {
+        // FIXME: transformations are not supported
     }
 
     fn apply_opacity(&mut self, opacity: f32) {
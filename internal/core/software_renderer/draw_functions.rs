@@ -19,6 +19,8 @@ pub(super) fn draw_texture_line(
     line: PhysicalLength,
     texture: &super::SceneTexture,
     line_buffer: &mut [impl TargetPixel],
+    extra_left_clip: i16,
+    extra_right_clip: i16,
 ) {
     let super::SceneTexture { data, format, stride, source_size, color } = *texture;
     let source_size = source_size.cast::<usize>();
@@ -26,11 +28,12 @@ pub(super) fn draw_texture_line(
     let bpp = format.bpp();
     let y = (line - span.origin.y_length()).cast::<usize>();
     let y_pos = (y.get() * source_size.height / span_size.height) * stride as usize;
-    for (x, pix) in line_buffer
-        [span.origin.x as usize..(span.origin.x_length() + span.size.width_length()).get() as usize]
+    for (x, pix) in line_buffer[(span.origin.x + extra_left_clip) as usize
+        ..(span.origin.x + span.size.width - extra_right_clip) as usize]
         .iter_mut()
         .enumerate()
     {
+        let x = x + extra_left_clip as usize;
         let pos = y_pos + (x * source_size.width / span_size.width) * bpp;
         let c = match format {
             PixelFormat::Rgb => {
@@ -133,8 +136,10 @@ fn mul(self, rhs: Self) -> Self::Output {
     let rev = |x: Shifted| {
         (Shifted::new(span.size.width) + Shifted::new(rr.right_clip.get())).saturating_sub(x)
     };
-    let (x1, x2, x3, x4) = if y < rr.radius {
-        let r = Shifted::new(rr.radius.get());
+    // Returns the coordinates of the curve of a corner of the given radius, from the edge of the
+    // rectangle, when the line is at `y` from the top or bottom edge
+    let corner = |radius: PhysicalLength, y: PhysicalLength| {
+        let r = Shifted::new(radius.get());
         // `y` is how far away from the center of the circle the current line is.
         let y = r - Shifted::new(y.get());
         // Circle equation: x = √(r² - y²)
@@ -145,9 +150,20 @@ fn mul(self, rhs: Self) -> Self::Output {
         let x4 = r - (r2 * r2).saturating_sub(y * y).sqrt();
         let x3 = r - (r2 * r2).saturating_sub((y - ONE) * (y - ONE)).sqrt();
         (x1, x2, x3, x4)
-    } else {
-        (Shifted(0), Shifted(0), border, border)
     };
+    // Each side of the line is in the top corner, the bottom corner, or between them
+    let side = |top_radius: PhysicalLength, bottom_radius: PhysicalLength| {
+        if y1 < top_radius {
+            corner(top_radius, y1)
+        } else if y2 < bottom_radius {
+            corner(bottom_radius, y2)
+        } else {
+            (Shifted(0), Shifted(0), border, border)
+        }
+    };
+    let (x1, x2, x3, x4) = side(rr.radius.top_left, rr.radius.bottom_left);
+    // The coordinates of the right side, from the right edge
+    let (rx1, rx2, rx3, rx4) = side(rr.radius.top_right, rr.radius.bottom_right);
     anti_alias(
         x1.saturating_sub(Shifted::new(rr.left_clip.get())),
         x2.saturating_sub(Shifted::new(rr.left_clip.get())),
@@ -169,7 +185,7 @@ fn mul(self, rhs: Self) -> Self::Output {
         // up or down border (x2 .. x2)
         let l = x2.ceil().saturating_sub(rr.left_clip.get() as u32).min(span.size.width as u32)
             as usize;
-        let r = rev(x2).floor().min(span.size.width as u32) as usize;
+        let r = rev(rx2).floor().min(span.size.width as u32) as usize;
         if l < r {
             TargetPixel::blend_slice(&mut line_buffer[pos_x + l..pos_x + r], rr.border_color)
         }
@@ -207,7 +223,7 @@ fn mul(self, rhs: Self) -> Self::Output {
             // 5. inside (x4 .. x4)
             let begin =
                 x4.ceil().saturating_sub(rr.left_clip.get() as u32).min(span.size.width as u32);
-            let end = rev(x4).floor().min(span.size.width as u32);
+            let end = rev(rx4).floor().min(span.size.width as u32);
             if begin < end {
                 TargetPixel::blend_slice(
                     &mut line_buffer[pos_x + begin as usize..pos_x + end as usize],
@@ -217,7 +233,7 @@ fn mul(self, rhs: Self) -> Self::Output {
         }
         if border > Shifted(0) {
             // 6. border anti-aliasing: x4..x3
-            anti_alias(rev(x4), rev(x3), &mut |x, cov| {
+            anti_alias(rev(rx4), rev(rx3), &mut |x, cov| {
                 if x >= span.size.width as usize {
                     return;
                 }
@@ -225,16 +241,16 @@ fn mul(self, rhs: Self) -> Self::Output {
                 line_buffer[pos_x + x].blend(col)
             });
             // 7. border x3 .. x2
-            if ONE + x2 <= x3 {
+            if ONE + rx2 <= rx3 {
                 TargetPixel::blend_slice(
-                    &mut line_buffer[pos_x + rev(x3).ceil().min(span.size.width as u32) as usize
-                        ..pos_x + rev(x2).floor().min(span.size.width as u32) as usize as usize],
+                    &mut line_buffer[pos_x + rev(rx3).ceil().min(span.size.width as u32) as usize
+                        ..pos_x + rev(rx2).floor().min(span.size.width as u32) as usize as usize],
                     rr.border_color,
                 )
             }
         }
     }
-    anti_alias(rev(x2), rev(x1), &mut |x, cov| {
+    anti_alias(rev(rx2), rev(rx1), &mut |x, cov| {
         if x >= span.size.width as usize {
             return;
         }
@@ -445,6 +461,187 @@ fn from(p: Rgb565Pixel) -> Self {
     }
 }
 
+/// A 16bit pixel that has 5 red bits, 6 green bits and 5 blue bits, stored in big-endian byte order.
+///
+/// Use this instead of [`Rgb565Pixel`] for displays that expect the most significant byte first,
+/// so that the buffer can be sent to the display without swapping the bytes.
+#[repr(transparent)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub struct Rgb565SwappedPixel(pub u16);
+
+impl TargetPixel for Rgb565SwappedPixel {
+    fn blend(&mut self, color: PremultipliedRgbaColor) {
+        let mut pix = Rgb565Pixel::from(*self);
+        pix.blend(color);
+        *self = pix.into();
+    }
+
+    fn from_rgb(r: u8, g: u8, b: u8) -> Self {
+        Rgb565Pixel::from_rgb(r, g, b).into()
+    }
+}
+
+impl From<Rgb565Pixel> for Rgb565SwappedPixel {
+    fn from(p: Rgb565Pixel) -> Self {
+        Self(p.0.to_be())
+    }
+}
+
+impl From<Rgb565SwappedPixel> for Rgb565Pixel {
+    fn from(p: Rgb565SwappedPixel) -> Self {
+        Self(u16::from_be(p.0))
+    }
+}
+
+/// An 8bit pixel that has 3 red bits, 3 green bits and 2 blue bits
+#[repr(transparent)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub struct Rgb332Pixel(pub u8);
+
+impl Rgb332Pixel {
+    /// Return the red component as a u8, between 0 and 255
+    fn red(self) -> u8 {
+        let r = self.0 >> 5;
+        (r << 5) | (r << 2) | (r >> 1)
+    }
+    /// Return the green component as a u8, between 0 and 255
+    fn green(self) -> u8 {
+        let g = (self.0 >> 2) & 0b111;
+        (g << 5) | (g << 2) | (g >> 1)
+    }
+    /// Return the blue component as a u8, between 0 and 255
+    fn blue(self) -> u8 {
+        let b = self.0 & 0b11;
+        (b << 6) | (b << 4) | (b << 2) | b
+    }
+}
+
+impl TargetPixel for Rgb332Pixel {
+    fn blend(&mut self, color: PremultipliedRgbaColor) {
+        let a = (u8::MAX - color.alpha) as u16;
+        *self = Self::from_rgb(
+            (self.red() as u16 * a / 255) as u8 + color.red,
+            (self.green() as u16 * a / 255) as u8 + color.green,
+            (self.blue() as u16 * a / 255) as u8 + color.blue,
+        );
+    }
+
+    fn from_rgb(r: u8, g: u8, b: u8) -> Self {
+        Self((r & 0b11100000) | ((g & 0b11100000) >> 3) | (b >> 6))
+    }
+}
+
+impl From<Rgb332Pixel> for Rgb8Pixel {
+    fn from(p: Rgb332Pixel) -> Self {
+        Rgb8Pixel { r: p.red(), g: p.green(), b: p.blue() }
+    }
+}
+
+/// A 32bit pixel with the blue, green, red and alpha components in that order in memory.
+///
+/// This is the layout of the `XRGB8888` and `ARGB8888` formats of many frame buffers on
+/// little-endian systems. The alpha component is set to 255 by the renderer.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub struct Bgra8888Pixel {
+    /// The blue component
+    pub blue: u8,
+    /// The green component
+    pub green: u8,
+    /// The red component
+    pub red: u8,
+    /// The alpha component
+    pub alpha: u8,
+}
+
+impl TargetPixel for Bgra8888Pixel {
+    fn blend(&mut self, color: PremultipliedRgbaColor) {
+        let a = (u8::MAX - color.alpha) as u16;
+        self.red = (self.red as u16 * a / 255) as u8 + color.red;
+        self.green = (self.green as u16 * a / 255) as u8 + color.green;
+        self.blue = (self.blue as u16 * a / 255) as u8 + color.blue;
+    }
+
+    fn from_rgb(red: u8, green: u8, blue: u8) -> Self {
+        Self { blue, green, red, alpha: 255 }
+    }
+}
+
+/// The format of the pixels of a frame buffer passed as bytes to
+/// [`SoftwareRenderer::render_with_format()`](super::SoftwareRenderer::render_with_format),
+/// for displays whose format is only known at run-time.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TargetPixelFormat {
+    /// 16 bits per pixel, in the native byte order, like [`Rgb565Pixel`]
+    Rgb565,
+    /// 16 bits per pixel, with the most significant byte first, like [`Rgb565SwappedPixel`]
+    Rgb565Swapped,
+    /// 8 bits per pixel, like [`Rgb332Pixel`]
+    Rgb332,
+    /// 24 bits per pixel, with the red, green and blue components in that order
+    Rgb888,
+    /// 32 bits per pixel, like [`Bgra8888Pixel`]
+    Bgra8888,
+}
+
+impl TargetPixelFormat {
+    /// Returns the number of bytes of a pixel in this format
+    pub fn bytes_per_pixel(self) -> usize {
+        match self {
+            Self::Rgb332 => 1,
+            Self::Rgb565 | Self::Rgb565Swapped => 2,
+            Self::Rgb888 => 3,
+            Self::Bgra8888 => 4,
+        }
+    }
+
+    /// Returns the number of bits of the red, green and blue components
+    fn component_bits(self) -> (u32, u32, u32) {
+        match self {
+            Self::Rgb332 => (3, 3, 2),
+            Self::Rgb565 | Self::Rgb565Swapped => (5, 6, 5),
+            Self::Rgb888 | Self::Bgra8888 => (8, 8, 8),
+        }
+    }
+
+    /// Reads the pixel stored in `bytes`, which has `bytes_per_pixel()` bytes
+    pub(super) fn read(self, bytes: &[u8]) -> Rgb8Pixel {
+        match self {
+            Self::Rgb565 => Rgb565Pixel(u16::from_ne_bytes([bytes[0], bytes[1]])).into(),
+            Self::Rgb565Swapped => Rgb565Pixel(u16::from_be_bytes([bytes[0], bytes[1]])).into(),
+            Self::Rgb332 => Rgb332Pixel(bytes[0]).into(),
+            Self::Rgb888 => Rgb8Pixel { r: bytes[0], g: bytes[1], b: bytes[2] },
+            Self::Bgra8888 => Rgb8Pixel { r: bytes[2], g: bytes[1], b: bytes[0] },
+        }
+    }
+
+    /// Stores the pixel in `bytes`, which has `bytes_per_pixel()` bytes
+    pub(super) fn write(self, bytes: &mut [u8], pix: Rgb8Pixel) {
+        match self {
+            Self::Rgb565 => bytes.copy_from_slice(&Rgb565Pixel::from(pix).0.to_ne_bytes()),
+            Self::Rgb565Swapped => bytes.copy_from_slice(&Rgb565Pixel::from(pix).0.to_be_bytes()),
+            Self::Rgb332 => bytes[0] = Rgb332Pixel::from_rgb(pix.r, pix.g, pix.b).0,
+            Self::Rgb888 => bytes.copy_from_slice(&[pix.r, pix.g, pix.b]),
+            Self::Bgra8888 => bytes.copy_from_slice(&[pix.b, pix.g, pix.r, 255]),
+        }
+    }
+}
+
+/// Adds the noise of a 4x4 ordered dithering to a pixel at the given position, before it is
+/// converted to `format`. The components are truncated by the conversion, so a color that falls
+/// between two levels of the format is spread over the neighbouring pixels instead of showing bands.
+pub(super) fn dither(pix: Rgb8Pixel, format: TargetPixelFormat, x: usize, y: usize) -> Rgb8Pixel {
+    const BAYER: [[u16; 4]; 4] = [[0, 8, 2, 10], [12, 4, 14, 6], [3, 11, 1, 9], [15, 7, 13, 5]];
+    let threshold = BAYER[y % 4][x % 4];
+    let component = |value: u8, bits: u32| {
+        let step = 1u16 << (8 - bits);
+        (value as u16 + threshold * step / 16).min(u8::MAX as u16) as u8
+    };
+    let (r, g, b) = format.component_bits();
+    Rgb8Pixel { r: component(pix.r, r), g: component(pix.g, g), b: component(pix.b, b) }
+}
+
 #[test]
 fn rgb565() {
     let pix565 = Rgb565Pixel::from_rgb(0xff, 0x25, 0);
@@ -455,3 +652,66 @@ fn rgb565() {
     let pix888: Rgb8Pixel = pix565.into();
     assert_eq!(pix565, pix888.into());
 }
+
+#[test]
+fn rgb565_swapped() {
+    let pix = Rgb565SwappedPixel::from_rgb(0xff, 0x25, 0);
+    assert_eq!(pix.0.to_ne_bytes(), Rgb565Pixel::from_rgb(0xff, 0x25, 0).0.to_be_bytes());
+    let mut blended = pix;
+    blended.blend(PremultipliedRgbaColor { red: 0, green: 0, blue: 0, alpha: 0 });
+    assert_eq!(blended, pix);
+}
+
+#[test]
+fn rgb332() {
+    let pix = Rgb332Pixel::from_rgb(0xff, 0x25, 0xc0);
+    assert_eq!(pix.0, 0b111_001_11);
+    let pix888: Rgb8Pixel = pix.into();
+    assert_eq!(pix888, Rgb8Pixel { r: 0xff, g: 0x24, b: 0xff });
+    assert_eq!(Rgb332Pixel::from_rgb(pix888.r, pix888.g, pix888.b), pix);
+}
+
+#[test]
+fn target_pixel_format() {
+    let pix = Rgb8Pixel { r: 0xff, g: 0x24, b: 0 };
+    for format in [
+        TargetPixelFormat::Rgb565,
+        TargetPixelFormat::Rgb565Swapped,
+        TargetPixelFormat::Rgb332,
+        TargetPixelFormat::Rgb888,
+        TargetPixelFormat::Bgra8888,
+    ] {
+        let (mut bytes, mut again) = ([0; 4], [0; 4]);
+        let len = format.bytes_per_pixel();
+        format.write(&mut bytes[..len], pix);
+        format.write(&mut again[..len], format.read(&bytes[..len]));
+        assert_eq!(bytes, again, "{:?}", format);
+    }
+    let mut bytes = [0; 2];
+    TargetPixelFormat::Rgb565Swapped.write(&mut bytes, pix);
+    assert_eq!(bytes, Rgb565Pixel::from(pix).0.to_be_bytes());
+    let mut bytes = [0; 4];
+    TargetPixelFormat::Bgra8888.write(&mut bytes, pix);
+    assert_eq!(bytes, [0, 0x24, 0xff, 0xff]);
+}
+
+#[test]
+fn rgb565_dithering() {
+    // 0x44 is halfway between the levels 0x40 and 0x48 of the red and blue components in RGB565
+    let pix = Rgb8Pixel { r: 0x44, g: 0x44, b: 0x44 };
+    let mut red_sum = 0;
+    for y in 0..4 {
+        for x in 0..4 {
+            let dithered: Rgb565Pixel = dither(pix, TargetPixelFormat::Rgb565, x, y).into();
+            let dithered: Rgb8Pixel = dithered.into();
+            assert!(dithered.r == 0x40 || dithered.r == 0x48);
+            red_sum += dithered.r as u32;
+        }
+    }
+    assert_eq!(red_sum / 16, 0x44);
+    // The colors that are exactly represented are not changed
+    let black = dither(Rgb8Pixel { r: 0, g: 0, b: 0 }, TargetPixelFormat::Rgb565, 3, 1);
+    assert_eq!(Rgb565Pixel::from(black), Rgb565Pixel(0));
+    let white = dither(Rgb8Pixel { r: 0xff, g: 0xff, b: 0xff }, TargetPixelFormat::Rgb565, 3, 1);
+    assert_eq!(Rgb565Pixel::from(white), Rgb565Pixel(0xffff));
+}
@@ -154,15 +154,17 @@ pub fn match_font(request: &FontRequest, scale_factor: ScaleFactor) -> PixelFont
             .first()
             .expect("The software renderer requires enabling the `EmbedForSoftwareRenderer` option when compiling slint files.");
 
-        request.family.as_ref().map_or(fallback_font, |requested_family| {
-            fonts
-                .iter()
-                .find(|bitmap_font| {
+        // Use the first family of the list that is available. There is no per glyph fallback.
+        request
+            .families()
+            .find_map(|requested_family| {
+                fonts.iter().find(|bitmap_font| {
                     core::str::from_utf8(bitmap_font.family_name.as_slice()).unwrap()
-                        == requested_family.as_str()
+                        == requested_family
                 })
-                .unwrap_or(&fallback_font)
-        })
+            })
+            .copied()
+            .unwrap_or(fallback_font)
     });
 
     let requested_pixel_size: PhysicalLength =
@@ -186,7 +188,10 @@ pub fn text_layout_for_font<'a>(
     let letter_spacing =
         font_request.letter_spacing.map(|spacing| (spacing.cast() * scale_factor).cast());
 
-    TextLayout { font, letter_spacing }
+    let word_spacing =
+        font_request.word_spacing.map(|spacing| (spacing.cast() * scale_factor).cast());
+
+    TextLayout { font, letter_spacing, word_spacing }
 }
 
 pub fn register_bitmap_font(font_data: &'static BitmapFont) {
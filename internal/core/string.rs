@@ -343,6 +343,16 @@ pub extern "C" fn slint_shared_string_bytes(ss: &SharedString) -> *const c_char
         core::ptr::write(out, str);
     }
 
+    /// Create a lowercase copy of the string.
+    /// The resulting structure must be passed to slint_shared_string_drop
+    #[no_mangle]
+    pub unsafe extern "C" fn slint_shared_string_to_lowercase(
+        out: *mut SharedString,
+        ss: &SharedString,
+    ) {
+        core::ptr::write(out, SharedString::from(ss.as_str().to_lowercase()));
+    }
+
     #[test]
     fn test_slint_shared_string_from_number() {
         unsafe {
@@ -25,7 +25,10 @@
 
 use euclid::num::{One, Zero};
 
-use crate::items::{TextHorizontalAlignment, TextOverflow, TextVerticalAlignment, TextWrap};
+use crate::items::{
+    TextDirection, TextElidePosition, TextHorizontalAlignment, TextOverflow, TextVerticalAlignment,
+    TextWrap,
+};
 
 #[cfg(feature = "unicode-linebreak")]
 mod linebreak_unicode;
@@ -37,6 +40,8 @@
 #[cfg(not(feature = "unicode-linebreak"))]
 use linebreak_simple::{BreakOpportunity, LineBreakIterator};
 
+mod bidi;
+pub use bidi::is_rtl;
 mod fragments;
 mod glyphclusters;
 mod shaping;
@@ -46,11 +51,15 @@
 mod linebreaker;
 pub use linebreaker::TextLine;
 
+mod rich_text;
+pub use rich_text::{parse_rich_text, RichText, RichTextSpan, RichTextStyle, ITALIC_SLANT};
+
 pub use linebreaker::TextLineBreaker;
 
 pub struct TextLayout<'a, Font: AbstractFont> {
     pub font: &'a Font,
     pub letter_spacing: Option<<Font as TextShaper>::Length>,
+    pub word_spacing: Option<<Font as TextShaper>::Length>,
 }
 
 impl<'a, Font: AbstractFont> TextLayout<'a, Font> {
@@ -82,6 +91,8 @@ pub struct PositionedGlyph<'a, Length, PlatformGlyphData> {
     pub x: Length,
     pub y: Length,
     pub platform_glyph: &'a PlatformGlyphData,
+    /// The byte offset in the laid out string of the character that produced this glyph
+    pub text_byte_offset: usize,
 }
 
 pub struct TextParagraphLayout<'a, Font: AbstractFont> {
@@ -93,7 +104,12 @@ pub struct TextParagraphLayout<'a, Font: AbstractFont> {
     pub vertical_alignment: TextVerticalAlignment,
     pub wrap: TextWrap,
     pub overflow: TextOverflow,
+    pub elide_position: TextElidePosition,
+    /// The maximum number of lines to lay out, or None if the number of lines is not limited
+    pub max_lines: Option<usize>,
     pub single_line: bool,
+    /// The base direction of the paragraph, used to order left-to-right and right-to-left runs
+    pub text_direction: TextDirection,
 }
 
 impl<'a, Font: AbstractFont> TextParagraphLayout<'a, Font> {
@@ -118,6 +134,8 @@ pub fn layout_lines(
             self.max_width - elide_glyph.as_ref().map_or(Font::Length::zero(), |g| g.advance);
 
         let shape_buffer = ShapeBuffer::new(&self.layout, self.string);
+        #[cfg(feature = "unicode-bidi")]
+        let bidi_paragraphs = bidi::BidiParagraphs::new(self.string, self.text_direction);
 
         let new_line_break_iter = || {
             TextLineBreaker::<Font>::new(
@@ -132,8 +150,10 @@ pub fn layout_lines(
             if self.single_line {
                 self.layout.font.height()
             } else {
-                text_lines = Some(new_line_break_iter().collect::<Vec<_>>());
-                self.layout.font.height() * (text_lines.as_ref().unwrap().len() as i16).into()
+                let lines = new_line_break_iter().collect::<Vec<_>>();
+                let line_count = self.max_lines.map_or(lines.len(), |max| lines.len().min(max));
+                text_lines = Some(lines);
+                self.layout.font.height() * (line_count as i16).into()
             }
         };
 
@@ -147,57 +167,125 @@ pub fn layout_lines(
 
         let mut y = baseline_y;
 
-        let mut process_line =
-            |line: &TextLine<Font::Length>,
-             glyphs: &[Glyph<Font::Length, Font::PlatformGlyphData>]| {
-                let x = match self.horizontal_alignment {
-                    TextHorizontalAlignment::Left => Font::Length::zero(),
-                    TextHorizontalAlignment::Center => {
-                        self.max_width / two
-                            - euclid::approxord::min(self.max_width, line.text_width) / two
-                    }
-                    TextHorizontalAlignment::Right => {
-                        self.max_width - euclid::approxord::min(self.max_width, line.text_width)
-                    }
-                };
-
-                let mut elide_glyph = elide_glyph.as_ref().clone();
-
-                let glyph_it = glyphs[line.glyph_range.clone()].iter();
-                let mut glyph_x = Font::Length::zero();
-                let mut positioned_glyph_it = glyph_it.map_while(|glyph| {
-                    // TODO: cut off at grapheme boundaries
-                    if glyph_x > max_width_without_elision {
-                        if let Some(elide_glyph) = elide_glyph.take() {
-                            return Some(PositionedGlyph {
+        let mut process_line = |line: &TextLine<Font::Length>,
+                                glyphs: &[Glyph<Font::Length, Font::PlatformGlyphData>],
+                                force_elision: bool| {
+            let x = match self.horizontal_alignment {
+                TextHorizontalAlignment::Left => Font::Length::zero(),
+                TextHorizontalAlignment::Center => {
+                    self.max_width / two
+                        - euclid::approxord::min(self.max_width, line.text_width) / two
+                }
+                TextHorizontalAlignment::Right => {
+                    self.max_width - euclid::approxord::min(self.max_width, line.text_width)
+                }
+            };
+
+            let glyphs = &glyphs[line.glyph_range.clone()];
+            #[cfg(feature = "unicode-bidi")]
+            let reordered_glyphs;
+            #[cfg(feature = "unicode-bidi")]
+            let glyphs = if let Some(bidi_paragraphs) = bidi_paragraphs.as_ref() {
+                let mut glyphs = glyphs.to_vec();
+                bidi_paragraphs.reorder_line(line.byte_range.start, &mut glyphs);
+                reordered_glyphs = glyphs;
+                &reordered_glyphs[..]
+            } else {
+                glyphs
+            };
+            let elide_glyph = elide_glyph.as_ref();
+
+            match elide_glyph {
+                Some(elide_glyph)
+                    if force_elision
+                        || (self.elide_position != TextElidePosition::End
+                            && line.text_width > self.max_width) =>
+                {
+                    let (prefix_end, suffix_start) = elided_glyph_ranges::<Font>(
+                        glyphs,
+                        max_width_without_elision,
+                        if force_elision { TextElidePosition::End } else { self.elide_position },
+                    );
+                    let elide_offset = glyphs
+                        .get(prefix_end)
+                        .or(glyphs.last())
+                        .map_or(0, |glyph| glyph.text_byte_offset);
+                    let mut glyph_x = Font::Length::zero();
+                    let mut positioned_glyph_it = glyphs[..prefix_end]
+                        .iter()
+                        .map(Some)
+                        .chain(core::iter::once(None))
+                        .chain(glyphs[suffix_start..].iter().map(Some))
+                        .map(|glyph| {
+                            let (platform_glyph, advance, text_byte_offset) = match glyph {
+                                Some(glyph) => {
+                                    (&glyph.platform_glyph, glyph.advance, glyph.text_byte_offset)
+                                }
+                                None => {
+                                    (&elide_glyph.platform_glyph, elide_glyph.advance, elide_offset)
+                                }
+                            };
+                            let positioned_glyph = PositionedGlyph {
                                 x: glyph_x,
                                 y: Font::Length::zero(),
-                                platform_glyph: &elide_glyph.platform_glyph,
-                            });
-                        } else {
-                            return None;
+                                platform_glyph,
+                                text_byte_offset,
+                            };
+                            glyph_x += advance;
+                            positioned_glyph
+                        });
+                    line_callback(&mut positioned_glyph_it, x, y);
+                }
+                _ => {
+                    let mut elide_glyph = elide_glyph.clone();
+                    let mut glyph_x = Font::Length::zero();
+                    let mut positioned_glyph_it = glyphs.iter().map_while(|glyph| {
+                        // TODO: cut off at grapheme boundaries
+                        if glyph_x > max_width_without_elision {
+                            if let Some(elide_glyph) = elide_glyph.take() {
+                                return Some(PositionedGlyph {
+                                    x: glyph_x,
+                                    y: Font::Length::zero(),
+                                    platform_glyph: &elide_glyph.platform_glyph,
+                                    text_byte_offset: glyph.text_byte_offset,
+                                });
+                            } else {
+                                return None;
+                            }
                         }
-                    }
-                    let positioned_glyph = PositionedGlyph {
-                        x: glyph_x,
-                        y: Font::Length::zero(),
-                        platform_glyph: &glyph.platform_glyph,
-                    };
-                    glyph_x += glyph.advance;
-                    Some(positioned_glyph)
-                });
-
-                line_callback(&mut positioned_glyph_it, x, y);
-                y += self.layout.font.height();
-            };
-
-        if let Some(lines_vec) = text_lines.take() {
-            for line in lines_vec {
-                process_line(&line, &shape_buffer.glyphs);
+                        let positioned_glyph = PositionedGlyph {
+                            x: glyph_x,
+                            y: Font::Length::zero(),
+                            platform_glyph: &glyph.platform_glyph,
+                            text_byte_offset: glyph.text_byte_offset,
+                        };
+                        glyph_x += glyph.advance;
+                        Some(positioned_glyph)
+                    });
+                    line_callback(&mut positioned_glyph_it, x, y);
+                }
             }
-        } else {
-            for line in new_line_break_iter() {
-                process_line(&line, &shape_buffer.glyphs);
+            y += self.layout.font.height();
+        };
+
+        let mut lines_vec_iter;
+        let mut line_break_iter;
+        let lines: &mut dyn Iterator<Item = TextLine<Font::Length>> =
+            if let Some(lines_vec) = text_lines.take() {
+                lines_vec_iter = lines_vec.into_iter();
+                &mut lines_vec_iter
+            } else {
+                line_break_iter = new_line_break_iter();
+                &mut line_break_iter
+            };
+        let mut lines = lines.peekable();
+        let mut line_count = 0;
+        while let Some(line) = lines.next() {
+            line_count += 1;
+            let last_allowed_line = self.max_lines.map_or(false, |max| line_count >= max);
+            process_line(&line, &shape_buffer.glyphs, last_allowed_line && lines.peek().is_some());
+            if last_allowed_line {
+                break;
             }
         }
 
@@ -205,6 +293,37 @@ pub fn layout_lines(
     }
 }
 
+/// Returns the number of glyphs to keep at the start of the line, and the index of the first glyph
+/// to keep at the end of the line, such that together they fit in `available_width`.
+fn elided_glyph_ranges<Font: AbstractFont>(
+    glyphs: &[Glyph<Font::Length, Font::PlatformGlyphData>],
+    available_width: Font::Length,
+    position: TextElidePosition,
+) -> (usize, usize) {
+    let two = Font::LengthPrimitive::one() + Font::LengthPrimitive::one();
+    let prefix_width = match position {
+        TextElidePosition::End => available_width,
+        TextElidePosition::Start => Font::Length::zero(),
+        TextElidePosition::Middle => available_width / two,
+    };
+    let mut width = Font::Length::zero();
+    let mut prefix_end = 0;
+    while prefix_end < glyphs.len() && width + glyphs[prefix_end].advance <= prefix_width {
+        width += glyphs[prefix_end].advance;
+        prefix_end += 1;
+    }
+    let mut suffix_start = glyphs.len();
+    if position != TextElidePosition::End {
+        while suffix_start > prefix_end
+            && width + glyphs[suffix_start - 1].advance <= available_width
+        {
+            width += glyphs[suffix_start - 1].advance;
+            suffix_start -= 1;
+        }
+    }
+    (prefix_end, suffix_start)
+}
+
 #[test]
 fn test_no_linebreak_opportunity_at_eot() {
     let mut it = LineBreakIterator::new("Hello World");
@@ -282,14 +401,17 @@ fn test_elision() {
 
     let paragraph = TextParagraphLayout {
         string: text,
-        layout: TextLayout { font: &font, letter_spacing: None },
+        layout: TextLayout { font: &font, letter_spacing: None, word_spacing: None },
         max_width: 13. * 10.,
         max_height: 10.,
         horizontal_alignment: TextHorizontalAlignment::Left,
         vertical_alignment: TextVerticalAlignment::Top,
         wrap: TextWrap::NoWrap,
         overflow: TextOverflow::Elide,
+        elide_position: TextElidePosition::End,
+        max_lines: None,
         single_line: true,
+        text_direction: TextDirection::Auto,
     };
     paragraph.layout_lines(|glyphs, _, _| {
         lines.push(
@@ -305,6 +427,64 @@ fn test_elision() {
     debug_assert_eq!(rendered_text, "This is a lon…")
 }
 
+#[cfg(test)]
+fn layout_with_fixed_font(
+    text: &str,
+    max_width: f32,
+    wrap: TextWrap,
+    elide_position: TextElidePosition,
+    max_lines: Option<usize>,
+) -> Vec<String> {
+    let font = FixedTestFont;
+    let paragraph = TextParagraphLayout {
+        string: text,
+        layout: TextLayout { font: &font, letter_spacing: None, word_spacing: None },
+        max_width,
+        max_height: 100.,
+        horizontal_alignment: TextHorizontalAlignment::Left,
+        vertical_alignment: TextVerticalAlignment::Top,
+        wrap,
+        overflow: TextOverflow::Elide,
+        elide_position,
+        max_lines,
+        single_line: false,
+        text_direction: TextDirection::Auto,
+    };
+    let mut lines = Vec::new();
+    paragraph.layout_lines(|glyphs, _, _| {
+        lines.push(glyphs.map(|glyph| glyph.platform_glyph.char.unwrap()).collect::<String>());
+    });
+    lines
+}
+
+#[test]
+fn test_elide_position() {
+    let text = "0123456789";
+    assert_eq!(
+        layout_with_fixed_font(text, 60., TextWrap::NoWrap, TextElidePosition::Start, None),
+        vec!["…56789"]
+    );
+    assert_eq!(
+        layout_with_fixed_font(text, 60., TextWrap::NoWrap, TextElidePosition::Middle, None),
+        vec!["01…789"]
+    );
+    assert_eq!(
+        layout_with_fixed_font(text, 100., TextWrap::NoWrap, TextElidePosition::Middle, None),
+        vec!["0123456789"]
+    );
+}
+
+#[test]
+fn test_max_lines() {
+    let text = "Hello World and all";
+    let lines =
+        layout_with_fixed_font(text, 60., TextWrap::WordWrap, TextElidePosition::End, Some(2));
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[1], "World…");
+    let lines = layout_with_fixed_font(text, 60., TextWrap::WordWrap, TextElidePosition::End, None);
+    assert_eq!(lines.len(), 4);
+}
+
 #[test]
 fn test_exact_fit() {
     let font = FixedTestFont;
@@ -314,14 +494,17 @@ fn test_exact_fit() {
 
     let paragraph = TextParagraphLayout {
         string: text,
-        layout: TextLayout { font: &font, letter_spacing: None },
+        layout: TextLayout { font: &font, letter_spacing: None, word_spacing: None },
         max_width: 4. * 10.,
         max_height: 10.,
         horizontal_alignment: TextHorizontalAlignment::Left,
         vertical_alignment: TextVerticalAlignment::Top,
         wrap: TextWrap::NoWrap,
         overflow: TextOverflow::Elide,
+        elide_position: TextElidePosition::End,
+        max_lines: None,
         single_line: true,
+        text_direction: TextDirection::Auto,
     };
     paragraph.layout_lines(|glyphs, _, _| {
         lines.push(
@@ -346,14 +529,17 @@ fn test_no_line_separators_characters_rendered() {
 
     let paragraph = TextParagraphLayout {
         string: text,
-        layout: TextLayout { font: &font, letter_spacing: None },
+        layout: TextLayout { font: &font, letter_spacing: None, word_spacing: None },
         max_width: 13. * 10.,
         max_height: 10.,
         horizontal_alignment: TextHorizontalAlignment::Left,
         vertical_alignment: TextVerticalAlignment::Top,
         wrap: TextWrap::NoWrap,
         overflow: TextOverflow::Clip,
+        elide_position: TextElidePosition::End,
+        max_lines: None,
         single_line: true,
+        text_direction: TextDirection::Auto,
     };
     paragraph.layout_lines(|glyphs, _, _| {
         lines.push(
@@ -0,0 +1,135 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+//! Support for bidirectional text, based on the unicode bidi algorithm.
+
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use super::Glyph;
+use crate::items::TextDirection;
+
+/// Returns true if the base direction of the given text is right-to-left. With `TextDirection::Auto`,
+/// the direction is the one of the first character with a strong direction.
+pub fn is_rtl(text: &str, direction: TextDirection) -> bool {
+    match direction {
+        TextDirection::Ltr => false,
+        TextDirection::Rtl => true,
+        TextDirection::Auto => {
+            #[cfg(feature = "unicode-bidi")]
+            for ch in text.chars() {
+                use unicode_bidi::BidiClass;
+                match unicode_bidi::bidi_class(ch) {
+                    BidiClass::L => return false,
+                    BidiClass::R | BidiClass::AL => return true,
+                    _ => {}
+                }
+            }
+            #[cfg(not(feature = "unicode-bidi"))]
+            let _ = text;
+            false
+        }
+    }
+}
+
+/// The result of the unicode bidi algorithm for a text, used to reorder the glyphs of each
+/// line from logical to visual order.
+#[cfg(feature = "unicode-bidi")]
+pub struct BidiParagraphs<'a> {
+    text: &'a str,
+    bidi_info: unicode_bidi::BidiInfo<'a>,
+}
+
+#[cfg(feature = "unicode-bidi")]
+impl<'a> BidiParagraphs<'a> {
+    /// Runs the bidi algorithm on the text. Returns None if the text doesn't need any reordering,
+    /// because it only contains left-to-right text.
+    pub fn new(text: &'a str, direction: TextDirection) -> Option<Self> {
+        let level = match direction {
+            TextDirection::Auto => None,
+            TextDirection::Ltr => Some(unicode_bidi::Level::ltr()),
+            TextDirection::Rtl => Some(unicode_bidi::Level::rtl()),
+        };
+        let bidi_info = unicode_bidi::BidiInfo::new(text, level);
+        if !bidi_info.has_rtl() {
+            return None;
+        }
+        Some(Self { text, bidi_info })
+    }
+
+    /// Reorders the glyphs of the line that starts at the given byte offset from logical order to
+    /// visual order, following the rules L1 and L2 of the unicode bidi algorithm.
+    pub fn reorder_line<Length: Clone, PlatformGlyphData: Clone>(
+        &self,
+        line_start: usize,
+        glyphs: &mut [Glyph<Length, PlatformGlyphData>],
+    ) {
+        let last_glyph_offset = match glyphs.last() {
+            Some(glyph) => glyph.text_byte_offset,
+            None => return,
+        };
+        let paragraph = match self
+            .bidi_info
+            .paragraphs
+            .iter()
+            .find(|paragraph| paragraph.range.contains(&line_start))
+        {
+            Some(paragraph) => paragraph,
+            None => return,
+        };
+        let line_end = self.text[last_glyph_offset..]
+            .chars()
+            .next()
+            .map_or(last_glyph_offset, |ch| last_glyph_offset + ch.len_utf8());
+        let line: Range<usize> = line_start..line_end.min(paragraph.range.end);
+        let levels = self.bidi_info.reordered_levels(paragraph, line);
+
+        let mut glyph_levels = glyphs
+            .iter()
+            .map(|glyph| levels.get(glyph.text_byte_offset).map_or(0, |level| level.number()))
+            .collect::<Vec<_>>();
+        let highest_level = glyph_levels.iter().copied().max().unwrap_or(0);
+        let lowest_odd_level =
+            glyph_levels.iter().copied().filter(|level| level % 2 == 1).min().unwrap_or(1);
+
+        // Reverse any contiguous sequence of glyphs that are at the given level or higher,
+        // from the highest level down to the lowest odd level.
+        for level in (lowest_odd_level..=highest_level).rev() {
+            let mut index = 0;
+            while index < glyphs.len() {
+                if glyph_levels[index] < level {
+                    index += 1;
+                    continue;
+                }
+                let start = index;
+                while index < glyphs.len() && glyph_levels[index] >= level {
+                    index += 1;
+                }
+                glyphs[start..index].reverse();
+                glyph_levels[start..index].reverse();
+            }
+        }
+    }
+}
+
+#[cfg(feature = "unicode-bidi")]
+#[test]
+fn test_reorder_line() {
+    let text = "abc אבג def";
+    let mut glyphs = text
+        .char_indices()
+        .map(|(text_byte_offset, _)| Glyph::<i32, ()> { text_byte_offset, ..Default::default() })
+        .collect::<Vec<_>>();
+    let paragraphs = BidiParagraphs::new(text, TextDirection::Auto).unwrap();
+    paragraphs.reorder_line(0, &mut glyphs);
+    let visual = glyphs
+        .iter()
+        .map(|glyph| text[glyph.text_byte_offset..].chars().next().unwrap())
+        .collect::<alloc::string::String>();
+    assert_eq!(visual, "abc גבא def");
+
+    assert!(BidiParagraphs::new("only latin", TextDirection::Auto).is_none());
+    assert!(is_rtl("123 אבג abc", TextDirection::Auto));
+    assert!(!is_rtl("abc אבג", TextDirection::Auto));
+    assert!(is_rtl("abc", TextDirection::Rtl));
+}
@@ -0,0 +1,301 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+//! Parser for the limited markup supported by the `Text` element when `rich-text` is set.
+//!
+//! The supported tags are:
+//!  - `<b>` for bold text
+//!  - `<i>` for italic text
+//!  - `<u>` for underlined text
+//!  - `<font color="#rrggbb">` to change the color of a span
+//!  - `<a href="...">` for links, which are reported through the `link-activated` callback
+//!
+//! The entities `&lt;`, `&gt;`, `&amp;`, `&quot;` and `&apos;` are decoded, and `<br>` inserts a line break.
+//! Unknown tags are dropped, and unbalanced closing tags are ignored.
+
+use crate::graphics::Color;
+use crate::SharedString;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::ops::Range;
+
+/// The slant of the italic text, for the renderers that synthesize it by shearing the glyphs:
+/// the horizontal shift of a point, relative to its height above the baseline.
+pub const ITALIC_SLANT: f32 = 0.2;
+
+/// The style that applies to a span of rich text.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RichTextStyle {
+    pub bold: bool,
+    pub italic: bool,
+    pub underline: bool,
+    /// The color of the span, or None if the color of the Text element should be used
+    pub color: Option<Color>,
+    /// The target of the link, if this span is part of a link
+    pub link: Option<SharedString>,
+}
+
+/// A range of the plain text with a style that differs from the default.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RichTextSpan {
+    /// The byte range in [`RichText::text`]
+    pub range: Range<usize>,
+    pub style: RichTextStyle,
+}
+
+/// The result of parsing a string with markup: the text to display without the tags,
+/// and the styled spans. Text that is not covered by a span is displayed with the default style.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RichText {
+    pub text: String,
+    pub spans: Vec<RichTextSpan>,
+}
+
+impl RichText {
+    /// Returns the style at the given byte offset of the plain text
+    pub fn style_at(&self, byte_offset: usize) -> Option<&RichTextStyle> {
+        self.spans.iter().find(|s| s.range.contains(&byte_offset)).map(|s| &s.style)
+    }
+
+    /// Returns the link target at the given byte offset of the plain text, if any
+    pub fn link_at(&self, byte_offset: usize) -> Option<&SharedString> {
+        self.style_at(byte_offset).and_then(|s| s.link.as_ref())
+    }
+
+    /// Split the given byte range of the plain text into sub-ranges of uniform style.
+    /// Parts that have the default style yield `None`.
+    pub fn segments(
+        &self,
+        range: Range<usize>,
+    ) -> impl Iterator<Item = (Range<usize>, Option<&RichTextStyle>)> + '_ {
+        let mut pos = range.start;
+        let end = range.end;
+        core::iter::from_fn(move || {
+            if pos >= end {
+                return None;
+            }
+            let start = pos;
+            let (next, style) = match self.spans.iter().find(|s| s.range.contains(&start)) {
+                Some(span) => (span.range.end.min(end), Some(&span.style)),
+                None => (
+                    self.spans
+                        .iter()
+                        .map(|s| s.range.start)
+                        .filter(|s| *s > start)
+                        .min()
+                        .unwrap_or(end)
+                        .min(end),
+                    None,
+                ),
+            };
+            pos = next;
+            Some((start..next, style))
+        })
+    }
+}
+
+enum Tag {
+    Bold,
+    Italic,
+    Underline,
+    Color(Color),
+    Link(SharedString),
+    Other,
+}
+
+/// Parse the markup and return the plain text with its styled spans.
+pub fn parse_rich_text(markup: &str) -> RichText {
+    let mut result = RichText::default();
+    let mut stack: Vec<(SharedString, Tag)> = Vec::new();
+    let mut current_style = RichTextStyle::default();
+    let mut span_start = 0;
+
+    let mut rest = markup;
+    while !rest.is_empty() {
+        let next_special = rest.find(|c| c == '<' || c == '&').unwrap_or(rest.len());
+        result.text.push_str(&rest[..next_special]);
+        rest = &rest[next_special..];
+        if rest.starts_with('&') {
+            let (decoded, len) = decode_entity(rest);
+            result.text.push(decoded);
+            rest = &rest[len..];
+            continue;
+        }
+        if rest.is_empty() {
+            break;
+        }
+        let looks_like_tag = rest[1..].starts_with(|c: char| c.is_ascii_alphabetic() || c == '/');
+        let tag_end = match rest.find('>').filter(|_| looks_like_tag) {
+            Some(pos) => pos,
+            None => {
+                // Not a tag, keep the `<` literally
+                result.text.push('<');
+                rest = &rest[1..];
+                continue;
+            }
+        };
+        let tag = &rest[1..tag_end];
+        rest = &rest[tag_end + 1..];
+
+        let style_before = current_style.clone();
+        if let Some(closing) = tag.strip_prefix('/') {
+            let name = closing.trim().to_ascii_lowercase();
+            if let Some(pos) = stack.iter().rposition(|(n, _)| n.as_str() == name) {
+                stack.truncate(pos);
+                current_style = compute_style(&stack);
+            }
+        } else {
+            let tag = tag.trim_end_matches('/').trim();
+            let (name, attributes) = tag.split_once(char::is_whitespace).unwrap_or((tag, ""));
+            let name = name.to_ascii_lowercase();
+            let parsed = match name.as_str() {
+                "b" | "strong" => Tag::Bold,
+                "i" | "em" => Tag::Italic,
+                "u" => Tag::Underline,
+                "font" => attribute(attributes, "color")
+                    .and_then(|c| parse_color(&c))
+                    .map_or(Tag::Other, Tag::Color),
+                "a" => Tag::Link(attribute(attributes, "href").unwrap_or_default().into()),
+                "br" => {
+                    result.text.push('\n');
+                    continue;
+                }
+                _ => Tag::Other,
+            };
+            stack.push((name.into(), parsed));
+            current_style = compute_style(&stack);
+        }
+        if current_style != style_before {
+            if style_before != RichTextStyle::default() && span_start < result.text.len() {
+                result.spans.push(RichTextSpan {
+                    range: span_start..result.text.len(),
+                    style: style_before,
+                });
+            }
+            span_start = result.text.len();
+        }
+    }
+    if current_style != RichTextStyle::default() && span_start < result.text.len() {
+        result
+            .spans
+            .push(RichTextSpan { range: span_start..result.text.len(), style: current_style });
+    }
+    result
+}
+
+fn compute_style(stack: &[(SharedString, Tag)]) -> RichTextStyle {
+    let mut style = RichTextStyle::default();
+    for (_, tag) in stack {
+        match tag {
+            Tag::Bold => style.bold = true,
+            Tag::Italic => style.italic = true,
+            Tag::Underline => style.underline = true,
+            Tag::Color(c) => style.color = Some(*c),
+            Tag::Link(l) => style.link = Some(l.clone()),
+            Tag::Other => {}
+        }
+    }
+    style
+}
+
+/// Returns the decoded character and the number of bytes consumed
+fn decode_entity(s: &str) -> (char, usize) {
+    for (entity, c) in [
+        ("&lt;", '<'),
+        ("&gt;", '>'),
+        ("&amp;", '&'),
+        ("&quot;", '"'),
+        ("&apos;", '\''),
+        ("&nbsp;", '\u{a0}'),
+    ] {
+        if s.starts_with(entity) {
+            return (c, entity.len());
+        }
+    }
+    ('&', 1)
+}
+
+/// Extract the value of an attribute in a list like `href="foo" color='#fff'`
+fn attribute(attributes: &str, name: &str) -> Option<String> {
+    let mut rest = attributes.trim_start();
+    while !rest.is_empty() {
+        let eq = rest.find('=')?;
+        let attr_name = rest[..eq].trim();
+        rest = rest[eq + 1..].trim_start();
+        let (value, len) = match rest.chars().next()? {
+            q @ ('"' | '\'') => {
+                let end = rest[1..].find(q)? + 1;
+                (&rest[1..end], end + 1)
+            }
+            _ => {
+                let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+                (&rest[..end], end)
+            }
+        };
+        if attr_name.eq_ignore_ascii_case(name) {
+            let mut decoded = String::new();
+            let mut v = value;
+            while let Some(pos) = v.find('&') {
+                decoded.push_str(&v[..pos]);
+                let (c, len) = decode_entity(&v[pos..]);
+                decoded.push(c);
+                v = &v[pos + len..];
+            }
+            decoded.push_str(v);
+            return Some(decoded);
+        }
+        rest = rest[len..].trim_start();
+    }
+    None
+}
+
+/// Parse colors in the `#rgb`, `#rrggbb` or `#rrggbbaa` form
+fn parse_color(s: &str) -> Option<Color> {
+    let hex = s.trim().strip_prefix('#')?;
+    let digit = |i: usize| u8::from_str_radix(hex.get(i..i + 1)?, 16).ok();
+    let byte = |i: usize| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok();
+    match hex.len() {
+        3 => Some(Color::from_rgb_u8(digit(0)? * 0x11, digit(1)? * 0x11, digit(2)? * 0x11)),
+        6 => Some(Color::from_rgb_u8(byte(0)?, byte(2)?, byte(4)?)),
+        8 => Some(Color::from_argb_u8(byte(6)?, byte(0)?, byte(2)?, byte(4)?)),
+        _ => None,
+    }
+}
+
+#[test]
+fn test_parse_rich_text() {
+    let rt = parse_rich_text("Hello <b>bold <i>both</i></b> and <u>under</u>");
+    assert_eq!(rt.text, "Hello bold both and under");
+    assert_eq!(rt.spans.len(), 3);
+    assert_eq!(rt.spans[0].range, 6..11);
+    assert!(rt.spans[0].style.bold && !rt.spans[0].style.italic);
+    assert_eq!(rt.spans[1].range, 11..15);
+    assert!(rt.spans[1].style.bold && rt.spans[1].style.italic);
+    assert_eq!(rt.spans[2].range, 20..25);
+    assert!(rt.spans[2].style.underline);
+}
+
+#[test]
+fn test_parse_rich_text_links_and_colors() {
+    let rt = parse_rich_text(
+        r##"<font color="#ff0000">red</font> <a href="https://slint-ui.com?a=1&amp;b=2">link</a>"##,
+    );
+    assert_eq!(rt.text, "red link");
+    assert_eq!(rt.spans[0].style.color, Some(Color::from_rgb_u8(0xff, 0, 0)));
+    assert_eq!(rt.link_at(5).map(|l| l.as_str()), Some("https://slint-ui.com?a=1&b=2"));
+    assert_eq!(rt.link_at(2), None);
+}
+
+#[test]
+fn test_parse_rich_text_entities_and_errors() {
+    let rt = parse_rich_text("a &lt; b &amp;&amp; c</b> <unknown>x</unknown> 1 < 2<br>end");
+    assert_eq!(rt.text, "a < b && c x 1 < 2\nend");
+    assert!(rt.spans.is_empty());
+}
+
+#[test]
+fn test_rich_text_segments() {
+    let rt = parse_rich_text("ab<b>cd</b>ef");
+    let segments = rt.segments(1..5).map(|(r, s)| (r, s.is_some())).collect::<Vec<_>>();
+    assert_eq!(segments, alloc::vec![(1..2, false), (2..4, true), (4..5, false)]);
+}
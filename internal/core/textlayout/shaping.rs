@@ -195,6 +195,17 @@ pub fn new<Font>(layout: &TextLayout<Font>, text: &str) -> Self
                     }
                 }
 
+                if let Some(word_spacing) = layout.word_spacing {
+                    let run_text = &text[*run_start..run_end];
+                    for glyph in &mut glyphs[glyphs_start..] {
+                        if run_text[glyph.text_byte_offset..]
+                            .starts_with(|c| c == ' ' || c == '\u{00a0}')
+                        {
+                            glyph.advance += word_spacing;
+                        }
+                    }
+                }
+
                 let run = TextRun {
                     byte_range: Range { start: *run_start, end: run_end },
                     //glyph_range: Range {
@@ -403,7 +414,7 @@ fn test_letter_spacing() {
             shaped_glyphs.iter().map(|g| g.advance).collect::<Vec<_>>()
         };
 
-        let layout = TextLayout { font: &face, letter_spacing: Some(20.) };
+        let layout = TextLayout { font: &face, letter_spacing: Some(20.), word_spacing: None };
         let buffer = ShapeBuffer::new(&layout, text);
 
         assert_eq!(buffer.glyphs.len(), advances.len());
@@ -418,3 +429,31 @@ fn test_letter_spacing() {
         );
     });
 }
+
+#[test]
+fn test_word_spacing() {
+    use TextShaper;
+
+    with_dejavu_font(|face| {
+        let text = "a b";
+        let advances = {
+            let mut shaped_glyphs = Vec::new();
+            face.shape_text(text, &mut shaped_glyphs);
+
+            assert_eq!(shaped_glyphs.len(), 3);
+
+            shaped_glyphs.iter().map(|g| g.advance).collect::<Vec<_>>()
+        };
+
+        let layout = TextLayout { font: &face, letter_spacing: None, word_spacing: Some(10.) };
+        let buffer = ShapeBuffer::new(&layout, text);
+
+        let mut expected_advances = advances;
+        expected_advances[1] += layout.word_spacing.unwrap();
+
+        assert_eq!(
+            buffer.glyphs.iter().map(|glyph| glyph.advance).collect::<Vec<_>>(),
+            expected_advances
+        );
+    });
+}
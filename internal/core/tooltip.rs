@@ -0,0 +1,193 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+/*!
+The component that shows the tooltip of the element under the pointer.
+
+The tooltip is a small component made of a `BorderRectangle` with a `Text`, that the window
+renders on top of its component and popup, like a popup window. It is drawn by the renderer of
+the window, so every backend shows it the same way. Its colors and border radius are taken from
+the `tooltip-*` properties of the `Window`.
+*/
+
+use crate::accessibility::AccessibleStringProperty;
+use crate::component::{Component, ComponentRc, ComponentVTable, ComponentWeak, IndexRange};
+use crate::item_tree::{
+    ItemTreeNode, ItemVisitorVTable, ItemWeak, TraversalOrder, VisitChildrenResult,
+};
+use crate::items::{AccessibleRole, BorderRectangle, ItemRef, Text, TextVerticalAlignment};
+use crate::layout::{LayoutInfo, Orientation};
+use crate::lengths::{LogicalLength, LogicalPoint, LogicalRect, LogicalSize, ScaleFactor};
+use crate::slice::Slice;
+use crate::window::{WindowAdapter, WindowInner};
+use crate::{Coord, SharedString};
+use alloc::rc::Rc;
+use const_field_offset::FieldOffsets;
+use core::pin::Pin;
+use vtable::VRc;
+
+/// The space between the border of the tooltip and its text
+const PADDING: LogicalLength = LogicalLength::new(4 as Coord);
+/// The distance between the pointer and the top of the tooltip, so that the cursor doesn't cover it
+const POINTER_OFFSET: LogicalLength = LogicalLength::new(20 as Coord);
+
+const ITEM_TREE: [ItemTreeNode; 2] = [
+    ItemTreeNode::Item {
+        is_accessible: false,
+        children_count: 1,
+        children_index: 1,
+        parent_index: 0,
+        item_array_index: 0,
+    },
+    ItemTreeNode::Item {
+        is_accessible: false,
+        children_count: 0,
+        children_index: 2,
+        parent_index: 0,
+        item_array_index: 1,
+    },
+];
+
+#[derive(FieldOffsets, Default)]
+#[pin]
+pub(crate) struct ToolTipComponent {
+    background: BorderRectangle,
+    text: Text,
+    self_weak: once_cell::unsync::OnceCell<vtable::VWeak<ComponentVTable, ToolTipComponent>>,
+}
+
+impl ToolTipComponent {
+    pub(crate) fn new() -> VRc<ComponentVTable, Self> {
+        let component = VRc::new(Self::default());
+        component.self_weak.set(VRc::downgrade(&component)).ok();
+        component
+    }
+
+    /// Sets the text and style of the tooltip, and returns the area that it covers in the window,
+    /// below the pointer at `position` and within the window.
+    pub(crate) fn update(
+        self: Pin<&Self>,
+        text: SharedString,
+        position: LogicalPoint,
+        window_adapter: &Rc<dyn WindowAdapter>,
+    ) -> LogicalRect {
+        let window_inner = WindowInner::from_pub(window_adapter.window());
+        let background = Self::FIELD_OFFSETS.background.apply_pin(self);
+        let label = Self::FIELD_OFFSETS.text.apply_pin(self);
+
+        let mut window_size = None;
+        if let Some(window_item) = window_inner.window_item() {
+            let window_item = window_item.as_pin_ref();
+            background.background.set(window_item.tooltip_background());
+            background.border_color.set(window_item.tooltip_border_color());
+            let radius = window_item.tooltip_border_radius();
+            for corner in [
+                &background.border_radius,
+                &background.border_top_left_radius,
+                &background.border_top_right_radius,
+                &background.border_bottom_right_radius,
+                &background.border_bottom_left_radius,
+            ] {
+                corner.set(radius);
+            }
+            label.color.set(window_item.tooltip_color());
+            window_size =
+                Some(LogicalSize::from_lengths(window_item.width(), window_item.height()));
+        }
+        background.border_width.set(LogicalLength::new(1 as Coord));
+        label.vertical_alignment.set(TextVerticalAlignment::Center);
+        label.text.set(text.clone());
+
+        let text_size = window_adapter.renderer().text_size(
+            label.font_request(window_inner),
+            text.as_str(),
+            None,
+            ScaleFactor::new(window_inner.scale_factor()),
+        );
+        let text_size = text_size.ceil();
+        label.x.set(PADDING);
+        label.y.set(PADDING);
+        label.width.set(text_size.width_length());
+        label.height.set(text_size.height_length());
+
+        let size = LogicalSize::from_lengths(
+            text_size.width_length() + PADDING * (2 as Coord),
+            text_size.height_length() + PADDING * (2 as Coord),
+        );
+        background.width.set(size.width_length());
+        background.height.set(size.height_length());
+
+        // Below the pointer, moved to the left or above it when the tooltip doesn't fit in the window
+        let mut origin = position + euclid::vec2(0 as Coord, POINTER_OFFSET.get());
+        if let Some(window_size) = window_size {
+            if origin.y + size.height > window_size.height {
+                origin.y = position.y - size.height;
+            }
+            origin.x = origin.x.min(window_size.width - size.width);
+        }
+        origin.x = origin.x.max(0 as Coord);
+        origin.y = origin.y.max(0 as Coord);
+        LogicalRect::new(origin, size)
+    }
+}
+
+impl Component for ToolTipComponent {
+    fn visit_children_item(
+        self: Pin<&Self>,
+        index: isize,
+        order: TraversalOrder,
+        visitor: vtable::VRefMut<ItemVisitorVTable>,
+    ) -> VisitChildrenResult {
+        let self_rc: ComponentRc = VRc::into_dyn(self.self_weak.get().unwrap().upgrade().unwrap());
+        crate::item_tree::visit_item_tree(
+            self,
+            &self_rc,
+            &ITEM_TREE,
+            index,
+            order,
+            visitor,
+            |_, _, _, _| VisitChildrenResult::CONTINUE,
+        )
+    }
+
+    fn get_item_ref(self: Pin<&Self>, index: usize) -> Pin<ItemRef> {
+        match index {
+            0 => vtable::VRef::new_pin(Self::FIELD_OFFSETS.background.apply_pin(self)),
+            _ => vtable::VRef::new_pin(Self::FIELD_OFFSETS.text.apply_pin(self)),
+        }
+    }
+
+    fn get_item_tree(self: Pin<&Self>) -> Slice<ItemTreeNode> {
+        Slice::from_slice(&ITEM_TREE)
+    }
+
+    fn get_subtree_range(self: Pin<&Self>, _: usize) -> IndexRange {
+        IndexRange { start: 0, end: 0 }
+    }
+
+    fn get_subtree_component(self: Pin<&Self>, _: usize, _: usize, _: &mut ComponentWeak) {}
+
+    fn parent_node(self: Pin<&Self>, _: &mut ItemWeak) {}
+
+    fn subtree_index(self: Pin<&Self>) -> usize {
+        core::usize::MAX
+    }
+
+    fn layout_info(self: Pin<&Self>, _: Orientation) -> LayoutInfo {
+        LayoutInfo::default()
+    }
+
+    fn accessible_role(self: Pin<&Self>, _: usize) -> AccessibleRole {
+        AccessibleRole::default()
+    }
+
+    fn accessible_string_property(
+        self: Pin<&Self>,
+        _: usize,
+        _: AccessibleStringProperty,
+        _: &mut SharedString,
+    ) {
+    }
+}
+
+crate::component::ComponentVTable_static!(static TOOLTIP_COMPONENT_VT for ToolTipComponent);
@@ -16,10 +16,13 @@
 };
 use crate::item_tree::ItemRc;
 use crate::items::{ItemRef, MouseCursor};
-use crate::lengths::{LogicalLength, LogicalPoint, LogicalSize, SizeLengths};
+use crate::lengths::{
+    LogicalLength, LogicalPoint, LogicalRect, LogicalSize, LogicalVector, SizeLengths,
+};
 use crate::properties::{Property, PropertyTracker};
 use crate::renderer::Renderer;
-use crate::Callback;
+use crate::tooltip::ToolTipComponent;
+use crate::{Callback, Coord, SharedString};
 use alloc::boxed::Box;
 use alloc::rc::{Rc, Weak};
 use core::cell::{Cell, RefCell};
@@ -31,10 +34,6 @@ fn next_focus_item(item: ItemRc) -> ItemRc {
     item.next_focus_item()
 }
 
-fn previous_focus_item(item: ItemRc) -> ItemRc {
-    item.previous_focus_item()
-}
-
 /// This trait represents the adaptation layer between the [`Window`] API, and the
 /// internal type from the backend that provides functionality such as device-independent pixels,
 /// window resizing, and other typically windowing system related tasks.
@@ -90,6 +89,19 @@ fn request_window_properties_update(&self) {}
     /// Request for the given title string to be set to the windowing system for use as window title.
     fn apply_window_properties(&self, _window_item: Pin<&crate::items::WindowItem>) {}
 
+    /// Show the given system tray icon and its context menu, or remove the icon if `tray_icon` is None.
+    /// This is called when the `SystemTrayIcon` element of the window, or one of its menu items, changes.
+    /// The default implementation does nothing, as the tray icon is not supported.
+    fn apply_system_tray_icon(&self, _tray_icon: Option<crate::items::SystemTrayIconState>) {}
+
+    /// Show the menus of the `PlatformMenuBar` element of the window in the menu bar of the platform,
+    /// or remove them if `menus` is None. Returns true if the platform shows the menu bar, in which
+    /// case the `native` property of the element is set.
+    /// The default implementation returns false, as the platform has no menu bar.
+    fn apply_menu_bar(&self, _menus: Option<alloc::vec::Vec<crate::items::MenuEntry>>) -> bool {
+        false
+    }
+
     /// Apply the given horizontal and vertical constraints to the window. This typically involves communication
     /// minimum/maximum sizes to the windowing system, for example.
     fn apply_geometry_constraint(
@@ -102,15 +114,55 @@ fn apply_geometry_constraint(
     /// Set the mouse cursor
     fn set_mouse_cursor(&self, _cursor: MouseCursor) {}
 
+    /// Returns how long the pointer needs to rest over an element before its tooltip is shown.
+    /// The default implementation returns 700 milliseconds.
+    fn tooltip_delay(&self) -> core::time::Duration {
+        core::time::Duration::from_millis(700)
+    }
+
+    /// Set the mouse cursor to an image, with the hotspot (the position of the pointer within the image)
+    /// in logical pixels. Backends that don't support image cursors show the `fallback` cursor,
+    /// which is what the default implementation does.
+    fn set_custom_mouse_cursor(
+        &self,
+        _image: &crate::graphics::Image,
+        _hotspot: LogicalPoint,
+        fallback: MouseCursor,
+    ) {
+        self.set_mouse_cursor(fallback)
+    }
+
+    /// Returns the distance that the pointer needs to move while pressed before the movement
+    /// is considered a drag. The default implementation returns 4 logical pixels.
+    fn drag_threshold(&self) -> LogicalLength {
+        LogicalLength::new(4 as crate::Coord)
+    }
+
+    /// Returns the maximum delay between two clicks for them to form a double click.
+    /// The default implementation returns 500ms.
+    fn double_click_interval(&self) -> core::time::Duration {
+        core::time::Duration::from_millis(500)
+    }
+
+    /// Start a platform drag and drop operation with the given data, so that it can also be dropped
+    /// in other applications. `image` is shown under the cursor while dragging, if it is not empty.
+    ///
+    /// Returns true if the operation was handled by the platform and is now finished. The default
+    /// implementation returns false, in which case the drag and drop only works within the window.
+    fn start_drag(&self, _mime_type: &str, _data: &str, _image: &crate::graphics::Image) -> bool {
+        false
+    }
+
     /// This is called when an editable text input field has received the focus and input methods such as
     /// virtual keyboard should be shown.
     fn enable_input_method(&self, _: crate::items::InputType) {}
     /// This is called when the widget that needed the keyboard loses focus and any active input method should
     /// be disabled.
     fn disable_input_method(&self) {}
-    /// Update the position of the text input area. The provided point is in
-    /// window coordinates (not item relative!).
-    fn set_ime_position(&self, _: LogicalPoint) {}
+    /// Update the rectangle of the text cursor of the focused text input, so that input methods can place
+    /// their candidate window next to it. The rectangle is in window coordinates (not item relative!).
+    /// During a composition, it is the rectangle of the cursor at the start of the pre-edit text.
+    fn set_ime_cursor_rect(&self, _: LogicalRect) {}
 
     /// Return self as any so the backend can upcast
     fn as_any(&self) -> &dyn core::any::Any {
@@ -139,7 +191,8 @@ fn set_position(&self, _position: WindowPosition) {}
     /// The default implementation does nothing
     fn set_size(&self, _size: WindowSize) {}
 
-    /// returns wether a dark theme is used
+    /// Returns whether the platform uses a dark theme. Backends that can detect changes should
+    /// return the value of a property, so that the bindings that depend on it are updated.
     fn dark_color_scheme(&self) -> bool {
         false
     }
@@ -180,6 +233,75 @@ pub enum PopupWindowLocation {
     ChildWindow(Point), // TODO: change to LogicalPoint
 }
 
+/// Information about the monitor that shows the window, as reported by the backend.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MonitorInfo {
+    /// The name of the monitor, if the windowing system provides one
+    pub name: SharedString,
+    /// The size of the monitor, in logical pixels
+    pub size: LogicalSize,
+    /// The refresh rate of the monitor, in Hz, or 0 if it is not known
+    pub refresh_rate: f32,
+}
+
+/// The distances from the edges of the window to the area that is not covered by the system UI,
+/// such as a status bar, a notch or the rounded corners of the display.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct SafeAreaInsets {
+    /// The inset from the top edge
+    pub top: LogicalLength,
+    /// The inset from the bottom edge
+    pub bottom: LogicalLength,
+    /// The inset from the left edge
+    pub left: LogicalLength,
+    /// The inset from the right edge
+    pub right: LogicalLength,
+}
+
+/// The tooltip of an element.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ToolTip {
+    /// The text of the tooltip
+    pub text: SharedString,
+    /// The position of the pointer, in window coordinates. The tooltip is shown near it.
+    pub position: LogicalPoint,
+}
+
+/// The tooltip of the element under the pointer
+struct ToolTipState {
+    item: crate::item_tree::ItemWeak,
+    tooltip: ToolTip,
+    /// The area of the window covered by the tooltip, when it is shown
+    shown_area: Option<LogicalRect>,
+    /// true after a press on the element: the tooltip is not shown again until the pointer leaves it
+    suppressed: bool,
+}
+
+/// The options of a popup, from the properties of the `PopupWindow` element.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PopupWindowOptions {
+    /// Close the popup when the pointer is released within the popup
+    pub close_on_click: bool,
+    /// Close the popup when the pointer is pressed outside of the popup
+    pub close_on_click_outside: bool,
+    /// Close the popup when the Escape key is pressed and not handled by the focused element
+    pub close_on_escape: bool,
+    /// Don't deliver the press that closes the popup to the elements below it
+    pub modal: bool,
+}
+
+impl Default for PopupWindowOptions {
+    fn default() -> Self {
+        Self {
+            close_on_click: true,
+            close_on_click_outside: true,
+            close_on_escape: true,
+            modal: false,
+        }
+    }
+}
+
 /// This structure defines a graphical element that is designed to pop up from the surrounding
 /// UI content, for example to show a context menu.
 pub struct PopupWindow {
@@ -187,6 +309,8 @@ pub struct PopupWindow {
     pub location: PopupWindowLocation,
     /// The component that is responsible for providing the popup content.
     pub component: ComponentRc,
+    /// Defines when the popup closes.
+    pub options: PopupWindowOptions,
 }
 
 /// Inner datastructure for the [`crate::api::Window`]
@@ -194,6 +318,12 @@ pub struct WindowInner {
     window_adapter_weak: Weak<dyn WindowAdapter>,
     component: RefCell<ComponentWeak>,
     mouse_input_state: Cell<MouseInputState>,
+    /// The drag and drop operation in progress, if any
+    drag_state: RefCell<Option<crate::input::DragState>>,
+    /// The fingers touching the window, used to recognize multi-touch gestures
+    touch_state: RefCell<crate::input::TouchState>,
+    /// The modifiers pressed, tracked from the key events dispatched with `Window::dispatch_event`
+    modifiers: Cell<crate::input::KeyboardModifiers>,
     redraw_tracker: Pin<Box<PropertyTracker<WindowRedrawTracker>>>,
     /// Gets dirty when the layout restrictions, or some other property of the windows change
     window_properties_tracker: Pin<Box<PropertyTracker<WindowPropertiesTracker>>>,
@@ -202,8 +332,21 @@ pub struct WindowInner {
     cursor_blinker: RefCell<pin_weak::rc::PinWeak<crate::input::TextCursorBlinker>>,
 
     scale_factor: Pin<Box<Property<f32>>>,
+    monitor: Pin<Box<Property<MonitorInfo>>>,
+    safe_area_insets: Pin<Box<Property<SafeAreaInsets>>>,
+    /// The color scheme set by the application, or `Unknown` to follow the platform
+    color_scheme: Pin<Box<Property<crate::items::ColorScheme>>>,
     active: Pin<Box<Property<bool>>>,
     active_popup: RefCell<Option<PopupWindow>>,
+    /// Set while the pointer is pressed outside of a non-modal popup that stays open: the events
+    /// of that press go to the elements below the popup
+    pointer_pressed_outside_popup: Cell<bool>,
+    tooltip: RefCell<Option<ToolTipState>>,
+    tooltip_timer: crate::timers::Timer,
+    /// The component that renders the tooltip, created the first time a tooltip is shown
+    tooltip_component: RefCell<Option<vtable::VRc<ComponentVTable, ToolTipComponent>>>,
+    /// Set when the window contains `ViewportObserver` items that need to be updated after each frame
+    has_viewport_observers: Cell<bool>,
     close_requested: Callback<(), CloseRequestResponse>,
     /// This is a cache of the size set by the set_inner_size setter.
     /// It should be mapping with the WindowItem::width and height (only in physical)
@@ -243,13 +386,33 @@ pub fn new(window_adapter_weak: Weak<dyn WindowAdapter>) -> Self {
             window_adapter_weak,
             component: Default::default(),
             mouse_input_state: Default::default(),
+            drag_state: Default::default(),
+            touch_state: Default::default(),
+            modifiers: Default::default(),
             redraw_tracker: Box::pin(redraw_tracker),
             window_properties_tracker: Box::pin(window_properties_tracker),
             focus_item: Default::default(),
             cursor_blinker: Default::default(),
             scale_factor: Box::pin(Property::new_named(1., "i_slint_core::Window::scale_factor")),
+            monitor: Box::pin(Property::new_named(
+                Default::default(),
+                "i_slint_core::Window::monitor",
+            )),
+            safe_area_insets: Box::pin(Property::new_named(
+                Default::default(),
+                "i_slint_core::Window::safe_area_insets",
+            )),
+            color_scheme: Box::pin(Property::new_named(
+                Default::default(),
+                "i_slint_core::Window::color_scheme",
+            )),
             active: Box::pin(Property::new_named(false, "i_slint_core::Window::active")),
             active_popup: Default::default(),
+            pointer_pressed_outside_popup: Default::default(),
+            tooltip: Default::default(),
+            tooltip_timer: Default::default(),
+            tooltip_component: Default::default(),
+            has_viewport_observers: Default::default(),
             close_requested: Default::default(),
             inner_size: Default::default(),
         };
@@ -296,31 +459,56 @@ pub fn process_mouse_input(&self, mut event: MouseEvent) {
             self.active_popup.borrow().as_ref().and_then(|popup| match popup.location {
                 PopupWindowLocation::TopLevel(_) => None,
                 PopupWindowLocation::ChildWindow(coordinates) => {
-                    Some((popup.component.clone(), coordinates))
+                    Some((popup.component.clone(), coordinates, popup.options))
                 }
             });
 
-        let component = embedded_popup_component
-            .as_ref()
-            .and_then(|(popup_component, coordinates)| {
-                let coordinates = LogicalPoint::from_untyped(*coordinates);
-                event.translate(-coordinates.to_vector());
-
-                if let MouseEvent::Pressed { position, .. } = &event {
-                    // close the popup if one press outside the popup
-                    let geom = ComponentRc::borrow_pin(popup_component)
-                        .as_ref()
-                        .get_item_ref(0)
-                        .as_ref()
-                        .geometry();
-                    if !geom.contains(*position) {
+        let mut component = self.component.borrow().upgrade();
+        // The options of the popup, if the event is delivered to it
+        let mut popup_options = None;
+        let mut popup_geometry = LogicalRect::default();
+        let pressed_outside_popup = if matches!(event, MouseEvent::Released { .. }) {
+            self.pointer_pressed_outside_popup.take()
+        } else {
+            self.pointer_pressed_outside_popup.get()
+        };
+        if let Some((popup_component, coordinates, options)) =
+            embedded_popup_component.as_ref().filter(|_| !pressed_outside_popup)
+        {
+            let coordinates = LogicalPoint::from_untyped(*coordinates);
+            let mut popup_event = event;
+            popup_event.translate(-coordinates.to_vector());
+            popup_geometry = ComponentRc::borrow_pin(popup_component)
+                .as_ref()
+                .get_item_ref(0)
+                .as_ref()
+                .geometry();
+
+            if let MouseEvent::Pressed { position, .. } = &popup_event {
+                if !popup_geometry.contains(*position) {
+                    if options.close_on_click_outside {
                         self.close_popup();
-                        return None;
                     }
+                    // A modal popup blocks the input to the elements below it
+                    if options.modal {
+                        return;
+                    }
+                    if !options.close_on_click_outside {
+                        // The popup stays open: deliver the moves and the release of this press
+                        // to the elements below too
+                        self.pointer_pressed_outside_popup.set(true);
+                    }
+                } else {
+                    popup_options = Some(*options);
                 }
-                Some(popup_component.clone())
-            })
-            .or_else(|| self.component.borrow().upgrade());
+            } else {
+                popup_options = Some(*options);
+            }
+            if popup_options.is_some() {
+                event = popup_event;
+                component = Some(popup_component.clone());
+            }
+        }
 
         let component = if let Some(component) = component {
             component
@@ -329,21 +517,98 @@ pub fn process_mouse_input(&self, mut event: MouseEvent) {
         };
 
         self.mouse_input_state.set(crate::input::process_mouse_input(
-            component,
+            component.clone(),
             event,
             &self.window_adapter(),
             self.mouse_input_state.take(),
         ));
 
-        if embedded_popup_component.is_some() {
-            //FIXME: currently the ComboBox is the only thing that uses the popup, and it should close automatically
-            // on release.  But ideally, there would be API to close the popup rather than always closing it on release
-            if matches!(event, MouseEvent::Released { .. }) {
-                self.close_popup();
+        if self.drag_state.borrow().is_some() {
+            self.process_drag_event_for_component(component, event);
+        }
+
+        if let Some(options) = popup_options {
+            // The click may have replaced the popup with another one, for example a submenu,
+            // which must stay open
+            let still_active = self.active_popup.borrow().as_ref().map_or(false, |popup| {
+                embedded_popup_component
+                    .as_ref()
+                    .map_or(false, |(c, ..)| vtable::VRc::ptr_eq(&popup.component, c))
+            });
+            match event {
+                MouseEvent::Released { position, .. }
+                    if options.close_on_click
+                        && still_active
+                        && popup_geometry.contains(position) =>
+                {
+                    self.close_popup()
+                }
+                _ => {}
             }
         }
     }
 
+    /// Receive an event of the touch point identified by `id`: one of `MouseEvent::Pressed`,
+    /// `MouseEvent::Moved` or `MouseEvent::Released`.
+    ///
+    /// Two fingers on a `GestureArea` form a gesture that is delivered to that item. Otherwise,
+    /// the first finger is processed like the mouse pointer with [`Self::process_mouse_input`].
+    pub fn process_touch_input(&self, id: u64, event: MouseEvent) {
+        crate::animations::update_animations();
+        let component = match self.try_component() {
+            Some(component) => component,
+            None => return,
+        };
+        let mouse_event = {
+            let mut state = self.touch_state.borrow_mut();
+            crate::input::process_touch_event(
+                component,
+                id,
+                event,
+                &mut state,
+                &self.window_adapter(),
+            )
+        };
+        if let Some(mouse_event) = mouse_event {
+            self.process_mouse_input(mouse_event);
+        }
+    }
+
+    /// Start a drag and drop operation with the given data. The subsequent pointer events will be
+    /// delivered to the `DropArea` under the pointer, until the data is dropped with a
+    /// `MouseEvent::Released`, or the operation is cancelled with `MouseEvent::Exit`.
+    ///
+    /// This is called by the `DragArea`, but it can also be called by the backend when a drag
+    /// operation from another application enters the window. In that case, the events must be
+    /// sent with [`Self::process_drag_event`].
+    pub fn start_drag(&self, mime_type: SharedString, data: SharedString) {
+        *self.drag_state.borrow_mut() = Some(crate::input::DragState::new(mime_type, data));
+    }
+
+    /// Receive a pointer event of a drag and drop operation started with [`Self::start_drag`],
+    /// without delivering it to the other items.
+    ///
+    /// Returns true if the data was dropped on a `DropArea`.
+    pub fn process_drag_event(&self, event: MouseEvent) -> bool {
+        match self.try_component() {
+            Some(component) => self.process_drag_event_for_component(component, event),
+            None => false,
+        }
+    }
+
+    fn process_drag_event_for_component(&self, component: ComponentRc, event: MouseEvent) -> bool {
+        let mut state = match self.drag_state.borrow_mut().take() {
+            Some(state) => state,
+            None => return false,
+        };
+        let dropped =
+            crate::input::process_drag_event(component, event, &mut state, &self.window_adapter());
+        if !matches!(event, MouseEvent::Released { .. } | MouseEvent::Exit) {
+            *self.drag_state.borrow_mut() = Some(state);
+        }
+        dropped
+    }
+
     /// Called by the input code's internal timer to send an event that was delayed
     pub(crate) fn process_delayed_event(&self) {
         self.mouse_input_state.set(crate::input::process_delayed_event(
@@ -352,6 +617,23 @@ pub(crate) fn process_delayed_event(&self) {
         ));
     }
 
+    /// Receive a key event from a platform that only provides the text of the keys, and
+    /// tracks the state of the modifiers from the events of the modifier keys.
+    pub(crate) fn process_platform_key_input(&self, text: SharedString, event_type: KeyEventType) {
+        let pressed = event_type == KeyEventType::KeyPressed;
+        let mut modifiers = self.modifiers.get();
+        match text.chars().next() {
+            Some(key_codes::Shift) => modifiers.shift = pressed,
+            Some(key_codes::Control) => modifiers.control = pressed,
+            Some(key_codes::Alt) => modifiers.alt = pressed,
+            Some(key_codes::Meta) => modifiers.meta = pressed,
+            _ => {}
+        }
+        self.modifiers.set(modifiers);
+        let event = KeyEvent { event_type, text, modifiers, ..Default::default() };
+        self.process_key_input(&event);
+    }
+
     /// Receive a key event and pass it to the items of the component to
     /// change their state.
     ///
@@ -377,6 +659,35 @@ pub fn process_key_input(&self, event: &KeyEvent) {
             item = focus_item.parent_item();
         }
 
+        // The shortcuts of the popup, such as the default button of a dialog, take precedence
+        // over closing it with Escape and over the shortcuts of the window
+        let popup_component = self.active_popup.borrow().as_ref().map(|p| p.component.clone());
+        if let Some(popup_component) = popup_component {
+            if event.event_type == KeyEventType::KeyPressed
+                && self.activate_shortcut(&popup_component, event)
+            {
+                return;
+            }
+        }
+
+        if event.event_type == KeyEventType::KeyPressed && event.text.starts_with(key_codes::Escape)
+        {
+            let close_on_escape =
+                self.active_popup.borrow().as_ref().map_or(false, |p| p.options.close_on_escape);
+            if close_on_escape {
+                self.close_popup();
+                return;
+            }
+        }
+
+        if event.event_type == KeyEventType::KeyPressed {
+            if let Some(component) = self.try_component() {
+                if self.activate_shortcut(&component, event) {
+                    return;
+                }
+            }
+        }
+
         // Make Tab/Backtab handle keyboard focus
         if event.text.starts_with(key_codes::Tab) && event.event_type == KeyEventType::KeyPressed {
             self.focus_next_item();
@@ -387,6 +698,83 @@ pub fn process_key_input(&self, event: &KeyEvent) {
         }
     }
 
+    /// Activate the `Shortcut` that matches the key press, if any. Returns true if the event
+    /// matched one or more shortcuts.
+    ///
+    /// A local shortcut only applies when the focus item is within its parent, and takes precedence
+    /// over the shortcuts further up the focus chain. If several shortcuts match equally, none
+    /// of them is activated.
+    fn activate_shortcut(&self, component: &ComponentRc, event: &KeyEvent) -> bool {
+        let focus_item = self.focus_item.borrow().upgrade();
+        // The distance between the focus item and the parent of a local shortcut
+        let scope_distance = |shortcut: &ItemRc| -> Option<usize> {
+            let parent = shortcut.parent_item();
+            let mut item = focus_item.clone();
+            let mut distance = 0;
+            while let Some(i) = item {
+                if Some(&i) == parent.as_ref() {
+                    return Some(distance);
+                }
+                item = i.parent_item();
+                distance += 1;
+            }
+            None
+        };
+
+        let mut best: Option<(usize, ItemRc)> = None;
+        let mut ambiguous = false;
+        crate::item_tree::visit_items(
+            component,
+            crate::item_tree::TraversalOrder::BackToFront,
+            |component, item, index, _| {
+                if let Some(shortcut) = ItemRef::downcast_pin::<crate::items::Shortcut>(item) {
+                    if shortcut.matches(event) {
+                        let item_rc = ItemRc::new(component.clone(), index);
+                        let priority = if shortcut.local() {
+                            scope_distance(&item_rc)
+                        } else {
+                            Some(usize::MAX)
+                        };
+                        if let Some(priority) = priority {
+                            let order = best
+                                .as_ref()
+                                .map_or(core::cmp::Ordering::Less, |(p, _)| priority.cmp(p));
+                            match order {
+                                core::cmp::Ordering::Less => {
+                                    ambiguous = false;
+                                    best = Some((priority, item_rc));
+                                }
+                                core::cmp::Ordering::Equal => ambiguous = true,
+                                core::cmp::Ordering::Greater => {}
+                            }
+                        }
+                    }
+                }
+                crate::item_tree::ItemVisitorResult::Continue(())
+            },
+            (),
+        );
+
+        match best {
+            Some((_, shortcut)) if !ambiguous => {
+                if let Some(shortcut) = shortcut.downcast::<crate::items::Shortcut>() {
+                    shortcut.as_pin_ref().activate();
+                }
+                true
+            }
+            Some((_, shortcut)) => {
+                if let Some(shortcut) = shortcut.downcast::<crate::items::Shortcut>() {
+                    crate::debug_log!(
+                        "Ambiguous shortcut '{}': several Shortcut elements use it",
+                        shortcut.as_pin_ref().keys()
+                    );
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
     /// Installs a binding on the specified property that's toggled whenever the text cursor is supposed to be visible or not.
     pub fn set_cursor_blink_binding(&self, prop: &crate::Property<bool>) {
         let existing_blinker = self.cursor_blinker.borrow().clone();
@@ -401,6 +789,11 @@ pub fn set_cursor_blink_binding(&self, prop: &crate::Property<bool>) {
         TextCursorBlinker::set_binding(blinker, prop);
     }
 
+    /// Returns the item that currently has the keyboard focus, if any.
+    pub fn focus_item(&self) -> Option<ItemRc> {
+        self.focus_item.borrow().upgrade()
+    }
+
     /// Sets the focus to the item pointed to by item_ptr. This will remove the focus from any
     /// currently focused item.
     pub fn set_focus_item(&self, focus_item: &ItemRc) {
@@ -448,11 +841,16 @@ fn publish_focus_item(&self, item: &Option<ItemRc>) -> crate::input::FocusEventR
         match item {
             Some(item) => {
                 *self.focus_item.borrow_mut() = item.downgrade();
-                item.borrow().as_ref().focus_event(
+                let result = item.borrow().as_ref().focus_event(
                     &crate::input::FocusEvent::FocusIn,
                     &self.window_adapter(),
                     &item,
-                )
+                );
+                if result == crate::input::FocusEventResult::FocusAccepted {
+                    // Scroll the item that got the focus into view, if it is in a Flickable
+                    crate::items::ensure_item_visible(item);
+                }
+                result
             }
             None => {
                 *self.focus_item.borrow_mut() = Default::default();
@@ -483,23 +881,49 @@ fn move_focus(&self, start_item: ItemRc, forward: impl Fn(ItemRc) -> ItemRc) ->
 
     /// Move keyboard focus to the next item
     pub fn focus_next_item(&self) {
-        let component = self.component();
-        let start_item = self
-            .take_focus_item()
-            .map(next_focus_item)
-            .unwrap_or_else(|| ItemRc::new(component, 0));
-        let end_item = self.move_focus(start_item.clone(), next_focus_item);
-        self.window_adapter().handle_focus_change(Some(start_item), end_item);
+        self.move_focus_in_tab_chain(true);
     }
 
     /// Move keyboard focus to the previous item.
     pub fn focus_previous_item(&self) {
-        let component = self.component();
-        let start_item = previous_focus_item(
-            self.take_focus_item().unwrap_or_else(|| ItemRc::new(component, 0)),
-        );
-        let end_item = self.move_focus(start_item.clone(), previous_focus_item);
-        self.window_adapter().handle_focus_change(Some(start_item), end_item);
+        self.move_focus_in_tab_chain(false);
+    }
+
+    /// Move the keyboard focus to the next (or previous) item of the tab focus chain that accepts it.
+    ///
+    /// The chain is ordered by `tab-index`, and limited to the `FocusScope` with `trap-focus`
+    /// around the current focus item, if any.
+    fn move_focus_in_tab_chain(&self, forward: bool) {
+        let old = self.take_focus_item();
+        let root = match &old {
+            Some(old) => crate::item_focus::focus_trap(old).unwrap_or_else(|| {
+                let mut root = old.clone();
+                while let Some(parent) = root.parent_item() {
+                    root = parent;
+                }
+                root
+            }),
+            None => ItemRc::new(self.component(), 0),
+        };
+        let chain = crate::item_focus::tab_chain(&root);
+        let len = chain.len();
+        let current = old.as_ref().and_then(|old| chain.iter().position(|item| item == old));
+        let start = match (current, forward) {
+            (Some(current), true) => current + 1,
+            (Some(current), false) => current + len - 1,
+            (None, true) => 0,
+            (None, false) => len.saturating_sub(1),
+        };
+        let index = |n| if forward { (start + n) % len } else { (start + len - n) % len };
+        let new = (0..len)
+            .map(|n| &chain[index(n)])
+            .find(|item| {
+                item.is_visible()
+                    && self.publish_focus_item(&Some((*item).clone()))
+                        == crate::input::FocusEventResult::FocusAccepted
+            })
+            .cloned();
+        self.window_adapter().handle_focus_change(old, new);
     }
 
     /// Marks the window to be the active window. This typically coincides with the keyboard
@@ -530,9 +954,59 @@ pub fn update_window_properties(&self) {
             if let Some(window_item) = self.window_item() {
                 self.window_adapter().apply_window_properties(window_item.as_pin_ref());
             }
+            self.window_adapter().apply_system_tray_icon(self.system_tray_icon());
+            self.update_menu_bar();
         });
     }
 
+    /// Passes the menus of the first `PlatformMenuBar` element of the component to the window
+    /// adapter, and sets the `native` property of the element to what the adapter returned.
+    fn update_menu_bar(&self) {
+        let component = self.component();
+        let mut menu_bar = None;
+        crate::item_tree::visit_items(
+            &component,
+            crate::item_tree::TraversalOrder::BackToFront,
+            |component, item, index, _| {
+                if ItemRef::downcast_pin::<crate::items::PlatformMenuBar>(item).is_some() {
+                    menu_bar = Some(ItemRc::new(component.clone(), index));
+                    return crate::item_tree::ItemVisitorResult::Abort;
+                }
+                crate::item_tree::ItemVisitorResult::Continue(())
+            },
+            (),
+        );
+        let menus = menu_bar.as_ref().map(crate::items::menu_entries);
+        let native = self.window_adapter().apply_menu_bar(menus);
+        if let Some(menu_bar) =
+            menu_bar.as_ref().and_then(|m| m.downcast::<crate::items::PlatformMenuBar>())
+        {
+            crate::items::PlatformMenuBar::FIELD_OFFSETS
+                .native
+                .apply_pin(menu_bar.as_pin_ref())
+                .set(native);
+        }
+    }
+
+    /// Returns the state of the first `SystemTrayIcon` element of the component, if any
+    fn system_tray_icon(&self) -> Option<crate::items::SystemTrayIconState> {
+        let component = self.component();
+        let mut tray_icon = None;
+        crate::item_tree::visit_items(
+            &component,
+            crate::item_tree::TraversalOrder::BackToFront,
+            |component, item, index, _| {
+                if let Some(item) = ItemRef::downcast_pin::<crate::items::SystemTrayIcon>(item) {
+                    tray_icon = Some(item.state(&ItemRc::new(component.clone(), index)));
+                    return crate::item_tree::ItemVisitorResult::Abort;
+                }
+                crate::item_tree::ItemVisitorResult::Continue(())
+            },
+            (),
+        );
+        tray_icon
+    }
+
     /// Calls the render_components to render the main component and any sub-window components, tracked by a
     /// property dependency tracker.
     pub fn draw_contents(&self, render_components: impl FnOnce(&[(&ComponentRc, LogicalPoint)])) {
@@ -547,17 +1021,76 @@ pub fn draw_contents(&self, render_components: impl FnOnce(&[(&ComponentRc, Logi
                     }
                 });
 
-            if let Some((popup_component, popup_coordinates)) = popup_component {
-                render_components(&[
-                    (&component_rc, LogicalPoint::default()),
-                    (&popup_component, popup_coordinates),
-                ])
-            } else {
-                render_components(&[(&component_rc, LogicalPoint::default())]);
+            let tooltip_component =
+                self.tooltip.borrow().as_ref().and_then(|state| state.shown_area).and_then(
+                    |area| {
+                        let component = self.tooltip_component.borrow().clone()?;
+                        Some((vtable::VRc::into_dyn(component), area.origin))
+                    },
+                );
+
+            let mut components = alloc::vec![(&component_rc, LogicalPoint::default())];
+            if let Some((popup_component, popup_coordinates)) = &popup_component {
+                components.push((popup_component, *popup_coordinates));
+            }
+            if let Some((tooltip_component, tooltip_origin)) = &tooltip_component {
+                components.push((tooltip_component, *tooltip_origin));
             }
+            render_components(&components);
+        };
+
+        crate::profiling::begin_frame();
+        self.redraw_tracker.as_ref().evaluate_as_dependency_root(draw_fn);
+        crate::profiling::end_frame();
+        self.update_viewport_observers();
+    }
+
+    /// Called by the `ViewportObserver` items when they are initialized, so that their visibility
+    /// is updated after each frame.
+    pub(crate) fn register_viewport_observer(&self) {
+        self.has_viewport_observers.set(true);
+    }
+
+    /// Updates the `viewport-visible` property of the `ViewportObserver` items in this window,
+    /// depending on whether they intersect the window and the area of their clipping ancestors,
+    /// such as the enclosing Flickable.
+    fn update_viewport_observers(&self) {
+        if !self.has_viewport_observers.get() {
+            return;
+        }
+        let component_rc = match self.try_component() {
+            Some(component_rc) => component_rc,
+            None => return,
         };
+        let window_rect =
+            LogicalRect::from_size(ItemRc::new(component_rc.clone(), 0).geometry().size);
+
+        // Collect first and update afterwards, as the callbacks may modify the item tree.
+        let mut observers = alloc::vec::Vec::new();
+        crate::item_tree::visit_items(
+            &component_rc,
+            crate::item_tree::TraversalOrder::BackToFront,
+            |component, item, index, (offset, clip): &(LogicalVector, LogicalRect)| {
+                let geometry = item.as_ref().geometry().translate(*offset);
+                if ItemRef::downcast_pin::<crate::items::ViewportObserver>(item).is_some() {
+                    observers
+                        .push((ItemRc::new(component.clone(), index), geometry.intersects(clip)));
+                }
+                let clip = if crate::item_rendering::is_clipping_item(item) {
+                    clip.intersection(&geometry).unwrap_or_else(LogicalRect::zero)
+                } else {
+                    *clip
+                };
+                crate::item_tree::ItemVisitorResult::Continue((geometry.origin.to_vector(), clip))
+            },
+            (LogicalVector::default(), window_rect),
+        );
 
-        self.redraw_tracker.as_ref().evaluate_as_dependency_root(draw_fn)
+        for (item_rc, visible) in observers {
+            if let Some(observer) = item_rc.downcast::<crate::items::ViewportObserver>() {
+                observer.as_pin_ref().set_visible_in_viewport(visible);
+            }
+        }
     }
 
     /// Registers the window with the windowing system, in order to render the component's items and react
@@ -572,20 +1105,34 @@ pub fn hide(&self) {
         self.window_adapter().hide();
     }
 
-    /// Show a popup at the given position relative to the item
+    /// Show a popup at the given position relative to the item.
+    ///
+    /// If the popup doesn't fit in the window at that position, it is mirrored on the other side of the
+    /// item, for example above it instead of below, or moved so that it remains within the window.
     pub fn show_popup(
         &self,
         popup_componentrc: &ComponentRc,
         position: Point,
+        options: PopupWindowOptions,
         parent_item: &ItemRc,
     ) {
-        let mut position = LogicalPoint::from_untyped(position);
-        let mut parent_item = parent_item.clone();
-        loop {
-            position += parent_item.borrow().as_ref().geometry().origin.to_vector();
-            parent_item = match parent_item.parent_item() {
-                None => break,
-                Some(pi) => pi,
+        let relative_position = LogicalPoint::from_untyped(position);
+        let parent_geometry = parent_item.geometry();
+        let mut parent_origin = parent_item.map_to_window(parent_geometry.origin);
+        // The items of a popup shown in the window, such as the item of a submenu, are
+        // positioned relative to that popup
+        if let Some(PopupWindow {
+            location: PopupWindowLocation::ChildWindow(coordinates),
+            component,
+            ..
+        }) = &*self.active_popup.borrow()
+        {
+            let mut root = parent_item.clone();
+            while let Some(parent) = root.parent_item() {
+                root = parent;
+            }
+            if vtable::VRc::ptr_eq(&root.component(), component) {
+                parent_origin += LogicalPoint::from_untyped(*coordinates).to_vector();
             }
         }
 
@@ -615,6 +1162,11 @@ pub fn show_popup(
         h = h.max(LogicalLength::new(layout_info_v.min)).min(LogicalLength::new(layout_info_v.max));
 
         let size = LogicalSize::from_lengths(w, h);
+        let position = self.fit_popup_in_window(
+            relative_position,
+            size,
+            LogicalRect::new(parent_origin, parent_geometry.size),
+        );
 
         if let Some(window_item) = ItemRef::downcast_pin(popup_root) {
             let width_property =
@@ -640,8 +1192,133 @@ pub fn show_popup(
             }
         };
 
-        self.active_popup
-            .replace(Some(PopupWindow { location, component: popup_componentrc.clone() }));
+        self.active_popup.replace(Some(PopupWindow {
+            location,
+            component: popup_componentrc.clone(),
+            options,
+        }));
+    }
+
+    /// Returns the position in the window of a popup of the given size, placed at `relative_position`
+    /// relative to the `parent` rectangle, in window coordinates.
+    fn fit_popup_in_window(
+        &self,
+        relative_position: LogicalPoint,
+        size: LogicalSize,
+        parent: LogicalRect,
+    ) -> LogicalPoint {
+        let position = parent.origin + relative_position.to_vector();
+        let window_size = match self.window_item() {
+            Some(window_item) => window_item.as_pin_ref().geometry().size,
+            None => return position,
+        };
+        // Along one axis: flip around the parent if it overflows, then keep it in the window
+        let fit = |pos: Coord, len: Coord, parent_pos: Coord, parent_len: Coord, max: Coord| {
+            let mut pos = pos;
+            if pos + len > max || pos < 0 as Coord {
+                let flipped = parent_pos + parent_len - (pos - parent_pos) - len;
+                if flipped >= 0 as Coord && flipped + len <= max {
+                    pos = flipped;
+                }
+            }
+            pos.min(max - len).max(0 as Coord)
+        };
+        LogicalPoint::new(
+            fit(position.x, size.width, parent.origin.x, parent.size.width, window_size.width),
+            fit(position.y, size.height, parent.origin.y, parent.size.height, window_size.height),
+        )
+    }
+
+    /// Called when the pointer moves over an element that has a tooltip, at the given position in
+    /// window coordinates. The tooltip is shown when the pointer doesn't move for the tooltip delay.
+    pub fn hover_tooltip(&self, item: &ItemRc, text: SharedString, position: LogicalPoint) {
+        let previous_area = {
+            let mut state = self.tooltip.borrow_mut();
+            match state.as_mut() {
+                Some(state) if state.item.upgrade().as_ref() == Some(item) => {
+                    if state.shown_area.is_some() || state.suppressed {
+                        return;
+                    }
+                    state.tooltip = ToolTip { text, position };
+                    None
+                }
+                _ => state
+                    .replace(ToolTipState {
+                        item: item.downgrade(),
+                        tooltip: ToolTip { text, position },
+                        shown_area: None,
+                        suppressed: false,
+                    })
+                    .and_then(|previous| previous.shown_area),
+            }
+        };
+        if let Some(previous_area) = previous_area {
+            self.refresh_area(previous_area);
+        }
+        let window_adapter_weak = self.window_adapter_weak.clone();
+        self.tooltip_timer.start(
+            crate::timers::TimerMode::SingleShot,
+            self.window_adapter().tooltip_delay(),
+            move || {
+                if let Some(window_adapter) = window_adapter_weak.upgrade() {
+                    WindowInner::from_pub(window_adapter.window()).show_tooltip();
+                }
+            },
+        );
+    }
+
+    /// Shows the tooltip of the element under the pointer, when the tooltip delay has elapsed
+    fn show_tooltip(&self) {
+        let tooltip = match self.tooltip.borrow().as_ref() {
+            Some(state) if state.shown_area.is_none() && !state.suppressed => state.tooltip.clone(),
+            _ => return,
+        };
+        let window_adapter = self.window_adapter();
+        let component =
+            self.tooltip_component.borrow_mut().get_or_insert_with(ToolTipComponent::new).clone();
+        let area = crate::properties::evaluate_no_tracking(|| {
+            component.as_pin_ref().update(tooltip.text, tooltip.position, &window_adapter)
+        });
+        if let Some(state) = self.tooltip.borrow_mut().as_mut() {
+            state.shown_area = Some(area);
+        }
+        self.refresh_area(area);
+    }
+
+    /// Hides the tooltip of the item, if any. When `suppress` is true, the tooltip isn't shown again
+    /// until the pointer leaves the item, for example after a press.
+    pub fn hide_tooltip(&self, item: &ItemRc, suppress: bool) {
+        let shown_area = {
+            let mut state = self.tooltip.borrow_mut();
+            match state.as_mut() {
+                Some(s) if s.item.upgrade().as_ref() == Some(item) => {
+                    let shown_area = s.shown_area.take();
+                    if suppress {
+                        s.suppressed = true;
+                    } else {
+                        *state = None;
+                    }
+                    shown_area
+                }
+                _ => return,
+            }
+        };
+        self.tooltip_timer.stop();
+        if let Some(shown_area) = shown_area {
+            self.refresh_area(shown_area);
+        }
+    }
+
+    /// Returns the tooltip currently shown, if any
+    pub fn tooltip(&self) -> Option<ToolTip> {
+        self.tooltip.borrow().as_ref().filter(|s| s.shown_area.is_some()).map(|s| s.tooltip.clone())
+    }
+
+    /// Redraws the given area of the window, for example after the tooltip was shown or hidden.
+    fn refresh_area(&self, area: LogicalRect) {
+        let window_adapter = self.window_adapter();
+        window_adapter.renderer().mark_dirty_region(area.to_box2d().into());
+        window_adapter.request_redraw();
     }
 
     /// Removes any active popup.
@@ -658,7 +1335,7 @@ pub fn close_popup(&self) {
 
                 if !popup_region.is_empty() {
                     let window_adapter = self.window_adapter();
-                    window_adapter.renderer().mark_dirty_region(popup_region.to_box2d());
+                    window_adapter.renderer().mark_dirty_region(popup_region.to_box2d().into());
                     window_adapter.request_redraw();
                 }
             }
@@ -671,8 +1348,68 @@ pub fn scale_factor(&self) -> f32 {
     }
 
     /// Sets the scale factor for the window. This is set by the backend or for testing.
+    ///
+    /// The backend calls this again when the window moves to a monitor with a different scale factor.
+    /// The bindings that depend on the scale factor, as well as the cached glyphs and images, are then
+    /// re-evaluated by the property dependency tracking.
     pub fn set_scale_factor(&self, factor: f32) {
-        self.scale_factor.as_ref().set(factor)
+        if self.scale_factor.as_ref().get_untracked() == factor {
+            return;
+        }
+        self.scale_factor.as_ref().set(factor);
+        if let Some(window_adapter) = self.window_adapter_weak.upgrade() {
+            // The geometry constraints are passed in physical pixels to the windowing system
+            window_adapter.request_window_properties_update();
+            window_adapter.request_redraw();
+        }
+    }
+
+    /// Returns the color scheme of the window: the one set with [`Self::set_color_scheme`], or the
+    /// one of the platform.
+    pub fn color_scheme(&self) -> crate::items::ColorScheme {
+        use crate::items::ColorScheme;
+        match self.color_scheme.as_ref().get() {
+            ColorScheme::Unknown => {
+                if self.window_adapter().dark_color_scheme() {
+                    ColorScheme::Dark
+                } else {
+                    ColorScheme::Light
+                }
+            }
+            scheme => scheme,
+        }
+    }
+
+    /// Overrides the color scheme of the platform. Pass `ColorScheme::Unknown` to follow the platform again.
+    pub fn set_color_scheme(&self, scheme: crate::items::ColorScheme) {
+        self.color_scheme.as_ref().set(scheme)
+    }
+
+    /// Returns true if the window uses a dark color scheme
+    pub fn dark_color_scheme(&self) -> bool {
+        self.color_scheme() == crate::items::ColorScheme::Dark
+    }
+
+    /// Returns the monitor that shows the window, as set by the backend.
+    pub fn monitor(&self) -> MonitorInfo {
+        self.monitor.as_ref().get()
+    }
+
+    /// Sets the monitor that shows the window. This is called by the backend when the window is shown
+    /// or moves to another monitor.
+    pub fn set_monitor(&self, monitor: MonitorInfo) {
+        self.monitor.as_ref().set(monitor)
+    }
+
+    /// Returns the insets of the area of the window that is not covered by the system UI.
+    pub fn safe_area_insets(&self) -> SafeAreaInsets {
+        self.safe_area_insets.as_ref().get()
+    }
+
+    /// Sets the insets of the area of the window that is not covered by the system UI. This is called
+    /// by the backend when the window is shown, and when the insets change, for example after a rotation.
+    pub fn set_safe_area_insets(&self, insets: SafeAreaInsets) {
+        self.safe_area_insets.as_ref().set(insets)
     }
 
     /// Returns the window item that is the first item in the component.
@@ -682,6 +1419,21 @@ pub fn window_item(&self) -> Option<VRcMapped<ComponentVTable, crate::items::Win
         })
     }
 
+    /// Returns the minimum and maximum size of the window, from the given layout constraints of
+    /// its component. See [`window_size_limits`].
+    pub fn size_limits(
+        &self,
+        constraints_horizontal: crate::layout::LayoutInfo,
+        constraints_vertical: crate::layout::LayoutInfo,
+    ) -> (LogicalSize, LogicalSize) {
+        let fixed_size = self.window_item().and_then(|window_item| {
+            let window_item = window_item.as_pin_ref();
+            (!window_item.resizable())
+                .then(|| LogicalSize::from_lengths(window_item.width(), window_item.height()))
+        });
+        window_size_limits(constraints_horizontal, constraints_vertical, fixed_size)
+    }
+
     /// Sets the size of the window item. This method is typically called in response to receiving a
     /// window resize event from the windowing system.
     pub fn set_window_item_geometry(&self, size: LogicalSize) {
@@ -722,6 +1474,46 @@ pub fn from_pub(window: &crate::api::Window) -> &Self {
     }
 }
 
+/// Returns the minimum and maximum size of a window, from the layout constraints of its component.
+///
+/// `fixed_size` is the size of the `width` and `height` properties of a window that is not resizable.
+/// Such a window gets that size as minimum and maximum size: the user can't resize it, but setting
+/// these properties resizes it. A dimension that isn't set yet falls back to the preferred size of the
+/// layout. The backends apply these limits, so that every windowing system behaves the same way.
+pub fn window_size_limits(
+    constraints_horizontal: crate::layout::LayoutInfo,
+    constraints_vertical: crate::layout::LayoutInfo,
+    fixed_size: Option<LogicalSize>,
+) -> (LogicalSize, LogicalSize) {
+    match fixed_size {
+        Some(size) => {
+            let size = LogicalSize::new(
+                if size.width > 0 as Coord {
+                    size.width
+                } else {
+                    constraints_horizontal.preferred_bounded()
+                },
+                if size.height > 0 as Coord {
+                    size.height
+                } else {
+                    constraints_vertical.preferred_bounded()
+                },
+            );
+            (size, size)
+        }
+        None => (
+            LogicalSize::new(
+                constraints_horizontal.min.min(constraints_horizontal.max),
+                constraints_vertical.min.min(constraints_vertical.max),
+            ),
+            LogicalSize::new(
+                constraints_horizontal.max.max(constraints_horizontal.min),
+                constraints_vertical.max.max(constraints_vertical.min),
+            ),
+        ),
+    }
+}
+
 /// Internal alias for `Rc<dyn WindowAdapter>`.
 pub type WindowAdapterRc = Rc<dyn WindowAdapter>;
 
@@ -839,12 +1631,19 @@ pub enum GraphicsAPI {
         handle: *const WindowAdapterRcOpaque,
         popup: &ComponentRc,
         position: crate::graphics::Point,
+        options: PopupWindowOptions,
         parent_item: &ItemRc,
     ) {
         let window_adapter = &*(handle as *const Rc<dyn WindowAdapter>);
-        WindowInner::from_pub(window_adapter.window()).show_popup(popup, position, parent_item);
+        WindowInner::from_pub(window_adapter.window()).show_popup(
+            popup,
+            position,
+            options,
+            parent_item,
+        );
     }
     /// Close the current popup
+    #[no_mangle]
     pub unsafe extern "C" fn slint_windowrc_close_popup(handle: *const WindowAdapterRcOpaque) {
         let window_adapter = &*(handle as *const Rc<dyn WindowAdapter>);
         WindowInner::from_pub(window_adapter.window()).close_popup();
@@ -1013,6 +1812,67 @@ fn call(&self) -> CloseRequestResponse {
         handle: *const WindowAdapterRcOpaque,
     ) -> bool {
         let window_adapter = &*(handle as *const Rc<dyn WindowAdapter>);
-        window_adapter.dark_color_scheme()
+        WindowInner::from_pub(window_adapter.window()).dark_color_scheme()
+    }
+
+    /// Returns the color scheme of the window
+    #[no_mangle]
+    pub unsafe extern "C" fn slint_windowrc_color_scheme(
+        handle: *const WindowAdapterRcOpaque,
+    ) -> crate::items::ColorScheme {
+        let window_adapter = &*(handle as *const Rc<dyn WindowAdapter>);
+        WindowInner::from_pub(window_adapter.window()).color_scheme()
     }
+
+    /// Overrides the color scheme of the window
+    #[no_mangle]
+    pub unsafe extern "C" fn slint_windowrc_set_color_scheme(
+        handle: *const WindowAdapterRcOpaque,
+        scheme: crate::items::ColorScheme,
+    ) {
+        let window_adapter = &*(handle as *const Rc<dyn WindowAdapter>);
+        WindowInner::from_pub(window_adapter.window()).set_color_scheme(scheme)
+    }
+}
+
+#[test]
+fn non_resizable_window_size_limits() {
+    use crate::layout::LayoutInfo;
+    let constraints_horizontal = LayoutInfo {
+        min: 50 as Coord,
+        preferred: 100 as Coord,
+        max: 200 as Coord,
+        ..LayoutInfo::default()
+    };
+    let constraints_vertical = LayoutInfo {
+        min: 20 as Coord,
+        preferred: 40 as Coord,
+        max: 80 as Coord,
+        ..LayoutInfo::default()
+    };
+
+    // A resizable window can be resized within the constraints of the layout
+    assert_eq!(
+        window_size_limits(constraints_horizontal, constraints_vertical, None),
+        (LogicalSize::new(50 as Coord, 20 as Coord), LogicalSize::new(200 as Coord, 80 as Coord))
+    );
+
+    // A window that isn't resizable keeps the size of its width and height properties, when they change too
+    let size = LogicalSize::new(120 as Coord, 60 as Coord);
+    assert_eq!(
+        window_size_limits(constraints_horizontal, constraints_vertical, Some(size)),
+        (size, size)
+    );
+    let size = LogicalSize::new(150 as Coord, 30 as Coord);
+    assert_eq!(
+        window_size_limits(constraints_horizontal, constraints_vertical, Some(size)),
+        (size, size)
+    );
+
+    // Until its size is set, it has the preferred size of its layout
+    let preferred = LogicalSize::new(100 as Coord, 40 as Coord);
+    assert_eq!(
+        window_size_limits(constraints_horizontal, constraints_vertical, Some(LogicalSize::zero())),
+        (preferred, preferred)
+    );
 }
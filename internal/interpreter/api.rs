@@ -14,7 +14,9 @@
 use std::rc::Rc;
 
 #[doc(inline)]
-pub use i_slint_compiler::diagnostics::{Diagnostic, DiagnosticLevel};
+pub use i_slint_compiler::diagnostics::{Diagnostic, DiagnosticCode, DiagnosticLevel};
+#[doc(inline)]
+pub use i_slint_compiler::ResourceSandbox;
 
 pub use i_slint_core::api::*;
 
@@ -226,6 +228,7 @@ fn try_from(v: Value) -> Result<$ty, Self::Error> {
 declare_value_conversion!(PathData => [PathData]);
 declare_value_conversion!(EasingCurve => [i_slint_core::animations::EasingCurve]);
 declare_value_conversion!(LayoutCache => [SharedVector<f32>] );
+declare_value_conversion!(Model => [ModelRc<Value>] );
 
 /// Implement From / TryFrom for Value that convert a `struct` to/from `Value::Object`
 macro_rules! declare_value_struct_conversion {
@@ -256,12 +259,16 @@ fn try_from(v: Value) -> Result<$name, Self::Error> {
 }
 
 declare_value_struct_conversion!(struct i_slint_core::model::StandardListViewItem { text });
+declare_value_struct_conversion!(struct i_slint_core::model::StandardTreeViewItem { text, level, has_children, expanded });
 declare_value_struct_conversion!(struct i_slint_core::properties::StateInfo { current_state, previous_state, change_time });
 declare_value_struct_conversion!(struct i_slint_core::input::KeyboardModifiers { control, alt, shift, meta });
 declare_value_struct_conversion!(struct i_slint_core::input::KeyEvent { text, modifiers, ..Default::default() });
 declare_value_struct_conversion!(struct i_slint_core::layout::LayoutInfo { min, max, min_percent, max_percent, preferred, stretch });
 declare_value_struct_conversion!(struct i_slint_core::graphics::Point { x, y, ..Default::default()});
 declare_value_struct_conversion!(struct i_slint_core::items::PointerEvent { kind, button });
+declare_value_struct_conversion!(struct i_slint_core::items::PointerScrollEvent { delta_x, delta_y });
+declare_value_struct_conversion!(struct i_slint_core::items::DropEvent { mime_type, data, position });
+declare_value_struct_conversion!(struct i_slint_core::items::GestureEvent { center, scale, rotation, translation, velocity });
 
 /// Implement From / TryFrom for Value that convert an `enum` to/from `Value::EnumerationValue`
 ///
@@ -367,6 +374,86 @@ fn try_from(v: Value) -> Result<i_slint_core::lengths::LogicalLength, Self::Erro
     }
 }
 
+/// Wraps a model whose rows convert to and from [`Value`], such as a model of
+/// [`SharedString`] or of a struct converted with [`Struct`], so that it can be set as the value
+/// of an array property.
+///
+/// The rows are converted when they are accessed, so the model is not copied. The changes of the
+/// wrapped model are shown by the component, and the rows changed by the .slint code, for example
+/// by a two-way binding to the `model` of a `for`, are converted and set on the wrapped model.
+/// Rows that can't be converted are not set, and a warning is printed.
+///
+/// ## Examples
+///
+/// ```
+/// use slint_interpreter::{ComponentCompiler, ModelRc, SharedString, Value, ValueModelAdapter, VecModel};
+/// use std::rc::Rc;
+/// let code = r#"
+///     MyWin := Window {
+///         property <[string]> names;
+///         property <int> count: names.length;
+///     }
+/// "#;
+/// let definition = spin_on::spin_on(
+///     ComponentCompiler::default().build_from_source(code.into(), Default::default()));
+/// let instance = definition.unwrap().create();
+/// let names = Rc::new(VecModel::from(vec![SharedString::from("Alice")]));
+/// let model = ModelRc::new(ValueModelAdapter::new(names.clone()));
+/// instance.set_property("names", model.into()).unwrap();
+/// names.push("Bob".into());
+/// assert_eq!(instance.get_property("count").unwrap(), Value::from(2));
+/// ```
+pub struct ValueModelAdapter<M> {
+    wrapped_model: M,
+}
+
+impl<M> ValueModelAdapter<M> {
+    /// Creates a model of [`Value`] for the rows of `wrapped_model`.
+    pub fn new(wrapped_model: M) -> Self {
+        Self { wrapped_model }
+    }
+
+    /// Returns the wrapped model.
+    pub fn source_model(&self) -> &M {
+        &self.wrapped_model
+    }
+}
+
+impl<M> Model for ValueModelAdapter<M>
+where
+    M: Model + 'static,
+    M::Data: Into<Value> + TryFrom<Value>,
+{
+    type Data = Value;
+
+    fn row_count(&self) -> usize {
+        self.wrapped_model.row_count()
+    }
+
+    fn row_data(&self, row: usize) -> Option<Value> {
+        self.wrapped_model.row_data(row).map(Into::into)
+    }
+
+    fn set_row_data(&self, row: usize, data: Value) {
+        match M::Data::try_from(data) {
+            Ok(data) => self.wrapped_model.set_row_data(row, data),
+            Err(_) => i_slint_core::debug_log!(
+                "Cannot set the row {} of a model of {}: the value has the wrong type",
+                row,
+                core::any::type_name::<M::Data>()
+            ),
+        }
+    }
+
+    fn model_tracker(&self) -> &dyn i_slint_core::model::ModelTracker {
+        self.wrapped_model.model_tracker()
+    }
+
+    fn as_any(&self) -> &dyn core::any::Any {
+        self
+    }
+}
+
 /// Normalize the identifier to use dashes
 pub(crate) fn normalize_identifier(ident: &str) -> Cow<'_, str> {
     if ident.contains('_') {
@@ -438,12 +525,14 @@ pub struct ComponentCompiler {
 
 impl Default for ComponentCompiler {
     fn default() -> Self {
-        Self {
-            config: i_slint_compiler::CompilerConfiguration::new(
-                i_slint_compiler::generator::OutputFormat::Interpreter,
-            ),
-            diagnostics: vec![],
+        let mut config = i_slint_compiler::CompilerConfiguration::new(
+            i_slint_compiler::generator::OutputFormat::Interpreter,
+        );
+        if !i_slint_backend_selector::HAS_NATIVE_STYLE {
+            config.native_style_fallback =
+                Some(i_slint_compiler::native_style_lookalike(std::env::consts::OS));
         }
+        Self { config, diagnostics: vec![] }
     }
 }
 
@@ -473,13 +562,91 @@ pub fn style(&self) -> Option<&String> {
         self.config.style.as_ref()
     }
 
-    /// Sets the callback that will be invoked when loading imported .slint files. The specified
+    /// Sets the style that is used when the `native` style is selected.
+    ///
+    /// By default, this is only set when the native style is not available because Slint was
+    /// built without Qt, to the builtin style that looks the most like the native widgets of the
+    /// platform: `cupertino` on macOS, `fluent` on Windows, and `material` on the other systems.
+    pub fn set_native_style_fallback(&mut self, style: String) {
+        self.config.native_style_fallback = Some(style);
+    }
+
+    /// Returns the style that is used instead of the `native` style, or `None` if the native
+    /// style is available.
+    pub fn native_style_fallback(&self) -> Option<&String> {
+        self.config.native_style_fallback.as_ref()
+    }
+
+    /// Restricts the directories from which the compiled code may import files, fonts, and images,
+    /// and the size of the images, to compile .slint code that is not trusted, such as plugins or
+    /// user themes. Accessing a file outside of the sandbox is a compilation error.
+    ///
+    /// The sandbox applies to the files referenced by the code, but not to the main file passed to
+    /// [`Self::build_from_path()`], nor to the values set from the application.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use slint_interpreter::{ComponentCompiler, ResourceSandbox};
+    /// let mut compiler = ComponentCompiler::default();
+    /// compiler.set_sandbox(Some(ResourceSandbox {
+    ///     allowed_directories: vec!["/usr/share/my-app/themes".into()],
+    ///     max_image_size: Some((1024, 1024)),
+    /// }));
+    /// let code = r#"
+    ///     MyWin := Window {
+    ///         Image { source: @image-url("/etc/secret.png"); }
+    ///     }
+    /// "#;
+    /// let definition =
+    ///     spin_on::spin_on(compiler.build_from_source(code.into(), Default::default()));
+    /// assert!(definition.is_none());
+    /// ```
+    pub fn set_sandbox(&mut self, sandbox: Option<ResourceSandbox>) {
+        self.config.sandbox = sandbox;
+    }
+
+    /// Returns the sandbox set with [`Self::set_sandbox()`].
+    pub fn sandbox(&self) -> Option<&ResourceSandbox> {
+        self.config.sandbox.as_ref()
+    }
+
+    /// Sets the callback that will be invoked when loading .slint files. The specified
     /// `file_loader_callback` parameter will be called with a canonical file path as argument
     /// and is expected to return a future that, when resolved, provides the source code of the
     /// .slint file to be imported as a string.
     /// If an error is returned, then the build will abort with that error.
     /// If None is returned, it means the normal resolution algorithm will proceed as if the hook
     /// was not in place (i.e: load from the file system following the include paths)
+    ///
+    /// The callback is also invoked for the file passed to [`Self::build_from_path`]. Together with
+    /// relative imports, which are resolved relative to the path of the importing file even if it
+    /// doesn't exist on disk, this allows to load a whole set of .slint files from memory, such as
+    /// from a database or an archive.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use slint_interpreter::ComponentCompiler;
+    /// use std::collections::HashMap;
+    /// use std::path::PathBuf;
+    ///
+    /// let files: HashMap<PathBuf, String> = [
+    ///     ("/virtual/main.slint".into(), "import { Label } from \"label.slint\"; Main := Label {}".into()),
+    ///     ("/virtual/label.slint".into(), "export Label := Text { text: \"hello\"; }".into()),
+    /// ]
+    /// .into_iter()
+    /// .collect();
+    ///
+    /// let mut compiler = ComponentCompiler::default();
+    /// compiler.set_file_loader(move |path| {
+    ///     let source = files.get(path).cloned();
+    ///     Box::pin(async move { source.map(Ok) })
+    /// });
+    /// let definition = spin_on::spin_on(compiler.build_from_path("/virtual/main.slint"));
+    /// assert!(compiler.diagnostics().is_empty(), "{:?}", compiler.diagnostics());
+    /// assert_eq!(definition.unwrap().name(), "Main");
+    /// ```
     pub fn set_file_loader(
         &mut self,
         file_loader_fallback: impl Fn(
@@ -492,7 +659,44 @@ pub fn set_file_loader(
             Some(Rc::new(move |path| file_loader_fallback(Path::new(path.as_str()))));
     }
 
+    /// Calls `observer` with the path of each file loaded by the compiler, before the callback
+    /// set with [`Self::set_file_loader`], if any.
+    #[cfg(feature = "live-reload")]
+    pub(crate) fn observe_loaded_files(&mut self, observer: impl Fn(&Path) + 'static) {
+        let file_loader = self.config.open_import_fallback.take();
+        self.config.open_import_fallback = Some(Rc::new(move |path| {
+            observer(Path::new(path.as_str()));
+            match &file_loader {
+                Some(file_loader) => file_loader(path),
+                None => Box::pin(async { None }),
+            }
+        }));
+    }
+
     /// Returns the diagnostics that were produced in the last call to [`Self::build_from_path`] or [`Self::build_from_source`].
+    ///
+    /// Each diagnostic has a level, a message, the range in the source file it applies to, and a
+    /// [`DiagnosticCode`] identifying the kind of problem.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use slint_interpreter::{ComponentCompiler, DiagnosticCode, DiagnosticLevel};
+    /// let code = r#"
+    ///     MyWin := Window {
+    ///         property <int> count: true;
+    ///     }
+    /// "#;
+    /// let mut compiler = ComponentCompiler::default();
+    /// let definition =
+    ///     spin_on::spin_on(compiler.build_from_source(code.into(), Default::default()));
+    /// assert!(definition.is_none());
+    /// let diagnostic = &compiler.diagnostics()[0];
+    /// assert_eq!(diagnostic.level(), DiagnosticLevel::Error);
+    /// assert_eq!(diagnostic.code(), DiagnosticCode::TypeMismatch);
+    /// assert_eq!(diagnostic.line_column().0, 3);
+    /// assert_eq!(diagnostic.end_line_column().0, 3);
+    /// ```
     pub fn diagnostics(&self) -> &Vec<Diagnostic> {
         &self.diagnostics
     }
@@ -519,7 +723,18 @@ pub async fn build_from_path<P: AsRef<Path>>(
         path: P,
     ) -> Option<ComponentDefinition> {
         let path = path.as_ref();
-        let source = match i_slint_compiler::diagnostics::load_from_path(path) {
+        let source = match &self.config.open_import_fallback {
+            Some(file_loader) if path != Path::new("-") => {
+                match file_loader(path.to_string_lossy().into()).await {
+                    Some(source) => {
+                        source.map_err(|err| i_slint_compiler::diagnostics::load_error(path, err))
+                    }
+                    None => i_slint_compiler::diagnostics::load_from_path(path),
+                }
+            }
+            _ => i_slint_compiler::diagnostics::load_from_path(path),
+        };
+        let source = match source {
             Ok(s) => s,
             Err(d) => {
                 self.diagnostics = vec![d];
@@ -563,6 +778,30 @@ pub async fn build_from_source(
     }
 }
 
+/// The signature of a callback declared in a .slint file.
+///
+/// It is returned by [`ComponentDefinition::callback_signatures()`] and [`ComponentDefinition::global_callback_signatures()`].
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct CallbackSignature {
+    /// The types of the arguments of the callback
+    pub arguments: Vec<ValueType>,
+    /// The type of the return value. This is [`ValueType::Void`] if the callback doesn't return a value.
+    pub return_type: ValueType,
+}
+
+impl CallbackSignature {
+    fn from_lang_type(ty: LangType) -> Option<Self> {
+        match ty {
+            LangType::Callback { args, return_type } => Some(Self {
+                arguments: args.into_iter().map(ValueType::from).collect(),
+                return_type: return_type.map_or(ValueType::Void, |ty| (*ty).into()),
+            }),
+            _ => None,
+        }
+    }
+}
+
 /// ComponentDefinition is a representation of a compiled component from .slint markup.
 ///
 /// It can be constructed from a .slint file using the [`ComponentCompiler::build_from_path`] or [`ComponentCompiler::build_from_source`] functions.
@@ -620,6 +859,21 @@ pub fn properties_and_callbacks(
         self.inner.unerase(guard).properties()
     }
 
+    /// List of publicly declared properties or callback in the exported global singleton specified
+    /// by its name.
+    ///
+    /// This is internal because it exposes the `Type` from compilerlib.
+    #[doc(hidden)]
+    pub fn global_properties_and_callbacks(
+        &self,
+        global_name: &str,
+    ) -> Option<impl Iterator<Item = (String, i_slint_compiler::langtype::Type)> + '_> {
+        // We create here a 'static guard, because unfortunately the returned type would be restricted to the guard lifetime
+        // which is not required, but this is safe because there is only one instance of the unerased type
+        let guard = unsafe { generativity::Guard::new(generativity::Id::new()) };
+        self.inner.unerase(guard).global_properties(global_name)
+    }
+
     /// List of publicly declared properties.
     pub fn properties(&self) -> impl Iterator<Item = (String, ValueType)> + '_ {
         // We create here a 'static guard, because unfortunately the returned type would be restricted to the guard lifetime
@@ -694,6 +948,92 @@ pub fn global_callbacks(&self, global_name: &str) -> Option<impl Iterator<Item =
         })
     }
 
+    /// Returns the names and the signatures of all publicly declared callbacks.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use slint_interpreter::{ComponentCompiler, ValueType};
+    /// let code = r#"
+    ///     export Demo := Window {
+    ///         callback compute(string, int) -> bool;
+    ///     }
+    /// "#;
+    /// let mut compiler = ComponentCompiler::default();
+    /// let definition = spin_on::spin_on(
+    ///     compiler.build_from_source(code.into(), Default::default()));
+    /// let (name, signature) = definition.unwrap().callback_signatures().next().unwrap();
+    /// assert_eq!(name, "compute");
+    /// assert_eq!(signature.arguments, [ValueType::String, ValueType::Number]);
+    /// assert_eq!(signature.return_type, ValueType::Bool);
+    /// ```
+    pub fn callback_signatures(&self) -> impl Iterator<Item = (String, CallbackSignature)> + '_ {
+        // We create here a 'static guard, because unfortunately the returned type would be restricted to the guard lifetime
+        // which is not required, but this is safe because there is only one instance of the unerased type
+        let guard = unsafe { generativity::Guard::new(generativity::Id::new()) };
+        self.inner.unerase(guard).properties().filter_map(|(prop_name, prop_type)| {
+            CallbackSignature::from_lang_type(prop_type).map(|signature| (prop_name, signature))
+        })
+    }
+
+    /// Returns the names and the signatures of the publicly declared callbacks in the exported global
+    /// singleton specified by its name.
+    pub fn global_callback_signatures(
+        &self,
+        global_name: &str,
+    ) -> Option<impl Iterator<Item = (String, CallbackSignature)> + '_> {
+        // We create here a 'static guard, because unfortunately the returned type would be restricted to the guard lifetime
+        // which is not required, but this is safe because there is only one instance of the unerased type
+        let guard = unsafe { generativity::Guard::new(generativity::Id::new()) };
+        self.inner.unerase(guard).global_properties(global_name).map(|iter| {
+            iter.filter_map(|(prop_name, prop_type)| {
+                CallbackSignature::from_lang_type(prop_type).map(|signature| (prop_name, signature))
+            })
+        })
+    }
+
+    /// Returns the names of the structs exported from the .slint file, with the names and types of
+    /// their fields.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use slint_interpreter::{ComponentCompiler, ValueType};
+    /// let code = r#"
+    ///     export struct Point := { x: length, label: string }
+    ///     export Demo := Window { property <Point> point; }
+    /// "#;
+    /// let mut compiler = ComponentCompiler::default();
+    /// let definition = spin_on::spin_on(
+    ///     compiler.build_from_source(code.into(), Default::default()));
+    /// let (name, fields) = definition.unwrap().structs().next().unwrap();
+    /// assert_eq!(name, "Point");
+    /// assert_eq!(fields, [("label".into(), ValueType::String), ("x".into(), ValueType::Number)]);
+    /// ```
+    pub fn structs(&self) -> impl Iterator<Item = (String, Vec<(String, ValueType)>)> + '_ {
+        // We create here a 'static guard, because unfortunately the returned type would be restricted to the guard lifetime
+        // which is not required, but this is safe because there is only one instance of the unerased type
+        let guard = unsafe { generativity::Guard::new(generativity::Id::new()) };
+        self.inner.unerase(guard).exported_types().filter_map(|(name, ty)| match ty {
+            LangType::Struct { fields, .. } => Some((
+                name.clone(),
+                fields.iter().map(|(name, ty)| (name.clone(), ty.clone().into())).collect(),
+            )),
+            _ => None,
+        })
+    }
+
+    /// Returns the names of the enums exported from the .slint file, with the names of their values.
+    pub fn enums(&self) -> impl Iterator<Item = (String, Vec<String>)> + '_ {
+        // We create here a 'static guard, because unfortunately the returned type would be restricted to the guard lifetime
+        // which is not required, but this is safe because there is only one instance of the unerased type
+        let guard = unsafe { generativity::Guard::new(generativity::Id::new()) };
+        self.inner.unerase(guard).exported_types().filter_map(|(name, ty)| match ty {
+            LangType::Enumeration(enumeration) => Some((name.clone(), enumeration.values.clone())),
+            _ => None,
+        })
+    }
+
     /// The name of this Component as written in the .slint file
     pub fn name(&self) -> &str {
         // We create here a 'static guard, because unfortunately the returned type would be restricted to the guard lifetime
@@ -739,6 +1079,22 @@ pub fn definition(&self) -> ComponentDefinition {
         ComponentDefinition { inner: self.inner.unerase(guard).description().into() }
     }
 
+    /// Creates another instance of the same component, in its own window, which shares the global
+    /// singletons with this instance. Use this to show several windows of the same application.
+    ///
+    /// The new instance holds a reference to the globals, so this instance can be dropped before it.
+    pub fn create_sharing_globals(&self) -> ComponentInstance {
+        generativity::make_guard!(guard);
+        let comp = self.inner.unerase(guard);
+        ComponentInstance {
+            inner: comp.description().create_sharing_globals_with(
+                comp.borrow(),
+                #[cfg(target_arch = "wasm32")]
+                "canvas".into(),
+            ),
+        }
+    }
+
     /// Return the value for a public property of this component.
     ///
     /// ## Examples
@@ -772,6 +1128,48 @@ pub fn set_property(&self, name: &str, value: Value) -> Result<(), SetPropertyEr
         comp.description().set_property(comp.borrow(), &normalize_identifier(name), value)
     }
 
+    /// Calls `callback` with the new value every time the public property with the given name changes.
+    ///
+    /// The callback is called from the event loop, after the property changed. Setting the property
+    /// several times in a row results in a single call with the last value.
+    /// The observation stops when the returned [`PropertyObserver`] is dropped.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use slint_interpreter::{ComponentDefinition, ComponentCompiler, Value, SharedString};
+    /// let code = r#"
+    ///     MyWin := Window {
+    ///         property <int> my_property: 42;
+    ///     }
+    /// "#;
+    /// let mut compiler = ComponentCompiler::default();
+    /// let definition = spin_on::spin_on(
+    ///     compiler.build_from_source(code.into(), Default::default()));
+    /// let instance = definition.unwrap().create();
+    /// let _observer = instance.on_property_changed("my_property", |value| {
+    ///     println!("my_property changed to {:?}", value);
+    /// }).unwrap();
+    /// ```
+    pub fn on_property_changed(
+        &self,
+        name: &str,
+        mut callback: impl FnMut(&Value) + 'static,
+    ) -> Result<PropertyObserver, GetPropertyError> {
+        let name = normalize_identifier(name).into_owned();
+        // Report an error right away if there is no such property
+        self.get_property(&name)?;
+        let weak = self.as_weak();
+        Ok(PropertyObserver::new(
+            move || weak.upgrade().and_then(|instance| instance.get_property(&name).ok()),
+            move |value| {
+                if let Some(value) = value {
+                    callback(value)
+                }
+            },
+        ))
+    }
+
     /// Set a handler for the callback with the given name. A callback with that
     /// name must be defined in the document otherwise an error will be returned.
     ///
@@ -819,6 +1217,9 @@ pub fn set_callback(
 
     /// Call the given callback with the arguments
     ///
+    /// The arguments are checked against the declaration of the callback: an error is returned
+    /// if their number or their type doesn't match.
+    ///
     /// ## Examples
     /// See the documentation of [`Self::set_callback`] for an example
     pub fn invoke_callback(
@@ -828,11 +1229,56 @@ pub fn invoke_callback(
     ) -> Result<Value, InvokeCallbackError> {
         generativity::make_guard!(guard);
         let comp = self.inner.unerase(guard);
+        let name = normalize_identifier(name);
+        if let Some((_, ty)) =
+            comp.description().properties().find(|(n, _)| normalize_identifier(n) == name)
+        {
+            check_callback_arguments(&ty, args)?;
+        }
         comp.description()
-            .invoke_callback(comp.borrow(), &normalize_identifier(name), args)
+            .invoke_callback(comp.borrow(), &name, args)
             .map_err(|()| InvokeCallbackError::NoSuchCallback)
     }
 
+    /// Call the callback with the arguments, like [`Self::invoke_callback`]. The callback of an exported
+    /// global singleton is specified with the name of the global, followed by a dot and the name of the
+    /// callback, as in the .slint code.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use slint_interpreter::{ComponentCompiler, InvokeCallbackError, Value, SharedString};
+    /// let code = r#"
+    ///     export global Logic := {
+    ///         callback double(int) -> int;
+    ///     }
+    ///     MyWin := Window {
+    ///         callback clicked();
+    ///     }
+    /// "#;
+    /// let definition = spin_on::spin_on(
+    ///     ComponentCompiler::default().build_from_source(code.into(), Default::default()));
+    /// let instance = definition.unwrap().create();
+    /// instance.set_global_callback("Logic", "double", |args| {
+    ///     Value::from(2. * f64::try_from(args[0].clone()).unwrap())
+    /// }).unwrap();
+    /// assert_eq!(instance.invoke("Logic.double", &[Value::from(21)]), Ok(Value::from(42)));
+    /// assert_eq!(
+    ///     instance.invoke("Logic.double", &[SharedString::from("21").into()]),
+    ///     Err(InvokeCallbackError::WrongArgumentType { index: 0 })
+    /// );
+    /// assert_eq!(
+    ///     instance.invoke("clicked", &[Value::from(1)]),
+    ///     Err(InvokeCallbackError::WrongArgumentCount { expected: 0, actual: 1 })
+    /// );
+    /// ```
+    pub fn invoke(&self, name: &str, args: &[Value]) -> Result<Value, InvokeCallbackError> {
+        match name.split_once('.') {
+            Some((global, callback)) => self.invoke_global_callback(global, callback, args),
+            None => self.invoke_callback(name, args),
+        }
+    }
+
     /// Return the value for a property within an exported global singleton used by this component.
     ///
     /// The `global` parameter is the exported name of the global singleton. The `property` argument
@@ -862,14 +1308,8 @@ pub fn get_global_property(
         global: &str,
         property: &str,
     ) -> Result<Value, GetPropertyError> {
-        generativity::make_guard!(guard);
-        let comp = self.inner.unerase(guard);
-        comp.description()
-            .get_global(comp.borrow(), &normalize_identifier(global))
-            .map_err(|()| GetPropertyError::NoSuchProperty)? // FIXME: should there be a NoSuchGlobal error?
-            .as_ref()
-            .get_property(&normalize_identifier(property))
-            .map_err(|()| GetPropertyError::NoSuchProperty)
+        // FIXME: should there be a NoSuchGlobal error?
+        self.global(global).ok_or(GetPropertyError::NoSuchProperty)?.get_property(property)
     }
 
     /// Set the value for a property within an exported global singleton used by this component.
@@ -879,13 +1319,8 @@ pub fn set_global_property(
         property: &str,
         value: Value,
     ) -> Result<(), SetPropertyError> {
-        generativity::make_guard!(guard);
-        let comp = self.inner.unerase(guard);
-        comp.description()
-            .get_global(comp.borrow(), &normalize_identifier(global))
-            .map_err(|()| SetPropertyError::NoSuchProperty)? // FIXME: should there be a NoSuchGlobal error?
-            .as_ref()
-            .set_property(&normalize_identifier(property), value)
+        // FIXME: should there be a NoSuchGlobal error?
+        self.global(global).ok_or(SetPropertyError::NoSuchProperty)?.set_property(property, value)
     }
 
     /// Set a handler for the callback in the exported global singleton. A callback with that
@@ -927,14 +1362,8 @@ pub fn set_global_callback(
         name: &str,
         callback: impl Fn(&[Value]) -> Value + 'static,
     ) -> Result<(), SetCallbackError> {
-        generativity::make_guard!(guard);
-        let comp = self.inner.unerase(guard);
-        comp.description()
-            .get_global(comp.borrow(), &normalize_identifier(global))
-            .map_err(|()| SetCallbackError::NoSuchCallback)? // FIXME: should there be a NoSuchGlobal error?
-            .as_ref()
-            .set_callback_handler(&normalize_identifier(name), Box::new(callback))
-            .map_err(|()| SetCallbackError::NoSuchCallback)
+        // FIXME: should there be a NoSuchGlobal error?
+        self.global(global).ok_or(SetCallbackError::NoSuchCallback)?.set_callback(name, callback)
     }
 
     /// Call the given callback within a global singleton with the arguments
@@ -947,13 +1376,169 @@ pub fn invoke_global_callback(
         callback_name: &str,
         args: &[Value],
     ) -> Result<Value, InvokeCallbackError> {
+        // FIXME: should there be a NoSuchGlobal error?
+        self.global(global)
+            .ok_or(InvokeCallbackError::NoSuchCallback)?
+            .invoke_callback(callback_name, args)
+    }
+
+    /// Evaluates the .slint expression in `source` in the scope of the root element of this
+    /// component, and returns its value.
+    ///
+    /// The expression can use the properties and callbacks of the root element, the elements with an id,
+    /// and the globals, like a binding of the root element. This is meant for debugging tools, for example
+    /// to watch the value of an expression in a running application.
+    ///
+    /// Returns the diagnostics if the expression is not valid. The elements within a `for` or `if`, and
+    /// the properties that the compiler optimized away because they are not used, can't be accessed.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use slint_interpreter::{ComponentCompiler, Value};
+    /// let code = r#"
+    ///     export global Settings := {
+    ///         property <int> volume: 3;
+    ///     }
+    ///     MyWin := Window {
+    ///         property <int> counter: 4;
+    ///         property <int> volume: Settings.volume;
+    ///     }
+    /// "#;
+    /// let definition = spin_on::spin_on(
+    ///     ComponentCompiler::default().build_from_source(code.into(), Default::default()));
+    /// let instance = definition.unwrap().create();
+    /// let value = instance.evaluate_expression("counter * 10 + Settings.volume").unwrap();
+    /// assert_eq!(value, Value::from(43));
+    /// assert!(instance.evaluate_expression("counter +").is_err());
+    /// ```
+    pub fn evaluate_expression(&self, source: &str) -> Result<Value, Vec<Diagnostic>> {
         generativity::make_guard!(guard);
         let comp = self.inner.unerase(guard);
         comp.description()
-            .get_global(comp.borrow(), &normalize_identifier(global))
-            .map_err(|()| InvokeCallbackError::NoSuchCallback)? // FIXME: should there be a NoSuchGlobal error?
+            .evaluate_expression(comp.borrow(), source.into())
+            .map_err(|diag| diag.into_iter().collect())
+    }
+
+    /// Returns a handle to the exported global singleton with the given name, or None if this component
+    /// doesn't use such a global.
+    ///
+    /// The handle can be used to read and write the properties of the global, and to set and invoke its
+    /// callbacks, like the generated code does for the globals of compiled components.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use slint_interpreter::{ComponentDefinition, ComponentCompiler, Value, SharedString};
+    /// let code = r#"
+    ///     export global Settings := {
+    ///         property <int> volume: 42;
+    ///     }
+    ///     MyWin := Window {
+    ///         property <int> volume: Settings.volume;
+    ///     }
+    /// "#;
+    /// let definition = spin_on::spin_on(
+    ///     ComponentCompiler::default().build_from_source(code.into(), Default::default()));
+    /// let instance = definition.unwrap().create();
+    /// let settings = instance.global("Settings").unwrap();
+    /// assert_eq!(settings.get_property("volume").unwrap(), Value::from(42));
+    /// settings.set_property("volume", Value::from(11)).unwrap();
+    /// assert_eq!(instance.get_property("volume").unwrap(), Value::from(11));
+    /// ```
+    pub fn global(&self, name: &str) -> Option<GlobalInstance> {
+        generativity::make_guard!(guard);
+        let comp = self.inner.unerase(guard);
+        let inner =
+            comp.description().get_global(comp.borrow(), &normalize_identifier(name)).ok()?;
+        let callback_types = comp
+            .description()
+            .global_properties(name)
+            .map(|iter| {
+                iter.filter(|(_, ty)| matches!(ty, LangType::Callback { .. }))
+                    .map(|(name, ty)| (normalize_identifier(&name).into_owned(), ty))
+                    .collect()
+            })
+            .unwrap_or_default();
+        Some(GlobalInstance { inner, callback_types })
+    }
+}
+
+/// Checks that the arguments match the parameters of the callback of type `ty`
+fn check_callback_arguments(ty: &LangType, args: &[Value]) -> Result<(), InvokeCallbackError> {
+    let params = match ty {
+        LangType::Callback { args: params, .. } => params,
+        _ => return Err(InvokeCallbackError::NoSuchCallback),
+    };
+    if params.len() != args.len() {
+        return Err(InvokeCallbackError::WrongArgumentCount {
+            expected: params.len(),
+            actual: args.len(),
+        });
+    }
+    for (index, (param, arg)) in params.iter().zip(args).enumerate() {
+        let expected = ValueType::from(param.clone());
+        // The internal types, such as enumerations, are not checked
+        if expected != ValueType::Other && expected != arg.value_type() {
+            return Err(InvokeCallbackError::WrongArgumentType { index });
+        }
+    }
+    Ok(())
+}
+
+/// A handle to an exported global singleton of a [`ComponentInstance`], returned by [`ComponentInstance::global()`].
+///
+/// The global is shared by all the components of the instance, and the handle keeps it alive.
+#[derive(Clone)]
+pub struct GlobalInstance {
+    inner: core::pin::Pin<Rc<dyn crate::global_component::GlobalComponent>>,
+    /// The normalized names and the types of the public callbacks
+    callback_types: Vec<(String, LangType)>,
+}
+
+impl GlobalInstance {
+    /// Return the value for a public property of this global.
+    pub fn get_property(&self, name: &str) -> Result<Value, GetPropertyError> {
+        self.inner
             .as_ref()
-            .invoke_callback(&normalize_identifier(callback_name), args)
+            .get_property(&normalize_identifier(name))
+            .map_err(|()| GetPropertyError::NoSuchProperty)
+    }
+
+    /// Set the value for a public property of this global.
+    pub fn set_property(&self, name: &str, value: Value) -> Result<(), SetPropertyError> {
+        self.inner.as_ref().set_property(&normalize_identifier(name), value)
+    }
+
+    /// Set a handler for the callback with the given name. A callback with that
+    /// name must be defined in the global otherwise an error will be returned.
+    pub fn set_callback(
+        &self,
+        name: &str,
+        callback: impl Fn(&[Value]) -> Value + 'static,
+    ) -> Result<(), SetCallbackError> {
+        self.inner
+            .as_ref()
+            .set_callback_handler(&normalize_identifier(name), Box::new(callback))
+            .map_err(|()| SetCallbackError::NoSuchCallback)
+    }
+
+    /// Call the given callback with the arguments
+    ///
+    /// The arguments are checked against the declaration of the callback: an error is returned
+    /// if their number or their type doesn't match.
+    pub fn invoke_callback(
+        &self,
+        name: &str,
+        args: &[Value],
+    ) -> Result<Value, InvokeCallbackError> {
+        let name = normalize_identifier(name);
+        if let Some((_, ty)) = self.callback_types.iter().find(|(n, _)| *n == name) {
+            check_callback_arguments(ty, args)?;
+        }
+        self.inner
+            .as_ref()
+            .invoke_callback(&name, args)
             .map_err(|()| InvokeCallbackError::NoSuchCallback)
     }
 }
@@ -1053,6 +1638,20 @@ pub enum InvokeCallbackError {
     /// There is no callback with the given name
     #[error("no such callback")]
     NoSuchCallback,
+    /// The number of arguments doesn't match the declaration of the callback
+    #[error("wrong number of arguments: expected {expected}, got {actual}")]
+    WrongArgumentCount {
+        /// The number of arguments of the callback
+        expected: usize,
+        /// The number of arguments that were passed
+        actual: usize,
+    },
+    /// The type of an argument doesn't match the declaration of the callback
+    #[error("argument {index} has the wrong type")]
+    WrongArgumentType {
+        /// The index of the argument
+        index: usize,
+    },
 }
 
 /// Enters the main event loop. This is necessary in order to receive
@@ -1285,6 +1884,41 @@ fn globals() {
     );
 }
 
+#[test]
+fn create_sharing_globals() {
+    i_slint_backend_testing::init();
+    let mut compiler = ComponentCompiler::default();
+    compiler.set_style("fluent".into());
+    let definition = spin_on::spin_on(
+        compiler.build_from_source(
+            r#"
+    export global Settings := {
+        property <int> value: 21;
+        property <int> doubled: value * 2;
+    }
+    export Dummy := Rectangle {
+        property <int> value: Settings.doubled;
+    }"#
+            .into(),
+            "".into(),
+        ),
+    )
+    .unwrap();
+
+    let first = definition.create();
+    let second = first.create_sharing_globals();
+    let independent = definition.create();
+    assert_eq!(first.set_global_property("Settings", "value", Value::Number(5.)), Ok(()));
+    assert_eq!(second.get_property("value"), Ok(Value::Number(10.)));
+    assert_eq!(independent.get_property("value"), Ok(Value::Number(42.)));
+
+    // The globals stay alive with the second instance
+    drop(first);
+    assert_eq!(second.set_global_property("Settings", "value", Value::Number(7.)), Ok(()));
+    assert_eq!(second.get_property("value"), Ok(Value::Number(14.)));
+    assert_eq!(second.get_global_property("Settings", "doubled"), Ok(Value::Number(14.)));
+}
+
 #[test]
 fn component_definition_struct_properties() {
     i_slint_backend_testing::init();
@@ -1335,6 +1969,321 @@ fn component_definition_struct_properties() {
     );
 }
 
+#[test]
+fn callback_signatures() {
+    i_slint_backend_testing::init();
+    let mut compiler = ComponentCompiler::default();
+    compiler.set_style("fluent".into());
+    let definition = spin_on::spin_on(
+        compiler.build_from_source(
+            r#"
+    export global Logic := {
+        property <int> factor: 2;
+        callback double(int) -> int;
+    }
+    export Dummy := Rectangle {
+        property <int> factor: Logic.factor;
+        callback compute(string, int) -> bool;
+        callback clicked();
+    }"#
+            .into(),
+            "".into(),
+        ),
+    )
+    .unwrap();
+
+    let signatures = definition.callback_signatures().collect::<HashMap<_, _>>();
+    assert_eq!(signatures.len(), 2);
+    assert_eq!(signatures["compute"].arguments, [ValueType::String, ValueType::Number]);
+    assert_eq!(signatures["compute"].return_type, ValueType::Bool);
+    assert!(signatures["clicked"].arguments.is_empty());
+    assert_eq!(signatures["clicked"].return_type, ValueType::Void);
+
+    assert!(definition.global_callback_signatures("Unknown").is_none());
+    let global_signatures =
+        definition.global_callback_signatures("Logic").unwrap().collect::<Vec<_>>();
+    assert_eq!(global_signatures.len(), 1);
+    assert_eq!(global_signatures[0].0, "double");
+    assert_eq!(global_signatures[0].1.arguments, [ValueType::Number]);
+    assert_eq!(global_signatures[0].1.return_type, ValueType::Number);
+}
+
+#[test]
+fn global_instance() {
+    i_slint_backend_testing::init();
+    let mut compiler = ComponentCompiler::default();
+    compiler.set_style("fluent".into());
+    let definition = spin_on::spin_on(
+        compiler.build_from_source(
+            r#"
+    export global Logic := {
+        property <int> factor: 2;
+        callback double(int) -> int;
+    }
+    export Dummy := Rectangle {
+        property <int> factor: Logic.factor;
+    }"#
+            .into(),
+            "".into(),
+        ),
+    )
+    .unwrap();
+
+    let instance = definition.create();
+    assert!(instance.global("Unknown").is_none());
+    let logic = instance.global("Logic").unwrap();
+    assert_eq!(logic.get_property("unknown"), Err(GetPropertyError::NoSuchProperty));
+    assert_eq!(
+        logic.set_property("unknown", Value::from(1)),
+        Err(SetPropertyError::NoSuchProperty)
+    );
+    assert_eq!(logic.set_property("factor", Value::Bool(true)), Err(SetPropertyError::WrongType));
+    assert_eq!(logic.set_callback("unknown", |_| panic!()), Err(SetCallbackError::NoSuchCallback));
+    assert_eq!(logic.set_callback("factor", |_| panic!()), Err(SetCallbackError::NoSuchCallback));
+    assert_eq!(logic.invoke_callback("unknown", &[]), Err(InvokeCallbackError::NoSuchCallback));
+
+    logic.set_property("factor", Value::from(3)).unwrap();
+    assert_eq!(instance.get_property("factor"), Ok(Value::from(3)));
+    logic
+        .set_callback("double", |args| Value::from(2. * f64::try_from(args[0].clone()).unwrap()))
+        .unwrap();
+    assert_eq!(logic.invoke_callback("double", &[Value::from(4)]), Ok(Value::from(8)));
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn json_errors() {
+    use serde_json::json;
+    i_slint_backend_testing::init();
+    let mut compiler = ComponentCompiler::default();
+    compiler.set_style("fluent".into());
+    let definition = spin_on::spin_on(
+        compiler.build_from_source(
+            r#"
+    export global Palette := {
+        property <color> accent;
+        callback pick() -> color;
+    }
+    export Dummy := Rectangle {
+        property <string> name;
+        property <[{ label: string, count: int }]> items;
+        property <color> accent: Palette.accent;
+        callback fetch() -> int;
+        callback notify();
+    }"#
+            .into(),
+            "".into(),
+        ),
+    )
+    .unwrap();
+
+    let instance = definition.create();
+    assert_eq!(
+        instance.set_property_from_json("unknown", &json!("a")),
+        Err(SetPropertyError::NoSuchProperty)
+    );
+    // A callback is not a property
+    assert_eq!(
+        instance.set_property_from_json("fetch", &json!(1)),
+        Err(SetPropertyError::NoSuchProperty)
+    );
+    assert_eq!(
+        instance.set_property_from_json("name", &json!(42)),
+        Err(SetPropertyError::WrongType)
+    );
+    assert_eq!(
+        instance.set_property_from_json("items", &json!({ "label": "a" })),
+        Err(SetPropertyError::WrongType)
+    );
+    assert_eq!(
+        instance.set_property_from_json("items", &json!([{ "label": "a", "count": "1" }])),
+        Err(SetPropertyError::WrongType)
+    );
+    assert_eq!(
+        instance.set_property_from_json("items", &json!([{ "label": "a", "unknown": 1 }])),
+        Err(SetPropertyError::WrongType)
+    );
+    // The missing fields get their default value
+    instance.set_property_from_json("items", &json!([{ "label": "a" }])).unwrap();
+    assert_eq!(
+        instance.get_property("items").unwrap().to_json(),
+        Some(json!([{ "label": "a", "count": 0.0 }]))
+    );
+
+    assert_eq!(
+        instance.set_global_property_from_json("Unknown", "accent", &json!("#ff0000")),
+        Err(SetPropertyError::NoSuchProperty)
+    );
+    assert_eq!(
+        instance.set_global_property_from_json("Palette", "unknown", &json!("#ff0000")),
+        Err(SetPropertyError::NoSuchProperty)
+    );
+    assert_eq!(
+        instance.set_global_property_from_json("Palette", "accent", &json!("red")),
+        Err(SetPropertyError::WrongType)
+    );
+
+    assert_eq!(
+        definition.callback_return_value_from_json(None, "fetch", &json!(3)),
+        Some(Value::from(3))
+    );
+    assert_eq!(definition.callback_return_value_from_json(None, "fetch", &json!("3")), None);
+    assert_eq!(definition.callback_return_value_from_json(None, "unknown", &json!(3)), None);
+    assert_eq!(definition.callback_return_value_from_json(None, "name", &json!("a")), None);
+    assert_eq!(
+        definition.callback_return_value_from_json(None, "notify", &json!(null)),
+        Some(Value::Void)
+    );
+    assert_eq!(definition.callback_return_value_from_json(None, "notify", &json!(1)), None);
+    assert_eq!(
+        definition.callback_return_value_from_json(Some("Palette"), "pick", &json!(1)),
+        None
+    );
+    assert_eq!(
+        definition.callback_return_value_from_json(Some("Unknown"), "pick", &json!("#ff0000")),
+        None
+    );
+
+    // Values that can't be represented in JSON
+    assert_eq!(Value::Image(Image::default()).to_json(), None);
+    assert_eq!(Value::Number(f64::NAN).to_json(), None);
+}
+
+#[test]
+fn file_loader_errors() {
+    i_slint_backend_testing::init();
+    let mut compiler = ComponentCompiler::default();
+    compiler.set_style("fluent".into());
+    compiler.set_file_loader(|path| {
+        let source = match path.file_name().and_then(|name| name.to_str()) {
+            Some("main.slint") => {
+                Some(Ok("import { Label } from \"missing.slint\"; export Main := Label {}".into()))
+            }
+            Some("denied.slint") => {
+                Some(Err(std::io::Error::new(std::io::ErrorKind::PermissionDenied, "denied")))
+            }
+            _ => None,
+        };
+        Box::pin(async move { source })
+    });
+    let errors = |compiler: &ComponentCompiler| {
+        compiler
+            .diagnostics()
+            .iter()
+            .filter(|d| d.level() == DiagnosticLevel::Error)
+            .map(|d| (d.code(), d.message().to_owned()))
+            .collect::<Vec<_>>()
+    };
+
+    // The files that the loader doesn't provide are read from the disk
+    assert!(spin_on::spin_on(compiler.build_from_path("/virtual/main.slint")).is_none());
+    let main_errors = errors(&compiler);
+    assert!(
+        main_errors.iter().any(|(code, e)| *code == DiagnosticCode::ImportNotFound
+            && e.starts_with("Cannot find requested import \"missing.slint\"")),
+        "{:?}",
+        main_errors
+    );
+    assert!(spin_on::spin_on(compiler.build_from_path("/virtual/not-there.slint")).is_none());
+    let not_there_errors = errors(&compiler);
+    assert_eq!(not_there_errors.len(), 1);
+    assert_eq!(not_there_errors[0].0, DiagnosticCode::FileAccess);
+    assert!(not_there_errors[0].1.starts_with("Could not load"), "{}", not_there_errors[0].1);
+    assert!(spin_on::spin_on(compiler.build_from_path("/virtual/denied.slint")).is_none());
+    assert_eq!(
+        errors(&compiler),
+        [(
+            DiagnosticCode::FileAccess,
+            String::from("Could not load /virtual/denied.slint: denied")
+        )]
+    );
+}
+
+#[test]
+fn invoke_with_wrong_arguments() {
+    i_slint_backend_testing::init();
+    let mut compiler = ComponentCompiler::default();
+    compiler.set_style("fluent".into());
+    let definition = spin_on::spin_on(
+        compiler.build_from_source(
+            r#"
+    export global Logic := {
+        callback double(int) -> int;
+    }
+    export Dummy := Rectangle {
+        property <int> factor: 2;
+        callback compute(string, int) -> bool;
+    }"#
+            .into(),
+            "".into(),
+        ),
+    )
+    .unwrap();
+
+    let instance = definition.create();
+    instance.set_callback("compute", |args| Value::Bool(args[1] == Value::from(1))).unwrap();
+    assert_eq!(
+        instance.invoke_callback("compute", &[Value::from(1)]),
+        Err(InvokeCallbackError::WrongArgumentCount { expected: 2, actual: 1 })
+    );
+    assert_eq!(
+        instance.invoke_callback("compute", &[Value::from(1), Value::from(1)]),
+        Err(InvokeCallbackError::WrongArgumentType { index: 0 })
+    );
+    assert_eq!(
+        instance.invoke("compute", &[Value::String("a".into()), Value::from(1)]),
+        Ok(Value::Bool(true))
+    );
+    // A property is not a callback
+    assert_eq!(instance.invoke("factor", &[]), Err(InvokeCallbackError::NoSuchCallback));
+    assert_eq!(instance.invoke("unknown", &[]), Err(InvokeCallbackError::NoSuchCallback));
+
+    let logic = instance.global("Logic").unwrap();
+    assert_eq!(
+        logic.invoke_callback("double", &[Value::Bool(true)]),
+        Err(InvokeCallbackError::WrongArgumentType { index: 0 })
+    );
+    assert_eq!(
+        instance.invoke("Logic.double", &[]),
+        Err(InvokeCallbackError::WrongArgumentCount { expected: 1, actual: 0 })
+    );
+    assert_eq!(instance.invoke("Logic.unknown", &[]), Err(InvokeCallbackError::NoSuchCallback));
+    assert_eq!(instance.invoke("Unknown.double", &[]), Err(InvokeCallbackError::NoSuchCallback));
+}
+
+#[test]
+fn evaluate_expression_errors() {
+    i_slint_backend_testing::init();
+    let mut compiler = ComponentCompiler::default();
+    compiler.set_style("fluent".into());
+    let definition = spin_on::spin_on(
+        compiler.build_from_source(
+            r#"
+    export Dummy := Rectangle {
+        property <int> counter: 4;
+        if false: Rectangle { property <int> hidden: 3; }
+    }"#
+            .into(),
+            "".into(),
+        ),
+    )
+    .unwrap();
+
+    let instance = definition.create();
+    assert_eq!(instance.evaluate_expression("counter * 2").unwrap(), Value::from(8));
+    let errors = |source: &str| {
+        let diagnostics = instance.evaluate_expression(source).unwrap_err();
+        assert!(!diagnostics.is_empty());
+        assert!(diagnostics.iter().all(|d| d.level() == DiagnosticLevel::Error));
+        diagnostics.iter().map(|d| d.code()).collect::<Vec<_>>()
+    };
+    assert!(errors("counter +").contains(&DiagnosticCode::Syntax));
+    assert!(errors("counter + 1px").contains(&DiagnosticCode::TypeMismatch));
+    assert_eq!(errors("unknown + 1"), [DiagnosticCode::UnknownIdentifier]);
+    // The elements within an `if` are not accessible
+    errors("hidden");
+}
+
 #[test]
 fn component_definition_model_properties() {
     use i_slint_core::model::*;
@@ -1393,6 +2342,84 @@ fn check_model(val: Value, r: &[f64]) {
     check_model(instance.get_property("prop").unwrap(), &[]);
 }
 
+#[test]
+fn sandbox() {
+    i_slint_backend_testing::init();
+    let dir = std::env::temp_dir().join(format!("slint_sandbox_test_{}", std::process::id()));
+    let allowed = dir.join("allowed");
+    std::fs::create_dir_all(&allowed).unwrap();
+    let examples: PathBuf = [env!("CARGO_MANIFEST_DIR"), "..", "..", "examples"].iter().collect();
+    let small_png = examples.join("iot-dashboard").join("images").join("rooms.png"); // 14x14
+    let large_png = examples.join("gallery").join("thumbsup.png"); // 160x160
+    std::fs::copy(&small_png, allowed.join("small.png")).unwrap();
+    std::fs::copy(&small_png, allowed.join("replaced.png")).unwrap();
+    std::fs::copy(&large_png, allowed.join("large.png")).unwrap();
+    std::fs::copy(&small_png, dir.join("outside.png")).unwrap();
+    std::fs::write(allowed.join("broken.png"), "not an image").unwrap();
+    std::fs::write(dir.join("outside.ttf"), "").unwrap();
+
+    let mut compiler = ComponentCompiler::default();
+    compiler.set_style("fluent".into());
+    compiler.set_sandbox(Some(ResourceSandbox {
+        allowed_directories: vec![allowed.clone()],
+        max_image_size: Some((100, 100)),
+    }));
+    let mut build = |code: String| {
+        let definition = spin_on::spin_on(compiler.build_from_source(code, "".into()));
+        let errors = compiler
+            .diagnostics()
+            .iter()
+            .filter(|d| d.level() == DiagnosticLevel::Error)
+            .map(|d| d.message().to_owned())
+            .collect::<Vec<_>>();
+        (definition, errors)
+    };
+    let image_code = |path: &Path| {
+        format!(
+            "export Dummy := Rectangle {{ property <image> img: @image-url(\"{}\"); }}",
+            path.display()
+        )
+    };
+
+    let (definition, errors) = build(image_code(&allowed.join("small.png")));
+    assert_eq!(errors, Vec::<String>::new());
+    let img = definition.unwrap().create().get_property("img").unwrap();
+    assert_eq!(Image::try_from(img).unwrap().size(), [14, 14].into());
+
+    let (_, errors) = build(image_code(&dir.join("outside.png")));
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].ends_with("the file is outside of the allowed directories"), "{}", errors[0]);
+
+    let (_, errors) = build(image_code(&allowed.join("large.png")));
+    assert_eq!(errors.len(), 1);
+    assert!(
+        errors[0].ends_with("is 160x160 pixels, more than the maximum of 100x100"),
+        "{}",
+        errors[0]
+    );
+
+    // An image whose size can't be read is rejected
+    let (_, errors) = build(image_code(&allowed.join("broken.png")));
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].contains("its size can't be read"), "{}", errors[0]);
+
+    // The size is checked again when the image is loaded
+    let (definition, errors) = build(image_code(&allowed.join("replaced.png")));
+    assert_eq!(errors, Vec::<String>::new());
+    std::fs::copy(&large_png, allowed.join("replaced.png")).unwrap();
+    let img = definition.unwrap().create().get_property("img").unwrap();
+    assert_eq!(Image::try_from(img).unwrap().size(), [0, 0].into());
+
+    let (_, errors) = build(format!(
+        "import \"{}\"; export Dummy := Rectangle {{}}",
+        dir.join("outside.ttf").display()
+    ));
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].ends_with("the file is outside of the allowed directories"), "{}", errors[0]);
+
+    std::fs::remove_dir_all(&dir).ok();
+}
+
 #[cfg(feature = "ffi")]
 #[allow(missing_docs)]
 #[path = "ffi.rs"]
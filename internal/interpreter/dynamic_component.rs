@@ -340,6 +340,13 @@ pub struct ComponentDescription<'id> {
     /// Map of all exported global singletons and their index in the compiled_globals vector. The key
     /// is the normalized name of the global.
     exported_globals_by_name: BTreeMap<String, usize>,
+    /// The structs and enums exported from the .slint file, with their exported name.
+    /// Only set for the main component of the file.
+    exported_types: Vec<(String, Type)>,
+    /// The maximum size of the images loaded at runtime, from the sandbox of the compiler.
+    /// Only set for the main component and its globals: the other components use the one of
+    /// their top level instance.
+    pub(crate) max_image_size: Option<(u32, u32)>,
 }
 
 fn internal_properties_to_public<'a>(
@@ -393,6 +400,11 @@ pub fn global_properties(
             .map(|global| internal_properties_to_public(global.public_properties()))
     }
 
+    /// List the structs and enums exported from the .slint file
+    pub fn exported_types(&self) -> impl Iterator<Item = &(String, Type)> + '_ {
+        self.exported_types.iter()
+    }
+
     /// Instantiate a runtime component from this ComponentDescription
     pub fn create(
         self: Rc<Self>,
@@ -408,12 +420,48 @@ pub fn create(
         self.create_with_existing_window(&window_adapter)
     }
 
+    /// Instantiate a runtime component from this ComponentDescription, in a new window, which shares
+    /// the globals of `other`.
+    /// Panics if `other` is not an instance corresponding to this ComponentDescription.
+    pub fn create_sharing_globals_with(
+        self: Rc<Self>,
+        other: ComponentRefPin,
+        #[cfg(target_arch = "wasm32")] canvas_id: String,
+    ) -> vtable::VRc<ComponentVTable, ErasedComponentBox> {
+        if !core::ptr::eq((&self.ct) as *const _, other.get_vtable() as *const _) {
+            panic!("mismatch instance and vtable");
+        }
+        let globals = {
+            generativity::make_guard!(guard);
+            // Safety: we just verified that the component has the right vtable
+            let c = unsafe { InstanceRef::from_pin_ref(other, guard) };
+            c.component_type.extra_data_offset.apply(c.instance.get_ref()).globals.clone()
+        };
+
+        let window_adapter = i_slint_backend_selector::with_platform(|_b| {
+            #[cfg(not(target_arch = "wasm32"))]
+            return _b.create_window_adapter();
+            #[cfg(target_arch = "wasm32")]
+            i_slint_backend_winit::create_gl_window_with_canvas_id(canvas_id)
+        });
+
+        self.create_with_existing_window_and_globals(&window_adapter, globals)
+    }
+
     #[doc(hidden)]
     pub fn create_with_existing_window(
         self: Rc<Self>,
         window_adapter: &Rc<dyn WindowAdapter>,
     ) -> vtable::VRc<ComponentVTable, ErasedComponentBox> {
-        let component_ref = instantiate(self, None, window_adapter, Default::default());
+        self.create_with_existing_window_and_globals(window_adapter, Default::default())
+    }
+
+    fn create_with_existing_window_and_globals(
+        self: Rc<Self>,
+        window_adapter: &Rc<dyn WindowAdapter>,
+        globals: crate::global_component::GlobalStorage,
+    ) -> vtable::VRc<ComponentVTable, ErasedComponentBox> {
+        let component_ref = instantiate(self, None, window_adapter, globals);
         WindowInner::from_pub(component_ref.as_pin_ref().window_adapter().window())
             .set_component(&vtable::VRc::into_dyn(component_ref.clone()));
         component_ref.run_setup_code();
@@ -603,6 +651,64 @@ pub fn invoke_callback(
         }
     }
 
+    /// Evaluates the expression in the scope of the root element of the component instance.
+    ///
+    /// Returns the diagnostics if the expression could not be compiled, or if it references
+    /// properties that are not available in the instance.
+    pub fn evaluate_expression(
+        &self,
+        component: ComponentRefPin,
+        source: String,
+    ) -> Result<Value, BuildDiagnostics> {
+        if !core::ptr::eq((&self.ct) as *const _, component.get_vtable() as *const _) {
+            let mut diag = BuildDiagnostics::default();
+            diag.push_error_with_span("mismatch instance and vtable".into(), Default::default());
+            return Err(diag);
+        }
+        let mut diag = BuildDiagnostics::default();
+        let expr =
+            i_slint_compiler::resolve_standalone_expression(source, &self.original, &mut diag);
+        // The passes may have removed or renamed the properties that are not used in the
+        // .slint code, and the elements within a repeater belong to another component
+        expr.visit_recursive(&mut |e| {
+            let nr = match e {
+                Expression::PropertyReference(nr) | Expression::CallbackReference(nr) => nr,
+                _ => return,
+            };
+            let element = nr.element();
+            let enclosing = element.borrow().enclosing_component.upgrade().unwrap();
+            if enclosing.is_global() {
+                return;
+            }
+            let name = nr.name();
+            let available = Rc::ptr_eq(&enclosing, &self.original)
+                && ((Rc::ptr_eq(&element, &self.original.root_element)
+                    && (self.custom_properties.contains_key(name)
+                        || self.custom_callbacks.contains_key(name)))
+                    || self.items.get(element.borrow().id.as_str()).map_or(false, |item| {
+                        item.rtti.properties.contains_key(name)
+                            || item.rtti.callbacks.contains_key(name)
+                    }));
+            if !available {
+                diag.push_error_with_span(
+                    format!(
+                        "'{}.{}' is not available in the running instance",
+                        element.borrow().id,
+                        name
+                    ),
+                    Default::default(),
+                );
+            }
+        });
+        if diag.has_error() {
+            return Err(diag);
+        }
+        generativity::make_guard!(guard);
+        // Safety: we just verified that the component has the right vtable
+        let c = unsafe { InstanceRef::from_pin_ref(component, guard) };
+        Ok(eval::eval_expression(&expr, &mut eval::EvalLocalContext::from_component_instance(c)))
+    }
+
     // Return the global with the given name
     pub fn get_global(
         &self,
@@ -750,6 +856,8 @@ pub async fn load(
     if diag.has_error() {
         return (Err(()), diag);
     }
+    let max_image_size =
+        compiler_config.sandbox.as_ref().and_then(|sandbox| sandbox.max_image_size);
     let (doc, mut diag) = compile_syntax_node(syntax_node, diag, compiler_config).await;
     if diag.has_error() {
         return (Err(()), diag);
@@ -761,7 +869,26 @@ pub async fn load(
         diag.push_error_with_span("No component found".into(), Default::default());
         return (Err(()), diag);
     }
-    (Ok(generate_component(&doc.root_component, guard)), diag)
+    let mut component = generate_component(&doc.root_component, guard);
+    let description = Rc::get_mut(&mut component).expect("the description was just created");
+    description.exported_types = doc
+        .exports
+        .0
+        .iter()
+        .filter_map(|(name, export)| {
+            export.as_ref().right().map(|ty| (name.name.clone(), ty.clone()))
+        })
+        .filter(|(_, ty)| matches!(ty, Type::Struct { .. } | Type::Enumeration(_)))
+        .collect();
+    description.max_image_size = max_image_size;
+    for global in &mut description.compiled_globals {
+        if let crate::global_component::CompiledGlobal::Component { component, .. } = global {
+            Rc::get_mut(&mut component.0)
+                .expect("the description of the global was just created")
+                .max_image_size = max_image_size;
+        }
+    }
+    (Ok(component), diag)
 }
 
 pub(crate) fn generate_component<'id>(
@@ -782,15 +909,26 @@ pub(crate) fn generate_component<'id>(
                 rtti_for::<BorderRectangle>(),
                 rtti_for::<TouchArea>(),
                 rtti_for::<FocusScope>(),
+                rtti_for::<DragArea>(),
+                rtti_for::<DropArea>(),
+                rtti_for::<GestureArea>(),
+                rtti_for::<Shortcut>(),
+                rtti_for::<ToastQueue>(),
+                rtti_for::<SystemTrayIcon>(),
+                rtti_for::<PlatformMenuItem>(),
+                rtti_for::<PlatformMenuBar>(),
                 rtti_for::<Path>(),
                 rtti_for::<Flickable>(),
                 rtti_for::<WindowItem>(),
                 rtti_for::<TextInput>(),
                 rtti_for::<Clip>(),
                 rtti_for::<BoxShadow>(),
-                rtti_for::<Rotate>(),
+                rtti_for::<Transform>(),
+                rtti_for::<DropShadow>(),
+                rtti_for::<ViewportObserver>(),
                 rtti_for::<Opacity>(),
                 rtti_for::<Layer>(),
+                rtti_for::<Effect>(),
             ]
             .iter()
             .cloned(),
@@ -1104,6 +1242,8 @@ macro_rules! match_enum_type {
         public_properties,
         compiled_globals,
         exported_globals_by_name,
+        exported_types: Vec::new(),
+        max_image_size: None,
     };
 
     Rc::new(t)
@@ -1211,6 +1351,13 @@ pub fn instantiate(
             Some(parent);
     } else {
         for g in &component_type.compiled_globals {
+            // The globals may be shared with another instance, see `create_sharing_globals_with`
+            if g.names()
+                .iter()
+                .any(|name| globals.contains_key(&*crate::normalize_identifier(name)))
+            {
+                continue;
+            }
             crate::global_component::instantiate(g, &mut globals, window_adapter);
         }
         let extra_data = component_type.extra_data_offset.apply_mut(instance.as_mut());
@@ -1731,6 +1878,7 @@ pub fn toplevel_instance(&self) -> InstanceRef<'a, 'id> {
 pub fn show_popup(
     popup: &object_tree::PopupWindow,
     pos: i_slint_core::graphics::Point,
+    options: i_slint_core::window::PopupWindowOptions,
     parent_comp: ComponentRefPin,
     parent_window_adapter: &Rc<dyn WindowAdapter>,
     parent_item: &ItemRc,
@@ -1743,6 +1891,7 @@ pub fn show_popup(
     WindowInner::from_pub(parent_window_adapter.window()).show_popup(
         &vtable::VRc::into_dyn(inst),
         pos,
+        options,
         parent_item,
     );
 }
@@ -12,7 +12,8 @@
 use corelib::window::WindowInner;
 use corelib::{Brush, Color, PathData, SharedString, SharedVector};
 use i_slint_compiler::expression_tree::{
-    BuiltinFunction, EasingCurve, Expression, Path as ExprPath, PathElement as ExprPathElement,
+    BuiltinFunction, EasingCurve, Expression, NamedReference, Path as ExprPath,
+    PathElement as ExprPathElement,
 };
 use i_slint_compiler::langtype::Type;
 use i_slint_compiler::object_tree::ElementRc;
@@ -272,7 +273,7 @@ pub fn eval_expression(expression: &Expression, local_context: &mut EvalLocalCon
                     Ok(Default::default())
                 }
                 i_slint_compiler::expression_tree::ImageReference::AbsolutePath(path) => {
-                    corelib::graphics::Image::load_from_path(std::path::Path::new(path))
+                    load_image_from_path(std::path::Path::new(path), local_context)
                 }
                 i_slint_compiler::expression_tree::ImageReference::EmbeddedData { resource_id, extension } => {
                     let toplevel_instance = match local_context.component_instance {
@@ -402,6 +403,7 @@ fn call_builtin_function(
         BuiltinFunction::AnimationTick => {
             Value::Number(i_slint_core::animations::animation_tick() as f64)
         }
+        BuiltinFunction::ReducedMotion => Value::Bool(i_slint_core::animations::reduced_motion()),
         BuiltinFunction::Debug => {
             let to_print: SharedString =
                 eval_expression(&arguments[0], local_context).try_into().unwrap();
@@ -498,6 +500,104 @@ fn call_builtin_function(
                 panic!("internal error: argument to SetFocusItem must be an element")
             }
         }
+        BuiltinFunction::EnsureItemVisible => {
+            if arguments.len() != 1 {
+                panic!("internal error: incorrect argument count to EnsureItemVisible")
+            }
+            let component = match local_context.component_instance {
+                ComponentInstance::InstanceRef(c) => c,
+                ComponentInstance::GlobalComponent(_) => {
+                    panic!("Cannot access elements from a global component")
+                }
+            };
+            if let Expression::ElementReference(item) = &arguments[0] {
+                generativity::make_guard!(guard);
+
+                let item = item.upgrade().unwrap();
+                let enclosing_component = enclosing_component_for_element(&item, component, guard);
+                let item_info =
+                    &enclosing_component.component_type.items[item.borrow().id.as_str()];
+                let item_comp = enclosing_component.self_weak().get().unwrap().upgrade().unwrap();
+
+                corelib::items::ensure_item_visible(&corelib::items::ItemRc::new(
+                    vtable::VRc::into_dyn(item_comp),
+                    item_info.item_index(),
+                ));
+                Value::Void
+            } else {
+                panic!("internal error: argument to EnsureItemVisible must be an element")
+            }
+        }
+        BuiltinFunction::FlickableScrollTo => {
+            if arguments.len() != 3 {
+                panic!("internal error: incorrect argument count to FlickableScrollTo")
+            }
+            let component = match local_context.component_instance {
+                ComponentInstance::InstanceRef(c) => c,
+                ComponentInstance::GlobalComponent(_) => {
+                    panic!("Cannot access elements from a global component")
+                }
+            };
+            if let Expression::ElementReference(item) = &arguments[0] {
+                let x: f64 = eval_expression(&arguments[1], local_context).try_into().unwrap();
+                let y: f64 = eval_expression(&arguments[2], local_context).try_into().unwrap();
+
+                generativity::make_guard!(guard);
+
+                let item = item.upgrade().unwrap();
+                let enclosing_component = enclosing_component_for_element(&item, component, guard);
+                let item_info =
+                    &enclosing_component.component_type.items[item.borrow().id.as_str()];
+                let item_ref =
+                    unsafe { item_info.item_from_component(enclosing_component.as_ptr()) };
+                let flickable = ItemRef::downcast_pin::<corelib::items::Flickable>(item_ref)
+                    .expect("internal error: scroll-to must be called on a Flickable");
+                flickable.scroll_to(
+                    corelib::lengths::LogicalLength::new(x as _),
+                    corelib::lengths::LogicalLength::new(y as _),
+                );
+                Value::Void
+            } else {
+                panic!("internal error: first argument to FlickableScrollTo must be an element")
+            }
+        }
+        BuiltinFunction::ToastQueuePush | BuiltinFunction::ToastQueueDismiss => {
+            let component = match local_context.component_instance {
+                ComponentInstance::InstanceRef(c) => c,
+                ComponentInstance::GlobalComponent(_) => {
+                    panic!("Cannot access elements from a global component")
+                }
+            };
+            if let Some(Expression::ElementReference(item)) = arguments.first() {
+                generativity::make_guard!(guard);
+
+                let item = item.upgrade().unwrap();
+                let enclosing_component = enclosing_component_for_element(&item, component, guard);
+                let item_info =
+                    &enclosing_component.component_type.items[item.borrow().id.as_str()];
+                let item_ref =
+                    unsafe { item_info.item_from_component(enclosing_component.as_ptr()) };
+                let queue = ItemRef::downcast_pin::<corelib::items::ToastQueue>(item_ref)
+                    .expect("internal error: push and dismiss must be called on a ToastQueue");
+                if matches!(f, BuiltinFunction::ToastQueuePush) {
+                    if arguments.len() != 4 {
+                        panic!("internal error: incorrect argument count to ToastQueuePush")
+                    }
+                    let text: SharedString =
+                        eval_expression(&arguments[1], local_context).try_into().unwrap();
+                    let action_text: SharedString =
+                        eval_expression(&arguments[2], local_context).try_into().unwrap();
+                    let duration: i64 =
+                        eval_expression(&arguments[3], local_context).try_into().unwrap();
+                    queue.push(text, action_text, duration);
+                } else {
+                    queue.dismiss();
+                }
+                Value::Void
+            } else {
+                panic!("internal error: first argument to {:?} must be an element", f)
+            }
+        }
         BuiltinFunction::ShowPopupWindow => {
             if arguments.len() != 1 {
                 panic!("internal error: incorrect argument count to ShowPopupWindow")
@@ -534,17 +634,29 @@ fn call_builtin_function(
                     popup.y.name(),
                 )
                 .unwrap();
+                let load_bool = |nr: &NamedReference| -> bool {
+                    load_property_helper(local_context.component_instance, &nr.element(), nr.name())
+                        .unwrap()
+                        .try_into()
+                        .unwrap()
+                };
+                let options = i_slint_core::window::PopupWindowOptions {
+                    close_on_click: load_bool(&popup.close_on_click),
+                    close_on_click_outside: load_bool(&popup.close_on_click_outside),
+                    close_on_escape: load_bool(&popup.close_on_escape),
+                    modal: load_bool(&popup.modal),
+                };
 
                 generativity::make_guard!(guard);
                 let enclosing_component =
-                    enclosing_component_for_element(&popup.parent_element, component, guard);
-                let parent_item_info = &enclosing_component.component_type.items
-                    [popup.parent_element.borrow().id.as_str()];
-                let parent_item_comp =
+                    enclosing_component_for_element(&popup.anchor, component, guard);
+                let anchor_item_info =
+                    &enclosing_component.component_type.items[popup.anchor.borrow().id.as_str()];
+                let anchor_item_comp =
                     enclosing_component.self_weak().get().unwrap().upgrade().unwrap();
-                let parent_item = corelib::items::ItemRc::new(
-                    vtable::VRc::into_dyn(parent_item_comp),
-                    parent_item_info.item_index(),
+                let anchor_item = corelib::items::ItemRc::new(
+                    vtable::VRc::into_dyn(anchor_item_comp),
+                    anchor_item_info.item_index(),
                 );
 
                 crate::dynamic_component::show_popup(
@@ -553,15 +665,26 @@ fn call_builtin_function(
                         x.try_into().unwrap(),
                         y.try_into().unwrap(),
                     ),
+                    options,
                     component.borrow(),
                     window_adapter_ref(component).unwrap(),
-                    &parent_item,
+                    &anchor_item,
                 );
                 Value::Void
             } else {
                 panic!("internal error: argument to SetFocusItem must be an element")
             }
         }
+        BuiltinFunction::ClosePopupWindow => {
+            let component = match local_context.component_instance {
+                ComponentInstance::InstanceRef(c) => c,
+                ComponentInstance::GlobalComponent(_) => {
+                    panic!("Cannot close popup from a global component")
+                }
+            };
+            WindowInner::from_pub(window_adapter_ref(component).unwrap().window()).close_popup();
+            Value::Void
+        }
         BuiltinFunction::StringIsFloat => {
             if arguments.len() != 1 {
                 panic!("internal error: incorrect argument count to StringIsFloat")
@@ -572,6 +695,30 @@ fn call_builtin_function(
                 panic!("Argument not a string");
             }
         }
+        BuiltinFunction::StringToLowercase => {
+            if arguments.len() != 1 {
+                panic!("internal error: incorrect argument count to StringToLowercase")
+            }
+            if let Value::String(s) = eval_expression(&arguments[0], local_context) {
+                Value::String(s.as_str().to_lowercase().into())
+            } else {
+                panic!("Argument not a string");
+            }
+        }
+        BuiltinFunction::StringContains => {
+            if arguments.len() != 2 {
+                panic!("internal error: incorrect argument count to StringContains")
+            }
+            match (
+                eval_expression(&arguments[0], local_context),
+                eval_expression(&arguments[1], local_context),
+            ) {
+                (Value::String(s), Value::String(needle)) => {
+                    Value::Bool(s.as_str().contains(needle.as_str()))
+                }
+                _ => panic!("Argument not a string"),
+            }
+        }
         BuiltinFunction::StringToFloat => {
             if arguments.len() != 1 {
                 panic!("internal error: incorrect argument count to StringToFloat")
@@ -610,6 +757,29 @@ fn call_builtin_function(
                 panic!("First argument not a color");
             }
         }
+        BuiltinFunction::LoadImageInBackground => {
+            if arguments.len() != 2 {
+                panic!("internal error: incorrect argument count to LoadImageInBackground")
+            }
+            match (
+                eval_expression(&arguments[0], local_context),
+                eval_expression(&arguments[1], local_context),
+            ) {
+                (Value::String(path), Value::Image(_))
+                    if max_image_size(local_context).is_some() =>
+                {
+                    // The size of the image must be checked before it is decoded
+                    Value::Image(
+                        load_image_from_path(std::path::Path::new(path.as_str()), local_context)
+                            .unwrap_or_default(),
+                    )
+                }
+                (Value::String(path), Value::Image(placeholder)) => Value::Image(
+                    corelib::graphics::load_image_from_path_in_background(&path, placeholder),
+                ),
+                _ => panic!("internal error: invalid arguments to LoadImageInBackground"),
+            }
+        }
         BuiltinFunction::ImageSize => {
             if arguments.len() != 1 {
                 panic!("internal error: incorrect argument count to ImageSize")
@@ -652,9 +822,10 @@ fn call_builtin_function(
             Value::Brush(Brush::SolidColor(Color::from_argb_u8(a, r, g, b)))
         }
         BuiltinFunction::DarkColorScheme => match local_context.component_instance {
-            ComponentInstance::InstanceRef(component) => {
-                Value::Bool(window_adapter_ref(component).unwrap().dark_color_scheme())
-            }
+            ComponentInstance::InstanceRef(component) => Value::Bool(
+                WindowInner::from_pub(window_adapter_ref(component).unwrap().window())
+                    .dark_color_scheme(),
+            ),
             ComponentInstance::GlobalComponent(_) => {
                 panic!("Cannot get the window from a global component")
             }
@@ -685,6 +856,37 @@ fn call_builtin_function(
                 panic!("internal error: incorrect arguments to ImplicitLayoutInfo {:?}", arguments);
             }
         }
+        BuiltinFunction::TextContentSize(_) | BuiltinFunction::TextTruncated => {
+            let component = match local_context.component_instance {
+                ComponentInstance::InstanceRef(c) => c,
+                ComponentInstance::GlobalComponent(_) => {
+                    panic!("Cannot access the text metrics from a global component")
+                }
+            };
+            if let [Expression::ElementReference(item)] = arguments {
+                generativity::make_guard!(guard);
+
+                let item = item.upgrade().unwrap();
+                let enclosing_component = enclosing_component_for_element(&item, component, guard);
+                let component_type = enclosing_component.component_type;
+                let item_info = &component_type.items[item.borrow().id.as_str()];
+                let item_ref =
+                    unsafe { item_info.item_from_component(enclosing_component.as_ptr()) };
+                let text = ItemRef::downcast_pin::<corelib::items::Text>(item_ref)
+                    .expect("internal error: the text metrics must be computed on a Text");
+
+                let window_adapter = window_adapter_ref(component).unwrap();
+                match &f {
+                    BuiltinFunction::TextContentSize(orient) => Value::Number(
+                        text.content_size(crate::eval_layout::to_runtime(*orient), window_adapter)
+                            .get() as _,
+                    ),
+                    _ => Value::Bool(text.is_truncated(window_adapter)),
+                }
+            } else {
+                panic!("internal error: incorrect arguments to {:?} {:?}", f, arguments);
+            }
+        }
         BuiltinFunction::RegisterCustomFontByPath => {
             if arguments.len() != 1 {
                 panic!("internal error: incorrect argument count to RegisterCustomFontByPath")
@@ -1138,6 +1340,26 @@ pub(crate) fn enclosing_component_instance_for_element<'a, 'old_id, 'new_id>(
     }
 }
 
+/// The maximum size of the images loaded by the component, set by the sandbox of the compiler
+fn max_image_size(local_context: &EvalLocalContext) -> Option<(u32, u32)> {
+    match local_context.component_instance {
+        ComponentInstance::InstanceRef(instance) => {
+            instance.toplevel_instance().component_type.max_image_size
+        }
+        ComponentInstance::GlobalComponent(_) => None,
+    }
+}
+
+fn load_image_from_path(
+    path: &std::path::Path,
+    local_context: &EvalLocalContext,
+) -> Result<corelib::graphics::Image, corelib::graphics::LoadImageError> {
+    match max_image_size(local_context) {
+        Some(max_size) => corelib::graphics::load_image_from_path_with_max_size(path, max_size),
+        None => corelib::graphics::Image::load_from_path(path),
+    }
+}
+
 pub fn new_struct_with_bindings<ElementType: 'static + Default + corelib::rtti::BuiltinItem>(
     bindings: &i_slint_compiler::object_tree::BindingsMap,
     local_context: &mut EvalLocalContext,
@@ -0,0 +1,274 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+/*!
+Conversion between [`Value`] and [`serde_json::Value`].
+
+Structs are represented as JSON objects, models as JSON arrays, and brushes as strings
+with the color in the `#rrggbbaa` format.
+*/
+
+use crate::api::{
+    normalize_identifier, ComponentDefinition, ComponentInstance, SetPropertyError, Struct, Value,
+};
+use i_slint_compiler::langtype::Type as LangType;
+use i_slint_core::graphics::Image;
+use i_slint_core::model::{Model, ModelRc, SharedVectorModel};
+use i_slint_core::{Brush, Color, SharedString, SharedVector};
+
+impl Value {
+    /// Converts this value to JSON.
+    ///
+    /// Structs become objects, models become arrays, and brushes become strings in the `#rrggbbaa`
+    /// format. Images become the path of the file they were loaded from, and enumeration values the
+    /// name of the value.
+    ///
+    /// Returns None if the value, or one of the values it contains, can't be represented in JSON.
+    /// For example a gradient, or an image that was not loaded from a file.
+    ///
+    /// This function is only available with the `json` feature.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use slint_interpreter::{Value, Struct, SharedString};
+    /// let value: Value = [
+    ///     ("name".into(), Value::from(SharedString::from("Slint"))),
+    ///     ("color".into(), Value::from(slint_interpreter::Color::from_rgb_u8(255, 0, 0))),
+    /// ]
+    /// .into_iter()
+    /// .collect::<Struct>()
+    /// .into();
+    /// assert_eq!(
+    ///     value.to_json().unwrap(),
+    ///     serde_json::json!({ "name": "Slint", "color": "#ff0000ff" })
+    /// );
+    /// ```
+    pub fn to_json(&self) -> Option<serde_json::Value> {
+        Some(match self {
+            Value::Void => serde_json::Value::Null,
+            Value::Number(n) => serde_json::Number::from_f64(*n)?.into(),
+            Value::String(s) => s.as_str().into(),
+            Value::Bool(b) => (*b).into(),
+            Value::Image(image) => image.path()?.to_str()?.into(),
+            Value::Model(model) => {
+                model.iter().map(|v| v.to_json()).collect::<Option<Vec<_>>>()?.into()
+            }
+            Value::Struct(s) => s
+                .iter()
+                .map(|(k, v)| Some((k.to_string(), v.to_json()?)))
+                .collect::<Option<serde_json::Map<_, _>>>()?
+                .into(),
+            Value::Brush(Brush::SolidColor(color)) => {
+                let color = color.to_argb_u8();
+                format!(
+                    "#{:02x}{:02x}{:02x}{:02x}",
+                    color.red, color.green, color.blue, color.alpha
+                )
+                .into()
+            }
+            Value::EnumerationValue(_, value) => value.as_str().into(),
+            _ => return None,
+        })
+    }
+
+    /// Converts JSON to a value, without knowledge of the expected type.
+    ///
+    /// `null` becomes [`Value::Void`], arrays become models and objects become structs. Strings stay
+    /// strings, even if they contain a color: use [`ComponentInstance::set_property_from_json()`] to
+    /// convert the JSON to the type of a property.
+    ///
+    /// This function is only available with the `json` feature.
+    pub fn from_json(json: &serde_json::Value) -> Value {
+        match json {
+            serde_json::Value::Null => Value::Void,
+            serde_json::Value::Bool(b) => (*b).into(),
+            serde_json::Value::Number(n) => Value::Number(n.as_f64().unwrap_or(f64::NAN)),
+            serde_json::Value::String(s) => SharedString::from(s.as_str()).into(),
+            serde_json::Value::Array(array) => Value::Model(ModelRc::new(SharedVectorModel::from(
+                array.iter().map(Value::from_json).collect::<SharedVector<Value>>(),
+            ))),
+            serde_json::Value::Object(obj) => {
+                obj.iter().map(|(k, v)| (k.clone(), Value::from_json(v))).collect::<Struct>().into()
+            }
+        }
+    }
+}
+
+/// Converts JSON to a value of the given type. Returns None if the JSON doesn't match the type.
+fn from_json_with_type(json: &serde_json::Value, ty: &LangType) -> Option<Value> {
+    Some(match (ty, json) {
+        (
+            LangType::Float32
+            | LangType::Int32
+            | LangType::Duration
+            | LangType::Angle
+            | LangType::PhysicalLength
+            | LangType::LogicalLength
+            | LangType::Percent
+            | LangType::UnitProduct(_),
+            serde_json::Value::Number(n),
+        ) => Value::Number(n.as_f64()?),
+        (LangType::String, serde_json::Value::String(s)) => SharedString::from(s.as_str()).into(),
+        (LangType::Bool, serde_json::Value::Bool(b)) => (*b).into(),
+        (LangType::Color | LangType::Brush, serde_json::Value::String(s)) => {
+            let color = i_slint_compiler::literals::parse_color_literal(s)?;
+            Value::Brush(Brush::SolidColor(Color::from_argb_encoded(color)))
+        }
+        (LangType::Image, serde_json::Value::String(path)) => {
+            Value::Image(Image::load_from_path(std::path::Path::new(path)).ok()?)
+        }
+        (LangType::Array(ty), serde_json::Value::Array(array)) => {
+            Value::Model(ModelRc::new(SharedVectorModel::from(
+                array
+                    .iter()
+                    .map(|json| from_json_with_type(json, ty))
+                    .collect::<Option<SharedVector<Value>>>()?,
+            )))
+        }
+        (LangType::Struct { fields, .. }, serde_json::Value::Object(obj)) => {
+            if obj.keys().any(|k| !fields.contains_key(normalize_identifier(k).as_ref())) {
+                return None;
+            }
+            fields
+                .iter()
+                .map(|(name, ty)| {
+                    let value = match obj.iter().find(|(k, _)| normalize_identifier(k) == *name) {
+                        Some((_, json)) => from_json_with_type(json, ty)?,
+                        None => crate::eval::default_value_for_type(ty),
+                    };
+                    Some((name.clone(), value))
+                })
+                .collect::<Option<Struct>>()?
+                .into()
+        }
+        (LangType::Enumeration(enumeration), serde_json::Value::String(s)) => {
+            let value = enumeration.values.iter().find(|v| **v == normalize_identifier(s))?;
+            Value::EnumerationValue(enumeration.name.clone(), value.clone())
+        }
+        _ => return None,
+    })
+}
+
+impl ComponentInstance {
+    /// Sets the property from its JSON representation, as returned by [`Value::to_json()`].
+    ///
+    /// Unlike [`Value::from_json()`], the JSON is converted according to the type of the property,
+    /// so that strings are parsed as colors for brush properties or as paths for image properties,
+    /// including in structs and arrays. Fields of a struct that are missing in the JSON object get
+    /// their default value.
+    ///
+    /// This function is only available with the `json` feature.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use slint_interpreter::ComponentCompiler;
+    /// let code = r#"
+    ///     MyWin := Window {
+    ///         property <[{ name: string, color: color }]> items;
+    ///     }
+    /// "#;
+    /// let definition = spin_on::spin_on(
+    ///     ComponentCompiler::default().build_from_source(code.into(), Default::default()));
+    /// let instance = definition.unwrap().create();
+    /// let json = serde_json::json!([{ "name": "Red", "color": "#ff0000" }]);
+    /// instance.set_property_from_json("items", &json).unwrap();
+    /// let json = instance.get_property("items").unwrap().to_json().unwrap();
+    /// assert_eq!(json, serde_json::json!([{ "name": "Red", "color": "#ff0000ff" }]));
+    /// ```
+    pub fn set_property_from_json(
+        &self,
+        name: &str,
+        json: &serde_json::Value,
+    ) -> Result<(), SetPropertyError> {
+        let ty = self
+            .definition()
+            .properties_and_callbacks()
+            .find(|(prop_name, ty)| {
+                ty.is_property_type()
+                    && normalize_identifier(prop_name) == normalize_identifier(name)
+            })
+            .map(|(_, ty)| ty)
+            .ok_or(SetPropertyError::NoSuchProperty)?;
+        let value = from_json_with_type(json, &ty).ok_or(SetPropertyError::WrongType)?;
+        self.set_property(name, value)
+    }
+
+    /// Sets the property of an exported global singleton from its JSON representation, converted
+    /// according to the type of the property like with [`Self::set_property_from_json()`].
+    ///
+    /// This function is only available with the `json` feature.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use slint_interpreter::{ComponentCompiler, Value};
+    /// let code = r#"
+    ///     export global Palette := {
+    ///         property <color> accent;
+    ///     }
+    ///     export MyWin := Window {
+    ///         background: Palette.accent;
+    ///     }
+    /// "#;
+    /// let definition = spin_on::spin_on(
+    ///     ComponentCompiler::default().build_from_source(code.into(), Default::default()));
+    /// let instance = definition.unwrap().create();
+    /// let json = serde_json::json!("#336699");
+    /// instance.set_global_property_from_json("Palette", "accent", &json).unwrap();
+    /// let json = instance.get_global_property("Palette", "accent").unwrap().to_json().unwrap();
+    /// assert_eq!(json, serde_json::json!("#336699ff"));
+    /// ```
+    pub fn set_global_property_from_json(
+        &self,
+        global: &str,
+        name: &str,
+        json: &serde_json::Value,
+    ) -> Result<(), SetPropertyError> {
+        let ty = self
+            .definition()
+            .global_properties_and_callbacks(global)
+            .ok_or(SetPropertyError::NoSuchProperty)?
+            .find(|(prop_name, ty)| {
+                ty.is_property_type()
+                    && normalize_identifier(prop_name) == normalize_identifier(name)
+            })
+            .map(|(_, ty)| ty)
+            .ok_or(SetPropertyError::NoSuchProperty)?;
+        let value = from_json_with_type(json, &ty).ok_or(SetPropertyError::WrongType)?;
+        self.set_global_property(global, name, value)
+    }
+}
+
+impl ComponentDefinition {
+    /// Converts the JSON representation of a value returned by a callback to the return type of
+    /// the callback, like [`ComponentInstance::set_property_from_json()`] does for properties.
+    ///
+    /// `global` is the name of the exported global singleton that declares the callback, or `None`
+    /// for a callback of the component. Returns None if there is no such callback, or if the JSON
+    /// doesn't match its return type. The JSON `null` is accepted for callbacks that return nothing.
+    ///
+    /// This function is only available with the `json` feature.
+    pub fn callback_return_value_from_json(
+        &self,
+        global: Option<&str>,
+        name: &str,
+        json: &serde_json::Value,
+    ) -> Option<Value> {
+        let find_callback = |(callback_name, ty): &(String, LangType)| {
+            matches!(ty, LangType::Callback { .. })
+                && normalize_identifier(callback_name) == normalize_identifier(name)
+        };
+        let ty = match global {
+            Some(global) => self.global_properties_and_callbacks(global)?.find(find_callback)?.1,
+            None => self.properties_and_callbacks().find(find_callback)?.1,
+        };
+        match ty {
+            LangType::Callback { return_type: Some(return_type), .. } => {
+                from_json_with_type(json, &return_type)
+            }
+            _ => json.is_null().then(|| Value::Void),
+        }
+    }
+}
@@ -78,15 +78,25 @@
 mod eval;
 mod eval_layout;
 mod global_component;
+#[cfg(feature = "json")]
+mod json;
+#[cfg(feature = "live-reload")]
+mod live_reload;
 mod value_model;
 
 #[doc(inline)]
 pub use api::*;
+#[cfg(feature = "live-reload")]
+pub use live_reload::LiveReloadingComponent;
 
 /// (Re-export from corelib.)
 #[doc(inline)]
 pub use i_slint_core::{Brush, Color, SharedString, SharedVector};
 
+/// (Re-export from corelib.)
+#[doc(inline)]
+pub use i_slint_core::model::{Model, ModelExt, ModelNotify, ModelRc, ModelTracker, VecModel};
+
 /// One need to use at least one function in each module in order to get them
 /// exported in the final binary.
 /// This only use functions from modules which are not otherwise used.
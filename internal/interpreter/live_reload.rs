@@ -0,0 +1,400 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+/*!
+Reloading of components when their `.slint` files change on disk.
+*/
+
+use crate::api::{
+    ComponentCompiler, ComponentDefinition, ComponentHandle, ComponentInstance, Diagnostic,
+    SetCallbackError, Value, ValueType,
+};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::rc::{Rc, Weak};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+type CallbackHandler = Rc<dyn Fn(&[Value]) -> Value>;
+
+/// The name of the exported global, or `None` for the component itself, and the name of the
+/// property or callback
+type MemberKey = (Option<String>, String);
+
+thread_local! {
+    /// All the live reloading components of this thread, checked when a file changed.
+    static LIVE_RELOADING_COMPONENTS: RefCell<Vec<Weak<RefCell<LiveReloadingComponent>>>> =
+        Default::default();
+}
+
+/// A component that is compiled again and re-instantiated in the same window when its `.slint` file,
+/// or one of the files it imports, changes on disk.
+///
+/// The values of the public properties, and of the properties of the exported globals, that were changed
+/// since the component was instantiated, for example by the application or by the user interacting with
+/// the UI, are set on the new instance when the property still exists with the same type. This includes
+/// the models, so the new instance shows the same data. Property values that were not changed keep the
+/// value of the binding in the new source code, so that editing these bindings has an effect.
+/// The new instance also keeps the size of the window.
+///
+/// The callback handlers must be set with [`Self::set_callback()`] and [`Self::set_global_callback()`]
+/// instead of on the instance, so that they can be set again on each new instance.
+///
+/// The files are watched from a separate thread, and the component is reloaded from the event loop.
+///
+/// This type is only available with the `live-reload` feature.
+///
+/// ## Example
+///
+/// ```rust,no_run
+/// use slint_interpreter::{ComponentCompiler, ComponentHandle, LiveReloadingComponent, Value};
+///
+/// let component = LiveReloadingComponent::new(ComponentCompiler::default(), "app.slint".into())
+///     .expect("app.slint has errors");
+/// component.borrow_mut().set_callback("quit", |_| {
+///     slint_interpreter::quit_event_loop().unwrap();
+///     Value::Void
+/// }).unwrap();
+/// let instance = component.borrow().instance().clone_strong();
+/// instance.run();
+/// ```
+pub struct LiveReloadingComponent {
+    compiler: ComponentCompiler,
+    path: PathBuf,
+    instance: ComponentInstance,
+    /// The values of the public properties just after the instance was created
+    initial_values: HashMap<MemberKey, Value>,
+    callbacks: HashMap<MemberKey, CallbackHandler>,
+    reload_handlers: Vec<Box<dyn FnMut(&ComponentInstance)>>,
+    /// None if the watcher could not be created, in which case the component is only reloaded by [`Self::reload()`]
+    watcher: Option<Arc<Mutex<notify::RecommendedWatcher>>>,
+    /// Set by the watcher thread when a file changed and the component needs to be reloaded
+    changed: Arc<AtomicBool>,
+}
+
+impl LiveReloadingComponent {
+    /// Compiles the `.slint` file at `path` with the configuration of `compiler`, instantiates
+    /// it and starts watching the file and its imports for changes.
+    ///
+    /// The files loaded by the compiler, including the ones provided by the callback set with
+    /// [`ComponentCompiler::set_file_loader()`], are registered with the watcher.
+    /// Returns the diagnostics if the file could not be compiled.
+    pub fn new(
+        mut compiler: ComponentCompiler,
+        path: PathBuf,
+    ) -> Result<Rc<RefCell<Self>>, Vec<Diagnostic>> {
+        let changed = Arc::new(AtomicBool::new(false));
+        let watcher = {
+            let changed = changed.clone();
+            notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+                use notify::EventKind::*;
+                if let Ok(event) = event {
+                    if matches!(event.kind, Modify(_) | Remove(_) | Create(_))
+                        && !changed.swap(true, Ordering::SeqCst)
+                    {
+                        i_slint_core::api::invoke_from_event_loop(reload_changed_components).ok();
+                    }
+                }
+            })
+            .map_err(|err| eprintln!("Warning: cannot watch the files for changes: {}", err))
+            .ok()
+            .map(|watcher| Arc::new(Mutex::new(watcher)))
+        };
+        if let Some(watcher) = &watcher {
+            watch(watcher, &path);
+            let import_watcher = watcher.clone();
+            compiler.observe_loaded_files(move |path| watch(&import_watcher, path));
+        }
+
+        let definition = match spin_on::spin_on(compiler.build_from_path(&path)) {
+            Some(definition) => definition,
+            None => return Err(compiler.diagnostics().clone()),
+        };
+        let instance = definition.create();
+        let initial_values = public_property_values(&instance);
+
+        let component = Rc::new(RefCell::new(Self {
+            compiler,
+            path,
+            instance,
+            initial_values,
+            callbacks: Default::default(),
+            reload_handlers: Default::default(),
+            watcher,
+            changed,
+        }));
+        LIVE_RELOADING_COMPONENTS.with(|components| {
+            let mut components = components.borrow_mut();
+            components.retain(|c| c.strong_count() > 0);
+            components.push(Rc::downgrade(&component));
+        });
+        Ok(component)
+    }
+
+    /// Returns the current instance of the component. It changes on each reload.
+    pub fn instance(&self) -> &ComponentInstance {
+        &self.instance
+    }
+
+    /// Returns the diagnostics of the last compilation.
+    pub fn diagnostics(&self) -> &Vec<Diagnostic> {
+        self.compiler.diagnostics()
+    }
+
+    /// Sets the handler of a callback on the current instance, and on the instances created by
+    /// subsequent reloads.
+    pub fn set_callback(
+        &mut self,
+        name: &str,
+        callback: impl Fn(&[Value]) -> Value + 'static,
+    ) -> Result<(), SetCallbackError> {
+        let callback: CallbackHandler = Rc::new(callback);
+        let handler = callback.clone();
+        self.instance.set_callback(name, move |args| handler(args))?;
+        self.callbacks.insert((None, name.into()), callback);
+        Ok(())
+    }
+
+    /// Sets the handler of a callback of an exported global on the current instance, and on the
+    /// instances created by subsequent reloads.
+    pub fn set_global_callback(
+        &mut self,
+        global: &str,
+        name: &str,
+        callback: impl Fn(&[Value]) -> Value + 'static,
+    ) -> Result<(), SetCallbackError> {
+        let callback: CallbackHandler = Rc::new(callback);
+        let handler = callback.clone();
+        self.instance.set_global_callback(global, name, move |args| handler(args))?;
+        self.callbacks.insert((Some(global.into()), name.into()), callback);
+        Ok(())
+    }
+
+    /// Registers a function called with the new instance after each successful reload, for
+    /// example to initialize it.
+    pub fn on_reload(&mut self, handler: impl FnMut(&ComponentInstance) + 'static) {
+        self.reload_handlers.push(Box::new(handler));
+    }
+
+    /// Also reload the component when the file at `path` changes, for example a data file
+    /// used by a reload handler.
+    pub fn watch_path(&self, path: &Path) {
+        if let Some(watcher) = &self.watcher {
+            watch(watcher, path);
+        }
+    }
+
+    /// Compiles the `.slint` file again, replaces the instance in its window and calls the
+    /// handlers registered with [`Self::on_reload()`].
+    ///
+    /// Returns false if the file could not be compiled, in which case the current instance
+    /// remains and the errors are in [`Self::diagnostics()`].
+    pub fn reload(&mut self) -> bool {
+        if !self.reload_instance() {
+            return false;
+        }
+        for handler in &mut self.reload_handlers {
+            handler(&self.instance);
+        }
+        true
+    }
+
+    /// Compiles the `.slint` file again and replaces the instance, without calling the reload
+    /// handlers.
+    fn reload_instance(&mut self) -> bool {
+        self.changed.store(false, Ordering::SeqCst);
+
+        let definition = match spin_on::spin_on(self.compiler.build_from_path(&self.path)) {
+            Some(definition) => definition,
+            None => return false,
+        };
+        let window_size = self.instance.window().size();
+        let instance = definition.create_with_existing_window(self.instance.window());
+        if window_size.width > 0 && window_size.height > 0 {
+            // Also lays out the new instance with the current size of the window
+            instance.window().set_size(window_size);
+        }
+        let initial_values = public_property_values(&instance);
+
+        let old_types: HashMap<MemberKey, ValueType> =
+            public_properties(&self.instance.definition()).collect();
+        for (key, value_type) in public_properties(&definition) {
+            if old_types.get(&key) != Some(&value_type) {
+                continue;
+            }
+            if let Some(value) = get_property(&self.instance, &key) {
+                if self.initial_values.get(&key) != Some(&value) {
+                    set_property(&instance, &key, value);
+                }
+            }
+        }
+
+        for ((global, name), callback) in &self.callbacks {
+            let callback = callback.clone();
+            match global {
+                Some(global) => {
+                    instance.set_global_callback(global, name, move |args| callback(args)).ok()
+                }
+                None => instance.set_callback(name, move |args| callback(args)).ok(),
+            };
+        }
+
+        self.instance = instance;
+        self.initial_values = initial_values;
+        true
+    }
+}
+
+fn watch(watcher: &Mutex<notify::RecommendedWatcher>, path: &Path) {
+    notify::Watcher::watch(
+        &mut *watcher.lock().unwrap(),
+        path,
+        notify::RecursiveMode::NonRecursive,
+    )
+    .unwrap_or_else(|err| eprintln!("Warning: error while watching {}: {:?}", path.display(), err));
+}
+
+/// The public properties of the component, followed by the properties of the exported globals
+fn public_properties(
+    definition: &ComponentDefinition,
+) -> impl Iterator<Item = (MemberKey, ValueType)> + '_ {
+    definition.properties().map(|(name, value_type)| ((None, name), value_type)).chain(
+        definition.globals().flat_map(move |global| {
+            definition
+                .global_properties(&global)
+                .into_iter()
+                .flatten()
+                .map(move |(name, value_type)| ((Some(global.clone()), name), value_type))
+                .collect::<Vec<_>>()
+        }),
+    )
+}
+
+fn get_property(instance: &ComponentInstance, (global, name): &MemberKey) -> Option<Value> {
+    match global {
+        Some(global) => instance.get_global_property(global, name).ok(),
+        None => instance.get_property(name).ok(),
+    }
+}
+
+fn set_property(instance: &ComponentInstance, (global, name): &MemberKey, value: Value) {
+    match global {
+        Some(global) => instance.set_global_property(global, name, value).ok(),
+        None => instance.set_property(name, value).ok(),
+    };
+}
+
+fn public_property_values(instance: &ComponentInstance) -> HashMap<MemberKey, Value> {
+    public_properties(&instance.definition())
+        .filter_map(|(key, _)| get_property(instance, &key).map(|value| (key, value)))
+        .collect()
+}
+
+/// Called in the event loop after a file changed
+fn reload_changed_components() {
+    let components = LIVE_RELOADING_COMPONENTS.with(|components| components.borrow().clone());
+    for component in components.iter().filter_map(Weak::upgrade) {
+        let (instance, mut handlers) = {
+            let mut component = component.borrow_mut();
+            if !component.changed.load(Ordering::SeqCst) {
+                continue;
+            }
+            if !component.reload_instance() {
+                #[cfg(feature = "display-diagnostics")]
+                crate::print_diagnostics(component.diagnostics());
+                continue;
+            }
+            (component.instance.clone_strong(), core::mem::take(&mut component.reload_handlers))
+        };
+        // The component is not borrowed while the handlers run, so they can access it
+        for handler in &mut handlers {
+            handler(&instance);
+        }
+        let mut component = component.borrow_mut();
+        // Keep the handlers registered by the handlers
+        handlers.append(&mut component.reload_handlers);
+        component.reload_handlers = handlers;
+    }
+}
+
+#[test]
+fn reload_keeps_the_changed_properties_and_the_file_loader() {
+    i_slint_backend_testing::init();
+    let dir = std::env::temp_dir().join(format!("slint_live_reload_test_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("main.slint");
+    std::fs::write(
+        &path,
+        "import { Label } from \"label.slint\"; export Main := Label { property <int> a: 1; property <int> b: 2; }",
+    )
+    .unwrap();
+
+    // The imported file only exists in memory
+    let mut compiler = ComponentCompiler::default();
+    compiler.set_file_loader(move |path| {
+        let source = (path.file_name() == Some("label.slint".as_ref()))
+            .then(|| Ok("export Label := Text { text: \"hello\"; }".into()));
+        Box::pin(async move { source })
+    });
+    let component = LiveReloadingComponent::new(compiler, path.clone()).unwrap();
+    let reloads = Rc::new(std::cell::Cell::new(0));
+    {
+        let mut component = component.borrow_mut();
+        component.instance().set_property("a", Value::Number(10.)).unwrap();
+        let reloads = reloads.clone();
+        component.on_reload(move |_| reloads.set(reloads.get() + 1));
+    }
+
+    std::fs::write(
+        &path,
+        "import { Label } from \"label.slint\"; export Main := Label { property <int> a: 3; property <int> b: 4; }",
+    )
+    .unwrap();
+    assert!(component.borrow_mut().reload());
+    assert_eq!(reloads.get(), 1);
+    {
+        let component = component.borrow();
+        let instance = component.instance();
+        // Changed by the application
+        assert_eq!(instance.get_property("a").unwrap(), Value::Number(10.));
+        // Changed in the source
+        assert_eq!(instance.get_property("b").unwrap(), Value::Number(4.));
+    }
+
+    std::fs::write(&path, "export Main := Text { property <int> a: error; }").unwrap();
+    assert!(!component.borrow_mut().reload());
+    assert!(!component.borrow().diagnostics().is_empty());
+    assert_eq!(reloads.get(), 1);
+    assert_eq!(component.borrow().instance().get_property("a").unwrap(), Value::Number(10.));
+
+    std::fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn reload_handlers_can_access_the_component() {
+    i_slint_backend_testing::init();
+    let path = std::env::temp_dir()
+        .join(format!("slint_live_reload_handlers_test_{}.slint", std::process::id()));
+    std::fs::write(&path, "export Main := Window { property <int> a: 1; }").unwrap();
+
+    let component =
+        LiveReloadingComponent::new(ComponentCompiler::default(), path.clone()).unwrap();
+    let values = Rc::new(RefCell::new(Vec::new()));
+    component.borrow_mut().on_reload({
+        let component = Rc::downgrade(&component);
+        let values = values.clone();
+        move |_| {
+            let component = component.upgrade().unwrap();
+            let value = component.borrow().instance().get_property("a").unwrap();
+            values.borrow_mut().push(value);
+        }
+    });
+
+    std::fs::write(&path, "export Main := Window { property <int> a: 2; }").unwrap();
+    component.borrow().changed.store(true, Ordering::SeqCst);
+    reload_changed_components();
+    assert_eq!(*values.borrow(), vec![Value::Number(2.)]);
+
+    std::fs::remove_file(&path).ok();
+}
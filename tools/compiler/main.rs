@@ -4,7 +4,16 @@
 use clap::Parser;
 use i_slint_compiler::diagnostics::BuildDiagnostics;
 use i_slint_compiler::*;
-use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, clap::ValueEnum)]
+enum EmbedResources {
+    /// Reference the images with their absolute path on the file system
+    AsAbsolutePath,
+    /// Embed the content of the files in the generated code
+    EmbedFiles,
+}
 
 #[derive(Parser)]
 #[command(author, version, about, long_about = None)]
@@ -17,63 +26,194 @@ struct Cli {
     #[arg(short = 'I', name = "include path", number_of_values = 1, action)]
     include_paths: Vec<std::path::PathBuf>,
 
-    /// Path to .slint file ('-' for stdin)
-    #[arg(name = "file", action)]
-    path: std::path::PathBuf,
+    /// Library path for the files imported with '@name', given as 'name=path'
+    #[arg(short = 'L', name = "library path", value_parser = parse_library_path, number_of_values = 1, action)]
+    library_paths: Vec<(String, std::path::PathBuf)>,
 
-    /// The style name ('native' or 'fluent')
+    /// Path to .slint file ('-' for stdin). Several files, or glob patterns such as 'ui/*.slint',
+    /// are compiled in one go into the directory given with -o
+    #[arg(name = "file", required = true, action)]
+    paths: Vec<std::path::PathBuf>,
+
+    /// The style name ('native', 'fluent', 'material' or 'cupertino')
     #[arg(long, name = "style name", action)]
     style: Option<String>,
 
+    /// How the images are embedded in the generated code
+    #[arg(long, name = "embed resources", value_enum, action)]
+    embed_resources: Option<EmbedResources>,
+
+    /// The C++ namespace in which the generated types are put
+    #[arg(long, name = "C++ namespace", action)]
+    cpp_namespace: Option<String>,
+
     /// Generate a dependency file
     #[arg(name = "dependency file", long = "depfile", number_of_values = 1, action)]
     depfile: Option<std::path::PathBuf>,
 
-    /// Sets the output file ('-' for stdout)
+    /// Sets the output file ('-' for stdout), or the output directory when compiling several files
     #[arg(name = "file to generate", short = 'o', default_value = "-", action)]
     output: std::path::PathBuf,
 }
 
-fn main() -> std::io::Result<()> {
-    proc_macro2::fallback::force(); // avoid a abort if panic=abort is set
-    let args = Cli::parse();
+fn parse_library_path(arg: &str) -> Result<(String, std::path::PathBuf), String> {
+    let (name, path) = arg
+        .split_once('=')
+        .ok_or_else(|| format!("invalid library path '{}': expected 'name=path'", arg))?;
+    Ok((name.to_string(), path.into()))
+}
+
+/// The extension of the files generated when compiling several files
+fn output_extension(format: generator::OutputFormat) -> &'static str {
+    match format {
+        generator::OutputFormat::Cpp => "h",
+        generator::OutputFormat::Rust => "rs",
+        generator::OutputFormat::Interpreter | generator::OutputFormat::Llr => "llr",
+    }
+}
+
+fn is_glob_pattern(path: &Path) -> bool {
+    path.to_string_lossy().contains(|c| matches!(c, '*' | '?' | '['))
+}
+
+/// Expands the glob patterns among the paths given on the command line
+fn expand_paths(paths: &[PathBuf]) -> Vec<PathBuf> {
+    let mut result = Vec::new();
+    for path in paths {
+        if !is_glob_pattern(path) {
+            result.push(path.clone());
+            continue;
+        }
+        let matches = glob::glob(&path.to_string_lossy()).unwrap_or_else(|err| {
+            eprintln!("Invalid pattern {}: {}", path.display(), err);
+            std::process::exit(-1);
+        });
+        let len = result.len();
+        result.extend(matches.filter_map(Result::ok));
+        if result.len() == len {
+            eprintln!("No file matches {}", path.display());
+            std::process::exit(-1);
+        }
+    }
+    result
+}
+
+/// Compiles the .slint file at `path` into `output`, and returns the files that the generated file
+/// depends on, or None if there were errors
+fn compile(
+    path: &Path,
+    output: &Path,
+    format: generator::OutputFormat,
+    compiler_config: &CompilerConfiguration,
+) -> std::io::Result<Option<Vec<String>>> {
     let mut diag = BuildDiagnostics::default();
-    let syntax_node = parser::parse_file(&args.path, &mut diag);
+    let syntax_node = parser::parse_file(path, &mut diag);
     //println!("{:#?}", syntax_node);
     if diag.has_error() {
         diag.print();
-        std::process::exit(-1);
+        return Ok(None);
+    }
+    let syntax_node = syntax_node.expect("diags contained no compilation errors");
+    let (doc, diag) =
+        spin_on::spin_on(compile_syntax_node(syntax_node, diag, compiler_config.clone()));
+
+    if diag.has_error() {
+        diag.print();
+        return Ok(None);
+    }
+
+    if output == Path::new("-") {
+        generator::generate(format, &mut std::io::stdout(), &doc, compiler_config)?;
+    } else {
+        generator::generate(format, &mut std::fs::File::create(output)?, &doc, compiler_config)?;
+    }
+
+    let mut dependencies = Vec::new();
+    for x in &diag.all_loaded_files {
+        if x.is_absolute() {
+            dependencies.push(x.display().to_string());
+        }
     }
+    for resource in doc.root_component.embedded_file_resources.borrow().keys() {
+        dependencies.push(resource.clone());
+    }
+    diag.print();
+    Ok(Some(dependencies))
+}
+
+fn main() -> std::io::Result<()> {
+    proc_macro2::fallback::force(); // avoid a abort if panic=abort is set
+    let args = Cli::parse();
     let mut compiler_config = CompilerConfiguration::new(args.format);
     compiler_config.include_paths = args.include_paths;
+    compiler_config.library_paths = args.library_paths.into_iter().collect();
     if let Some(style) = args.style {
         compiler_config.style = Some(style);
     }
-    let syntax_node = syntax_node.expect("diags contained no compilation errors");
-    let (doc, diag) = spin_on::spin_on(compile_syntax_node(syntax_node, diag, compiler_config));
+    if let Some(embed_resources) = args.embed_resources {
+        compiler_config.embed_resources = match embed_resources {
+            EmbedResources::AsAbsolutePath => EmbedResourcesKind::OnlyBuiltinResources,
+            EmbedResources::EmbedFiles => EmbedResourcesKind::EmbedAllResources,
+        };
+    }
+    compiler_config.cpp_namespace = args.cpp_namespace;
 
-    let diag = diag.check_and_exit_on_error();
+    let batch = args.paths.len() > 1 || args.paths.iter().any(|path| is_glob_pattern(path));
+    let paths = expand_paths(&args.paths);
 
-    if args.output == std::path::Path::new("-") {
-        generator::generate(args.format, &mut std::io::stdout(), &doc)?;
+    // Each file is generated next to the others in the output directory
+    let outputs: Vec<PathBuf> = if batch {
+        if args.output == Path::new("-") || paths.iter().any(|path| path == Path::new("-")) {
+            eprintln!("With several files, -o must be a directory, and the files can't be read from stdin");
+            std::process::exit(-1);
+        }
+        std::fs::create_dir_all(&args.output)?;
+        let outputs: Vec<PathBuf> = paths
+            .iter()
+            .map(|path| {
+                let file_name = path.file_stem().map_or_else(|| "slint_out".into(), PathBuf::from);
+                args.output.join(file_name.with_extension(output_extension(args.format)))
+            })
+            .collect();
+        for (i, output) in outputs.iter().enumerate() {
+            if let Some(j) = outputs[..i].iter().position(|o| o == output) {
+                eprintln!(
+                    "{} and {} would both be generated into {}",
+                    paths[j].display(),
+                    paths[i].display(),
+                    output.display()
+                );
+                std::process::exit(-1);
+            }
+        }
+        // The files imported by several of the files are parsed only once
+        compiler_config.parsed_files_cache = Some(Rc::new(Default::default()));
+        outputs
     } else {
-        generator::generate(args.format, &mut std::fs::File::create(&args.output)?, &doc)?;
-    }
+        vec![args.output.clone()]
+    };
 
-    if let Some(depfile) = args.depfile {
-        let mut f = std::fs::File::create(depfile)?;
-        write!(f, "{}:", args.output.display())?;
-        for x in &diag.all_loaded_files {
-            if x.is_absolute() {
-                write!(f, " {}", x.display())?;
+    let mut has_error = false;
+    let mut depfile_rules = String::new();
+    for (path, output) in paths.iter().zip(outputs.iter()) {
+        match compile(path, output, args.format, &compiler_config)? {
+            Some(dependencies) => {
+                depfile_rules += &format!("{}:", output.display());
+                for dependency in dependencies {
+                    depfile_rules += &format!(" {}", dependency);
+                }
+                depfile_rules += "\n";
             }
+            None => has_error = true,
         }
-        for resource in doc.root_component.embedded_file_resources.borrow().keys() {
-            write!(f, " {}", resource)?;
-        }
+    }
+
+    if has_error {
+        std::process::exit(-1);
+    }
 
-        writeln!(f)?;
+    if let Some(depfile) = args.depfile {
+        std::fs::write(depfile, depfile_rules)?;
     }
-    diag.print_warnings_and_exit_on_error();
     Ok(())
 }
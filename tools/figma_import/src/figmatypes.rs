@@ -46,6 +46,15 @@ pub struct NodeCommon {
     pub visible: bool,
     #[serde(default)]
     pub children: Vec<Node>,
+    /// In an auto-layout frame, "STRETCH" if the node fills the frame in the counter axis
+    #[serde(default)]
+    pub layoutAlign: String,
+    /// In an auto-layout frame, 1 if the node fills the frame in the primary axis
+    #[serde(default)]
+    pub layoutGrow: f32,
+    /// "ABSOLUTE" if the node is not positioned by the auto-layout of its frame
+    #[serde(default)]
+    pub layoutPositioning: String,
 }
 
 #[derive(Debug, Deserialize, Default)]
@@ -222,6 +231,22 @@ pub struct Frame {
     pub isMask: bool,
     #[serde(default)]
     pub isMaskOutline: bool,
+    /// "HORIZONTAL" or "VERTICAL" for the auto-layout frames
+    #[serde(default)]
+    pub layoutMode: String,
+    #[serde(default)]
+    pub itemSpacing: f32,
+    #[serde(default)]
+    pub paddingLeft: f32,
+    #[serde(default)]
+    pub paddingRight: f32,
+    #[serde(default)]
+    pub paddingTop: f32,
+    #[serde(default)]
+    pub paddingBottom: f32,
+    /// "MIN", "CENTER", "MAX" or "SPACE_BETWEEN"
+    #[serde(default)]
+    pub primaryAxisAlignItems: String,
 }
 
 #[derive(Debug, Deserialize)]
@@ -327,6 +352,8 @@ pub enum Node {
         relativeTransform: Option<Transform>,
     },
     COMPONENT(Frame),
+    /// The variants of a component, which are its children
+    COMPONENT_SET(Frame),
     INSTANCE {
         #[serde(flatten)]
         frame: Frame,
@@ -351,9 +378,22 @@ pub fn common(&self) -> &NodeCommon {
             Node::TEXT { vector: VectorNode { node, .. }, .. } => node,
             Node::SLICE { node, .. } => node,
             Node::COMPONENT(Frame { node, .. }) => node,
+            Node::COMPONENT_SET(Frame { node, .. }) => node,
             Node::INSTANCE { frame: Frame { node, .. }, .. } => node,
         }
     }
+
+    /// The frame of the frames, groups, components and instances
+    pub fn frame(&self) -> Option<&Frame> {
+        match self {
+            Node::FRAME(frame)
+            | Node::GROUP(frame)
+            | Node::COMPONENT(frame)
+            | Node::COMPONENT_SET(frame)
+            | Node::INSTANCE { frame, .. } => Some(frame),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug, Deserialize)]
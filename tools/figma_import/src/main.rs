@@ -32,11 +32,12 @@ struct Opt {
 mod figmatypes;
 mod rendered;
 
-fn fill_hash<'x>(hash: &mut HashMap<&'x str, &'x figmatypes::Node>, node: &'x figmatypes::Node) {
+fn fill_hash<'x>(doc: &mut rendered::Document<'x>, node: &'x figmatypes::Node) {
     let n = node.common();
-    hash.insert(&n.id, node);
+    doc.nodeHash.insert(&n.id, node);
     for x in n.children.iter() {
-        fill_hash(hash, x);
+        doc.parents.insert(&x.common().id, node);
+        fill_hash(doc, x);
     }
 }
 
@@ -100,30 +101,25 @@ struct ImageResultMeta {
     Ok(r)
 }
 
-#[tokio::main]
-async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let opt = Opt::parse();
-
-    let r = if !opt.read_from_cache {
-        load_from_network(&opt).await?
-    } else {
-        let full_doc = std::fs::read("figma_output/cache.json")?;
-        serde_json::from_slice(&full_doc)?
-    };
-
-    let mut nodeHash = HashMap::new();
-    fill_hash(&mut nodeHash, &r.document);
-    let doc = rendered::Document { nodeHash };
+/// Renders the node of the first page with the given id or at the given index, or the start node
+/// of the prototype
+fn render_file(
+    file: &figmatypes::File,
+    node_id: Option<&str>,
+    child_index: Option<usize>,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let mut doc = rendered::Document { nodeHash: HashMap::new(), parents: HashMap::new() };
+    fill_hash(&mut doc, &file.document);
 
-    if let figmatypes::Node::DOCUMENT(document) = &r.document {
+    if let figmatypes::Node::DOCUMENT(document) = &file.document {
         if let figmatypes::Node::CANVAS { node, prototypeStartNodeID, backgroundColor, .. } =
             &document.children[0]
         {
-            let render_node = if let Some(node_id) = &opt.node_id {
+            let render_node = if let Some(node_id) = node_id {
                 doc.nodeHash
-                    .get(node_id.as_str())
+                    .get(node_id)
                     .ok_or_else(|| Error(format!("Could not find node id {}", node_id)))?
-            } else if let Some(child_index) = opt.child_index {
+            } else if let Some(child_index) = child_index {
                 node.children
                     .get(child_index)
                     .ok_or_else(|| Error(format!("The index {} does not exist", child_index)))?
@@ -137,11 +133,35 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                     )
                     .ok_or_else(|| Error("Start node not found".into()))?
             };
-            let result = rendered::render(node.name.as_str(), render_node, *backgroundColor, &doc)?;
-
-            std::fs::write("figma_output/main.slint", &result)?;
+            return rendered::render(node.name.as_str(), render_node, *backgroundColor, &doc);
         }
     }
+    Err(Error("The document has no page".into()).into())
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let opt = Opt::parse();
+
+    let r = if !opt.read_from_cache {
+        load_from_network(&opt).await?
+    } else {
+        let full_doc = std::fs::read("figma_output/cache.json")?;
+        serde_json::from_slice(&full_doc)?
+    };
+
+    let result = render_file(&r, opt.node_id.as_deref(), opt.child_index)?;
+    std::fs::write("figma_output/main.slint", &result)?;
 
     Ok(())
 }
+
+#[test]
+fn render_components_and_auto_layout() {
+    let dir: std::path::PathBuf = [env!("CARGO_MANIFEST_DIR"), "tests"].iter().collect();
+    let file: figmatypes::File =
+        serde_json::from_slice(&std::fs::read(dir.join("components.json")).unwrap()).unwrap();
+    let result = render_file(&file, None, None).unwrap();
+    let expected = std::fs::read_to_string(dir.join("components.slint")).unwrap();
+    assert_eq!(result, expected);
+}
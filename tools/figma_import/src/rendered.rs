@@ -2,12 +2,14 @@
 // SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
 
 use crate::figmatypes::{self, *};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fmt::Display;
 use std::fmt::Write;
 
 pub struct Document<'doc> {
     pub nodeHash: HashMap<&'doc str, &'doc figmatypes::Node>,
+    /// The parent of each node, by id
+    pub parents: HashMap<&'doc str, &'doc figmatypes::Node>,
     //pub images: HashMap<String, Vec<u8>>,
 }
 
@@ -35,11 +37,21 @@ fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
     }
 }
 
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum LayoutDirection {
+    Horizontal,
+    Vertical,
+}
+
 #[derive(Default)]
 struct Ctx {
     out: String,
     indent: Indent,
     offset: Vector,
+    /// The direction of the auto-layout of the parent frame, if it has one
+    layout: Option<LayoutDirection>,
+    /// The names of the generated components, by id of the Figma component or component set
+    components: HashMap<String, String>,
 }
 
 impl Ctx {
@@ -49,19 +61,39 @@ fn begin_element(
         node: &NodeCommon,
         absoluteBoundingBox: Option<&Rectangle>,
     ) -> std::fmt::Result {
-        writeln!(
-            self,
-            "id_{} := {} {{ /* {} */",
-            node.id.replace(":", "-").replace(";", "_"),
-            element,
-            node.name
-        )?;
+        writeln!(self, "{} := {} {{ /* {} */", element_id(node), element, node.name)?;
         self.indent += 1;
         if let Some(bb) = absoluteBoundingBox {
-            writeln!(self, "width: {}px;", bb.width)?;
-            writeln!(self, "height: {}px;", bb.height)?;
-            writeln!(self, "x: {}px;", bb.x - self.offset.x)?;
-            writeln!(self, "y: {}px;", bb.y - self.offset.y)?;
+            match self.layout {
+                // The layout positions the element, and sizes it if it fills the frame
+                Some(direction) => {
+                    let fills_primary_axis = node.layoutGrow > 0.;
+                    let fills_counter_axis = node.layoutAlign == "STRETCH";
+                    let (fills_width, fills_height) = match direction {
+                        LayoutDirection::Horizontal => (fills_primary_axis, fills_counter_axis),
+                        LayoutDirection::Vertical => (fills_counter_axis, fills_primary_axis),
+                    };
+                    if fills_width {
+                        writeln!(self, "horizontal-stretch: 1;")?;
+                    } else {
+                        writeln!(self, "width: {}px;", bb.width)?;
+                    }
+                    if fills_height {
+                        writeln!(self, "vertical-stretch: 1;")?;
+                    } else {
+                        writeln!(self, "height: {}px;", bb.height)?;
+                    }
+                }
+                None => {
+                    writeln!(self, "width: {}px;", bb.width)?;
+                    writeln!(self, "height: {}px;", bb.height)?;
+                    writeln!(self, "x: {}px;", bb.x - self.offset.x)?;
+                    writeln!(self, "y: {}px;", bb.y - self.offset.y)?;
+                }
+            }
+        }
+        if !node.visible {
+            writeln!(self, "visible: false;")?;
         }
         Ok(())
     }
@@ -120,6 +152,29 @@ pub fn render(
     };
 
     let mut ctx = Ctx::default();
+
+    let mut components = Vec::new();
+    collect_components(node, doc, &mut components);
+    let mut used_names: HashSet<String> =
+        ["App", "Clip", "Image", "Path", "Rectangle", "Text", "Window"]
+            .iter()
+            .map(|name| name.to_string())
+            .collect();
+    for component in components.iter() {
+        let base_name = component_name(&component.common().name);
+        let mut name = base_name.clone();
+        let mut suffix = 1;
+        while !used_names.insert(name.clone()) {
+            suffix += 1;
+            name = format!("{}{}", base_name, suffix);
+        }
+        ctx.components.insert(component.common().id.clone(), name);
+    }
+    for component in components {
+        render_component(component, &mut ctx, doc)?;
+        writeln!(ctx)?;
+    }
+
     writeln!(ctx, "App := Window {{")?;
     ctx.indent += 1;
     writeln!(ctx, "background: {};", background)?;
@@ -132,9 +187,279 @@ pub fn render(
     Ok(ctx.out)
 }
 
+fn element_id(node: &NodeCommon) -> String {
+    format!("id_{}", node.id.replace(":", "-").replace(";", "_"))
+}
+
+/// The name of the component generated for a Figma component: "Button / Primary" becomes "ButtonPrimary"
+fn component_name(name: &str) -> String {
+    let mut result = String::new();
+    for word in name.split(|c: char| !c.is_ascii_alphanumeric()).filter(|word| !word.is_empty()) {
+        let mut chars = word.chars();
+        result.extend(chars.next().map(|c| c.to_ascii_uppercase()));
+        result.push_str(chars.as_str());
+    }
+    if !result.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        result.insert_str(0, "Component");
+    }
+    result
+}
+
+/// The name of the property generated for a property of the variants: "Icon Position" becomes "icon-position"
+fn property_name(name: &str) -> String {
+    let result = name
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| word.to_ascii_lowercase())
+        .collect::<Vec<_>>()
+        .join("-");
+    if result.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        result
+    } else {
+        format!("variant-{}", result)
+    }
+}
+
+/// The properties of a variant, from its name such as "Size=Large, State=Hover"
+fn parse_variant_name(name: &str) -> Vec<(String, String)> {
+    name.split(',')
+        .filter_map(|property| {
+            let (name, value) = property.split_once('=')?;
+            Some((name.trim().to_string(), value.trim().to_string()))
+        })
+        .collect()
+}
+
+/// The node for which a component is generated when instantiating the component with the given id:
+/// the component set for a variant, the component itself otherwise
+fn component_node<'doc>(component_id: &str, doc: &Document<'doc>) -> Option<&'doc Node> {
+    let component = *doc.nodeHash.get(component_id)?;
+    match doc.parents.get(component_id) {
+        Some(set @ Node::COMPONENT_SET(_)) => Some(*set),
+        _ => Some(component),
+    }
+}
+
+/// Adds the components and component sets instantiated by the node and its children to `result`,
+/// after the components they use themselves.
+fn collect_components<'doc>(node: &'doc Node, doc: &Document<'doc>, result: &mut Vec<&'doc Node>) {
+    if let Node::INSTANCE { componentId, .. } = node {
+        if let Some(component) = component_node(componentId, doc) {
+            if !result.iter().any(|c| std::ptr::eq(*c, component)) {
+                collect_components(component, doc, result);
+                result.push(component);
+            }
+        }
+        // The children of an instance are copies of the children of its component
+        return;
+    }
+    for child in node.common().children.iter() {
+        collect_components(child, doc, result);
+    }
+}
+
+/// Declares the component generated for a Figma component or component set. The variants of a
+/// component set become states of the component, selected with a string property per property
+/// of the variants.
+fn render_component(
+    node: &Node,
+    rc: &mut Ctx,
+    doc: &Document,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let variants: Vec<&Node> = match node {
+        Node::COMPONENT(_) => vec![node],
+        Node::COMPONENT_SET(_) => node
+            .common()
+            .children
+            .iter()
+            .filter(|variant| matches!(variant, Node::COMPONENT(_)))
+            .collect(),
+        _ => return Err(super::Error("Rendering not a component".into()).into()),
+    };
+    let default_variant = *variants
+        .first()
+        .ok_or_else(|| super::Error(format!("Component set {:?} is empty", node.common().name)))?;
+    let frame = default_variant.frame().unwrap();
+
+    let prev_ctx = (rc.indent, rc.offset, rc.layout.take());
+    let name = rc.components[&node.common().id].clone();
+    writeln!(rc, "{} := Rectangle {{ /* {} */", name, node.common().name)?;
+    rc.indent += 1;
+    if matches!(node, Node::COMPONENT_SET(_)) {
+        for (name, value) in parse_variant_name(&frame.node.name) {
+            writeln!(
+                rc,
+                "property <string> {}: \"{}\";",
+                property_name(&name),
+                value.escape_debug()
+            )?;
+        }
+    }
+    writeln!(rc, "width: {}px;", frame.absoluteBoundingBox.width)?;
+    writeln!(rc, "height: {}px;", frame.absoluteBoundingBox.height)?;
+    rc.offset = frame.absoluteBoundingBox.origin();
+    render_frame_properties(frame, rc)?;
+    render_children(default_variant, rc, doc)?;
+
+    if variants.len() > 1 {
+        writeln!(rc, "states [")?;
+        rc.indent += 1;
+        for variant in variants.iter().skip(1) {
+            let properties = parse_variant_name(&variant.common().name);
+            if properties.is_empty() {
+                eprintln!(
+                    "Warning: ignoring variant {:?} without properties",
+                    variant.common().name
+                );
+                continue;
+            }
+            let state_name = property_name(
+                &properties
+                    .iter()
+                    .map(|(name, value)| format!("{} {}", name, value))
+                    .collect::<Vec<_>>()
+                    .join(" "),
+            );
+            let condition = properties
+                .iter()
+                .map(|(name, value)| {
+                    format!("root.{} == \"{}\"", property_name(name), value.escape_debug())
+                })
+                .collect::<Vec<_>>()
+                .join(" && ");
+            writeln!(rc, "{} when {} : {{", state_name, condition)?;
+            rc.indent += 1;
+            let mut changes = Vec::new();
+            diff_variant(default_variant, variant, "root", &mut changes);
+            for change in changes {
+                writeln!(rc, "{}", change)?;
+            }
+            rc.end_element()?;
+        }
+        rc.indent -= 1;
+        writeln!(rc, "]")?;
+    }
+
+    while rc.indent != prev_ctx.0 {
+        rc.indent -= 1;
+        writeln!(rc, "}}")?;
+    }
+    rc.offset = prev_ctx.1;
+    rc.layout = prev_ctx.2;
+    Ok(())
+}
+
+/// The properties that the states of a component change between its variants:
+/// the visibility, the texts and the solid colors
+fn variant_properties(node: &Node) -> Vec<(&'static str, String)> {
+    let solid_color = |paints: &[Paint]| {
+        paints
+            .iter()
+            .find(|p| p.visible && p.gradientStops.is_empty() && p.imageRef.is_none())
+            .and_then(|p| p.color)
+            .filter(|color| !color.is_transparent())
+            .map(|color| color.to_string())
+    };
+    let mut result = Vec::new();
+    if !node.common().visible {
+        result.push(("visible", "false".to_string()));
+    }
+    match node {
+        Node::FRAME(frame)
+        | Node::GROUP(frame)
+        | Node::COMPONENT(frame)
+        | Node::INSTANCE { frame, .. } => {
+            let color = solid_color(&frame.background).or_else(|| {
+                (!frame.backgroundColor.is_transparent()).then(|| frame.backgroundColor.to_string())
+            });
+            result.extend(color.map(|color| ("background", color)));
+        }
+        Node::RECTANGLE { vector, .. } => {
+            result.extend(solid_color(&vector.fills).map(|color| ("background", color)));
+        }
+        Node::TEXT { vector, characters, .. } => {
+            result.push(("text", format!("\"{}\"", characters.escape_debug())));
+            result.extend(solid_color(&vector.fills).map(|color| ("color", color)));
+        }
+        _ => (),
+    }
+    result
+}
+
+/// Adds to `changes` the property changes that turn the element of `default_variant` into `variant`,
+/// matching their children by name. The children that the variant doesn't have are hidden.
+fn diff_variant(default_variant: &Node, variant: &Node, element: &str, changes: &mut Vec<String>) {
+    let default_properties = variant_properties(default_variant);
+    let properties = variant_properties(variant);
+    for (name, value) in properties.iter() {
+        if !default_properties.contains(&(name, value.clone())) {
+            changes.push(format!("{}.{}: {};", element, name, value));
+        }
+    }
+    for (name, _) in default_properties.iter() {
+        if !properties.iter().any(|(n, _)| n == name) {
+            let default_value = match *name {
+                "visible" => "true",
+                "text" => "\"\"",
+                _ => "transparent",
+            };
+            changes.push(format!("{}.{}: {};", element, name, default_value));
+        }
+    }
+
+    let mut variant_children: Vec<&Node> = variant.common().children.iter().collect();
+    for child in default_variant.common().children.iter() {
+        let child_id = element_id(child.common());
+        match variant_children.iter().position(|c| c.common().name == child.common().name) {
+            Some(index) => diff_variant(child, variant_children.remove(index), &child_id, changes),
+            None => changes.push(format!("{}.visible: false;", child_id)),
+        }
+    }
+    for child in variant_children {
+        eprintln!(
+            "Warning: the layer {:?} of the variant {:?} is not in the default variant",
+            child.common().name,
+            variant.common().name
+        );
+    }
+}
+
+/// Renders an instance of a component declared with `render_component`
+fn render_instance(
+    frame: &Frame,
+    component_id: &str,
+    rc: &mut Ctx,
+    doc: &Document,
+) -> Result<bool, Box<dyn std::error::Error>> {
+    let component = match component_node(component_id, doc) {
+        Some(component) => component,
+        None => return Ok(false),
+    };
+    let name = match rc.components.get(&component.common().id) {
+        Some(name) => name.clone(),
+        None => return Ok(false),
+    };
+    rc.begin_element(&name, &frame.node, Some(&frame.absoluteBoundingBox))?;
+    if matches!(component, Node::COMPONENT_SET(_)) {
+        let variant = &doc.nodeHash[component_id].common().name;
+        for (name, value) in parse_variant_name(variant) {
+            writeln!(rc, "{}: \"{}\";", property_name(&name), value.escape_debug())?;
+        }
+    }
+    rc.end_element()?;
+    Ok(true)
+}
+
 fn render_frame(frame: &Frame, rc: &mut Ctx) -> Result<bool, Box<dyn std::error::Error>> {
     rc.begin_element("Rectangle", &frame.node, Some(&frame.absoluteBoundingBox))?;
     rc.offset = frame.absoluteBoundingBox.origin();
+    render_frame_properties(frame, rc)
+}
+
+fn render_frame_properties(
+    frame: &Frame,
+    rc: &mut Ctx,
+) -> Result<bool, Box<dyn std::error::Error>> {
     let mut has_background = false;
     for p in frame.background.iter() {
         has_background |= handle_paint(p, rc, "background")?;
@@ -148,6 +473,61 @@ fn render_frame(frame: &Frame, rc: &mut Ctx) -> Result<bool, Box<dyn std::error:
     Ok(frame.isMask)
 }
 
+fn auto_layout(node: &Node) -> Option<(&Frame, LayoutDirection)> {
+    let frame = node.frame()?;
+    match frame.layoutMode.as_str() {
+        "HORIZONTAL" => Some((frame, LayoutDirection::Horizontal)),
+        "VERTICAL" => Some((frame, LayoutDirection::Vertical)),
+        _ => None,
+    }
+}
+
+/// Renders the children of a node. The children of an auto-layout frame are put in a layout,
+/// except the ones that are positioned absolutely.
+fn render_children(
+    node: &figmatypes::Node,
+    rc: &mut Ctx,
+    doc: &Document,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let children = &node.common().children;
+    let prev_layout = rc.layout.take();
+    if let Some((frame, direction)) = auto_layout(node) {
+        let element = match direction {
+            LayoutDirection::Horizontal => "HorizontalLayout",
+            LayoutDirection::Vertical => "VerticalLayout",
+        };
+        writeln!(rc, "{} {{", element)?;
+        rc.indent += 1;
+        writeln!(rc, "padding-left: {}px;", frame.paddingLeft)?;
+        writeln!(rc, "padding-right: {}px;", frame.paddingRight)?;
+        writeln!(rc, "padding-top: {}px;", frame.paddingTop)?;
+        writeln!(rc, "padding-bottom: {}px;", frame.paddingBottom)?;
+        writeln!(rc, "spacing: {}px;", frame.itemSpacing)?;
+        let alignment = match frame.primaryAxisAlignItems.as_str() {
+            "CENTER" => "center",
+            "MAX" => "end",
+            "SPACE_BETWEEN" => "space-between",
+            _ => "start",
+        };
+        writeln!(rc, "alignment: {};", alignment)?;
+        rc.layout = Some(direction);
+        for x in children.iter().filter(|x| x.common().layoutPositioning != "ABSOLUTE") {
+            render_node(x, rc, doc)?;
+        }
+        rc.layout = None;
+        rc.end_element()?;
+        for x in children.iter().filter(|x| x.common().layoutPositioning == "ABSOLUTE") {
+            render_node(x, rc, doc)?;
+        }
+    } else {
+        for x in children.iter() {
+            render_node(x, rc, doc)?;
+        }
+    }
+    rc.layout = prev_layout;
+    Ok(())
+}
+
 fn render_vector(
     vector: &VectorNode,
     rc: &mut Ctx,
@@ -285,7 +665,13 @@ fn render_node(
         Node::FRAME(f) => render_frame(f, rc)?,
         Node::GROUP(f) => render_frame(f, rc)?,
         Node::COMPONENT(f) => render_frame(f, rc)?,
-        // Node::INSTANCE { frame } => frame,
+        Node::COMPONENT_SET(f) => render_frame(f, rc)?,
+        Node::INSTANCE { frame, componentId } => {
+            if render_instance(frame, componentId, rc, doc)? {
+                return Ok(());
+            }
+            render_frame(frame, rc)?
+        }
         Node::VECTOR(vector) => render_vector(vector, rc, doc)?,
         Node::BOOLEAN_OPERATION { vector, .. } => render_vector(vector, rc, doc)?,
         Node::STAR(vector) => render_vector(vector, rc, doc)?,
@@ -305,9 +691,7 @@ fn render_node(
         _ => false,
     };
 
-    for x in node.common().children.iter() {
-        render_node(&x, rc, doc)?;
-    }
+    render_children(node, rc, doc)?;
 
     if is_mask {
         return Ok(());
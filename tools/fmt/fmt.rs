@@ -4,16 +4,38 @@
 use crate::writer::TokenWriter;
 use i_slint_compiler::parser::{syntax_nodes, NodeOrToken, SyntaxKind, SyntaxNode};
 
-pub(crate) fn format_document(
+/// The options of the formatter
+#[derive(Clone, Debug)]
+pub struct FormatOptions {
+    /// The number of spaces of one level of indentation. The default is 4.
+    pub indent_width: usize,
+    /// The length after which long binary expressions and callback declarations are broken into
+    /// several lines. The default is 100.
+    pub max_line_length: usize,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self { indent_width: 4, max_line_length: 100 }
+    }
+}
+
+/// Formats the document, writing each of its tokens, or their replacement, to the writer
+pub fn format_document(
     doc: syntax_nodes::Document,
     writer: &mut impl TokenWriter,
+    options: &FormatOptions,
 ) -> Result<(), std::io::Error> {
-    let mut state = FormatState::default();
+    let mut state = FormatState { options: options.clone(), ..Default::default() };
     format_node(&doc, writer, &mut state)
 }
 
 #[derive(Default)]
 struct FormatState {
+    options: FormatOptions,
+    /// The number of characters written since the last new line
+    column: usize,
+
     /// The whitespace have been written, all further whitespace can be skipped
     skip_all_whitespace: bool,
     /// The whitespace to add before the next token
@@ -38,11 +60,29 @@ fn new_line(&mut self) {
             x.insert(0, '\n');
             return;
         }
-        let mut new_line = String::from("\n");
-        for _ in 0..self.indentation_level {
-            new_line += "    ";
+        let indentation = " ".repeat(self.indentation_level as usize * self.options.indent_width);
+        self.whitespace_to_add = Some(format!("\n{}", indentation));
+    }
+
+    /// Breaks the line before the next token, with one more level of indentation
+    fn continuation_line(&mut self) {
+        self.whitespace_to_add = None;
+        self.indentation_level += 1;
+        self.new_line();
+        self.indentation_level -= 1;
+    }
+
+    /// Whether `len` more characters fit in the line, after the whitespace to add
+    fn fits_in_line(&self, len: usize) -> bool {
+        let pending = self.whitespace_to_add.as_ref().map_or(0, |ws| ws.chars().count());
+        self.column + pending + len <= self.options.max_line_length
+    }
+
+    fn advance_column(&mut self, text: &str) {
+        match text.rfind('\n') {
+            Some(pos) => self.column = text[pos + 1..].chars().count(),
+            None => self.column += text.chars().count(),
         }
-        self.whitespace_to_add = Some(new_line);
     }
 
     fn insert_whitespace(&mut self, arg: &str) {
@@ -127,11 +167,14 @@ fn fold(
                 state.skip_all_whitespace = false;
                 if let Some(x) = state.whitespace_to_add.take() {
                     state.insertion_count += 1;
+                    state.advance_column(&x);
+                    state.advance_column(t.text());
                     writer.insert_before(t, x.as_ref())?;
                     return Ok(());
                 }
             }
             state.insertion_count += 1;
+            state.advance_column(t.text());
             writer.no_change(t)
         }
     }
@@ -320,12 +363,22 @@ fn format_callback_declaration(
     let _ok = whitespace_to(&mut sub, SyntaxKind::Identifier, writer, state, "")?
         && whitespace_to(&mut sub, SyntaxKind::DeclaredIdentifier, writer, state, " ")?;
 
+    let argument_lengths: Vec<usize> =
+        node.children().filter(|n| n.kind() == SyntaxKind::Type).map(|n| text_length(&n)).collect();
+    let mut argument_index = 0;
     while let Some(n) = sub.next() {
         state.skip_all_whitespace = true;
         match n.kind() {
             SyntaxKind::Comma => {
                 fold(n, writer, state)?;
-                state.insert_whitespace(" ");
+                argument_index += 1;
+                // The comma is followed by the next argument
+                let next_length = argument_lengths.get(argument_index).copied().unwrap_or(0);
+                if state.fits_in_line(next_length + 1) {
+                    state.insert_whitespace(" ");
+                } else {
+                    state.continuation_line();
+                }
             }
             SyntaxKind::Arrow => {
                 state.insert_whitespace(" ");
@@ -404,33 +457,53 @@ fn format_binary_expression(
     state: &mut FormatState,
 ) -> Result<(), std::io::Error> {
     let mut sub = node.children_with_tokens();
+    let rhs_length = node
+        .children()
+        .filter(|n| n.kind() == SyntaxKind::Expression)
+        .nth(1)
+        .map_or(0, |n| text_length(&n));
 
-    let _ok = whitespace_to(&mut sub, SyntaxKind::Expression, writer, state, "")?
-        && whitespace_to_one_of(
-            &mut sub,
-            &[
-                SyntaxKind::Plus,
-                SyntaxKind::Minus,
-                SyntaxKind::Star,
-                SyntaxKind::Div,
-                SyntaxKind::AndAnd,
-                SyntaxKind::OrOr,
-                SyntaxKind::EqualEqual,
-                SyntaxKind::NotEqual,
-                SyntaxKind::LAngle,
-                SyntaxKind::LessEqual,
-                SyntaxKind::RAngle,
-                SyntaxKind::GreaterEqual,
-            ],
-            writer,
-            state,
-            " ",
-        )?
-        && whitespace_to(&mut sub, SyntaxKind::Expression, writer, state, " ")?;
+    if !whitespace_to(&mut sub, SyntaxKind::Expression, writer, state, "")? {
+        return Ok(());
+    }
+    // Break the line before the operator if the right hand side doesn't fit
+    let operator_prefix = if state.fits_in_line(rhs_length + 4) {
+        " "
+    } else {
+        state.continuation_line();
+        ""
+    };
+    let _ok = whitespace_to_one_of(
+        &mut sub,
+        &[
+            SyntaxKind::Plus,
+            SyntaxKind::Minus,
+            SyntaxKind::Star,
+            SyntaxKind::Div,
+            SyntaxKind::AndAnd,
+            SyntaxKind::OrOr,
+            SyntaxKind::EqualEqual,
+            SyntaxKind::NotEqual,
+            SyntaxKind::LAngle,
+            SyntaxKind::LessEqual,
+            SyntaxKind::RAngle,
+            SyntaxKind::GreaterEqual,
+        ],
+        writer,
+        state,
+        operator_prefix,
+    )? && whitespace_to(&mut sub, SyntaxKind::Expression, writer, state, " ")?;
 
     Ok(())
 }
 
+/// The length of the text of the node, with its whitespace collapsed
+fn text_length(node: &SyntaxNode) -> usize {
+    let text = node.text().to_string();
+    let words: Vec<&str> = text.split_whitespace().collect();
+    words.iter().map(|w| w.chars().count()).sum::<usize>() + words.len().saturating_sub(1)
+}
+
 fn format_expression(
     node: &SyntaxNode,
     writer: &mut impl TokenWriter,
@@ -471,6 +544,10 @@ mod tests {
 
     // FIXME more descriptive errors when an assertion fails
     fn assert_formatting(unformatted: &str, formatted: &str) {
+        assert_formatting_with_options(unformatted, formatted, &FormatOptions::default())
+    }
+
+    fn assert_formatting_with_options(unformatted: &str, formatted: &str, options: &FormatOptions) {
         // Parse the unformatted string
         let syntax_node = i_slint_compiler::parser::parse(
             String::from(unformatted),
@@ -480,7 +557,7 @@ fn assert_formatting(unformatted: &str, formatted: &str) {
         // Turn the syntax node into a document
         let doc = syntax_nodes::Document::new(syntax_node).unwrap();
         let mut file = Vec::new();
-        format_document(doc, &mut FileWriter { file: &mut file }).unwrap();
+        format_document(doc, &mut FileWriter { file: &mut file }, options).unwrap();
         assert_eq!(String::from_utf8(file).unwrap(), formatted);
     }
 
@@ -637,4 +714,50 @@ fn children() {
 }"#,
         );
     }
+
+    #[test]
+    fn indent_width() {
+        assert_formatting_with_options(
+            r#"
+A := B { C { x: 3px; } }"#,
+            r#"
+A := B {
+  C {
+    x: 3px;
+  }
+}"#,
+            &FormatOptions { indent_width: 2, ..Default::default() },
+        );
+    }
+
+    #[test]
+    fn max_line_length() {
+        let options = FormatOptions { max_line_length: 30, ..Default::default() };
+        assert_formatting_with_options(
+            r#"
+A := B {
+    text: first-value + second-value;
+    short: a + b;
+}"#,
+            r#"
+A := B {
+    text: first-value
+        + second-value;
+    short: a + b;
+}"#,
+            &options,
+        );
+        assert_formatting_with_options(
+            r#"
+A := B {
+    callback clicked(string, int, bool);
+}"#,
+            r#"
+A := B {
+    callback clicked(string,
+        int, bool);
+}"#,
+            &options,
+        );
+    }
 }
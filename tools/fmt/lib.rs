@@ -0,0 +1,16 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+/*!
+    The formatter engine of `slint-fmt`, also used by the LSP to format documents.
+
+    [`format_document()`] goes through all the tokens of a parsed document and passes them to a
+    [`TokenWriter`], either unchanged or with the whitespace to insert before them. The
+    [`FileWriter`] writes the formatted document, while the LSP collects the edits.
+*/
+
+mod fmt;
+mod writer;
+
+pub use fmt::{format_document, FormatOptions};
+pub use writer::{FileWriter, TokenWriter};
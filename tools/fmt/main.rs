@@ -8,52 +8,95 @@
         cargo run --bin slint-fmt -- -i some_file.slint
     ```
 
+    Use `--check` to only verify that the files are formatted, for example in CI.
+
     Some code in this main.rs file is duplicated with the syntax_updater, i guess it could
     be refactored in a separate utility crate or module or something.
 
-    The [`writer::TokenWriter`] trait is meant to be able to support the LSP later as the
-    LSP wants just the edits, not the full file
+    The formatter itself is in the library of this crate, which is shared with the LSP.
 */
 
 use i_slint_compiler::diagnostics::BuildDiagnostics;
 use i_slint_compiler::parser::{syntax_nodes, SyntaxNode};
-use std::io::Write;
+use slint_fmt::FormatOptions;
+use std::io::{Read, Write};
 use std::path::Path;
 
 use clap::Parser;
 
-mod fmt;
-mod writer;
-
 #[derive(clap::Parser)]
 #[command(author, version, about, long_about = None)]
 struct Cli {
+    /// The files to format ('-' or none for stdin, which is formatted as .slint to stdout)
     #[arg(name = "path to .slint file(s)", action)]
     paths: Vec<std::path::PathBuf>,
 
     /// modify the file inline instead of printing to stdout
     #[arg(short, long, action)]
     inline: bool,
+
+    /// don't write anything, but exit with an error if a file is not formatted
+    #[arg(long, conflicts_with = "inline", action)]
+    check: bool,
+
+    /// the number of spaces of one level of indentation
+    #[arg(long, default_value_t = FormatOptions::default().indent_width, action)]
+    indent_width: usize,
+
+    /// the length after which long expressions and callback declarations are broken into several lines
+    #[arg(long, default_value_t = FormatOptions::default().max_line_length, action)]
+    max_line_length: usize,
 }
 
 fn main() -> std::io::Result<()> {
     let args = Cli::parse();
+    let options =
+        FormatOptions { indent_width: args.indent_width, max_line_length: args.max_line_length };
+
+    let paths =
+        if args.paths.is_empty() { vec![std::path::PathBuf::from("-")] } else { args.paths };
+    let mut unformatted_files = 0;
+    for path in paths {
+        let source = if path == Path::new("-") {
+            let mut source = String::new();
+            std::io::stdin().read_to_string(&mut source)?;
+            source
+        } else {
+            std::fs::read_to_string(&path)?
+        };
 
-    for path in args.paths {
-        let source = std::fs::read_to_string(&path)?;
-
-        if args.inline {
-            let file = std::fs::File::create(&path)?;
-            process_file(source, path, file)?
+        let mut formatted = Vec::new();
+        process_file(source.clone(), path.clone(), &mut formatted, &options)?;
+        if args.check {
+            if formatted != source.as_bytes() {
+                let name = if path == Path::new("-") {
+                    "<stdin>".to_string()
+                } else {
+                    path.display().to_string()
+                };
+                eprintln!("{} is not formatted", name);
+                unformatted_files += 1;
+            }
+        } else if args.inline && path != Path::new("-") {
+            if formatted != source.as_bytes() {
+                std::fs::write(&path, formatted)?;
+            }
         } else {
-            process_file(source, path, std::io::stdout())?
+            std::io::stdout().write_all(&formatted)?;
         }
     }
+    if unformatted_files > 0 {
+        std::process::exit(1);
+    }
     Ok(())
 }
 
 /// FIXME! this is duplicated with the updater
-fn process_rust_file(source: String, mut file: impl Write) -> std::io::Result<()> {
+fn process_rust_file(
+    source: String,
+    mut file: impl Write,
+    options: &FormatOptions,
+) -> std::io::Result<()> {
     let mut source_slice = &source[..];
     let slint_macro = format!("{}!", "slint"); // in a variable so it does not appear as is
     'l: while let Some(idx) = source_slice.find(&slint_macro) {
@@ -98,7 +141,7 @@ fn process_rust_file(source: String, mut file: impl Write) -> std::io::Result<()
         let mut diag = BuildDiagnostics::default();
         let syntax_node = i_slint_compiler::parser::parse(code.to_owned(), None, &mut diag);
         let len = syntax_node.text_range().end().into();
-        visit_node(syntax_node, &mut file)?;
+        visit_node(syntax_node, &mut file, options)?;
         if diag.has_error() {
             file.write_all(&code.as_bytes()[len..])?;
             diag.print();
@@ -108,7 +151,11 @@ fn process_rust_file(source: String, mut file: impl Write) -> std::io::Result<()
 }
 
 /// FIXME! this is duplicated with the updater
-fn process_markdown_file(source: String, mut file: impl Write) -> std::io::Result<()> {
+fn process_markdown_file(
+    source: String,
+    mut file: impl Write,
+    options: &FormatOptions,
+) -> std::io::Result<()> {
     let mut source_slice = &source[..];
     const CODE_FENCE_START: &str = "```slint\n";
     const CODE_FENCE_END: &str = "```\n";
@@ -128,7 +175,7 @@ fn process_markdown_file(source: String, mut file: impl Write) -> std::io::Resul
         let mut diag = BuildDiagnostics::default();
         let syntax_node = i_slint_compiler::parser::parse(code.to_owned(), None, &mut diag);
         let len = syntax_node.text_range().end().into();
-        visit_node(syntax_node, &mut file)?;
+        visit_node(syntax_node, &mut file, options)?;
         if diag.has_error() {
             file.write_all(&code.as_bytes()[len..])?;
             diag.print();
@@ -141,11 +188,12 @@ fn process_slint_file(
     source: String,
     path: std::path::PathBuf,
     mut file: impl Write,
+    options: &FormatOptions,
 ) -> std::io::Result<()> {
     let mut diag = BuildDiagnostics::default();
     let syntax_node = i_slint_compiler::parser::parse(source.clone(), Some(&path), &mut diag);
     let len = syntax_node.node.text_range().end().into();
-    visit_node(syntax_node, &mut file)?;
+    visit_node(syntax_node, &mut file, options)?;
     if diag.has_error() {
         file.write_all(&source.as_bytes()[len..])?;
         diag.print();
@@ -157,16 +205,19 @@ fn process_file(
     source: String,
     path: std::path::PathBuf,
     mut file: impl Write,
+    options: &FormatOptions,
 ) -> std::io::Result<()> {
     match path.extension() {
-        Some(ext) if ext == "rs" => process_rust_file(source, file),
-        Some(ext) if ext == "md" => process_markdown_file(source, file),
+        Some(ext) if ext == "rs" => process_rust_file(source, file, options),
+        Some(ext) if ext == "md" => process_markdown_file(source, file, options),
         // Formatting .60 files because of backwards compatibility (project was recently renamed)
-        Some(ext) if ext == "slint" || ext == ".60" => process_slint_file(source, path, file),
+        Some(ext) if ext == "slint" || ext == ".60" => {
+            process_slint_file(source, path, file, options)
+        }
         _ => {
             // This allows usage like `cat x.slint | slint-fmt /dev/stdin`
-            if path.as_path() == Path::new("/dev/stdin") {
-                return process_slint_file(source, path, file);
+            if path.as_path() == Path::new("/dev/stdin") || path.as_path() == Path::new("-") {
+                return process_slint_file(source, path, file, options);
             }
             // With other file types, we just output them in their original form.
             return file.write_all(source.as_bytes());
@@ -174,10 +225,14 @@ fn process_file(
     }
 }
 
-fn visit_node(node: SyntaxNode, file: &mut impl Write) -> std::io::Result<()> {
+fn visit_node(
+    node: SyntaxNode,
+    file: &mut impl Write,
+    options: &FormatOptions,
+) -> std::io::Result<()> {
     if let Some(doc) = syntax_nodes::Document::new(node) {
-        let mut writer = writer::FileWriter { file };
-        fmt::format_document(doc, &mut writer)
+        let mut writer = slint_fmt::FileWriter { file };
+        slint_fmt::format_document(doc, &mut writer, options)
     } else {
         Err(std::io::Error::new(std::io::ErrorKind::Other, "Not a Document"))
     }
@@ -0,0 +1,1166 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+use super::DocumentCache;
+#[cfg(target_arch = "wasm32")]
+use crate::wasm_prelude::*;
+use i_slint_compiler::diagnostics::{BuildDiagnostics, Spanned};
+use i_slint_compiler::expression_tree::Expression;
+use i_slint_compiler::langtype::Type;
+use i_slint_compiler::object_tree::Element;
+use i_slint_compiler::parser::{normalize_identifier, syntax_nodes, SyntaxKind, SyntaxNode};
+use lsp_types::{
+    CodeAction, CodeActionKind, CreateFile, DocumentChangeOperation, DocumentChanges, OneOf,
+    OptionalVersionedTextDocumentIdentifier, Position, Range, ResourceOp, TextDocumentEdit,
+    TextEdit, Url, WorkspaceEdit,
+};
+use rowan::{TextRange, TextSize};
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashSet};
+use std::path::Path;
+
+/// The kind of the code action that moves a component into a new file
+pub const REFACTOR_MOVE: CodeActionKind = CodeActionKind::new("refactor.move");
+
+/// Parse the document again, returning None if it has syntax errors, since the edits
+/// would then be computed on a partial tree
+fn parse_document(document_cache: &DocumentCache, uri: &Url) -> Option<syntax_nodes::Document> {
+    let path = uri.to_file_path().ok()?;
+    let source = document_cache
+        .documents
+        .get_document(&path)?
+        .node
+        .as_ref()?
+        .source_file()?
+        .source()?
+        .to_owned();
+    let mut diag = BuildDiagnostics::default();
+    let node = i_slint_compiler::parser::parse(source, Some(&path), &mut diag);
+    if diag.has_error() {
+        return None;
+    }
+    syntax_nodes::Document::new(node)
+}
+
+fn text_edit(
+    document_cache: &mut DocumentCache,
+    uri: &Url,
+    start: u32,
+    end: u32,
+    new_text: String,
+) -> Option<TextEdit> {
+    Some(TextEdit::new(
+        Range::new(
+            document_cache.byte_offset_to_position(start, uri)?,
+            document_cache.byte_offset_to_position(end, uri)?,
+        ),
+        new_text,
+    ))
+}
+
+fn workspace_edit(uri: &Url, edits: Vec<TextEdit>) -> WorkspaceEdit {
+    WorkspaceEdit {
+        changes: Some(std::iter::once((uri.clone(), edits)).collect()),
+        ..Default::default()
+    }
+}
+
+/// The byte offset of a position, whose character is a byte offset within the line as everywhere
+/// in this server
+fn position_to_offset(source: &str, pos: Position) -> u32 {
+    let line_start = match pos.line {
+        0 => 0,
+        line => source.match_indices('\n').nth(line as usize - 1).map_or(source.len(), |m| m.0 + 1),
+    };
+    (line_start + pos.character as usize).min(source.len()) as u32
+}
+
+/// The indentation of the line that contains the offset
+fn line_indentation(source: &str, offset: usize) -> &str {
+    let line_start = source[..offset].rfind('\n').map_or(0, |p| p + 1);
+    let line = &source[line_start..];
+    &line[..line.find(|c: char| c != ' ' && c != '\t').unwrap_or(line.len())]
+}
+
+/// The offset after the end of the line, if there is only whitespace between the offset and it
+fn end_of_line(source: &str, offset: usize) -> usize {
+    let rest = &source[offset..];
+    rest.find('\n')
+        .filter(|pos| rest[..*pos].trim().is_empty())
+        .map_or(offset, |pos| offset + pos + 1)
+}
+
+/// The range of the node without the leading and trailing whitespace and comments
+fn trimmed_range(node: &SyntaxNode) -> Option<TextRange> {
+    let mut tokens = node
+        .descendants_with_tokens()
+        .filter_map(|e| e.into_token())
+        .filter(|t| !matches!(t.kind(), SyntaxKind::Whitespace | SyntaxKind::Comment));
+    let first = tokens.next()?;
+    let last = tokens.last().unwrap_or_else(|| first.clone());
+    Some(TextRange::new(first.text_range().start(), last.text_range().end()))
+}
+
+/// The imports of one file, as they are merged by organize_imports
+#[derive(Default)]
+struct FileImports {
+    /// There is an `import "file";` statement without names (e.g. for fonts)
+    plain: bool,
+    /// The text of the imported names, indexed by their normalized (external, internal) names
+    names: BTreeMap<(String, String), String>,
+}
+
+/// Merge the import statements of the same file, remove the imported names that are not used in
+/// the document, and sort the files and the names.
+/// Returns None if the imports are already organized.
+pub fn organize_imports(document_cache: &mut DocumentCache, uri: &Url) -> Option<CodeAction> {
+    let doc = parse_document(document_cache, uri)?;
+    let imports = doc.ImportSpecifier().collect::<Vec<_>>();
+    if imports.is_empty() {
+        return None;
+    }
+
+    // The identifiers outside of the import statements: the element types, the globals, and the
+    // re-exported names are all referred to by one of them
+    let used = doc
+        .descendants_with_tokens()
+        .filter_map(|e| e.into_token())
+        .filter(|t| {
+            t.kind() == SyntaxKind::Identifier
+                && !t.parent_ancestors().any(|n| n.kind() == SyntaxKind::ImportSpecifier)
+        })
+        .map(|t| normalize_identifier(t.text()))
+        .collect::<HashSet<_>>();
+
+    let mut files = BTreeMap::<String, FileImports>::new();
+    for import in &imports {
+        let file = import.child_text(SyntaxKind::StringLiteral)?;
+        let entry = files.entry(file).or_default();
+        let list = match import.ImportIdentifierList() {
+            Some(list) => list,
+            None => {
+                entry.plain = true;
+                continue;
+            }
+        };
+        for identifier in list.ImportIdentifier() {
+            let external = identifier.ExternalName().text().to_string().trim().to_owned();
+            let internal =
+                identifier.InternalName().map(|n| n.text().to_string().trim().to_owned());
+            let key = (
+                normalize_identifier(&external),
+                normalize_identifier(internal.as_ref().unwrap_or(&external)),
+            );
+            if !used.contains(&key.1) {
+                continue;
+            }
+            let text = match internal {
+                Some(internal) => format!("{} as {}", external, internal),
+                None => external,
+            };
+            entry.names.entry(key).or_insert(text);
+        }
+    }
+
+    let mut organized = String::new();
+    for (file, imports) in &files {
+        if imports.plain {
+            organized += &format!("import {};\n", file);
+        }
+        if !imports.names.is_empty() {
+            let names = imports.names.values().cloned().collect::<Vec<_>>().join(", ");
+            organized += &format!("import {{ {} }} from {};\n", names, file);
+        }
+    }
+
+    let source = doc.source_file()?.source()?;
+    // Remove the import statements up to the end of their line
+    let end_of_import = |import: &syntax_nodes::ImportSpecifier| -> u32 {
+        end_of_line(source, import.text_range().end().into()) as u32
+    };
+    let first = imports.first()?;
+    let start = u32::from(first.text_range().start());
+    // Only whitespace separates the imports
+    let contiguous = imports.windows(2).all(|w| {
+        source[usize::from(w[0].text_range().end())..usize::from(w[1].text_range().start())]
+            .trim()
+            .is_empty()
+    });
+    if contiguous
+        && source[start as usize..end_of_import(imports.last()?) as usize].trim_end()
+            == organized.trim_end()
+    {
+        return None;
+    }
+
+    let mut edits = vec![];
+    edits.push(text_edit(document_cache, uri, start, end_of_import(first), organized)?);
+    for import in &imports[1..] {
+        edits.push(text_edit(
+            document_cache,
+            uri,
+            import.text_range().start().into(),
+            end_of_import(import),
+            String::new(),
+        )?);
+    }
+
+    Some(CodeAction {
+        title: "Organize imports".into(),
+        kind: Some(CodeActionKind::SOURCE_ORGANIZE_IMPORTS),
+        edit: Some(workspace_edit(uri, edits)),
+        ..Default::default()
+    })
+}
+
+/// The token texts of the expression, to find the identical occurrences
+fn expression_tokens(node: &SyntaxNode) -> Vec<String> {
+    node.descendants_with_tokens()
+        .filter_map(|e| e.into_token())
+        .filter(|t| !matches!(t.kind(), SyntaxKind::Whitespace | SyntaxKind::Comment))
+        .map(|t| t.text().to_string())
+        .collect()
+}
+
+/// The expression is (part of) the binding of a property, not in a callback handler
+fn is_in_binding(node: &SyntaxNode) -> bool {
+    let mut in_binding = false;
+    for ancestor in node.ancestors() {
+        match ancestor.kind() {
+            SyntaxKind::BindingExpression => in_binding = true,
+            SyntaxKind::CallbackConnection => return false,
+            SyntaxKind::Element => break,
+            _ => (),
+        }
+    }
+    in_binding
+}
+
+/// Compile the expression as if it was in a binding of the element of `context`.
+/// Returns its type, and what identifies its meaning: the debug output and the elements it refers
+/// to, since the debug output only contains their ids.
+fn compile_in_context(
+    document_cache: &DocumentCache,
+    context: SyntaxNode,
+    expression: &syntax_nodes::Expression,
+) -> Option<(Type, String, Vec<*const RefCell<Element>>)> {
+    crate::util::with_lookup_ctx(document_cache, context, |ctx| {
+        let expr = Expression::from_expression_node(expression.clone(), ctx);
+        if ctx.diag.has_error() {
+            return None;
+        }
+        let mut elements = vec![];
+        expr.visit_recursive(&mut |e| match e {
+            Expression::PropertyReference(nr) | Expression::CallbackReference(nr) => {
+                elements.push(std::rc::Rc::as_ptr(&nr.element()))
+            }
+            Expression::ElementReference(element)
+            | Expression::RepeaterIndexReference { element }
+            | Expression::RepeaterModelReference { element } => elements.push(element.as_ptr()),
+            _ => (),
+        });
+        Some((expr.ty(), format!("{:?}", expr), elements))
+    })?
+}
+
+/// Extract the selected expression into a new property of the component, and replace the
+/// occurrences of the same expression in the component by a reference to that property
+pub fn extract_property(
+    document_cache: &mut DocumentCache,
+    uri: &Url,
+    range: Range,
+) -> Option<CodeAction> {
+    let doc = parse_document(document_cache, uri)?;
+    let source = doc.source_file()?.source()?;
+    let start = position_to_offset(source, range.start);
+    let end = position_to_offset(source, range.end);
+    let selected = &source[start as usize..end as usize];
+    let selection = TextRange::at(
+        TextSize::from(start + (selected.len() - selected.trim_start().len()) as u32),
+        TextSize::from(selected.trim().len() as u32),
+    );
+    if selection.is_empty() {
+        return None;
+    }
+
+    // The outermost expression that is exactly the selection
+    let expression = doc
+        .covering_element(selection)
+        .ancestors()
+        .filter(|n| n.kind() == SyntaxKind::Expression)
+        .map(|node| SyntaxNode { node, source_file: doc.source_file.clone() })
+        .take_while(|n| trimmed_range(n) == Some(selection))
+        .last()?;
+    if !is_in_binding(&expression) {
+        return None;
+    }
+
+    let component =
+        expression.ancestors().find(|n| n.kind() == SyntaxKind::Component).and_then(|node| {
+            syntax_nodes::Component::new(SyntaxNode { node, source_file: doc.source_file.clone() })
+        })?;
+    let root_element: SyntaxNode = component.Element().into();
+    // The meaning of the expression must be the same in the root element, where the property goes
+    let (ty, meaning, elements) = compile_in_context(
+        document_cache,
+        root_element.clone(),
+        &syntax_nodes::Expression::new(expression.clone())?,
+    )?;
+    if !ty.is_property_type()
+        || matches!(ty, Type::ElementReference | Type::InferredProperty | Type::Model)
+    {
+        return None;
+    }
+
+    let used_names = component
+        .descendants_with_tokens()
+        .filter_map(|e| e.into_token())
+        .filter(|t| t.kind() == SyntaxKind::Identifier)
+        .map(|t| normalize_identifier(t.text()))
+        .collect::<HashSet<_>>();
+    let name = std::iter::once("new-property".to_owned())
+        .chain((2..).map(|i| format!("new-property-{}", i)))
+        .find(|n| !used_names.contains(n))?;
+
+    let tokens = expression_tokens(&expression);
+    let mut replaced: Vec<TextRange> = vec![];
+    for node in component.descendants() {
+        if node.kind() != SyntaxKind::Expression
+            || replaced.iter().any(|r| r.contains_range(node.text_range()))
+        {
+            continue;
+        }
+        let node = SyntaxNode { node, source_file: doc.source_file.clone() };
+        if expression_tokens(&node) != tokens || !is_in_binding(&node) {
+            continue;
+        }
+        let same_meaning = syntax_nodes::Expression::new(node.clone())
+            .and_then(|e| compile_in_context(document_cache, node.clone(), &e))
+            .map_or(false, |(_, m, e)| m == meaning && e == elements);
+        if same_meaning {
+            replaced.push(trimmed_range(&node)?);
+        }
+    }
+    if replaced.is_empty() {
+        // The selection itself has another meaning in the root element
+        return None;
+    }
+
+    let mut edits = vec![];
+    let lbrace = root_element.child_token(SyntaxKind::LBrace)?;
+    let indent = match lbrace.next_token() {
+        Some(ws) if ws.kind() == SyntaxKind::Whitespace && ws.text().contains('\n') => {
+            ws.text().rsplit('\n').next().unwrap_or_default().to_owned()
+        }
+        _ => format!("{}    ", line_indentation(source, lbrace.text_range().start().into())),
+    };
+    edits.push(text_edit(
+        document_cache,
+        uri,
+        lbrace.text_range().end().into(),
+        lbrace.text_range().end().into(),
+        format!(
+            "\n{}property <{}> {}: {};",
+            indent,
+            ty,
+            name,
+            &source[usize::from(selection.start())..usize::from(selection.end())]
+        ),
+    )?);
+    for range in replaced {
+        let in_root_element = doc
+            .covering_element(range)
+            .ancestors()
+            .find(|n| n.kind() == SyntaxKind::Element)
+            .map_or(false, |e| e.text_range() == root_element.text_range());
+        let reference = if in_root_element { name.clone() } else { format!("root.{}", name) };
+        edits.push(text_edit(
+            document_cache,
+            uri,
+            range.start().into(),
+            range.end().into(),
+            reference,
+        )?);
+    }
+
+    Some(CodeAction {
+        title: "Extract into a property".into(),
+        kind: Some(CodeActionKind::REFACTOR_EXTRACT),
+        edit: Some(workspace_edit(uri, edits)),
+        ..Default::default()
+    })
+}
+
+/// The source of the range, with the replacements of the sub-ranges applied
+fn text_with_replacements(
+    source: &str,
+    range: TextRange,
+    replacements: &[(TextRange, String)],
+) -> String {
+    let mut replacements =
+        replacements.iter().filter(|(r, _)| range.contains_range(*r)).collect::<Vec<_>>();
+    replacements.sort_by_key(|(r, _)| r.start());
+    let mut result = String::new();
+    let mut pos = usize::from(range.start());
+    for (r, text) in replacements {
+        result += &source[pos..r.start().into()];
+        result += text;
+        pos = r.end().into();
+    }
+    result += &source[pos..range.end().into()];
+    result
+}
+
+/// Replace the indentation `from` of the lines after the first one by `to`
+fn reindent(text: &str, from: &str, to: &str) -> String {
+    text.split('\n')
+        .enumerate()
+        .map(|(i, line)| match line.strip_prefix(from) {
+            Some(rest) if i > 0 => format!("{}{}", to, rest),
+            _ => line.to_owned(),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// The normalized identifiers of a qualified name
+fn qualified_name_identifiers(qualified_name: &SyntaxNode) -> Vec<String> {
+    qualified_name
+        .children_with_tokens()
+        .filter_map(|e| e.into_token())
+        .filter(|t| t.kind() == SyntaxKind::Identifier)
+        .map(|t| normalize_identifier(t.text()))
+        .collect()
+}
+
+/// The name of the property or callback set by a binding, a two-way binding, or a callback connection
+fn member_name(member: &SyntaxNode) -> Option<String> {
+    match member.kind() {
+        SyntaxKind::Binding | SyntaxKind::TwoWayBinding | SyntaxKind::CallbackConnection => {
+            i_slint_compiler::parser::identifier_text(member)
+        }
+        _ => None,
+    }
+}
+
+fn is_child_element(member: &SyntaxNode) -> bool {
+    matches!(
+        member.kind(),
+        SyntaxKind::SubElement | SyntaxKind::RepeatedElement | SyntaxKind::ConditionalElement
+    )
+}
+
+/// Replace the instantiation of a component of this document under the cursor by the content of
+/// the component. The properties set to a constant at the use site are substituted into the
+/// bindings of the component when possible. The component is removed if it is not used elsewhere.
+pub fn inline_component(
+    document_cache: &mut DocumentCache,
+    uri: &Url,
+    position: Position,
+) -> Option<CodeAction> {
+    let doc = parse_document(document_cache, uri)?;
+    let source = doc.source_file()?.source()?;
+    let source_file = doc.source_file.clone();
+    let wrap = |node| SyntaxNode { node, source_file: source_file.clone() };
+
+    // The element whose type name is under the cursor
+    let offset = position_to_offset(source, position);
+    let element = doc
+        .token_at_offset(offset.into())
+        .find_map(|t| t.parent_ancestors().find(|n| n.kind() == SyntaxKind::QualifiedName))
+        .and_then(|q| q.parent())
+        .filter(|e| e.kind() == SyntaxKind::Element)
+        .map(wrap)
+        .and_then(syntax_nodes::Element::new)?;
+    let name = normalize_identifier(element.QualifiedName()?.text().to_string().trim());
+
+    let component =
+        doc.Component().chain(doc.ExportsList().filter_map(|e| e.Component())).find(|c| {
+            i_slint_compiler::parser::identifier_text(&c.DeclaredIdentifier()).as_ref()
+                == Some(&name)
+        })?;
+    if component.child_text(SyntaxKind::Identifier).map_or(false, |t| t == "global") {
+        return None;
+    }
+    let root = component.Element();
+    // Components without base (with the `component` keyword) cannot be inlined into an element
+    let base = root.QualifiedName()?;
+    let use_site = element
+        .ancestors()
+        .find(|n| n.kind() == SyntaxKind::Component)
+        .map(wrap)
+        .and_then(syntax_nodes::Component::new)?;
+
+    let element_ids = |c: &SyntaxNode| {
+        c.descendants()
+            .filter(|n| n.kind() == SyntaxKind::SubElement)
+            .filter_map(|n| wrap(n).child_text(SyntaxKind::Identifier))
+            .map(|id| normalize_identifier(&id))
+            .collect::<HashSet<_>>()
+    };
+    let use_site_ids = element_ids(&use_site);
+    if !element_ids(&root).is_disjoint(&use_site_ids) {
+        return None;
+    }
+
+    // The id that replaces `root` in the bindings of the component, and whether it must be added
+    let parent = element.parent()?;
+    let (id, add_id) = match parent.kind() {
+        SyntaxKind::Component => (None, false),
+        SyntaxKind::SubElement => match parent.child_text(SyntaxKind::Identifier) {
+            Some(id) => (Some(id), false),
+            None => {
+                let base_id = name.to_lowercase();
+                let id = std::iter::once(base_id.clone())
+                    .chain((2..).map(|i| format!("{}-{}", base_id, i)))
+                    .find(|id| !use_site_ids.contains(id))?;
+                (Some(id), true)
+            }
+        },
+        _ => return None,
+    };
+
+    let element_members = element.children().filter(|n| n.kind() != SyntaxKind::QualifiedName);
+    let (children, members): (Vec<_>, Vec<_>) = element_members.partition(is_child_element);
+    let set_members = members
+        .iter()
+        .filter_map(|m| Some((member_name(m)?, m.clone())))
+        .collect::<BTreeMap<_, _>>();
+
+    // The references to the properties, in the form of qualified names, in the component
+    let references = root
+        .descendants()
+        .filter(|n| n.kind() == SyntaxKind::QualifiedName)
+        .map(wrap)
+        .filter(|q| q.parent().map_or(false, |p| p.kind() != SyntaxKind::Element))
+        .collect::<Vec<_>>();
+    let in_root_element = |node: &SyntaxNode| {
+        node.ancestors()
+            .find(|n| n.kind() == SyntaxKind::Element)
+            .map_or(false, |e| e.text_range() == root.text_range())
+    };
+
+    let mut replacements = vec![];
+    let mut removed = HashSet::new();
+    let mut decl_without_binding = HashSet::new();
+    for decl in root.PropertyDeclaration() {
+        let prop = i_slint_compiler::parser::identifier_text(&decl.DeclaredIdentifier())?;
+        let set = match set_members.get(&prop) {
+            Some(set) => set,
+            None => continue,
+        };
+        if decl.TwoWayBinding().is_some() {
+            return None;
+        }
+        // Substitute the value if it is a constant, that has the same meaning everywhere
+        let value = syntax_nodes::Binding::new(set.clone())
+            .and_then(|b| b.BindingExpression().Expression())
+            .filter(|e| {
+                e.descendants_with_tokens().filter_map(|e| e.into_token()).all(|t| {
+                    t.kind() != SyntaxKind::Identifier || matches!(t.text(), "true" | "false")
+                })
+            });
+        // The property must not be used from the use site, nor be assigned in the component
+        let used_at_use_site = use_site.descendants_with_tokens().any(|e| {
+            e.kind() == SyntaxKind::Identifier
+                && normalize_identifier(e.as_token().unwrap().text()) == prop
+                && !set.text_range().contains_range(e.text_range())
+        });
+        let mut substitutions = vec![];
+        let substitutable = value.is_some()
+            && !used_at_use_site
+            && references.iter().all(|q| {
+                let ids = qualified_name_identifiers(q);
+                let prop_index = match ids.iter().position(|i| *i == prop) {
+                    Some(i) => i,
+                    None => return true,
+                };
+                let is_reference = match prop_index {
+                    0 => true,
+                    1 => ids[0] == "root" || (ids[0] == "self" && in_root_element(q)),
+                    _ => false,
+                };
+                if !is_reference {
+                    return true;
+                }
+                // Unqualified in a child element, it could also be a property of that element
+                if prop_index == 0 && !in_root_element(q) {
+                    return false;
+                }
+                let expression = match q.parent() {
+                    Some(e) if e.kind() == SyntaxKind::Expression => e,
+                    // the property is changed in a state, or animated
+                    _ => return false,
+                };
+                let assigned = expression.parent().map_or(false, |p| {
+                    p.kind() == SyntaxKind::TwoWayBinding
+                        || (p.kind() == SyntaxKind::SelfAssignment
+                            && p.first_child().map(|c| c.text_range())
+                                == Some(expression.text_range()))
+                });
+                if assigned {
+                    return false;
+                }
+                let prop_token = q
+                    .children_with_tokens()
+                    .filter_map(|e| e.into_token())
+                    .filter(|t| t.kind() == SyntaxKind::Identifier)
+                    .nth(prop_index)
+                    .unwrap();
+                substitutions
+                    .push(TextRange::new(q.text_range().start(), prop_token.text_range().end()));
+                true
+            });
+        if substitutable {
+            let value = value.unwrap();
+            let value_text = &source[trimmed_range(&value)?];
+            let value_text = if expression_tokens(&value).len() > 1 {
+                format!("({})", value_text)
+            } else {
+                value_text.to_owned()
+            };
+            replacements.extend(substitutions.into_iter().map(|r| (r, value_text.clone())));
+            removed.insert(decl.text_range());
+            removed.insert(set.text_range());
+        } else if decl.BindingExpression().is_some() {
+            decl_without_binding.insert(decl.text_range());
+        }
+    }
+
+    // `root` is now the root of the use site
+    let mut uses_id = false;
+    if let Some(id) = &id {
+        for q in &references {
+            let first = match q.children_with_tokens().filter_map(|e| e.into_token()).next() {
+                Some(first) if first.kind() == SyntaxKind::Identifier => first,
+                _ => continue,
+            };
+            if normalize_identifier(first.text()) == "root"
+                && !replacements.iter().any(|(r, _)| r.start() == first.text_range().start())
+            {
+                replacements.push((first.text_range(), id.clone()));
+                uses_id = true;
+            }
+        }
+    }
+
+    let indent = format!("{}    ", line_indentation(source, element.text_range().start().into()));
+    let member_text =
+        |member: &SyntaxNode, replacements: &[(TextRange, String)]| -> Option<String> {
+            let range = trimmed_range(member)?;
+            Some(reindent(
+                &text_with_replacements(source, range, replacements),
+                line_indentation(source, range.start().into()),
+                &indent,
+            ))
+        };
+    let children_text = |indent: &str| -> Option<String> {
+        let texts = children
+            .iter()
+            .map(|c| {
+                let range = trimmed_range(c)?;
+                Some(reindent(
+                    &source[range],
+                    line_indentation(source, range.start().into()),
+                    indent,
+                ))
+            })
+            .collect::<Option<Vec<_>>>()?;
+        Some(texts.join(&format!("\n{}", indent)))
+    };
+
+    let mut has_placeholder = false;
+    for placeholder in root.descendants().filter(|n| n.kind() == SyntaxKind::ChildrenPlaceholder) {
+        has_placeholder = true;
+        let placeholder = wrap(placeholder);
+        let range = trimmed_range(&placeholder)?;
+        replacements.push((range, children_text(line_indentation(source, range.start().into()))?));
+    }
+
+    let mut new_members = vec![];
+    for member in root.children().filter(|n| n.kind() != SyntaxKind::QualifiedName) {
+        if removed.contains(&member.text_range())
+            || member_name(&member).map_or(false, |n| set_members.contains_key(&n))
+        {
+            continue;
+        }
+        if decl_without_binding.contains(&member.text_range()) {
+            // The declaration without its default value, that is set at the use site
+            let binding = syntax_nodes::PropertyDeclaration::new(member.clone())?
+                .BindingExpression()?
+                .text_range()
+                .start();
+            let decl = &source[usize::from(member.text_range().start())..binding.into()];
+            new_members.push(format!("{};", decl.trim_end().trim_end_matches(':').trim_end()));
+            continue;
+        }
+        if member.kind() == SyntaxKind::ChildrenPlaceholder && children.is_empty() {
+            continue;
+        }
+        new_members.push(member_text(&member, &replacements)?);
+    }
+    for member in &members {
+        if !removed.contains(&member.text_range()) {
+            new_members.push(member_text(member, &[])?);
+        }
+    }
+    if !has_placeholder && !children.is_empty() {
+        new_members.push(children_text(&indent)?);
+    }
+
+    let mut text = String::new();
+    if add_id && uses_id {
+        text += &format!("{} := ", id.unwrap());
+    }
+    text += &source[trimmed_range(&base)?];
+    if new_members.is_empty() {
+        text += " { }";
+    } else {
+        text += " {";
+        for member in new_members {
+            text += &format!("\n{}{}", indent, member);
+        }
+        text += &format!("\n{}}}", line_indentation(source, element.text_range().start().into()));
+    }
+
+    let mut edits = vec![];
+    let element_range = trimmed_range(&element)?;
+    edits.push(text_edit(
+        document_cache,
+        uri,
+        element_range.start().into(),
+        element_range.end().into(),
+        text,
+    )?);
+
+    // Remove the component if this was its only use
+    let uses = doc
+        .descendants_with_tokens()
+        .filter(|e| {
+            e.kind() == SyntaxKind::Identifier
+                && normalize_identifier(e.as_token().unwrap().text()) == name
+        })
+        .count();
+    let is_last_component =
+        doc.Component().last().map_or(false, |c| c.text_range() == component.text_range());
+    let is_exported = component.parent().map_or(false, |p| p.kind() == SyntaxKind::ExportsList);
+    if uses == 2 && !is_exported && !is_last_component {
+        let start = component.text_range().start();
+        let end = end_of_line(source, component.text_range().end().into());
+        edits.push(text_edit(document_cache, uri, start.into(), end as u32, String::new())?);
+    }
+
+    Some(CodeAction {
+        title: format!("Inline {}", name),
+        kind: Some(CodeActionKind::REFACTOR_INLINE),
+        edit: Some(workspace_edit(uri, edits)),
+        ..Default::default()
+    })
+}
+
+/// `MyButton` -> `my-button`
+fn kebab_case(name: &str) -> String {
+    let mut result = String::new();
+    let mut previous_is_lower = false;
+    for c in name.chars() {
+        if c.is_uppercase() && previous_is_lower {
+            result.push('-');
+        }
+        previous_is_lower = c.is_lowercase() || c.is_ascii_digit();
+        result.extend(c.to_lowercase());
+    }
+    result.replace('_', "-")
+}
+
+fn text_document_edit(uri: &Url, edits: Vec<TextEdit>) -> DocumentChangeOperation {
+    DocumentChangeOperation::Edit(TextDocumentEdit {
+        text_document: OptionalVersionedTextDocumentIdentifier { uri: uri.clone(), version: None },
+        edits: edits.into_iter().map(OneOf::Left).collect(),
+    })
+}
+
+/// Move the component whose name is under the cursor into a new file next to this one, and
+/// import it from there in this document and in the loaded documents that imported it from here
+pub fn move_component_to_new_file(
+    document_cache: &mut DocumentCache,
+    uri: &Url,
+    position: Position,
+) -> Option<CodeAction> {
+    let doc = parse_document(document_cache, uri)?;
+    let source = doc.source_file()?.source()?;
+    let path = doc.source_file()?.path().to_owned();
+
+    let offset = position_to_offset(source, position);
+    let component = doc
+        .token_at_offset(offset.into())
+        .find(|t| t.kind() == SyntaxKind::Identifier)
+        .and_then(|t| t.parent())
+        .filter(|n| n.kind() == SyntaxKind::DeclaredIdentifier)
+        .and_then(|n| n.parent())
+        .map(|node| SyntaxNode { node, source_file: doc.source_file.clone() })
+        .and_then(syntax_nodes::Component::new)?;
+    let name = i_slint_compiler::parser::identifier_text(&component.DeclaredIdentifier())?;
+    let declaration: SyntaxNode = match component.parent() {
+        Some(p) if p.kind() == SyntaxKind::ExportsList => p,
+        _ => component.clone().into(),
+    };
+
+    // The component cannot use the other components and structs of this file: they would have
+    // to import each other
+    let declared_names = doc
+        .Component()
+        .chain(doc.ExportsList().filter_map(|e| e.Component()))
+        .map(|c| c.DeclaredIdentifier())
+        .chain(
+            doc.StructDeclaration()
+                .chain(doc.ExportsList().flat_map(|e| e.StructDeclaration()))
+                .map(|s| s.DeclaredIdentifier()),
+        )
+        .filter_map(|i| i_slint_compiler::parser::identifier_text(&i))
+        .collect::<HashSet<_>>();
+    let used_names = component
+        .Element()
+        .descendants_with_tokens()
+        .filter_map(|e| e.into_token())
+        .filter(|t| t.kind() == SyntaxKind::Identifier)
+        .map(|t| normalize_identifier(t.text()))
+        .collect::<HashSet<_>>();
+    if used_names.iter().any(|n| *n != name && declared_names.contains(n)) {
+        return None;
+    }
+
+    let directory = path.parent()?;
+    let file_name = std::iter::once(format!("{}.slint", kebab_case(&name)))
+        .chain((2..).map(|i| format!("{}-{}.slint", kebab_case(&name), i)))
+        .find(|f| !directory.join(f).exists())?;
+    let new_uri = Url::from_file_path(directory.join(&file_name)).ok()?;
+
+    // The imports of this file that the component uses. The paths are relative to the directory,
+    // which is the same for the new file.
+    let mut new_file = String::new();
+    for import in doc.ImportSpecifier() {
+        let list = match import.ImportIdentifierList() {
+            Some(list) => list,
+            None => continue,
+        };
+        let names = list
+            .ImportIdentifier()
+            .filter(|i| {
+                let imported = i_slint_compiler::typeloader::ImportedName::from_node(i.clone());
+                used_names.contains(&imported.internal_name)
+            })
+            .map(|i| i.text().to_string().trim().to_owned())
+            .collect::<Vec<_>>();
+        if !names.is_empty() {
+            new_file += &format!(
+                "import {{ {} }} from {};\n",
+                names.join(", "),
+                import.child_text(SyntaxKind::StringLiteral)?
+            );
+        }
+    }
+    if !new_file.is_empty() {
+        new_file += "\n";
+    }
+
+    // Take the comments above the declaration along
+    let mut start = declaration.text_range().start();
+    let mut token = declaration.first_token()?;
+    while let Some(prev) = token.prev_token() {
+        match prev.kind() {
+            SyntaxKind::Comment => start = prev.text_range().start(),
+            SyntaxKind::Whitespace if prev.text().matches('\n').count() <= 1 => (),
+            _ => break,
+        }
+        token = prev;
+    }
+    let comments = &source[usize::from(start)..declaration.text_range().start().into()];
+    new_file += &format!("{}export {}\n", comments, &source[trimmed_range(&component)?]);
+
+    let mut operations = vec![
+        DocumentChangeOperation::Op(ResourceOp::Create(CreateFile {
+            uri: new_uri.clone(),
+            options: None,
+            annotation_id: None,
+        })),
+        text_document_edit(
+            &new_uri,
+            vec![TextEdit::new(Range::new(Position::new(0, 0), Position::new(0, 0)), new_file)],
+        ),
+    ];
+
+    // Remove the component from this file, and import it if it is still used
+    let mut edits = vec![text_edit(
+        document_cache,
+        uri,
+        start.into(),
+        end_of_line(source, declaration.text_range().end().into()) as u32,
+        String::new(),
+    )?];
+    let still_used = doc
+        .descendants_with_tokens()
+        .filter_map(|e| e.into_token())
+        .filter(|t| !component.text_range().contains_range(t.text_range()))
+        .any(|t| t.kind() == SyntaxKind::Identifier && normalize_identifier(t.text()) == name);
+    if still_used {
+        let import_offset = doc
+            .ImportSpecifier()
+            .last()
+            .map_or(0, |i| end_of_line(source, i.text_range().end().into()) as u32);
+        let import = format!(
+            "import {{ {} }} from \"{}\";\n",
+            component.DeclaredIdentifier().text().to_string().trim(),
+            file_name
+        );
+        edits.push(text_edit(document_cache, uri, import_offset, import_offset, import)?);
+    }
+    operations.push(text_document_edit(uri, edits));
+
+    // Import it from the new file in the documents that imported it from this one
+    let canonical_path = dunce::canonicalize(&path).unwrap_or_else(|_| path.clone());
+    let other_files = document_cache
+        .documents
+        .all_files()
+        .filter(|f| **f != canonical_path && !f.starts_with("builtin:/"))
+        .cloned()
+        .collect::<Vec<_>>();
+    for file in other_files {
+        let file_uri = match Url::from_file_path(&file) {
+            Ok(file_uri) => file_uri,
+            Err(_) => continue,
+        };
+        let mut replacements = vec![];
+        let other_doc = match document_cache.documents.get_document(&file) {
+            Some(d) => d,
+            None => continue,
+        };
+        for import in other_doc.node.iter().flat_map(|n| n.ImportSpecifier()) {
+            let import_path = match import.child_text(SyntaxKind::StringLiteral) {
+                Some(p) => p.trim_matches('"').to_owned(),
+                None => continue,
+            };
+            let imported_file = file.parent().unwrap_or_else(|| Path::new("/")).join(&import_path);
+            if dunce::canonicalize(&imported_file).unwrap_or(imported_file) != canonical_path {
+                continue;
+            }
+            let (moved, kept): (Vec<_>, Vec<_>) =
+                import.ImportIdentifierList().iter().flat_map(|l| l.ImportIdentifier()).partition(
+                    |i| normalize_identifier(i.ExternalName().text().to_string().trim()) == name,
+                );
+            if moved.is_empty() {
+                continue;
+            }
+            let text_of = |ids: &[syntax_nodes::ImportIdentifier]| {
+                ids.iter().map(|i| i.text().to_string().trim().to_owned()).collect::<Vec<_>>()
+            };
+            let new_path = match file_uri.make_relative(&new_uri) {
+                Some(new_path) => new_path,
+                None => continue,
+            };
+            let mut text = String::new();
+            if !kept.is_empty() {
+                text += &format!(
+                    "import {{ {} }} from \"{}\";\n",
+                    text_of(&kept[..]).join(", "),
+                    import_path
+                );
+            }
+            text +=
+                &format!("import {{ {} }} from \"{}\";", text_of(&moved[..]).join(", "), new_path);
+            replacements.push((import.text_range(), text));
+        }
+        if replacements.is_empty() {
+            continue;
+        }
+        let edits = replacements
+            .into_iter()
+            .map(|(range, text)| {
+                text_edit(document_cache, &file_uri, range.start().into(), range.end().into(), text)
+            })
+            .collect::<Option<Vec<_>>>()?;
+        operations.push(text_document_edit(&file_uri, edits));
+    }
+
+    Some(CodeAction {
+        title: format!("Move {} to {}", name, file_name),
+        kind: Some(REFACTOR_MOVE),
+        edit: Some(WorkspaceEdit {
+            document_changes: Some(DocumentChanges::Operations(operations)),
+            ..Default::default()
+        }),
+        ..Default::default()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn organize(source: &str) -> Option<Vec<TextEdit>> {
+        let (mut dc, uri, _) = crate::test::loaded_document_cache("fluent", source.into());
+        let action = organize_imports(&mut dc, &uri)?;
+        action.edit?.changes?.remove(&uri)
+    }
+
+    #[test]
+    fn test_organize_imports() {
+        let edits = organize(
+            r#"import { Slider, Button } from "std-widgets.slint";
+import { CheckBox as Check, LineEdit } from "std-widgets.slint";
+import { Button } from "std-widgets.slint";
+Main := Rectangle {
+    Button {}
+    Check {}
+}
+"#,
+        )
+        .unwrap();
+        assert_eq!(edits.len(), 3);
+        assert_eq!(
+            edits[0].new_text,
+            "import { Button, CheckBox as Check } from \"std-widgets.slint\";\n"
+        );
+        assert_eq!(edits[0].range, Range::new(Position::new(0, 0), Position::new(1, 0)));
+        assert_eq!(edits[1].new_text, "");
+        assert_eq!(edits[2].range, Range::new(Position::new(2, 0), Position::new(3, 0)));
+
+        assert_eq!(
+            organize(
+                r#"import { Button, CheckBox as Check } from "std-widgets.slint";
+Main := Rectangle {
+    Button {}
+    Check {}
+}
+"#
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_extract_property() {
+        let source = r#"Main := Rectangle {
+    property <length> size: 10px;
+    width: size * 2;
+    Rectangle {
+        height: root.size * 2;
+        width: size * 2;
+        x: width * 2;
+    }
+}
+"#;
+        let (mut dc, uri, _) = crate::test::loaded_document_cache("fluent", source.into());
+        let range = Range::new(Position::new(2, 11), Position::new(2, 19));
+        let action = extract_property(&mut dc, &uri, range).unwrap();
+        let edits = action.edit.unwrap().changes.unwrap().remove(&uri).unwrap();
+        let edit = |line, start, end, text: &str| {
+            TextEdit::new(
+                Range::new(Position::new(line, start), Position::new(line, end)),
+                text.into(),
+            )
+        };
+        assert_eq!(
+            edits,
+            vec![
+                edit(0, 19, 19, "\n    property <length> new-property: size * 2;"),
+                edit(2, 11, 19, "new-property"),
+                edit(5, 15, 23, "root.new-property"),
+            ]
+        );
+
+        // `width` is the width of the inner Rectangle
+        let range = Range::new(Position::new(6, 11), Position::new(6, 20));
+        assert!(extract_property(&mut dc, &uri, range).is_none());
+    }
+
+    #[test]
+    fn test_inline_component() {
+        let source = r#"Label := Rectangle {
+    property <string> text;
+    property <length> size: 10px;
+    property <bool> big;
+    width: size * 2;
+    background: blue;
+    Text { text: root.text; font-size: big ? 20px : 10px; }
+}
+Main := Rectangle {
+    Label { text: "hello"; size: 5px; background: red; }
+}
+"#;
+        let (mut dc, uri, _) = crate::test::loaded_document_cache("fluent", source.into());
+        let action = inline_component(&mut dc, &uri, Position::new(9, 6)).unwrap();
+        let edits = action.edit.unwrap().changes.unwrap().remove(&uri).unwrap();
+        assert_eq!(edits.len(), 2);
+        assert_eq!(
+            edits[0].new_text,
+            r#"Rectangle {
+        property <bool> big;
+        width: 5px * 2;
+        Text { text: "hello"; font-size: big ? 20px : 10px; }
+        background: red;
+    }"#
+        );
+        assert_eq!(edits[0].range.start, Position::new(9, 4));
+        // Label is not used anywhere else
+        assert_eq!(
+            edits[1],
+            TextEdit::new(Range::new(Position::new(0, 0), Position::new(8, 0)), "".into())
+        );
+    }
+
+    #[test]
+    fn test_move_component_to_new_file() {
+        let source = r#"import { Button, Slider } from "std-widgets.slint";
+
+/// A button
+MyButton := Button {
+    text: "ok";
+}
+Main := Rectangle {
+    MyButton {}
+    Slider {}
+}
+"#;
+        let (mut dc, uri, _) = crate::test::loaded_document_cache("fluent", source.into());
+        let action = move_component_to_new_file(&mut dc, &uri, Position::new(3, 2)).unwrap();
+        assert_eq!(action.title, "Move MyButton to my-button.slint");
+        let operations = match action.edit.unwrap().document_changes.unwrap() {
+            DocumentChanges::Operations(operations) => operations,
+            _ => panic!("expected operations"),
+        };
+        let edits = |operation: &DocumentChangeOperation| match operation {
+            DocumentChangeOperation::Edit(e) => e
+                .edits
+                .iter()
+                .map(|e| match e {
+                    OneOf::Left(e) => e.clone(),
+                    OneOf::Right(e) => e.text_edit.clone(),
+                })
+                .collect::<Vec<_>>(),
+            _ => panic!("expected an edit"),
+        };
+        assert_eq!(operations.len(), 3);
+        assert!(matches!(operations[0], DocumentChangeOperation::Op(ResourceOp::Create(_))));
+        assert_eq!(
+            edits(&operations[1])[0].new_text,
+            r#"import { Button } from "std-widgets.slint";
+
+/// A button
+export MyButton := Button {
+    text: "ok";
+}
+"#
+        );
+        let position = |line, character| Position::new(line, character);
+        assert_eq!(
+            edits(&operations[2]),
+            vec![
+                TextEdit::new(Range::new(position(2, 0), position(6, 0)), "".into()),
+                TextEdit::new(
+                    Range::new(position(1, 0), position(1, 0)),
+                    "import { MyButton } from \"my-button.slint\";\n".into()
+                ),
+            ]
+        );
+    }
+}
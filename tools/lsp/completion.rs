@@ -9,10 +9,11 @@
 use i_slint_compiler::expression_tree::Expression;
 use i_slint_compiler::langtype::{ElementType, Type};
 use i_slint_compiler::lookup::{LookupCtx, LookupObject, LookupResult};
+use i_slint_compiler::object_tree::ElementRc;
 use i_slint_compiler::parser::{syntax_nodes, SyntaxKind, SyntaxToken};
 use lsp_types::{
     CompletionClientCapabilities, CompletionItem, CompletionItemKind, CompletionResponse,
-    InsertTextFormat, Position, Range, TextEdit,
+    Documentation, InsertTextFormat, MarkupContent, MarkupKind, Position, Range, TextEdit,
 };
 use std::collections::{HashMap, HashSet};
 use std::path::Path;
@@ -145,13 +146,10 @@ pub(crate) fn completion_at(
                         if available_types.contains(&exported_name.name) {
                             continue;
                         }
-                        if let Some(c) = ty.as_ref().left() {
-                            if c.is_global() {
-                                continue;
-                            }
-                        } else {
-                            continue;
-                        }
+                        let c = match ty.as_ref().left() {
+                            Some(c) if !c.is_global() => c,
+                            _ => continue,
+                        };
                         available_types.insert(exported_name.name.clone());
                         let the_import = import_locations.get(&file).map_or_else(
                             || {
@@ -182,6 +180,7 @@ pub(crate) fn completion_at(
                             kind: Some(CompletionItemKind::CLASS),
                             detail: Some(format!("(import from \"{}\")", file)),
                             additional_text_edits: Some(vec![the_import.into()]),
+                            documentation: documentation(c.doc_comment.clone()),
                             ..Default::default()
                         });
                     }
@@ -383,12 +382,17 @@ fn resolve_element_scope(
             .property_list()
             .into_iter()
             .map(|(k, t)| {
+                let doc = match &element_type {
+                    ElementType::Component(c) => property_doc_comment(&c.root_element, &k),
+                    _ => None,
+                };
                 let mut c = CompletionItem::new_simple(k, t.to_string());
                 c.kind = Some(if matches!(t, Type::InferredCallback | Type::Callback { .. }) {
                     CompletionItemKind::METHOD
                 } else {
                     CompletionItemKind::PROPERTY
                 });
+                c.documentation = documentation(doc);
                 c
             })
             .chain(element.PropertyDeclaration().map(|pr| {
@@ -398,6 +402,7 @@ fn resolve_element_scope(
                     pr.Type().map(|t| t.text().into()).unwrap_or_else(|| "property".to_owned()),
                 );
                 c.kind = Some(CompletionItemKind::PROPERTY);
+                c.documentation = documentation(pr.doc_comment());
                 c
             }))
             .chain(element.CallbackDeclaration().map(|cd| {
@@ -407,6 +412,7 @@ fn resolve_element_scope(
                     "callback".into(),
                 );
                 c.kind = Some(CompletionItemKind::METHOD);
+                c.documentation = documentation(cd.doc_comment());
                 c
             }))
             .chain(i_slint_compiler::typeregister::reserved_properties().filter_map(|(k, t)| {
@@ -422,13 +428,14 @@ fn resolve_element_scope(
                 Some(c)
             }))
             .chain(tr.all_elements().into_iter().filter_map(|(k, t)| {
-                match t {
-                    ElementType::Component(c) if !c.is_global() => (),
-                    ElementType::Builtin(b) if !b.is_internal && !b.is_global => (),
+                let doc = match t {
+                    ElementType::Component(c) if !c.is_global() => c.doc_comment.clone(),
+                    ElementType::Builtin(b) if !b.is_internal && !b.is_global => None,
                     _ => return None,
                 };
                 let mut c = CompletionItem::new_simple(k, "element".into());
                 c.kind = Some(CompletionItemKind::CLASS);
+                c.documentation = documentation(doc);
                 Some(c)
             }))
             .collect(),
@@ -466,6 +473,11 @@ fn completion_item_from_expression(str: &str, lookup_result: LookupResult) -> Co
                 Expression::EnumerationValue(_) => Some(CompletionItemKind::ENUM_MEMBER),
                 _ => None,
             };
+            if let Expression::CallbackReference(nr) | Expression::PropertyReference(nr) =
+                &expression
+            {
+                c.documentation = documentation(property_doc_comment(&nr.element(), nr.name()));
+            }
             c
         }
         LookupResult::Enumeration(e) => {
@@ -481,6 +493,27 @@ fn completion_item_from_expression(str: &str, lookup_result: LookupResult) -> Co
     }
 }
 
+/// Returns the doc comment of the property `name` as declared in the element or its base components
+fn property_doc_comment(element: &ElementRc, name: &str) -> Option<String> {
+    let mut element = element.clone();
+    loop {
+        if let Some(p) = element.borrow().property_declarations.get(name) {
+            return p.doc_comment();
+        }
+        let base = element.borrow().base_type.clone();
+        match base {
+            ElementType::Component(c) => element = c.root_element.clone(),
+            _ => return None,
+        }
+    }
+}
+
+fn documentation(doc_comment: Option<String>) -> Option<Documentation> {
+    doc_comment.map(|value| {
+        Documentation::MarkupContent(MarkupContent { kind: MarkupKind::Markdown, value })
+    })
+}
+
 fn resolve_type_scope(
     token: SyntaxToken,
     document_cache: &DocumentCache,
@@ -533,3 +566,41 @@ fn complete_path_in_string(base: &Path, text: &str, offset: u32) -> Option<Vec<C
         .collect(),
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns the completion items at the position of the `🔺` in the source
+    fn get_completions(file: &str) -> Option<Vec<CompletionItem>> {
+        let offset = file.find("🔺").unwrap() as u32;
+        let file = file.replace("🔺", "");
+        let (mut dc, uri, _) = crate::test::loaded_document_cache("fluent", file);
+        let position = dc.byte_offset_to_position(offset, &uri)?;
+        let (token, offset) = crate::server_loop::token_descr(
+            &mut dc,
+            lsp_types::TextDocumentIdentifier { uri },
+            position,
+        )?;
+        match completion_at(&mut dc, token, offset, None)? {
+            CompletionResponse::Array(items) => Some(items),
+            CompletionResponse::List(list) => Some(list.items),
+        }
+    }
+
+    #[test]
+    fn repeated_model_struct_fields() {
+        let source = r#"
+            Foo := Rectangle {
+                property <[{name: string, age: int}]> people;
+                for person in people : Text {
+                    text: person.🔺
+                }
+            }
+        "#;
+        let items = get_completions(source).unwrap();
+        let mut labels = items.iter().map(|item| item.label.as_str()).collect::<Vec<_>>();
+        labels.sort();
+        assert_eq!(labels, ["age", "name"]);
+    }
+}
@@ -17,6 +17,12 @@ pub fn goto_definition(
     document_cache: &mut DocumentCache,
     token: SyntaxToken,
 ) -> Option<GotoDefinitionResponse> {
+    let node = find_definition(document_cache, token)?;
+    goto_node(document_cache, &node)
+}
+
+/// Returns the node that declares what the token refers to
+pub fn find_definition(document_cache: &DocumentCache, token: SyntaxToken) -> Option<SyntaxNode> {
     let mut node = token.parent();
     loop {
         if let Some(n) = syntax_nodes::QualifiedName::new(node.clone()) {
@@ -26,9 +32,7 @@ pub fn goto_definition(
                     let qual = i_slint_compiler::object_tree::QualifiedTypeName::from_node(n);
                     let doc = document_cache.documents.get_document(node.source_file.path())?;
                     match doc.local_registry.lookup_qualified(&qual.members) {
-                        Type::Struct { node: Some(node), .. } => {
-                            goto_node(document_cache, node.parent().as_ref()?)
-                        }
+                        Type::Struct { node: Some(node), .. } => node.parent(),
                         _ => None,
                     }
                 }
@@ -37,7 +41,7 @@ pub fn goto_definition(
                     let doc = document_cache.documents.get_document(node.source_file.path())?;
                     match doc.local_registry.lookup_element(&qual.to_string()) {
                         Ok(ElementType::Component(c)) => {
-                            goto_node(document_cache, &*c.root_element.borrow().node.as_ref()?)
+                            c.root_element.borrow().node.clone().map(|n| n.into())
                         }
                         _ => None,
                     }
@@ -89,7 +93,7 @@ pub fn goto_definition(
                         }
                         _ => return None,
                     };
-                    goto_node(document_cache, &gn)
+                    Some(gn)
                 }
                 _ => None,
             };
@@ -98,7 +102,7 @@ pub fn goto_definition(
             let imp_name = i_slint_compiler::typeloader::ImportedName::from_node(n);
             return match doc.local_registry.lookup_element(&imp_name.internal_name) {
                 Ok(ElementType::Component(c)) => {
-                    goto_node(document_cache, &*c.root_element.borrow().node.as_ref()?)
+                    c.root_element.borrow().node.clone().map(|n| n.into())
                 }
                 _ => None,
             };
@@ -112,7 +116,7 @@ pub fn goto_definition(
             let import_file = dunce::canonicalize(&import_file).unwrap_or(import_file);
             let doc = document_cache.documents.get_document(&import_file)?;
             let doc_node = doc.node.clone()?;
-            return goto_node(document_cache, &*doc_node);
+            return Some(doc_node.into());
         } else if syntax_nodes::BindingExpression::new(node.clone()).is_some() {
             // don't fallback to the Binding
             return None;
@@ -126,10 +130,10 @@ pub fn goto_definition(
                 (i_slint_compiler::parser::identifier_text(&p.DeclaredIdentifier())? == prop_name)
                     .then(|| p)
             }) {
-                return goto_node(document_cache, &p);
+                return Some(p.into());
             }
             let n = find_property_declaration_in_base(document_cache, element, prop_name)?;
-            return goto_node(document_cache, &n);
+            return Some(n);
         } else if let Some(n) = syntax_nodes::TwoWayBinding::new(node.clone()) {
             if token.kind() != SyntaxKind::Identifier {
                 return None;
@@ -143,10 +147,10 @@ pub fn goto_definition(
                 (i_slint_compiler::parser::identifier_text(&p.DeclaredIdentifier())? == prop_name)
                     .then(|| p)
             }) {
-                return goto_node(document_cache, &p);
+                return Some(p.into());
             }
             let n = find_property_declaration_in_base(document_cache, element, prop_name)?;
-            return goto_node(document_cache, &n);
+            return Some(n);
         } else if let Some(n) = syntax_nodes::CallbackConnection::new(node.clone()) {
             if token.kind() != SyntaxKind::Identifier {
                 return None;
@@ -160,10 +164,10 @@ pub fn goto_definition(
                 (i_slint_compiler::parser::identifier_text(&p.DeclaredIdentifier())? == prop_name)
                     .then(|| p)
             }) {
-                return goto_node(document_cache, &p);
+                return Some(p.into());
             }
             let n = find_property_declaration_in_base(document_cache, element, prop_name)?;
-            return goto_node(document_cache, &n);
+            return Some(n);
         }
         node = node.parent()?;
     }
@@ -3,6 +3,7 @@
 
 #![cfg(not(target_arch = "wasm32"))]
 
+mod code_actions;
 mod completion;
 mod goto;
 mod lsp_ext;
@@ -37,7 +38,7 @@ struct Cli {
     )]
     include_paths: Vec<std::path::PathBuf>,
 
-    /// The style name for the preview ('native' or 'fluent')
+    /// The style name for the preview ('native', 'fluent', 'material' or 'cupertino')
     #[arg(long, name = "style name", default_value_t, action)]
     style: String,
 
@@ -240,20 +240,19 @@ fn get_properties(
                     defined_at: None,
                     group: String::new(),
                 });
+                result.extend(get_reserved_properties(
+                    "effect",
+                    i_slint_compiler::typeregister::RESERVED_EFFECT_PROPERTIES,
+                ));
 
-                if b.name == "Image" {
-                    result.extend(get_reserved_properties(
-                        "rotation",
-                        i_slint_compiler::typeregister::RESERVED_ROTATION_PROPERTIES,
-                    ));
-                }
-
-                if b.name == "Rectangle" {
-                    result.extend(get_reserved_properties(
-                        "drop-shadow",
-                        i_slint_compiler::typeregister::RESERVED_DROP_SHADOW_PROPERTIES,
-                    ));
-                }
+                result.extend(get_reserved_properties(
+                    "transform",
+                    i_slint_compiler::typeregister::RESERVED_TRANSFORM_PROPERTIES,
+                ));
+                result.extend(get_reserved_properties(
+                    "drop-shadow",
+                    i_slint_compiler::typeregister::RESERVED_DROP_SHADOW_PROPERTIES,
+                ));
             }
             ElementType::Global => {
                 break;
@@ -5,7 +5,7 @@
 
 #[cfg(target_arch = "wasm32")]
 use crate::wasm_prelude::*;
-use crate::{completion, goto, semantic_tokens, util, RequestHolder};
+use crate::{code_actions, completion, goto, semantic_tokens, util, RequestHolder};
 use i_slint_compiler::diagnostics::{BuildDiagnostics, Spanned};
 use i_slint_compiler::langtype::Type;
 use i_slint_compiler::object_tree::ElementRc;
@@ -15,15 +15,16 @@
 use i_slint_compiler::CompilerConfiguration;
 use lsp_types::request::{
     CodeActionRequest, CodeLensRequest, ColorPresentationRequest, Completion, DocumentColor,
-    DocumentSymbolRequest, ExecuteCommand, GotoDefinition, HoverRequest, SemanticTokensFullRequest,
+    DocumentSymbolRequest, ExecuteCommand, Formatting, GotoDefinition, HoverRequest,
+    SemanticTokensFullRequest,
 };
 use lsp_types::{
-    CodeActionOrCommand, CodeActionProviderCapability, CodeLens, CodeLensOptions, Color,
-    ColorInformation, ColorPresentation, Command, CompletionOptions, DocumentSymbol,
-    DocumentSymbolResponse, Hover, InitializeParams, OneOf, Position, PublishDiagnosticsParams,
-    Range, SemanticTokensFullOptions, SemanticTokensLegend, SemanticTokensOptions,
-    ServerCapabilities, TextDocumentIdentifier, TextDocumentSyncCapability, Url,
-    WorkDoneProgressOptions,
+    CodeActionKind, CodeActionOptions, CodeActionOrCommand, CodeActionProviderCapability, CodeLens,
+    CodeLensOptions, Color, ColorInformation, ColorPresentation, Command, CompletionOptions,
+    DocumentSymbol, DocumentSymbolResponse, Hover, InitializeParams, OneOf, Position,
+    PublishDiagnosticsParams, Range, SemanticTokensFullOptions, SemanticTokensLegend,
+    SemanticTokensOptions, ServerCapabilities, TextDocumentIdentifier, TextDocumentSyncCapability,
+    TextEdit, Url, WorkDoneProgressOptions,
 };
 use std::collections::HashMap;
 
@@ -124,7 +125,17 @@ pub fn server_capabilities() -> ServerCapabilities {
         text_document_sync: Some(TextDocumentSyncCapability::Kind(
             lsp_types::TextDocumentSyncKind::FULL,
         )),
-        code_action_provider: Some(CodeActionProviderCapability::Simple(true)),
+        code_action_provider: Some(CodeActionProviderCapability::Options(CodeActionOptions {
+            code_action_kinds: Some(vec![
+                CodeActionKind::SOURCE_ORGANIZE_IMPORTS,
+                CodeActionKind::REFACTOR_EXTRACT,
+                CodeActionKind::REFACTOR_INLINE,
+                code_actions::REFACTOR_MOVE,
+            ]),
+            resolve_provider: None,
+            work_done_progress_options: WorkDoneProgressOptions::default(),
+        })),
+        hover_provider: Some(true.into()),
         #[cfg(any(feature = "preview", target_arch = "wasm32"))]
         execute_command_provider: Some(lsp_types::ExecuteCommandOptions {
             commands: command_list(),
@@ -133,6 +144,7 @@ pub fn server_capabilities() -> ServerCapabilities {
         document_symbol_provider: Some(OneOf::Left(true)),
         color_provider: Some(true.into()),
         code_lens_provider: Some(CodeLensOptions { resolve_provider: Some(true) }),
+        document_formatting_provider: Some(OneOf::Left(true)),
         semantic_tokens_provider: Some(
             SemanticTokensOptions {
                 legend: SemanticTokensLegend {
@@ -185,23 +197,17 @@ pub fn handle_request(
         });
         Ok(result)
     })? {
-    } else if req.handle_request::<HoverRequest, _>(|_| {
-        /*let result =
-            token_descr(document_cache, params.text_document_position_params).map(|x| Hover {
-                contents: lsp_types::HoverContents::Scalar(MarkedString::from_language_code(
-                    "text".into(),
-                    format!("{:?}", x.token),
-                )),
-                range: None,
-            });
-        let resp = Response::new_ok(id, result);
-        connection.sender.send(Message::Response(resp))?;*/
-        Ok(None::<Hover>)
+    } else if req.handle_request::<HoverRequest, _>(|params| {
+        let result = token_descr(
+            document_cache,
+            params.text_document_position_params.text_document,
+            params.text_document_position_params.position,
+        )
+        .and_then(|token| get_hover(document_cache, token.0));
+        Ok(result)
     })? {
     } else if req.handle_request::<CodeActionRequest, _>(|params| {
-        let result = token_descr(document_cache, params.text_document, params.range.start)
-            .and_then(|token| get_code_actions(document_cache, token.0.parent()));
-        Ok(result)
+        Ok(get_code_actions(document_cache, params))
     })? {
     } else if req.handle_request::<ExecuteCommand, _>(|params| {
         if params.command.as_str() == SHOW_PREVIEW_COMMAND {
@@ -252,6 +258,9 @@ pub fn handle_request(
     } else if req.handle_request::<SemanticTokensFullRequest, _>(|params| {
         Ok(semantic_tokens::get_semantic_tokens(document_cache, &params.text_document))
     })? {
+    } else if req.handle_request::<Formatting, _>(|params| {
+        Ok(format_document(document_cache, &params.text_document, &params.options))
+    })? {
     };
     Ok(())
 }
@@ -461,7 +470,7 @@ pub fn element_at_position(
 }
 
 /// return the token, and the offset within the file
-fn token_descr(
+pub(crate) fn token_descr(
     document_cache: &mut DocumentCache,
     text_document: lsp_types::TextDocumentIdentifier,
     pos: Position,
@@ -491,10 +500,103 @@ fn token_descr(
     Some((SyntaxToken { token, source_file: node.source_file.clone() }, o))
 }
 
+/// Show the doc comment of the component, property or callback under the cursor
+fn get_hover(document_cache: &DocumentCache, token: SyntaxToken) -> Option<Hover> {
+    if token.kind() != SyntaxKind::Identifier {
+        return None;
+    }
+    let parent = token.parent();
+    let node = match parent.parent() {
+        // Hovering the name in the declaration itself
+        Some(decl)
+            if parent.kind() == SyntaxKind::DeclaredIdentifier
+                && matches!(
+                    decl.kind(),
+                    SyntaxKind::Component
+                        | SyntaxKind::PropertyDeclaration
+                        | SyntaxKind::CallbackDeclaration
+                ) =>
+        {
+            decl
+        }
+        _ => goto::find_definition(document_cache, token)?,
+    };
+    // The definition of a component is its root element
+    let node = match node.parent() {
+        Some(component)
+            if node.kind() == SyntaxKind::Element && component.kind() == SyntaxKind::Component =>
+        {
+            component
+        }
+        _ => node,
+    };
+    let doc = node
+        .doc_comment()
+        .or_else(|| node.parent().filter(|p| p.kind() == SyntaxKind::ExportsList)?.doc_comment())?;
+    Some(Hover {
+        contents: lsp_types::HoverContents::Markup(lsp_types::MarkupContent {
+            kind: lsp_types::MarkupKind::Markdown,
+            value: doc,
+        }),
+        range: None,
+    })
+}
+
 fn get_code_actions(
-    _document_cache: &mut DocumentCache,
-    node: SyntaxNode,
+    document_cache: &mut DocumentCache,
+    params: lsp_types::CodeActionParams,
 ) -> Option<Vec<CodeActionOrCommand>> {
+    let only = params.context.only.as_ref();
+    // The kinds are hierarchical: requesting `source` includes `source.organizeImports`
+    let requested = |kind: CodeActionKind| {
+        only.map_or(true, |only| only.iter().any(|o| kind.as_str().starts_with(o.as_str())))
+    };
+    let mut result = vec![];
+
+    if only.is_none() {
+        result.extend(
+            token_descr(document_cache, params.text_document.clone(), params.range.start)
+                .and_then(|token| show_preview_code_action(token.0.parent()))
+                .map(CodeActionOrCommand::Command),
+        );
+    }
+    if requested(CodeActionKind::REFACTOR_EXTRACT) {
+        result.extend(
+            code_actions::extract_property(document_cache, &params.text_document.uri, params.range)
+                .map(CodeActionOrCommand::CodeAction),
+        );
+    }
+    if requested(CodeActionKind::REFACTOR_INLINE) {
+        result.extend(
+            code_actions::inline_component(
+                document_cache,
+                &params.text_document.uri,
+                params.range.start,
+            )
+            .map(CodeActionOrCommand::CodeAction),
+        );
+    }
+    if requested(code_actions::REFACTOR_MOVE) {
+        result.extend(
+            code_actions::move_component_to_new_file(
+                document_cache,
+                &params.text_document.uri,
+                params.range.start,
+            )
+            .map(CodeActionOrCommand::CodeAction),
+        );
+    }
+    if requested(CodeActionKind::SOURCE_ORGANIZE_IMPORTS) {
+        result.extend(
+            code_actions::organize_imports(document_cache, &params.text_document.uri)
+                .map(CodeActionOrCommand::CodeAction),
+        );
+    }
+
+    (!result.is_empty()).then(|| result)
+}
+
+fn show_preview_code_action(node: SyntaxNode) -> Option<Command> {
     let component = syntax_nodes::Component::new(node.clone())
         .or_else(|| {
             syntax_nodes::DeclaredIdentifier::new(node.clone())
@@ -512,11 +614,11 @@ fn get_code_actions(
     let component_name =
         i_slint_compiler::parser::identifier_text(&component.DeclaredIdentifier())?;
 
-    Some(vec![CodeActionOrCommand::Command(create_show_preview_command(
+    create_show_preview_command(
         false,
         &component.source_file.path().to_string_lossy(),
         &component_name,
-    )?)])
+    )
 }
 
 fn get_document_color(
@@ -673,6 +775,94 @@ fn get_code_lenses(
     Some(r)
 }
 
+/// Collects the changes of the formatter as ranges of the source to replace
+#[derive(Default)]
+struct FormatEdits {
+    edits: Vec<(rowan::TextRange, String)>,
+}
+
+impl FormatEdits {
+    fn push(&mut self, range: rowan::TextRange, text: &str) {
+        match self.edits.last_mut() {
+            // Merge with the previous edit, so that a whitespace that is replaced is a single edit
+            Some((last_range, last_text)) if last_range.end() == range.start() => {
+                *last_range = last_range.cover(range);
+                *last_text += text;
+            }
+            _ => self.edits.push((range, text.into())),
+        }
+    }
+}
+
+impl slint_fmt::TokenWriter for FormatEdits {
+    fn no_change(&mut self, _token: i_slint_compiler::parser::SyntaxToken) -> std::io::Result<()> {
+        Ok(())
+    }
+
+    fn with_new_content(
+        &mut self,
+        token: i_slint_compiler::parser::SyntaxToken,
+        contents: &str,
+    ) -> std::io::Result<()> {
+        self.push(token.text_range(), contents);
+        Ok(())
+    }
+
+    fn insert_before(
+        &mut self,
+        token: i_slint_compiler::parser::SyntaxToken,
+        contents: &str,
+    ) -> std::io::Result<()> {
+        self.push(rowan::TextRange::empty(token.text_range().start()), contents);
+        Ok(())
+    }
+}
+
+fn format_document(
+    document_cache: &mut DocumentCache,
+    text_document: &lsp_types::TextDocumentIdentifier,
+    options: &lsp_types::FormattingOptions,
+) -> Option<Vec<TextEdit>> {
+    let uri = &text_document.uri;
+    let path = uri.to_file_path().ok()?;
+    let source = document_cache
+        .documents
+        .get_document(&path)?
+        .node
+        .as_ref()?
+        .source_file()?
+        .source()?
+        .to_owned();
+
+    let mut diag = BuildDiagnostics::default();
+    let node = i_slint_compiler::parser::parse(source.clone(), Some(&path), &mut diag);
+    if diag.has_error() {
+        // The formatter would mangle the code that could not be parsed
+        return None;
+    }
+    let options = slint_fmt::FormatOptions {
+        indent_width: options.tab_size as usize,
+        ..slint_fmt::FormatOptions::default()
+    };
+    let mut edits = FormatEdits::default();
+    slint_fmt::format_document(syntax_nodes::Document::new(node)?, &mut edits, &options).ok()?;
+
+    edits
+        .edits
+        .into_iter()
+        .filter(|(range, text)| source[*range] != *text)
+        .map(|(range, text)| {
+            Some(TextEdit::new(
+                Range::new(
+                    document_cache.byte_offset_to_position(range.start().into(), uri)?,
+                    document_cache.byte_offset_to_position(range.end().into(), uri)?,
+                ),
+                text,
+            ))
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -855,4 +1045,53 @@ fn test_element_at_position_child() {
         assert_eq!(base_type_at_position(&mut dc, &url, 28, 8), Some("Text".to_string()));
         assert_eq!(base_type_at_position(&mut dc, &url, 51, 4), Some("VerticalBox".to_string()));
     }
+
+    #[test]
+    fn test_format_document() {
+        let (mut dc, url, _) = loaded_document_cache("fluent", "Main:=Rectangle{  }".into());
+
+        let options =
+            lsp_types::FormattingOptions { tab_size: 4, insert_spaces: true, ..Default::default() };
+        let edits = format_document(&mut dc, &TextDocumentIdentifier { uri: url }, &options)
+            .expect("the document was formatted");
+        let edit = |start, end, text: &str| {
+            TextEdit::new(Range::new(Position::new(0, start), Position::new(0, end)), text.into())
+        };
+        assert_eq!(
+            edits,
+            vec![edit(4, 4, " "), edit(6, 6, " "), edit(15, 15, " "), edit(16, 18, " ")]
+        );
+    }
+
+    #[test]
+    fn test_hover_doc_comments() {
+        let source = r#"
+/// A button
+/// with a label
+export MyButton := Rectangle {
+    /// The text shown
+    property <string> text;
+
+    // Not documented
+    property <int> count;
+}
+Main := Rectangle {
+    MyButton { text: "hello"; count: 3; }
+}
+"#;
+        let (mut dc, url, _) = loaded_document_cache("fluent", source.into());
+        let mut hover_at = |needle: &str| -> Option<String> {
+            let offset = source.rfind(needle).unwrap() as u32;
+            let position = dc.byte_offset_to_position(offset, &url)?;
+            let (token, _) =
+                token_descr(&mut dc, TextDocumentIdentifier { uri: url.clone() }, position)?;
+            match get_hover(&dc, token)?.contents {
+                lsp_types::HoverContents::Markup(m) => Some(m.value),
+                _ => None,
+            }
+        };
+        assert_eq!(hover_at("MyButton {").as_deref(), Some("A button\nwith a label"));
+        assert_eq!(hover_at("text:").as_deref(), Some("The text shown"));
+        assert_eq!(hover_at("count:"), None);
+    }
 }
@@ -2,9 +2,10 @@
 // SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
 
 use i_slint_compiler::diagnostics::{DiagnosticLevel, Spanned};
-use i_slint_compiler::langtype::ElementType;
+use i_slint_compiler::expression_tree::Expression;
+use i_slint_compiler::langtype::{ElementType, Type};
 use i_slint_compiler::lookup::LookupCtx;
-use i_slint_compiler::object_tree;
+use i_slint_compiler::object_tree::{self, ElementRc, RepeatedElementInfo};
 use i_slint_compiler::parser::{syntax_nodes, SyntaxKind, SyntaxNode};
 use i_slint_compiler::typeregister::TypeRegister;
 
@@ -68,7 +69,6 @@ pub fn with_lookup_ctx<R>(
                 .map(|el_ty| el_ty.lookup_property(&prop_name).property_type)
         });
 
-    // FIXME: we need also to fill in the repeated element
     let component = {
         let mut n = element.parent()?;
         loop {
@@ -82,7 +82,10 @@ pub fn with_lookup_ctx<R>(
     let component = i_slint_compiler::parser::identifier_text(&component.DeclaredIdentifier())
         .and_then(|component_name| tr.lookup_element(&component_name).ok())?;
     let scope = if let ElementType::Component(c) = component {
-        vec![c.root_element.clone()]
+        let mut scope = vec![c.root_element.clone()];
+        fill_element_scope(&mut scope, &element);
+        resolve_repeated_models(&scope, tr);
+        scope
     } else {
         Vec::new()
     };
@@ -95,6 +98,52 @@ pub fn with_lookup_ctx<R>(
     Some(f(&mut lookup_context))
 }
 
+/// Append to `scope` the elements from its last element down to the element of the given node,
+/// so that the `for` and `if` elements are in the scope. Returns false if the element was not found.
+fn fill_element_scope(scope: &mut Vec<ElementRc>, element: &syntax_nodes::Element) -> bool {
+    let current = scope.last().unwrap().clone();
+    let range = element.text_range();
+    if current.borrow().node.as_ref().map_or(false, |n| n.text_range() == range) {
+        return true;
+    }
+    for child in current.borrow().children.iter() {
+        if child.borrow().node.as_ref().map_or(false, |n| n.text_range().contains_range(range)) {
+            scope.push(child.clone());
+            if fill_element_scope(scope, element) {
+                return true;
+            }
+            scope.pop();
+        }
+    }
+    false
+}
+
+/// The models of the repeated elements are not resolved when the document has errors, which is
+/// usually the case while typing. Resolve them so that the type of the model data is known.
+fn resolve_repeated_models(scope: &[ElementRc], tr: &TypeRegister) {
+    for (index, element) in scope.iter().enumerate() {
+        let (node, is_conditional_element) = match &element.borrow().repeated {
+            Some(RepeatedElementInfo {
+                model: Expression::Uncompiled(node),
+                is_conditional_element,
+                ..
+            }) => (node.clone(), *is_conditional_element),
+            _ => continue,
+        };
+        let model_type = if is_conditional_element { Type::Bool } else { Type::Model };
+        let mut build_diagnostics = Default::default();
+        let mut lookup_context = LookupCtx::empty_context(tr, &mut build_diagnostics);
+        lookup_context.property_type = model_type.clone();
+        // The model is in the scope of the parent element
+        lookup_context.component_scope = &scope[..index];
+        let model = Expression::from_expression_node(node.clone().into(), &mut lookup_context)
+            .maybe_convert_to(model_type, &node, lookup_context.diag);
+        if let Some(repeated) = &mut element.borrow_mut().repeated {
+            repeated.model = model;
+        }
+    }
+}
+
 /// Return the element and property name in which we are
 fn lookup_expression_context(mut n: SyntaxNode) -> Option<(syntax_nodes::Element, String)> {
     let (element, prop_name) = loop {
@@ -129,10 +178,14 @@ fn lookup_expression_context(mut n: SyntaxNode) -> Option<(syntax_nodes::Element
 }
 
 pub fn to_lsp_diag(d: &i_slint_compiler::diagnostics::Diagnostic) -> lsp_types::Diagnostic {
+    let code = match d.code() {
+        i_slint_compiler::diagnostics::DiagnosticCode::Other => None,
+        code => Some(lsp_types::NumberOrString::String(code.as_str().into())),
+    };
     lsp_types::Diagnostic::new(
-        to_range(d.line_column()),
+        lsp_types::Range::new(to_position(d.line_column()), to_position(d.end_line_column())),
         Some(to_lsp_diag_level(d.level())),
-        None,
+        code,
         None,
         d.message().to_owned(),
         None,
@@ -140,12 +193,11 @@ pub fn to_lsp_diag(d: &i_slint_compiler::diagnostics::Diagnostic) -> lsp_types::
     )
 }
 
-fn to_range(span: (usize, usize)) -> lsp_types::Range {
-    let pos = lsp_types::Position::new(
-        (span.0 as u32).saturating_sub(1),
-        (span.1 as u32).saturating_sub(1),
-    );
-    lsp_types::Range::new(pos, pos)
+fn to_position(line_column: (usize, usize)) -> lsp_types::Position {
+    lsp_types::Position::new(
+        (line_column.0 as u32).saturating_sub(1),
+        (line_column.1 as u32).saturating_sub(1),
+    )
 }
 
 pub fn text_range_to_lsp_range(
@@ -3,6 +3,7 @@
 
 #![cfg(target_arch = "wasm32")]
 
+mod code_actions;
 mod completion;
 mod goto;
 mod lsp_ext;
@@ -0,0 +1,129 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+//! Replace the deprecated names of properties and files by their new name:
+//! `color` of a Rectangle becomes `background`, `maximum-width` becomes `max-width`,
+//! and the import from "sixtyfps_widgets.60" becomes an import from "std-widgets.slint".
+
+use crate::Cli;
+use i_slint_compiler::expression_tree::Expression;
+use i_slint_compiler::lookup::{LookupObject, LookupResult};
+use i_slint_compiler::parser::{normalize_identifier, SyntaxKind, SyntaxNode, SyntaxToken};
+use std::io::Write;
+
+pub(crate) fn fold_node(
+    node: &SyntaxNode,
+    file: &mut impl Write,
+    state: &mut crate::State,
+    args: &Cli,
+) -> std::io::Result<bool> {
+    let kind = node.kind();
+    let renamed = if matches!(kind, SyntaxKind::Binding | SyntaxKind::TwoWayBinding)
+        && node.parent().map_or(false, |n| n.kind() == SyntaxKind::Element)
+    {
+        renamed_binding(node, state)
+    } else if kind == SyntaxKind::QualifiedName
+        && node.parent().map_or(false, |n| n.kind() == SyntaxKind::Expression)
+    {
+        renamed_property_access(node, state)
+    } else {
+        None
+    };
+
+    let (token, new_name) = match renamed {
+        Some(renamed) => renamed,
+        None => return Ok(false),
+    };
+    for n in node.children_with_tokens() {
+        if n.as_token().map_or(false, |t| t.text_range() == token.text_range()) {
+            write!(file, "{new_name}")?;
+        } else {
+            crate::visit_node_or_token(n, file, state, args)?;
+        }
+    }
+    Ok(true)
+}
+
+pub(crate) fn fold_token(
+    token: &SyntaxToken,
+    file: &mut impl Write,
+    _state: &mut crate::State,
+) -> std::io::Result<bool> {
+    if token.kind() == SyntaxKind::StringLiteral
+        && token.parent().kind() == SyntaxKind::ImportSpecifier
+        && token.text() == "\"sixtyfps_widgets.60\""
+    {
+        write!(file, "\"std-widgets.slint\"")?;
+        return Ok(true);
+    }
+    Ok(false)
+}
+
+/// The property of a binding, if it is a deprecated alias
+fn renamed_binding(node: &SyntaxNode, state: &crate::State) -> Option<(SyntaxToken, String)> {
+    let element = state.current_elem.as_ref()?;
+    let token = node.child_token(SyntaxKind::Identifier)?;
+    let name = normalize_identifier(token.text());
+    let resolved_name = element.borrow().lookup_property(&name).resolved_name.to_string();
+    (resolved_name != name).then(|| (token, resolved_name))
+}
+
+/// The property in a qualified name like `color` or `rect.color`, if it is a deprecated alias
+fn renamed_property_access(
+    node: &SyntaxNode,
+    state: &crate::State,
+) -> Option<(SyntaxToken, String)> {
+    let mut it = node
+        .children_with_tokens()
+        .filter(|n| n.kind() == SyntaxKind::Identifier)
+        .filter_map(|n| n.into_token());
+    let first = it.next()?;
+    let first_str = normalize_identifier(first.text());
+    crate::experiments::lookup_changes::with_lookup_ctx(state, |ctx| {
+        ctx.current_token = Some(first.clone().into());
+        let result = i_slint_compiler::lookup::global_lookup().lookup(ctx, &first_str)?;
+        if let Some(new_name) = result.deprecated() {
+            return Some((first.clone(), new_name.to_string()));
+        }
+        match result {
+            LookupResult::Expression { expression: Expression::ElementReference(el), .. } => {
+                let second = it.next()?;
+                let name = normalize_identifier(second.text());
+                let resolved_name =
+                    el.upgrade()?.borrow().lookup_property(&name).resolved_name.to_string();
+                (resolved_name != name).then(|| (second, resolved_name))
+            }
+            _ => None,
+        }
+    })
+    .flatten()
+}
+
+#[test]
+fn rename_deprecated_names() {
+    use clap::Parser;
+    let path: std::path::PathBuf =
+        [env!("CARGO_MANIFEST_DIR"), "tests", "deprecated_names.slint"].iter().collect();
+    let args = Cli::parse_from(["slint-updater", path.to_str().unwrap()]);
+    let source = std::fs::read_to_string(&path).unwrap();
+    let mut output = Vec::new();
+    crate::process_file(source, &path, &mut output, &args).unwrap();
+    let output = String::from_utf8(output).unwrap();
+
+    for expected in [
+        "import { Button } from \"std-widgets.slint\";",
+        "    background: red;\n",
+        "    max-width: 100px;\n",
+        "background;\n",
+        "rect.background;\n",
+        "        background: green;\n",
+        "        min-height: 5px;\n",
+        // Not deprecated
+        "        text: \"color\";\n",
+    ] {
+        assert!(output.contains(expected), "{:?} not in {}", expected, output);
+    }
+    for deprecated in ["sixtyfps_widgets", "color:", "color;", "maximum", "minimum"] {
+        assert!(!output.contains(deprecated), "{:?} in {}", deprecated, output);
+    }
+}
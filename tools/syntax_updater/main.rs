@@ -2,17 +2,19 @@
 // SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
 
 //!
-//! Tool to change the syntax or reformat a .slint file
+//! Tool to change the syntax of .slint files
 //!
-//! As of know, it just rewrite the exact same as the input, but it can be changed
+//! It rewrites the legacy syntax and the deprecated names to the current syntax, keeping the
+//! comments and the formatting.
 //!
 //! This is how it can be used:
 //!
 //! ````shell
-//! cargo run --bin syntax_updater -- --from 0.0.5 -i  **/*.60
-//! cargo run --bin syntax_updater -- --from 0.0.5 -i  **/*.slint
-//! cargo run --bin syntax_updater -- --from 0.0.5 -i  **/*.rs
-//! cargo run --bin syntax_updater -- --from 0.0.5 -i  **/*.md
+//! cargo run --bin slint-updater -- -i  **/*.60
+//! cargo run --bin slint-updater -- -i  **/*.slint
+//! cargo run --bin slint-updater -- -i  **/*.rs
+//! cargo run --bin slint-updater -- -i  **/*.md
+//! cargo run --bin slint-updater -- --dry-run path/to/ui/
 //! ````
 
 use clap::Parser;
@@ -23,9 +25,10 @@
 use i_slint_compiler::typeloader::TypeLoader;
 use std::cell::RefCell;
 use std::io::Write;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::rc::Rc;
 
+mod deprecated_names;
 mod experiments {
     pub(super) mod input_output_properties;
     pub(super) mod lookup_changes;
@@ -35,6 +38,7 @@ mod experiments {
 #[derive(clap::Parser)]
 #[command(author, version, about, long_about = None)]
 pub struct Cli {
+    /// The files to update. The directories are searched recursively for .slint files
     #[arg(name = "path to .slint file(s)", action)]
     paths: Vec<std::path::PathBuf>,
 
@@ -42,6 +46,10 @@ pub struct Cli {
     #[arg(short, long, action)]
     inline: bool,
 
+    /// print the changes as a diff instead of the updated files
+    #[arg(long, conflicts_with = "inline", action)]
+    dry_run: bool,
+
     /// Move all properties declaration to root
     #[arg(long, action)]
     move_declaration: bool,
@@ -50,15 +58,56 @@ pub struct Cli {
 fn main() -> std::io::Result<()> {
     let args = Cli::parse();
 
-    for path in &args.paths {
-        let source = std::fs::read_to_string(path)?;
+    let mut paths = Vec::new();
+    collect_files(&args.paths, &mut paths)?;
+    for path in &paths {
+        update_file(path, &args, &mut std::io::stdout())?;
+    }
+    Ok(())
+}
+
+/// Updates the file at `path` in place with `--inline`. Otherwise, writes the updated file, or the
+/// diff with `--dry-run`, to `output`.
+fn update_file(path: &Path, args: &Cli, output: &mut impl Write) -> std::io::Result<()> {
+    let source = std::fs::read_to_string(path)?;
+    let mut updated = Vec::new();
+    process_file(source.clone(), path, &mut updated, args)?;
 
-        if args.inline {
-            let file = std::fs::File::create(path)?;
-            process_file(source, path, file, &args)?
-        } else {
-            process_file(source, path, std::io::stdout(), &args)?
+    if args.dry_run {
+        let updated = String::from_utf8_lossy(&updated);
+        let name = path.display().to_string();
+        write!(
+            output,
+            "{}",
+            similar::TextDiff::from_lines(&source, &*updated).unified_diff().header(&name, &name)
+        )?;
+    } else if args.inline {
+        if updated != source.as_bytes() {
+            std::fs::write(path, updated)?;
+        }
+    } else {
+        output.write_all(&updated)?;
+    }
+    Ok(())
+}
+
+/// Adds the paths to `result`, replacing the directories by the .slint files they contain
+fn collect_files(paths: &[PathBuf], result: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    for path in paths {
+        if !path.is_dir() {
+            result.push(path.clone());
+            continue;
         }
+        let mut entries = std::fs::read_dir(path)?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<std::io::Result<Vec<_>>>()?;
+        entries.sort();
+        entries.retain(|p| {
+            let hidden = p.file_name().map_or(false, |n| n.to_string_lossy().starts_with('.'));
+            !hidden
+                && (p.is_dir() || p.extension().map_or(false, |ext| ext == "slint" || ext == "60"))
+        });
+        collect_files(&entries, result)?;
     }
     Ok(())
 }
@@ -298,14 +347,20 @@ fn fold_node(
     if experiments::lookup_changes::fold_node(node, file, state, args)? {
         return Ok(true);
     }
+    if deprecated_names::fold_node(node, file, state, args)? {
+        return Ok(true);
+    }
     Ok(false)
 }
 
 fn fold_token(
     node: i_slint_compiler::parser::SyntaxToken,
     file: &mut impl Write,
-    #[allow(unused)] state: &mut State,
+    state: &mut State,
 ) -> std::io::Result<()> {
+    if deprecated_names::fold_token(&node, file, state)? {
+        return Ok(());
+    }
     /* Example: this adds the "ms" prefix to the number within a "duration" binding
     if state.property_name == Some("duration".into()) && node.kind() == SyntaxKind::NumberLiteral {
         if !node.text().ends_with("s") {
@@ -321,3 +376,31 @@ fn fold_token(
     }*/
     file.write_all(node.text().as_bytes())
 }
+
+#[test]
+fn dry_run_on_directory() {
+    let dir: PathBuf = [env!("CARGO_MANIFEST_DIR"), "tests", "dry_run"].iter().collect();
+    let args = Cli::parse_from(["slint-updater", "--dry-run", dir.to_str().unwrap()]);
+    assert!(Cli::try_parse_from(["slint-updater", "-i", "--dry-run", "ui/"]).is_err());
+
+    // Only the .slint files are collected from the directories, recursively
+    let mut paths = Vec::new();
+    collect_files(&args.paths, &mut paths).unwrap();
+    assert_eq!(paths, [dir.join("main.slint"), dir.join("sub").join("nested.slint")]);
+
+    let mut output = Vec::new();
+    for path in &paths {
+        update_file(path, &args, &mut output).unwrap();
+    }
+    let output = String::from_utf8(output).unwrap();
+    let main_path = dir.join("main.slint").display().to_string();
+    assert!(output.contains(&format!("--- {}\n+++ {}\n", main_path, main_path)), "{}", output);
+    assert!(output.contains("\n-    color: blue;\n"), "{}", output);
+    assert!(output.contains("\n+    background: blue;\n"), "{}", output);
+    assert!(output.contains("\n-    maximum-height: 10px;\n"), "{}", output);
+    assert!(output.contains("\n+    max-height: 10px;\n"), "{}", output);
+
+    // The files are not modified
+    let source = std::fs::read_to_string(dir.join("main.slint")).unwrap();
+    assert!(source.contains("    color: blue;\n"));
+}
@@ -3,16 +3,15 @@
 
 #![doc = include_str!("README.md")]
 
-use i_slint_core::model::{Model, ModelRc};
-use i_slint_core::SharedVector;
-use slint_interpreter::{ComponentHandle, ComponentInstance, SharedString, Value};
-use std::future::Future;
-use std::pin::Pin;
-use std::sync::atomic::{AtomicU32, Ordering};
-use std::sync::{Arc, Mutex};
-use std::task::Wake;
+use slint_interpreter::{ComponentHandle, ComponentInstance, Value};
 
 use clap::Parser;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::path::Path;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 
 type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
 
@@ -26,7 +25,7 @@ struct Cli {
     #[arg(name = "path to .slint file", action)]
     path: std::path::PathBuf,
 
-    /// The style name ('native' or 'fluent')
+    /// The style name ('native', 'fluent', 'material' or 'cupertino')
     #[arg(long, name = "style name", action)]
     style: Option<String>,
 
@@ -38,7 +37,28 @@ struct Cli {
     #[arg(long, action)]
     auto_reload: bool,
 
-    /// Load properties from a json file ('-' for stdin)
+    /// Render the window without showing it into an image file (PNG or JPEG), and exit
+    #[arg(long, name = "screenshot file", conflicts_with = "auto_reload", action)]
+    screenshot: Option<std::path::PathBuf>,
+
+    /// The size of the window in the screenshot, like `800x600`. Defaults to the preferred size
+    #[arg(long, name = "size", requires = "screenshot file", value_parser = parse_size, action)]
+    size: Option<(f32, f32)>,
+
+    /// The scale factor of the window in the screenshot
+    #[arg(long, name = "scale factor", requires = "screenshot file", default_value_t = 1., action)]
+    scale_factor: f32,
+
+    /// Capture this number of frames instead of one screenshot. The images are numbered, like
+    /// `out-0001.png` for `--screenshot out.png`
+    #[arg(long, name = "frame count", requires = "screenshot file", action)]
+    frames: Option<u32>,
+
+    /// The time between two captured frames, in milliseconds
+    #[arg(long, name = "interval", requires = "frame count", default_value_t = 40, action)]
+    frame_interval: u64,
+
+    /// Load properties from a json file ('-' for stdin), and load them again when the file changes
     #[arg(long, name = "load data file", action)]
     load_data: Option<std::path::PathBuf>,
 
@@ -52,28 +72,135 @@ struct Cli {
     /// and so on.
     #[arg(long, value_names(&["callback", "handler"]), number_of_values = 2, action)]
     on: Vec<String>,
+
+    /// Print every invoked callback with its arguments as a line of JSON to stdout
+    #[arg(long, action)]
+    log_callbacks: bool,
+
+    /// Answer the callbacks with the responses from a json file, see the "Scripts" section of the README
+    #[arg(long, name = "script file", action)]
+    script: Option<std::path::PathBuf>,
+}
+
+/// The canned responses of the callbacks, loaded from the `--script` file
+struct Script {
+    /// The responses, by callback name. They are used in order, and the last one is repeated.
+    responses: HashMap<String, Vec<serde_json::Value>>,
+    /// How many times each callback was invoked
+    invocations: RefCell<HashMap<String, usize>>,
+}
+
+impl Script {
+    fn load(path: &Path) -> Result<Self> {
+        let json: serde_json::Value = serde_json::from_reader(std::fs::File::open(path)?)?;
+        let obj = match json {
+            serde_json::Value::Object(obj) => obj,
+            _ => return Err("The script is not a JSON object".into()),
+        };
+        let responses = obj
+            .into_iter()
+            .map(|(name, responses)| {
+                let responses = match responses {
+                    serde_json::Value::Array(responses) => responses,
+                    response => vec![response],
+                };
+                (normalize_callback_name(&name), responses)
+            })
+            .collect();
+        Ok(Self { responses, invocations: Default::default() })
+    }
+
+    fn has_responses(&self, callback: &str) -> bool {
+        self.responses.get(&normalize_callback_name(callback)).map_or(false, |r| !r.is_empty())
+    }
+
+    fn next_response(&self, callback: &str) -> Option<&serde_json::Value> {
+        let callback = normalize_callback_name(callback);
+        let responses = self.responses.get(&callback)?;
+        let mut invocations = self.invocations.borrow_mut();
+        let count = invocations.entry(callback).or_default();
+        let response = responses.get(*count).or_else(|| responses.last());
+        *count += 1;
+        response
+    }
 }
 
-thread_local! {static CURRENT_INSTANCE: std::cell::RefCell<Option<ComponentInstance>> = Default::default();}
 static EXIT_CODE: std::sync::atomic::AtomicI32 = std::sync::atomic::AtomicI32::new(0);
 
+thread_local! {
+    /// Loads the data file again in the current instance, called when the file changed
+    static RELOAD_DATA: RefCell<Option<Box<dyn Fn()>>> = Default::default();
+}
+
 fn main() -> Result<()> {
     env_logger::init();
     let args = Cli::parse();
 
-    if args.auto_reload && args.save_data.is_some() {
-        eprintln!("Cannot pass both --auto-reload and --save-data");
-        std::process::exit(-1);
-    }
-
     if let Some(backend) = &args.backend {
         std::env::set_var("SLINT_BACKEND", backend);
     }
 
-    let fswatcher = if args.auto_reload { Some(start_fswatch_thread(args.clone())?) } else { None };
-    let mut compiler = init_compiler(&args, fswatcher);
+    let mut compiler = init_compiler(&args);
+    let script = match &args.script {
+        Some(path) => Some(Rc::new(
+            Script::load(path)
+                .map_err(|err| format!("Cannot load the script {}: {}", path.display(), err))?,
+        )),
+        None => None,
+    };
+
+    if args.screenshot.is_some() {
+        i_slint_backend_testing::init_with_software_renderer();
+        if compiler.native_style_fallback().is_none() {
+            // The native style renders with Qt, which is not used for the screenshots
+            compiler.set_native_style_fallback(i_slint_compiler::native_style_lookalike(
+                std::env::consts::OS,
+            ));
+        }
+    }
+
+    if args.auto_reload {
+        let live_component =
+            match slint_interpreter::LiveReloadingComponent::new(compiler, args.path.clone()) {
+                Ok(live_component) => live_component,
+                Err(diagnostics) => {
+                    slint_interpreter::print_diagnostics(&diagnostics);
+                    std::process::exit(-1);
+                }
+            };
+        slint_interpreter::print_diagnostics(live_component.borrow().diagnostics());
+
+        let component = {
+            let mut live_component = live_component.borrow_mut();
+            let reload_args = args.clone();
+            let reload_script = script.clone();
+            live_component.on_reload(move |instance| {
+                eprintln!("Successful reload of {}", reload_args.path.display());
+                init_instance(instance, &reload_args, &reload_script).unwrap_or_else(|err| {
+                    eprintln!("Warning: cannot load data from {:?}: {}", reload_args.load_data, err)
+                });
+            });
+            live_component.instance().clone_strong()
+        };
+
+        init_instance(&component, &args, &script)?;
+        let _data_watcher = args.load_data.as_ref().and_then(|data_path| {
+            let live_component = live_component.clone();
+            watch_data_file(data_path, move |data_path| {
+                let instance = live_component.borrow().instance().clone_strong();
+                reload_data(&instance, data_path);
+            })
+        });
+        component.run();
+
+        if let Some(data_path) = &args.save_data {
+            // The instance shown last, after the reloads
+            save_data(live_component.borrow().instance(), data_path)?;
+        }
+        std::process::exit(EXIT_CODE.load(std::sync::atomic::Ordering::Relaxed))
+    }
 
-    let c = spin_on::spin_on(compiler.build_from_path(args.path));
+    let c = spin_on::spin_on(compiler.build_from_path(&args.path));
     slint_interpreter::print_diagnostics(compiler.diagnostics());
 
     let c = match c {
@@ -82,169 +209,96 @@ fn main() -> Result<()> {
     };
 
     let component = c.create();
-    init_dialog(&component);
+    init_instance(&component, &args, &script)?;
 
-    if let Some(data_path) = args.load_data {
-        load_data(&component, &data_path)?;
+    if let Some(screenshot_path) = &args.screenshot {
+        capture(&component, &args, screenshot_path)?;
+        if let Some(data_path) = &args.save_data {
+            save_data(&component, data_path)?;
+        }
+        return Ok(());
     }
-    install_callbacks(&component, &args.on);
 
-    if args.auto_reload {
-        CURRENT_INSTANCE.with(|current| current.replace(Some(component.clone_strong())));
-    }
+    let _data_watcher = args.load_data.as_ref().and_then(|data_path| {
+        let instance = component.as_weak();
+        watch_data_file(data_path, move |data_path| {
+            if let Some(instance) = instance.upgrade() {
+                reload_data(&instance, data_path);
+            }
+        })
+    });
 
     component.run();
 
-    if let Some(data_path) = args.save_data {
-        let mut obj = serde_json::Map::new();
-        for (name, _) in c.properties() {
-            fn to_json(val: slint_interpreter::Value) -> Option<serde_json::Value> {
-                match val {
-                    slint_interpreter::Value::Number(x) => Some(x.into()),
-                    slint_interpreter::Value::String(x) => Some(x.as_str().into()),
-                    slint_interpreter::Value::Bool(x) => Some(x.into()),
-                    slint_interpreter::Value::Model(model) => {
-                        let mut res = Vec::with_capacity(model.row_count());
-                        for i in 0..model.row_count() {
-                            res.push(to_json(model.row_data(i).unwrap())?);
-                        }
-                        Some(serde_json::Value::Array(res))
-                    }
-                    slint_interpreter::Value::Struct(st) => {
-                        let mut obj = serde_json::Map::new();
-                        for (k, v) in st.iter() {
-                            obj.insert(k.into(), to_json(v.clone())?);
-                        }
-                        Some(obj.into())
-                    }
-                    _ => None,
-                }
-            }
-            if let Some(v) = to_json(component.get_property(&name).unwrap()) {
-                obj.insert(name, v);
-            }
-        }
-        if data_path == std::path::Path::new("-") {
-            serde_json::to_writer_pretty(std::io::stdout(), &obj)?;
-        } else {
-            serde_json::to_writer_pretty(std::fs::File::create(data_path)?, &obj)?;
-        }
+    if let Some(data_path) = &args.save_data {
+        save_data(&component, data_path)?;
     }
 
     std::process::exit(EXIT_CODE.load(std::sync::atomic::Ordering::Relaxed))
 }
 
-fn init_compiler(
-    args: &Cli,
-    fswatcher: Option<Arc<Mutex<notify::RecommendedWatcher>>>,
-) -> slint_interpreter::ComponentCompiler {
-    let mut compiler = slint_interpreter::ComponentCompiler::default();
-    compiler.set_include_paths(args.include_paths.clone());
-    if let Some(style) = &args.style {
-        compiler.set_style(style.clone());
+/// Renders the window into `path`, or into a numbered image for each frame with `--frames`
+fn capture(instance: &ComponentInstance, args: &Cli, path: &Path) -> Result<()> {
+    if let Some((width, height)) = args.size {
+        instance.window().set_size(slint_interpreter::PhysicalSize::new(
+            (width * args.scale_factor) as u32,
+            (height * args.scale_factor) as u32,
+        ));
     }
-    if let Some(watcher) = fswatcher {
-        notify::Watcher::watch(
-            &mut *watcher.lock().unwrap(),
-            &args.path,
-            notify::RecursiveMode::NonRecursive,
-        )
-        .unwrap_or_else(|err| {
-            eprintln!("Warning: error while watching {}: {:?}", args.path.display(), err)
-        });
-        if let Some(data_path) = &args.load_data {
-            notify::Watcher::watch(
-                &mut *watcher.lock().unwrap(),
-                data_path,
-                notify::RecursiveMode::NonRecursive,
-            )
-            .unwrap_or_else(|err| {
-                eprintln!("Warning: error while watching {}: {:?}", data_path.display(), err)
-            });
+    instance.show();
+    i_slint_core::platform::update_timers_and_animations();
+
+    let frames = match args.frames {
+        Some(frames) => frames,
+        None => {
+            let screenshot =
+                i_slint_backend_testing::render_screenshot(instance.window(), args.scale_factor)?;
+            i_slint_backend_testing::save_screenshot(&screenshot, path)?;
+            return Ok(());
         }
-        compiler.set_file_loader(move |path| {
-            notify::Watcher::watch(
-                &mut *watcher.lock().unwrap(),
-                &path,
-                notify::RecursiveMode::NonRecursive,
-            )
-            .unwrap_or_else(|err| {
-                eprintln!("Warning: error while watching {}: {:?}", path.display(), err)
-            });
-            Box::pin(async { None })
-        })
+    };
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let extension = path.extension().map_or("png".into(), |e| e.to_string_lossy());
+    for frame in 1..=frames {
+        if frame > 1 {
+            // The animations and timers use the time of the testing backend
+            i_slint_core::tests::slint_mock_elapsed_time(args.frame_interval);
+        }
+        let screenshot =
+            i_slint_backend_testing::render_screenshot(instance.window(), args.scale_factor)?;
+        let frame_path = path.with_file_name(format!("{}-{:04}.{}", stem, frame, extension));
+        i_slint_backend_testing::save_screenshot(&screenshot, &frame_path)?;
     }
-    compiler
+    Ok(())
 }
 
-fn init_dialog(instance: &ComponentInstance) {
-    for cb in instance.definition().callbacks() {
-        let exit_code = match cb.as_str() {
-            "ok-clicked" | "yes-clicked" | "close-clicked" => 0,
-            "cancel-clicked" | "no-clicked" => 1,
-            _ => continue,
-        };
-        // this is a dialog, so clicking the "x" should cancel
-        EXIT_CODE.store(1, std::sync::atomic::Ordering::Relaxed);
-        instance
-            .set_callback(&cb, move |_| {
-                EXIT_CODE.store(exit_code, std::sync::atomic::Ordering::Relaxed);
-                i_slint_core::api::quit_event_loop().unwrap();
-                Default::default()
-            })
-            .unwrap();
-    }
+/// Parses a window size like `800x600`
+fn parse_size(size: &str) -> std::result::Result<(f32, f32), String> {
+    let error = || format!("'{}' is not a size like 800x600", size);
+    let (width, height) = size.split_once('x').ok_or_else(error)?;
+    Ok((width.trim().parse().map_err(|_| error())?, height.trim().parse().map_err(|_| error())?))
 }
 
-static PENDING_EVENTS: AtomicU32 = AtomicU32::new(0);
-
-fn start_fswatch_thread(args: Cli) -> Result<Arc<Mutex<notify::RecommendedWatcher>>> {
-    let (tx, rx) = std::sync::mpsc::channel();
-    let w = Arc::new(Mutex::new(notify::recommended_watcher(tx)?));
-    let w2 = w.clone();
-    std::thread::spawn(move || {
-        while let Ok(event) = rx.recv() {
-            use notify::EventKind::*;
-            if let Ok(event) = event {
-                if (matches!(event.kind, Modify(_) | Remove(_) | Create(_)))
-                    && PENDING_EVENTS.load(Ordering::SeqCst) == 0
-                {
-                    PENDING_EVENTS.fetch_add(1, Ordering::SeqCst);
-                    run_in_ui_thread(Box::pin(reload(args.clone(), w2.clone())));
-                }
-            }
-        }
-    });
-    Ok(w)
+fn init_compiler(args: &Cli) -> slint_interpreter::ComponentCompiler {
+    let mut compiler = slint_interpreter::ComponentCompiler::default();
+    compiler.set_include_paths(args.include_paths.clone());
+    if let Some(style) = &args.style {
+        compiler.set_style(style.clone());
+    }
+    compiler
 }
 
-async fn reload(args: Cli, fswatcher: Arc<Mutex<notify::RecommendedWatcher>>) {
-    let mut compiler = init_compiler(&args, Some(fswatcher));
-    let c = compiler.build_from_path(&args.path).await;
-    slint_interpreter::print_diagnostics(compiler.diagnostics());
-
-    if let Some(c) = c {
-        CURRENT_INSTANCE.with(|current| {
-            let mut current = current.borrow_mut();
-            if let Some(handle) = current.take() {
-                let window = handle.window();
-                let new_handle = c.create_with_existing_window(window);
-                init_dialog(&new_handle);
-                current.replace(new_handle);
-            } else {
-                let handle = c.create();
-                init_dialog(&handle);
-                handle.show();
-                current.replace(handle);
-            }
-            if let Some(data_path) = args.load_data {
-                let _ = load_data(current.as_ref().unwrap(), &data_path);
-            }
-            eprintln!("Successful reload of {}", args.path.display());
-        });
+/// Sets up a new instance, or the new instance after a reload
+fn init_instance(
+    instance: &ComponentInstance,
+    args: &Cli,
+    script: &Option<Rc<Script>>,
+) -> Result<()> {
+    if let Some(data_path) = &args.load_data {
+        load_data(instance, data_path)?;
     }
-
-    PENDING_EVENTS.fetch_sub(1, Ordering::SeqCst);
+    install_callbacks(instance, args, script);
+    Ok(())
 }
 
 fn load_data(instance: &ComponentInstance, data_path: &std::path::Path) -> Result<()> {
@@ -255,54 +309,254 @@ fn load_data(instance: &ComponentInstance, data_path: &std::path::Path) -> Resul
     };
 
     let obj = json.as_object().ok_or("The data is not a JSON object")?;
+    apply_data(instance, obj);
+    Ok(())
+}
+
+/// Sets the properties from the content of a data file
+fn apply_data(instance: &ComponentInstance, obj: &serde_json::Map<String, serde_json::Value>) {
+    let globals: Vec<String> = instance.definition().globals().collect();
     for (name, v) in obj {
-        fn from_json(v: &serde_json::Value) -> slint_interpreter::Value {
-            match v {
-                serde_json::Value::Null => slint_interpreter::Value::Void,
-                serde_json::Value::Bool(b) => (*b).into(),
-                serde_json::Value::Number(n) => {
-                    slint_interpreter::Value::Number(n.as_f64().unwrap_or(f64::NAN))
-                }
-                serde_json::Value::String(s) => SharedString::from(s.as_str()).into(),
-                serde_json::Value::Array(array) => slint_interpreter::Value::Model(ModelRc::new(
-                    i_slint_core::model::SharedVectorModel::from(
-                        array.iter().map(from_json).collect::<SharedVector<Value>>(),
-                    ),
-                )),
-                serde_json::Value::Object(obj) => obj
-                    .iter()
-                    .map(|(k, v)| (k.clone(), from_json(v)))
-                    .collect::<slint_interpreter::Struct>()
-                    .into(),
+        // An object named like an exported global holds the properties of the global
+        let global = globals.iter().find(|g| g.replace('_', "-") == name.replace('_', "-"));
+        if let (Some(global), Some(global_obj)) = (global, v.as_object()) {
+            for (prop_name, v) in global_obj {
+                match instance.set_global_property_from_json(global, prop_name, v) {
+                    Ok(()) => (),
+                    Err(e) => {
+                        eprintln!(
+                            "Warning: cannot set property '{}.{}': {:?}",
+                            global, prop_name, e
+                        )
+                    }
+                };
             }
+            continue;
         }
-
-        match instance.set_property(name, from_json(v)) {
+        match instance.set_property_from_json(name, v) {
             Ok(()) => (),
-            Err(e) => eprintln!("Warning: cannot set property '{}' from data file: {:?}", name, e),
+            Err(e) => eprintln!("Warning: cannot set property '{}': {:?}", name, e),
         };
     }
+}
+
+fn reload_data(instance: &ComponentInstance, data_path: &Path) {
+    load_data(instance, data_path).unwrap_or_else(|err| {
+        eprintln!("Warning: cannot load data from {}: {}", data_path.display(), err)
+    });
+}
+
+/// Watches the data file, and calls `reload` from the event loop when it changes.
+/// The returned watcher must be kept alive.
+fn watch_data_file(
+    data_path: &Path,
+    reload: impl Fn(&Path) + 'static,
+) -> Option<notify::RecommendedWatcher> {
+    if data_path == Path::new("-") {
+        return None;
+    }
+    let path = data_path.to_owned();
+    RELOAD_DATA
+        .with(|reload_data| *reload_data.borrow_mut() = Some(Box::new(move || reload(&path))));
+
+    // Editors may write the file in several steps, only reload once
+    let pending = Arc::new(AtomicBool::new(false));
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+        use notify::EventKind::*;
+        if let Ok(event) = event {
+            if matches!(event.kind, Modify(_) | Create(_)) && !pending.swap(true, Ordering::SeqCst)
+            {
+                let pending = pending.clone();
+                slint_interpreter::invoke_from_event_loop(move || {
+                    pending.store(false, Ordering::SeqCst);
+                    RELOAD_DATA.with(|reload_data| {
+                        if let Some(reload_data) = &*reload_data.borrow() {
+                            reload_data();
+                        }
+                    });
+                })
+                .ok();
+            }
+        }
+    })
+    .map_err(|err| eprintln!("Warning: cannot watch the data file for changes: {}", err))
+    .ok()?;
+    notify::Watcher::watch(&mut watcher, data_path, notify::RecursiveMode::NonRecursive)
+        .map_err(|err| {
+            eprintln!("Warning: error while watching {}: {:?}", data_path.display(), err)
+        })
+        .ok()?;
+    Some(watcher)
+}
+
+fn save_data(instance: &ComponentInstance, data_path: &std::path::Path) -> Result<()> {
+    let definition = instance.definition();
+    let mut obj = serde_json::Map::new();
+    for (name, _) in definition.properties() {
+        if let Some(v) = instance.get_property(&name).unwrap().to_json() {
+            obj.insert(name, v);
+        }
+    }
+    for global in definition.globals() {
+        let mut global_obj = serde_json::Map::new();
+        for (name, _) in definition.global_properties(&global).into_iter().flatten() {
+            if let Some(v) = instance.get_global_property(&global, &name).unwrap().to_json() {
+                global_obj.insert(name, v);
+            }
+        }
+        if !global_obj.is_empty() {
+            obj.insert(global, global_obj.into());
+        }
+    }
+    if data_path == std::path::Path::new("-") {
+        serde_json::to_writer_pretty(std::io::stdout(), &obj)?;
+    } else {
+        serde_json::to_writer_pretty(std::fs::File::create(data_path)?, &obj)?;
+    }
     Ok(())
 }
 
-fn install_callbacks(instance: &ComponentInstance, callbacks: &[String]) {
-    assert!(callbacks.len() % 2 == 0);
-    for chunk in callbacks.chunks(2) {
-        if let [callback, cmd] = chunk {
-            let cmd = cmd.clone();
-            match instance.set_callback(callback, move |args| {
-                match execute_cmd(&cmd, args) {
-                    Ok(()) => (),
-                    Err(e) => eprintln!("Error: {}", e),
-                }
-                Value::Void
-            }) {
+/// Callbacks can be named with `-` or `_`. The callbacks of the exported globals are prefixed with
+/// the name of the global, like `Logic.login`.
+fn normalize_callback_name(name: &str) -> String {
+    name.replace('_', "-")
+}
+
+/// What the viewer does when a callback is invoked
+struct CallbackHandler {
+    global: Option<String>,
+    name: String,
+    log: bool,
+    cmd: Option<String>,
+    script: Option<Rc<Script>>,
+    /// Set for the callbacks of the standard buttons of a dialog
+    dialog_exit_code: Option<i32>,
+    instance: slint_interpreter::Weak<ComponentInstance>,
+}
+
+impl CallbackHandler {
+    fn full_name(&self) -> String {
+        match &self.global {
+            Some(global) => format!("{}.{}", global, self.name),
+            None => self.name.clone(),
+        }
+    }
+
+    fn invoke(&self, args: &[Value]) -> Value {
+        if self.log {
+            let args: Vec<serde_json::Value> =
+                args.iter().map(|a| a.to_json().unwrap_or_default()).collect();
+            let mut line = serde_json::json!({ "callback": self.name, "args": args });
+            if let Some(global) = &self.global {
+                line["global"] = global.clone().into();
+            }
+            println!("{}", line);
+        }
+
+        if let Some(cmd) = &self.cmd {
+            match execute_cmd(cmd, args) {
                 Ok(()) => (),
-                Err(e) => {
-                    eprintln!("Warning: cannot set callback handler for '{}': {}", callback, e)
+                Err(e) => eprintln!("Error: {}", e),
+            }
+        }
+
+        let mut result = Value::Void;
+        let response = self.script.as_ref().and_then(|s| s.next_response(&self.full_name()));
+        if let (Some(response), Some(instance)) = (response, self.instance.upgrade()) {
+            match response.get("set") {
+                Some(serde_json::Value::Object(obj)) => apply_data(&instance, obj),
+                Some(_) => eprintln!(
+                    "Warning: the \"set\" of the response to '{}' is not a JSON object",
+                    self.full_name()
+                ),
+                None => (),
+            }
+            if let Some(json) = response.get("return") {
+                match instance.definition().callback_return_value_from_json(
+                    self.global.as_deref(),
+                    &self.name,
+                    json,
+                ) {
+                    Some(value) => result = value,
+                    None => eprintln!(
+                        "Warning: cannot convert the return value of '{}': {}",
+                        self.full_name(),
+                        json
+                    ),
                 }
             }
         }
+
+        if let Some(exit_code) = self.dialog_exit_code {
+            EXIT_CODE.store(exit_code, std::sync::atomic::Ordering::Relaxed);
+            i_slint_core::api::quit_event_loop().unwrap();
+        }
+        result
+    }
+}
+
+fn install_callbacks(instance: &ComponentInstance, args: &Cli, script: &Option<Rc<Script>>) {
+    assert!(args.on.len() % 2 == 0);
+    let mut commands: HashMap<String, String> = args
+        .on
+        .chunks(2)
+        .map(|chunk| (normalize_callback_name(&chunk[0]), chunk[1].clone()))
+        .collect();
+
+    let definition = instance.definition();
+    let root_callbacks = definition.callbacks().map(|name| (None, name));
+    let global_callbacks = definition.globals().flat_map(|global| {
+        let callbacks: Vec<String> =
+            definition.global_callbacks(&global).into_iter().flatten().collect();
+        callbacks.into_iter().map(move |name| (Some(global.clone()), name))
+    });
+    for (global, name) in root_callbacks.chain(global_callbacks).collect::<Vec<_>>() {
+        let mut handler = CallbackHandler {
+            global,
+            name,
+            log: args.log_callbacks,
+            cmd: None,
+            script: None,
+            dialog_exit_code: None,
+            instance: instance.as_weak(),
+        };
+        let full_name = handler.full_name();
+        handler.cmd = commands.remove(&normalize_callback_name(&full_name));
+        handler.script = script.clone().filter(|s| s.has_responses(&full_name));
+        let dialog_exit_code = match handler.name.as_str() {
+            _ if handler.global.is_some() => None,
+            "ok-clicked" | "yes-clicked" | "close-clicked" => Some(0),
+            "cancel-clicked" | "no-clicked" => Some(1),
+            _ => None,
+        };
+        if dialog_exit_code.is_some() {
+            // this is a dialog, so clicking the "x" should cancel
+            EXIT_CODE.store(1, std::sync::atomic::Ordering::Relaxed);
+            // The standard buttons close the dialog, unless they have a handler
+            if handler.cmd.is_none() {
+                handler.dialog_exit_code = dialog_exit_code;
+            }
+        }
+        if !handler.log
+            && handler.cmd.is_none()
+            && handler.script.is_none()
+            && handler.dialog_exit_code.is_none()
+        {
+            continue;
+        }
+
+        let (global, name) = (handler.global.clone(), handler.name.clone());
+        match global {
+            Some(global) => {
+                instance.set_global_callback(&global, &name, move |a| handler.invoke(a))
+            }
+            None => instance.set_callback(&name, move |a| handler.invoke(a)),
+        }
+        .unwrap();
+    }
+
+    for callback in commands.keys() {
+        eprintln!("Warning: cannot set callback handler for '{}': no such callback", callback);
     }
 }
 
@@ -333,34 +587,3 @@ fn execute_cmd(cmd: &str, callback_args: &[Value]) -> Result<()> {
     command.spawn()?;
     Ok(())
 }
-
-/// This type is duplicated with lsp/preview.rs
-struct FutureRunner {
-    fut: Mutex<Option<Pin<Box<dyn Future<Output = ()>>>>>,
-}
-
-/// Safety: the future is only going to be run in the UI thread
-unsafe impl Send for FutureRunner {}
-/// Safety: the future is only going to be run in the UI thread
-unsafe impl Sync for FutureRunner {}
-
-impl Wake for FutureRunner {
-    fn wake(self: Arc<Self>) {
-        i_slint_core::api::invoke_from_event_loop(move || {
-            let waker = self.clone().into();
-            let mut cx = std::task::Context::from_waker(&waker);
-            let mut fut_opt = self.fut.lock().unwrap();
-            if let Some(fut) = &mut *fut_opt {
-                match fut.as_mut().poll(&mut cx) {
-                    std::task::Poll::Ready(_) => *fut_opt = None,
-                    std::task::Poll::Pending => {}
-                }
-            }
-        })
-        .unwrap();
-    }
-}
-
-fn run_in_ui_thread(fut: Pin<Box<dyn Future<Output = ()>>>) {
-    Arc::new(FutureRunner { fut: Mutex::new(Some(fut)) }).wake()
-}
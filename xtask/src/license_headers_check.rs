@@ -312,6 +312,7 @@ enum LicenseLocation {
         ("\\.md$", LicenseLocation::NoLicense),
         ("\\.mjs$", LicenseLocation::Tag(LicenseTagStyle::c_style_comment_style())),
         ("\\.png$", LicenseLocation::NoLicense),
+        ("\\.py$", LicenseLocation::Tag(LicenseTagStyle::shell_comment_style())),
         ("\\.rs$", LicenseLocation::Tag(LicenseTagStyle::c_style_comment_style())),
         ("\\.rst$", LicenseLocation::Tag(LicenseTagStyle::rst_comment_style())),
         ("\\.sh$", LicenseLocation::Tag(LicenseTagStyle::shell_comment_style())),